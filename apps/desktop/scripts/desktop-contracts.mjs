@@ -114,6 +114,12 @@ const contractDefinitions = [
         struct: "OperatorProviderCapacity",
         shape: true,
       },
+      {
+        id: "providerScheduleStatus",
+        file: "../../crates/core/src/dashboard_core/types.rs",
+        struct: "ProviderScheduleStatus",
+        shape: true,
+      },
       {
         id: "operatorPolicyActionSummary",
         file: "../../crates/core/src/dashboard_core/operator_summary.rs",
@@ -581,6 +587,12 @@ const contractDefinitions = [
         fieldsFrom: "operatorProviderCapacity",
         strictShape: true,
       },
+      {
+        file: "src/lib/api/admin-types.ts",
+        type: "ApiProviderScheduleStatus",
+        fieldsFrom: "providerScheduleStatus",
+        strictShape: true,
+      },
       {
         file: "src/lib/api/admin-types.ts",
         type: "ApiOperatorPolicyActionSummary",
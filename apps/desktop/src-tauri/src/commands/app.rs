@@ -1,7 +1,9 @@
-use serde::Serialize;
+use codex_helper_core::config::{UiConfig, load_config, mutate_helper_config};
+use codex_helper_core::notify::{TestNotificationOutcome, send_test_notification};
+use serde::{Deserialize, Serialize};
 use tauri::AppHandle;
 
-use crate::error::CommandError;
+use crate::error::{CommandError, DesktopError};
 use crate::lifecycle;
 
 #[derive(Debug, Serialize)]
@@ -21,6 +23,74 @@ pub fn get_app_metadata() -> AppMetadata {
     }
 }
 
+/// The desktop density switches stored under `[ui]` in `config.toml`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UiPreferences {
+    pub compact: bool,
+    pub auto_compact: bool,
+}
+
+impl From<&UiConfig> for UiPreferences {
+    fn from(ui: &UiConfig) -> Self {
+        Self {
+            compact: ui.compact,
+            auto_compact: ui.auto_compact,
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn get_ui_preferences() -> Result<UiPreferences, CommandError> {
+    let config = load_config()
+        .await
+        .map_err(|err| DesktopError::Config(err.to_string()))?;
+    Ok(UiPreferences::from(&config.ui))
+}
+
+#[tauri::command]
+pub async fn save_ui_preferences(payload: UiPreferences) -> Result<UiPreferences, CommandError> {
+    let (_, saved) = mutate_helper_config(|config| {
+        config.ui.compact = payload.compact;
+        config.ui.auto_compact = payload.auto_compact;
+        Ok(UiPreferences::from(&config.ui))
+    })
+    .await
+    .map_err(|err| DesktopError::Config(err.to_string()))?;
+    Ok(saved)
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TestNotificationResult {
+    pub ok: bool,
+    pub backend: Option<&'static str>,
+    pub message: String,
+}
+
+impl From<TestNotificationOutcome> for TestNotificationResult {
+    fn from(outcome: TestNotificationOutcome) -> Self {
+        let backend = match &outcome {
+            TestNotificationOutcome::Delivered { backend }
+            | TestNotificationOutcome::Failed { backend, .. } => Some(*backend),
+            TestNotificationOutcome::Unsupported => None,
+        };
+        Self {
+            ok: outcome.is_delivered(),
+            backend,
+            message: outcome.message(),
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn send_test_system_notification() -> Result<TestNotificationResult, CommandError> {
+    tauri::async_runtime::spawn_blocking(send_test_notification)
+        .await
+        .map(TestNotificationResult::from)
+        .map_err(|err| DesktopError::Notify(err.to_string()).into())
+}
+
 #[tauri::command]
 pub fn show_main_window(app: AppHandle) -> Result<(), CommandError> {
     lifecycle::show_main_window(&app)
@@ -10,6 +10,8 @@ pub enum DesktopError {
     Lifecycle(String),
     #[error("client switch action failed: {0}")]
     Switch(String),
+    #[error("test notification failed: {0}")]
+    Notify(String),
 }
 
 impl DesktopError {
@@ -19,6 +21,7 @@ impl DesktopError {
             DesktopError::Config(_) => "desktop_config_error",
             DesktopError::Lifecycle(_) => "desktop_lifecycle_error",
             DesktopError::Switch(_) => "desktop_switch_error",
+            DesktopError::Notify(_) => "desktop_notify_error",
         }
     }
 
@@ -21,9 +21,12 @@ pub fn run() {
             commands::admin_api::get_admin_read_model,
             commands::admin_api::get_request_chain,
             commands::app::get_app_metadata,
+            commands::app::get_ui_preferences,
             commands::app::hide_main_window,
             commands::app::minimize_main_window,
             commands::app::quit_app,
+            commands::app::save_ui_preferences,
+            commands::app::send_test_system_notification,
             commands::app::show_main_window,
             commands::app::toggle_main_window_maximized,
             commands::control::attach_existing_proxy,
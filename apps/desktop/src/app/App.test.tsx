@@ -301,6 +301,74 @@ describe("desktop app routes", () => {
     expect(mockedInvoke).not.toHaveBeenCalledWith("stop_proxy", expect.anything());
   });
 
+  it("switches pages, focuses search, and shows help from the keyboard", async () => {
+    render(<App />);
+
+    expect(await screen.findByRole("heading", { name: "仪表盘" })).toBeInTheDocument();
+    await userEvent.keyboard("3");
+    expect(await screen.findByRole("heading", { name: "用量" })).toBeInTheDocument();
+
+    await userEvent.keyboard("/");
+    const search = screen.getByPlaceholderText("搜索 request id、模型或供应商");
+    expect(search).toHaveFocus();
+    // Typing into the search box must not trigger page shortcuts.
+    await userEvent.keyboard("2?");
+    expect(search).toHaveValue("2?");
+    expect(screen.getByRole("heading", { name: "用量" })).toBeInTheDocument();
+    expect(screen.queryByRole("dialog", { name: "键盘快捷键" })).not.toBeInTheDocument();
+
+    search.blur();
+    await userEvent.keyboard("?");
+    expect(screen.getByRole("dialog", { name: "键盘快捷键" })).toBeInTheDocument();
+    await userEvent.keyboard("{Escape}");
+    expect(screen.queryByRole("dialog", { name: "键盘快捷键" })).not.toBeInTheDocument();
+
+    await userEvent.keyboard("2");
+    expect(await screen.findByRole("heading", { name: "供应商" })).toBeInTheDocument();
+  });
+
+  it("persists the compact density toggle from Settings and applies it app-wide", async () => {
+    window.location.hash = "#/settings";
+    mockedInvoke.mockImplementation(async (command, args) => {
+      if (command === "get_ui_preferences") {
+        return { compact: false, autoCompact: false };
+      }
+      if (command === "save_ui_preferences") {
+        return (args as { payload: unknown }).payload;
+      }
+      throw new Error(`unexpected command ${command}`);
+    });
+
+    render(<App />);
+
+    expect(await screen.findByRole("heading", { name: "设置" })).toBeInTheDocument();
+    await userEvent.click(screen.getByRole("button", { name: "紧凑" }));
+
+    expect(document.documentElement.dataset.density).toBe("compact");
+    await waitFor(() =>
+      expect(mockedInvoke).toHaveBeenCalledWith("save_ui_preferences", {
+        payload: { compact: true, autoCompact: false },
+      }),
+    );
+
+    await userEvent.click(screen.getByRole("button", { name: "舒适" }));
+    expect(document.documentElement.dataset.density).toBeUndefined();
+  });
+
+  it("starts in compact density when config.toml enables it", async () => {
+    mockedInvoke.mockImplementation(async (command) => {
+      if (command === "get_ui_preferences") {
+        return { compact: true, autoCompact: false };
+      }
+      throw new Error(`unexpected command ${command}`);
+    });
+
+    render(<App />);
+
+    await waitFor(() => expect(document.documentElement.dataset.density).toBe("compact"));
+    delete document.documentElement.dataset.density;
+  });
+
   it("keeps local Settings lifecycle actions after remote controls are removed", async () => {
     window.location.hash = "#/settings";
     mockedInvoke.mockImplementation(async (command) => {
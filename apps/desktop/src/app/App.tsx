@@ -3,11 +3,14 @@ import { RouterProvider } from "@tanstack/react-router";
 
 import { queryClient } from "@/app/query-client";
 import { router } from "@/app/router";
+import { UiDensityProvider } from "@/app/ui-density";
 
 export function App() {
   return (
     <QueryClientProvider client={queryClient}>
-      <RouterProvider router={router} />
+      <UiDensityProvider>
+        <RouterProvider router={router} />
+      </UiDensityProvider>
     </QueryClientProvider>
   );
 }
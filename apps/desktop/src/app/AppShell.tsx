@@ -1,6 +1,6 @@
-import { Link, Outlet, useRouterState } from "@tanstack/react-router";
+import { Link, Outlet, useNavigate, useRouterState } from "@tanstack/react-router";
 import { emit } from "@tauri-apps/api/event";
-import { useEffect } from "react";
+import { useCallback, useEffect } from "react";
 import {
   Bell,
   ChevronDown,
@@ -19,7 +19,9 @@ import {
   X,
 } from "lucide-react";
 
+import { ShortcutHelpOverlay, useAppShortcuts } from "@/app/keyboard-shortcuts";
 import { Badge, Button, Card, Separator, Switch } from "@/components/ui";
+import { useRuntimeActions } from "@/features/runtime/actions";
 import { useRuntimeSummary } from "@/features/runtime/hooks";
 import { hideMainWindow, minimizeMainWindow, toggleMainWindowMaximized } from "@/lib/tauri/commands";
 import { cn } from "@/lib/utils";
@@ -31,10 +33,24 @@ const navItems = [
   { to: "/settings", label: "设置", icon: Settings },
 ] as const;
 
+const navLabels = navItems.map((item) => item.label);
+
 export function AppShell() {
   const pathname = useRouterState({ select: (state) => state.location.pathname });
   const runtime = useRuntimeSummary();
   const runtimeHealthy = runtime.source === "live" && !runtime.state.isStale;
+  const navigate = useNavigate();
+  const { startProxy } = useRuntimeActions();
+  const navigateToPage = useCallback(
+    (index: number) => void navigate({ to: navItems[index].to }),
+    [navigate],
+  );
+  const shortcuts = useAppShortcuts({
+    pageCount: navItems.length,
+    onNavigate: navigateToPage,
+    canStartProxy: runtime.state.canStartProxy && !startProxy.isPending,
+    onStartProxy: startProxy.mutate,
+  });
 
   useEffect(() => {
     void emit("codex-helper://window-ready").catch((error) => {
@@ -55,18 +71,19 @@ export function AppShell() {
           </div>
           <div>
             <div className="font-semibold tracking-tight text-slate-950">codex-helper</div>
-            <div className="text-sm text-slate-500">Local Relay Helper</div>
+            <div className="text-sm text-slate-500 compact:hidden">Local Relay Helper</div>
           </div>
         </div>
 
         <nav className="space-y-1 px-4">
-          {navItems.map((item) => {
+          {navItems.map((item, index) => {
             const Icon = item.icon;
             const active = pathname === item.to;
             return (
               <Link
                 key={item.to}
                 to={item.to}
+                title={`${item.label} (${index + 1})`}
                 className={cn(
                   "flex h-11 items-center gap-3 rounded-xl px-3 text-sm font-medium transition",
                   active
@@ -122,6 +139,9 @@ export function AppShell() {
           <Outlet />
         </div>
       </main>
+      {shortcuts.helpOpen ? (
+        <ShortcutHelpOverlay pageLabels={navLabels} onClose={shortcuts.closeHelp} />
+      ) : null}
     </div>
   );
 }
@@ -185,7 +205,7 @@ export function PageHeader({
     <div className="mb-5 flex items-start justify-between gap-4">
       <div>
         <h1 className="text-2xl font-semibold tracking-tight text-slate-950">{title}</h1>
-        <p className="mt-1 text-sm text-slate-500">{subtitle}</p>
+        <p className="mt-1 text-sm text-slate-500 compact:hidden">{subtitle}</p>
       </div>
       <div className="flex items-center gap-3">
         {action ?? (
@@ -197,7 +217,7 @@ export function PageHeader({
         <Button variant="outline" className="w-11 px-0">
           <Bell className="h-4 w-4" />
         </Button>
-        <Button variant="outline">
+        <Button variant="outline" className="compact:hidden">
           中文
           <ChevronDown className="h-4 w-4" />
         </Button>
@@ -214,7 +234,7 @@ export function PageHeader({
           <WalletCards className="h-3.5 w-3.5" />
           余额 {runtime.data.balance}
         </Badge>
-        <Badge variant="muted">本机</Badge>
+        <Badge variant="muted" className="compact:hidden">本机</Badge>
       </div>
     </div>
   );
@@ -0,0 +1,172 @@
+import { useQueryClient } from "@tanstack/react-query";
+import { X } from "lucide-react";
+import { useEffect, useState } from "react";
+
+import { Card } from "@/components/ui";
+
+/** Marks the search box `/` focuses; the first visible one on the current page wins. */
+export const SHORTCUT_SEARCH_ATTRIBUTE = "data-shortcut-search";
+
+export type ShortcutAction =
+  | { kind: "navigate"; index: number }
+  | { kind: "start-proxy" }
+  | { kind: "refresh" }
+  | { kind: "focus-search" }
+  | { kind: "toggle-help" }
+  | { kind: "close-help" };
+
+type ShortcutKeyEvent = Pick<KeyboardEvent, "key" | "altKey" | "ctrlKey" | "metaKey" | "target">;
+
+/**
+ * Maps a keydown to a shortcut, or `null` when it should reach the page untouched: modifier
+ * chords, and anything typed into a text field (only Escape still closes the help overlay).
+ */
+export function shortcutForKey(event: ShortcutKeyEvent, pageCount: number): ShortcutAction | null {
+  if (event.key === "Escape") {
+    return { kind: "close-help" };
+  }
+  if (event.altKey || event.ctrlKey || event.metaKey || isEditableTarget(event.target)) {
+    return null;
+  }
+  if (/^[1-8]$/.test(event.key)) {
+    const index = Number(event.key) - 1;
+    return index < pageCount ? { kind: "navigate", index } : null;
+  }
+  switch (event.key) {
+    case "s":
+      return { kind: "start-proxy" };
+    case "r":
+      return { kind: "refresh" };
+    case "/":
+      return { kind: "focus-search" };
+    case "?":
+      return { kind: "toggle-help" };
+    default:
+      return null;
+  }
+}
+
+function isEditableTarget(target: EventTarget | null) {
+  if (!(target instanceof HTMLElement)) {
+    return false;
+  }
+  return (
+    target.isContentEditable ||
+    target instanceof HTMLInputElement ||
+    target instanceof HTMLTextAreaElement ||
+    target instanceof HTMLSelectElement
+  );
+}
+
+/**
+ * The single keydown handler for the shell. Pages expose what shortcuts act on (the search box
+ * through {@link SHORTCUT_SEARCH_ATTRIBUTE}, data through React Query) instead of listening
+ * themselves.
+ */
+export function useAppShortcuts({
+  pageCount,
+  onNavigate,
+  canStartProxy,
+  onStartProxy,
+}: {
+  pageCount: number;
+  onNavigate: (index: number) => void;
+  canStartProxy: boolean;
+  onStartProxy: () => void;
+}) {
+  const queryClient = useQueryClient();
+  const [helpOpen, setHelpOpen] = useState(false);
+
+  useEffect(() => {
+    const handleKeyDown = (event: KeyboardEvent) => {
+      const action = shortcutForKey(event, pageCount);
+      if (!action || (action.kind === "close-help" && !helpOpen)) {
+        return;
+      }
+      event.preventDefault();
+      switch (action.kind) {
+        case "navigate":
+          setHelpOpen(false);
+          onNavigate(action.index);
+          break;
+        case "start-proxy":
+          if (canStartProxy) {
+            onStartProxy();
+          }
+          break;
+        case "refresh":
+          void queryClient.invalidateQueries();
+          break;
+        case "focus-search":
+          document.querySelector<HTMLInputElement>(`[${SHORTCUT_SEARCH_ATTRIBUTE}]`)?.focus();
+          break;
+        case "toggle-help":
+          setHelpOpen((open) => !open);
+          break;
+        case "close-help":
+          setHelpOpen(false);
+          break;
+      }
+    };
+    window.addEventListener("keydown", handleKeyDown);
+    return () => window.removeEventListener("keydown", handleKeyDown);
+  }, [canStartProxy, helpOpen, onNavigate, onStartProxy, pageCount, queryClient]);
+
+  return { helpOpen, closeHelp: () => setHelpOpen(false) };
+}
+
+export function ShortcutHelpOverlay({
+  pageLabels,
+  onClose,
+}: {
+  pageLabels: readonly string[];
+  onClose: () => void;
+}) {
+  const rows = [
+    ...pageLabels.map((label, index) => [String(index + 1), `切换到${label}`]),
+    ["s", "启动本地代理"],
+    ["r", "刷新当前页面数据"],
+    ["/", "聚焦搜索框"],
+    ["?", "显示 / 隐藏快捷键"],
+    ["Esc", "关闭此面板"],
+  ];
+
+  return (
+    <div
+      className="no-drag fixed inset-0 z-50 flex items-center justify-center bg-slate-900/25"
+      onClick={onClose}
+    >
+      <Card
+        className="w-80 p-5"
+        role="dialog"
+        aria-label="键盘快捷键"
+        onClick={(event) => event.stopPropagation()}
+      >
+        <div className="mb-3 flex items-center justify-between">
+          <h2 className="font-semibold text-slate-950">键盘快捷键</h2>
+          <button
+            className="text-slate-400 hover:text-slate-700"
+            type="button"
+            aria-label="关闭快捷键帮助"
+            onClick={onClose}
+          >
+            <X className="h-4 w-4" />
+          </button>
+        </div>
+        <p className="mb-3 text-xs text-slate-500">在输入框中输入时快捷键不生效。</p>
+        <dl className="space-y-2 text-sm">
+          {rows.map(([key, description]) => (
+            <div key={key} className="flex items-center justify-between gap-4">
+              <dt>
+                <kbd className="rounded-md border border-slate-200 bg-slate-50 px-1.5 py-0.5 font-mono text-xs text-slate-700">
+                  {key}
+                </kbd>
+              </dt>
+              <dd className="text-slate-600">{description}</dd>
+            </div>
+          ))}
+        </dl>
+      </Card>
+    </div>
+  );
+}
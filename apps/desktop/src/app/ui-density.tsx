@@ -0,0 +1,88 @@
+import { useMutation, useQuery, useQueryClient } from "@tanstack/react-query";
+import { createContext, useContext, useEffect, useMemo, useState, type ReactNode } from "react";
+
+import { queryKeys } from "@/lib/api/query-keys";
+import {
+  defaultUiPreferences,
+  loadUiPreferences,
+  resolveCompact,
+  saveUiPreferences,
+  type UiPreferences,
+} from "@/lib/ui-preferences";
+
+type UiDensity = {
+  /** Effective density after applying the window-width threshold. */
+  compact: boolean;
+  preferences: UiPreferences;
+  setPreferences: (update: Partial<UiPreferences>) => void;
+};
+
+const UiDensityContext = createContext<UiDensity | null>(null);
+
+export function UiDensityProvider({ children }: { children: ReactNode }) {
+  const queryClient = useQueryClient();
+  const stored = useQuery({
+    queryFn: loadUiPreferences,
+    queryKey: queryKeys.uiPreferences,
+    retry: false,
+    staleTime: Infinity,
+  });
+  const { mutate: persist } = useMutation({
+    mutationFn: saveUiPreferences,
+    onSuccess: (saved) => queryClient.setQueryData(queryKeys.uiPreferences, saved),
+    onError: (error) => console.warn("desktop ui preferences could not be saved", error),
+  });
+  const preferences = stored.data ?? defaultUiPreferences;
+  const windowWidth = useWindowWidth();
+  const compact = resolveCompact(preferences, windowWidth);
+
+  // Spacing shrinks through the `data-density` attribute (see globals.css), so
+  // every page follows without threading the flag through each component.
+  useEffect(() => {
+    const root = document.documentElement;
+    if (compact) {
+      root.dataset.density = "compact";
+    } else {
+      delete root.dataset.density;
+    }
+  }, [compact]);
+
+  const value = useMemo<UiDensity>(() => ({
+    compact,
+    preferences,
+    setPreferences: (update) => {
+      // Apply right away; the config.toml write follows in the background.
+      const next = { ...preferences, ...update };
+      queryClient.setQueryData(queryKeys.uiPreferences, next);
+      persist(next);
+    },
+  }), [compact, preferences, queryClient, persist]);
+
+  return <UiDensityContext.Provider value={value}>{children}</UiDensityContext.Provider>;
+}
+
+export function useUiDensity() {
+  const density = useContext(UiDensityContext);
+  if (!density) {
+    throw new Error("useUiDensity must be used inside UiDensityProvider");
+  }
+  return density;
+}
+
+function useWindowWidth() {
+  const [width, setWidth] = useState(() => window.innerWidth);
+
+  useEffect(() => {
+    const onResize = () => setWidth(window.innerWidth);
+    window.addEventListener("resize", onResize);
+    return () => window.removeEventListener("resize", onResize);
+  }, []);
+
+  return width;
+}
+
+/** Renders the shorter label while compact mode is active. */
+export function DensityLabel({ full, short }: { full: string; short: string }) {
+  const { compact } = useUiDensity();
+  return <>{compact ? short : full}</>;
+}
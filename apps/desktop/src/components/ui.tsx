@@ -146,24 +146,39 @@ export function Segment({
   items,
   value,
   className,
+  disabled,
+  onValueChange,
 }: {
   items: Array<string>;
   value: string;
   className?: string;
+  disabled?: boolean;
+  onValueChange?: (value: string) => void;
 }) {
   return (
     <div className={cn("inline-flex overflow-hidden rounded-xl border border-slate-200 bg-slate-50 p-0.5", className)}>
-      {items.map((item) => (
-        <span
-          key={item}
-          className={cn(
-            "min-w-20 px-3 py-1.5 text-center text-sm text-slate-500",
-            item === value && "rounded-lg bg-white text-teal-700 shadow-sm ring-1 ring-teal-200",
-          )}
-        >
-          {item}
-        </span>
-      ))}
+      {items.map((item) => {
+        const itemClassName = cn(
+          "min-w-20 px-3 py-1.5 text-center text-sm text-slate-500",
+          item === value && "rounded-lg bg-white text-teal-700 shadow-sm ring-1 ring-teal-200",
+        );
+        return onValueChange ? (
+          <button
+            key={item}
+            type="button"
+            aria-pressed={item === value}
+            disabled={disabled}
+            className={cn(itemClassName, "transition hover:text-slate-800 disabled:pointer-events-none disabled:opacity-50")}
+            onClick={() => onValueChange(item)}
+          >
+            {item}
+          </button>
+        ) : (
+          <span key={item} className={itemClassName}>
+            {item}
+          </span>
+        );
+      })}
     </div>
   );
 }
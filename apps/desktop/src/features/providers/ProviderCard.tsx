@@ -37,6 +37,14 @@ export function ProviderCard({ provider }: { provider: ProviderCardView }) {
           <Info label="Routable" value={`${provider.routableEndpoints}/${provider.endpointCount}`} />
         </div>
         {provider.capacity ? <Info label="Provider capacity" value={provider.capacity} mono /> : null}
+        {provider.schedule ? (
+          <div className="flex items-end justify-between gap-3">
+            <Info label="Schedule" value={provider.schedule.summary} mono />
+            <Badge variant={provider.schedule.active ? "success" : "warning"}>
+              {provider.schedule.active ? "in window" : "outside window"}
+            </Badge>
+          </div>
+        ) : null}
 
         <div className="space-y-2 border-t border-slate-100 pt-3">
           <div className="text-xs font-semibold uppercase text-slate-500">Endpoints</div>
@@ -1,9 +1,10 @@
 import { useState } from "react";
 
 import { save } from "@tauri-apps/plugin-dialog";
-import { Check, Copy, FolderOpen, RefreshCw } from "lucide-react";
+import { Bell, Check, Copy, FolderOpen, RefreshCw } from "lucide-react";
 
 import { PageHeader } from "@/app/AppShell";
+import { useUiDensity } from "@/app/ui-density";
 import { DataStateBanner } from "@/components/page/DataStateBanner";
 import { StatusStrip } from "@/components/shell/StatusStrip";
 import {
@@ -39,8 +40,10 @@ import {
   hideMainWindow,
   openKnownPath,
   quitApp,
+  sendTestSystemNotification,
   type KnownPathKind,
 } from "@/lib/tauri/commands";
+import { AUTO_COMPACT_MAX_WIDTH } from "@/lib/ui-preferences";
 
 const updatePolicy =
   "自动更新暂未启用：Tauri updater 需要签名私钥、固定公钥、HTTPS 发布端点和回滚策略；当前版本请从 GitHub Releases 手动安装。";
@@ -65,6 +68,7 @@ export function SettingsPage() {
   const runtime = useRuntimeSummary();
   const control = useDesktopControlState();
   const actions = useRuntimeActions();
+  const density = useUiDensity();
   const [desktopStatus, setDesktopStatus] = useState<{ kind: "idle" | "success" | "error"; message: string }>({
     kind: "idle",
     message: "",
@@ -158,6 +162,15 @@ export function SettingsPage() {
         : "已关闭开机启动；下次登录系统时不会自动启动桌面端。";
     });
   };
+  const runTestNotification = () => {
+    void runDesktopAction(async () => {
+      const result = await sendTestSystemNotification();
+      if (!result.ok) {
+        throw new Error(result.message);
+      }
+      return `已发送测试通知（${result.backend}）；如果没有看到，请检查系统通知权限和勿扰模式。`;
+    });
+  };
   const runDesktopAction = async (action: () => Promise<string>) => {
     setDesktopBusy(true);
     setDesktopStatus({ kind: "idle", message: "" });
@@ -219,6 +232,12 @@ export function SettingsPage() {
             checked={false}
             disabled
           />
+          <FieldRow label="系统通知">
+            <Button variant="outline" disabled={desktopBusy} onClick={runTestNotification}>
+              <Bell className="h-4 w-4" />
+              发送测试通知
+            </Button>
+          </FieldRow>
         </SettingsCard>
 
         <SettingsCard title="外观与语言" description="调整界面语言和显示偏好。">
@@ -232,8 +251,19 @@ export function SettingsPage() {
             <Segment items={["跟随系统", "浅色", "深色"]} value="跟随系统" />
           </FieldRow>
           <FieldRow label="界面密度">
-            <Segment items={["舒适", "紧凑"]} value="舒适" />
+            <Segment
+              items={["舒适", "紧凑"]}
+              value={density.preferences.compact ? "紧凑" : "舒适"}
+              onValueChange={(value) => density.setPreferences({ compact: value === "紧凑" })}
+            />
           </FieldRow>
+          <ToggleRow
+            label="窄窗口自动紧凑"
+            description={`窗口宽度小于 ${AUTO_COMPACT_MAX_WIDTH}px 时自动切换到紧凑布局。`}
+            checked={density.preferences.autoCompact}
+            disabled={density.preferences.compact}
+            onCheckedChange={(autoCompact) => density.setPreferences({ autoCompact })}
+          />
         </SettingsCard>
 
         <SettingsCard title="本地代理" description="本机代理监听地址和运行时配置。">
@@ -2,6 +2,7 @@ import { createColumnHelper, flexRender, getCoreRowModel, useReactTable } from "
 import { useState } from "react";
 import { GitBranch, Info, Search } from "lucide-react";
 
+import { DensityLabel, useUiDensity } from "@/app/ui-density";
 import { Badge, Button, Card, Input, SelectBox, TooltipHint } from "@/components/ui";
 import { errorToMessage } from "@/lib/api/data-state";
 import type { UsageRowView } from "@/lib/api/types";
@@ -12,6 +13,14 @@ type UsageRow = UsageRowView;
 
 const columnHelper = createColumnHelper<UsageRow>();
 
+/** Columns dropped in compact mode; cost, tokens and timing stay visible. */
+const compactHiddenColumns = {
+  effort: false,
+  type: false,
+  billing: false,
+  firstToken: false,
+};
+
 const baseColumns = [
   columnHelper.accessor("key", {
     header: "API Key",
@@ -38,7 +47,7 @@ const baseColumns = [
     },
   }),
   columnHelper.accessor("cost", {
-    header: "预估费用",
+    header: () => <DensityLabel full="预估费用" short="费用" />,
     cell: (info) => (
       <TooltipHint
         content={
@@ -74,7 +83,7 @@ const baseColumns = [
     ),
   }),
   columnHelper.accessor("firstToken", { header: "First Token" }),
-  columnHelper.accessor("duration", { header: "Duration" }),
+  columnHelper.accessor("duration", { header: () => <DensityLabel full="Duration" short="Dur." /> }),
   columnHelper.accessor("time", { header: "Time" }),
 ];
 
@@ -93,6 +102,7 @@ export function UsageTable({
   totalRows: number;
   onRefresh?: () => void;
 }) {
+  const { compact } = useUiDensity();
   const [chainState, setChainState] = useState<ChainState>({ status: "idle" });
   const tableColumns = [
     ...baseColumns,
@@ -111,7 +121,7 @@ export function UsageTable({
             title="查看请求链路"
           >
             <GitBranch className="h-3.5 w-3.5" />
-            {loading ? "Loading" : "Chain"}
+            {compact ? null : loading ? "Loading" : "Chain"}
           </Button>
         );
       },
@@ -121,6 +131,7 @@ export function UsageTable({
   const table = useReactTable({
     data: rows,
     columns: tableColumns,
+    state: { columnVisibility: compact ? compactHiddenColumns : {} },
     getCoreRowModel: getCoreRowModel(),
   });
 
@@ -160,7 +171,11 @@ export function UsageTable({
           </SelectBox>
           <div className="relative">
             <Search className="absolute left-3 top-2.5 h-4 w-4 text-slate-400" />
-            <Input className="w-72 pl-9" placeholder="搜索 request id、模型或供应商" />
+            <Input
+              className="w-72 pl-9"
+              placeholder={compact ? "搜索" : "搜索 request id、模型或供应商"}
+              data-shortcut-search
+            />
           </div>
         </div>
         <div className="flex gap-2">
@@ -171,7 +186,7 @@ export function UsageTable({
       </div>
       <RequestChainPanel state={chainState} />
       <div className="app-scroll min-h-0 flex-1 overflow-auto">
-        <table className="w-full min-w-[1120px] border-collapse text-left text-sm">
+        <table className="w-full min-w-[1120px] border-collapse compact:min-w-[840px] compact:text-xs text-left text-sm">
           <thead className="sticky top-0 z-10 bg-slate-50 text-xs uppercase tracking-wide text-slate-500 shadow-[0_1px_0_rgba(226,232,240,1)]">
             {table.getHeaderGroups().map((headerGroup) => (
               <tr key={headerGroup.id}>
@@ -186,7 +201,7 @@ export function UsageTable({
           <tbody>
             {table.getRowModel().rows.length === 0 ? (
               <tr>
-                <td className="px-3 py-12 text-center text-sm text-slate-500" colSpan={tableColumns.length}>
+                <td className="px-3 py-12 text-center text-sm text-slate-500" colSpan={table.getVisibleLeafColumns().length}>
                   暂无请求历史。Codex 请求通过本地代理后，这里会显示 request-ledger 记录。
                 </td>
               </tr>
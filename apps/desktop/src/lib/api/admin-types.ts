@@ -447,7 +447,8 @@ export type ApiRequestUsageSummaryGroup =
   | "provider_endpoint"
   | "provider"
   | "model"
-  | "session";
+  | "session"
+  | "tenant";
 
 export type ApiRequestUsageSummaryCoverage = {
   source: string;
@@ -623,6 +624,7 @@ export type ApiOperatorProviderEndpointSummary = {
   runtime_state_override?: ApiRuntimeConfigState;
   capacity?: ApiOperatorProviderCapacity;
   policy_actions?: ApiOperatorPolicyActionSummary[];
+  primary_sticky_remaining_secs?: number;
 };
 
 export type ApiOperatorProviderSummary = {
@@ -634,6 +636,13 @@ export type ApiOperatorProviderSummary = {
   credential_readiness?: ApiCredentialAggregateReadiness;
   endpoints: ApiOperatorProviderEndpointSummary[];
   capacity?: ApiOperatorProviderCapacity;
+  schedule?: ApiProviderScheduleStatus;
+};
+
+export type ApiProviderScheduleStatus = {
+  windows: string[];
+  timezone?: string;
+  active: boolean;
 };
 
 export type ApiOperatorRequestObservability = {
@@ -677,6 +686,8 @@ export type ApiOperatorRequestSummary = {
   id: number;
   trace_key?: string;
   session_key?: string;
+  body_hash?: string;
+  upstream_request_id?: string;
   model?: string;
   reasoning_effort?: string;
   service_tier?: string;
@@ -1166,6 +1177,7 @@ export type ApiRouteStrategy =
   | "manual-sticky"
   | "ordered-failover"
   | "round-robin"
+  | "least-recent-errors"
   | "tag-preferred"
   | "conditional";
 export type ApiRouteAffinityPolicy = "off" | "preferred-group" | "fallback-sticky" | "hard";
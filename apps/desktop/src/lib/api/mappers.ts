@@ -214,6 +214,12 @@ export function mapProviders(
       credentialReadiness: provider.credential_readiness,
       endpointCount,
       capacity: capacitySummary(provider.capacity),
+      schedule: provider.schedule
+        ? {
+            summary: `${provider.schedule.windows.join("; ")} (${provider.schedule.timezone ?? "local"})`,
+            active: provider.schedule.active,
+          }
+        : undefined,
       endpoints: endpoints.map((endpoint) => ({
         key: endpoint.provider_endpoint_key,
         name: endpoint.name,
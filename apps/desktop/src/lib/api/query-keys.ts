@@ -2,6 +2,7 @@ export const queryKeys = {
   appMetadata: ["desktop", "app-metadata"] as const,
   launchAtLogin: ["desktop", "launch-at-login"] as const,
   knownPaths: ["desktop", "known-paths"] as const,
+  uiPreferences: ["desktop", "ui-preferences"] as const,
   admin: {
     readModel: ["admin", "read-model"] as const,
     controlState: ["admin", "control-state"] as const,
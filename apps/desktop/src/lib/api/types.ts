@@ -138,11 +138,17 @@ export type ProviderCardView = {
   credentialReadiness?: "ready" | "degraded" | "blocked";
   endpointCount: number;
   capacity?: string;
+  schedule?: ProviderScheduleView;
   endpoints: ProviderEndpointInventoryView[];
   controlSummary: string;
   controlBadges: ProviderControlBadgeView[];
 };
 
+export type ProviderScheduleView = {
+  summary: string;
+  active: boolean;
+};
+
 export type ProviderEndpointInventoryView = {
   key: string;
   name: string;
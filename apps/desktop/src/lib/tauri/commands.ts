@@ -36,6 +36,17 @@ export type ConfigFileActionResult = {
   secretWarning: boolean;
 };
 
+export type UiPreferencesPayload = {
+  compact: boolean;
+  autoCompact: boolean;
+};
+
+export type TestNotificationResult = {
+  ok: boolean;
+  backend: string | null;
+  message: string;
+};
+
 export type AdminEndpointConfig = {
   proxyPort: number;
   adminPort: number;
@@ -86,6 +97,18 @@ export async function quitApp() {
   return invoke<void>("quit_app");
 }
 
+export async function getUiPreferences() {
+  return invoke<UiPreferencesPayload>("get_ui_preferences");
+}
+
+export async function saveUiPreferences(payload: UiPreferencesPayload) {
+  return invoke<UiPreferencesPayload>("save_ui_preferences", { payload });
+}
+
+export async function sendTestSystemNotification() {
+  return invoke<TestNotificationResult>("send_test_system_notification");
+}
+
 export async function getKnownPaths() {
   return invoke<KnownPaths>("get_known_paths");
 }
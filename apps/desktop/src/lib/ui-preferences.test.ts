@@ -0,0 +1,55 @@
+import { invoke } from "@tauri-apps/api/core";
+import { beforeEach, describe, expect, it, vi } from "vitest";
+
+import {
+  AUTO_COMPACT_MAX_WIDTH,
+  defaultUiPreferences,
+  loadUiPreferences,
+  resolveCompact,
+  saveUiPreferences,
+} from "@/lib/ui-preferences";
+
+vi.mock("@tauri-apps/api/core", () => ({
+  invoke: vi.fn(),
+}));
+
+const mockedInvoke = vi.mocked(invoke);
+
+describe("desktop ui preferences", () => {
+  beforeEach(() => {
+    mockedInvoke.mockReset();
+  });
+
+  it("reads and writes the ui config through desktop commands", async () => {
+    mockedInvoke.mockResolvedValueOnce({ compact: true, autoCompact: false });
+    expect(await loadUiPreferences()).toEqual({ compact: true, autoCompact: false });
+    expect(mockedInvoke).toHaveBeenLastCalledWith("get_ui_preferences");
+
+    mockedInvoke.mockResolvedValueOnce({ compact: false, autoCompact: true });
+    expect(await saveUiPreferences({ compact: false, autoCompact: true })).toEqual({
+      compact: false,
+      autoCompact: true,
+    });
+    expect(mockedInvoke).toHaveBeenLastCalledWith("save_ui_preferences", {
+      payload: { compact: false, autoCompact: true },
+    });
+  });
+
+  it("falls back to defaults when the config cannot be read", async () => {
+    vi.spyOn(console, "warn").mockImplementation(() => {});
+    mockedInvoke.mockRejectedValueOnce(new Error("tauri runtime unavailable"));
+    expect(await loadUiPreferences()).toEqual(defaultUiPreferences);
+
+    mockedInvoke.mockResolvedValueOnce({ compact: "yes" });
+    expect(await loadUiPreferences()).toEqual(defaultUiPreferences);
+  });
+
+  it("only auto-enables compact mode below the width threshold", () => {
+    const auto = { compact: false, autoCompact: true };
+
+    expect(resolveCompact(auto, AUTO_COMPACT_MAX_WIDTH - 1)).toBe(true);
+    expect(resolveCompact(auto, AUTO_COMPACT_MAX_WIDTH)).toBe(false);
+    expect(resolveCompact(defaultUiPreferences, 800)).toBe(false);
+    expect(resolveCompact({ compact: true, autoCompact: false }, 1920)).toBe(true);
+  });
+});
@@ -0,0 +1,37 @@
+import { z } from "zod";
+
+import {
+  getUiPreferences,
+  saveUiPreferences as saveUiPreferencesCommand,
+} from "@/lib/tauri/commands";
+
+/** Window widths below this switch to compact mode when `autoCompact` is on. */
+export const AUTO_COMPACT_MAX_WIDTH = 1280;
+
+/** Mirrors `ui.compact` / `ui.auto_compact` in the helper `config.toml`. */
+export const uiPreferencesSchema = z.object({
+  compact: z.boolean().default(false),
+  autoCompact: z.boolean().default(false),
+});
+
+export type UiPreferences = z.infer<typeof uiPreferencesSchema>;
+
+export const defaultUiPreferences: UiPreferences = uiPreferencesSchema.parse({});
+
+export async function loadUiPreferences(): Promise<UiPreferences> {
+  try {
+    const parsed = uiPreferencesSchema.safeParse(await getUiPreferences());
+    return parsed.success ? parsed.data : defaultUiPreferences;
+  } catch (error) {
+    console.warn("desktop ui preferences could not be loaded", error);
+    return defaultUiPreferences;
+  }
+}
+
+export async function saveUiPreferences(preferences: UiPreferences): Promise<UiPreferences> {
+  return uiPreferencesSchema.parse(await saveUiPreferencesCommand(preferences));
+}
+
+export function resolveCompact(preferences: UiPreferences, windowWidth: number) {
+  return preferences.compact || (preferences.autoCompact && windowWidth < AUTO_COMPACT_MAX_WIDTH);
+}
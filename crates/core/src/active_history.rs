@@ -0,0 +1,200 @@
+//! Append-only record of changes to what a service is actively routed to.
+//!
+//! "Active" is the persisted manual-sticky pin of the entry route and the runtime
+//! new-session preference set from an operator console. Each change is one JSONL line in
+//! `logs/active_history.jsonl`, bounded by the shared local log retention.
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::config::proxy_home_dir;
+use crate::local_log_store::{LogRetention, append_line, collect_rotated_logs};
+
+pub const ACTIVE_HISTORY_FILE_NAME: &str = "active_history.jsonl";
+const DEFAULT_ACTIVE_HISTORY_MAX_BYTES: u64 = 1024 * 1024;
+const DEFAULT_ACTIVE_HISTORY_MAX_FILES: usize = 5;
+
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ActiveChangeKind {
+    /// The persisted manual-sticky target of the entry route.
+    Pin,
+    /// The runtime preference for new sessions set through the local operator API.
+    NewSessionPreference,
+    /// The `routing.auto_active_by_health` pick, moved by runtime health.
+    AutoActive,
+}
+
+impl ActiveChangeKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Pin => "pin",
+            Self::NewSessionPreference => "new_session_preference",
+            Self::AutoActive => "auto_active",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ActiveChangeSource {
+    Cli,
+    Tui,
+    Gui,
+    Api,
+    Auto,
+}
+
+impl ActiveChangeSource {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Cli => "cli",
+            Self::Tui => "tui",
+            Self::Gui => "gui",
+            Self::Api => "api",
+            Self::Auto => "auto",
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+pub struct ActiveChangeRecord {
+    pub ts_ms: u64,
+    pub service: String,
+    pub kind: ActiveChangeKind,
+    pub old: Option<String>,
+    pub new: Option<String>,
+    pub source: ActiveChangeSource,
+}
+
+impl ActiveChangeRecord {
+    pub fn new(
+        service: impl Into<String>,
+        kind: ActiveChangeKind,
+        old: Option<String>,
+        new: Option<String>,
+        source: ActiveChangeSource,
+    ) -> Self {
+        Self {
+            ts_ms: crate::logging::now_ms(),
+            service: service.into(),
+            kind,
+            old,
+            new,
+            source,
+        }
+    }
+}
+
+pub fn active_history_path() -> PathBuf {
+    proxy_home_dir().join("logs").join(ACTIVE_HISTORY_FILE_NAME)
+}
+
+fn active_history_retention() -> LogRetention {
+    LogRetention::from_env(
+        "CODEX_HELPER_ACTIVE_HISTORY_MAX_BYTES",
+        "CODEX_HELPER_ACTIVE_HISTORY_MAX_FILES",
+        DEFAULT_ACTIVE_HISTORY_MAX_BYTES,
+        DEFAULT_ACTIVE_HISTORY_MAX_FILES,
+    )
+}
+
+/// Appends `record` unless it is a no-op (`old == new`).
+pub fn record_active_change(record: &ActiveChangeRecord) -> io::Result<()> {
+    append_active_change_to(&active_history_path(), active_history_retention(), record)
+}
+
+fn append_active_change_to(
+    path: &Path,
+    retention: LogRetention,
+    record: &ActiveChangeRecord,
+) -> io::Result<()> {
+    if record.old == record.new {
+        return Ok(());
+    }
+    let line = serde_json::to_string(record).map_err(io::Error::other)?;
+    append_line(path, retention, &line)
+}
+
+/// Returns up to `limit` most recent changes, oldest first, optionally for one service.
+pub fn read_recent_active_changes(limit: usize, service: Option<&str>) -> Vec<ActiveChangeRecord> {
+    read_recent_active_changes_from(&active_history_path(), limit, service)
+}
+
+fn read_recent_active_changes_from(
+    path: &Path,
+    limit: usize,
+    service: Option<&str>,
+) -> Vec<ActiveChangeRecord> {
+    let mut files = collect_rotated_logs(path)
+        .into_iter()
+        .map(|file| file.path)
+        .collect::<Vec<_>>();
+    files.push(path.to_path_buf());
+    let mut records = files
+        .iter()
+        .filter_map(|file| std::fs::read_to_string(file).ok())
+        .flat_map(|text| {
+            text.lines()
+                .filter_map(|line| serde_json::from_str::<ActiveChangeRecord>(line).ok())
+                .collect::<Vec<_>>()
+        })
+        .filter(|record| service.is_none_or(|service| record.service == service))
+        .collect::<Vec<_>>();
+    records.sort_by_key(|record| record.ts_ms);
+    let skip = records.len().saturating_sub(limit);
+    records.split_off(skip)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(
+        ts_ms: u64,
+        service: &str,
+        old: Option<&str>,
+        new: Option<&str>,
+    ) -> ActiveChangeRecord {
+        ActiveChangeRecord {
+            ts_ms,
+            service: service.to_string(),
+            kind: ActiveChangeKind::Pin,
+            old: old.map(str::to_string),
+            new: new.map(str::to_string),
+            source: ActiveChangeSource::Cli,
+        }
+    }
+
+    #[test]
+    fn active_history_skips_no_ops_and_returns_recent_entries_per_service() {
+        let dir = std::env::temp_dir().join(format!(
+            "codex-helper-active-history-{}-{}",
+            std::process::id(),
+            crate::logging::now_ms()
+        ));
+        let path = dir.join(ACTIVE_HISTORY_FILE_NAME);
+        let retention = LogRetention::new(1024 * 1024, 2);
+
+        for entry in [
+            record(1, "codex", None, Some("alpha")),
+            record(2, "codex", Some("alpha"), Some("alpha")),
+            record(3, "claude", None, Some("relay")),
+            record(4, "codex", Some("alpha"), Some("beta")),
+            record(5, "codex", Some("beta"), None),
+        ] {
+            append_active_change_to(&path, retention, &entry).expect("append active change");
+        }
+
+        let codex = read_recent_active_changes_from(&path, 2, Some("codex"));
+        assert_eq!(
+            codex.iter().map(|entry| entry.ts_ms).collect::<Vec<_>>(),
+            vec![4, 5]
+        );
+        assert_eq!(codex[1].new, None);
+        assert_eq!(read_recent_active_changes_from(&path, 10, None).len(), 4);
+        assert!(read_recent_active_changes_from(&dir.join("missing.jsonl"), 10, None).is_empty());
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
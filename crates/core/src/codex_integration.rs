@@ -898,12 +898,8 @@ pub fn guard_claude_settings_before_switch_on_interactive() -> Result<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::sync::{Mutex, OnceLock};
 
-    fn env_lock() -> std::sync::MutexGuard<'static, ()> {
-        static LOCK: OnceLock<Mutex<()>> = OnceLock::new();
-        LOCK.get_or_init(|| Mutex::new(())).lock().unwrap()
-    }
+    use crate::test_support::env_lock_blocking as env_lock;
 
     struct ScopedEnv {
         saved: Vec<(String, Option<String>)>,
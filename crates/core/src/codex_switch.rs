@@ -969,6 +969,21 @@ pub fn restore_if_owned_with_retry(
     Err(last_error.expect("restore retry schedule is non-empty"))
 }
 
+/// Whether `config.toml` changed since the switch-on that issued `lease`.
+///
+/// Restoring still merges unmanaged edits back, but a foreground proxy can use this to ask before
+/// touching a config someone edited by hand while it ran.
+pub fn config_changed_since_switch_on(
+    lease: &CodexSwitchRestoreLease,
+) -> Result<bool, CodexSwitchError> {
+    let paths = SwitchPaths::resolve()?;
+    if paths.config_fingerprint != lease.config_path_fingerprint {
+        return Ok(false);
+    }
+    let current = read_config_snapshot(paths.config.as_path())?;
+    Ok(!current.present || current.fingerprint != lease.applied_fingerprint)
+}
+
 fn retryable_restore_error(error: &CodexSwitchError) -> bool {
     if matches!(error, CodexSwitchError::LockBusy { .. }) {
         return true;
@@ -6193,6 +6208,21 @@ trust_level = "trusted"
         assert!(!env.state_path().exists());
     }
 
+    #[test]
+    fn config_change_since_switch_on_detects_out_of_band_edits() {
+        let env = TestEnvironment::new();
+        env.write_config("model_provider = \"openai\"\n");
+        let applied = acquire_test_ephemeral_switch(3211).expect("switch on");
+        let lease = applied.restore_lease.expect("switch owns restore");
+        assert!(!config_changed_since_switch_on(&lease).expect("inspect unchanged config"));
+
+        let mut edited = env.read_config();
+        edited.push_str("\nmodel = \"hand-edited\"\n");
+        env.write_config(edited.as_str());
+
+        assert!(config_changed_since_switch_on(&lease).expect("inspect edited config"));
+    }
+
     #[test]
     fn explicit_client_patches_expose_only_the_requested_codex_capabilities() {
         for (client_patch, provider_name, exposes_tools) in [
@@ -54,14 +54,14 @@ pub use retry_impl::{
 pub use storage_impl::{
     ConfigInitOutcome, LoadedConfig, config_file_path, init_config_toml,
     init_config_toml_with_outcome, load_config, load_config_with_source, mutate_helper_config,
-    save_helper_config,
+    redacted_helper_config, save_helper_config,
 };
 
 pub mod storage {
     pub use super::storage_impl::{
         ConfigInitOutcome, LoadedConfig, config_file_path, init_config_toml,
         init_config_toml_with_outcome, load_config, load_config_with_source, mutate_helper_config,
-        save_helper_config,
+        redacted_helper_config, save_helper_config,
     };
 }
 
@@ -403,6 +403,13 @@ pub struct NotifyPolicyConfig {
     pub global_cooldown_ms: u64,
     /// Events within this window will be merged into one notification.
     pub merge_window_ms: u64,
+    /// Flush as soon as this many events are pending and merge at most this many per
+    /// notification; later events wait for the next flush. 0 means unlimited.
+    #[serde(default)]
+    pub max_merged: usize,
+    /// How a merged batch is presented in the system notification.
+    #[serde(default)]
+    pub strategy: NotifyMergeStrategy,
     /// Suppress notifications for the same thread-id within this cooldown.
     pub per_thread_cooldown_ms: u64,
     /// How far back to search typed operator requests when matching a thread-id.
@@ -417,6 +424,8 @@ impl Default for NotifyPolicyConfig {
             min_duration_ms: 60_000,
             global_cooldown_ms: 60_000,
             merge_window_ms: 10_000,
+            max_merged: 0,
+            strategy: NotifyMergeStrategy::default(),
             per_thread_cooldown_ms: 180_000,
             recent_search_window_ms: 5 * 60_000,
             recent_endpoint_timeout_ms: 500,
@@ -424,20 +433,104 @@ impl Default for NotifyPolicyConfig {
     }
 }
 
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum NotifyMergeStrategy {
+    /// Title counts the merged turns; the body lists the newest few.
+    #[default]
+    SummarizeCount,
+    /// Only the newest turn is shown; the title notes how many were merged into it.
+    LatestOnly,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct NotifySystemConfig {
     /// Whether to show system notifications (toasts). Default: false.
     pub enabled: bool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NotifyExecConfig {
-    /// Enable executing an external command for each aggregated notification.
+    /// Enable executing external commands for processed notifications.
     pub enabled: bool,
     /// Command to execute; the aggregated JSON is written to stdin.
     /// Example: ["python", "my_script.py"].
     #[serde(default)]
     pub command: Vec<String>,
+    /// Notification types that trigger `command`; empty means `agent-turn-complete` only.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub types: Vec<String>,
+    /// Additional callbacks, each with its own command and optional type filter.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub callbacks: Vec<NotifyExecCallbackConfig>,
+    /// Kill a callback that has not exited after this many milliseconds.
+    #[serde(default = "default_notify_exec_timeout_ms")]
+    pub timeout_ms: u64,
+}
+
+impl Default for NotifyExecConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            command: Vec::new(),
+            types: Vec::new(),
+            callbacks: Vec::new(),
+            timeout_ms: default_notify_exec_timeout_ms(),
+        }
+    }
+}
+
+impl NotifyExecConfig {
+    /// All configured callbacks, with the legacy top-level `command` first.
+    pub fn all_callbacks(&self) -> Vec<NotifyExecCallbackConfig> {
+        let mut out = Vec::with_capacity(self.callbacks.len() + 1);
+        if !self.command.is_empty() {
+            out.push(NotifyExecCallbackConfig {
+                command: self.command.clone(),
+                types: self.types.clone(),
+                timeout_ms: None,
+            });
+        }
+        out.extend(
+            self.callbacks
+                .iter()
+                .filter(|callback| !callback.command.is_empty())
+                .cloned(),
+        );
+        out
+    }
+
+    pub fn has_callbacks(&self) -> bool {
+        self.enabled
+            && (!self.command.is_empty() || self.callbacks.iter().any(|c| !c.command.is_empty()))
+    }
+}
+
+fn default_notify_exec_timeout_ms() -> u64 {
+    10_000
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct NotifyExecCallbackConfig {
+    /// Command to execute; the notification JSON is written to stdin.
+    pub command: Vec<String>,
+    /// Notification types that trigger this callback; empty means `agent-turn-complete` only.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub types: Vec<String>,
+    /// Per-callback override of `notify.exec.timeout_ms`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_ms: Option<u64>,
+}
+
+impl NotifyExecCallbackConfig {
+    pub fn matches_type(&self, notification_type: &str) -> bool {
+        if self.types.is_empty() {
+            return notification_type == crate::notify::AGENT_TURN_COMPLETE_TYPE;
+        }
+        self.types
+            .iter()
+            .any(|t| t.trim() == "*" || t.trim().eq_ignore_ascii_case(notification_type))
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -558,6 +651,10 @@ fn bool_is_false(value: &bool) -> bool {
     !*value
 }
 
+fn u64_is_zero(value: &u64) -> bool {
+    *value == 0
+}
+
 fn is_default_service_status_refresh_interval_secs(value: &u64) -> bool {
     *value == default_service_status_refresh_interval_secs()
 }
@@ -618,6 +715,30 @@ pub struct HelperConfig {
     pub fleet: FleetRegistryConfig,
     #[serde(default)]
     pub ui: UiConfig,
+    #[serde(default, skip_serializing_if = "PrivacyConfig::is_default")]
+    pub privacy: PrivacyConfig,
+    #[serde(default, skip_serializing_if = "EffortGuardConfig::is_default")]
+    pub effort_guard: EffortGuardConfig,
+    #[serde(default, skip_serializing_if = "RequestQuotaConfig::is_default")]
+    pub quota: RequestQuotaConfig,
+    #[serde(default, skip_serializing_if = "DashboardConfig::is_default")]
+    pub dashboard: DashboardConfig,
+    #[serde(default, skip_serializing_if = "HttpConfig::is_default")]
+    pub http: HttpConfig,
+    #[serde(default, skip_serializing_if = "ResponseCacheConfig::is_default")]
+    pub cache: ResponseCacheConfig,
+    #[serde(default, skip_serializing_if = "StreamConfig::is_default")]
+    pub stream: StreamConfig,
+    #[serde(default, skip_serializing_if = "LbConfig::is_default")]
+    pub lb: LbConfig,
+    #[serde(default, skip_serializing_if = "HealthCheckConfig::is_default")]
+    pub healthcheck: HealthCheckConfig,
+    #[serde(default, skip_serializing_if = "ServeConfig::is_default")]
+    pub serve: ServeConfig,
+    #[serde(default, skip_serializing_if = "LoggingConfig::is_default")]
+    pub logging: LoggingConfig,
+    #[serde(default, skip_serializing_if = "TelemetryConfig::is_default")]
+    pub telemetry: TelemetryConfig,
 }
 
 impl Default for HelperConfig {
@@ -632,6 +753,18 @@ impl Default for HelperConfig {
             relay_targets: BTreeMap::new(),
             fleet: FleetRegistryConfig::default(),
             ui: UiConfig::default(),
+            privacy: PrivacyConfig::default(),
+            effort_guard: EffortGuardConfig::default(),
+            quota: RequestQuotaConfig::default(),
+            dashboard: DashboardConfig::default(),
+            http: HttpConfig::default(),
+            cache: ResponseCacheConfig::default(),
+            stream: StreamConfig::default(),
+            lb: LbConfig::default(),
+            healthcheck: HealthCheckConfig::default(),
+            serve: ServeConfig::default(),
+            logging: LoggingConfig::default(),
+            telemetry: TelemetryConfig::default(),
         }
     }
 }
@@ -687,6 +820,50 @@ pub struct ServiceRouteConfig {
     pub providers: BTreeMap<String, ProviderConfig>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub routing: Option<RouteGraphConfig>,
+    /// When non-empty, only these upstream response headers reach the client.
+    ///
+    /// Body framing (`content-type`, `content-encoding`) and rate-limit hints (`retry-after`,
+    /// `x-ratelimit-*`) always pass. A trailing `*` matches a name prefix.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub response_header_allowlist: Vec<String>,
+    /// Upstream response headers removed before replying to the client; wins over the allowlist.
+    ///
+    /// Hop-by-hop headers and `set-cookie` are always removed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub response_header_denylist: Vec<String>,
+    /// Reply sent instead of a routing error while every provider of the service is disabled.
+    ///
+    /// Unset keeps the regular "no routable provider candidate" failure.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub maintenance: Option<MaintenanceResponseConfig>,
+}
+
+pub const DEFAULT_MAINTENANCE_STATUS: u16 = 503;
+
+/// `[<service>.maintenance]`: what clients see while a service is taken down on purpose.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MaintenanceResponseConfig {
+    /// HTTP status of the reply; anything outside 400-599 falls back to the default (default: 503).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<u16>,
+    /// Message carried in the JSON error body (default: "<service> is under maintenance").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+impl MaintenanceResponseConfig {
+    pub fn status(&self) -> u16 {
+        self.status
+            .filter(|status| (400..600).contains(status))
+            .unwrap_or(DEFAULT_MAINTENANCE_STATUS)
+    }
+
+    pub fn message(&self, service_name: &str) -> String {
+        self.message
+            .clone()
+            .filter(|message| !message.trim().is_empty())
+            .unwrap_or_else(|| format!("{service_name} is under maintenance"))
+    }
 }
 
 impl ServiceRouteConfig {
@@ -740,6 +917,57 @@ pub struct ProviderConfig {
     pub limits: ProviderConcurrencyLimits,
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub endpoints: BTreeMap<String, ProviderEndpointConfig>,
+    /// Time windows (`Mon-Fri 20:00-08:00`, `Sat,Sun`) outside which automatic routing skips
+    /// this provider. Empty means always eligible.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub enabled_schedule: Vec<String>,
+    /// IANA timezone for `enabled_schedule`; local time when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schedule_timezone: Option<String>,
+    #[serde(default, skip_serializing_if = "ProviderTlsConfig::is_default")]
+    pub tls: ProviderTlsConfig,
+    /// User-Agent for this provider's upstream requests; overrides `http.user_agent`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_agent: Option<String>,
+    /// Address family for this provider's upstream connections; overrides `http.address_family`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address_family: Option<AddressFamily>,
+    /// Collapse this provider's streamed responses into one JSON body; overrides
+    /// `stream.buffer_responses`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub buffer_responses: Option<bool>,
+    /// Client request headers (case-insensitive names) removed before forwarding to this provider.
+    ///
+    /// A client `Authorization` or `x-api-key` is always removed when the provider supplies its
+    /// own credential, so this is for anything else the upstream should not see.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub strip_request_headers: Vec<String>,
+    /// Health-probe success criteria for this provider; replaces the global `[healthcheck]`
+    /// criteria. The probe interval stays global, so `interval_ms` is not allowed here.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub healthcheck: Option<HealthCheckConfig>,
+}
+
+/// PEM files for a provider that needs a client certificate (mTLS) or a private CA.
+///
+/// The files are read when the runtime snapshot is built (startup and reload), never per request.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProviderTlsConfig {
+    /// Client certificate chain, leaf first.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_cert_path: Option<PathBuf>,
+    /// Private key for `client_cert_path` (PKCS#8, PKCS#1 or SEC1).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_key_path: Option<PathBuf>,
+    /// Extra root certificates trusted for this provider, on top of the system roots.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ca_bundle_path: Option<PathBuf>,
+}
+
+impl ProviderTlsConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
 }
 
 impl Default for ProviderConfig {
@@ -756,6 +984,14 @@ impl Default for ProviderConfig {
             model_mapping: BTreeMap::new(),
             limits: ProviderConcurrencyLimits::default(),
             endpoints: BTreeMap::new(),
+            enabled_schedule: Vec::new(),
+            schedule_timezone: None,
+            tls: ProviderTlsConfig::default(),
+            user_agent: None,
+            address_family: None,
+            buffer_responses: None,
+            strip_request_headers: Vec::new(),
+            healthcheck: None,
         }
     }
 }
@@ -814,10 +1050,46 @@ pub struct RouteGraphConfig {
     pub fallback_ttl_ms: Option<u64>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub reprobe_preferred_after_ms: Option<u64>,
+    /// Point new sessions at the highest-priority healthy candidate, following runtime health.
+    /// An explicit manual-sticky pin or operator new-session preference takes precedence.
+    #[serde(default, skip_serializing_if = "is_default_auto_active_by_health")]
+    pub auto_active_by_health: bool,
+    /// Within the best preference group, try the candidate with the lowest price for the requested
+    /// model first. Prices are looked up under the provider id; unpriced candidates go last.
+    #[serde(default, skip_serializing_if = "is_default_prefer_cheapest")]
+    pub prefer_cheapest: bool,
+    /// Warn and notify when failover-served requests exceed this share (1-100) of successful
+    /// requests over `fallback_alert_window_secs`. Unset disables the alert.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fallback_alert_threshold_pct: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fallback_alert_window_secs: Option<u64>,
+    /// After a request fails over away from a candidate, keep new selections off it for this
+    /// long while another candidate is routable. Separate from the failure cooldown.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub primary_sticky_ms: Option<u64>,
+    /// Let the running proxy fetch each provider's `/v1/models` (cached for an hour) so
+    /// `config lint` can check model mappings, profiles and route conditions against it.
+    #[serde(default, skip_serializing_if = "is_default_validate_models_online")]
+    pub validate_models_online: bool,
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub routes: BTreeMap<String, RouteNodeConfig>,
 }
 
+pub const DEFAULT_FALLBACK_ALERT_WINDOW_SECS: u64 = 600;
+
+fn is_default_auto_active_by_health(value: &bool) -> bool {
+    !*value
+}
+
+fn is_default_prefer_cheapest(value: &bool) -> bool {
+    !*value
+}
+
+fn is_default_validate_models_online(value: &bool) -> bool {
+    !*value
+}
+
 impl Default for RouteGraphConfig {
     fn default() -> Self {
         Self {
@@ -826,6 +1098,12 @@ impl Default for RouteGraphConfig {
             scheduling_preset: SchedulingPreset::default(),
             fallback_ttl_ms: None,
             reprobe_preferred_after_ms: None,
+            auto_active_by_health: false,
+            prefer_cheapest: false,
+            fallback_alert_threshold_pct: None,
+            fallback_alert_window_secs: None,
+            primary_sticky_ms: None,
+            validate_models_online: false,
             routes: BTreeMap::new(),
         }
     }
@@ -848,6 +1126,14 @@ impl RouteGraphConfig {
         })
     }
 
+    pub fn least_recent_errors(children: Vec<String>) -> Self {
+        Self::single_entry_node(RouteNodeConfig {
+            strategy: RouteStrategy::LeastRecentErrors,
+            children,
+            ..RouteNodeConfig::default()
+        })
+    }
+
     pub fn manual_sticky(target: String, children: Vec<String>) -> Self {
         Self::single_entry_node(RouteNodeConfig {
             strategy: RouteStrategy::ManualSticky,
@@ -871,6 +1157,16 @@ impl RouteGraphConfig {
         })
     }
 
+    /// `(threshold_pct, window_secs)` when the fallback-share alert is enabled.
+    pub fn fallback_alert(&self) -> Option<(u8, u64)> {
+        let threshold_pct = self.fallback_alert_threshold_pct?;
+        Some((
+            threshold_pct,
+            self.fallback_alert_window_secs
+                .unwrap_or(DEFAULT_FALLBACK_ALERT_WINDOW_SECS),
+        ))
+    }
+
     pub fn single_entry_node(node: RouteNodeConfig) -> Self {
         let entry = non_conflicting_default_route_entry(&node);
         Self {
@@ -880,6 +1176,12 @@ impl RouteGraphConfig {
             scheduling_preset: SchedulingPreset::default(),
             fallback_ttl_ms: None,
             reprobe_preferred_after_ms: None,
+            auto_active_by_health: false,
+            prefer_cheapest: false,
+            fallback_alert_threshold_pct: None,
+            fallback_alert_window_secs: None,
+            primary_sticky_ms: None,
+            validate_models_online: false,
         }
     }
 
@@ -1144,6 +1446,7 @@ pub enum RouteStrategy {
     ManualSticky,
     OrderedFailover,
     RoundRobin,
+    LeastRecentErrors,
     TagPreferred,
     Conditional,
 }
@@ -1227,6 +1530,16 @@ pub struct PersistedRoutingSpec {
     pub fallback_ttl_ms: Option<u64>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub reprobe_preferred_after_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "is_default_auto_active_by_health")]
+    pub auto_active_by_health: bool,
+    #[serde(default, skip_serializing_if = "is_default_prefer_cheapest")]
+    pub prefer_cheapest: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fallback_alert_threshold_pct: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fallback_alert_window_secs: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub primary_sticky_ms: Option<u64>,
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub routes: BTreeMap<String, RouteNodeConfig>,
     #[serde(default = "default_route_strategy")]
@@ -1311,6 +1624,421 @@ pub struct PersistedProvidersCatalog {
     pub providers: Vec<PersistedProviderSpec>,
 }
 
+/// Log-side redaction of local session details.
+///
+/// Only what codex-helper records and displays changes; requests forwarded upstream are untouched.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct PrivacyConfig {
+    /// Store session working directories as a stable hash instead of the real path.
+    #[serde(default, skip_serializing_if = "bool_is_false")]
+    pub redact_cwd: bool,
+    /// Omit prompt text from request debug previews and session lists.
+    #[serde(default, skip_serializing_if = "bool_is_false")]
+    pub redact_prompts: bool,
+}
+
+impl PrivacyConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Ceiling on the reasoning effort a request may carry upstream.
+///
+/// Applies to client-sent efforts and profile overrides alike; unknown effort names pass through.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct EffortGuardConfig {
+    /// Highest effort forwarded upstream, such as `high` (default: unset = no ceiling).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_effort: Option<String>,
+    /// What happens to a request above the ceiling (default: `downgrade`).
+    #[serde(default, skip_serializing_if = "EffortGuardAction::is_default")]
+    pub action: EffortGuardAction,
+}
+
+impl EffortGuardConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum EffortGuardAction {
+    /// Rewrite the effort to `max_effort` and forward the request.
+    #[default]
+    Downgrade,
+    /// Refuse the request with `400 Bad Request`.
+    Reject,
+}
+
+impl EffortGuardAction {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+pub const DEFAULT_REQUEST_QUOTA_WINDOW_SECS: u64 = 3600;
+
+/// Sliding-window request and output-token quotas, enforced per provider before forwarding.
+///
+/// Requests over a limit are answered with `429 Too Many Requests` and a `Retry-After` header.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct RequestQuotaConfig {
+    /// Window length in seconds (default: 3600).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub window_secs: Option<u64>,
+    /// Requests admitted per window (default: unset = unlimited).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_requests: Option<u64>,
+    /// Upstream-reported output tokens per window (default: unset = unlimited).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_output_tokens: Option<u64>,
+    /// Count each `x-codex-helper-tenant` value separately (default: false = shared budget).
+    #[serde(default, skip_serializing_if = "bool_is_false")]
+    pub per_tenant: bool,
+}
+
+impl RequestQuotaConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.max_requests.is_some() || self.max_output_tokens.is_some()
+    }
+
+    pub fn window_secs(&self) -> u64 {
+        self.window_secs
+            .unwrap_or(DEFAULT_REQUEST_QUOTA_WINDOW_SECS)
+    }
+}
+
+/// Upper bound for `dashboard.max_recent`, keeping the in-memory request buffer small.
+pub const MAX_DASHBOARD_RECENT_REQUESTS: usize = 10_000;
+
+/// Runtime-side sizing for what operator dashboards (TUI, GUI, `/api/v1`) can show.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct DashboardConfig {
+    /// Finished requests kept in memory for the Requests views, 1-10000
+    /// (default: unset = `CODEX_HELPER_RECENT_FINISHED_MAX`, else 1000).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_recent: Option<usize>,
+}
+
+impl DashboardConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// User-Agent sent upstream when nothing is configured and the client sent none.
+pub fn default_upstream_user_agent() -> String {
+    format!("codex-helper/{}", env!("CARGO_PKG_VERSION"))
+}
+
+/// Outbound HTTP settings shared by every upstream request.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct HttpConfig {
+    /// User-Agent for forwarded requests, replacing the client's own (default: unset, the
+    /// client's header is forwarded, or `codex-helper/<version>` when it sent none); a
+    /// provider's `user_agent` overrides it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_agent: Option<String>,
+    /// Address family used when connecting to upstream hosts (default: `any`, whatever the
+    /// resolver returns); a provider's `address_family` overrides it. Hosts given as IP
+    /// literals are connected to as written.
+    #[serde(default, skip_serializing_if = "AddressFamily::is_default")]
+    pub address_family: AddressFamily,
+}
+
+impl HttpConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum AddressFamily {
+    #[default]
+    Any,
+    /// Only connect over IPv4.
+    V4,
+    /// Only connect over IPv6.
+    V6,
+}
+
+impl AddressFamily {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Any => "any",
+            Self::V4 => "v4",
+            Self::V6 => "v6",
+        }
+    }
+}
+
+/// Upper bound for `cache.models_ttl_ms`; the cache is meant to absorb polling bursts only.
+pub const MAX_MODELS_CACHE_TTL_MS: u64 = 60 * 60 * 1000;
+
+/// Short-lived proxy-side caching of idempotent GET responses.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct ResponseCacheConfig {
+    /// How long a successful `GET .../models` response is served from memory
+    /// (default: 0, disabled).
+    #[serde(default, skip_serializing_if = "u64_is_zero")]
+    pub models_ttl_ms: u64,
+}
+
+impl ResponseCacheConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// How successful streamed responses are relayed to the client.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct StreamConfig {
+    /// Read the whole upstream event stream and answer with one non-streamed JSON body
+    /// (default: false, events are forwarded as they arrive); a provider's
+    /// `buffer_responses` overrides it. Buffering is capped by the upstream response body limit.
+    #[serde(default, skip_serializing_if = "bool_is_false")]
+    pub buffer_responses: bool,
+}
+
+impl StreamConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Load-balancing controls shared by every provider of a service.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct LbConfig {
+    #[serde(default, skip_serializing_if = "AdaptiveConcurrencyConfig::is_default")]
+    pub adaptive_concurrency: AdaptiveConcurrencyConfig,
+}
+
+impl LbConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// AIMD control of provider concurrency below each `limits.max_concurrent_requests`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct AdaptiveConcurrencyConfig {
+    /// Lower the allowed concurrency on 429/5xx responses and grow it back on sustained
+    /// success (default: false, the configured maximum always applies).
+    #[serde(default, skip_serializing_if = "bool_is_false")]
+    pub enabled: bool,
+    /// Floor the adaptive limit never drops below (default: 1). Values above a provider's
+    /// `max_concurrent_requests` are capped at that maximum.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_concurrent_requests: Option<u32>,
+}
+
+impl AdaptiveConcurrencyConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// The floor to apply when enabled, or `None` when the controller is off.
+    pub fn active_floor(&self) -> Option<u32> {
+        self.enabled
+            .then(|| self.min_concurrent_requests.unwrap_or(1).max(1))
+    }
+}
+
+/// Periodic background reachability probes of routable upstreams.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct HealthCheckConfig {
+    /// Probe every routable upstream origin this often (default: unset, no periodic probes).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub interval_ms: Option<u64>,
+    /// Path requested on each origin (default `/`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// Statuses that count as healthy: codes (`200`), ranges (`"200-299"`) or classes (`"2xx"`).
+    /// Empty means any status, i.e. reachability only.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub expect_status: Vec<HealthStatusPattern>,
+    /// Healthy only when the response body contains this text; probes send `GET` instead of `HEAD`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expect_body_contains: Option<String>,
+    /// Healthy only when the JSON body has a non-null value at this dotted path (`data.0.id`);
+    /// probes send `GET` instead of `HEAD`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expect_json_path: Option<String>,
+}
+
+impl HealthCheckConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// The probe period when enabled; `interval_ms = 0` counts as off.
+    pub fn interval(&self) -> Option<std::time::Duration> {
+        self.interval_ms
+            .filter(|ms| *ms > 0)
+            .map(std::time::Duration::from_millis)
+    }
+
+    pub fn probe_path(&self) -> &str {
+        self.path.as_deref().unwrap_or("/")
+    }
+
+    /// Whether the criteria need the response body.
+    pub fn probes_body(&self) -> bool {
+        self.expect_body_contains.is_some() || self.expect_json_path.is_some()
+    }
+
+    pub fn status_matches(&self, status: u16) -> bool {
+        self.expect_status.is_empty()
+            || self
+                .expect_status
+                .iter()
+                .filter_map(HealthStatusPattern::range)
+                .any(|range| range.contains(&status))
+    }
+
+    pub fn validate(&self) -> anyhow::Result<()> {
+        self.validate_at("healthcheck")
+    }
+
+    /// Validates the criteria, naming fields under `field` (`healthcheck` or a provider's table).
+    pub fn validate_at(&self, field: &str) -> anyhow::Result<()> {
+        if let Some(path) = self.path.as_deref()
+            && !path.starts_with('/')
+        {
+            anyhow::bail!("{field}.path `{path}` must start with `/`");
+        }
+        if let Some(pattern) = self
+            .expect_status
+            .iter()
+            .find(|pattern| pattern.range().is_none())
+        {
+            anyhow::bail!(
+                "{field}.expect_status entry `{pattern}` is invalid; expected a status code (200), a range (\"200-299\") or a class (\"2xx\")"
+            );
+        }
+        Ok(())
+    }
+}
+
+/// One `healthcheck.expect_status` entry.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum HealthStatusPattern {
+    Code(u16),
+    Pattern(String),
+}
+
+impl HealthStatusPattern {
+    /// The statuses this entry accepts, or `None` when it is malformed.
+    pub fn range(&self) -> Option<std::ops::RangeInclusive<u16>> {
+        let valid = |code: u16| (100..=599).contains(&code);
+        let range = match self {
+            Self::Code(code) => *code..=*code,
+            Self::Pattern(pattern) => {
+                let pattern = pattern.trim();
+                if let Some(class) = pattern
+                    .strip_suffix("xx")
+                    .or_else(|| pattern.strip_suffix("XX"))
+                {
+                    let class = class.parse::<u16>().ok().filter(|class| *class < 10)?;
+                    class * 100..=class * 100 + 99
+                } else if let Some((start, end)) = pattern.split_once('-') {
+                    start.trim().parse().ok()?..=end.trim().parse().ok()?
+                } else {
+                    let code = pattern.parse().ok()?;
+                    code..=code
+                }
+            }
+        };
+        (valid(*range.start()) && valid(*range.end()) && range.start() <= range.end())
+            .then_some(range)
+    }
+}
+
+impl fmt::Display for HealthStatusPattern {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Code(code) => write!(formatter, "{code}"),
+            Self::Pattern(pattern) => formatter.write_str(pattern),
+        }
+    }
+}
+
+/// Default bound on the graceful shutdown drain before in-flight requests are force-closed.
+pub const DEFAULT_SHUTDOWN_DRAIN_TIMEOUT_MS: u64 = 10_000;
+
+/// Listener behaviour of the proxy process itself.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct ServeConfig {
+    /// How long shutdown waits for in-flight requests before force-closing them
+    /// (default: 10000). `0` force-closes immediately.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shutdown_drain_timeout_ms: Option<u64>,
+}
+
+impl ServeConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn shutdown_drain_timeout(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(
+            self.shutdown_drain_timeout_ms
+                .unwrap_or(DEFAULT_SHUTDOWN_DRAIN_TIMEOUT_MS),
+        )
+    }
+}
+
+/// What the proxy writes to `logs/requests.jsonl`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq)]
+pub struct LoggingConfig {
+    /// Fraction (0.0-1.0) of successful, non-retried requests written to `requests.jsonl`
+    /// (default: unset, all of them). Errors and retried requests are always written.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sample_rate: Option<f64>,
+}
+
+impl LoggingConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// `Some(rate)` only while sampling actually drops entries.
+    pub fn active_sample_rate(&self) -> Option<f64> {
+        self.sample_rate
+            .filter(|rate| rate.is_finite() && *rate < 1.0)
+            .map(|rate| rate.max(0.0))
+    }
+}
+
+/// Optional span export for proxied requests; off unless an endpoint is configured.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct TelemetryConfig {
+    /// OTLP/HTTP collector base URL such as `http://127.0.0.1:4318` (default: unset, no export).
+    ///
+    /// Spans are POSTed as JSON to `<endpoint>/v1/traces`. `CODEX_HELPER_OTLP_ENDPOINT` wins
+    /// over this value.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub otlp_endpoint: Option<String>,
+}
+
+impl TelemetryConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct UiConfig {
     /// UI language: `en`, `zh`, or `auto` (default: unset).
@@ -1321,6 +2049,99 @@ pub struct UiConfig {
     /// Optional remote service status probes shown by operator UIs.
     #[serde(default, skip_serializing_if = "is_default_service_status_config")]
     pub service_status: ServiceStatusConfig,
+    /// Currency used to display locally estimated costs (default: unset = `$`).
+    ///
+    /// Display only: prices stay in the catalog's per-1k/per-1M units and are never converted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currency: Option<CurrencyDisplayConfig>,
+    /// Number formatting locale such as `en-US`, `de-DE`, or `fr-FR` (default: unset = `1234.5`).
+    ///
+    /// Controls digit grouping and the decimal separator for costs and token counts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub number_locale: Option<String>,
+    /// Color theme for the TUI and colored CLI output (default: unset = `auto`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tui_theme: Option<TuiTheme>,
+    /// Desktop GUI compact density: tighter spacing, fewer grid columns, shorter labels
+    /// (default: false).
+    #[serde(default, skip_serializing_if = "bool_is_false")]
+    pub compact: bool,
+    /// Desktop GUI: use compact density while the window is narrower than 1280px (default: false).
+    #[serde(default, skip_serializing_if = "bool_is_false")]
+    pub auto_compact: bool,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TuiTheme {
+    /// Pick `dark` or `light` from the terminal background (`COLORFGBG`), falling back to `dark`.
+    #[default]
+    Auto,
+    Dark,
+    Light,
+    HighContrast,
+}
+
+impl TuiTheme {
+    pub const ALL: [TuiTheme; 4] = [
+        TuiTheme::Auto,
+        TuiTheme::Dark,
+        TuiTheme::Light,
+        TuiTheme::HighContrast,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TuiTheme::Auto => "auto",
+            TuiTheme::Dark => "dark",
+            TuiTheme::Light => "light",
+            TuiTheme::HighContrast => "high_contrast",
+        }
+    }
+
+    /// The theme after this one in the cycle order used by the TUI keybinding.
+    pub fn next(self) -> Self {
+        let idx = Self::ALL
+            .iter()
+            .position(|theme| *theme == self)
+            .unwrap_or(0);
+        Self::ALL[(idx + 1) % Self::ALL.len()]
+    }
+
+    /// Resolve `auto` to a concrete theme; other themes are returned unchanged.
+    pub fn resolve(self) -> Self {
+        match self {
+            TuiTheme::Auto => {
+                let colorfgbg = env::var("COLORFGBG").ok();
+                if terminal_background_is_light(colorfgbg.as_deref()) {
+                    TuiTheme::Light
+                } else {
+                    TuiTheme::Dark
+                }
+            }
+            theme => theme,
+        }
+    }
+}
+
+/// Interpret `COLORFGBG` (`fg;bg` or `fg;default;bg`, as set by rxvt, Konsole and others).
+///
+/// ANSI background 7 (white) and 9..=15 except 8 (bright black) count as light.
+fn terminal_background_is_light(colorfgbg: Option<&str>) -> bool {
+    colorfgbg
+        .and_then(|value| value.rsplit(';').next())
+        .and_then(|bg| bg.trim().parse::<u8>().ok())
+        .is_some_and(|bg| bg == 7 || (9..=15).contains(&bg))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct CurrencyDisplayConfig {
+    /// Symbol prefixed to cost amounts, for example `€` or `¥`.
+    #[serde(default)]
+    pub symbol: String,
+    /// ISO 4217 code such as `EUR`; appended instead when `symbol` is empty.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
 }
 
 /// 获取 codex-helper 的主目录（用于配置、日志等）
@@ -1362,6 +2183,55 @@ pub fn proxy_home_dir() -> PathBuf {
     }
 }
 
+/// Subdirectory of the helper home that holds named `--profile` homes.
+pub const HELPER_PROFILES_DIR: &str = "profiles";
+
+/// Resolve the isolated home of profile `name` nested under `base`: `<base>/profiles/<name>`.
+///
+/// Names are limited to ASCII letters, digits, `-` and `_` so a profile can never escape the
+/// profiles directory.
+pub fn helper_profile_home(base: &Path, name: &str) -> Result<PathBuf> {
+    let name = name.trim();
+    if name.is_empty() {
+        anyhow::bail!("profile name must not be empty");
+    }
+    if name.len() > 64 {
+        anyhow::bail!("profile name '{name}' is longer than 64 characters");
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        anyhow::bail!("profile name '{name}' may only contain ASCII letters, digits, '-' and '_'");
+    }
+    Ok(base.join(HELPER_PROFILES_DIR).join(name))
+}
+
+/// List the profiles that exist under `base`, sorted by name.
+pub fn list_helper_profiles(base: &Path) -> Result<Vec<String>> {
+    let dir = base.join(HELPER_PROFILES_DIR);
+    let entries = match std::fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err).with_context(|| format!("read {}", dir.display())),
+    };
+    let mut names = Vec::new();
+    for entry in entries {
+        let entry = entry.with_context(|| format!("read {}", dir.display()))?;
+        if !entry.file_type().is_ok_and(|kind| kind.is_dir()) {
+            continue;
+        }
+        let Some(name) = entry.file_name().to_str().map(str::to_string) else {
+            continue;
+        };
+        if helper_profile_home(base, &name).is_ok() {
+            names.push(name);
+        }
+    }
+    names.sort();
+    Ok(names)
+}
+
 /// Directory where Codex stores conversation sessions: `~/.codex/sessions` (or `$CODEX_HOME/sessions`).
 pub fn codex_sessions_dir() -> PathBuf {
     codex_home().join("sessions")
@@ -275,6 +275,9 @@ fn current_v5_tag_preferred_stop_excludes_non_matching_fallbacks() {
                 )])],
                 RouteExhaustedAction::Stop,
             )),
+            response_header_allowlist: Vec::new(),
+            response_header_denylist: Vec::new(),
+            maintenance: None,
         },
         claude: ServiceRouteConfig::default(),
         retry: RetryConfig::default(),
@@ -283,6 +286,18 @@ fn current_v5_tag_preferred_stop_excludes_non_matching_fallbacks() {
         relay_targets: std::collections::BTreeMap::new(),
         fleet: Default::default(),
         ui: UiConfig::default(),
+        privacy: Default::default(),
+        effort_guard: Default::default(),
+        quota: Default::default(),
+        dashboard: Default::default(),
+        http: Default::default(),
+        cache: Default::default(),
+        stream: Default::default(),
+        lb: Default::default(),
+        healthcheck: Default::default(),
+        serve: Default::default(),
+        logging: Default::default(),
+        telemetry: Default::default(),
     };
 
     validate_helper_config(&source).expect("validate current config");
@@ -367,6 +382,9 @@ fn current_v5_nested_route_graph_expands_monthly_pool_before_paygo() {
                 ]),
                 ..RouteGraphConfig::default()
             }),
+            response_header_allowlist: Vec::new(),
+            response_header_denylist: Vec::new(),
+            maintenance: None,
         },
         claude: ServiceRouteConfig::default(),
         retry: RetryConfig::default(),
@@ -375,6 +393,18 @@ fn current_v5_nested_route_graph_expands_monthly_pool_before_paygo() {
         relay_targets: std::collections::BTreeMap::new(),
         fleet: Default::default(),
         ui: UiConfig::default(),
+        privacy: Default::default(),
+        effort_guard: Default::default(),
+        quota: Default::default(),
+        dashboard: Default::default(),
+        http: Default::default(),
+        cache: Default::default(),
+        stream: Default::default(),
+        lb: Default::default(),
+        healthcheck: Default::default(),
+        serve: Default::default(),
+        logging: Default::default(),
+        telemetry: Default::default(),
     };
 
     validate_helper_config(&source).expect("validate current config");
@@ -492,6 +522,9 @@ fn current_v5_compiled_route_graph_preserves_endpoint_order_and_model_mapping()
                 "disabled".to_string(),
                 "paygo".to_string(),
             ])),
+            response_header_allowlist: Vec::new(),
+            response_header_denylist: Vec::new(),
+            maintenance: None,
         },
         claude: ServiceRouteConfig::default(),
         retry: RetryConfig::default(),
@@ -500,6 +533,18 @@ fn current_v5_compiled_route_graph_preserves_endpoint_order_and_model_mapping()
         relay_targets: std::collections::BTreeMap::new(),
         fleet: Default::default(),
         ui: UiConfig::default(),
+        privacy: Default::default(),
+        effort_guard: Default::default(),
+        quota: Default::default(),
+        dashboard: Default::default(),
+        http: Default::default(),
+        cache: Default::default(),
+        stream: Default::default(),
+        lb: Default::default(),
+        healthcheck: Default::default(),
+        serve: Default::default(),
+        logging: Default::default(),
+        telemetry: Default::default(),
     };
 
     validate_helper_config(&source).expect("validate current config");
@@ -426,6 +426,11 @@ fn v0_20_3_supported_version_5_fixture_migrates_with_exact_backup() {
     assert_eq!(config.notify.policy.min_duration_ms, 30_000);
     assert_eq!(config.notify.policy.global_cooldown_ms, 60_000);
     assert_eq!(config.notify.policy.merge_window_ms, 10_000);
+    assert_eq!(config.notify.policy.max_merged, 0);
+    assert_eq!(
+        config.notify.policy.strategy,
+        NotifyMergeStrategy::SummarizeCount
+    );
     assert_eq!(config.notify.policy.per_thread_cooldown_ms, 180_000);
     assert_eq!(config.notify.policy.recent_search_window_ms, 300_000);
     assert_eq!(config.notify.policy.recent_endpoint_timeout_ms, 500);
@@ -480,3 +480,46 @@ fn legacy_affinity_route_key_is_unchanged_by_version_6_migration() {
 
     assert_eq!(after, before);
 }
+
+#[test]
+fn ui_tui_theme_parses_snake_case_and_detects_light_backgrounds() {
+    let config: HelperConfig = toml::from_str(
+        r#"
+version = 6
+
+[ui]
+tui_theme = "high_contrast"
+"#,
+    )
+    .expect("parse ui theme");
+    assert_eq!(config.ui.tui_theme, Some(TuiTheme::HighContrast));
+    assert_eq!(TuiTheme::HighContrast.next(), TuiTheme::Auto);
+    assert_eq!(TuiTheme::Light.resolve(), TuiTheme::Light);
+
+    assert!(terminal_background_is_light(Some("0;15")));
+    assert!(terminal_background_is_light(Some("0;default;7")));
+    assert!(!terminal_background_is_light(Some("15;0")));
+    assert!(!terminal_background_is_light(Some("7;8")));
+    assert!(!terminal_background_is_light(None));
+}
+
+#[test]
+fn ui_desktop_density_round_trips_and_stays_out_of_default_configs() {
+    let config: HelperConfig = toml::from_str(
+        r#"
+version = 6
+
+[ui]
+compact = true
+auto_compact = true
+"#,
+    )
+    .expect("parse ui density");
+    assert!(config.ui.compact);
+    assert!(config.ui.auto_compact);
+    let rendered = toml::to_string(&config.ui).expect("serialize ui config");
+    assert!(rendered.contains("compact = true"), "{rendered}");
+
+    let rendered = toml::to_string(&HelperConfig::default().ui).expect("serialize default ui");
+    assert!(!rendered.contains("compact"), "{rendered}");
+}
@@ -883,3 +883,35 @@ env_key = "RIGHTCODE_API_KEY"
         );
     });
 }
+
+#[test]
+fn helper_profile_home_nests_under_base_and_rejects_escaping_names() {
+    let base = Path::new("/tmp/helper-home");
+    assert_eq!(
+        helper_profile_home(base, "work_2").expect("valid profile"),
+        base.join("profiles").join("work_2")
+    );
+    for name in ["", "  ", "../work", "a/b", ".hidden", "a b", "工作"] {
+        assert!(
+            helper_profile_home(base, name).is_err(),
+            "expected '{name}' to be rejected"
+        );
+    }
+}
+
+#[test]
+fn list_helper_profiles_returns_sorted_profile_directories() {
+    let env = setup_temp_codex_home();
+    let base = env.home.join("profile-base");
+    assert!(list_helper_profiles(&base).expect("missing dir").is_empty());
+
+    std::fs::create_dir_all(base.join("profiles").join("work")).expect("create work");
+    std::fs::create_dir_all(base.join("profiles").join("personal")).expect("create personal");
+    std::fs::create_dir_all(base.join("profiles").join("not valid")).expect("create invalid");
+    write_file(&base.join("profiles").join("notes.txt"), "not a profile");
+
+    assert_eq!(
+        list_helper_profiles(&base).expect("list profiles"),
+        vec!["personal".to_string(), "work".to_string()]
+    );
+}
@@ -1,6 +1,5 @@
 use super::*;
 use std::path::Path;
-use std::sync::{Mutex, OnceLock};
 
 mod canonical_schema;
 mod client_patch_contract;
@@ -44,16 +43,10 @@ impl Drop for ScopedEnv {
     }
 }
 
-fn env_lock() -> std::sync::MutexGuard<'static, ()> {
-    static LOCK: OnceLock<Mutex<()>> = OnceLock::new();
-    match LOCK.get_or_init(|| Mutex::new(())).lock() {
-        Ok(g) => g,
-        Err(e) => e.into_inner(),
-    }
-}
+use crate::test_support::env_lock_blocking as env_lock;
 
 struct TestEnv {
-    _lock: std::sync::MutexGuard<'static, ()>,
+    _lock: tokio::sync::MutexGuard<'static, ()>,
     _env: ScopedEnv,
     home: PathBuf,
 }
@@ -81,6 +81,20 @@ fn retry_profile_cost_primary_sets_probe_back_defaults() {
     assert_eq!(resolved.cooldown_backoff_factor, 2);
     assert_eq!(resolved.cooldown_backoff_max_secs, 900);
     assert_eq!(resolved.transport_cooldown_secs, 30);
+    assert_eq!(resolved.probe_back_success_threshold, 1);
+}
+
+#[test]
+fn retry_probe_back_success_threshold_overrides_profile_default() {
+    let cfg: RetryConfig = toml::from_str(
+        r#"
+profile = "cost-primary"
+probe_back_success_threshold = 3
+"#,
+    )
+    .expect("parse retry config");
+
+    assert_eq!(cfg.resolve().probe_back_success_threshold, 3);
 }
 
 #[test]
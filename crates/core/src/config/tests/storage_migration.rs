@@ -1227,6 +1227,31 @@ headers = { Authorization = "Bearer preview-secret", Cookie = "session=preview-c
     assert!(report.contains("<redacted>"));
 }
 
+#[test]
+fn redacted_helper_config_masks_inline_credentials_and_headers() {
+    let mut cfg = HelperConfig::default();
+    cfg.codex.providers.insert(
+        "relay".to_string(),
+        ProviderConfig {
+            base_url: Some("https://relay.example/v1".to_string()),
+            inline_auth: UpstreamAuth {
+                auth_token: Some("sk-print-config-secret".into()),
+                api_key_env: Some("RELAY_API_KEY".to_string()),
+                ..UpstreamAuth::default()
+            },
+            ..ProviderConfig::default()
+        },
+    );
+
+    let value = redacted_helper_config(&cfg).expect("redact config");
+    let text = toml::to_string(&value).expect("render redacted config");
+
+    assert!(!text.contains("sk-print-config-secret"), "{text}");
+    assert!(text.contains("auth_token = \"<redacted>\""), "{text}");
+    assert!(text.contains("RELAY_API_KEY"), "{text}");
+    assert!(text.contains("https://relay.example/v1"), "{text}");
+}
+
 #[tokio::test]
 async fn migration_retains_unknown_nested_fields_after_validating_known_contract() {
     let temp = TempConfigDir::new();
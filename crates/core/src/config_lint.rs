@@ -0,0 +1,87 @@
+use std::collections::BTreeMap;
+
+use crate::config::ServiceRouteConfig;
+
+/// Provider endpoints of one service that point at the same `base_url`.
+///
+/// Two providers on one URL usually means the same relay with different keys. That is valid:
+/// health, cooldowns and usage are keyed by provider endpoint, never by URL. It is also a common
+/// copy-paste mistake, so `config lint` reports it.
+pub fn shared_base_url_warnings(view: &ServiceRouteConfig) -> Vec<String> {
+    let mut by_url = BTreeMap::<String, Vec<String>>::new();
+    for (provider_id, provider) in &view.providers {
+        let default = provider
+            .base_url
+            .as_deref()
+            .map(|base_url| ("default", base_url));
+        let endpoints = provider
+            .endpoints
+            .iter()
+            .map(|(endpoint_id, endpoint)| (endpoint_id.as_str(), endpoint.base_url.as_str()));
+        for (endpoint_id, base_url) in default.into_iter().chain(endpoints) {
+            let base_url = normalize_base_url(base_url);
+            if base_url.is_empty() {
+                continue;
+            }
+            by_url
+                .entry(base_url)
+                .or_default()
+                .push(format!("{provider_id}.{endpoint_id}"));
+        }
+    }
+    by_url
+        .into_iter()
+        .filter(|(_, endpoints)| endpoints.len() > 1)
+        .map(|(base_url, endpoints)| {
+            format!(
+                "{} share base_url {base_url}; they are routed and tracked independently, so make sure this is intended (for example, different keys)",
+                endpoints.join(", ")
+            )
+        })
+        .collect()
+}
+
+/// Every advisory finding for one service: shared base URLs and unusable schedule entries.
+pub fn service_lint_warnings(view: &ServiceRouteConfig) -> Vec<String> {
+    let mut warnings = shared_base_url_warnings(view);
+    warnings.extend(crate::provider_schedule::service_schedule_warnings(view));
+    warnings
+}
+
+fn normalize_base_url(base_url: &str) -> String {
+    base_url.trim().trim_end_matches('/').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ProviderConfig;
+
+    fn provider(base_url: &str) -> ProviderConfig {
+        ProviderConfig {
+            base_url: Some(base_url.to_string()),
+            ..ProviderConfig::default()
+        }
+    }
+
+    #[test]
+    fn shared_base_url_lists_every_endpoint_on_the_url() {
+        let mut view = ServiceRouteConfig::default();
+        view.providers
+            .insert("work".to_string(), provider("https://relay.example/v1"));
+        view.providers.insert(
+            "personal".to_string(),
+            provider("https://relay.example/v1/"),
+        );
+        view.providers
+            .insert("other".to_string(), provider("https://other.example/v1"));
+
+        assert_eq!(
+            shared_base_url_warnings(&view),
+            vec![
+                "personal.default, work.default share base_url https://relay.example/v1; they are routed and tracked independently, so make sure this is intended (for example, different keys)"
+                    .to_string()
+            ]
+        );
+    }
+}
@@ -33,6 +33,20 @@ pub struct ResolvedRetryConfig {
     pub transport_cooldown_secs: u64,
     pub cooldown_backoff_factor: u64,
     pub cooldown_backoff_max_secs: u64,
+    #[serde(default = "default_probe_back_success_threshold")]
+    pub probe_back_success_threshold: u32,
+    #[serde(default = "default_connection_reset_fast_retries")]
+    pub connection_reset_fast_retries: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_upstreams_tried: Option<u32>,
+}
+
+fn default_probe_back_success_threshold() -> u32 {
+    1
+}
+
+fn default_connection_reset_fast_retries() -> u32 {
+    1
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -83,6 +97,18 @@ pub struct RetryConfig {
     pub cooldown_backoff_factor: Option<u64>,
     #[serde(default)]
     pub cooldown_backoff_max_secs: Option<u64>,
+    /// Consecutive successful probes a cooled-down upstream must return before it rejoins
+    /// normal selection. 1 keeps the historical behavior of un-cooling on the first success.
+    #[serde(default)]
+    pub probe_back_success_threshold: Option<u32>,
+    /// Immediate same-upstream retries for a connection reset before response headers arrive.
+    /// They skip backoff and cooldown and do not count against `upstream.max_attempts`.
+    #[serde(default)]
+    pub connection_reset_fast_retries: Option<u32>,
+    /// Distinct upstreams one request may try before giving up; each still gets up to
+    /// `upstream.max_attempts`. Unset tries every eligible upstream.
+    #[serde(default)]
+    pub max_upstreams_tried: Option<u32>,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
@@ -185,6 +211,9 @@ impl Default for RetryConfig {
             transport_cooldown_secs: None,
             cooldown_backoff_factor: None,
             cooldown_backoff_max_secs: None,
+            probe_back_success_threshold: None,
+            connection_reset_fast_retries: None,
+            max_upstreams_tried: None,
         }
     }
 }
@@ -231,6 +260,9 @@ impl RetryProfileName {
                 transport_cooldown_secs: 30,
                 cooldown_backoff_factor: 1,
                 cooldown_backoff_max_secs: 600,
+                probe_back_success_threshold: 1,
+                connection_reset_fast_retries: 1,
+                max_upstreams_tried: None,
             },
             RetryProfileName::SameUpstream => ResolvedRetryConfig {
                 upstream: ResolvedRetryLayerConfig {
@@ -439,6 +471,15 @@ impl RetryConfig {
         if let Some(v) = self.cooldown_backoff_max_secs {
             out.cooldown_backoff_max_secs = v;
         }
+        if let Some(v) = self.probe_back_success_threshold {
+            out.probe_back_success_threshold = v;
+        }
+        if let Some(v) = self.connection_reset_fast_retries {
+            out.connection_reset_fast_retries = v;
+        }
+        if let Some(v) = self.max_upstreams_tried {
+            out.max_upstreams_tried = Some(v);
+        }
 
         out
     }
@@ -262,6 +262,16 @@ version = 6
 # supported_models = { "gpt-5.5" = true }
 # model_mapping = { "gpt-5.5" = "openai/gpt-5.5", "gpt-*" = "openai/gpt-*" }
 #
+# 需要客户端证书（mTLS）或私有 CA 的 provider 可配置 tls；文件在启动/reload 时读取，
+# 相对路径相对于 ~/.codex-helper；`codex-helper doctor` 会检查证书链和过期时间：
+# tls.client_cert_path = "/etc/codex-helper/relay-client.pem"
+# tls.client_key_path = "/etc/codex-helper/relay-client-key.pem"
+# tls.ca_bundle_path = "/etc/codex-helper/relay-ca.pem"
+#
+# provider 配置了自己的凭据时，客户端的 Authorization / x-api-key 一律不转发；
+# 还想丢弃其他客户端请求头时可列出（名称不区分大小写，requests.jsonl 记录被丢弃的头名）：
+# strip_request_headers = ["x-client-secret"]
+#
 # [codex.routing]
 # entry = "main"
 # affinity_policy = "fallback-sticky"
@@ -270,6 +280,20 @@ version = 6
 # 如果你想每次都优先回到最高优先级 provider，可以显式改为 "preferred-group"。
 # fallback_ttl_ms = 120000
 # reprobe_preferred_after_ms = 30000
+# 让新 session 跟随运行时健康状态，自动指向最高优先级的健康 provider（有滞后，避免来回切换）；
+# 显式 `routing pin` 或运维端设置的新会话首选优先。
+# auto_active_by_health = true
+# 在最优优先级组内，按 pricing_overrides.toml 中以 provider id 记录的模型价格，优先尝试最便宜的 provider；
+# 没有价格的 provider 排在最后。
+# prefer_cheapest = true
+# 故障转移承接的成功请求占比超过阈值（百分比）时记录告警并发送系统通知（需开启 notify.system）。
+# fallback_alert_threshold_pct = 30
+# fallback_alert_window_secs = 600
+# 失败切到备用 provider 后，在这段时间内不再切回原 provider（仍有其他可用候选时），减少主备来回切换。
+# primary_sticky_ms = 300000
+# 运行中的 proxy 定期拉取各 provider 的 /v1/models 并缓存到 upstream_models_<service>.json；
+# `config lint` 会据此报告 provider 不提供的 model_mapping 目标、supported_models 和 profile/route 模型。
+# validate_models_online = true
 #
 # [codex.routing.routes.main]
 # strategy = "ordered-failover"
@@ -347,6 +371,10 @@ min_duration_ms = 60000
 
 # A：合并 + 限流（毫秒）
 merge_window_ms = 10000
+# 单条通知最多合并的事件数；排队达到该数时不等合并窗口结束立即发送，多出的留到下一次。0 表示不限。
+max_merged = 0
+# 合并后的展示方式："summarize-count"（标题显示条数，正文列出最近 3 条）或 "latest-only"（只显示最新一条）
+strategy = "summarize-count"
 global_cooldown_ms = 60000
 per_thread_cooldown_ms = 180000
 
@@ -357,9 +385,137 @@ recent_search_window_ms = 300000
 recent_endpoint_timeout_ms = 500
 
 [notify.exec]
-# 可选回调：执行一个命令，并把聚合后的 JSON 写到 stdin。
+# 可选回调：执行命令，并把通知 JSON（agent-turn-complete 为合并后的 JSON）写到 stdin。
+# 关键字段同时以环境变量提供：CODEX_NOTIFY_TYPE、CODEX_NOTIFY_COUNT、CODEX_NOTIFY_THREAD_ID、
+# CODEX_NOTIFY_TURN_ID、CODEX_NOTIFY_CWD、CODEX_NOTIFY_DURATION_MS。
+# 与 system toast 相互独立，`notify codex --no-toast` 时仍会执行；非零退出/超时会写入 logs/runtime.log。
 enabled = false
 # command = ["python", "my_hook.py"]
+# types 为空时只响应 "agent-turn-complete"；"*" 表示所有类型。
+# types = ["agent-turn-complete"]
+# 单个回调的超时（毫秒），超时后会被强制结束。
+timeout_ms = 10000
+#
+# 额外回调（可多个）：
+# [[notify.exec.callbacks]]
+# command = ["sh", "-c", "notify-send \"Codex\" \"$CODEX_NOTIFY_TYPE\""]
+# types = ["*"]
+# timeout_ms = 5000
+
+# ---
+#
+# --- 请求 / token 配额（可选） ---
+#
+# 按 provider 限制滑动窗口内的请求数与上游输出 token 数；超出时换用其他 provider，
+# 全部超限时返回 429 并带 Retry-After，不转发。
+#
+# [quota]
+# window_secs = 3600
+# max_requests = 500
+# max_output_tokens = 100000
+# # 按 x-codex-helper-tenant 头分别计算配额
+# per_tenant = false
+
+# ---
+#
+# --- 自适应并发（可选） ---
+#
+# 只作用于设置了 limits.max_concurrent_requests 的 provider：上游返回 429/5xx 时进程内上限减半，
+# 连续成功后逐个恢复，最多回到配置的上限；min_concurrent_requests 为退避下限，默认 1。
+#
+# [lb.adaptive_concurrency]
+# enabled = true
+# min_concurrent_requests = 1
+
+# ---
+#
+# --- 周期性健康探测（可选） ---
+#
+# 按固定间隔向每个可路由的 provider endpoint 发送一次 HEAD（携带该 provider 的凭据），记录可达性变化；
+# 不可达的 endpoint 指数退避（最多 32 个周期）。
+# 跳过已禁用的 endpoint；探测失败会让 endpoint 进入 cooldown（routing 随之避开），恢复后关闭 breaker。
+#
+# 可选：path 指定探测路径；expect_status 限定健康状态码；expect_body_contains / expect_json_path
+# 要求响应体匹配（改用 GET）。不满足时记为不可达并记录原因。
+# 单个 provider 可用 [codex.providers.<name>.healthcheck] 替换这些条件（interval_ms 仍为全局）。
+#
+# [healthcheck]
+# interval_ms = 300000
+# expect_status = ["2xx"]
+
+# ---
+#
+# --- 停止时的排空超时（可选） ---
+#
+# 收到停止信号后，最多等待这么久让进行中的请求完成；超时后强制关闭剩余连接（默认 10000，0 表示立即强制关闭）。
+#
+# [serve]
+# shutdown_drain_timeout_ms = 10000
+
+# ---
+#
+# --- 运维面板（可选） ---
+#
+# 内存中保留的最近完成请求条数（TUI / GUI Requests 页可见的上限），1-10000；
+# 未设置时沿用 CODEX_HELPER_RECENT_FINISHED_MAX，默认 1000。重载配置后立即生效。
+#
+# [dashboard]
+# max_recent = 2000
+
+# ---
+#
+# --- 上游 HTTP（可选） ---
+#
+# 默认透传客户端自带的 User-Agent，客户端没有发送时使用 "codex-helper/<版本>"。
+# 设置 user_agent 后替换客户端的值；单个 provider 可用 user_agent 覆盖。
+# address_family 限制连接上游时使用的地址族："any"（默认）、"v4" 或 "v6"；
+# 单个 provider 可用 address_family 覆盖。
+#
+# [http]
+# user_agent = "codex-helper"
+# address_family = "any"
+
+# ---
+#
+# --- 响应缓存（可选） ---
+#
+# Codex 会周期性请求 /v1/models；设置 models_ttl_ms 后，成功的 GET models 响应会在内存中缓存这么久，
+# 命中时带 X-Codex-Helper-Cache: hit 响应头。默认 0 表示关闭，最大 3600000（1 小时），配置重载时清空。
+#
+# [cache]
+# models_ttl_ms = 30000
+
+# ---
+#
+# --- 缓冲流式响应（可选） ---
+#
+# 客户端无法处理 SSE 时开启：代理读完整个上游流后返回一个非流式 JSON（Responses 最终对象 /
+# Anthropic message / chat.completion），上限为 CODEX_HELPER_UPSTREAM_RESPONSE_BODY_MAX_BYTES。默认关闭。
+# 单个 provider 可用 buffer_responses 覆盖。
+#
+# [stream]
+# buffer_responses = true
+
+# ---
+#
+# --- 请求日志采样（可选） ---
+#
+# 繁忙时 logs/requests.jsonl 增长很快。sample_rate（0.0~1.0）只按比例记录成功且未重试的请求，
+# 错误和发生过重试的请求始终记录；被采样的行带 log_sample_rate 字段。usage / 统计页面读取 runtime store，不受影响。
+#
+# [logging]
+# sample_rate = 0.1
+
+# ---
+#
+# --- OpenTelemetry 导出（可选） ---
+#
+# 设置 otlp_endpoint 后，每个代理请求会以 OTLP/HTTP JSON 发送一个 span（含每次上游尝试的子 span）
+# 到 <otlp_endpoint>/v1/traces。默认关闭；导出在后台进行，失败只写 debug 日志，不影响 requests.jsonl 和 TUI。
+# 环境变量 CODEX_HELPER_OTLP_ENDPOINT 优先于此配置。
+#
+# [telemetry]
+# otlp_endpoint = "http://127.0.0.1:4318"
 
 # ---
 #
@@ -388,6 +544,7 @@ profile = "balanced"
 # max_attempts = 2
 # strategy = "same_upstream"
 # backoff_ms = 200
+# 同一 upstream 重试前会优先等待上游 Retry-After（秒或 HTTP-date），但不超过 backoff_max_ms。
 # backoff_max_ms = 2000
 # jitter_ms = 100
 # on_status = "429,500-502,504-528,530-599"
@@ -445,6 +602,19 @@ profile = "balanced"
 # factor=1 表示关闭退避（默认行为）。
 # cooldown_backoff_factor = 2
 # cooldown_backoff_max_secs = 600
+
+# 可选：回切前要求主线路连续探测成功的次数。
+# 1 表示 cooldown 到期即回切（默认行为）；更大的值可以避免在刚恢复的不稳定线路上来回抖动。
+# probe_back_success_threshold = 3
+
+# 可选：响应头返回前连接被对端 reset 时，立即在同一 upstream 上重试的次数。
+# 不退避、不进入 cooldown，也不占用 upstream.max_attempts；用尽后才走正常的重试/failover 计划。
+# 0 表示关闭；默认 1，最大 3。
+# connection_reset_fast_retries = 1
+
+# 可选：单个请求最多触达的不同 upstream 数量（1..=32）；默认不限制。
+# 每个 upstream 先用完 [retry.upstream].max_attempts 再换下一个。
+# max_upstreams_tried = 3
 "#;
 
 fn toml_schema_version(value: &TomlValue, source_name: &str) -> Result<Option<u64>> {
@@ -2133,6 +2303,14 @@ fn migration_root_unknown_fields(value: &TomlValue, notices: &mut Vec<String>) {
         "relay_targets",
         "fleet",
         "ui",
+        "privacy",
+        "effort_guard",
+        "quota",
+        "dashboard",
+        "http",
+        "cache",
+        "logging",
+        "telemetry",
     ];
     if let Some(table) = value.as_table() {
         let unknown = table
@@ -2166,9 +2344,20 @@ fn migration_service_unknown_fields(value: &TomlValue, notices: &mut Vec<String>
                 "profiles",
                 "providers",
                 "routing",
+                "response_header_allowlist",
+                "response_header_denylist",
+                "maintenance",
             ]
         } else {
-            vec!["default_profile", "profiles", "providers", "routing"]
+            vec![
+                "default_profile",
+                "profiles",
+                "providers",
+                "routing",
+                "response_header_allowlist",
+                "response_header_denylist",
+                "maintenance",
+            ]
         };
         let unknown = service
             .keys()
@@ -2200,6 +2389,14 @@ fn redact_migration_preview(rendered: &str) -> String {
     format!("{CONFIG_TOML_DOC_HEADER}\n{body}")
 }
 
+/// The configuration as a TOML value with inline credentials and header values replaced by
+/// `<redacted>`, for pre-flight output such as `serve --print-config`.
+pub fn redacted_helper_config(cfg: &HelperConfig) -> Result<TomlValue> {
+    let mut value = TomlValue::try_from(cfg).context("serialize effective configuration")?;
+    redact_toml_secret_values(&mut value);
+    Ok(value)
+}
+
 fn redact_toml_secret_values(value: &mut TomlValue) {
     match value {
         TomlValue::Table(table) => {
@@ -33,6 +33,8 @@ use crate::proxy::{
     OperatorSessionBindingMutationResponse, ProviderBalanceRefreshResponse,
 };
 use crate::request_chain::{RequestChainExport, RequestChainSelector};
+use crate::routing_explain::RoutingExplainResponse;
+use crate::routing_ir::RouteRequestContext;
 use crate::service_target::{
     LocalCredentialRefreshRequest, LocalCredentialRefreshResponse, LocalServiceRuntimeReadRequest,
     LocalServiceRuntimeReadResponse,
@@ -238,6 +240,16 @@ impl ControlPlaneClient {
         self.fetch_json(&request_chain_path(selector, limit)).await
     }
 
+    /// Runs the proxy's routing decision for a hypothetical request without sending it upstream.
+    pub async fn routing_explain(
+        &self,
+        request: &RouteRequestContext,
+        session_id: Option<&str>,
+    ) -> Result<RoutingExplainResponse> {
+        self.fetch_json(&routing_explain_path(request, session_id))
+            .await
+    }
+
     fn admin_token(&self) -> Option<&HeaderValue> {
         self.admin_token.as_ref()
     }
@@ -802,6 +814,30 @@ fn request_chain_path(selector: RequestChainSelector, limit: usize) -> String {
     }
 }
 
+fn routing_explain_path(request: &RouteRequestContext, session_id: Option<&str>) -> String {
+    let mut url =
+        Url::parse("http://localhost/__codex_helper/api/v1/operator/routing-explain").expect("url");
+    {
+        let mut pairs = url.query_pairs_mut();
+        for (name, value) in [
+            ("model", request.model.as_deref()),
+            ("service_tier", request.service_tier.as_deref()),
+            ("reasoning_effort", request.reasoning_effort.as_deref()),
+            ("path", request.path.as_deref()),
+            ("method", request.method.as_deref()),
+            ("session", session_id),
+        ] {
+            if let Some(value) = value {
+                pairs.append_pair(name, value);
+            }
+        }
+    }
+    match url.query() {
+        Some(query) if !query.is_empty() => format!("{}?{query}", url.path()),
+        _ => url.path().to_string(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::ffi::OsString;
@@ -940,6 +976,7 @@ mod tests {
                 quota_analytics: Default::default(),
                 stats_5m: Default::default(),
                 stats_1h: Default::default(),
+                request_quotas: Vec::new(),
                 pricing_catalog: Default::default(),
                 service_status: None,
                 provider_balances: Vec::new(),
@@ -1701,4 +1738,23 @@ mod tests {
             "/__codex_helper/api/v1/request-ledger/chain?limit=20&trace_id=trace%2Fwith+space&request_id=42&session=session+a"
         );
     }
+
+    #[test]
+    fn routing_explain_path_encodes_only_present_request_fields() {
+        let bare = routing_explain_path(&RouteRequestContext::default(), None);
+        assert_eq!(bare, "/__codex_helper/api/v1/operator/routing-explain");
+
+        let path = routing_explain_path(
+            &RouteRequestContext {
+                model: Some("gpt-5".to_string()),
+                reasoning_effort: Some("high".to_string()),
+                ..RouteRequestContext::default()
+            },
+            Some("session a"),
+        );
+        assert_eq!(
+            path,
+            "/__codex_helper/api/v1/operator/routing-explain?model=gpt-5&reasoning_effort=high&session=session+a"
+        );
+    }
 }
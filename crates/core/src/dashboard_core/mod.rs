@@ -18,10 +18,13 @@ pub use operator_summary::{
     OperatorRevisionBundle, OperatorRouteAttemptSummary, OperatorRouteCandidateSummary,
     OperatorRouteTargetSummary, OperatorRoutingControlView, OperatorRoutingSummary,
     OperatorRuntimeSummary, OperatorSessionRouteAffinitySummary, OperatorSessionSummary,
-    OperatorSummaryCounts, build_operator_routing_summary, build_operator_session_stats,
-    operator_session_key, redact_operator_pricing_catalog, redact_operator_quota_analytics,
-    redact_operator_usage_day, redact_operator_usage_summaries,
+    OperatorSummaryCounts, OperatorUpstreamPinSummary, build_operator_routing_summary,
+    build_operator_session_stats, operator_session_key, redact_operator_pricing_catalog,
+    redact_operator_quota_analytics, redact_operator_usage_day, redact_operator_usage_summaries,
     summarize_recent_retry_observations,
 };
-pub use types::{ControlProfileOption, ProviderCapacity, ProviderEndpointOption, ProviderOption};
-pub use window_stats::WindowStats;
+pub use types::{
+    ControlProfileOption, ProviderCapacity, ProviderEndpointOption, ProviderOption,
+    ProviderScheduleStatus,
+};
+pub use window_stats::{LatencyHistogram, WindowStats};
@@ -4,11 +4,14 @@ use crate::config::{ServiceControlProfile, ServiceRouteConfig};
 use crate::credentials::{
     CredentialAggregateReadiness, CredentialReadinessCode, CredentialReadinessDetail,
 };
+use crate::provider_schedule::ProviderSchedule;
 use crate::routing_ir::{RoutePlanRuntimeState, RoutePlanTemplate};
 use crate::runtime_identity::ProviderEndpointKey;
 use crate::state::RuntimeConfigState;
 
-use super::types::{ControlProfileOption, ProviderEndpointOption, ProviderOption};
+use super::types::{
+    ControlProfileOption, ProviderEndpointOption, ProviderOption, ProviderScheduleStatus,
+};
 
 pub fn build_profile_options_from_route_view(
     view: &ServiceRouteConfig,
@@ -100,6 +103,13 @@ pub fn build_provider_options_from_route_runtime(
                 credential_readiness: None,
                 endpoints,
                 capacity: Default::default(),
+                schedule: ProviderSchedule::from_provider(provider).map(|schedule| {
+                    ProviderScheduleStatus {
+                        windows: provider.enabled_schedule.clone(),
+                        timezone: provider.schedule_timezone.clone(),
+                        active: schedule.is_active_now(),
+                    }
+                }),
             }
         })
         .collect::<Vec<_>>();
@@ -172,6 +182,7 @@ pub fn build_provider_options_from_route_runtime(
         };
         endpoint.runtime_state_override = (endpoint.runtime_state != RuntimeConfigState::Normal)
             .then_some(endpoint.runtime_state);
+        endpoint.primary_sticky_remaining_secs = candidate_runtime.primary_sticky_remaining_secs;
     }
 
     for provider in &mut providers {
@@ -254,6 +265,7 @@ fn build_route_provider_endpoint_option(
         runtime_state_override: None,
         capacity: Default::default(),
         policy_actions: Vec::new(),
+        primary_sticky_remaining_secs: None,
     }
 }
 
@@ -20,7 +20,7 @@ use crate::logging::{RouteAttemptLog, upstream_origin};
 use crate::pricing::{CostBreakdown, ModelPriceCatalogSnapshot};
 use crate::quota_analytics::QuotaAnalyticsView;
 use crate::request_ledger::{RequestUsageSummary, RequestUsageSummaryGroup};
-use crate::routing_ir::{RouteCandidate, RoutePlanTemplate};
+use crate::routing_ir::{RouteCandidate, RoutePlanTemplate, UpstreamPin};
 use crate::runtime_identity::ProviderEndpointKey;
 use crate::state::{
     ActiveRequest, FinishedRequest, ResolvedRouteValue, RouteDecisionProvenance,
@@ -31,6 +31,7 @@ use crate::usage::UsageMetrics;
 
 use super::types::{
     ControlProfileOption, ProviderCapacity, ProviderEndpointOption, ProviderOption,
+    ProviderScheduleStatus,
 };
 use super::window_stats::WindowStats;
 
@@ -130,6 +131,9 @@ pub struct OperatorReadData {
     pub stats_5m: WindowStats,
     #[serde(default)]
     pub stats_1h: WindowStats,
+    /// Consumption of each live `quota` budget; empty when no quota is configured.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub request_quotas: Vec<crate::state::RequestQuotaUsage>,
     pub pricing_catalog: ModelPriceCatalogSnapshot,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub service_status: Option<crate::service_status::ServiceStatusSnapshot>,
@@ -148,28 +152,72 @@ pub struct OperatorRoutingSummary {
     pub entry_target: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub new_session_preference: Option<OperatorRouteTargetSummary>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub auto_active_by_health: bool,
+    /// Health-selected new-session target; only set while no manual-sticky pin applies.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_active: Option<OperatorRouteTargetSummary>,
     pub affinity_policy: RouteAffinityPolicy,
     pub scheduling_preset: SchedulingPreset,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub fallback_ttl_ms: Option<u64>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub reprobe_preferred_after_ms: Option<u64>,
+    /// Service-wide operator pin that bypasses load balancing.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upstream_pin: Option<OperatorUpstreamPinSummary>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub session_upstream_pins: Vec<OperatorUpstreamPinSummary>,
     #[serde(default)]
     pub candidates: Vec<OperatorRouteCandidateSummary>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct OperatorUpstreamPinSummary {
+    pub provider_id: String,
+    pub endpoint_id: String,
+    pub failover: bool,
+    /// Operator session key; absent for the service-wide pin.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_key: Option<String>,
+}
+
+impl OperatorUpstreamPinSummary {
+    fn new(pin: &UpstreamPin, session_id: Option<&str>) -> Self {
+        Self {
+            provider_id: pin.target.provider_id.clone(),
+            endpoint_id: pin.target.endpoint_id.clone(),
+            failover: pin.failover,
+            session_key: session_id.map(operator_session_key),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct OperatorRouteTargetSummary {
     pub provider_id: String,
     pub endpoint_id: String,
 }
 
+impl From<&ProviderEndpointKey> for OperatorRouteTargetSummary {
+    fn from(target: &ProviderEndpointKey) -> Self {
+        Self {
+            provider_id: target.provider_id.clone(),
+            endpoint_id: target.endpoint_id.clone(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct OperatorRoutingControlView<'a> {
     pub route_graph_key: &'a str,
     pub control_revision: u64,
     pub provider_policy_revision: u64,
     pub new_session_preference: Option<&'a ProviderEndpointKey>,
+    pub auto_active: Option<&'a ProviderEndpointKey>,
+    pub upstream_pin: Option<&'a UpstreamPin>,
+    /// `(raw session id, pin)` pairs.
+    pub session_upstream_pins: &'a [(&'a str, &'a UpstreamPin)],
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -214,16 +262,28 @@ pub fn build_operator_routing_summary(
         entry: route_template.entry.clone(),
         entry_strategy,
         entry_target,
-        new_session_preference: control.new_session_preference.map(|target| {
-            OperatorRouteTargetSummary {
-                provider_id: target.provider_id.clone(),
-                endpoint_id: target.endpoint_id.clone(),
-            }
-        }),
+        new_session_preference: control
+            .new_session_preference
+            .map(OperatorRouteTargetSummary::from),
+        auto_active_by_health: route_template.auto_active_by_health,
+        auto_active: control
+            .auto_active
+            .filter(|_| {
+                route_template.auto_active_by_health && !route_template.entry_is_manual_sticky()
+            })
+            .map(OperatorRouteTargetSummary::from),
         affinity_policy: route_template.affinity_policy,
         scheduling_preset: route_template.scheduling_preset,
         fallback_ttl_ms: route_template.fallback_ttl_ms,
         reprobe_preferred_after_ms: route_template.reprobe_preferred_after_ms,
+        upstream_pin: control
+            .upstream_pin
+            .map(|pin| OperatorUpstreamPinSummary::new(pin, None)),
+        session_upstream_pins: control
+            .session_upstream_pins
+            .iter()
+            .map(|(session_id, pin)| OperatorUpstreamPinSummary::new(pin, Some(session_id)))
+            .collect(),
         candidates: route_template
             .candidates
             .iter()
@@ -684,6 +744,8 @@ pub struct OperatorProviderEndpointSummary {
     pub capacity: OperatorProviderCapacity,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub policy_actions: Vec<OperatorPolicyActionSummary>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub primary_sticky_remaining_secs: Option<u64>,
 }
 
 impl From<&ProviderEndpointOption> for OperatorProviderEndpointSummary {
@@ -712,6 +774,7 @@ impl From<&ProviderEndpointOption> for OperatorProviderEndpointSummary {
                     cooldown_remaining_secs: action.cooldown_remaining_secs,
                 })
                 .collect(),
+            primary_sticky_remaining_secs: endpoint.primary_sticky_remaining_secs,
         }
     }
 }
@@ -730,6 +793,8 @@ pub struct OperatorProviderSummary {
     pub endpoints: Vec<OperatorProviderEndpointSummary>,
     #[serde(default, skip_serializing_if = "OperatorProviderCapacity::is_empty")]
     pub capacity: OperatorProviderCapacity,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schedule: Option<ProviderScheduleStatus>,
 }
 
 impl From<&ProviderOption> for OperatorProviderSummary {
@@ -743,6 +808,7 @@ impl From<&ProviderOption> for OperatorProviderSummary {
             credential_readiness: provider.credential_readiness,
             endpoints: provider.endpoints.iter().map(Into::into).collect(),
             capacity: OperatorProviderCapacity::from(&provider.capacity),
+            schedule: provider.schedule.clone(),
         }
     }
 }
@@ -881,6 +947,12 @@ pub struct OperatorRequestSummary {
     pub trace_key: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub session_key: Option<String>,
+    /// Hex SHA-256 of the client request body.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body_hash: Option<String>,
+    /// The provider's id for the final upstream response, for support tickets.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upstream_request_id: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub model: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -1030,6 +1102,8 @@ impl OperatorRequestSummary {
             id: request.id,
             trace_key: request.trace_id.as_deref().map(operator_request_trace_key),
             session_key: request.session_id.as_deref().map(operator_session_key),
+            body_hash: request.body_hash.clone(),
+            upstream_request_id: request.upstream_request_id.clone(),
             model: request.model.clone(),
             reasoning_effort: request.reasoning_effort.clone(),
             service_tier: request.service_tier.clone(),
@@ -1266,6 +1340,7 @@ fn operator_route_attempt_code(attempt: &RouteAttemptLog) -> String {
         | "failed_client_request"
         | "failed_reasoning_guard"
         | "failed_transport"
+        | "failed_tls_certificate"
         | "failed_target_build"
         | "failed_body_read"
         | "failed_body_too_large"
@@ -1446,6 +1521,8 @@ pub struct OperatorRetryPolicySummary {
     pub never_on_status: String,
     #[serde(default)]
     pub never_on_class: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_upstreams_tried: Option<u32>,
     pub cloudflare_challenge_cooldown_secs: u64,
     pub cloudflare_timeout_cooldown_secs: u64,
     pub transport_cooldown_secs: u64,
@@ -1461,6 +1538,7 @@ impl From<&ResolvedRetryConfig> for OperatorRetryPolicySummary {
             provider: (&retry.route).into(),
             never_on_status: retry.never_on_status.clone(),
             never_on_class: retry.never_on_class.clone(),
+            max_upstreams_tried: retry.max_upstreams_tried,
             cloudflare_challenge_cooldown_secs: retry.cloudflare_challenge_cooldown_secs,
             cloudflare_timeout_cooldown_secs: retry.cloudflare_timeout_cooldown_secs,
             transport_cooldown_secs: retry.transport_cooldown_secs,
@@ -1503,6 +1581,9 @@ pub struct OperatorSummaryCounts {
     pub active_requests: usize,
     #[serde(default)]
     pub recent_requests: usize,
+    /// Size of the runtime's recent-request buffer (`dashboard.max_recent`); 0 when unknown.
+    #[serde(default)]
+    pub recent_requests_capacity: usize,
     #[serde(default)]
     pub sessions: usize,
     #[serde(default)]
@@ -1668,6 +1749,7 @@ mod tests {
                 quota_analytics: Default::default(),
                 stats_5m: Default::default(),
                 stats_1h: Default::default(),
+                request_quotas: Vec::new(),
                 pricing_catalog: Default::default(),
                 service_status: None,
                 provider_balances: Vec::new(),
@@ -56,6 +56,9 @@ pub struct ProviderEndpointOption {
     pub capacity: ProviderCapacity,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub policy_actions: Vec<PolicyActionProjection>,
+    /// Seconds left on the `routing.primary_sticky_ms` hold after a failover away from it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub primary_sticky_remaining_secs: Option<u64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -75,6 +78,17 @@ pub struct ProviderOption {
     pub endpoints: Vec<ProviderEndpointOption>,
     #[serde(default, skip_serializing_if = "ProviderCapacity::is_empty")]
     pub capacity: ProviderCapacity,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schedule: Option<ProviderScheduleStatus>,
+}
+
+/// Configured `enabled_schedule` of a provider and whether one of its windows is open now.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ProviderScheduleStatus {
+    pub windows: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+    pub active: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
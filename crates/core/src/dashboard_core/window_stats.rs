@@ -18,6 +18,13 @@ pub struct WindowStats {
     pub retry_rate: Option<f64>,
     pub top_provider: Option<(String, usize)>,
     pub top_provider_endpoint: Option<(String, usize)>,
+    /// Successful requests served by a fallback: after failing over to another provider, or by
+    /// a lower preference group or lower-ranked provider because the primary was skipped.
+    #[serde(default)]
+    pub fallback_2xx: usize,
+    /// `fallback_2xx / ok_2xx`; `None` until the window has a successful request.
+    #[serde(default)]
+    pub fallback_share: Option<f64>,
 }
 
 pub fn compute_window_stats<F>(
@@ -74,6 +81,9 @@ where
         if is_logical_request_success_status(r.status_code) {
             out.ok_2xx += 1;
             ok_lat.push(r.duration_ms);
+            if served_by_fallback(r) {
+                out.fallback_2xx += 1;
+            }
 
             if let Some(pid) = r.provider_id.as_deref()
                 && !pid.trim().is_empty()
@@ -94,12 +104,79 @@ where
         out.avg_attempts = Some(attempts_sum as f64 / out.total as f64);
         out.retry_rate = Some(retry_cnt as f64 / out.total as f64);
     }
+    if out.ok_2xx > 0 {
+        out.fallback_share = Some(out.fallback_2xx as f64 / out.ok_2xx as f64);
+    }
 
     out.top_provider = by_provider.into_iter().max_by_key(|(_, v)| *v);
     out.top_provider_endpoint = by_provider_endpoint.into_iter().max_by_key(|(_, v)| *v);
     out
 }
 
+/// Upper bounds (exclusive) of the latency histogram buckets; the last bucket is open-ended.
+pub const LATENCY_BUCKET_UPPER_MS: [u64; 6] = [200, 500, 1_000, 2_000, 5_000, 10_000];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LatencyHistogram {
+    pub counts: [usize; LATENCY_BUCKET_UPPER_MS.len() + 1],
+    pub total: usize,
+}
+
+impl LatencyHistogram {
+    pub fn from_durations(durations: impl IntoIterator<Item = u64>) -> Self {
+        let mut out = Self::default();
+        for duration_ms in durations {
+            out.record(duration_ms);
+        }
+        out
+    }
+
+    pub fn record(&mut self, duration_ms: u64) {
+        let idx = LATENCY_BUCKET_UPPER_MS
+            .iter()
+            .position(|upper| duration_ms < *upper)
+            .unwrap_or(LATENCY_BUCKET_UPPER_MS.len());
+        self.counts[idx] += 1;
+        self.total += 1;
+    }
+
+    /// Share of requests in `idx`, in `0.0..=1.0`; `None` for an empty histogram.
+    pub fn share(&self, idx: usize) -> Option<f64> {
+        (self.total > 0).then(|| self.counts[idx] as f64 / self.total as f64)
+    }
+
+    /// Short label such as `<500ms`, `<2s` or `10s+`.
+    pub fn bucket_label(idx: usize) -> String {
+        fn ms_label(ms: u64) -> String {
+            if ms >= 1_000 {
+                format!("{}s", ms / 1_000)
+            } else {
+                format!("{ms}ms")
+            }
+        }
+        match LATENCY_BUCKET_UPPER_MS.get(idx) {
+            Some(upper) => format!("<{}", ms_label(*upper)),
+            None => format!(
+                "{}+",
+                ms_label(LATENCY_BUCKET_UPPER_MS[LATENCY_BUCKET_UPPER_MS.len() - 1])
+            ),
+        }
+    }
+}
+
+fn served_by_fallback(request: &FinishedRequest) -> bool {
+    if request.observability_view().cross_provider_failover {
+        return true;
+    }
+    request
+        .retry
+        .as_ref()
+        .and_then(|retry| retry.route_attempts.iter().rev().find(|a| !a.skipped))
+        .is_some_and(|attempt| {
+            attempt.preference_group.is_some_and(|group| group > 0) || attempt.below_top_provider
+        })
+}
+
 fn canonical_provider_endpoint_key(request: &FinishedRequest) -> Option<String> {
     let route_decision = request.route_decision.as_ref()?;
     let service_name = non_empty(request.service.as_str())?;
@@ -117,6 +194,7 @@ fn non_empty(value: &str) -> Option<&str> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::logging::{RetryInfo, RouteAttemptLog};
     use crate::state::RouteDecisionProvenance;
 
     fn finished_request(
@@ -131,12 +209,14 @@ mod tests {
             session_id: None,
             session_identity_source: None,
             client_name: None,
+            tenant: None,
             client_addr: None,
             cwd: None,
             model: None,
             reasoning_effort: None,
             service_tier: None,
             provider_id: provider_id.map(str::to_string),
+            body_hash: None,
             route_decision: Some(RouteDecisionProvenance {
                 provider_id: provider_id.map(str::to_string),
                 endpoint_id: endpoint_id.map(str::to_string),
@@ -157,6 +237,7 @@ mod tests {
             ttfb_ms: None,
             streaming: false,
             ended_at_ms: 1_000,
+            upstream_request_id: None,
         }
     }
 
@@ -201,4 +282,84 @@ mod tests {
         assert_eq!(stats.err_5xx, 1);
         assert_eq!(stats.p50_ms, Some(100));
     }
+
+    #[test]
+    fn fallback_share_counts_successes_served_after_cross_provider_failover() {
+        let mut failed_over = finished_request(1, 200, Some("backup"), Some("default"));
+        failed_over.retry = Some(RetryInfo {
+            attempts: 2,
+            route_attempts: vec![
+                RouteAttemptLog {
+                    attempt_index: 0,
+                    provider_id: Some("primary".to_string()),
+                    ..RouteAttemptLog::default()
+                },
+                RouteAttemptLog {
+                    attempt_index: 1,
+                    provider_id: Some("backup".to_string()),
+                    ..RouteAttemptLog::default()
+                },
+            ],
+        });
+        let recent = vec![
+            failed_over,
+            finished_request(2, 200, Some("primary"), Some("default")),
+            finished_request(3, 200, Some("primary"), Some("default")),
+            finished_request(4, 200, Some("primary"), Some("default")),
+            finished_request(5, 502, Some("primary"), Some("default")),
+        ];
+
+        let stats = compute_window_stats(&recent, 1_000, 60_000, |_| true);
+
+        assert_eq!(stats.fallback_2xx, 1);
+        assert_eq!(stats.fallback_share, Some(0.25));
+        assert_eq!(
+            compute_window_stats(&[], 1_000, 60_000, |_| true).fallback_share,
+            None
+        );
+    }
+
+    #[test]
+    fn fallback_share_counts_successes_served_while_the_primary_is_in_cooldown() {
+        let served_by = |id, preference_group, below_top_provider| {
+            let mut request = finished_request(id, 200, Some("backup"), Some("default"));
+            request.retry = Some(RetryInfo {
+                attempts: 1,
+                route_attempts: vec![RouteAttemptLog {
+                    attempt_index: 0,
+                    provider_id: Some("backup".to_string()),
+                    preference_group: Some(preference_group),
+                    below_top_provider,
+                    ..RouteAttemptLog::default()
+                }],
+            });
+            request
+        };
+        let recent = vec![
+            served_by(1, 1, true),
+            served_by(2, 0, true),
+            served_by(3, 0, false),
+            finished_request(4, 200, Some("primary"), Some("default")),
+        ];
+
+        let stats = compute_window_stats(&recent, 1_000, 60_000, |_| true);
+
+        assert_eq!(stats.fallback_2xx, 2);
+        assert_eq!(stats.fallback_share, Some(0.5));
+    }
+
+    #[test]
+    fn latency_histogram_buckets_by_exclusive_upper_bound() {
+        let histogram = LatencyHistogram::from_durations([
+            0, 199, 200, 999, 1_000, 4_999, 9_999, 10_000, 60_000,
+        ]);
+
+        assert_eq!(histogram.counts, [2, 1, 1, 1, 1, 1, 2]);
+        assert_eq!(histogram.total, 9);
+        assert_eq!(histogram.share(6), Some(2.0 / 9.0));
+        assert_eq!(LatencyHistogram::default().share(0), None);
+        assert_eq!(LatencyHistogram::bucket_label(0), "<200ms");
+        assert_eq!(LatencyHistogram::bucket_label(2), "<1s");
+        assert_eq!(LatencyHistogram::bucket_label(6), "10s+");
+    }
 }
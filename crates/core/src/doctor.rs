@@ -17,6 +17,7 @@ use crate::credentials::{
     InstallationIdentity,
 };
 use crate::logging::request_log_path;
+use crate::provider_tls::{ProviderTlsCheck, check_provider_tls};
 use crate::relay_target::default_proxy_port_for_service_kind;
 use crate::routing_ir::CompiledRouteGraph;
 use crate::runtime_identity::ProviderEndpointKey;
@@ -43,11 +44,78 @@ pub enum DoctorStatus {
     Fail,
 }
 
+impl DoctorStatus {
+    pub fn severity(self) -> DoctorSeverity {
+        match self {
+            DoctorStatus::Ok | DoctorStatus::Info => DoctorSeverity::Ok,
+            DoctorStatus::Warn => DoctorSeverity::Warn,
+            DoctorStatus::Fail => DoctorSeverity::Error,
+        }
+    }
+}
+
+/// Machine-facing severity of a check; `Info` findings count as `ok`.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum DoctorSeverity {
+    Ok,
+    Warn,
+    Error,
+}
+
+/// One doctor finding. Both the JSON output and the human report are rendered from these records.
 #[derive(Debug, Clone, Serialize)]
 pub struct DoctorCheck {
     pub id: &'static str,
+    pub title: &'static str,
     pub status: DoctorStatus,
-    pub message: String,
+    pub severity: DoctorSeverity,
+    pub detail: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remediation: Option<String>,
+}
+
+impl DoctorCheck {
+    pub fn new(
+        lang: DoctorLang,
+        id: &'static str,
+        status: DoctorStatus,
+        detail: impl Into<String>,
+    ) -> Self {
+        Self {
+            id,
+            title: check_title(id, lang),
+            status,
+            severity: status.severity(),
+            detail: detail.into(),
+            remediation: None,
+        }
+    }
+
+    pub fn with_remediation(mut self, remediation: impl Into<String>) -> Self {
+        self.remediation = Some(remediation.into());
+        self
+    }
+}
+
+fn check_title(id: &str, lang: DoctorLang) -> &'static str {
+    let (zh, en) = match id {
+        "proxy_config.codex" => ("canonical 配置", "Canonical configuration"),
+        "proxy_config.routes" => ("路由快照", "Route snapshot"),
+        "proxy_config.auth.env" => ("环境变量引用", "Environment references"),
+        "proxy_config.auth.plaintext" => ("明文密钥", "Plaintext secrets"),
+        "proxy_config.routing.schedule" => ("Provider 时段", "Provider schedules"),
+        "proxy_config.tls" => ("Provider TLS 证书", "Provider TLS certificates"),
+        "codex.client_patch.configured" => ("Codex client patch 配置", "Codex client patch"),
+        "codex.client_patch.applied" => ("Codex client 集成", "Codex client integration"),
+        "codex.switch_state" => ("Codex switch 状态", "Codex switch state"),
+        "codex.onboarding" => ("Codex onboarding", "Codex onboarding"),
+        "logs.requests" => ("请求日志", "Request logs"),
+        "usage_providers" => ("用量提供商", "Usage providers"),
+        id if id.starts_with("proxy_config.auth.") => ("凭据就绪", "Credential readiness"),
+        _ => ("其他", "Other"),
+    };
+    pick(lang, zh, en)
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -57,6 +125,17 @@ pub struct DoctorReport {
     pub checks: Vec<DoctorCheck>,
 }
 
+impl DoctorReport {
+    /// Worst severity across all checks; `Ok` for an empty report.
+    pub fn worst_severity(&self) -> DoctorSeverity {
+        self.checks
+            .iter()
+            .map(|check| check.severity)
+            .max()
+            .unwrap_or(DoctorSeverity::Ok)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, PartialEq, Eq)]
 pub struct ConfigurationStatusSnapshot {
     pub config_version: u32,
@@ -296,6 +375,263 @@ fn doctor_credential_targets(config: &HelperConfig) -> Result<Vec<DoctorCredenti
     Ok(targets)
 }
 
+/// One `*_env` credential reference found in a provider's auth settings.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct EnvVarReference {
+    pub service: &'static str,
+    pub provider_id: String,
+    pub field: &'static str,
+    pub variable: String,
+    /// Whether the provider is enabled, i.e. a missing variable would break routing.
+    pub active: bool,
+    pub set: bool,
+}
+
+/// Lists every environment variable referenced by provider auth without reading its value.
+pub fn env_var_references(config: &HelperConfig, services: &[ServiceKind]) -> Vec<EnvVarReference> {
+    env_var_references_with(config, services, |variable| {
+        std::env::var_os(variable).is_some_and(|value| !value.is_empty())
+    })
+}
+
+fn env_var_references_with(
+    config: &HelperConfig,
+    services: &[ServiceKind],
+    is_set: impl Fn(&str) -> bool,
+) -> Vec<EnvVarReference> {
+    let mut references = Vec::new();
+    for (kind, service, view) in [
+        (ServiceKind::Codex, "codex", &config.codex),
+        (ServiceKind::Claude, "claude", &config.claude),
+    ] {
+        if !services.contains(&kind) {
+            continue;
+        }
+        for (provider_id, provider) in &view.providers {
+            let auth = provider.effective_auth();
+            for (field, variable) in [
+                ("auth_token_env", auth.auth_token_env.as_deref()),
+                ("api_key_env", auth.api_key_env.as_deref()),
+            ] {
+                let Some(variable) = variable.map(str::trim).filter(|name| !name.is_empty()) else {
+                    continue;
+                };
+                references.push(EnvVarReference {
+                    service,
+                    provider_id: provider_id.clone(),
+                    field,
+                    variable: variable.to_string(),
+                    active: provider.enabled,
+                    set: is_set(variable),
+                });
+            }
+        }
+    }
+    references
+}
+
+/// One credential stored as plaintext in config.toml instead of behind `*_env` or `*_ref`.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct InlineSecret {
+    pub service: &'static str,
+    pub provider_id: String,
+    /// `auth_token` or `api_key`.
+    pub field: &'static str,
+    /// The variable `config migrate-secrets` would move it to: the provider's existing
+    /// `*_env` name when one is set, otherwise a name derived from service, provider and field.
+    pub suggested_env: String,
+}
+
+/// Lists every non-empty inline `auth_token` / `api_key`, never the value itself.
+pub fn inline_secrets(config: &HelperConfig) -> Vec<InlineSecret> {
+    let mut secrets = Vec::new();
+    for (service, view) in [("codex", &config.codex), ("claude", &config.claude)] {
+        for (provider_id, provider) in &view.providers {
+            let has_inline = |value: Option<&str>| value.is_some_and(|v| !v.trim().is_empty());
+            let auth = provider.effective_auth();
+            for (field, present, variable) in [
+                (
+                    "auth_token",
+                    has_inline(provider.auth.auth_token.as_deref())
+                        || has_inline(provider.inline_auth.auth_token.as_deref()),
+                    auth.auth_token_env.as_deref(),
+                ),
+                (
+                    "api_key",
+                    has_inline(provider.auth.api_key.as_deref())
+                        || has_inline(provider.inline_auth.api_key.as_deref()),
+                    auth.api_key_env.as_deref(),
+                ),
+            ] {
+                if !present {
+                    continue;
+                }
+                let suggested_env = variable
+                    .map(str::trim)
+                    .filter(|name| !name.is_empty())
+                    .map(str::to_string)
+                    .unwrap_or_else(|| generated_env_name(service, provider_id, field));
+                secrets.push(InlineSecret {
+                    service,
+                    provider_id: provider_id.clone(),
+                    field,
+                    suggested_env,
+                });
+            }
+        }
+    }
+    secrets
+}
+
+/// `codex` / `my-relay` / `auth_token` -> `CODEX_MY_RELAY_AUTH_TOKEN`.
+fn generated_env_name(service: &str, provider_id: &str, field: &str) -> String {
+    [service, provider_id, field]
+        .join("_")
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// One-line startup warning when config.toml holds plaintext credentials.
+pub fn inline_secret_warning(config: &HelperConfig) -> Option<String> {
+    let secrets = inline_secrets(config);
+    if secrets.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "{} provider credential(s) are stored as plaintext in config.toml ({}); prefer auth_token_env/api_key_env, or run `codex-helper config migrate-secrets`",
+        secrets.len(),
+        inline_secret_labels(&secrets).join(", ")
+    ))
+}
+
+fn inline_secret_labels(secrets: &[InlineSecret]) -> Vec<String> {
+    secrets
+        .iter()
+        .map(|secret| format!("{}/{} {}", secret.service, secret.provider_id, secret.field))
+        .collect()
+}
+
+fn append_inline_secret_check(
+    checks: &mut Vec<DoctorCheck>,
+    lang: DoctorLang,
+    secrets: &[InlineSecret],
+) {
+    if secrets.is_empty() {
+        return;
+    }
+    let labels = inline_secret_labels(secrets).join(", ");
+    checks.push(
+        DoctorCheck::new(
+            lang,
+            "proxy_config.auth.plaintext",
+            DoctorStatus::Warn,
+            match lang {
+                DoctorLang::Zh => format!(
+                    "~/.codex-helper/config.toml 中有 {} 个明文凭据：{labels}",
+                    secrets.len()
+                ),
+                DoctorLang::En => format!(
+                    "{} credential(s) are stored as plaintext in ~/.codex-helper/config.toml: {labels}",
+                    secrets.len()
+                ),
+            },
+        )
+        .with_remediation(pick(
+            lang,
+            "建议改用 auth_token_env/api_key_env；`codex-helper config migrate-secrets` 可预览并执行迁移（写入前备份）",
+            "prefer auth_token_env/api_key_env; `codex-helper config migrate-secrets` previews the move and applies it after a backup",
+        )),
+    );
+}
+
+fn append_env_reference_check(
+    checks: &mut Vec<DoctorCheck>,
+    lang: DoctorLang,
+    references: &[EnvVarReference],
+) {
+    if references.is_empty() {
+        return;
+    }
+    let missing = |active: bool| {
+        references
+            .iter()
+            .filter(|reference| !reference.set && reference.active == active)
+            .map(|reference| {
+                format!(
+                    "{}/{}={}",
+                    reference.service, reference.provider_id, reference.variable
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+    let missing_active = missing(true);
+    let missing_inactive = missing(false);
+    let check = if !missing_active.is_empty() {
+        DoctorCheck::new(
+            lang,
+            "proxy_config.auth.env",
+            DoctorStatus::Fail,
+            match lang {
+                DoctorLang::Zh => format!(
+                    "已启用 provider 引用的环境变量未设置：{}；首个请求将因缺少凭据失败",
+                    missing_active.join(", ")
+                ),
+                DoctorLang::En => format!(
+                    "Environment variables referenced by enabled providers are not set: {}; the first request will fail without credentials",
+                    missing_active.join(", ")
+                ),
+            },
+        )
+        .with_remediation(pick(
+            lang,
+            "在启动代理的环境中导出这些变量；可运行 `codex-helper config validate-env` 查看",
+            "export these variables where the proxy is started; see `codex-helper config validate-env`",
+        ))
+    } else if !missing_inactive.is_empty() {
+        DoctorCheck::new(
+            lang,
+            "proxy_config.auth.env",
+            DoctorStatus::Warn,
+            match lang {
+                DoctorLang::Zh => format!(
+                    "已禁用 provider 引用的环境变量未设置：{}",
+                    missing_inactive.join(", ")
+                ),
+                DoctorLang::En => format!(
+                    "Environment variables referenced by disabled providers are not set: {}",
+                    missing_inactive.join(", ")
+                ),
+            },
+        )
+        .with_remediation(pick(
+            lang,
+            "重新启用这些 provider 之前先设置对应变量",
+            "set these variables before re-enabling those providers",
+        ))
+    } else {
+        DoctorCheck::new(
+            lang,
+            "proxy_config.auth.env",
+            DoctorStatus::Ok,
+            match lang {
+                DoctorLang::Zh => format!("{} 个 provider 环境变量引用均已设置", references.len()),
+                DoctorLang::En => format!(
+                    "All {} provider environment variable references are set",
+                    references.len()
+                ),
+            },
+        )
+    };
+    checks.push(check);
+}
+
 pub async fn run_doctor(
     lang: DoctorLang,
     credential_sources: CredentialSourceCapabilities,
@@ -310,23 +646,25 @@ pub async fn run_doctor(
         Ok(cfg) => {
             match configuration_status_snapshot(&cfg) {
                 Ok(snapshot) => configuration = Some(snapshot),
-                Err(error) => checks.push(DoctorCheck {
-                    id: "proxy_config.routes",
-                    status: DoctorStatus::Fail,
-                    message: match lang {
+                Err(error) => checks.push(DoctorCheck::new(
+                    lang,
+                    "proxy_config.routes",
+                    DoctorStatus::Fail,
+                    match lang {
                         DoctorLang::Zh => format!("无法编译 canonical 路由快照：{error}"),
                         DoctorLang::En => {
                             format!("Failed to compile the canonical route snapshot: {error}")
                         }
                     },
-                }),
+                )),
             }
             let client_patch = cfg.codex.client_patch.unwrap_or_default();
             configured_codex_patch = Some(client_patch);
-            checks.push(DoctorCheck {
-                id: "codex.client_patch.configured",
-                status: DoctorStatus::Info,
-                message: match lang {
+            checks.push(DoctorCheck::new(
+                lang,
+                "codex.client_patch.configured",
+                DoctorStatus::Info,
+                match lang {
                     DoctorLang::Zh => format!(
                         "canonical Codex client patch：{}",
                         codex_client_patch_summary(&client_patch)
@@ -336,34 +674,40 @@ pub async fn run_doctor(
                         codex_client_patch_summary(&client_patch)
                     ),
                 },
-            });
+            ));
             let codex_count = cfg.codex.providers.len();
             if codex_count == 0 {
-                checks.push(DoctorCheck {
-                    id: "proxy_config.codex",
-                    status: DoctorStatus::Warn,
-                    message: pick(
+                checks.push(
+                    DoctorCheck::new(
                         lang,
-                        "检测到 canonical ~/.codex-helper/config.toml 中尚无 Codex provider；请用 `codex-helper provider add` 显式添加。",
-                        "No Codex providers found in canonical ~/.codex-helper/config.toml; add one explicitly with `codex-helper provider add`.",
+                        "proxy_config.codex",
+                        DoctorStatus::Warn,
+                        pick(
+                            lang,
+                            "检测到 canonical ~/.codex-helper/config.toml 中尚无 Codex provider。",
+                            "No Codex providers found in canonical ~/.codex-helper/config.toml.",
+                        ),
                     )
-                    .to_string(),
-                });
+                    .with_remediation(pick(
+                        lang,
+                        "请用 `codex-helper provider add` 显式添加",
+                        "add one explicitly with `codex-helper provider add`",
+                    )),
+                );
             } else {
-                checks.push(DoctorCheck {
-                    id: "proxy_config.codex",
-                    status: DoctorStatus::Ok,
-                    message: match lang {
+                checks.push(DoctorCheck::new(
+                    lang,
+                    "proxy_config.codex",
+                    DoctorStatus::Ok,
+                    match lang {
                         DoctorLang::Zh => format!(
-                            "已从 canonical ~/.codex-helper/config.toml 读取到 {} 个 Codex provider",
-                            codex_count
+                            "已从 canonical ~/.codex-helper/config.toml 读取到 {codex_count} 个 Codex provider"
                         ),
                         DoctorLang::En => format!(
-                            "Loaded {} Codex providers from canonical ~/.codex-helper/config.toml",
-                            codex_count
+                            "Loaded {codex_count} Codex providers from canonical ~/.codex-helper/config.toml"
                         ),
                     },
-                });
+                ));
             }
 
             match capture_doctor_credential_observations(cfg.clone(), credential_sources).await {
@@ -372,10 +716,11 @@ pub async fn run_doctor(
                         append_credential_readiness_checks(&mut checks, lang, observation);
                     }
                 }
-                Err(error) => checks.push(DoctorCheck {
-                    id: "proxy_config.auth.readiness",
-                    status: DoctorStatus::Fail,
-                    message: match lang {
+                Err(error) => checks.push(DoctorCheck::new(
+                    lang,
+                    "proxy_config.auth.readiness",
+                    DoctorStatus::Fail,
+                    match lang {
                         DoctorLang::Zh => {
                             format!("无法从 canonical credential runtime 评估凭据状态：{error}")
                         }
@@ -383,57 +728,84 @@ pub async fn run_doctor(
                             "Failed to evaluate credential readiness through the canonical credential runtime: {error}"
                         ),
                     },
-                }),
+                )),
+            }
+
+            append_env_reference_check(
+                &mut checks,
+                lang,
+                &env_var_references(&cfg, &[ServiceKind::Codex, ServiceKind::Claude]),
+            );
+
+            for (svc_label, view) in [("Codex", &cfg.codex), ("Claude", &cfg.claude)] {
+                let warnings = crate::provider_schedule::service_schedule_warnings(view);
+                if warnings.is_empty() {
+                    continue;
+                }
+                checks.push(
+                    DoctorCheck::new(
+                        lang,
+                        "proxy_config.routing.schedule",
+                        DoctorStatus::Warn,
+                        match lang {
+                            DoctorLang::Zh => format!(
+                                "{svc_label} 的 enabled_schedule 配置有误：{}",
+                                warnings.join("; ")
+                            ),
+                            DoctorLang::En => format!(
+                                "{svc_label} enabled_schedule problems: {}",
+                                warnings.join("; ")
+                            ),
+                        },
+                    )
+                    .with_remediation(pick(
+                        lang,
+                        "按 `Mon-Fri 20:00-08:00` 格式修正条目；无效条目会被忽略",
+                        "fix the entries using the `Mon-Fri 20:00-08:00` form; invalid entries are ignored",
+                    )),
+                );
             }
 
             for (svc_label, view) in [("Codex", &cfg.codex), ("Claude", &cfg.claude)] {
                 for (provider_id, provider) in &view.providers {
-                    let has_plaintext =
-                        [&provider.auth, &provider.inline_auth]
-                            .into_iter()
-                            .any(|auth| {
-                                auth.auth_token
-                                    .as_deref()
-                                    .is_some_and(|value| !value.trim().is_empty())
-                                    || auth
-                                        .api_key
-                                        .as_deref()
-                                        .is_some_and(|value| !value.trim().is_empty())
-                            });
-                    if has_plaintext {
-                        checks.push(DoctorCheck {
-                            id: "proxy_config.auth.plaintext",
-                            status: DoctorStatus::Warn,
-                            message: match lang {
-                                DoctorLang::Zh => format!(
-                                    "{} provider '{}' 在 ~/.codex-helper/config.toml 中检测到明文密钥字段（建议改用 auth_token_env/api_key_env 以避免落盘泄露）",
-                                    svc_label, provider_id
-                                ),
-                                DoctorLang::En => format!(
-                                    "{} provider '{}' contains plaintext secrets in ~/.codex-helper/config.toml (prefer auth_token_env/api_key_env)",
-                                    svc_label, provider_id
-                                ),
-                            },
-                        });
+                    if !provider.tls.is_default() {
+                        checks.push(provider_tls_check(
+                            lang,
+                            svc_label,
+                            provider_id,
+                            check_provider_tls(&provider.tls, chrono::Utc::now()),
+                        ));
                     }
                 }
             }
+
+            append_inline_secret_check(&mut checks, lang, &inline_secrets(&cfg));
             loaded_config = Some(cfg);
         }
         Err(err) => {
-            checks.push(DoctorCheck {
-                id: "proxy_config.codex",
-                status: DoctorStatus::Fail,
-                message: match lang {
-                    DoctorLang::Zh => format!(
-                        "无法读取 canonical ~/.codex-helper/config.toml：{}；请确认它是有效的 version = {} TOML。",
-                        err, CURRENT_CONFIG_VERSION
-                    ),
-                    DoctorLang::En => format!(
-                        "Failed to read canonical ~/.codex-helper/config.toml: {err}; ensure it is valid version = {CURRENT_CONFIG_VERSION} TOML.",
-                    ),
-                },
-            });
+            checks.push(
+                DoctorCheck::new(
+                    lang,
+                    "proxy_config.codex",
+                    DoctorStatus::Fail,
+                    match lang {
+                        DoctorLang::Zh => {
+                            format!("无法读取 canonical ~/.codex-helper/config.toml：{err}")
+                        }
+                        DoctorLang::En => {
+                            format!("Failed to read canonical ~/.codex-helper/config.toml: {err}")
+                        }
+                    },
+                )
+                .with_remediation(match lang {
+                    DoctorLang::Zh => {
+                        format!("请确认它是有效的 version = {CURRENT_CONFIG_VERSION} TOML")
+                    }
+                    DoctorLang::En => {
+                        format!("ensure it is valid version = {CURRENT_CONFIG_VERSION} TOML")
+                    }
+                }),
+            );
         }
     }
 
@@ -441,67 +813,78 @@ pub async fn run_doctor(
     let switch_status = inspect_codex_switch();
     match switch_status.as_ref() {
         Ok(status) if status.phase == CodexSwitchPhase::Off && status.managed => {
-            checks.push(DoctorCheck {
-                id: "codex.switch_state",
-                status: DoctorStatus::Info,
-                message: pick(
+            checks.push(DoctorCheck::new(
+                lang,
+                "codex.switch_state",
+                DoctorStatus::Info,
+                pick(
                     lang,
                     "Codex 本地 switch 已关闭；codex-helper 仍保留认证恢复点，用于修复旧 Codex 进程延迟写回的 facade。",
                     "The local Codex switch is off; codex-helper retains an auth recovery point for repairing a delayed facade write from an older Codex process.",
-                )
-                .to_string(),
-            });
+                ),
+            ));
         }
-        Ok(status) if status.phase == CodexSwitchPhase::Off => checks.push(DoctorCheck {
-            id: "codex.switch_state",
-            status: DoctorStatus::Info,
-            message: pick(
+        Ok(status) if status.phase == CodexSwitchPhase::Off => checks.push(DoctorCheck::new(
+            lang,
+            "codex.switch_state",
+            DoctorStatus::Info,
+            pick(
                 lang,
                 "未检测到 codex-helper 显式 switch state；doctor 不推断或导入 Codex CLI 配置。",
                 "No explicit codex-helper switch state found; doctor does not infer or import Codex CLI configuration.",
-            )
-            .to_string(),
-        }),
-        Ok(status) if status.phase == CodexSwitchPhase::Applied && status.enabled => checks.push(DoctorCheck {
-            id: "codex.switch_state",
-            status: DoctorStatus::Ok,
-            message: match lang {
-                DoctorLang::Zh => format!(
-                    "显式 switch state 与当前 Codex helper stanza 一致（base_url = {}）。",
-                    status.base_url.as_deref().unwrap_or("<missing>")
-                ),
-                DoctorLang::En => format!(
-                    "Explicit switch state matches the current Codex helper stanza (base_url = {}).",
-                    status.base_url.as_deref().unwrap_or("<missing>")
-                ),
-            },
-        }),
-        Ok(status) => checks.push(DoctorCheck {
-            id: "codex.switch_state",
-            status: DoctorStatus::Warn,
-            message: pick(
+            ),
+        )),
+        Ok(status) if status.phase == CodexSwitchPhase::Applied && status.enabled => {
+            let base_url = status.base_url.as_deref().unwrap_or("<missing>");
+            checks.push(DoctorCheck::new(
+                lang,
+                "codex.switch_state",
+                DoctorStatus::Ok,
+                match lang {
+                    DoctorLang::Zh => format!(
+                        "显式 switch state 与当前 Codex helper stanza 一致（base_url = {base_url}）。"
+                    ),
+                    DoctorLang::En => format!(
+                        "Explicit switch state matches the current Codex helper stanza (base_url = {base_url})."
+                    ),
+                },
+            ))
+        }
+        Ok(status) => checks.push(
+            DoctorCheck::new(
                 lang,
-                "Codex switch 状态需要核对；请运行 `codex-helper switch status`，不要直接覆盖 config.toml。",
-                "The Codex switch state needs reconciliation; run `codex-helper switch status` and do not overwrite config.toml.",
+                "codex.switch_state",
+                DoctorStatus::Warn,
+                pick(
+                    lang,
+                    "Codex switch 状态需要核对。",
+                    "The Codex switch state needs reconciliation.",
+                )
+                .to_string()
+                    + status
+                        .recovery_reason
+                        .as_deref()
+                        .map(|reason| format!(" {reason}"))
+                        .as_deref()
+                        .unwrap_or(""),
             )
-            .to_string()
-                + status
-                    .recovery_reason
-                    .as_deref()
-                    .map(|reason| format!(" {reason}"))
-                    .as_deref()
-                    .unwrap_or(""),
-        }),
-        Err(err) => checks.push(DoctorCheck {
-            id: "codex.switch_state",
-            status: DoctorStatus::Warn,
-            message: match lang {
+            .with_remediation(pick(
+                lang,
+                "请运行 `codex-helper switch status`，不要直接覆盖 config.toml",
+                "run `codex-helper switch status` and do not overwrite config.toml",
+            )),
+        ),
+        Err(err) => checks.push(DoctorCheck::new(
+            lang,
+            "codex.switch_state",
+            DoctorStatus::Warn,
+            match lang {
                 DoctorLang::Zh => format!("无法验证 codex-helper 显式 switch state：{err}"),
                 DoctorLang::En => {
                     format!("Failed to validate explicit codex-helper switch state: {err}")
                 }
             },
-        }),
+        )),
     }
     if let Ok(status) = switch_status.as_ref() {
         append_applied_client_patch_check(
@@ -525,29 +908,52 @@ pub async fn run_doctor(
     // 3) logs and usage_providers
     let log_path = request_log_path();
     if log_path.exists() {
-        checks.push(DoctorCheck {
-            id: "logs.requests",
-            status: DoctorStatus::Ok,
-            message: match lang {
+        checks.push(DoctorCheck::new(
+            lang,
+            "logs.requests",
+            DoctorStatus::Ok,
+            match lang {
                 DoctorLang::Zh => format!("检测到请求日志文件：{:?}", log_path),
                 DoctorLang::En => format!("Found request logs: {:?}", log_path),
             },
-        });
+        ));
     } else {
-        checks.push(DoctorCheck {
-            id: "logs.requests",
-            status: DoctorStatus::Info,
-            message: match lang {
+        let detail = match lang {
+            DoctorLang::Zh => format!(
+                "尚未生成请求日志：{:?}，可能尚未通过 codex-helper 代理发送请求",
+                log_path
+            ),
+            DoctorLang::En => format!(
+                "Request logs not found: {:?}; you may not have sent requests through codex-helper yet.",
+                log_path
+            ),
+        };
+        checks.push(DoctorCheck::new(
+            lang,
+            "logs.requests",
+            DoctorStatus::Info,
+            detail,
+        ));
+    }
+
+    if let Some(rate) = loaded_config
+        .as_ref()
+        .and_then(|config| config.logging.active_sample_rate())
+    {
+        let percent = rate * 100.0;
+        checks.push(DoctorCheck::new(
+            lang,
+            "logs.sampling",
+            DoctorStatus::Info,
+            match lang {
                 DoctorLang::Zh => format!(
-                    "尚未生成请求日志：{:?}，可能尚未通过 codex-helper 代理发送请求",
-                    log_path
+                    "logging.sample_rate = {rate}：requests.jsonl 只记录约 {percent:.0}% 的成功请求，错误和重试请求始终记录；usage 与统计读取 runtime store，不受影响"
                 ),
                 DoctorLang::En => format!(
-                    "Request logs not found: {:?}; you may not have sent requests through codex-helper yet.",
-                    log_path
+                    "logging.sample_rate = {rate}: requests.jsonl keeps about {percent:.0}% of successful requests; errors and retried requests are always logged, and usage/stats read the runtime store, which stays complete"
                 ),
             },
-        });
+        ));
     }
 
     let usage_path: PathBuf = proxy_home_dir().join("usage_providers.json");
@@ -556,18 +962,20 @@ pub async fn run_doctor(
             .ok()
             .and_then(|s| serde_json::from_str::<serde_json::Value>(&s).ok())
         {
-            Some(_) => checks.push(DoctorCheck {
-                id: "usage_providers",
-                status: DoctorStatus::Ok,
-                message: match lang {
+            Some(_) => {
+                let detail = match lang {
                     DoctorLang::Zh => format!("检测到用量提供商配置：{:?}", usage_path),
                     DoctorLang::En => format!("Found usage providers config: {:?}", usage_path),
-                },
-            }),
-            None => checks.push(DoctorCheck {
-                id: "usage_providers",
-                status: DoctorStatus::Warn,
-                message: match lang {
+                };
+                checks.push(DoctorCheck::new(
+                    lang,
+                    "usage_providers",
+                    DoctorStatus::Ok,
+                    detail,
+                ))
+            }
+            None => {
+                let detail = match lang {
                     DoctorLang::Zh => format!(
                         "无法解析 {:?} 为 JSON，用量查询（如 Packy 额度）将不可用",
                         usage_path
@@ -576,24 +984,34 @@ pub async fn run_doctor(
                         "Failed to parse {:?} as JSON; usage queries (e.g. Packy quota) will be unavailable.",
                         usage_path
                     ),
-                },
-            }),
+                };
+                checks.push(
+                    DoctorCheck::new(lang, "usage_providers", DoctorStatus::Warn, detail)
+                        .with_remediation(pick(
+                            lang,
+                            "修正该文件，或删除它以便重新生成默认示例",
+                            "fix the file, or delete it so the default example is regenerated",
+                        )),
+                )
+            }
         }
     } else {
-        checks.push(DoctorCheck {
-            id: "usage_providers",
-            status: DoctorStatus::Info,
-            message: match lang {
-                DoctorLang::Zh => format!(
-                    "未找到 {:?}，codex-helper 将在首次需要时写入一个默认示例（当前包含 packycode）",
-                    usage_path
-                ),
-                DoctorLang::En => format!(
-                    "{:?} not found; codex-helper will write a default example when needed (currently includes packycode).",
-                    usage_path
-                ),
-            },
-        });
+        let detail = match lang {
+            DoctorLang::Zh => format!(
+                "未找到 {:?}，codex-helper 将在首次需要时写入一个默认示例（当前包含 packycode）",
+                usage_path
+            ),
+            DoctorLang::En => format!(
+                "{:?} not found; codex-helper will write a default example when needed (currently includes packycode).",
+                usage_path
+            ),
+        };
+        checks.push(DoctorCheck::new(
+            lang,
+            "usage_providers",
+            DoctorStatus::Info,
+            detail,
+        ));
     }
 
     DoctorReport {
@@ -602,6 +1020,41 @@ pub async fn run_doctor(
     }
 }
 
+fn provider_tls_check(
+    lang: DoctorLang,
+    svc_label: &str,
+    provider_id: &str,
+    outcome: ProviderTlsCheck,
+) -> DoctorCheck {
+    let (status, message) = match outcome {
+        ProviderTlsCheck::Ok(message) => (DoctorStatus::Ok, message),
+        ProviderTlsCheck::Warn(message) => (DoctorStatus::Warn, message),
+        ProviderTlsCheck::Fail(message) => (DoctorStatus::Fail, message),
+    };
+    let check = DoctorCheck::new(
+        lang,
+        "proxy_config.tls",
+        status,
+        match lang {
+            DoctorLang::Zh => format!("{svc_label} provider '{provider_id}' 的 tls：{message}"),
+            DoctorLang::En => format!("{svc_label} provider '{provider_id}' tls: {message}"),
+        },
+    );
+    match status {
+        DoctorStatus::Fail => check.with_remediation(pick(
+            lang,
+            "检查 tls.client_cert_path / tls.client_key_path / tls.ca_bundle_path 指向有效的 PEM 文件，证书未过期且由 CA bundle 签发",
+            "point tls.client_cert_path / tls.client_key_path / tls.ca_bundle_path at valid PEM files with an unexpired certificate issued by the CA bundle",
+        )),
+        DoctorStatus::Warn => check.with_remediation(pick(
+            lang,
+            "尽快续期客户端证书，或在 tls.ca_bundle_path 中提供签发 CA 以便校验证书链",
+            "renew the client certificate soon, or provide the issuing CA in tls.ca_bundle_path so the chain can be verified",
+        )),
+        DoctorStatus::Ok | DoctorStatus::Info => check,
+    }
+}
+
 fn codex_client_patch_summary(client_patch: &CodexClientPatchConfig) -> String {
     format!(
         "preset={}, responses_websocket={}, compaction={}, translate_models={}, hosted_image_generation={}",
@@ -637,16 +1090,17 @@ fn append_applied_client_patch_check(
     } else {
         ""
     };
-    checks.push(DoctorCheck {
-        id: "codex.client_patch.applied",
-        status: if status.phase == CodexSwitchPhase::RecoveryRequired {
+    checks.push(DoctorCheck::new(
+        lang,
+        "codex.client_patch.applied",
+        if status.phase == CodexSwitchPhase::RecoveryRequired {
             DoctorStatus::Warn
         } else if status.phase == CodexSwitchPhase::Applied && status.enabled {
             DoctorStatus::Ok
         } else {
             DoctorStatus::Info
         },
-        message: match lang {
+        match lang {
             DoctorLang::Zh => format!(
                 "Codex client 集成：phase={}，applied patch={}{}",
                 status.phase.as_str(),
@@ -660,7 +1114,7 @@ fn append_applied_client_patch_check(
                 difference
             ),
         },
-    });
+    ));
 }
 
 fn append_codex_onboarding_check(
@@ -668,7 +1122,7 @@ fn append_codex_onboarding_check(
     lang: DoctorLang,
     feasibility: CodexOnboardingFeasibility,
 ) {
-    let (status, message) = match feasibility {
+    let (status, detail) = match feasibility {
         CodexOnboardingFeasibility::ExistingConfiguration => (
             DoctorStatus::Ok,
             pick(
@@ -725,11 +1179,7 @@ fn append_codex_onboarding_check(
             )
         }
     };
-    checks.push(DoctorCheck {
-        id: "codex.onboarding",
-        status,
-        message,
-    });
+    checks.push(DoctorCheck::new(lang, "codex.onboarding", status, detail));
 }
 
 fn append_credential_readiness_checks(
@@ -772,36 +1222,38 @@ fn append_credential_readiness_checks(
                 DoctorLang::En => format!(", last refresh failure={cause}"),
             })
             .unwrap_or_default();
-        checks.push(DoctorCheck {
-            id,
-            status: DoctorStatus::Warn,
-            message: match lang {
-                DoctorLang::Zh => format!(
-                    "{} provider '{}.{}' 的 {} 状态={}（source={}, reference=`{}`{}）：{}",
-                    observation.service_label,
-                    observation.provider_endpoint.provider_id,
-                    observation.provider_endpoint.endpoint_id,
-                    credential_kind,
-                    detail.code,
-                    source_kind,
-                    reference,
-                    stale_cause,
-                    remediation
-                ),
-                DoctorLang::En => format!(
-                    "{} provider '{}.{}' {} readiness={} (source={}, reference=`{}`{}): {}",
-                    observation.service_label,
-                    observation.provider_endpoint.provider_id,
-                    observation.provider_endpoint.endpoint_id,
-                    credential_kind,
-                    detail.code,
-                    source_kind,
-                    reference,
-                    stale_cause,
-                    remediation
-                ),
-            },
-        });
+        checks.push(
+            DoctorCheck::new(
+                lang,
+                id,
+                DoctorStatus::Warn,
+                match lang {
+                    DoctorLang::Zh => format!(
+                        "{} provider '{}.{}' 的 {} 状态={}（source={}, reference=`{}`{}）",
+                        observation.service_label,
+                        observation.provider_endpoint.provider_id,
+                        observation.provider_endpoint.endpoint_id,
+                        credential_kind,
+                        detail.code,
+                        source_kind,
+                        reference,
+                        stale_cause,
+                    ),
+                    DoctorLang::En => format!(
+                        "{} provider '{}.{}' {} readiness={} (source={}, reference=`{}`{})",
+                        observation.service_label,
+                        observation.provider_endpoint.provider_id,
+                        observation.provider_endpoint.endpoint_id,
+                        credential_kind,
+                        detail.code,
+                        source_kind,
+                        reference,
+                        stale_cause,
+                    ),
+                },
+            )
+            .with_remediation(remediation),
+        );
     }
 }
 
@@ -904,7 +1356,6 @@ mod tests {
     use crate::credentials::SecretValue;
     use crate::runtime_store::RuntimeStore;
     use std::path::Path;
-    use std::sync::{Mutex, OnceLock};
 
     struct ScopedEnv {
         saved: Vec<(String, Option<String>)>,
@@ -934,13 +1385,7 @@ mod tests {
         }
     }
 
-    fn env_lock() -> std::sync::MutexGuard<'static, ()> {
-        static LOCK: OnceLock<Mutex<()>> = OnceLock::new();
-        match LOCK.get_or_init(|| Mutex::new(())).lock() {
-            Ok(guard) => guard,
-            Err(poisoned) => poisoned.into_inner(),
-        }
-    }
+    use crate::test_support::env_lock_blocking as env_lock;
 
     #[test]
     fn configuration_snapshot_keeps_route_shape_without_credentials_or_origins() {
@@ -1071,8 +1516,8 @@ mod tests {
         assert!(report.checks.iter().all(|check| {
             !check.id.starts_with("codex.auth")
                 && check.id != "bootstrap.codex"
-                && !check.message.contains("import-from-codex")
-                && !check.message.contains("not-json")
+                && !check.detail.contains("import-from-codex")
+                && !check.detail.contains("not-json")
         }));
     }
 
@@ -1149,25 +1594,25 @@ requires_openai_auth = false
             .iter()
             .find(|check| check.id == "codex.client_patch.configured")
             .expect("configured client patch check");
-        assert!(configured.message.contains("preset=official-relay"));
+        assert!(configured.detail.contains("preset=official-relay"));
         let applied = report
             .checks
             .iter()
             .find(|check| check.id == "codex.client_patch.applied")
             .expect("applied client patch check");
         assert_eq!(applied.status, DoctorStatus::Ok);
-        assert!(applied.message.contains("phase=applied"));
-        assert!(applied.message.contains("preset=imagegen-bridge"));
-        assert!(!applied.message.contains("preset=default"));
-        assert!(applied.message.contains("explicit CLI override"));
+        assert!(applied.detail.contains("phase=applied"));
+        assert!(applied.detail.contains("preset=imagegen-bridge"));
+        assert!(!applied.detail.contains("preset=default"));
+        assert!(applied.detail.contains("explicit CLI override"));
         let onboarding = report
             .checks
             .iter()
             .find(|check| check.id == "codex.onboarding")
             .expect("onboarding feasibility check");
         assert_eq!(onboarding.status, DoctorStatus::Info);
-        assert!(onboarding.message.contains("provider=`relay`"));
-        assert!(onboarding.message.contains("`RELAY_API_KEY`"));
+        assert!(onboarding.detail.contains("provider=`relay`"));
+        assert!(onboarding.detail.contains("`RELAY_API_KEY`"));
 
         let serialized = serde_json::to_string(&report).expect("serialize doctor report");
         assert!(!serialized.contains(SECRET));
@@ -1301,10 +1746,10 @@ requires_openai_auth = false
             .find(|check| check.id == "codex.switch_state")
             .expect("switch-state doctor check");
         assert_eq!(check.status, DoctorStatus::Info);
-        assert!(check.message.contains("retains an auth recovery point"));
+        assert!(check.detail.contains("retains an auth recovery point"));
         assert!(
             !check
-                .message
+                .detail
                 .contains("No explicit codex-helper switch state")
         );
     }
@@ -1427,11 +1872,11 @@ requires_openai_auth = false
         assert_eq!(missing_checks.len(), 1);
         assert!(
             missing_checks[0]
-                .message
+                .detail
                 .contains("provider 'missing.default'")
         );
-        assert!(missing_checks[0].message.contains(&missing_reference));
-        assert!(!missing_checks[0].message.contains(&resolved_reference));
+        assert!(missing_checks[0].detail.contains(&missing_reference));
+        assert!(!missing_checks[0].detail.contains(&resolved_reference));
 
         let invalid_checks = report
             .checks
@@ -1441,10 +1886,10 @@ requires_openai_auth = false
         assert_eq!(invalid_checks.len(), 1);
         assert!(
             invalid_checks[0]
-                .message
+                .detail
                 .contains("provider 'invalid.default'")
         );
-        assert!(invalid_checks[0].message.contains(&invalid_reference));
+        assert!(invalid_checks[0].detail.contains(&invalid_reference));
 
         let anonymous_checks = report
             .checks
@@ -1452,8 +1897,8 @@ requires_openai_auth = false
             .filter(|check| check.id == "proxy_config.auth.anonymous_not_allowed")
             .collect::<Vec<_>>();
         assert_eq!(anonymous_checks.len(), 1);
-        assert!(anonymous_checks[0].message.contains("anonymous-denied"));
-        assert!(!anonymous_checks[0].message.contains("anonymous-allowed"));
+        assert!(anonymous_checks[0].detail.contains("anonymous-denied"));
+        assert!(!anonymous_checks[0].detail.contains("anonymous-allowed"));
     }
 
     #[test]
@@ -1511,9 +1956,9 @@ requires_openai_auth = false
             .filter(|check| check.id == "proxy_config.auth.unsupported_reference")
             .collect::<Vec<_>>();
         assert_eq!(unsupported.len(), 1);
-        assert!(unsupported[0].message.contains("provider 'native.default'"));
-        assert!(unsupported[0].message.contains(&reference));
-        assert!(!unsupported[0].message.contains("native-secret"));
+        assert!(unsupported[0].detail.contains("provider 'native.default'"));
+        assert!(unsupported[0].detail.contains(&reference));
+        assert!(!unsupported[0].detail.contains("native-secret"));
     }
 
     #[test]
@@ -1580,7 +2025,86 @@ requires_openai_auth = false
             report
                 .checks
                 .iter()
-                .all(|check| !check.message.contains("native-secret"))
+                .all(|check| !check.detail.contains("native-secret"))
         );
     }
+
+    #[test]
+    fn env_references_report_set_and_missing_without_values() {
+        let mut config = HelperConfig::default();
+        config.codex.providers.insert(
+            "primary".to_string(),
+            ProviderConfig {
+                base_url: Some("https://primary.example/v1".to_string()),
+                auth: UpstreamAuth {
+                    auth_token_env: Some("PRIMARY_TOKEN".to_string()),
+                    ..UpstreamAuth::default()
+                },
+                ..ProviderConfig::default()
+            },
+        );
+        config.codex.providers.insert(
+            "spare".to_string(),
+            ProviderConfig {
+                enabled: false,
+                base_url: Some("https://spare.example/v1".to_string()),
+                auth: UpstreamAuth {
+                    api_key_env: Some("SPARE_KEY".to_string()),
+                    ..UpstreamAuth::default()
+                },
+                ..ProviderConfig::default()
+            },
+        );
+        config.claude.providers.insert(
+            "anthropic".to_string(),
+            ProviderConfig {
+                base_url: Some("https://anthropic.example".to_string()),
+                auth: UpstreamAuth {
+                    api_key_env: Some("CLAUDE_KEY".to_string()),
+                    ..UpstreamAuth::default()
+                },
+                ..ProviderConfig::default()
+            },
+        );
+
+        let references = env_var_references_with(&config, &[ServiceKind::Codex], |name| {
+            name == "PRIMARY_TOKEN"
+        });
+        assert_eq!(references.len(), 2);
+        assert!(references[0].set && references[0].active);
+        assert_eq!(references[1].variable, "SPARE_KEY");
+        assert!(!references[1].set && !references[1].active);
+
+        let mut checks = Vec::new();
+        append_env_reference_check(&mut checks, DoctorLang::En, &references);
+        assert_eq!(checks[0].status, DoctorStatus::Warn);
+
+        let references =
+            env_var_references_with(&config, &[ServiceKind::Codex, ServiceKind::Claude], |_| {
+                false
+            });
+        let mut checks = Vec::new();
+        append_env_reference_check(&mut checks, DoctorLang::En, &references);
+        assert_eq!(checks[0].status, DoctorStatus::Fail);
+        assert!(checks[0].detail.contains("claude/anthropic=CLAUDE_KEY"));
+        assert!(
+            checks[0]
+                .remediation
+                .as_deref()
+                .is_some_and(|remediation| remediation.contains("validate-env"))
+        );
+        let record = serde_json::to_value(&checks[0]).expect("serialize check");
+        assert_eq!(record["severity"], "error");
+        assert_eq!(record["title"], "Environment references");
+
+        let report = DoctorReport {
+            configuration: None,
+            checks: vec![
+                DoctorCheck::new(DoctorLang::En, "logs.requests", DoctorStatus::Info, "x"),
+                checks.remove(0),
+            ],
+        };
+        assert_eq!(report.checks[0].severity, DoctorSeverity::Ok);
+        assert_eq!(report.worst_severity(), DoctorSeverity::Error);
+    }
 }
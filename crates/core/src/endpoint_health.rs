@@ -40,6 +40,8 @@ pub(crate) enum RuntimeHealthHalfOpenTerminal {
 pub struct CooldownBackoff {
     pub factor: u64,
     pub max_secs: u64,
+    /// Consecutive successful probes required before an opened breaker fully closes.
+    pub probe_back_success_threshold: u32,
 }
 
 impl CooldownBackoff {
@@ -76,6 +78,7 @@ mod tests {
         let backoff = CooldownBackoff {
             factor: 2,
             max_secs: 120,
+            probe_back_success_threshold: 1,
         };
 
         assert_eq!(backoff.effective_cooldown_secs(30, 0), 30);
@@ -89,6 +92,7 @@ mod tests {
         let backoff = CooldownBackoff {
             factor: 1,
             max_secs: 0,
+            probe_back_success_threshold: 1,
         };
 
         assert_eq!(backoff.effective_cooldown_secs(30, 8), 30);
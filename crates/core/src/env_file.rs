@@ -0,0 +1,161 @@
+//! Optional `.env` loading for upstream secrets kept next to the config.
+//!
+//! The file is sourced into the process environment before providers resolve
+//! `auth_token_env` / `api_key_env`. Variables that are already set in the process
+//! always win, so a shell export can override the file without editing it.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use crate::config::proxy_home_dir;
+
+pub const ENV_FILE_NAME: &str = ".env";
+
+/// `~/.codex-helper/.env` (or `$CODEX_HELPER_HOME/.env`).
+pub fn default_env_file_path() -> PathBuf {
+    proxy_home_dir().join(ENV_FILE_NAME)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnvFileLoadOutcome {
+    pub path: PathBuf,
+    /// Variables copied from the file into the process environment.
+    pub applied: usize,
+    /// Variables left alone because the process already had them.
+    pub kept_from_process: usize,
+}
+
+/// Loads `explicit` when given, otherwise the default `.env` in the helper home.
+///
+/// A missing default file is not an error; a missing explicit file is. Call this before
+/// starting the proxy: it writes the process environment.
+pub fn load_env_file(explicit: Option<&Path>) -> Result<Option<EnvFileLoadOutcome>> {
+    let path = match explicit {
+        Some(path) => path.to_path_buf(),
+        None => {
+            let path = default_env_file_path();
+            if !path.is_file() {
+                return Ok(None);
+            }
+            path
+        }
+    };
+    let text = std::fs::read_to_string(&path)
+        .with_context(|| format!("read env file {}", path.display()))?;
+    let mut outcome = EnvFileLoadOutcome {
+        path,
+        applied: 0,
+        kept_from_process: 0,
+    };
+    for (key, value) in parse_env_file(&text) {
+        if std::env::var_os(&key).is_some() {
+            outcome.kept_from_process += 1;
+            continue;
+        }
+        // SAFETY: callers load the file before the proxy starts or any task is spawned, so
+        // no other thread reads the environment while it changes.
+        unsafe { std::env::set_var(&key, value) };
+        outcome.applied += 1;
+    }
+    Ok(Some(outcome))
+}
+
+/// Parses `KEY=VALUE` lines; blank lines, `#` comments, and an optional `export ` prefix
+/// are accepted, and one pair of matching surrounding quotes is stripped from values.
+pub fn parse_env_file(text: &str) -> Vec<(String, String)> {
+    text.lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                return None;
+            }
+            let line = line.strip_prefix("export ").unwrap_or(line);
+            let (key, value) = line.split_once('=')?;
+            let key = key.trim();
+            if key.is_empty()
+                || !key
+                    .chars()
+                    .all(|ch| ch.is_ascii_alphanumeric() || ch == '_')
+            {
+                return None;
+            }
+            Some((key.to_string(), unquote(value.trim()).to_string()))
+        })
+        .collect()
+}
+
+fn unquote(value: &str) -> &str {
+    for quote in ['"', '\''] {
+        if value.len() >= 2 && value.starts_with(quote) && value.ends_with(quote) {
+            return &value[1..value.len() - 1];
+        }
+    }
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::env_lock_blocking;
+
+    #[test]
+    fn parse_env_file_skips_comments_and_strips_export_and_quotes() {
+        let parsed = parse_env_file(
+            "# secrets\n\nexport RELAY_KEY=\"sk-one\"\nOTHER_KEY='sk two'\nPLAIN=value # not a comment\nnot a pair\n=missing\n",
+        );
+
+        assert_eq!(
+            parsed,
+            vec![
+                ("RELAY_KEY".to_string(), "sk-one".to_string()),
+                ("OTHER_KEY".to_string(), "sk two".to_string()),
+                ("PLAIN".to_string(), "value # not a comment".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn load_env_file_never_overrides_process_environment() {
+        const FROM_PROCESS: &str = "CODEX_HELPER_ENV_FILE_TEST_FROM_PROCESS";
+        const FROM_FILE: &str = "CODEX_HELPER_ENV_FILE_TEST_FROM_FILE";
+        let _env_lock = env_lock_blocking();
+        let dir =
+            std::env::temp_dir().join(format!("codex-helper-env-file-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("create temp dir");
+        let path = dir.join(".env");
+        std::fs::write(
+            &path,
+            format!("{FROM_PROCESS}=file-value\n{FROM_FILE}=file-value\n"),
+        )
+        .expect("write env file");
+        unsafe {
+            std::env::set_var(FROM_PROCESS, "process-value");
+            std::env::remove_var(FROM_FILE);
+        }
+
+        let outcome = load_env_file(Some(&path))
+            .expect("load env file")
+            .expect("explicit file is loaded");
+
+        assert_eq!(outcome.path, path);
+        assert_eq!(outcome.applied, 1);
+        assert_eq!(outcome.kept_from_process, 1);
+        assert_eq!(std::env::var(FROM_PROCESS).as_deref(), Ok("process-value"));
+        assert_eq!(std::env::var(FROM_FILE).as_deref(), Ok("file-value"));
+
+        unsafe {
+            std::env::remove_var(FROM_PROCESS);
+            std::env::remove_var(FROM_FILE);
+        }
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn load_env_file_requires_an_explicit_path_to_exist() {
+        let _env_lock = env_lock_blocking();
+        let missing = std::env::temp_dir().join("codex-helper-env-file-test-missing/.env");
+
+        assert!(load_env_file(Some(&missing)).is_err());
+    }
+}
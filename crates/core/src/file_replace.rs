@@ -1109,8 +1109,7 @@ pub fn write_text_file(path: &Path, data: &str) -> Result<()> {
     write_bytes_file(path, data.as_bytes()).with_context(|| format!("atomically write {:?}", path))
 }
 
-#[cfg(test)]
-pub(crate) fn write_text_private_file(path: &Path, data: &str) -> Result<()> {
+pub fn write_text_private_file(path: &Path, data: &str) -> Result<()> {
     write_bytes_file_with_operations_and_prepare_staged(
         path,
         data.as_bytes(),
@@ -320,6 +320,7 @@ mod tests {
                 quota_analytics: Default::default(),
                 stats_5m: Default::default(),
                 stats_1h: Default::default(),
+                request_quotas: Vec::new(),
                 pricing_catalog: Default::default(),
                 service_status: None,
                 provider_balances: Vec::new(),
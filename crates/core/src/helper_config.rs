@@ -9,6 +9,31 @@ fn validate_runtime_provider_shape(
 ) -> Result<()> {
     validate_provider_auth(service_name, provider_name, provider)?;
     validate_provider_concurrency_limits(service_name, provider_name, None, &provider.limits)?;
+    validate_user_agent(
+        &format!("[{service_name}] provider '{provider_name}' user_agent"),
+        provider.user_agent.as_deref(),
+    )?;
+    for name in &provider.strip_request_headers {
+        if http::HeaderName::from_bytes(name.trim().as_bytes()).is_err() {
+            anyhow::bail!(
+                "[{service_name}] provider '{provider_name}' strip_request_headers entry '{name}' is not a valid header name"
+            );
+        }
+    }
+    if let Some(healthcheck) = provider.healthcheck.as_ref() {
+        let field = format!("[{service_name}] provider '{provider_name}' healthcheck");
+        if healthcheck.interval_ms.is_some() {
+            anyhow::bail!(
+                "{field}.interval_ms is not supported per provider; set interval_ms in the top-level [healthcheck] table instead"
+            );
+        }
+        healthcheck.validate_at(&field)?;
+    }
+    if provider.tls.client_cert_path.is_some() != provider.tls.client_key_path.is_some() {
+        anyhow::bail!(
+            "[{service_name}] provider '{provider_name}' must set tls.client_cert_path and tls.client_key_path together"
+        );
+    }
     let mut has_endpoint = false;
     if let Some(_base_url) = provider
         .base_url
@@ -46,6 +71,16 @@ fn validate_runtime_provider_shape(
     Ok(())
 }
 
+fn validate_user_agent(field: &str, value: Option<&str>) -> Result<()> {
+    let Some(value) = value else {
+        return Ok(());
+    };
+    if value.trim().is_empty() || http::HeaderValue::from_str(value).is_err() {
+        anyhow::bail!("{field} must be a non-empty header value without control characters");
+    }
+    Ok(())
+}
+
 fn validate_provider_auth(
     service_name: &str,
     provider_name: &str,
@@ -183,6 +218,49 @@ pub fn validate_helper_config(source: &HelperConfig) -> Result<()> {
         anyhow::bail!("unsupported route graph config version: {}", source.version);
     }
     source.fleet.validate()?;
+    source.healthcheck.validate()?;
+    if let Some(max_effort) = source.effort_guard.max_effort.as_deref()
+        && crate::model_routing::reasoning_effort_rank(max_effort).is_none()
+    {
+        anyhow::bail!(
+            "effort_guard.max_effort `{max_effort}` is invalid; expected one of: {}",
+            crate::model_routing::REASONING_EFFORT_LEVELS.join(", ")
+        );
+    }
+    if source.quota.window_secs == Some(0) {
+        anyhow::bail!("quota.window_secs must be greater than 0");
+    }
+    if source.quota.max_requests == Some(0) || source.quota.max_output_tokens == Some(0) {
+        anyhow::bail!("quota.max_requests and quota.max_output_tokens must be greater than 0");
+    }
+    if source
+        .dashboard
+        .max_recent
+        .is_some_and(|max| max == 0 || max > crate::config::MAX_DASHBOARD_RECENT_REQUESTS)
+    {
+        anyhow::bail!(
+            "dashboard.max_recent must be between 1 and {}",
+            crate::config::MAX_DASHBOARD_RECENT_REQUESTS
+        );
+    }
+    validate_user_agent("http.user_agent", source.http.user_agent.as_deref())?;
+    if source
+        .logging
+        .sample_rate
+        .is_some_and(|rate| !(0.0..=1.0).contains(&rate))
+    {
+        anyhow::bail!("logging.sample_rate must be between 0.0 and 1.0");
+    }
+    if let Some(endpoint) = source.telemetry.otlp_endpoint.as_deref() {
+        crate::telemetry::validate_otlp_endpoint(endpoint)
+            .map_err(|error| anyhow::anyhow!("telemetry.otlp_endpoint: {error}"))?;
+    }
+    if source.cache.models_ttl_ms > crate::config::MAX_MODELS_CACHE_TTL_MS {
+        anyhow::bail!(
+            "cache.models_ttl_ms must be at most {}",
+            crate::config::MAX_MODELS_CACHE_TTL_MS
+        );
+    }
     source
         .codex
         .client_patch
@@ -1,3 +1,4 @@
+pub mod active_history;
 mod auth_resolution;
 pub mod balance;
 pub mod basellm_catalog;
@@ -8,11 +9,13 @@ pub mod codex_integration;
 pub mod codex_onboarding;
 pub mod codex_switch;
 pub mod config;
+pub mod config_lint;
 pub mod control_plane_client;
 pub mod credentials;
 pub mod dashboard_core;
 pub mod doctor;
 pub mod endpoint_health;
+pub mod env_file;
 mod file_replace;
 pub mod filter;
 pub mod fleet;
@@ -24,8 +27,11 @@ pub mod notify;
 pub mod path_identity;
 pub mod policy_actions;
 pub mod pricing;
+pub mod privacy;
 pub mod provider_catalog;
+pub mod provider_schedule;
 pub mod provider_signals;
+pub mod provider_tls;
 pub mod proxy;
 pub mod quota_analytics;
 pub mod quota_pool;
@@ -46,6 +52,11 @@ pub mod service_target;
 pub mod sessions;
 mod sse;
 pub mod state;
+pub mod telemetry;
+#[cfg(test)]
+pub(crate) mod test_support;
+mod upstream_dns;
+pub mod upstream_models;
 pub mod usage;
 pub mod usage_day;
 pub mod usage_format;
@@ -55,5 +66,5 @@ mod windows_file_info;
 
 pub use file_replace::{
     ManagedFileSnapshot, ManagedFileTransaction, ManagedFileTransactionError,
-    read_managed_file_snapshot,
+    read_managed_file_snapshot, write_text_file, write_text_private_file,
 };
@@ -1,12 +1,21 @@
-use std::fs::{self, File, OpenOptions};
+use std::fs::{self, File, OpenOptions, TryLockError};
 use std::io::{self, Write};
 use std::path::{Path, PathBuf};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+const GZIP_EXTENSION: &str = "gz";
+
+/// `logs/runtime.log`: tracing output of the TUI, service and detached `serve` processes.
+pub const RUNTIME_LOG_FILE_NAME: &str = "runtime.log";
+pub const DEFAULT_RUNTIME_LOG_MAX_BYTES: u64 = 20 * 1024 * 1024;
+pub const DEFAULT_RUNTIME_LOG_MAX_FILES: usize = 10;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct LogRetention {
     pub max_bytes: u64,
     pub max_files: usize,
+    /// Gzip each file as it is rotated out; compressed files count toward `max_files`.
+    pub compress_rotated: bool,
 }
 
 impl LogRetention {
@@ -14,19 +23,50 @@ impl LogRetention {
         Self {
             max_bytes,
             max_files,
+            compress_rotated: false,
         }
     }
 
+    pub const fn with_compression(mut self, compress_rotated: bool) -> Self {
+        self.compress_rotated = compress_rotated;
+        self
+    }
+
+    /// Reads a boolean toggle such as `CODEX_HELPER_RUNTIME_LOG_COMPRESS`, keeping `default`
+    /// when the variable is unset or not a recognizable boolean.
+    pub fn with_compression_from_env(self, key: &str, default: bool) -> Self {
+        let compress = std::env::var(key)
+            .ok()
+            .and_then(|value| match value.trim().to_ascii_lowercase().as_str() {
+                "1" | "true" | "yes" | "on" => Some(true),
+                "0" | "false" | "no" | "off" => Some(false),
+                _ => None,
+            })
+            .unwrap_or(default);
+        self.with_compression(compress)
+    }
+
     pub fn from_env(
         max_bytes_key: &str,
         max_files_key: &str,
         default_max_bytes: u64,
         default_max_files: usize,
     ) -> Self {
-        Self {
-            max_bytes: parse_u64_env(max_bytes_key).unwrap_or(default_max_bytes),
-            max_files: parse_usize_env(max_files_key).unwrap_or(default_max_files),
-        }
+        Self::new(
+            parse_u64_env(max_bytes_key).unwrap_or(default_max_bytes),
+            parse_usize_env(max_files_key).unwrap_or(default_max_files),
+        )
+    }
+
+    /// Retention for `runtime.log`, honoring the `CODEX_HELPER_RUNTIME_LOG_*` overrides.
+    pub fn runtime_log_from_env() -> Self {
+        Self::from_env(
+            "CODEX_HELPER_RUNTIME_LOG_MAX_BYTES",
+            "CODEX_HELPER_RUNTIME_LOG_MAX_FILES",
+            DEFAULT_RUNTIME_LOG_MAX_BYTES,
+            DEFAULT_RUNTIME_LOG_MAX_FILES,
+        )
+        .with_compression_from_env("CODEX_HELPER_RUNTIME_LOG_COMPRESS", true)
     }
 
     pub fn enabled(self) -> bool {
@@ -62,6 +102,54 @@ pub fn append_line(path: impl AsRef<Path>, retention: LogRetention, line: &str)
     Ok(())
 }
 
+/// Takes the cross-process lock on `<path>.lock`. The proxy holds it while appending to the
+/// request log, so an offline rewrite can copy the lines appended during its scan and swap the
+/// file in without losing any. Released when the returned file is dropped.
+pub fn lock_log_file(path: impl AsRef<Path>) -> io::Result<File> {
+    let file = open_log_lock_file(path.as_ref())?;
+    file.lock()?;
+    Ok(file)
+}
+
+/// [`lock_log_file`] for the proxy's own appends. A rewrite holds the lock only while it copies
+/// the tail and swaps the file in, so this polls for at most `wait` rather than blocking the
+/// response path; fails with `TimedOut` when the lock is still held.
+pub fn lock_log_file_within(path: impl AsRef<Path>, wait: Duration) -> io::Result<File> {
+    let file = open_log_lock_file(path.as_ref())?;
+    let deadline = Instant::now() + wait;
+    loop {
+        match file.try_lock() {
+            Ok(()) => return Ok(file),
+            Err(TryLockError::WouldBlock) if Instant::now() < deadline => {
+                std::thread::sleep(Duration::from_millis(5));
+            }
+            Err(TryLockError::WouldBlock) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::TimedOut,
+                    format!("log file lock still held after {}ms", wait.as_millis()),
+                ));
+            }
+            Err(TryLockError::Error(error)) => return Err(error),
+        }
+    }
+}
+
+fn open_log_lock_file(path: &Path) -> io::Result<File> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut lock_path = path.as_os_str().to_owned();
+    lock_path.push(".lock");
+    let mut options = OpenOptions::new();
+    options.read(true).write(true).create(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options.open(PathBuf::from(lock_path))
+}
+
 fn open_private_append_file(path: &Path) -> io::Result<File> {
     let existed_before_open = path.exists();
     let mut options = OpenOptions::new();
@@ -153,7 +241,7 @@ fn try_rotate_and_prune_if_needed(
         prune_rotated_logs(path, retention);
         return Ok(());
     }
-    rotate_log_path(path)?;
+    rotate_log_path(path, retention)?;
     prune_rotated_logs(path, retention);
     Ok(())
 }
@@ -264,7 +352,7 @@ impl RotatingLogWriter {
             return;
         }
         self.file.take();
-        if rotate_log_path(&self.path).is_ok() {
+        if rotate_log_path(&self.path, self.retention).is_ok() {
             self.current_len = 0;
             prune_rotated_logs(&self.path, self.retention);
         } else {
@@ -292,13 +380,18 @@ impl Write for RotatingLogWriter {
     }
 }
 
-fn rotate_log_path(path: &Path) -> io::Result<()> {
+fn rotate_log_path(path: &Path, retention: LogRetention) -> io::Result<()> {
     for attempt in 0..100 {
         let rotated_path = rotated_path_for(path, attempt);
-        if rotated_path.exists() {
+        if rotated_path.exists() || gzip_path_for(&rotated_path).exists() {
             continue;
         }
-        return fs::rename(path, rotated_path);
+        fs::rename(path, &rotated_path)?;
+        if retention.compress_rotated {
+            // Compression is best-effort: an uncompressed rotated file is still a valid log.
+            let _ = compress_rotated_log(&rotated_path);
+        }
+        return Ok(());
     }
     Err(io::Error::new(
         io::ErrorKind::AlreadyExists,
@@ -306,6 +399,42 @@ fn rotate_log_path(path: &Path) -> io::Result<()> {
     ))
 }
 
+fn gzip_path_for(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(".");
+    name.push(GZIP_EXTENSION);
+    PathBuf::from(name)
+}
+
+fn compress_rotated_log(rotated_path: &Path) -> io::Result<PathBuf> {
+    let gz_path = gzip_path_for(rotated_path);
+    let result = (|| {
+        let mut source = File::open(rotated_path)?;
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let file = options.open(&gz_path)?;
+        secure_open_private_log_file(&file, &gz_path, false)?;
+        let mut encoder = flate2::write::GzEncoder::new(file, flate2::Compression::fast());
+        io::copy(&mut source, &mut encoder)?;
+        encoder.finish()?.sync_all()
+    })();
+    match result {
+        Ok(()) => {
+            fs::remove_file(rotated_path)?;
+            Ok(gz_path)
+        }
+        Err(error) => {
+            let _ = fs::remove_file(&gz_path);
+            Err(error)
+        }
+    }
+}
+
 fn rotated_path_for(path: &Path, attempt: u32) -> PathBuf {
     let ts = SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -341,7 +470,8 @@ fn is_rotated_log_path(active_path: &Path, candidate_path: &Path) -> bool {
     if active_path.extension().and_then(|s| s.to_str()) == Some("jsonl")
         && let Some(stem) = active_path.file_stem().and_then(|s| s.to_str())
     {
-        return name.starts_with(&format!("{stem}.")) && name.ends_with(".jsonl");
+        return name.starts_with(&format!("{stem}."))
+            && (name.ends_with(".jsonl") || name.ends_with(".jsonl.gz"));
     }
 
     name.starts_with(&format!("{active_name}."))
@@ -509,6 +639,114 @@ mod tests {
         let _ = fs::remove_dir_all(dir);
     }
 
+    fn read_gzip(path: &Path) -> String {
+        let mut text = String::new();
+        io::Read::read_to_string(
+            &mut flate2::read::GzDecoder::new(File::open(path).expect("open gzip log")),
+            &mut text,
+        )
+        .expect("decompress rotated log");
+        text
+    }
+
+    #[test]
+    fn rotating_log_writer_gzips_rotated_files_when_enabled() {
+        let dir = temp_log_dir("writer-compresses");
+        let active = dir.join("runtime.log");
+        let retention = LogRetention::new(64, 2).with_compression(true);
+        let first_line = format!("{}\n", "a".repeat(60));
+        let mut writer = RotatingLogWriter::new(active.clone(), retention);
+
+        writer
+            .write_all(first_line.as_bytes())
+            .expect("write first line");
+        writer
+            .write_all(b"second-line-00\n")
+            .expect("write second line");
+        writer.flush().expect("flush runtime log writer");
+        drop(writer);
+
+        let rotated = collect_rotated_logs(&active);
+        assert_eq!(rotated.len(), 1);
+        let rotated_path = &rotated[0].path;
+        assert_eq!(
+            rotated_path.extension().and_then(|ext| ext.to_str()),
+            Some("gz")
+        );
+        assert_eq!(read_gzip(rotated_path), first_line);
+        assert_eq!(
+            fs::read_to_string(&active).expect("read active log"),
+            "second-line-00\n"
+        );
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(rotated_path)
+                .expect("gzip metadata")
+                .permissions()
+                .mode()
+                & 0o777;
+            assert_eq!(mode, 0o600);
+        }
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn append_line_prunes_compressed_jsonl_rotations_by_max_files() {
+        let dir = temp_log_dir("jsonl-compressed-prune");
+        let active = dir.join("requests.jsonl");
+        let retention = LogRetention::new(64, 2).with_compression(true);
+
+        for idx in 0..4 {
+            write_test_file(&active, 100);
+            append_line(&active, retention, &format!("{{\"idx\":{idx}}}"))
+                .expect("append jsonl line");
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+
+        let rotated = collect_rotated_logs(&active);
+        assert_eq!(
+            rotated.len(),
+            2,
+            "compressed rotations count toward max_files"
+        );
+        for file in &rotated {
+            let name = file
+                .path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .expect("rotated file name");
+            assert!(name.starts_with("requests."));
+            assert!(name.ends_with(".jsonl.gz"), "unexpected rotation {name}");
+        }
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn compression_toggle_reads_boolean_env_values() {
+        const KEY: &str = "CODEX_HELPER_LOCAL_LOG_STORE_TEST_COMPRESS";
+        let _env_lock = crate::test_support::env_lock_blocking();
+        unsafe { std::env::remove_var(KEY) };
+        assert!(
+            LogRetention::new(1, 1)
+                .with_compression_from_env(KEY, true)
+                .compress_rotated
+        );
+        unsafe { std::env::set_var(KEY, "off") };
+        assert!(
+            !LogRetention::new(1, 1)
+                .with_compression_from_env(KEY, true)
+                .compress_rotated
+        );
+        unsafe { std::env::set_var(KEY, "maybe") };
+        assert!(
+            !LogRetention::new(1, 1)
+                .with_compression_from_env(KEY, false)
+                .compress_rotated
+        );
+        unsafe { std::env::remove_var(KEY) };
+    }
+
     #[cfg(unix)]
     #[test]
     fn log_writers_create_and_repair_private_files() {
@@ -586,4 +824,19 @@ mod tests {
         }
         let _ = fs::remove_dir_all(dir);
     }
+
+    #[test]
+    fn bounded_log_lock_times_out_while_a_rewrite_holds_it() {
+        let dir = temp_log_dir("bounded-lock");
+        let path = dir.join("requests.jsonl");
+
+        let rewrite_lock = lock_log_file(&path).expect("take rewrite lock");
+        let error = lock_log_file_within(&path, Duration::from_millis(20))
+            .expect_err("lock is held elsewhere");
+        assert_eq!(error.kind(), io::ErrorKind::TimedOut);
+        drop(rewrite_lock);
+        lock_log_file_within(&path, Duration::from_millis(20)).expect("lock is free again");
+
+        let _ = fs::remove_dir_all(dir);
+    }
 }
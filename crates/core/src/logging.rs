@@ -2,7 +2,7 @@ use std::fs;
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Mutex, OnceLock};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use base64::Engine;
 use serde::{Deserialize, Serialize};
@@ -63,6 +63,9 @@ pub fn request_trace_id(service: &str, request_id: u64) -> String {
 }
 
 const REQUEST_TRACE_ID_PREFIX: &str = "ch-trace:v1:";
+/// How long a request-log append waits for `usage gc` / `usage check --fix` to finish swapping
+/// the file in before it appends without the lock.
+const REQUEST_LOG_LOCK_WAIT: Duration = Duration::from_millis(200);
 
 pub(crate) fn request_trace_id_for_boot(
     boot_uuid: &uuid::Uuid,
@@ -407,8 +410,16 @@ pub struct CodexBridgeLog {
     pub strips_client_auth: bool,
 }
 
+/// Schema version stamped on every `requests.jsonl` line as `v`.
+///
+/// Bump it whenever a field is renamed, removed or changes meaning, and teach
+/// [`crate::usage::request_log::RequestLogRecord`] how to migrate the previous version.
+pub const REQUEST_LOG_SCHEMA_VERSION: u32 = 1;
+
 #[derive(Debug, Serialize)]
 pub struct RequestLog<'a> {
+    /// Always [`REQUEST_LOG_SCHEMA_VERSION`]; lines written before versioning have no `v`.
+    pub v: u32,
     pub timestamp_ms: u64,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub request_id: Option<u64>,
@@ -436,6 +447,9 @@ pub struct RequestLog<'a> {
     pub session_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub session_identity_source: Option<SessionIdentitySource>,
+    /// Optional `x-codex-helper-tenant` value for shared-instance accounting.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tenant: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cwd: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -448,6 +462,16 @@ pub struct RequestLog<'a> {
     pub codex_bridge: Option<CodexBridgeLog>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub usage: Option<UsageMetrics>,
+    /// Total USD cost priced when the request finished; later price changes do not alter it.
+    /// Absent when the model had no price or the request reported no usage.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cost_usd: Option<String>,
+    /// The provider's id for the final upstream response, for support tickets.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub upstream_request_id: Option<String>,
+    /// Answered from the `cache.models_ttl_ms` cache; no upstream was contacted.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub cache_hit: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub http_debug: Option<HttpDebugLog>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -462,6 +486,10 @@ pub struct RequestLog<'a> {
     pub provider_signals: Vec<ProviderSignal>,
     #[serde(default, skip_serializing_if = "policy_actions_is_empty")]
     pub policy_actions: Vec<PolicyAction>,
+    /// `logging.sample_rate` this entry survived; only set on sampled successes, so readers can
+    /// scale counts back up.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub log_sample_rate: Option<f64>,
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -497,6 +525,10 @@ fn provider_signals_is_empty(value: &[ProviderSignal]) -> bool {
     value.is_empty()
 }
 
+fn is_false(value: &bool) -> bool {
+    !*value
+}
+
 fn policy_actions_is_empty(value: &[PolicyAction]) -> bool {
     value.is_empty()
 }
@@ -532,6 +564,9 @@ pub struct RouteAttemptLog {
     pub provider_endpoint_key: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub preference_group: Option<u32>,
+    /// The attempt's provider ranks below the first provider of the route plan.
+    #[serde(default, skip_serializing_if = "bool_is_false")]
+    pub below_top_provider: bool,
     #[serde(default, skip_serializing_if = "route_attempt_route_path_is_empty")]
     pub route_path: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -542,6 +577,11 @@ pub struct RouteAttemptLog {
     pub provider_max_attempts: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub upstream_max_attempts: Option<u32>,
+    /// Distinct upstreams tried by this request so far, counting this attempt's upstream.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upstreams_tried: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_upstreams_tried: Option<u32>,
     #[serde(
         default,
         skip_serializing_if = "route_attempt_avoided_candidate_indices_is_empty"
@@ -568,12 +608,25 @@ pub struct RouteAttemptLog {
     pub cooldown_secs: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cooldown_reason: Option<String>,
+    /// Upstream `Retry-After` wait honored before retrying the same upstream, after the cap.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_after_ms: Option<u64>,
     #[serde(default, skip_serializing_if = "provider_signals_is_empty")]
     pub provider_signals: Vec<ProviderSignal>,
     #[serde(default, skip_serializing_if = "policy_actions_is_empty")]
     pub policy_actions: Vec<PolicyAction>,
     #[serde(default, skip_serializing_if = "bool_is_false")]
     pub skipped: bool,
+    /// The attempt hit a connection reset and was retried on the same upstream immediately,
+    /// outside the normal retry plan.
+    #[serde(default, skip_serializing_if = "bool_is_false")]
+    pub fast_retry: bool,
+    /// Client request headers dropped instead of forwarded to this upstream (names only).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stripped_request_headers: Vec<String>,
+    /// Set when an operator upstream pin chose this attempt's endpoint.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pinned_by: Option<crate::routing_ir::UpstreamPinScope>,
     #[serde(skip)]
     pub(crate) http_debug: Option<HttpDebugLog>,
 }
@@ -606,6 +659,9 @@ fn route_attempt_code(decision: &str, error_class: Option<&str>) -> &'static str
     ) {
         return "failed_reasoning_guard";
     }
+    if error_class == Some("upstream_tls_certificate_error") {
+        return "failed_tls_certificate";
+    }
     match decision {
         "selected" => "selected",
         "observed" => "observed",
@@ -706,7 +762,8 @@ fn request_log_options() -> RequestLogOptions {
             "CODEX_HELPER_REQUEST_LOG_MAX_FILES",
             50 * 1024 * 1024,
             10,
-        );
+        )
+        .with_compression_from_env("CODEX_HELPER_REQUEST_LOG_COMPRESS", true);
         let only_errors = env_bool("CODEX_HELPER_REQUEST_LOG_ONLY_ERRORS");
         RequestLogOptions {
             retention,
@@ -719,6 +776,33 @@ pub fn request_log_retention() -> LogRetention {
     request_log_options().retention
 }
 
+/// Whether a request is subject to sampling at all: errors and retried requests never are.
+fn request_log_samplable(status_code: u16, retry: Option<&RetryInfo>) -> bool {
+    is_logical_request_success_status(status_code) && retry.is_none_or(|retry| retry.attempts <= 1)
+}
+
+/// Deterministic per-request draw in `[0, 1)` so the same request id always samples the same way.
+fn request_log_sample_draw(request_id: u64) -> f64 {
+    // splitmix64 finalizer: spreads sequential ids evenly over the range.
+    let mut z = request_id.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^= z >> 31;
+    (z >> 11) as f64 / (1u64 << 53) as f64
+}
+
+fn request_log_sampled_out(
+    rate: Option<f64>,
+    request_id: Option<u64>,
+    status_code: u16,
+    retry: Option<&RetryInfo>,
+) -> bool {
+    let (Some(rate), Some(request_id)) = (rate, request_id) else {
+        return false;
+    };
+    request_log_samplable(status_code, retry) && request_log_sample_draw(request_id) >= rate
+}
+
 fn append_json_line(path: &PathBuf, opt: RequestLogOptions, line: &str) -> bool {
     append_line(path, opt.retention, line).is_ok()
 }
@@ -738,20 +822,30 @@ pub(crate) fn log_committed_request_with_debug(
     upstream_origin: Option<String>,
     session_id: Option<String>,
     session_identity_source: Option<SessionIdentitySource>,
+    tenant: Option<String>,
     cwd: Option<String>,
     model: Option<String>,
     reasoning_effort: Option<String>,
     service_tier: ServiceTierLog,
     codex_bridge: Option<CodexBridgeLog>,
     usage: Option<UsageMetrics>,
+    cost_usd: Option<String>,
+    upstream_request_id: Option<String>,
+    cache_hit: bool,
     route_decision: Option<RouteDecisionProvenance>,
     retry: Option<RetryInfo>,
     http_debug: Option<HttpDebugLog>,
+    sample_rate: Option<f64>,
 ) {
     let opt = request_log_options();
     if opt.only_errors && is_logical_request_success_status(status_code) {
         return;
     }
+    if request_log_sampled_out(sample_rate, request_id, status_code, retry.as_ref()) {
+        return;
+    }
+    let log_sample_rate =
+        sample_rate.filter(|_| request_log_samplable(status_code, retry.as_ref()));
 
     let ts = now_ms();
     let trace_id = request_id.map(|id| request_trace_id(service, id));
@@ -942,6 +1036,7 @@ pub(crate) fn log_committed_request_with_debug(
     let policy_actions = policy_actions_from_retry(retry.as_ref());
 
     let entry = RequestLog {
+        v: REQUEST_LOG_SCHEMA_VERSION,
         timestamp_ms: ts,
         request_id,
         trace_id,
@@ -957,12 +1052,16 @@ pub(crate) fn log_committed_request_with_debug(
         upstream_origin,
         session_id,
         session_identity_source,
+        tenant,
         cwd,
         model,
         reasoning_effort,
         service_tier,
         codex_bridge,
         usage,
+        cost_usd,
+        upstream_request_id,
+        cache_hit,
         http_debug: http_debug_for_main,
         http_debug_ref,
         http_debug_attempt_refs,
@@ -970,9 +1069,20 @@ pub(crate) fn log_committed_request_with_debug(
         retry,
         provider_signals,
         policy_actions,
+        log_sample_rate,
     };
 
     if let Ok(line) = serde_json::to_string(&entry) {
+        // `usage gc` and `usage check --fix` rewrite this file from another process.
+        let _file_lock =
+            crate::local_log_store::lock_log_file_within(&log_file_path, REQUEST_LOG_LOCK_WAIT)
+                .inspect_err(|error| {
+                    tracing::warn!(
+                        path = %log_file_path.display(),
+                        error = %error,
+                        "appending to the request log without its rewrite lock"
+                    );
+                });
         let _ = append_json_line(&log_file_path, opt, &line);
     }
 
@@ -41,6 +41,7 @@ fn request_trace_id_changes_with_boot_uuid() {
 #[test]
 fn request_log_serializes_request_id_when_present() {
     let value = serde_json::to_value(RequestLog {
+        v: REQUEST_LOG_SCHEMA_VERSION,
         timestamp_ms: 1,
         request_id: Some(42),
         trace_id: Some(request_trace_id("codex", 42)),
@@ -56,6 +57,7 @@ fn request_log_serializes_request_id_when_present() {
         upstream_origin: Some("https://example.com".to_string()),
         session_id: Some("sid-1".to_string()),
         session_identity_source: Some(crate::state::SessionIdentitySource::Header),
+        tenant: None,
         cwd: Some("/workdir".to_string()),
         model: Some("gpt-5".to_string()),
         reasoning_effort: Some("medium".to_string()),
@@ -66,6 +68,9 @@ fn request_log_serializes_request_id_when_present() {
         },
         codex_bridge: None,
         usage: None,
+        cost_usd: None,
+        upstream_request_id: Some("req_upstream_7".to_string()),
+        cache_hit: false,
         http_debug: None,
         http_debug_ref: None,
         http_debug_attempt_refs: Vec::new(),
@@ -73,9 +78,14 @@ fn request_log_serializes_request_id_when_present() {
         retry: None,
         provider_signals: Vec::new(),
         policy_actions: Vec::new(),
+        log_sample_rate: None,
     })
     .expect("serialize request log");
 
+    assert_eq!(
+        value["v"].as_u64(),
+        Some(u64::from(REQUEST_LOG_SCHEMA_VERSION))
+    );
     assert_eq!(value["request_id"].as_u64(), Some(42));
     assert_eq!(
         value["trace_id"].as_str(),
@@ -83,11 +93,17 @@ fn request_log_serializes_request_id_when_present() {
     );
     assert_eq!(value["model"].as_str(), Some("gpt-5"));
     assert_eq!(value["session_identity_source"].as_str(), Some("header"));
+    assert_eq!(
+        value["upstream_request_id"].as_str(),
+        Some("req_upstream_7")
+    );
+    assert!(value.get("cache_hit").is_none());
 }
 
 #[test]
 fn request_log_serializes_canonical_provider_endpoint_identity() {
     let value = serde_json::to_value(RequestLog {
+        v: REQUEST_LOG_SCHEMA_VERSION,
         timestamp_ms: 1,
         request_id: Some(42),
         trace_id: Some(request_trace_id("codex", 42)),
@@ -103,12 +119,16 @@ fn request_log_serializes_canonical_provider_endpoint_identity() {
         upstream_origin: Some("https://input.example".to_string()),
         session_id: Some("sid-1".to_string()),
         session_identity_source: None,
+        tenant: None,
         cwd: Some("/workdir".to_string()),
         model: None,
         reasoning_effort: Some("medium".to_string()),
         service_tier: ServiceTierLog::default(),
         codex_bridge: None,
         usage: None,
+        cost_usd: None,
+        upstream_request_id: None,
+        cache_hit: false,
         http_debug: None,
         http_debug_ref: None,
         http_debug_attempt_refs: Vec::new(),
@@ -116,6 +136,7 @@ fn request_log_serializes_canonical_provider_endpoint_identity() {
         retry: None,
         provider_signals: Vec::new(),
         policy_actions: Vec::new(),
+        log_sample_rate: None,
     })
     .expect("serialize request log");
 
@@ -136,6 +157,7 @@ fn request_log_serializes_canonical_provider_endpoint_identity() {
 #[test]
 fn request_log_serializes_codex_bridge_metadata() {
     let value = serde_json::to_value(RequestLog {
+        v: REQUEST_LOG_SCHEMA_VERSION,
         timestamp_ms: 1,
         request_id: Some(42),
         trace_id: Some(request_trace_id("codex", 42)),
@@ -151,6 +173,7 @@ fn request_log_serializes_codex_bridge_metadata() {
         upstream_origin: Some("https://relay.example".to_string()),
         session_id: Some("sid-1".to_string()),
         session_identity_source: Some(crate::state::SessionIdentitySource::PromptCacheKey),
+        tenant: None,
         cwd: Some("/workdir".to_string()),
         model: Some("gpt-5".to_string()),
         reasoning_effort: Some("medium".to_string()),
@@ -164,6 +187,9 @@ fn request_log_serializes_codex_bridge_metadata() {
             strips_client_auth: true,
         }),
         usage: None,
+        cost_usd: None,
+        upstream_request_id: None,
+        cache_hit: false,
         http_debug: None,
         http_debug_ref: None,
         http_debug_attempt_refs: Vec::new(),
@@ -171,6 +197,7 @@ fn request_log_serializes_codex_bridge_metadata() {
         retry: None,
         provider_signals: Vec::new(),
         policy_actions: Vec::new(),
+        log_sample_rate: None,
     })
     .expect("serialize request log");
 
@@ -355,3 +382,52 @@ fn retry_info_preserves_structured_route_attempt_endpoint_identity() {
         Some("codex/primary/responses")
     );
 }
+
+#[test]
+fn request_log_sampling_keeps_errors_and_retries_and_samples_successes_at_rate() {
+    let retried = RetryInfo {
+        attempts: 2,
+        route_attempts: Vec::new(),
+    };
+    let single_attempt = RetryInfo {
+        attempts: 1,
+        route_attempts: Vec::new(),
+    };
+    for id in 0..1_000 {
+        assert!(!request_log_sampled_out(Some(0.0), Some(id), 500, None));
+        assert!(!request_log_sampled_out(Some(0.0), Some(id), 429, None));
+        assert!(!request_log_sampled_out(
+            Some(0.0),
+            Some(id),
+            200,
+            Some(&retried)
+        ));
+        assert!(!request_log_sampled_out(None, Some(id), 200, None));
+        assert!(request_log_sampled_out(
+            Some(0.0),
+            Some(id),
+            200,
+            Some(&single_attempt)
+        ));
+    }
+
+    let total = 20_000u64;
+    let kept = (0..total)
+        .filter(|id| !request_log_sampled_out(Some(0.25), Some(*id), 200, None))
+        .count() as f64;
+    let share = kept / total as f64;
+    assert!((0.23..0.27).contains(&share), "kept share {share}");
+    assert_eq!(
+        request_log_sampled_out(Some(0.25), Some(7), 200, None),
+        request_log_sampled_out(Some(0.25), Some(7), 200, None),
+        "sampling is deterministic per request id"
+    );
+}
+
+#[test]
+fn logging_config_reports_sampling_only_below_one() {
+    let config = |sample_rate| crate::config::LoggingConfig { sample_rate };
+    assert_eq!(config(None).active_sample_rate(), None);
+    assert_eq!(config(Some(1.0)).active_sample_rate(), None);
+    assert_eq!(config(Some(0.1)).active_sample_rate(), Some(0.1));
+}
@@ -2,6 +2,8 @@ use std::collections::{BTreeMap, HashMap};
 use std::error::Error;
 use std::fmt;
 
+use crate::config::{EffortGuardAction, EffortGuardConfig, HelperConfig};
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 struct WildcardPattern {
     prefix: String,
@@ -259,6 +261,90 @@ pub fn is_model_supported(
     false
 }
 
+/// Reasoning effort levels from cheapest to most expensive.
+pub const REASONING_EFFORT_LEVELS: [&str; 8] = [
+    "none", "minimal", "low", "medium", "high", "xhigh", "max", "ultra",
+];
+
+/// Efforts at or above this level multiply spend enough to warn about at startup.
+pub const HIGH_COST_REASONING_EFFORT: &str = "xhigh";
+
+pub fn reasoning_effort_rank(effort: &str) -> Option<usize> {
+    let effort = effort.trim();
+    REASONING_EFFORT_LEVELS
+        .iter()
+        .position(|level| level.eq_ignore_ascii_case(effort))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EffortClamp<'a> {
+    /// Within the ceiling, no ceiling configured, or an effort name the guard does not know.
+    PassThrough,
+    /// Above the ceiling; forward the request with the ceiling instead.
+    Downgrade { ceiling: &'a str },
+    /// Above the ceiling; refuse the request.
+    Reject { ceiling: &'a str },
+}
+
+pub fn clamp_reasoning_effort<'a>(
+    requested: &str,
+    guard: &'a EffortGuardConfig,
+) -> EffortClamp<'a> {
+    let Some(ceiling) = guard.max_effort.as_deref().map(str::trim) else {
+        return EffortClamp::PassThrough;
+    };
+    match (
+        reasoning_effort_rank(requested),
+        reasoning_effort_rank(ceiling),
+    ) {
+        (Some(requested), Some(limit)) if requested > limit => match guard.action {
+            EffortGuardAction::Downgrade => EffortClamp::Downgrade { ceiling },
+            EffortGuardAction::Reject => EffortClamp::Reject { ceiling },
+        },
+        _ => EffortClamp::PassThrough,
+    }
+}
+
+/// Startup warnings for profiles that pin a high-cost reasoning effort on every request.
+pub fn effort_override_warnings(config: &HelperConfig) -> Vec<String> {
+    let high_cost = reasoning_effort_rank(HIGH_COST_REASONING_EFFORT).unwrap_or(usize::MAX);
+    let mut warnings = Vec::new();
+    for (service_name, service) in [("codex", &config.codex), ("claude", &config.claude)] {
+        for profile_name in service.profiles.keys() {
+            let Ok(profile) = crate::config::resolve_service_profile_from_catalog(
+                &service.profiles,
+                profile_name,
+            ) else {
+                continue;
+            };
+            let Some(effort) = profile.reasoning_effort.as_deref() else {
+                continue;
+            };
+            if reasoning_effort_rank(effort).is_none_or(|rank| rank < high_cost) {
+                continue;
+            }
+            let scope = if service.default_profile.as_deref() == Some(profile_name.as_str()) {
+                "default profile"
+            } else {
+                "profile"
+            };
+            let outcome = match clamp_reasoning_effort(effort, &config.effort_guard) {
+                EffortClamp::PassThrough => "no effort_guard.max_effort limits it".to_string(),
+                EffortClamp::Downgrade { ceiling } => {
+                    format!("effort_guard downgrades it to `{ceiling}`")
+                }
+                EffortClamp::Reject { ceiling } => {
+                    format!("effort_guard rejects it (max_effort `{ceiling}`)")
+                }
+            };
+            warnings.push(format!(
+                "{service_name} {scope} `{profile_name}` sets reasoning_effort `{effort}`, which multiplies request cost; {outcome}"
+            ));
+        }
+    }
+    warnings
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -319,4 +405,68 @@ mod tests {
             second.effective_model("gpt-5-mini")
         );
     }
+
+    fn guard(max_effort: &str, action: EffortGuardAction) -> EffortGuardConfig {
+        EffortGuardConfig {
+            max_effort: Some(max_effort.to_string()),
+            action,
+        }
+    }
+
+    #[test]
+    fn clamp_reasoning_effort_downgrades_or_rejects_above_the_ceiling() {
+        let downgrade = guard("high", EffortGuardAction::Downgrade);
+        assert_eq!(
+            clamp_reasoning_effort("xhigh", &downgrade),
+            EffortClamp::Downgrade { ceiling: "high" }
+        );
+        assert_eq!(
+            clamp_reasoning_effort("ULTRA", &downgrade),
+            EffortClamp::Downgrade { ceiling: "high" }
+        );
+        assert_eq!(
+            clamp_reasoning_effort("max", &guard("high", EffortGuardAction::Reject)),
+            EffortClamp::Reject { ceiling: "high" }
+        );
+    }
+
+    #[test]
+    fn clamp_reasoning_effort_passes_through_within_ceiling_or_without_guard() {
+        let downgrade = guard("high", EffortGuardAction::Downgrade);
+        for effort in ["none", "low", "medium", "high", "High", "future-effort"] {
+            assert_eq!(
+                clamp_reasoning_effort(effort, &downgrade),
+                EffortClamp::PassThrough,
+                "effort={effort}"
+            );
+        }
+        assert_eq!(
+            clamp_reasoning_effort("ultra", &EffortGuardConfig::default()),
+            EffortClamp::PassThrough
+        );
+    }
+
+    #[test]
+    fn effort_override_warnings_flag_high_cost_profiles() {
+        let mut config = HelperConfig::default();
+        config.codex.default_profile = Some("deep".to_string());
+        for (name, effort) in [("deep", "xhigh"), ("quick", "low")] {
+            config.codex.profiles.insert(
+                name.to_string(),
+                crate::config::ServiceControlProfile {
+                    reasoning_effort: Some(effort.to_string()),
+                    ..Default::default()
+                },
+            );
+        }
+        config.effort_guard = guard("high", EffortGuardAction::Downgrade);
+
+        assert_eq!(
+            effort_override_warnings(&config),
+            vec![
+                "codex default profile `deep` sets reasoning_effort `xhigh`, which multiplies request cost; effort_guard downgrades it to `high`"
+                    .to_string()
+            ]
+        );
+    }
 }
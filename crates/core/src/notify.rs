@@ -7,9 +7,16 @@ use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use serde::{Deserialize, Serialize};
 use tokio::time::sleep;
 
-use crate::config::{NotifyConfig, NotifyPolicyConfig, load_config, proxy_home_dir};
+use crate::config::{
+    NotifyConfig, NotifyExecCallbackConfig, NotifyExecConfig, NotifyMergeStrategy,
+    NotifyPolicyConfig, load_config, proxy_home_dir,
+};
 use crate::dashboard_core::OperatorRequestSummary;
 use crate::file_replace::write_bytes_file_async;
+use crate::local_log_store::{LogRetention, RUNTIME_LOG_FILE_NAME, append_line};
+
+/// Codex notification type that is merged, rate-limited and toasted.
+pub const AGENT_TURN_COMPLETE_TYPE: &str = "agent-turn-complete";
 
 #[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
@@ -207,7 +214,7 @@ async fn queue_event_and_spawn_flush(
     state.pending.push(event);
     save_state(&state).await?;
 
-    if cfg.enabled && (cfg.system.enabled || (cfg.exec.enabled && !cfg.exec.command.is_empty())) {
+    if cfg.enabled && (cfg.system.enabled || cfg.exec.has_callbacks()) {
         spawn_flush_process(force_toast)?;
     }
     Ok(())
@@ -226,14 +233,14 @@ pub async fn handle_codex_notify(
     let notify_cfg = cfg.notify;
     let system_enabled =
         force_toast || (notify_cfg.enabled && notify_cfg.system.enabled && !no_toast);
-    let exec_enabled =
-        notify_cfg.enabled && notify_cfg.exec.enabled && !notify_cfg.exec.command.is_empty();
+    let exec_enabled = notify_cfg.enabled && notify_cfg.exec.has_callbacks();
 
     if !system_enabled && !exec_enabled {
         return Ok(());
     }
 
-    let payload: CodexNotificationInput = match serde_json::from_str(&payload) {
+    let raw_payload = payload;
+    let payload: CodexNotificationInput = match serde_json::from_str(&raw_payload) {
         Ok(v) => v,
         Err(err) => {
             eprintln!("codex-helper notify: failed to parse notification JSON: {err}");
@@ -242,6 +249,18 @@ pub async fn handle_codex_notify(
     };
 
     if payload.r#type != CodexNotificationType::AgentTurnComplete {
+        // Other notification types bypass merging and only reach callbacks that opt in by type.
+        if exec_enabled && let Some(notification_type) = raw_notification_type(&raw_payload) {
+            let env = NotifyExecEnv {
+                notification_type,
+                count: 1,
+                thread_id: payload.thread_id.clone(),
+                turn_id: payload.turn_id.clone(),
+                cwd: payload.cwd.clone(),
+                duration_ms: None,
+            };
+            run_exec_callbacks(&notify_cfg.exec, &env, &raw_payload);
+        }
         return Ok(());
     }
 
@@ -311,6 +330,27 @@ pub async fn handle_codex_notify(
     queue_event_and_spawn_flush(&cfg_for_queue, event, force_toast).await
 }
 
+pub async fn handle_notify_test() -> anyhow::Result<()> {
+    let outcome = tokio::task::spawn_blocking(send_test_notification).await?;
+    if !outcome.is_delivered() {
+        anyhow::bail!("{}", outcome.message());
+    }
+    println!("{}", outcome.message());
+    match load_config().await {
+        Ok(cfg) => {
+            let blockers = notify_config_toast_blockers(&cfg.notify);
+            if !blockers.is_empty() {
+                println!(
+                    "note: Codex events will not show notifications while {}",
+                    blockers.join(" and ")
+                );
+            }
+        }
+        Err(err) => println!("note: could not read notify config: {err}"),
+    }
+    Ok(())
+}
+
 pub async fn handle_codex_flush() -> anyhow::Result<()> {
     let cfg = load_config().await?;
     let notify_cfg = cfg.notify;
@@ -326,67 +366,43 @@ pub async fn handle_codex_flush() -> anyhow::Result<()> {
     for _ in 0..20 {
         let _lock = acquire_notify_lock().await?;
         let mut state = load_state().await.unwrap_or_default();
-        if state.pending.is_empty() {
-            return Ok(());
-        }
-
         let now = now_ms();
-        let first_pending = state
-            .pending
-            .iter()
-            .map(|e| e.queued_at_ms)
-            .min()
-            .unwrap_or(now);
-        let due_ms = first_pending.saturating_add(notify_cfg.policy.merge_window_ms);
-
-        if now < due_ms {
-            drop(state);
-            sleep(Duration::from_millis((due_ms - now).min(60_000))).await;
-            continue;
-        }
-
-        if let Some(last) = state.last_toast_ms
-            && now.saturating_sub(last) < notify_cfg.policy.global_cooldown_ms
-        {
-            let wait = notify_cfg.policy.global_cooldown_ms - now.saturating_sub(last);
-            drop(state);
-            sleep(Duration::from_millis(wait.min(60_000))).await;
-            continue;
-        }
-
-        // Apply per-thread cooldown and prepare toast batch.
-        state.pending.sort_by_key(|e| e.ended_at_ms);
-        let mut send: Vec<QueuedEvent> = Vec::new();
-        let mut suppressed = 0u64;
-        for e in state.pending.iter() {
-            let last = state.per_thread_last_toast_ms.get(&e.thread_id).copied();
-            if let Some(last) = last
-                && now.saturating_sub(last) < notify_cfg.policy.per_thread_cooldown_ms
-            {
-                suppressed = suppressed.saturating_add(1);
+        let (send, suppressed) = match plan_flush(&mut state, &notify_cfg.policy, now) {
+            FlushPlan::Empty => return Ok(()),
+            FlushPlan::Wait(wait_ms) => {
+                drop(state);
+                sleep(Duration::from_millis(wait_ms.min(60_000))).await;
                 continue;
             }
-            send.push(e.clone());
-        }
+            FlushPlan::Send { send, suppressed } => (send, suppressed),
+        };
 
         if send.is_empty() {
-            state.pending.clear();
             state.suppressed_since_last_toast =
                 state.suppressed_since_last_toast.saturating_add(suppressed);
             save_state(&state).await?;
-            return Ok(());
+            if state.pending.is_empty() {
+                return Ok(());
+            }
+            continue;
         }
 
         let system_enabled = notify_cfg.system.enabled || force_toast;
-        let exec_enabled = notify_cfg.exec.enabled && !notify_cfg.exec.command.is_empty();
+        let exec_enabled = notify_cfg.exec.has_callbacks();
         if !system_enabled && !exec_enabled {
             state.pending.clear();
             save_state(&state).await?;
             return Ok(());
         }
 
-        let title = render_title(send.len(), suppressed, state.suppressed_since_last_toast);
-        let body = render_body(&send);
+        let strategy = notify_cfg.policy.strategy;
+        let title = render_title(
+            send.len(),
+            suppressed,
+            state.suppressed_since_last_toast,
+            strategy,
+        );
+        let body = render_body(&send, strategy);
         let aggregated = serde_json::json!({
             "type": "codex-helper-merged-agent-turn-complete",
             "count": send.len(),
@@ -400,8 +416,17 @@ pub async fn handle_codex_flush() -> anyhow::Result<()> {
         if system_enabled && let Err(err) = send_system_notification(&title, &body) {
             eprintln!("codex-helper notify: failed to show system notification: {err}");
         }
-        if exec_enabled && let Err(err) = run_exec_callback(&notify_cfg.exec.command, &aggregated) {
-            eprintln!("codex-helper notify: exec callback failed: {err}");
+        if exec_enabled {
+            let latest = send.last();
+            let env = NotifyExecEnv {
+                notification_type: AGENT_TURN_COMPLETE_TYPE.to_string(),
+                count: send.len(),
+                thread_id: latest.map(|e| e.thread_id.clone()),
+                turn_id: latest.and_then(|e| e.turn_id.clone()),
+                cwd: latest.and_then(|e| e.cwd.clone()),
+                duration_ms: latest.map(|e| e.duration_ms),
+            };
+            run_exec_callbacks(&notify_cfg.exec, &env, &aggregated);
         }
 
         state.last_toast_ms = Some(now);
@@ -411,19 +436,78 @@ pub async fn handle_codex_flush() -> anyhow::Result<()> {
                 .insert(e.thread_id.clone(), now);
         }
         state.suppressed_since_last_toast = 0;
-        state.pending.clear();
         save_state(&state).await?;
-        return Ok(());
+        if state.pending.is_empty() {
+            return Ok(());
+        }
     }
 
     Ok(())
 }
 
-fn render_title(count: usize, suppressed_in_batch: u64, suppressed_since_last: u64) -> String {
-    let mut title = if count == 1 {
-        "Codex: turn complete".to_string()
-    } else {
-        format!("Codex: {count} turns complete")
+#[derive(Debug, PartialEq, Eq)]
+enum FlushPlan {
+    /// Nothing is queued.
+    Empty,
+    /// Retry after this many milliseconds (merge window still open or global cooldown active).
+    Wait(u64),
+    /// A batch was taken off the queue; events past `max_merged` stay pending for the next flush.
+    Send {
+        send: Vec<QueuedEvent>,
+        suppressed: u64,
+    },
+}
+
+/// Decides what a flush does at `now`, draining the batch it sends from `state.pending`.
+fn plan_flush(state: &mut NotifyState, policy: &NotifyPolicyConfig, now: u64) -> FlushPlan {
+    let Some(first_pending) = state.pending.iter().map(|e| e.queued_at_ms).min() else {
+        return FlushPlan::Empty;
+    };
+    let batch_full = policy.max_merged > 0 && state.pending.len() >= policy.max_merged;
+    let due_ms = first_pending.saturating_add(policy.merge_window_ms);
+    if now < due_ms && !batch_full {
+        return FlushPlan::Wait(due_ms - now);
+    }
+
+    if let Some(last) = state.last_toast_ms
+        && now.saturating_sub(last) < policy.global_cooldown_ms
+    {
+        return FlushPlan::Wait(policy.global_cooldown_ms - now.saturating_sub(last));
+    }
+
+    // Apply per-thread cooldown and prepare toast batch.
+    state.pending.sort_by_key(|e| e.ended_at_ms);
+    let take = match policy.max_merged {
+        0 => state.pending.len(),
+        max => max.min(state.pending.len()),
+    };
+    let mut send: Vec<QueuedEvent> = Vec::new();
+    let mut suppressed = 0u64;
+    for e in state.pending.drain(..take) {
+        let last = state.per_thread_last_toast_ms.get(&e.thread_id).copied();
+        if let Some(last) = last
+            && now.saturating_sub(last) < policy.per_thread_cooldown_ms
+        {
+            suppressed = suppressed.saturating_add(1);
+            continue;
+        }
+        send.push(e);
+    }
+    FlushPlan::Send { send, suppressed }
+}
+
+fn render_title(
+    count: usize,
+    suppressed_in_batch: u64,
+    suppressed_since_last: u64,
+    strategy: NotifyMergeStrategy,
+) -> String {
+    let mut title = match (strategy, count) {
+        (_, 1) => "Codex: turn complete".to_string(),
+        (NotifyMergeStrategy::SummarizeCount, _) => format!("Codex: {count} turns complete"),
+        (NotifyMergeStrategy::LatestOnly, _) => {
+            format!("Codex: turn complete (latest of {count})")
+        }
     };
     let total_suppressed = suppressed_in_batch.saturating_add(suppressed_since_last);
     if total_suppressed > 0 {
@@ -432,9 +516,13 @@ fn render_title(count: usize, suppressed_in_batch: u64, suppressed_since_last: u
     title
 }
 
-fn render_body(events: &[QueuedEvent]) -> String {
+fn render_body(events: &[QueuedEvent], strategy: NotifyMergeStrategy) -> String {
+    let shown = match strategy {
+        NotifyMergeStrategy::SummarizeCount => 3,
+        NotifyMergeStrategy::LatestOnly => 1,
+    };
     let mut lines: Vec<String> = Vec::new();
-    for e in events.iter().rev().take(3) {
+    for e in events.iter().rev().take(shown) {
         let dur_s = (e.duration_ms as f64 / 1000.0).max(0.0);
         let cwd = e
             .cwd
@@ -447,30 +535,119 @@ fn render_body(events: &[QueuedEvent]) -> String {
             lines.push(format!("{cwd} ({dur_s:.1}s)"));
         }
     }
-    if events.len() > 3 {
-        lines.push(format!("+{} more", events.len() - 3));
+    if strategy == NotifyMergeStrategy::SummarizeCount && events.len() > shown {
+        lines.push(format!("+{} more", events.len() - shown));
     }
     lines.join("\n")
 }
 
-fn run_exec_callback(command: &[String], input_json: &str) -> anyhow::Result<()> {
-    if command.is_empty() {
-        return Ok(());
+fn raw_notification_type(payload: &str) -> Option<String> {
+    let value: serde_json::Value = serde_json::from_str(payload).ok()?;
+    value
+        .get("type")
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+}
+
+/// Key notification fields exposed to exec callbacks as `CODEX_NOTIFY_*` env vars.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct NotifyExecEnv {
+    notification_type: String,
+    count: usize,
+    thread_id: Option<String>,
+    turn_id: Option<String>,
+    cwd: Option<String>,
+    duration_ms: Option<u64>,
+}
+
+impl NotifyExecEnv {
+    fn vars(&self) -> Vec<(&'static str, String)> {
+        let mut vars = vec![
+            ("CODEX_NOTIFY_TYPE", self.notification_type.clone()),
+            ("CODEX_NOTIFY_COUNT", self.count.to_string()),
+        ];
+        if let Some(v) = self.thread_id.as_ref() {
+            vars.push(("CODEX_NOTIFY_THREAD_ID", v.clone()));
+        }
+        if let Some(v) = self.turn_id.as_ref() {
+            vars.push(("CODEX_NOTIFY_TURN_ID", v.clone()));
+        }
+        if let Some(v) = self.cwd.as_ref() {
+            vars.push(("CODEX_NOTIFY_CWD", v.clone()));
+        }
+        if let Some(v) = self.duration_ms {
+            vars.push(("CODEX_NOTIFY_DURATION_MS", v.to_string()));
+        }
+        vars
     }
-    let mut cmd = Command::new(&command[0]);
-    if command.len() > 1 {
-        cmd.args(&command[1..]);
+}
+
+fn run_exec_callbacks(cfg: &NotifyExecConfig, env: &NotifyExecEnv, input_json: &str) {
+    for callback in cfg
+        .all_callbacks()
+        .iter()
+        .filter(|c| c.matches_type(&env.notification_type))
+    {
+        let timeout = Duration::from_millis(callback.timeout_ms.unwrap_or(cfg.timeout_ms));
+        if let Err(err) = run_exec_callback(callback, env, input_json, timeout) {
+            let program = callback.command.first().map(String::as_str).unwrap_or("-");
+            let message = format!(
+                "notify exec callback `{program}` failed for {}: {err}",
+                env.notification_type
+            );
+            eprintln!("codex-helper notify: {message}");
+            log_to_runtime_log(&message);
+        }
     }
-    cmd.stdin(Stdio::piped())
+}
+
+fn run_exec_callback(
+    callback: &NotifyExecCallbackConfig,
+    env: &NotifyExecEnv,
+    input_json: &str,
+    timeout: Duration,
+) -> anyhow::Result<()> {
+    let Some((program, args)) = callback.command.split_first() else {
+        return Ok(());
+    };
+    let mut cmd = Command::new(program);
+    cmd.args(args)
+        .envs(env.vars())
+        .stdin(Stdio::piped())
         .stdout(Stdio::null())
         .stderr(Stdio::null());
     let mut child = cmd.spawn()?;
     if let Some(mut stdin) = child.stdin.take() {
         use std::io::Write;
-        stdin.write_all(input_json.as_bytes())?;
+        // A callback that ignores stdin may close it early; that is not a failure.
+        let _ = stdin.write_all(input_json.as_bytes());
     }
-    let _ = child.wait();
-    Ok(())
+
+    let deadline = std::time::Instant::now() + timeout;
+    loop {
+        if let Some(status) = child.try_wait()? {
+            if status.success() {
+                return Ok(());
+            }
+            anyhow::bail!("exited with {status}");
+        }
+        if std::time::Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            anyhow::bail!("timed out after {}ms and was killed", timeout.as_millis());
+        }
+        std::thread::sleep(Duration::from_millis(20));
+    }
+}
+
+fn log_to_runtime_log(message: &str) {
+    let path = proxy_home_dir().join("logs").join(RUNTIME_LOG_FILE_NAME);
+    let retention = LogRetention::runtime_log_from_env();
+    let timestamp = humantime::format_rfc3339_millis(SystemTime::now());
+    let line = format!("{timestamp}  WARN codex_helper_core::notify: {message}");
+    let _ = append_line(path, retention, &line);
 }
 
 fn spawn_flush_process(force_toast: bool) -> anyhow::Result<()> {
@@ -566,25 +743,110 @@ async fn acquire_notify_lock() -> anyhow::Result<NotifyLockGuard> {
     anyhow::bail!("failed to acquire notify lock: {:?}", path);
 }
 
-fn send_system_notification(title: &str, body: &str) -> anyhow::Result<()> {
+/// Show a system notification, printing `title: body` on platforms without a backend.
+pub fn send_system_notification(title: &str, body: &str) -> anyhow::Result<()> {
+    match show_system_notification(title, body) {
+        Some(result) => Ok(result?),
+        None => {
+            // No-op fallback: print a short line for non-supported platforms.
+            println!("{title}: {body}");
+            Ok(())
+        }
+    }
+}
+
+/// Name of the system notification backend on this platform, if there is one.
+pub fn system_notification_backend() -> Option<&'static str> {
+    if cfg!(windows) {
+        Some("windows-toast")
+    } else if cfg!(target_os = "macos") {
+        Some("osascript")
+    } else {
+        None
+    }
+}
+
+/// `None` when this platform has no system notification backend.
+fn show_system_notification(title: &str, body: &str) -> Option<std::io::Result<()>> {
     #[cfg(windows)]
     {
-        windows_toast::notify(title, body)?;
-        Ok(())
+        Some(windows_toast::notify(title, body))
     }
     #[cfg(target_os = "macos")]
     {
-        macos_notification::notify(title, body)?;
-        Ok(())
+        Some(macos_notification::notify(title, body))
     }
     #[cfg(not(any(windows, target_os = "macos")))]
     {
-        // No-op fallback: print a short line for non-supported platforms.
-        println!("{title}: {body}");
-        Ok(())
+        let _ = (title, body);
+        None
     }
 }
 
+/// What happened when `notify test` asked the OS to show a sample notification.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum TestNotificationOutcome {
+    /// The backend accepted the notification; the OS may still hide it (focus mode, permissions).
+    Delivered {
+        backend: &'static str,
+    },
+    Failed {
+        backend: &'static str,
+        error: String,
+    },
+    Unsupported,
+}
+
+impl TestNotificationOutcome {
+    pub fn is_delivered(&self) -> bool {
+        matches!(self, Self::Delivered { .. })
+    }
+
+    pub fn message(&self) -> String {
+        match self {
+            Self::Delivered { backend } => format!(
+                "sent a test notification via {backend}; if nothing appeared, check the OS notification settings and focus / do-not-disturb mode"
+            ),
+            Self::Failed { backend, error } => {
+                format!("the {backend} notification backend failed: {error}")
+            }
+            Self::Unsupported => "system notifications are only supported on Windows and macOS; on this platform use notify.exec callbacks instead".to_string(),
+        }
+    }
+}
+
+/// Shows a sample notification through the same backend Codex `notify` events use, ignoring
+/// `notify.enabled` / `notify.system.enabled` so the OS side can be checked on its own.
+pub fn send_test_notification() -> TestNotificationOutcome {
+    let Some(backend) = system_notification_backend() else {
+        return TestNotificationOutcome::Unsupported;
+    };
+    match show_system_notification(
+        "codex-helper test notification",
+        "Notifications from codex-helper are working.",
+    ) {
+        Some(Ok(())) => TestNotificationOutcome::Delivered { backend },
+        Some(Err(error)) => TestNotificationOutcome::Failed {
+            backend,
+            error: error.to_string(),
+        },
+        None => TestNotificationOutcome::Unsupported,
+    }
+}
+
+/// Config switches that would keep real Codex events from showing a system notification.
+pub fn notify_config_toast_blockers(cfg: &NotifyConfig) -> Vec<&'static str> {
+    let mut blockers = Vec::new();
+    if !cfg.enabled {
+        blockers.push("notify.enabled = false");
+    }
+    if !cfg.system.enabled {
+        blockers.push("notify.system.enabled = false");
+    }
+    blockers
+}
+
 #[cfg(windows)]
 mod windows_toast {
     use std::io;
@@ -820,6 +1082,8 @@ mod tests {
             session_key: Some(
                 crate::dashboard_core::operator_summary::operator_session_key(thread_id),
             ),
+            body_hash: None,
+            upstream_request_id: None,
             model: None,
             reasoning_effort: None,
             service_tier: None,
@@ -897,6 +1161,7 @@ mod tests {
                 quota_analytics: Default::default(),
                 stats_5m: Default::default(),
                 stats_1h: Default::default(),
+                request_quotas: Vec::new(),
                 pricing_catalog: Default::default(),
                 service_status: None,
                 provider_balances: Vec::new(),
@@ -967,6 +1232,230 @@ mod tests {
         assert_eq!(best.path, "/v1/responses");
     }
 
+    #[test]
+    fn exec_callbacks_filter_by_notification_type() {
+        let cfg: NotifyExecConfig = toml::from_str(
+            r#"
+enabled = true
+command = ["legacy-hook"]
+
+[[callbacks]]
+command = ["approval-hook"]
+types = ["approval-requested"]
+
+[[callbacks]]
+command = ["catch-all"]
+types = ["*"]
+timeout_ms = 500
+"#,
+        )
+        .expect("parse exec config");
+        assert_eq!(cfg.timeout_ms, 10_000);
+
+        let matching = |notification_type: &str| -> Vec<String> {
+            cfg.all_callbacks()
+                .into_iter()
+                .filter(|c| c.matches_type(notification_type))
+                .map(|c| c.command[0].clone())
+                .collect()
+        };
+        assert_eq!(
+            matching(AGENT_TURN_COMPLETE_TYPE),
+            vec!["legacy-hook", "catch-all"]
+        );
+        assert_eq!(
+            matching("approval-requested"),
+            vec!["approval-hook", "catch-all"]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn exec_callback_receives_stdin_and_env_and_reports_failures() {
+        let dir = std::env::temp_dir().join(format!("codex-helper-notify-exec-{}", now_ms()));
+        std::fs::create_dir_all(&dir).expect("create temp dir");
+        let out = dir.join("out.txt");
+        let script = format!(
+            "cat > '{}'; echo \"$CODEX_NOTIFY_TYPE $CODEX_NOTIFY_COUNT $CODEX_NOTIFY_THREAD_ID\" >> '{}'",
+            out.display(),
+            out.display()
+        );
+        let env = NotifyExecEnv {
+            notification_type: AGENT_TURN_COMPLETE_TYPE.to_string(),
+            count: 2,
+            thread_id: Some("th1".to_string()),
+            ..Default::default()
+        };
+        let callback = NotifyExecCallbackConfig {
+            command: vec!["sh".to_string(), "-c".to_string(), script],
+            ..Default::default()
+        };
+        run_exec_callback(&callback, &env, "{\"ok\":true}\n", Duration::from_secs(5))
+            .expect("callback succeeds");
+        let written = std::fs::read_to_string(&out).expect("read callback output");
+        assert_eq!(written, "{\"ok\":true}\nagent-turn-complete 2 th1\n");
+
+        let failing = NotifyExecCallbackConfig {
+            command: vec!["sh".to_string(), "-c".to_string(), "exit 3".to_string()],
+            ..Default::default()
+        };
+        let err = run_exec_callback(&failing, &env, "{}", Duration::from_secs(5))
+            .expect_err("nonzero exit is reported");
+        assert!(err.to_string().contains("exited with"), "{err}");
+
+        let slow = NotifyExecCallbackConfig {
+            command: vec!["sh".to_string(), "-c".to_string(), "sleep 5".to_string()],
+            ..Default::default()
+        };
+        let err = run_exec_callback(&slow, &env, "{}", Duration::from_millis(100))
+            .expect_err("slow callback times out");
+        assert!(err.to_string().contains("timed out"), "{err}");
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn notify_config_toast_blockers_name_the_disabled_switches() {
+        let mut cfg = NotifyConfig::default();
+        assert_eq!(
+            notify_config_toast_blockers(&cfg),
+            vec!["notify.enabled = false", "notify.system.enabled = false"]
+        );
+
+        cfg.enabled = true;
+        cfg.system.enabled = true;
+        assert!(notify_config_toast_blockers(&cfg).is_empty());
+    }
+
+    fn queued(thread_id: &str, queued_at_ms: u64) -> QueuedEvent {
+        QueuedEvent {
+            thread_id: thread_id.to_string(),
+            turn_id: None,
+            cwd: Some(format!("/work/{thread_id}")),
+            duration_ms: 90_000,
+            ended_at_ms: queued_at_ms,
+            queued_at_ms,
+            last_assistant_preview: None,
+        }
+    }
+
+    fn thread_ids(events: &[QueuedEvent]) -> Vec<&str> {
+        events.iter().map(|e| e.thread_id.as_str()).collect()
+    }
+
+    #[test]
+    fn flush_merges_a_burst_within_the_window_and_defers_later_events() {
+        let policy = NotifyPolicyConfig::default();
+        let mut state = NotifyState {
+            pending: vec![queued("a", 1_000), queued("b", 4_000), queued("c", 9_000)],
+            ..NotifyState::default()
+        };
+
+        assert_eq!(
+            plan_flush(&mut state, &policy, 5_000),
+            FlushPlan::Wait(6_000)
+        );
+        assert_eq!(state.pending.len(), 3, "waiting keeps the queue intact");
+
+        let FlushPlan::Send { send, suppressed } = plan_flush(&mut state, &policy, 11_000) else {
+            panic!("window elapsed, expected a batch");
+        };
+        assert_eq!(thread_ids(&send), vec!["a", "b", "c"]);
+        assert_eq!(suppressed, 0);
+        assert!(state.pending.is_empty());
+        assert_eq!(plan_flush(&mut state, &policy, 11_000), FlushPlan::Empty);
+
+        // An event arriving after the flush starts a new window, gated by the global cooldown.
+        state.last_toast_ms = Some(11_000);
+        state.pending.push(queued("d", 30_000));
+        assert_eq!(
+            plan_flush(&mut state, &policy, 40_000),
+            FlushPlan::Wait(31_000)
+        );
+    }
+
+    #[test]
+    fn flush_caps_batches_at_max_merged_and_flushes_early_when_full() {
+        let policy = NotifyPolicyConfig {
+            max_merged: 2,
+            ..NotifyPolicyConfig::default()
+        };
+        let mut state = NotifyState {
+            pending: vec![queued("c", 3_000), queued("a", 1_000), queued("b", 2_000)],
+            ..NotifyState::default()
+        };
+
+        // The window is still open, but the batch is already full.
+        let FlushPlan::Send { send, .. } = plan_flush(&mut state, &policy, 3_500) else {
+            panic!("full batch should flush before the window closes");
+        };
+        assert_eq!(thread_ids(&send), vec!["a", "b"]);
+        assert_eq!(thread_ids(&state.pending), vec!["c"]);
+
+        // The remainder is below the cap, so it waits out its own window.
+        assert_eq!(
+            plan_flush(&mut state, &policy, 3_500),
+            FlushPlan::Wait(9_500)
+        );
+    }
+
+    #[test]
+    fn flush_counts_per_thread_cooldown_as_suppressed() {
+        let policy = NotifyPolicyConfig::default();
+        let mut state = NotifyState {
+            pending: vec![queued("a", 1_000), queued("b", 2_000)],
+            per_thread_last_toast_ms: HashMap::from([("a".to_string(), 0)]),
+            ..NotifyState::default()
+        };
+
+        let FlushPlan::Send { send, suppressed } = plan_flush(&mut state, &policy, 20_000) else {
+            panic!("expected a batch");
+        };
+        assert_eq!(thread_ids(&send), vec!["b"]);
+        assert_eq!(suppressed, 1);
+    }
+
+    #[test]
+    fn merge_strategy_controls_toast_title_and_body() {
+        let events = vec![
+            queued("a", 1_000),
+            queued("b", 2_000),
+            queued("c", 3_000),
+            queued("d", 4_000),
+        ];
+
+        assert_eq!(
+            render_title(4, 1, 0, NotifyMergeStrategy::SummarizeCount),
+            "Codex: 4 turns complete (+1 suppressed)"
+        );
+        assert_eq!(
+            render_body(&events, NotifyMergeStrategy::SummarizeCount),
+            "d (90.0s)\nc (90.0s)\nb (90.0s)\n+1 more"
+        );
+
+        assert_eq!(
+            render_title(4, 0, 0, NotifyMergeStrategy::LatestOnly),
+            "Codex: turn complete (latest of 4)"
+        );
+        assert_eq!(
+            render_body(&events, NotifyMergeStrategy::LatestOnly),
+            "d (90.0s)"
+        );
+        assert_eq!(
+            render_title(1, 0, 0, NotifyMergeStrategy::LatestOnly),
+            "Codex: turn complete"
+        );
+    }
+
+    #[cfg(not(any(windows, target_os = "macos")))]
+    #[test]
+    fn test_notification_reports_unsupported_platforms() {
+        let outcome = send_test_notification();
+
+        assert_eq!(outcome, TestNotificationOutcome::Unsupported);
+        assert!(!outcome.is_delivered());
+        assert!(outcome.message().contains("Windows and macOS"));
+    }
+
     #[test]
     fn notify_admin_origin_is_derived_from_loopback_proxy_origin() {
         assert_eq!(
@@ -12,6 +12,7 @@ use crate::basellm_catalog::{BasellmCatalogLkg, basellm_catalog_snapshot};
 use crate::file_replace::write_text_file;
 use crate::provider_catalog::{ProviderCatalogEpoch, ProviderPriceKey, ProviderPricingTier};
 use crate::usage::{CacheAccountingConvention, EconomicsStatus, UsageMetrics};
+use crate::usage_format::DisplayFormat;
 
 const BASELLM_ALL_JSON_URL: &str = "https://basellm.github.io/llm-metadata/api/all.json";
 const FEMTO_USD_PER_USD: i128 = 1_000_000_000_000_000;
@@ -329,12 +330,12 @@ impl CostBreakdown {
         self.total_cost_femto_usd
     }
 
-    pub fn display_total(&self) -> String {
-        format_cost_display(self.total_cost_usd.as_deref())
+    pub fn display_total(&self, format: &DisplayFormat) -> String {
+        format_cost_display(self.total_cost_usd.as_deref(), format)
     }
 
-    pub fn display_total_with_confidence(&self) -> String {
-        format_cost_with_confidence(self.total_cost_usd.as_deref(), self.confidence)
+    pub fn display_total_with_confidence(&self, format: &DisplayFormat) -> String {
+        format_cost_with_confidence(self.total_cost_usd.as_deref(), self.confidence, format)
     }
 }
 
@@ -418,12 +419,12 @@ impl CostSummary {
         self.refresh_display();
     }
 
-    pub fn display_total(&self) -> String {
-        format_cost_display(self.total_cost_usd.as_deref())
+    pub fn display_total(&self, format: &DisplayFormat) -> String {
+        format_cost_display(self.total_cost_usd.as_deref(), format)
     }
 
-    pub fn display_total_with_confidence(&self) -> String {
-        format_cost_with_confidence(self.total_cost_usd.as_deref(), self.confidence)
+    pub fn display_total_with_confidence(&self, format: &DisplayFormat) -> String {
+        format_cost_with_confidence(self.total_cost_usd.as_deref(), self.confidence, format)
     }
 
     fn refresh_display(&mut self) {
@@ -1350,7 +1351,7 @@ pub struct EffectivePricingCatalogSnapshot {
     pub manual_modified_unix_ms: Option<u64>,
     pub manual_status: ManualPricingLayerStatus,
     pub manual_error: Option<String>,
-    catalog: ModelPriceCatalog,
+    catalog: Arc<ModelPriceCatalog>,
 }
 
 impl EffectivePricingCatalogSnapshot {
@@ -1358,6 +1359,11 @@ impl EffectivePricingCatalogSnapshot {
         &self.catalog
     }
 
+    /// The catalog without copying it, for consumers that outlive this snapshot borrow.
+    pub fn shared_catalog(&self) -> Arc<ModelPriceCatalog> {
+        Arc::clone(&self.catalog)
+    }
+
     pub fn catalog_snapshot(&self) -> ModelPriceCatalogSnapshot {
         self.catalog.snapshot(self.source.clone())
     }
@@ -1625,7 +1631,7 @@ fn build_effective_pricing_catalog_from_layers_with_warnings(
         manual_modified_unix_ms: manual.modified_unix_ms,
         manual_status: manual.status,
         manual_error: manual.error,
-        catalog,
+        catalog: Arc::new(catalog),
     }
 }
 
@@ -2058,7 +2064,7 @@ fn capture_effective_pricing_snapshot(
     snapshot: &EffectivePricingCatalogSnapshot,
 ) -> CapturedModelPriceCatalog {
     CapturedModelPriceCatalog {
-        catalog: snapshot.catalog.clone(),
+        catalog: snapshot.catalog().clone(),
         source: snapshot.source.clone(),
         revision: snapshot.revision.clone(),
     }
@@ -2319,17 +2325,18 @@ fn estimate_billable_usage_cost(
     }
 }
 
-pub fn format_cost_display(total_cost_usd: Option<&str>) -> String {
+pub fn format_cost_display(total_cost_usd: Option<&str>, format: &DisplayFormat) -> String {
     total_cost_usd
-        .map(|value| format!("${value}"))
+        .map(|value| format.cost(value))
         .unwrap_or_else(|| "-".to_string())
 }
 
 pub fn format_cost_with_confidence(
     total_cost_usd: Option<&str>,
     confidence: CostConfidence,
+    format: &DisplayFormat,
 ) -> String {
-    let total = format_cost_display(total_cost_usd);
+    let total = format_cost_display(total_cost_usd, format);
     if total == "-" {
         return "- (unknown)".to_string();
     }
@@ -2998,7 +3005,7 @@ mod tests {
         let cost = CostBreakdown::default();
 
         assert_eq!(cost.confidence, CostConfidence::Unknown);
-        assert_eq!(cost.display_total(), "-");
+        assert_eq!(cost.display_total(&DisplayFormat::default()), "-");
     }
 
     #[test]
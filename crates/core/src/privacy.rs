@@ -0,0 +1,73 @@
+use sha2::{Digest, Sha256};
+
+use crate::config::PrivacyConfig;
+use crate::sessions::SessionSummary;
+
+const REDACTED_CWD_PREFIX: &str = "cwd-sha256:";
+
+/// Stable, non-reversible stand-in for a working directory.
+///
+/// The same path always maps to the same value so grouping by project keeps working.
+pub fn hash_cwd(cwd: &str) -> String {
+    if cwd.starts_with(REDACTED_CWD_PREFIX) {
+        return cwd.to_string();
+    }
+    let digest = Sha256::digest(cwd.as_bytes());
+    let hex = digest
+        .iter()
+        .take(8)
+        .map(|byte| format!("{byte:02x}"))
+        .collect::<String>();
+    format!("{REDACTED_CWD_PREFIX}{hex}")
+}
+
+impl PrivacyConfig {
+    pub fn apply_to_cwd(&self, cwd: Option<String>) -> Option<String> {
+        match cwd {
+            Some(cwd) if self.redact_cwd => Some(hash_cwd(&cwd)),
+            cwd => cwd,
+        }
+    }
+
+    /// Redacts the cwd and first prompt of a session list row; the session id is kept as-is.
+    pub fn apply_to_session_summary(&self, summary: &mut SessionSummary) {
+        summary.cwd = self.apply_to_cwd(summary.cwd.take());
+        if self.redact_prompts {
+            summary.first_user_message = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cwd_hash_is_stable_and_idempotent() {
+        let hashed = hash_cwd("/home/alice/project");
+        assert!(hashed.starts_with(REDACTED_CWD_PREFIX));
+        assert_eq!(hashed.len(), REDACTED_CWD_PREFIX.len() + 16);
+        assert_eq!(hashed, hash_cwd("/home/alice/project"));
+        assert_ne!(hashed, hash_cwd("/home/alice/other"));
+        assert_eq!(hash_cwd(&hashed), hashed);
+    }
+
+    #[test]
+    fn default_policy_keeps_values() {
+        let policy = PrivacyConfig::default();
+        assert_eq!(
+            policy.apply_to_cwd(Some("/repo".to_string())).as_deref(),
+            Some("/repo")
+        );
+
+        let redacting = PrivacyConfig {
+            redact_cwd: true,
+            redact_prompts: true,
+        };
+        assert_eq!(
+            redacting.apply_to_cwd(Some("/repo".to_string())),
+            Some(hash_cwd("/repo"))
+        );
+        assert_eq!(redacting.apply_to_cwd(None), None);
+    }
+}
@@ -0,0 +1,273 @@
+use chrono::{DateTime, Datelike, Local, NaiveDateTime, Timelike, Utc, Weekday};
+use chrono_tz::Tz;
+
+use crate::config::{ProviderConfig, ServiceRouteConfig};
+
+const MINUTES_PER_DAY: u32 = 24 * 60;
+
+/// Time windows during which a provider takes part in automatic routing.
+///
+/// Entries look like `Mon-Fri 20:00-08:00`, `Sat,Sun`, or `00:00-07:00`. A window whose end is
+/// not after its start runs past midnight and belongs to the day it starts on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProviderSchedule {
+    windows: Vec<ScheduleWindow>,
+    timezone: Option<Tz>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ScheduleWindow {
+    /// Indexed by `Weekday::num_days_from_monday`.
+    days: [bool; 7],
+    start_minute: u32,
+    end_minute: u32,
+}
+
+impl ScheduleWindow {
+    fn contains(&self, weekday: Weekday, minute: u32) -> bool {
+        let today = self.days[weekday.num_days_from_monday() as usize];
+        if self.start_minute < self.end_minute {
+            return today && minute >= self.start_minute && minute < self.end_minute;
+        }
+        let yesterday = self.days[weekday.pred().num_days_from_monday() as usize];
+        (today && minute >= self.start_minute) || (yesterday && minute < self.end_minute)
+    }
+}
+
+impl ProviderSchedule {
+    /// Compiles `enabled_schedule`; malformed entries are dropped (see [`provider_schedule_warnings`]).
+    ///
+    /// Returns `None` when the provider has no usable window, which keeps it always eligible.
+    pub fn from_provider(provider: &ProviderConfig) -> Option<Self> {
+        let (schedule, _) = compile(provider);
+        schedule
+    }
+
+    pub fn is_active_at(&self, now: DateTime<Utc>) -> bool {
+        let local = self.local_time(now);
+        let weekday = local.weekday();
+        let minute = local.hour() * 60 + local.minute();
+        self.windows
+            .iter()
+            .any(|window| window.contains(weekday, minute))
+    }
+
+    pub fn is_active_now(&self) -> bool {
+        self.is_active_at(Utc::now())
+    }
+
+    fn local_time(&self, now: DateTime<Utc>) -> NaiveDateTime {
+        match self.timezone {
+            Some(timezone) => now.with_timezone(&timezone).naive_local(),
+            None => now.with_timezone(&Local).naive_local(),
+        }
+    }
+}
+
+/// Human-readable problems with a provider's `enabled_schedule` / `schedule_timezone`.
+pub fn provider_schedule_warnings(provider: &ProviderConfig) -> Vec<String> {
+    let (_, warnings) = compile(provider);
+    warnings
+}
+
+/// Schedule warnings for every provider of one service, prefixed with the provider name.
+pub fn service_schedule_warnings(view: &ServiceRouteConfig) -> Vec<String> {
+    view.providers
+        .iter()
+        .flat_map(|(name, provider)| {
+            provider_schedule_warnings(provider)
+                .into_iter()
+                .map(move |warning| format!("provider '{name}': {warning}"))
+        })
+        .collect()
+}
+
+fn compile(provider: &ProviderConfig) -> (Option<ProviderSchedule>, Vec<String>) {
+    let mut warnings = Vec::new();
+    let timezone = provider
+        .schedule_timezone
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty() && !value.eq_ignore_ascii_case("local"))
+        .and_then(|value| match value.parse::<Tz>() {
+            Ok(timezone) => Some(timezone),
+            Err(_) => {
+                warnings.push(format!(
+                    "unknown schedule_timezone '{value}'; using local time"
+                ));
+                None
+            }
+        });
+    let mut windows = Vec::new();
+    for entry in &provider.enabled_schedule {
+        match parse_window(entry) {
+            Ok(window) => windows.push(window),
+            Err(message) => warnings.push(format!(
+                "ignoring enabled_schedule entry '{entry}': {message}"
+            )),
+        }
+    }
+    if windows.is_empty() && !provider.enabled_schedule.is_empty() {
+        warnings.push(
+            "enabled_schedule has no valid windows; the provider stays always eligible".to_string(),
+        );
+    }
+    let schedule = (!windows.is_empty()).then_some(ProviderSchedule { windows, timezone });
+    (schedule, warnings)
+}
+
+fn parse_window(entry: &str) -> Result<ScheduleWindow, String> {
+    let mut days = None;
+    let mut minutes = None;
+    for token in entry.split_whitespace() {
+        if token.starts_with(|ch: char| ch.is_ascii_digit()) {
+            if minutes.is_some() {
+                return Err("more than one time range".to_string());
+            }
+            minutes = Some(parse_time_range(token)?);
+        } else {
+            if days.is_some() {
+                return Err("more than one day list".to_string());
+            }
+            days = Some(parse_days(token)?);
+        }
+    }
+    if days.is_none() && minutes.is_none() {
+        return Err("expected days, a time range, or both".to_string());
+    }
+    let (start_minute, end_minute) = minutes.unwrap_or((0, MINUTES_PER_DAY));
+    Ok(ScheduleWindow {
+        days: days.unwrap_or([true; 7]),
+        start_minute,
+        end_minute,
+    })
+}
+
+fn parse_days(token: &str) -> Result<[bool; 7], String> {
+    let mut days = [false; 7];
+    for item in token.split(',').filter(|item| !item.is_empty()) {
+        match item.to_ascii_lowercase().as_str() {
+            "daily" => days = [true; 7],
+            "weekdays" => days[..5].fill(true),
+            "weekends" => days[5..].fill(true),
+            _ => {
+                let (first, last) = item.split_once('-').unwrap_or((item, item));
+                let first = parse_weekday(first)?;
+                let last = parse_weekday(last)?;
+                let mut day = first;
+                loop {
+                    days[day.num_days_from_monday() as usize] = true;
+                    if day == last {
+                        break;
+                    }
+                    day = day.succ();
+                }
+            }
+        }
+    }
+    if !days.contains(&true) {
+        return Err(format!("no days in '{token}'"));
+    }
+    Ok(days)
+}
+
+fn parse_weekday(value: &str) -> Result<Weekday, String> {
+    value
+        .parse::<Weekday>()
+        .map_err(|_| format!("unknown day '{value}'"))
+}
+
+fn parse_time_range(token: &str) -> Result<(u32, u32), String> {
+    let (start, end) = token
+        .split_once('-')
+        .ok_or_else(|| format!("expected HH:MM-HH:MM, got '{token}'"))?;
+    let start = parse_minute_of_day(start)?;
+    let end = parse_minute_of_day(end)?;
+    if start == end {
+        return Err(format!("empty time range '{token}'"));
+    }
+    if start == MINUTES_PER_DAY {
+        return Err("a range cannot start at 24:00".to_string());
+    }
+    Ok((start, end))
+}
+
+fn parse_minute_of_day(value: &str) -> Result<u32, String> {
+    let invalid = || format!("invalid time '{value}'");
+    let (hour, minute) = value.split_once(':').ok_or_else(invalid)?;
+    let hour = hour.parse::<u32>().map_err(|_| invalid())?;
+    let minute = minute.parse::<u32>().map_err(|_| invalid())?;
+    if minute >= 60 || hour > 24 || (hour == 24 && minute != 0) {
+        return Err(invalid());
+    }
+    Ok(hour * 60 + minute)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn provider(entries: &[&str], timezone: Option<&str>) -> ProviderConfig {
+        ProviderConfig {
+            base_url: Some("https://cheap.example/v1".to_string()),
+            enabled_schedule: entries.iter().map(|entry| entry.to_string()).collect(),
+            schedule_timezone: timezone.map(str::to_string),
+            ..ProviderConfig::default()
+        }
+    }
+
+    fn utc(day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+        // 2026-06-01 is a Monday.
+        Utc.with_ymd_and_hms(2026, 6, day, hour, minute, 0)
+            .single()
+            .expect("valid test timestamp")
+    }
+
+    #[test]
+    fn overnight_weekday_window_spills_into_the_next_morning() {
+        let schedule =
+            ProviderSchedule::from_provider(&provider(&["Mon-Fri 20:00-08:00"], Some("UTC")))
+                .expect("schedule");
+
+        assert!(!schedule.is_active_at(utc(1, 19, 59)));
+        assert!(schedule.is_active_at(utc(1, 20, 0)));
+        assert!(schedule.is_active_at(utc(2, 7, 59)));
+        assert!(!schedule.is_active_at(utc(2, 8, 0)));
+        // Friday night runs into Saturday morning, but Saturday evening is off.
+        assert!(schedule.is_active_at(utc(6, 3, 0)));
+        assert!(!schedule.is_active_at(utc(6, 21, 0)));
+        // Monday early morning belongs to Sunday, which is not listed.
+        assert!(!schedule.is_active_at(utc(1, 3, 0)));
+    }
+
+    #[test]
+    fn schedule_uses_the_configured_timezone_and_whole_day_entries() {
+        let schedule = ProviderSchedule::from_provider(&provider(
+            &["Sat,Sun", "00:00-07:00"],
+            Some("Asia/Shanghai"),
+        ))
+        .expect("schedule");
+
+        // 2026-06-01 22:00 UTC is Tuesday 06:00 in Shanghai.
+        assert!(schedule.is_active_at(utc(1, 22, 0)));
+        assert!(!schedule.is_active_at(utc(2, 2, 0)));
+        // Friday 16:30 UTC is Saturday 00:30 in Shanghai.
+        assert!(schedule.is_active_at(utc(5, 16, 30)));
+    }
+
+    #[test]
+    fn malformed_entries_are_reported_and_do_not_restrict_the_provider() {
+        let config = provider(&["Mon-Fri 25:00-08:00", "Someday"], Some("Mars/Olympus"));
+        assert_eq!(ProviderSchedule::from_provider(&config), None);
+
+        let warnings = provider_schedule_warnings(&config);
+        assert_eq!(warnings.len(), 4);
+        assert!(warnings[0].contains("Mars/Olympus"));
+        assert!(warnings[1].contains("invalid time '25:00'"));
+        assert!(warnings[2].contains("unknown day 'Someday'"));
+        assert!(warnings[3].contains("always eligible"));
+
+        assert!(provider_schedule_warnings(&provider(&["weekends 09:00-24:00"], None)).is_empty());
+    }
+}
@@ -0,0 +1,387 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Context, Result, anyhow, bail};
+use chrono::{DateTime, Utc};
+use rustls_pki_types::pem::PemObject;
+use rustls_pki_types::{CertificateDer, PrivateKeyDer, UnixTime};
+use zeroize::Zeroizing;
+
+use crate::config::{AddressFamily, HelperConfig, ProviderTlsConfig, proxy_home_dir};
+
+/// Client certificates that expire within this window are reported by `doctor`.
+pub const CLIENT_CERT_EXPIRY_WARNING: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+/// Dedicated upstream clients for providers that configure `[tls]` or their own
+/// `address_family`, keyed by service then provider.
+///
+/// Other providers keep using the shared proxy client, unless `http.address_family` restricts
+/// resolution; then they share one restricted client.
+#[derive(Debug, Default)]
+pub struct ProviderTlsClients {
+    clients: BTreeMap<String, BTreeMap<String, reqwest::Client>>,
+    address_family_client: Option<reqwest::Client>,
+}
+
+impl ProviderTlsClients {
+    /// Reads every configured certificate, key and CA bundle and builds one client per provider.
+    /// Providers that only override `address_family` share one client per family.
+    pub fn build(config: &HelperConfig) -> Result<Self> {
+        let global_family = config.http.address_family;
+        let mut family_clients: BTreeMap<AddressFamily, reqwest::Client> = BTreeMap::new();
+        let mut clients: BTreeMap<String, BTreeMap<String, reqwest::Client>> = BTreeMap::new();
+        for (service_name, view) in [("codex", &config.codex), ("claude", &config.claude)] {
+            for (provider_id, provider) in &view.providers {
+                let family = provider.address_family.unwrap_or(global_family);
+                let client = if !provider.tls.is_default() {
+                    provider_tls_client(&provider.tls, family).with_context(|| {
+                        format!(
+                            "{service_name} provider '{provider_id}': invalid tls configuration"
+                        )
+                    })?
+                } else if family != global_family {
+                    match family_clients.get(&family) {
+                        Some(client) => client.clone(),
+                        None => {
+                            let client = address_family_client(family)?;
+                            family_clients.insert(family, client.clone());
+                            client
+                        }
+                    }
+                } else {
+                    continue;
+                };
+                clients
+                    .entry(service_name.to_string())
+                    .or_default()
+                    .insert(provider_id.clone(), client);
+            }
+        }
+        let address_family_client = match global_family {
+            AddressFamily::Any => None,
+            family => Some(address_family_client(family)?),
+        };
+        Ok(Self {
+            clients,
+            address_family_client,
+        })
+    }
+
+    pub fn client(&self, service_name: &str, provider_id: &str) -> Option<&reqwest::Client> {
+        self.clients
+            .get(service_name)
+            .and_then(|clients| clients.get(provider_id))
+            .or(self.address_family_client.as_ref())
+    }
+}
+
+fn address_family_client(family: AddressFamily) -> Result<reqwest::Client> {
+    crate::upstream_dns::with_address_family(upstream_client_builder(), family)
+        .build()
+        .map_err(|error| {
+            anyhow!(
+                "build address_family = \"{}\" client: {error}",
+                family.as_str()
+            )
+        })
+}
+
+fn upstream_client_builder() -> reqwest::ClientBuilder {
+    crate::proxy::upstream_http_client_builder()
+        .connect_timeout(Duration::from_secs(10))
+        .tcp_keepalive(Duration::from_secs(30))
+        .pool_idle_timeout(Duration::from_secs(30))
+}
+
+/// Builds an upstream client carrying the provider's client identity and extra trust roots.
+pub fn build_provider_tls_client(tls: &ProviderTlsConfig) -> Result<reqwest::Client> {
+    provider_tls_client(tls, AddressFamily::Any)
+}
+
+fn provider_tls_client(
+    tls: &ProviderTlsConfig,
+    address_family: AddressFamily,
+) -> Result<reqwest::Client> {
+    let mut builder =
+        crate::upstream_dns::with_address_family(upstream_client_builder(), address_family);
+    if let Some(identity) = load_identity(tls)? {
+        builder = builder.identity(identity);
+    }
+    if let Some(path) = tls.ca_bundle_path.as_deref() {
+        let path = resolve_tls_path(path);
+        for der in read_certificates(&path, "tls.ca_bundle_path")? {
+            let certificate = reqwest::Certificate::from_der(der.as_ref()).map_err(|_| {
+                anyhow!(
+                    "tls.ca_bundle_path {}: unsupported certificate",
+                    path.display()
+                )
+            })?;
+            builder = builder.add_root_certificate(certificate);
+        }
+    }
+    builder
+        .build()
+        .map_err(|error| anyhow!("build TLS client: {error}"))
+}
+
+/// Relative paths are resolved against the codex-helper home directory.
+pub fn resolve_tls_path(path: &Path) -> PathBuf {
+    if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        proxy_home_dir().join(path)
+    }
+}
+
+fn load_identity(tls: &ProviderTlsConfig) -> Result<Option<reqwest::Identity>> {
+    let (cert_path, key_path) = match (
+        tls.client_cert_path.as_deref(),
+        tls.client_key_path.as_deref(),
+    ) {
+        (None, None) => return Ok(None),
+        (Some(cert_path), Some(key_path)) => {
+            (resolve_tls_path(cert_path), resolve_tls_path(key_path))
+        }
+        _ => bail!("tls.client_cert_path and tls.client_key_path must be set together"),
+    };
+    let cert_pem = read_file(&cert_path, "tls.client_cert_path")?;
+    parse_certificates(&cert_pem, &cert_path, "tls.client_cert_path")?;
+    let key_pem = Zeroizing::new(read_file(&key_path, "tls.client_key_path")?);
+    // The parser error may quote key material, so only the path is reported.
+    if PrivateKeyDer::from_pem_slice(&key_pem).is_err() {
+        bail!(
+            "tls.client_key_path {}: no PEM private key (PKCS#8, PKCS#1 or SEC1) found",
+            key_path.display()
+        );
+    }
+    let mut bundle = Zeroizing::new(Vec::with_capacity(cert_pem.len() + key_pem.len() + 1));
+    bundle.extend_from_slice(&cert_pem);
+    bundle.push(b'\n');
+    bundle.extend_from_slice(&key_pem);
+    let identity = reqwest::Identity::from_pem(&bundle).map_err(|_| {
+        anyhow!(
+            "tls.client_key_path {}: key cannot be used with {}",
+            key_path.display(),
+            cert_path.display()
+        )
+    })?;
+    Ok(Some(identity))
+}
+
+fn read_file(path: &Path, field: &str) -> Result<Vec<u8>> {
+    std::fs::read(path).with_context(|| format!("{field}: cannot read {}", path.display()))
+}
+
+fn read_certificates(path: &Path, field: &str) -> Result<Vec<CertificateDer<'static>>> {
+    let pem = read_file(path, field)?;
+    parse_certificates(&pem, path, field)
+}
+
+fn parse_certificates(
+    pem: &[u8],
+    path: &Path,
+    field: &str,
+) -> Result<Vec<CertificateDer<'static>>> {
+    let certificates = CertificateDer::pem_slice_iter(pem)
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(|error| anyhow!("{field} {}: malformed PEM: {error}", path.display()))?;
+    if certificates.is_empty() {
+        bail!("{field} {}: no PEM certificate found", path.display());
+    }
+    Ok(certificates)
+}
+
+/// Outcome of the `doctor` inspection of one provider's TLS files.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProviderTlsCheck {
+    Ok(String),
+    Warn(String),
+    Fail(String),
+}
+
+/// Loads the provider's TLS files and validates the client certificate chain and expiry at `now`.
+///
+/// The chain is verified against the CA bundle plus any issuer certificates that follow the leaf
+/// in `client_cert_path`; with neither present only the validity period is checked.
+pub fn check_provider_tls(tls: &ProviderTlsConfig, now: DateTime<Utc>) -> ProviderTlsCheck {
+    if let Err(error) = build_provider_tls_client(tls) {
+        return ProviderTlsCheck::Fail(format!("{error:#}"));
+    }
+    let Some(cert_path) = tls.client_cert_path.as_deref() else {
+        return ProviderTlsCheck::Ok("CA bundle loaded".to_string());
+    };
+    let chain = match read_certificates(&resolve_tls_path(cert_path), "tls.client_cert_path") {
+        Ok(chain) => chain,
+        Err(error) => return ProviderTlsCheck::Fail(format!("{error:#}")),
+    };
+    let ca_bundle = match tls.ca_bundle_path.as_deref() {
+        Some(path) => match read_certificates(&resolve_tls_path(path), "tls.ca_bundle_path") {
+            Ok(certificates) => certificates,
+            Err(error) => return ProviderTlsCheck::Fail(format!("{error:#}")),
+        },
+        None => Vec::new(),
+    };
+
+    let leaf = match webpki::EndEntityCert::try_from(&chain[0]) {
+        Ok(leaf) => leaf,
+        Err(error) => {
+            return ProviderTlsCheck::Fail(format!("client certificate cannot be parsed: {error}"));
+        }
+    };
+    let intermediates = &chain[1..];
+    let anchors = ca_bundle
+        .iter()
+        .chain(intermediates)
+        .filter_map(|certificate| webpki::anchor_from_trusted_cert(certificate).ok())
+        .collect::<Vec<_>>();
+    let verify_at = |time: DateTime<Utc>| {
+        let time = UnixTime::since_unix_epoch(Duration::from_secs(
+            u64::try_from(time.timestamp()).unwrap_or_default(),
+        ));
+        leaf.verify_for_usage(
+            webpki::ALL_VERIFICATION_ALGS,
+            &anchors,
+            intermediates,
+            time,
+            webpki::KeyUsage::client_auth(),
+            None,
+            None,
+        )
+        .map(|_| ())
+    };
+
+    let chain_verified = match verify_at(now) {
+        Ok(()) => true,
+        Err(webpki::Error::UnknownIssuer) if anchors.is_empty() => false,
+        Err(webpki::Error::CertExpired { not_after, .. }) => {
+            return ProviderTlsCheck::Fail(format!(
+                "client certificate chain expired at {}",
+                format_unix_time(not_after)
+            ));
+        }
+        Err(webpki::Error::CertNotValidYet { not_before, .. }) => {
+            return ProviderTlsCheck::Fail(format!(
+                "client certificate chain is not valid before {}",
+                format_unix_time(not_before)
+            ));
+        }
+        Err(error) => {
+            return ProviderTlsCheck::Fail(format!(
+                "client certificate chain does not verify: {error}"
+            ));
+        }
+    };
+    let soon = now
+        + chrono::Duration::from_std(CLIENT_CERT_EXPIRY_WARNING).unwrap_or(chrono::Duration::MAX);
+    if let Err(webpki::Error::CertExpired { not_after, .. }) = verify_at(soon) {
+        return ProviderTlsCheck::Warn(format!(
+            "client certificate chain expires at {}",
+            format_unix_time(not_after)
+        ));
+    }
+    if chain_verified {
+        ProviderTlsCheck::Ok("client certificate chain verified and not expiring soon".to_string())
+    } else {
+        ProviderTlsCheck::Warn(
+            "client certificate is within its validity period, but no issuer certificate is available to verify the chain"
+                .to_string(),
+        )
+    }
+}
+
+fn format_unix_time(time: UnixTime) -> String {
+    i64::try_from(time.as_secs())
+        .ok()
+        .and_then(|secs| DateTime::<Utc>::from_timestamp(secs, 0))
+        .map(|time| time.format("%Y-%m-%d %H:%M UTC").to_string())
+        .unwrap_or_else(|| time.as_secs().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn fixture(name: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/tls")
+            .join(name)
+    }
+
+    fn mtls_config() -> ProviderTlsConfig {
+        ProviderTlsConfig {
+            client_cert_path: Some(fixture("client.pem")),
+            client_key_path: Some(fixture("client-key.pem")),
+            ca_bundle_path: Some(fixture("ca.pem")),
+        }
+    }
+
+    #[test]
+    fn builds_client_from_pem_identity_and_ca_bundle() {
+        build_provider_tls_client(&mtls_config()).expect("client");
+    }
+
+    #[test]
+    fn missing_and_malformed_files_fail_with_the_field_and_path() {
+        let mut missing = mtls_config();
+        missing.client_key_path = Some(fixture("absent-key.pem"));
+        let error = format!("{:#}", build_provider_tls_client(&missing).unwrap_err());
+        assert!(
+            error.contains("tls.client_key_path: cannot read"),
+            "{error}"
+        );
+        assert!(error.contains("absent-key.pem"), "{error}");
+
+        let mut malformed = mtls_config();
+        malformed.client_key_path = Some(fixture("client.pem"));
+        let error = format!("{:#}", build_provider_tls_client(&malformed).unwrap_err());
+        assert!(error.contains("no PEM private key"), "{error}");
+
+        let mut swapped = mtls_config();
+        swapped.ca_bundle_path = Some(fixture("client-key.pem"));
+        let error = format!("{:#}", build_provider_tls_client(&swapped).unwrap_err());
+        assert!(error.contains("tls.ca_bundle_path"), "{error}");
+        assert!(!error.contains("PRIVATE KEY"), "{error}");
+
+        let mut half = mtls_config();
+        half.client_key_path = None;
+        let error = format!("{:#}", build_provider_tls_client(&half).unwrap_err());
+        assert!(error.contains("must be set together"), "{error}");
+    }
+
+    #[test]
+    fn doctor_check_reports_chain_and_expiry() {
+        let tls = mtls_config();
+        let at = |year, month, day| Utc.with_ymd_and_hms(year, month, day, 0, 0, 0).unwrap();
+
+        assert!(matches!(
+            check_provider_tls(&tls, at(2030, 1, 1)),
+            ProviderTlsCheck::Ok(_)
+        ));
+        assert_eq!(
+            check_provider_tls(&tls, at(2036, 10, 1)),
+            ProviderTlsCheck::Warn(
+                "client certificate chain expires at 2036-10-12 20:47 UTC".into()
+            )
+        );
+        assert!(matches!(
+            check_provider_tls(&tls, at(2037, 1, 1)),
+            ProviderTlsCheck::Fail(message) if message.contains("expired at 2036-10-12")
+        ));
+
+        let mut unanchored = tls.clone();
+        unanchored.ca_bundle_path = None;
+        assert!(matches!(
+            check_provider_tls(&unanchored, at(2030, 1, 1)),
+            ProviderTlsCheck::Warn(message) if message.contains("no issuer certificate")
+        ));
+
+        let mut wrong_root = tls;
+        wrong_root.ca_bundle_path = Some(fixture("client.pem"));
+        assert!(matches!(
+            check_provider_tls(&wrong_root, at(2030, 1, 1)),
+            ProviderTlsCheck::Fail(message) if message.contains("does not verify")
+        ));
+    }
+}
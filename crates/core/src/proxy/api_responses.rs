@@ -48,7 +48,7 @@ pub(super) async fn build_operator_read_capture(
             .state
             .capture_operator_lifecycle_snapshot(
                 proxy.service_name,
-                crate::state::recent_finished_max(),
+                proxy.state.recent_finished_max(),
             )
             .await;
         let state_revision = lifecycle_snapshot.state_revision;
@@ -147,6 +147,10 @@ async fn build_operator_read_model_once(
         })?;
     let route_template = route_graph.handshake_plan();
     let route_graph_key = route_graph.digest();
+    let auto_active = proxy
+        .state
+        .auto_active_selection(proxy.service_name, route_graph_key)
+        .await;
     let routing = build_operator_routing_summary(
         view,
         &route_template,
@@ -156,6 +160,10 @@ async fn build_operator_read_model_once(
             provider_policy_revision: provider_policy.policy_revision,
             new_session_preference: routing_control
                 .new_session_preference(proxy.service_name, route_graph_key),
+            auto_active: auto_active.as_ref().map(|selection| &selection.target),
+            upstream_pin: routing_control.global_upstream_pin(proxy.service_name, route_graph_key),
+            session_upstream_pins: &routing_control
+                .session_upstream_pins(proxy.service_name, route_graph_key),
         },
     )?;
     let operator_providers = providers
@@ -221,6 +229,7 @@ async fn build_operator_read_model_once(
         counts: OperatorSummaryCounts {
             active_requests: active.len(),
             recent_requests: recent.len(),
+            recent_requests_capacity: proxy.state.recent_finished_max(),
             sessions: sessions.len(),
             profiles: view.profiles.len(),
             providers: providers.len(),
@@ -286,6 +295,11 @@ async fn build_operator_read_model_once(
             usage_rollup,
             stats_5m,
             stats_1h,
+            request_quotas: proxy.state.request_quota_usage(
+                proxy.service_name,
+                &runtime_snapshot.config().quota,
+                captured_at_ms,
+            ),
             pricing_catalog: redact_operator_pricing_catalog(operator_pricing_catalog.snapshot()),
             service_status: Some(service_status),
             quota_analytics: redact_operator_quota_analytics(
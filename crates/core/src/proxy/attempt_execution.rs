@@ -3,7 +3,7 @@ use std::sync::OnceLock;
 use std::time::Instant;
 
 use axum::body::{Body, Bytes};
-use axum::http::{HeaderMap, HeaderValue, Method, Response, StatusCode, Uri, header};
+use axum::http::{HeaderMap, HeaderName, HeaderValue, Method, Response, StatusCode, Uri, header};
 
 use crate::endpoint_health::CooldownBackoff;
 use crate::logging::{
@@ -18,7 +18,8 @@ use crate::state::{
 
 use super::ProxyService;
 use super::attempt_request::{
-    AttemptRequestIdentity, AttemptRequestIdentityParams, prepare_attempt_request_identity,
+    AttemptRequestIdentity, AttemptRequestIdentityParams, UpstreamUserAgent,
+    prepare_attempt_request_identity,
 };
 use super::attempt_response::{
     AttemptResponseOutcome, AttemptResponseParams, StreamingAttemptResponseParams,
@@ -29,7 +30,7 @@ use super::attempt_transport::{
     AttemptTransportOutcome, AttemptTransportParams, handle_attempt_target_build_failure,
     handle_attempt_transport, read_attempt_response_body,
 };
-use super::concurrency_limits::ConcurrencyPermit;
+use super::concurrency_limits::{ConcurrencyPermit, ConcurrencySignal};
 use super::headers::filter_response_headers;
 use super::models_compat::ModelsTranslationScope;
 use super::reasoning_guard::should_strict_buffer_reasoning_guard;
@@ -38,6 +39,7 @@ use super::request_body::{
     is_stale_previous_response_error, remove_previous_response_id_from_body,
 };
 use super::request_preparation::RequestFlavor;
+use super::response_cache::{CacheableGetRequest, mark_cache_status, store_upstream_response};
 use super::response_entity::UpstreamResponseEntity;
 use super::response_fixer::{
     classify_remote_compaction_v2_response,
@@ -53,7 +55,7 @@ use super::selected_upstream_request::{
     SelectedModelMapping, SelectedUpstreamRequestSetupParams, apply_selected_model_mapping,
     prepare_selected_upstream_request,
 };
-use crate::routing_ir::CapturedRouteCandidate;
+use crate::routing_ir::{CapturedRouteCandidate, UpstreamPinScope};
 
 const UPSTREAM_AUTH_UNAVAILABLE_REASON: &str = "configured upstream credentials are unavailable";
 
@@ -90,7 +92,11 @@ struct PreparedRemoteCompactionDowngrade {
 
 pub(super) struct ExecuteSelectedUpstreamParams<'a> {
     pub(super) proxy: &'a ProxyService,
+    pub(super) upstream_client: &'a reqwest::Client,
+    pub(super) upstream_user_agent: &'a UpstreamUserAgent,
+    pub(super) upstream_strip_headers: &'a [HeaderName],
     pub(super) target: &'a CapturedRouteCandidate,
+    pub(super) pinned_by: Option<UpstreamPinScope>,
     pub(super) method: &'a Method,
     pub(super) uri: &'a Uri,
     pub(super) client_headers: &'a HeaderMap,
@@ -135,6 +141,7 @@ pub(super) struct ExecuteSelectedUpstreamParams<'a> {
     pub(super) route_attempts: &'a mut Vec<RouteAttemptLog>,
     pub(super) concurrency_permit: Option<ConcurrencyPermit>,
     pub(super) half_open_probe: Option<RuntimeHealthHalfOpenProbeLease>,
+    pub(super) response_cache: Option<&'a CacheableGetRequest>,
 }
 
 pub(super) async fn execute_selected_upstream(
@@ -142,7 +149,11 @@ pub(super) async fn execute_selected_upstream(
 ) -> SelectedUpstreamExecutionOutcome {
     let ExecuteSelectedUpstreamParams {
         proxy,
+        upstream_client,
+        upstream_user_agent,
+        upstream_strip_headers,
         target,
+        pinned_by,
         method,
         uri,
         client_headers,
@@ -187,6 +198,7 @@ pub(super) async fn execute_selected_upstream(
         route_attempts,
         mut concurrency_permit,
         mut half_open_probe,
+        response_cache,
     } = params;
 
     let remote_v2_downgrade_enabled = request_flavor.is_codex_service
@@ -231,10 +243,12 @@ pub(super) async fn execute_selected_upstream(
                     upstream_attempt: 0,
                     provider_max_attempts: provider_opt.max_attempts,
                     upstream_max_attempts: upstream_opt.max_attempts,
+                    max_upstreams_tried: plan.max_upstreams_tried,
                     model_note: model_mapping.model_note.as_str(),
                     avoid_set,
                     avoided_total: *avoided_total,
                     total_upstreams,
+                    pinned_by,
                 },
             );
             return match handle_attempt_target_build_failure(AttemptTargetBuildFailureParams {
@@ -259,7 +273,8 @@ pub(super) async fn execute_selected_upstream(
                 AttemptTransportOutcome::StopProviderChain => {
                     SelectedUpstreamExecutionOutcome::StopProviderChain
                 }
-                AttemptTransportOutcome::RetrySameUpstream
+                AttemptTransportOutcome::FastRetrySameUpstream
+                | AttemptTransportOutcome::RetrySameUpstream
                 | AttemptTransportOutcome::Continue(_) => {
                     unreachable!("target build failure cannot continue transport")
                 }
@@ -268,6 +283,8 @@ pub(super) async fn execute_selected_upstream(
     };
     let request_identity = match prepare_attempt_request_identity(AttemptRequestIdentityParams {
         service_name: proxy.service_name,
+        user_agent: upstream_user_agent,
+        strip_request_headers: upstream_strip_headers,
         credential: target.credential(),
         credential_scope: target.runtime_identity().credential_scope.as_deref(),
         state: proxy.state.as_ref(),
@@ -302,10 +319,12 @@ pub(super) async fn execute_selected_upstream(
                     upstream_attempt: 0,
                     provider_max_attempts: provider_opt.max_attempts,
                     upstream_max_attempts: upstream_opt.max_attempts,
+                    max_upstreams_tried: plan.max_upstreams_tried,
                     model_note: model_mapping.model_note.as_str(),
                     avoid_set,
                     avoided_total: *avoided_total,
                     total_upstreams,
+                    pinned_by,
                 },
             );
             tracing::warn!(
@@ -340,7 +359,8 @@ pub(super) async fn execute_selected_upstream(
                 AttemptTransportOutcome::StopProviderChain => {
                     SelectedUpstreamExecutionOutcome::StopProviderChain
                 }
-                AttemptTransportOutcome::RetrySameUpstream
+                AttemptTransportOutcome::FastRetrySameUpstream
+                | AttemptTransportOutcome::RetrySameUpstream
                 | AttemptTransportOutcome::Continue(_) => {
                     unreachable!("authentication resolution failure cannot continue transport")
                 }
@@ -423,6 +443,7 @@ pub(super) async fn execute_selected_upstream(
     let rectified_previous_response_body =
         remove_previous_response_id_from_body(&selected_filtered_body);
 
+    let mut connection_reset_fast_retries = plan.connection_reset_fast_retries;
     for upstream_attempt in 0..upstream_opt.max_attempts {
         let mut current_filtered_body = selected_filtered_body.clone();
         let mut current_upstream_request_body_len = selected_upstream_request_body_len;
@@ -456,15 +477,28 @@ pub(super) async fn execute_selected_upstream(
                     upstream_attempt,
                     provider_max_attempts: provider_opt.max_attempts,
                     upstream_max_attempts: upstream_opt.max_attempts,
+                    max_upstreams_tried: plan.max_upstreams_tried,
                     model_note: model_note.as_str(),
                     avoid_set,
                     avoided_total: *avoided_total,
                     total_upstreams,
+                    pinned_by,
                 },
             );
+            if !request_identity.stripped_headers.is_empty() {
+                tracing::debug!(
+                    request_id,
+                    provider_id = target.provider_id(),
+                    headers = request_identity.stripped_headers.join(",").as_str(),
+                    "stripped client request headers before forwarding"
+                );
+                route_attempts[route_attempt_index].stripped_request_headers =
+                    request_identity.stripped_headers.clone();
+            }
 
             let transport = handle_attempt_transport(AttemptTransportParams {
                 proxy,
+                upstream_client,
                 target,
                 method,
                 target_url: &target_url,
@@ -487,6 +521,7 @@ pub(super) async fn execute_selected_upstream(
                 last_http_debug,
                 upstream_opt,
                 upstream_attempt,
+                connection_reset_fast_retries: &mut connection_reset_fast_retries,
                 transport_cooldown_secs: plan.transport_cooldown_secs,
                 cooldown_backoff,
                 avoid_set,
@@ -509,6 +544,10 @@ pub(super) async fn execute_selected_upstream(
                 attempt_handle,
                 dispatched_half_open_probe,
             ) = match transport {
+                AttemptTransportOutcome::FastRetrySameUpstream => {
+                    *global_attempt = global_attempt.saturating_add(1);
+                    continue;
+                }
                 AttemptTransportOutcome::RetrySameUpstream => break,
                 AttemptTransportOutcome::TryNextUpstream => {
                     return SelectedUpstreamExecutionOutcome::ContinueProviderChain;
@@ -526,9 +565,19 @@ pub(super) async fn execute_selected_upstream(
                 ),
             };
             let status = resp.status();
+            if let (Some(permit), Some(signal)) = (
+                concurrency_permit.as_ref(),
+                ConcurrencySignal::from_status(status),
+            ) {
+                permit.record(signal);
+            }
             let success = status.is_success();
             let resp_headers = resp.headers().clone();
-            let resp_headers_filtered = filter_response_headers(&resp_headers);
+            let mut resp_headers_filtered =
+                filter_response_headers(&resp_headers, &request_flavor.response_header_policy);
+            if response_cache.is_some() {
+                mark_cache_status(&mut resp_headers_filtered, false);
+            }
             let strict_buffer_reasoning_guard = request_flavor.is_stream
                 && success
                 && should_strict_buffer_reasoning_guard(
@@ -626,6 +675,18 @@ pub(super) async fn execute_selected_upstream(
                 } => (bytes, half_open_probe),
             };
             let upstream_body_read_ms = upstream_body_read_started.elapsed().as_millis() as u64;
+            if let Some(cache) = response_cache {
+                store_upstream_response(
+                    proxy,
+                    cache,
+                    target,
+                    status,
+                    &resp_headers,
+                    &bytes,
+                    crate::logging::now_ms(),
+                )
+                .await;
+            }
 
             if remote_v2_downgrade_enabled && half_open_probe.is_none() {
                 let classification =
@@ -634,6 +695,8 @@ pub(super) async fn execute_selected_upstream(
                     let prepared = prepare_remote_compaction_downgrade(
                         PrepareRemoteCompactionDowngradeParams {
                             proxy,
+                            upstream_user_agent,
+                            upstream_strip_headers,
                             target,
                             uri,
                             client_uri,
@@ -721,14 +784,17 @@ pub(super) async fn execute_selected_upstream(
                                 upstream_attempt: compact_upstream_attempt,
                                 provider_max_attempts: provider_opt.max_attempts,
                                 upstream_max_attempts: upstream_opt.max_attempts,
+                                max_upstreams_tried: plan.max_upstreams_tried,
                                 model_note: model_note.as_str(),
                                 avoid_set,
                                 avoided_total: *avoided_total,
                                 total_upstreams,
+                                pinned_by,
                             },
                         );
                         let transport = handle_attempt_transport(AttemptTransportParams {
                             proxy,
+                            upstream_client,
                             target,
                             method,
                             target_url: &prepared.target_url,
@@ -751,6 +817,8 @@ pub(super) async fn execute_selected_upstream(
                             last_http_debug,
                             upstream_opt,
                             upstream_attempt: compact_upstream_attempt,
+                            // The compaction fallback stays on the normal retry plan.
+                            connection_reset_fast_retries: &mut 0,
                             transport_cooldown_secs: plan.transport_cooldown_secs,
                             cooldown_backoff,
                             avoid_set,
@@ -773,7 +841,8 @@ pub(super) async fn execute_selected_upstream(
                             compact_attempt_handle,
                             compact_half_open_probe,
                         ) = match transport {
-                            AttemptTransportOutcome::RetrySameUpstream => {
+                            AttemptTransportOutcome::FastRetrySameUpstream
+                            | AttemptTransportOutcome::RetrySameUpstream => {
                                 continue 'compact_attempts;
                             }
                             AttemptTransportOutcome::TryNextUpstream => {
@@ -795,7 +864,10 @@ pub(super) async fn execute_selected_upstream(
                         let compact_status = compact_resp.status();
                         let compact_success = compact_status.is_success();
                         let compact_headers = compact_resp.headers().clone();
-                        let compact_headers_filtered = filter_response_headers(&compact_headers);
+                        let compact_headers_filtered = filter_response_headers(
+                            &compact_headers,
+                            &request_flavor.response_header_policy,
+                        );
                         let compact_body_read_started = Instant::now();
                         let compact_bytes =
                             match read_attempt_response_body(AttemptReadBodyParams {
@@ -1129,6 +1201,8 @@ pub(super) async fn execute_selected_upstream(
 
 struct PrepareRemoteCompactionDowngradeParams<'a> {
     proxy: &'a ProxyService,
+    upstream_user_agent: &'a UpstreamUserAgent,
+    upstream_strip_headers: &'a [HeaderName],
     target: &'a CapturedRouteCandidate,
     uri: &'a Uri,
     client_uri: &'a str,
@@ -1143,6 +1217,8 @@ async fn prepare_remote_compaction_downgrade(
 ) -> Result<PreparedRemoteCompactionDowngrade, String> {
     let PrepareRemoteCompactionDowngradeParams {
         proxy,
+        upstream_user_agent,
+        upstream_strip_headers,
         target,
         uri,
         client_uri,
@@ -1162,6 +1238,8 @@ async fn prepare_remote_compaction_downgrade(
         .map_err(|error| format!("invalid remote compaction v1 target: {error}"))?;
     let request_identity = prepare_attempt_request_identity(AttemptRequestIdentityParams {
         service_name: proxy.service_name,
+        user_agent: upstream_user_agent,
+        strip_request_headers: upstream_strip_headers,
         credential: target.credential(),
         credential_scope: target.runtime_identity().credential_scope.as_deref(),
         state: proxy.state.as_ref(),
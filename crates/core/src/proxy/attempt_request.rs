@@ -31,10 +31,40 @@ pub(super) struct AttemptRequestSetup {
 pub(super) struct AttemptRequestIdentity {
     pub(super) headers: HeaderMap,
     pub(super) account_fingerprint: AccountFingerprint,
+    /// Client headers that were dropped instead of forwarded (lowercase names only).
+    pub(super) stripped_headers: Vec<String>,
+}
+
+/// User-Agent resolved for one provider from `http.user_agent` and the provider's `user_agent`.
+#[derive(Debug, Clone, Default)]
+pub(super) struct UpstreamUserAgent {
+    /// Replaces the client's header; `None` forwards it.
+    pub(super) configured: Option<HeaderValue>,
+}
+
+impl UpstreamUserAgent {
+    /// The client's header has already been filtered out of `headers`. A configured value wins,
+    /// then the client's own header, then `codex-helper/<version>`.
+    fn apply(&self, client_headers: &HeaderMap, headers: &mut HeaderMap) {
+        static DEFAULT: OnceLock<HeaderValue> = OnceLock::new();
+        let value = self
+            .configured
+            .as_ref()
+            .or_else(|| client_headers.get(header::USER_AGENT))
+            .unwrap_or_else(|| {
+                DEFAULT.get_or_init(|| {
+                    HeaderValue::from_str(&crate::config::default_upstream_user_agent())
+                        .unwrap_or_else(|_| HeaderValue::from_static("codex-helper"))
+                })
+            });
+        headers.insert(header::USER_AGENT, value.clone());
+    }
 }
 
 pub(super) struct AttemptRequestIdentityParams<'a> {
     pub(super) service_name: &'a str,
+    pub(super) user_agent: &'a UpstreamUserAgent,
+    pub(super) strip_request_headers: &'a [HeaderName],
     pub(super) credential: &'a CapturedUpstreamCredential,
     pub(super) credential_scope: Option<&'a str>,
     pub(super) state: &'a crate::state::ProxyState,
@@ -100,6 +130,8 @@ pub(super) fn prepare_attempt_request_identity(
 ) -> Result<AttemptRequestIdentity, UpstreamAuthResolutionError> {
     let AttemptRequestIdentityParams {
         service_name,
+        user_agent,
+        strip_request_headers,
         credential,
         credential_scope,
         state,
@@ -111,6 +143,10 @@ pub(super) fn prepare_attempt_request_identity(
     // Codex client credentials pass through only to the official origin when helper auth is absent.
     let mut headers = filter_request_headers(client_headers);
     strip_codex_client_facade_marker(&mut headers);
+    let client_header_names = headers.keys().cloned().collect::<Vec<_>>();
+    for name in strip_request_headers {
+        headers.remove(name);
+    }
     headers.insert(
         header::ACCEPT_ENCODING,
         HeaderValue::from_static("identity"),
@@ -118,10 +154,17 @@ pub(super) fn prepare_attempt_request_identity(
     inject_auth_headers(service_name, credential, target_url, &mut headers)?;
     normalize_codex_compact_headers(service_name, client_uri, &mut headers);
     let account_fingerprint = state.derive_provider_account_fingerprint(credential_scope, &headers);
+    user_agent.apply(client_headers, &mut headers);
+    let stripped_headers = client_header_names
+        .iter()
+        .filter(|name| !headers.contains_key(*name))
+        .map(|name| name.as_str().to_string())
+        .collect();
 
     Ok(AttemptRequestIdentity {
         headers,
         account_fingerprint,
+        stripped_headers,
     })
 }
 
@@ -158,6 +201,8 @@ pub(super) fn prepare_attempt_request(
     let state = crate::state::ProxyState::new();
     let identity = prepare_attempt_request_identity(AttemptRequestIdentityParams {
         service_name,
+        user_agent: &UpstreamUserAgent::default(),
+        strip_request_headers: &[],
         credential: &credential,
         credential_scope: runtime_identity.credential_scope.as_deref(),
         state: state.as_ref(),
@@ -297,15 +342,18 @@ pub(super) fn inject_auth_headers(
         headers.remove(CODEX_CLIENT_FACADE_ACTOR_HEADER);
     }
 
+    // A provider with its own credential never sees the client's, even in the header it does
+    // not use itself (a bearer token next to an `x-api-key` provider, and vice versa).
+    let forward_client_auth = allow_client_passthrough && !helper_credential_contract;
     if let Some(value) = token {
         headers.insert(HeaderName::from_static("authorization"), value);
-    } else if client_has_auth && !allow_client_passthrough {
+    } else if client_has_auth && !forward_client_auth {
         headers.remove("authorization");
     }
 
     if let Some(value) = api_key {
         headers.insert(HeaderName::from_static("x-api-key"), value);
-    } else if client_has_x_api_key && !allow_client_passthrough {
+    } else if client_has_x_api_key && !forward_client_auth {
         headers.remove("x-api-key");
     }
     if let Some(value) = headers.get_mut(CODEX_CLIENT_FACADE_ACTOR_HEADER) {
@@ -491,6 +539,68 @@ mod tests {
         }
     }
 
+    #[test]
+    fn provider_auth_replaces_client_authorization_and_strips_configured_headers() {
+        let mut client_headers = HeaderMap::new();
+        client_headers.insert(
+            "authorization",
+            HeaderValue::from_static("Bearer client-token"),
+        );
+        client_headers.insert("x-client-secret", HeaderValue::from_static("hunter2"));
+        client_headers.insert("anthropic-version", HeaderValue::from_static("2023-06-01"));
+        let state = crate::state::ProxyState::new();
+        let prepare = |auth: &UpstreamAuth, strip: &[HeaderName]| {
+            let credential = CapturedUpstreamCredential::from_config_for_test("claude", auth);
+            prepare_attempt_request_identity(AttemptRequestIdentityParams {
+                service_name: "claude",
+                user_agent: &UpstreamUserAgent::default(),
+                strip_request_headers: strip,
+                credential: &credential,
+                credential_scope: None,
+                state: state.as_ref(),
+                client_headers: &client_headers,
+                client_uri: "/v1/messages",
+                target_url: "https://relay.example/v1/messages",
+            })
+            .expect("prepare attempt identity")
+        };
+
+        let bearer = prepare(
+            &UpstreamAuth {
+                auth_token: Some("server-token".to_string().into()),
+                ..UpstreamAuth::default()
+            },
+            &[],
+        );
+        assert_eq!(
+            bearer.headers.get("authorization"),
+            Some(&HeaderValue::from_static("Bearer server-token"))
+        );
+        assert!(bearer.stripped_headers.is_empty());
+
+        let api_key = prepare(
+            &UpstreamAuth {
+                api_key: Some("server-key".to_string().into()),
+                ..UpstreamAuth::default()
+            },
+            &[HeaderName::from_static("x-client-secret")],
+        );
+        assert!(!api_key.headers.contains_key("authorization"));
+        assert!(!api_key.headers.contains_key("x-client-secret"));
+        assert_eq!(
+            api_key.headers.get("x-api-key"),
+            Some(&HeaderValue::from_static("server-key"))
+        );
+        assert_eq!(
+            api_key.headers.get("anthropic-version"),
+            Some(&HeaderValue::from_static("2023-06-01"))
+        );
+        assert_eq!(
+            api_key.stripped_headers,
+            vec!["authorization".to_string(), "x-client-secret".to_string()]
+        );
+    }
+
     #[test]
     fn prepare_attempt_request_rejects_unconfigured_remote_relay_by_default() {
         let credential =
@@ -498,6 +608,8 @@ mod tests {
         let state = crate::state::ProxyState::new();
         let result = prepare_attempt_request_identity(AttemptRequestIdentityParams {
             service_name: "codex",
+            user_agent: &UpstreamUserAgent::default(),
+            strip_request_headers: &[],
             credential: &credential,
             credential_scope: None,
             state: state.as_ref(),
@@ -520,6 +632,8 @@ mod tests {
         let prepare = |headers: &HeaderMap| {
             prepare_attempt_request_identity(AttemptRequestIdentityParams {
                 service_name: "codex",
+                user_agent: &UpstreamUserAgent::default(),
+                strip_request_headers: &[],
                 credential: &credential,
                 credential_scope: None,
                 state: state.as_ref(),
@@ -51,8 +51,9 @@ use super::response_semantics::{
     validate_success_response_semantics,
 };
 use super::retry::{
-    RetryLayerOptions, RetryPlan, response_penalty_cooldown_secs, retry_info_for_observed_attempts,
-    retry_sleep, should_never_retry, should_retry_class, should_retry_status,
+    RetryLayerOptions, RetryPlan, honored_retry_after_ms, response_penalty_cooldown_secs,
+    retry_info_for_observed_attempts, retry_sleep, should_never_retry, should_retry_class,
+    should_retry_status,
 };
 use super::route_affinity::prepare_session_route_affinity_success;
 use super::route_attempts::{
@@ -429,6 +430,10 @@ pub(super) async fn handle_attempt_response(
         mut half_open_probe,
     } = params;
 
+    // The body is fully buffered before we get here, so the client-visible first byte is
+    // the winning upstream's response headers, measured from the start of the request.
+    let ttfb_ms = duration_ms.saturating_sub(upstream_body_read_ms);
+    let upstream_request_id = super::headers::upstream_request_id(&response_headers);
     let upstream_status = status;
     let upstream_response_body = response_body.clone();
     let mut response_headers_filtered = response_headers_filtered;
@@ -697,7 +702,7 @@ pub(super) async fn handle_attempt_response(
             response_status,
             duration_ms,
             started_at_ms,
-            upstream_headers_ms,
+            ttfb_ms,
             provider_id,
             session_id,
             session_identity_source,
@@ -709,6 +714,7 @@ pub(super) async fn handle_attempt_response(
             attempt_handle,
             codex_bridge.clone(),
             usage,
+            upstream_request_id.clone(),
             Some(route_decision_from_model_note(
                 route_attempts,
                 route_attempt_index,
@@ -778,7 +784,7 @@ pub(super) async fn handle_attempt_response(
                 response_status,
                 duration_ms,
                 started_at_ms,
-                upstream_headers_ms,
+                ttfb_ms,
                 provider_id,
                 session_id,
                 session_identity_source,
@@ -790,6 +796,7 @@ pub(super) async fn handle_attempt_response(
                 attempt_handle,
                 codex_bridge.clone(),
                 None,
+                upstream_request_id.clone(),
                 Some(route_decision_from_model_note(
                     route_attempts,
                     route_attempt_index,
@@ -809,13 +816,12 @@ pub(super) async fn handle_attempt_response(
     if decision.retry_same_upstream {
         settle_half_open_probe_neutral(proxy.state.as_ref(), half_open_probe.take()).await;
         *last_http_debug = http_debug;
-        retry_sleep(
-            upstream_opt,
-            upstream_attempt,
-            &response_headers,
-            retry_after_secs,
-        )
-        .await;
+        let honored_retry_after_ms =
+            honored_retry_after_ms(upstream_opt, &response_headers, retry_after_secs);
+        if let Some(attempt) = route_attempts.get_mut(route_attempt_index) {
+            attempt.retry_after_ms = honored_retry_after_ms;
+        }
+        retry_sleep(upstream_opt, upstream_attempt, honored_retry_after_ms).await;
         return AttemptResponseOutcome::RetrySameUpstream;
     }
 
@@ -867,7 +873,7 @@ pub(super) async fn handle_attempt_response(
                 response_status,
                 duration_ms,
                 started_at_ms,
-                upstream_headers_ms,
+                ttfb_ms,
                 provider_id,
                 session_id,
                 session_identity_source,
@@ -879,6 +885,7 @@ pub(super) async fn handle_attempt_response(
                 attempt_handle,
                 codex_bridge.clone(),
                 None,
+                upstream_request_id.clone(),
                 Some(route_decision_from_model_note(
                     route_attempts,
                     route_attempt_index,
@@ -907,7 +914,7 @@ pub(super) async fn handle_attempt_response(
             response_status,
             duration_ms,
             started_at_ms,
-            upstream_headers_ms,
+            ttfb_ms,
             provider_id,
             session_id,
             session_identity_source,
@@ -919,6 +926,7 @@ pub(super) async fn handle_attempt_response(
             attempt_handle,
             codex_bridge,
             None,
+            upstream_request_id.clone(),
             Some(route_decision_from_model_note(
                 route_attempts,
                 route_attempt_index,
@@ -936,13 +944,8 @@ pub(super) async fn handle_attempt_response(
 }
 
 async fn enqueue_usage_probe_for_target(proxy: &ProxyService, target: &CapturedRouteCandidate) {
-    let provider_catalog = proxy.config.capture().await.usage_provider_catalog();
-    super::providers_api::enqueue_provider_balance_probe(
-        proxy.client.clone(),
-        proxy.state.clone(),
-        target.clone(),
-        provider_catalog,
-    );
+    let runtime = proxy.config.capture().await;
+    super::providers_api::enqueue_provider_balance_probe(proxy, &runtime, target.clone());
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -955,7 +958,7 @@ async fn finish_attempt_forward_response(
     status: StatusCode,
     duration_ms: u64,
     started_at_ms: u64,
-    upstream_headers_ms: u64,
+    ttfb_ms: u64,
     provider_id: Option<&str>,
     session_id: Option<&str>,
     session_identity_source: Option<SessionIdentitySource>,
@@ -967,6 +970,7 @@ async fn finish_attempt_forward_response(
     attempt_handle: AttemptHandle,
     codex_bridge: Option<CodexBridgeLog>,
     usage: Option<UsageMetrics>,
+    upstream_request_id: Option<String>,
     route_decision: Option<crate::state::RouteDecisionProvenance>,
     retry: Option<crate::logging::RetryInfo>,
     route_affinity_success: Option<SessionRouteAffinitySuccess>,
@@ -990,7 +994,7 @@ async fn finish_attempt_forward_response(
             status,
             duration_ms,
             started_at_ms,
-            upstream_headers_ms,
+            ttfb_ms,
             provider_id: provider_id
                 .map(ToOwned::to_owned)
                 .or_else(|| Some(target.provider_id().to_owned())),
@@ -1005,6 +1009,8 @@ async fn finish_attempt_forward_response(
             reported_model,
             codex_bridge,
             usage,
+            upstream_request_id,
+            cache_hit: false,
             route_decision,
             retry,
             route_affinity_success,
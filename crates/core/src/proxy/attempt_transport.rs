@@ -23,6 +23,9 @@ use super::attempt_failures::{TerminalUpstreamFailureParams, apply_terminal_upst
 use super::attempt_request::{
     AttemptRequestIdentity, FrozenAttemptRequestSetupParams, prepare_attempt_request_with_identity,
 };
+use super::classify::{
+    UPSTREAM_TLS_CERTIFICATE_CLASS, is_connection_reset, tls_certificate_problem,
+};
 use super::http_debug::{
     HttpDebugBase, HttpDebugTransportErrorParams, format_reqwest_error_for_retry_chain,
     warn_http_debug,
@@ -34,6 +37,7 @@ use super::route_attempts::{
     record_http_debug_route_attempt,
 };
 use crate::routing_ir::CapturedRouteCandidate;
+use crate::upstream_dns::no_address_for_family;
 
 pub(super) struct AttemptTransportSuccess {
     pub(super) response: reqwest::Response,
@@ -45,6 +49,9 @@ pub(super) struct AttemptTransportSuccess {
 }
 
 pub(super) enum AttemptTransportOutcome {
+    /// Connection reset before response headers; retry the same upstream now without
+    /// consuming an upstream attempt.
+    FastRetrySameUpstream,
     RetrySameUpstream,
     TryNextUpstream,
     StopProviderChain,
@@ -70,7 +77,7 @@ enum ResponseBodyReadError {
     },
 }
 
-fn upstream_response_body_max_bytes() -> usize {
+pub(super) fn upstream_response_body_max_bytes() -> usize {
     static MAX: OnceLock<usize> = OnceLock::new();
     *MAX.get_or_init(|| {
         std::env::var("CODEX_HELPER_UPSTREAM_RESPONSE_BODY_MAX_BYTES")
@@ -155,6 +162,8 @@ async fn read_response_body_with_limit(
 
 pub(super) struct AttemptTransportParams<'a> {
     pub(super) proxy: &'a ProxyService,
+    /// Shared proxy client, or the provider's dedicated client when it configures `[tls]`.
+    pub(super) upstream_client: &'a reqwest::Client,
     pub(super) target: &'a CapturedRouteCandidate,
     pub(super) method: &'a Method,
     pub(super) target_url: &'a reqwest::Url,
@@ -177,6 +186,8 @@ pub(super) struct AttemptTransportParams<'a> {
     pub(super) last_http_debug: &'a mut Option<HttpDebugLog>,
     pub(super) upstream_opt: &'a RetryLayerOptions,
     pub(super) upstream_attempt: u32,
+    /// Remaining `retry.connection_reset_fast_retries` budget for this upstream.
+    pub(super) connection_reset_fast_retries: &'a mut u32,
     pub(super) transport_cooldown_secs: u64,
     pub(super) cooldown_backoff: crate::endpoint_health::CooldownBackoff,
     pub(super) avoid_set: &'a mut HashSet<usize>,
@@ -285,6 +296,7 @@ pub(super) async fn handle_attempt_transport(
 ) -> AttemptTransportOutcome {
     let AttemptTransportParams {
         proxy,
+        upstream_client,
         target,
         method,
         target_url,
@@ -307,6 +319,7 @@ pub(super) async fn handle_attempt_transport(
         last_http_debug,
         upstream_opt,
         upstream_attempt,
+        connection_reset_fast_retries,
         transport_cooldown_secs,
         cooldown_backoff,
         avoid_set,
@@ -350,8 +363,7 @@ pub(super) async fn handle_attempt_transport(
         .await;
     let debug_base = attempt_request.debug_base;
 
-    let builder = proxy
-        .client
+    let builder = upstream_client
         .request(method.clone(), target_url.clone())
         .headers(headers)
         .body(filtered_body.clone());
@@ -417,9 +429,39 @@ pub(super) async fn handle_attempt_transport(
 
     let upstream_start = Instant::now();
     let response = match builder.send().await {
-        Ok(response) => response,
+        Ok(response) => {
+            if let Some(remote_addr) = response.remote_addr() {
+                tracing::debug!(
+                    request_id,
+                    provider_id = target.provider_id(),
+                    %remote_addr,
+                    address_family = if remote_addr.is_ipv6() { "ipv6" } else { "ipv4" },
+                    "upstream responded"
+                );
+            }
+            response
+        }
         Err(error) => {
-            let err_str = format_reqwest_error_for_retry_chain(&error);
+            let tls_problem = tls_certificate_problem(&error);
+            let err_str = match (tls_problem, no_address_for_family(&error)) {
+                (Some(problem), _) => {
+                    problem.failure_message(error.url().and_then(|url| url.host_str()))
+                }
+                (None, Some(problem)) => problem.to_string(),
+                (None, None) => format_reqwest_error_for_retry_chain(&error),
+            };
+            let (error_class, error_hint, error_kind) = match tls_problem {
+                Some(problem) => (
+                    UPSTREAM_TLS_CERTIFICATE_CLASS,
+                    problem.description(),
+                    RouteAttemptErrorKind::TlsCertificate,
+                ),
+                None => (
+                    "upstream_transport_error",
+                    "upstream connection failed before response headers were received",
+                    RouteAttemptErrorKind::Transport,
+                ),
+            };
             let status_code = StatusCode::BAD_GATEWAY.as_u16();
             if should_include_http_warn(status_code)
                 && let Some(http_debug) = debug_base.as_ref().and_then(|base| {
@@ -427,9 +469,8 @@ pub(super) async fn handle_attempt_transport(
                         response_headers: None,
                         upstream_headers_ms: None,
                         upstream_body_read_ms: None,
-                        error_class: "upstream_transport_error",
-                        error_hint:
-                            "upstream connection failed before response headers were received",
+                        error_class,
+                        error_hint,
                         upstream_error: err_str.clone(),
                         for_warn: true,
                     })
@@ -439,16 +480,17 @@ pub(super) async fn handle_attempt_transport(
             }
             *last_http_debug = should_include_http_debug(status_code)
                 .then(|| {
-                    debug_base.as_ref()?.transport_error_log(HttpDebugTransportErrorParams {
-                        response_headers: None,
-                        upstream_headers_ms: None,
-                        upstream_body_read_ms: None,
-                        error_class: "upstream_transport_error",
-                        error_hint:
-                            "upstream connection failed before response headers were received",
-                        upstream_error: err_str.clone(),
-                        for_warn: false,
-                    })
+                    debug_base
+                        .as_ref()?
+                        .transport_error_log(HttpDebugTransportErrorParams {
+                            response_headers: None,
+                            upstream_headers_ms: None,
+                            upstream_body_read_ms: None,
+                            error_class,
+                            error_hint,
+                            upstream_error: err_str.clone(),
+                            for_warn: false,
+                        })
                 })
                 .flatten();
             record_http_debug_route_attempt(
@@ -478,7 +520,36 @@ pub(super) async fn handle_attempt_transport(
                 *last_err = Some((StatusCode::INTERNAL_SERVER_ERROR, message));
                 return AttemptTransportOutcome::StopProviderChain;
             }
-            let can_retry_upstream = replay_policy.allows_after_dispatch()
+            if replay_policy.allows_after_dispatch()
+                && *connection_reset_fast_retries > 0
+                && is_connection_reset(&error)
+            {
+                *connection_reset_fast_retries -= 1;
+                record_error_route_attempt(
+                    route_attempts,
+                    ErrorRouteAttemptParams {
+                        target,
+                        route_attempt_index,
+                        kind: RouteAttemptErrorKind::Transport,
+                        model_note,
+                        duration_ms: Some(upstream_start.elapsed().as_millis() as u64),
+                        cooldown_secs: None,
+                        cooldown_reason: None,
+                    },
+                );
+                if let Some(attempt) = route_attempts.get_mut(route_attempt_index) {
+                    attempt.fast_retry = true;
+                }
+                tracing::info!(
+                    request_id,
+                    provider_id = target.provider_id(),
+                    "upstream reset the connection before responding; retrying immediately"
+                );
+                return AttemptTransportOutcome::FastRetrySameUpstream;
+            }
+            // A rejected certificate fails the same way on every retry, so only failover helps.
+            let can_retry_upstream = tls_problem.is_none()
+                && replay_policy.allows_after_dispatch()
                 && upstream_attempt + 1 < upstream_opt.max_attempts
                 && should_retry_class(upstream_opt, Some("upstream_transport_error"));
             record_error_route_attempt(
@@ -486,7 +557,7 @@ pub(super) async fn handle_attempt_transport(
                 ErrorRouteAttemptParams {
                     target,
                     route_attempt_index,
-                    kind: RouteAttemptErrorKind::Transport,
+                    kind: error_kind,
                     model_note,
                     duration_ms: Some(upstream_start.elapsed().as_millis() as u64),
                     cooldown_secs: (!can_retry_upstream
@@ -494,7 +565,7 @@ pub(super) async fn handle_attempt_transport(
                     .then_some(transport_cooldown_secs),
                     cooldown_reason: (!can_retry_upstream
                         && shared_route_state_impact.allows_shared_updates())
-                    .then_some("upstream_transport_error"),
+                    .then_some(error_class),
                 },
             );
             if can_retry_upstream {
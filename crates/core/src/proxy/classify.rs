@@ -12,6 +12,7 @@ pub(super) const UPSTREAM_OVERLOADED_CLASS: &str = "upstream_overloaded";
 pub(super) const CLIENT_ERROR_NON_RETRYABLE_CLASS: &str = "client_error_non_retryable";
 pub(super) const UPSTREAM_CREDENTIAL_CLASS: &str = "upstream_credential_error";
 pub(super) const UPSTREAM_SERVER_ERROR_CLASS: &str = "upstream_server_error";
+pub(super) const UPSTREAM_TLS_CERTIFICATE_CLASS: &str = "upstream_tls_certificate_error";
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum ProtocolFailureScope {
@@ -644,7 +645,7 @@ fn json_get_duration_secs(v: &Value, key: &str) -> Option<u64> {
     v.get(key).and_then(json_value_to_duration_secs)
 }
 
-fn retry_after_secs_from_header(headers: &HeaderMap, name: &str) -> Option<u64> {
+pub(super) fn retry_after_secs_from_header(headers: &HeaderMap, name: &str) -> Option<u64> {
     let raw = headers.get(name)?.to_str().ok()?.trim();
     if raw.is_empty() {
         return None;
@@ -882,6 +883,120 @@ pub(super) fn class_is_health_neutral(class: Option<&str>) -> bool {
     )
 }
 
+/// True when the peer reset the connection, e.g. a CDN edge dropping a fresh connection before
+/// it answers. Walks the source chain because reqwest wraps the hyper and io errors.
+pub(super) fn is_connection_reset(error: &(dyn std::error::Error + 'static)) -> bool {
+    let mut current = Some(error);
+    while let Some(error) = current {
+        if let Some(io_error) = error.downcast_ref::<std::io::Error>() {
+            if io_error.kind() == std::io::ErrorKind::ConnectionReset {
+                return true;
+            }
+            if let Some(inner) = io_error.get_ref()
+                && is_connection_reset(inner)
+            {
+                return true;
+            }
+        }
+        current = error.source();
+    }
+    false
+}
+
+/// Why the TLS handshake rejected the upstream's certificate.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum TlsCertificateProblem {
+    Expired,
+    UntrustedIssuer,
+    HostnameMismatch,
+    Rejected,
+}
+
+impl TlsCertificateProblem {
+    pub(super) fn description(self) -> &'static str {
+        match self {
+            Self::Expired => "certificate has expired",
+            Self::UntrustedIssuer => "certificate is self-signed or issued by an untrusted CA",
+            Self::HostnameMismatch => "certificate does not match the host name",
+            Self::Rejected => "certificate was rejected",
+        }
+    }
+
+    pub(super) fn suggestion(self) -> &'static str {
+        match self {
+            Self::Expired => "renew the upstream certificate or check the system clock",
+            Self::UntrustedIssuer => "add the issuing CA to the provider's tls.ca_bundle_path",
+            Self::HostnameMismatch => "check the host in the provider's base_url",
+            Self::Rejected => {
+                "check the upstream certificate chain or add its CA to tls.ca_bundle_path"
+            }
+        }
+    }
+
+    /// One-line, non-retryable failure text naming the host, the problem and a fix.
+    pub(super) fn failure_message(self, host: Option<&str>) -> String {
+        format!(
+            "upstream TLS certificate error for {}: {}; {}",
+            host.unwrap_or("upstream"),
+            self.description(),
+            self.suggestion()
+        )
+    }
+}
+
+/// Detects a certificate verification failure anywhere in the source chain. Matches on the error
+/// text because rustls and native-tls surface it through different wrapped error types.
+pub(super) fn tls_certificate_problem(
+    error: &(dyn std::error::Error + 'static),
+) -> Option<TlsCertificateProblem> {
+    let mut current = Some(error);
+    while let Some(error) = current {
+        let text = error.to_string().to_ascii_lowercase();
+        if let Some(problem) = tls_certificate_problem_from_text(&text) {
+            return Some(problem);
+        }
+        current = error.source();
+    }
+    None
+}
+
+fn tls_certificate_problem_from_text(text: &str) -> Option<TlsCertificateProblem> {
+    let is_certificate_failure = [
+        "invalid peer certificate",
+        "certificate verify failed",
+        "self signed certificate",
+        "self-signed certificate",
+        "certificate has expired",
+    ]
+    .iter()
+    .any(|marker| text.contains(marker));
+    if !is_certificate_failure {
+        return None;
+    }
+    let problem = if text.contains("expired") {
+        TlsCertificateProblem::Expired
+    } else if [
+        "unknownissuer",
+        "unknown issuer",
+        "self signed",
+        "self-signed",
+        "local issuer",
+    ]
+    .iter()
+    .any(|marker| text.contains(marker))
+    {
+        TlsCertificateProblem::UntrustedIssuer
+    } else if ["notvalidforname", "not valid for name", "hostname mismatch"]
+        .iter()
+        .any(|marker| text.contains(marker))
+    {
+        TlsCertificateProblem::HostnameMismatch
+    } else {
+        TlsCertificateProblem::Rejected
+    };
+    Some(problem)
+}
+
 fn capability_message_indicates_mismatch(message: &str) -> bool {
     let m = message.to_ascii_lowercase();
 
@@ -1166,6 +1281,59 @@ mod tests {
         );
     }
 
+    #[test]
+    fn connection_reset_is_detected_through_wrapped_io_errors() {
+        use std::io::{Error, ErrorKind};
+
+        assert!(is_connection_reset(&Error::from(
+            ErrorKind::ConnectionReset
+        )));
+        assert!(is_connection_reset(&Error::other(Error::from(
+            ErrorKind::ConnectionReset
+        ))));
+        assert!(!is_connection_reset(&Error::from(
+            ErrorKind::ConnectionRefused
+        )));
+        assert!(!is_connection_reset(&Error::other("connection closed")));
+    }
+
+    #[test]
+    fn tls_certificate_errors_are_classified_through_wrapped_errors() {
+        use std::io::{Error, ErrorKind};
+
+        let expired = Error::other(Error::new(
+            ErrorKind::InvalidData,
+            "invalid peer certificate: Expired",
+        ));
+        assert_eq!(
+            tls_certificate_problem(&expired),
+            Some(TlsCertificateProblem::Expired)
+        );
+        let self_signed = Error::other(
+            "error:0A000086:SSL routines::certificate verify failed:../ssl/statem/statem_clnt.c:1889: (self-signed certificate)",
+        );
+        assert_eq!(
+            tls_certificate_problem(&self_signed),
+            Some(TlsCertificateProblem::UntrustedIssuer)
+        );
+        let wrong_host = Error::new(
+            ErrorKind::InvalidData,
+            "invalid peer certificate: NotValidForName",
+        );
+        assert_eq!(
+            tls_certificate_problem(&wrong_host),
+            Some(TlsCertificateProblem::HostnameMismatch)
+        );
+        assert_eq!(
+            tls_certificate_problem(&Error::from(ErrorKind::ConnectionRefused)),
+            None
+        );
+
+        let message = TlsCertificateProblem::UntrustedIssuer.failure_message(Some("relay.example"));
+        assert!(message.contains("relay.example"));
+        assert!(message.contains("tls.ca_bundle_path"));
+    }
+
     #[test]
     fn classifies_529_as_overloaded_without_body_keywords() {
         let headers = HeaderMap::new();
@@ -6,7 +6,7 @@ use serde_json::Value;
 
 use crate::state::SessionIdentitySource;
 
-use super::CLIENT_NAME_HEADER;
+use super::{CLIENT_NAME_HEADER, TENANT_HEADER};
 
 fn header_str<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
     headers.get(name).and_then(|value| value.to_str().ok())
@@ -139,6 +139,10 @@ pub(super) fn extract_client_name(headers: &HeaderMap) -> Option<String> {
         })
 }
 
+pub(super) fn extract_tenant(headers: &HeaderMap) -> Option<String> {
+    header_str(headers, TENANT_HEADER).and_then(|value| normalize_client_identity_value(value, 80))
+}
+
 pub(super) fn extract_client_addr(extensions: &Extensions) -> Option<String> {
     extensions
         .get::<ConnectInfo<SocketAddr>>()
@@ -155,9 +159,9 @@ mod tests {
 
     use super::{
         extract_client_addr, extract_client_name, extract_session_identity,
-        extract_session_identity_with_body_fallback,
+        extract_session_identity_with_body_fallback, extract_tenant,
     };
-    use crate::proxy::CLIENT_NAME_HEADER;
+    use crate::proxy::{CLIENT_NAME_HEADER, TENANT_HEADER};
     use crate::state::SessionIdentitySource;
 
     #[test]
@@ -334,6 +338,18 @@ mod tests {
         assert!(name.chars().all(|ch| ch == 'A'));
     }
 
+    #[test]
+    fn extract_tenant_normalizes_the_header_and_ignores_blank_values() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(extract_tenant(&headers), None);
+
+        headers.insert(TENANT_HEADER, HeaderValue::from_static("   "));
+        assert_eq!(extract_tenant(&headers), None);
+
+        headers.insert(TENANT_HEADER, HeaderValue::from_static("  alice   team-a "));
+        assert_eq!(extract_tenant(&headers).as_deref(), Some("alice team-a"));
+    }
+
     #[test]
     fn extract_client_addr_reads_connect_info() {
         let mut extensions = Extensions::new();
@@ -13,7 +13,9 @@ use crate::config::UpstreamConfig;
 use crate::credentials::{CapturedUpstreamCredential, CredentialReadinessCode};
 use crate::model_routing;
 
-use super::classify::{ROUTING_MISMATCH_CAPABILITY_CLASS, classify_upstream_response};
+use super::classify::{
+    ROUTING_MISMATCH_CAPABILITY_CLASS, classify_upstream_response, tls_certificate_problem,
+};
 use super::codex_relay_target::{CodexRelayTargetSelection, select_codex_relay_target};
 use super::{ProxyControlError, ProxyService};
 
@@ -1161,13 +1163,15 @@ fn transport_result(
     )
 }
 
-fn reqwest_live_smoke_transport_reason(error: &reqwest::Error) -> &'static str {
-    if error.is_timeout() {
-        "live smoke request timed out"
+fn reqwest_live_smoke_transport_reason(error: &reqwest::Error) -> String {
+    if let Some(problem) = tls_certificate_problem(error) {
+        problem.failure_message(error.url().and_then(|url| url.host_str()))
+    } else if error.is_timeout() {
+        "live smoke request timed out".to_string()
     } else if error.is_connect() {
-        "live smoke connection failed"
+        "live smoke connection failed".to_string()
     } else {
-        "live smoke transport error"
+        "live smoke transport error".to_string()
     }
 }
 
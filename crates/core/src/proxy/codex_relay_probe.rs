@@ -8,7 +8,9 @@ use serde_json::Value;
 use crate::config::UpstreamConfig;
 use crate::credentials::{CapturedUpstreamCredential, CredentialReadinessCode};
 
-use super::classify::{ROUTING_MISMATCH_CAPABILITY_CLASS, classify_upstream_response};
+use super::classify::{
+    ROUTING_MISMATCH_CAPABILITY_CLASS, classify_upstream_response, tls_certificate_problem,
+};
 use super::models_compat::{ModelsTranslationScope, maybe_decode_models_response_body};
 
 const MAX_PROBE_RESPONSE_BYTES: usize = 2 * 1024 * 1024;
@@ -557,13 +559,15 @@ fn transport_observation(
     }
 }
 
-fn reqwest_probe_transport_reason(error: &reqwest::Error) -> &'static str {
-    if error.is_timeout() {
-        "probe request timed out"
+fn reqwest_probe_transport_reason(error: &reqwest::Error) -> String {
+    if let Some(problem) = tls_certificate_problem(error) {
+        problem.failure_message(error.url().and_then(|url| url.host_str()))
+    } else if error.is_timeout() {
+        "probe request timed out".to_string()
     } else if error.is_connect() {
-        "probe connection failed"
+        "probe connection failed".to_string()
     } else {
-        "probe transport error"
+        "probe transport error".to_string()
     }
 }
 
@@ -591,7 +595,10 @@ fn build_probe_url(base_url: &str, path: &str) -> Result<reqwest::Url, String> {
     reqwest::Url::parse(&full).map_err(|_| "invalid probe url".to_string())
 }
 
-async fn read_limited_body(response: reqwest::Response, max_bytes: usize) -> Result<Bytes, String> {
+pub(super) async fn read_limited_body(
+    response: reqwest::Response,
+    max_bytes: usize,
+) -> Result<Bytes, String> {
     let mut stream = response.bytes_stream();
     let mut out = Vec::new();
     while let Some(chunk) = stream.next().await {
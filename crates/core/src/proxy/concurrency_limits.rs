@@ -3,6 +3,7 @@ use std::num::NonZeroU32;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
+use axum::http::StatusCode;
 use tokio::sync::oneshot;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -45,10 +46,52 @@ struct ConcurrencyGate {
 struct ConcurrencyGateState {
     active: u32,
     observed_limit: Option<ConcurrencyLimit>,
+    adaptive: Option<AdaptiveLimit>,
+    /// Bumped on every adaptive backoff; pressure from permits granted earlier is ignored.
+    backoff_epoch: u64,
     next_waiter_id: u64,
     waiters: VecDeque<ConcurrencyWaiter>,
 }
 
+/// AIMD state for `lb.adaptive_concurrency`: halve on pressure, add one after a full window of
+/// successes, always within `floor..=observed maximum`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct AdaptiveLimit {
+    floor: u32,
+    /// `None` while the configured maximum applies.
+    limit: Option<u32>,
+    successes: u32,
+}
+
+impl AdaptiveLimit {
+    fn new(floor: u32) -> Self {
+        Self {
+            floor,
+            limit: None,
+            successes: 0,
+        }
+    }
+}
+
+/// Upstream feedback that drives the adaptive limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum ConcurrencySignal {
+    Success,
+    Pressure,
+}
+
+impl ConcurrencySignal {
+    pub(super) fn from_status(status: StatusCode) -> Option<Self> {
+        if status.is_success() {
+            Some(Self::Success)
+        } else if status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error() {
+            Some(Self::Pressure)
+        } else {
+            None
+        }
+    }
+}
+
 #[derive(Debug)]
 struct ConcurrencyWaiter {
     id: u64,
@@ -59,11 +102,20 @@ struct ConcurrencyWaiter {
 #[derive(Debug, Default)]
 pub(super) struct ConcurrencyLimiter {
     gates: Mutex<HashMap<String, Arc<ConcurrencyGate>>>,
+    adaptive_floor: Mutex<Option<u32>>,
 }
 
 #[derive(Debug)]
 pub(super) struct ConcurrencyPermit {
     gate: Arc<ConcurrencyGate>,
+    backoff_epoch: u64,
+}
+
+impl ConcurrencyPermit {
+    /// Feeds one upstream outcome to the gate's adaptive limit; a no-op when it is disabled.
+    pub(super) fn record(&self, signal: ConcurrencySignal) {
+        self.gate.record_signal(self.backoff_epoch, signal);
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -121,6 +173,25 @@ enum ConcurrencyAdmission {
 }
 
 impl ConcurrencyLimiter {
+    pub(super) fn with_adaptive_floor(floor: Option<u32>) -> Self {
+        Self {
+            gates: Mutex::default(),
+            adaptive_floor: Mutex::new(floor),
+        }
+    }
+
+    /// Applies `lb.adaptive_concurrency` after a config reload; `None` restores the static limits.
+    pub(super) fn set_adaptive_floor(&self, floor: Option<u32>) {
+        *lock_unpoisoned(&self.adaptive_floor) = floor;
+        let gates = lock_unpoisoned(&self.gates)
+            .values()
+            .cloned()
+            .collect::<Vec<_>>();
+        for gate in gates {
+            gate.set_adaptive_floor(floor);
+        }
+    }
+
     pub(super) fn snapshot(&self, key: &str, limit: ConcurrencyLimit) -> ConcurrencySnapshot {
         let gate = self.gate_for_key(key);
         gate.snapshot(limit)
@@ -212,7 +283,10 @@ impl ConcurrencyLimiter {
         }
 
         let gate = Arc::new(ConcurrencyGate {
-            state: Mutex::new(ConcurrencyGateState::default()),
+            state: Mutex::new(ConcurrencyGateState {
+                adaptive: lock_unpoisoned(&self.adaptive_floor).map(AdaptiveLimit::new),
+                ..ConcurrencyGateState::default()
+            }),
         });
         gates.insert(key.to_string(), gate.clone());
         gate
@@ -254,15 +328,16 @@ impl ConcurrencyGate {
 
     fn current_snapshot(&self) -> ConcurrencySnapshot {
         let state = self.lock_state();
-        let limit = state
+        let observed = state
             .observed_limit
             .expect("concurrency gate must observe a limit before use");
+        let limit = current_limit(&state);
         ConcurrencySnapshot {
             active: state.active,
             pending: pending_count(state.waiters.len()),
-            limit: limit.value(),
-            runtime_revision: limit.runtime_revision,
-            saturated: state.active >= limit.value(),
+            limit,
+            runtime_revision: observed.runtime_revision,
+            saturated: state.active >= limit,
         }
     }
 
@@ -283,7 +358,10 @@ impl ConcurrencyGate {
             let current_limit = current_limit(&state);
             let result = if state.waiters.is_empty() && state.active < current_limit {
                 state.active += 1;
-                Ok(ConcurrencyPermit { gate: self.clone() })
+                Ok(ConcurrencyPermit {
+                    gate: self.clone(),
+                    backoff_epoch: state.backoff_epoch,
+                })
             } else {
                 Err(ConcurrencyAcquireError::Saturated {
                     active: state.active,
@@ -311,6 +389,7 @@ impl ConcurrencyGate {
                 state.active += 1;
                 Ok(ConcurrencyAdmission::Immediate(ConcurrencyPermit {
                     gate: self.clone(),
+                    backoff_epoch: state.backoff_epoch,
                 }))
             } else if !policy.allows_waiting() {
                 Err(ConcurrencyAcquireError::Saturated {
@@ -368,6 +447,57 @@ impl ConcurrencyGate {
         drop(failed_permits);
     }
 
+    fn set_adaptive_floor(self: &Arc<Self>, floor: Option<u32>) {
+        let failed_permits = {
+            let mut state = self.lock_state();
+            state.adaptive = match (state.adaptive, floor) {
+                (_, None) => None,
+                (Some(adaptive), Some(floor)) => Some(AdaptiveLimit { floor, ..adaptive }),
+                (None, Some(floor)) => Some(AdaptiveLimit::new(floor)),
+            };
+            if state.observed_limit.is_none() {
+                return;
+            }
+            self.promote_waiters_locked(&mut state)
+        };
+        drop(failed_permits);
+    }
+
+    fn record_signal(self: &Arc<Self>, permit_epoch: u64, signal: ConcurrencySignal) {
+        let failed_permits = {
+            let mut state = self.lock_state();
+            let (Some(observed), Some(mut adaptive)) = (state.observed_limit, state.adaptive)
+            else {
+                return;
+            };
+            let max = observed.value();
+            let current = current_limit(&state);
+            match signal {
+                ConcurrencySignal::Success if current < max => {
+                    adaptive.successes += 1;
+                    if adaptive.successes >= current {
+                        adaptive.successes = 0;
+                        adaptive.limit = (current + 1 < max).then_some(current + 1);
+                    }
+                }
+                ConcurrencySignal::Success => adaptive.successes = 0,
+                // Requests already in flight when the limit dropped report the same overload.
+                ConcurrencySignal::Pressure if permit_epoch < state.backoff_epoch => {}
+                ConcurrencySignal::Pressure => {
+                    let lowered = (current / 2).max(adaptive.floor.min(max));
+                    adaptive.successes = 0;
+                    if lowered < current {
+                        adaptive.limit = Some(lowered);
+                        state.backoff_epoch = state.backoff_epoch.wrapping_add(1);
+                    }
+                }
+            }
+            state.adaptive = Some(adaptive);
+            self.promote_waiters_locked(&mut state)
+        };
+        drop(failed_permits);
+    }
+
     fn cancel_waiter(self: &Arc<Self>, id: u64) -> bool {
         let (removed, failed_permits) = {
             let mut state = self.lock_state();
@@ -395,7 +525,10 @@ impl ConcurrencyGate {
                 break;
             };
             state.active += 1;
-            let permit = ConcurrencyPermit { gate: self.clone() };
+            let permit = ConcurrencyPermit {
+                gate: self.clone(),
+                backoff_epoch: state.backoff_epoch,
+            };
             if let Err(permit) = waiter.permit_tx.send(permit) {
                 failed_permits.push(permit);
             }
@@ -446,10 +579,25 @@ fn observe_limit(state: &mut ConcurrencyGateState, observed: ConcurrencyLimit) {
 }
 
 fn current_limit(state: &ConcurrencyGateState) -> u32 {
-    state
+    let max = state
         .observed_limit
         .expect("concurrency gate must observe a limit before use")
-        .value()
+        .value();
+    state
+        .adaptive
+        .and_then(|adaptive| {
+            adaptive
+                .limit
+                .map(|limit| limit.clamp(adaptive.floor.min(max), max))
+        })
+        .unwrap_or(max)
+}
+
+fn lock_unpoisoned<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    match mutex.lock() {
+        Ok(guard) => guard,
+        Err(error) => error.into_inner(),
+    }
 }
 
 #[cfg(test)]
@@ -529,6 +677,68 @@ mod tests {
             .expect("permit after old request exits");
     }
 
+    #[test]
+    fn adaptive_limit_halves_on_pressure_and_recovers_additively() {
+        let limiter = ConcurrencyLimiter::with_adaptive_floor(Some(2));
+        let max = capacity(8, 1);
+        let permit = limiter
+            .try_acquire("relay".to_string(), max)
+            .expect("permit at configured max");
+        let stale = limiter
+            .try_acquire("relay".to_string(), max)
+            .expect("second in-flight permit");
+        assert_eq!(limiter.snapshot("relay", max).limit, 8);
+
+        permit.record(ConcurrencySignal::Pressure);
+        assert_eq!(limiter.snapshot("relay", max).limit, 4);
+        // Already in flight when the limit dropped: the same overload must not halve it again.
+        stale.record(ConcurrencySignal::Pressure);
+        assert_eq!(limiter.snapshot("relay", max).limit, 4);
+        drop((permit, stale));
+
+        let permit = limiter
+            .try_acquire("relay".to_string(), max)
+            .expect("permit after backoff");
+        permit.record(ConcurrencySignal::Pressure);
+        permit.record(ConcurrencySignal::Pressure);
+        assert_eq!(limiter.snapshot("relay", max).limit, 2, "floor holds");
+
+        for _ in 0..2 {
+            permit.record(ConcurrencySignal::Success);
+        }
+        assert_eq!(limiter.snapshot("relay", max).limit, 3);
+        for _ in 0..(3 + 4 + 5 + 6 + 7) {
+            permit.record(ConcurrencySignal::Success);
+        }
+        assert_eq!(limiter.snapshot("relay", max).limit, 8);
+        assert_eq!(
+            ConcurrencySignal::from_status(StatusCode::BAD_REQUEST),
+            None
+        );
+        assert_eq!(
+            ConcurrencySignal::from_status(StatusCode::TOO_MANY_REQUESTS),
+            Some(ConcurrencySignal::Pressure)
+        );
+    }
+
+    #[test]
+    fn disabling_adaptive_limit_restores_configured_max() {
+        let limiter = ConcurrencyLimiter::default();
+        let max = capacity(4, 1);
+        let permit = limiter
+            .try_acquire("relay".to_string(), max)
+            .expect("permit");
+        permit.record(ConcurrencySignal::Pressure);
+        assert_eq!(limiter.snapshot("relay", max).limit, 4, "static by default");
+
+        limiter.set_adaptive_floor(Some(1));
+        permit.record(ConcurrencySignal::Pressure);
+        assert_eq!(limiter.snapshot("relay", max).limit, 2);
+
+        limiter.set_adaptive_floor(None);
+        assert_eq!(limiter.snapshot("relay", max).limit, 4);
+    }
+
     #[tokio::test]
     async fn limiter_admits_bounded_waiters_in_fifo_order() {
         let limiter = Arc::new(ConcurrencyLimiter::default());
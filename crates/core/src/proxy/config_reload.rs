@@ -0,0 +1,230 @@
+use std::collections::BTreeSet;
+
+use tokio::sync::watch;
+
+use super::control_plane_service::service_route_config;
+use super::{ProxyControlError, ProxyService};
+use crate::config::ServiceRouteConfig;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RuntimeConfigReloadSummary {
+    pub changed: bool,
+    pub added_providers: Vec<String>,
+    pub removed_providers: Vec<String>,
+    pub updated_providers: Vec<String>,
+    pub added_profiles: Vec<String>,
+    pub removed_profiles: Vec<String>,
+    pub default_profile_changed: bool,
+    pub routing_changed: bool,
+}
+
+impl RuntimeConfigReloadSummary {
+    pub fn between(changed: bool, before: &ServiceRouteConfig, after: &ServiceRouteConfig) -> Self {
+        let updated_providers = before
+            .providers
+            .iter()
+            .filter(|(name, provider)| {
+                after
+                    .providers
+                    .get(*name)
+                    .is_some_and(|next| !same_serialized(next, *provider))
+            })
+            .map(|(name, _)| name.clone())
+            .collect();
+        Self {
+            changed,
+            added_providers: keys_only_in(&after.providers, &before.providers),
+            removed_providers: keys_only_in(&before.providers, &after.providers),
+            updated_providers,
+            added_profiles: keys_only_in(&after.profiles, &before.profiles),
+            removed_profiles: keys_only_in(&before.profiles, &after.profiles),
+            default_profile_changed: before.default_profile != after.default_profile,
+            routing_changed: !same_serialized(&before.routing, &after.routing),
+        }
+    }
+
+    /// One-line description of the route-relevant differences, or `None` when nothing moved.
+    pub fn describe(&self) -> Option<String> {
+        let mut parts = Vec::new();
+        for (label, names) in [
+            ("added providers", &self.added_providers),
+            ("removed providers", &self.removed_providers),
+            ("updated providers", &self.updated_providers),
+            ("added profiles", &self.added_profiles),
+            ("removed profiles", &self.removed_profiles),
+        ] {
+            if !names.is_empty() {
+                parts.push(format!("{label}: {}", names.join(", ")));
+            }
+        }
+        if self.default_profile_changed {
+            parts.push("default profile changed".to_string());
+        }
+        if self.routing_changed {
+            parts.push("routing changed".to_string());
+        }
+        (!parts.is_empty()).then(|| parts.join("; "))
+    }
+}
+
+// Config types do not implement `PartialEq`; compare their persisted form instead.
+fn same_serialized<T: serde::Serialize>(left: &T, right: &T) -> bool {
+    serde_json::to_value(left).ok() == serde_json::to_value(right).ok()
+}
+
+fn keys_only_in<V>(
+    left: &std::collections::BTreeMap<String, V>,
+    right: &std::collections::BTreeMap<String, V>,
+) -> Vec<String> {
+    let right: BTreeSet<&String> = right.keys().collect();
+    left.keys()
+        .filter(|key| !right.contains(key))
+        .cloned()
+        .collect()
+}
+
+impl ProxyService {
+    /// Re-reads the config file through [`ProxyService::reload_runtime_config`] and reports which
+    /// providers and profiles of this service were added, removed, or updated.
+    pub async fn reload_runtime_config_with_summary(
+        &self,
+    ) -> Result<RuntimeConfigReloadSummary, ProxyControlError> {
+        let before = self.config.capture().await;
+        let changed = self.reload_runtime_config().await?;
+        let after = self.config.capture().await;
+        Ok(RuntimeConfigReloadSummary::between(
+            changed,
+            service_route_config(before.config().as_ref(), self.service_name),
+            service_route_config(after.config().as_ref(), self.service_name),
+        ))
+    }
+}
+
+/// Reloads the runtime config on every SIGHUP until shutdown.
+///
+/// In-flight requests and runtime health are kept; a failed reload leaves the last known good
+/// snapshot in place. Windows has no SIGHUP, so this returns immediately there and operators use
+/// the control API (`POST /__codex_helper/local/v1/operator/runtime/reload`) or the GUI instead.
+pub async fn reload_runtime_config_on_sighup(
+    proxy: ProxyService,
+    mut shutdown_rx: watch::Receiver<bool>,
+) {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{SignalKind, signal};
+        let mut hangup = match signal(SignalKind::hangup()) {
+            Ok(hangup) => hangup,
+            Err(error) => {
+                tracing::warn!(
+                    "[{}] SIGHUP config reload unavailable: {error}",
+                    proxy.service_name
+                );
+                return;
+            }
+        };
+        loop {
+            tokio::select! {
+                received = hangup.recv() => {
+                    if received.is_none() {
+                        return;
+                    }
+                }
+                _ = shutdown_rx.changed() => {
+                    if *shutdown_rx.borrow() {
+                        return;
+                    }
+                    continue;
+                }
+            }
+            log_runtime_config_reload(
+                proxy.service_name,
+                proxy.reload_runtime_config_with_summary().await,
+            );
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = (proxy, &mut shutdown_rx);
+    }
+}
+
+fn log_runtime_config_reload(
+    service_name: &str,
+    result: Result<RuntimeConfigReloadSummary, ProxyControlError>,
+) {
+    match result {
+        Ok(summary) if !summary.changed => {
+            tracing::info!("[{service_name}] SIGHUP config reload: no changes on disk")
+        }
+        Ok(summary) => tracing::info!(
+            "[{service_name}] SIGHUP config reload applied: {}",
+            summary
+                .describe()
+                .unwrap_or_else(|| "no route changes for this service".to_string())
+        ),
+        Err(error) => tracing::warn!(
+            "[{service_name}] SIGHUP config reload failed; keeping the last known good config: {}",
+            error.message()
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ProviderConfig;
+
+    fn provider(base_url: &str) -> ProviderConfig {
+        ProviderConfig {
+            base_url: Some(base_url.to_string()),
+            ..ProviderConfig::default()
+        }
+    }
+
+    #[test]
+    fn reload_summary_reports_added_removed_and_updated_providers() {
+        let mut before = ServiceRouteConfig::default();
+        before
+            .providers
+            .insert("kept".to_string(), provider("https://kept.example/v1"));
+        before
+            .providers
+            .insert("edited".to_string(), provider("https://old.example/v1"));
+        before.providers.insert(
+            "dropped".to_string(),
+            provider("https://dropped.example/v1"),
+        );
+        let mut after = before.clone();
+        after.providers.remove("dropped");
+        after
+            .providers
+            .insert("edited".to_string(), provider("https://new.example/v1"));
+        after
+            .providers
+            .insert("fresh".to_string(), provider("https://fresh.example/v1"));
+
+        let summary = RuntimeConfigReloadSummary::between(true, &before, &after);
+
+        assert_eq!(summary.added_providers, vec!["fresh".to_string()]);
+        assert_eq!(summary.removed_providers, vec!["dropped".to_string()]);
+        assert_eq!(summary.updated_providers, vec!["edited".to_string()]);
+        assert!(!summary.routing_changed);
+        assert_eq!(
+            summary.describe().as_deref(),
+            Some("added providers: fresh; removed providers: dropped; updated providers: edited")
+        );
+    }
+
+    #[test]
+    fn reload_summary_is_silent_when_the_service_view_is_unchanged() {
+        let mut config = ServiceRouteConfig::default();
+        config
+            .providers
+            .insert("relay".to_string(), provider("https://relay.example/v1"));
+
+        let summary = RuntimeConfigReloadSummary::between(true, &config, &config);
+
+        assert!(summary.changed);
+        assert_eq!(summary.describe(), None);
+    }
+}
@@ -1,3 +1,5 @@
+mod live_events;
 mod operator_read_model;
 
+pub(super) use self::live_events::api_live_events;
 pub(super) use self::operator_read_model::api_operator_read_model;
@@ -0,0 +1,75 @@
+use std::convert::Infallible;
+
+use axum::response::sse::{Event, KeepAlive, Sse};
+use futures_util::Stream;
+use futures_util::stream;
+use tokio::sync::broadcast::Receiver;
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::state::LiveEvent;
+
+use super::super::ProxyService;
+
+pub(in crate::proxy) async fn api_live_events(
+    proxy: ProxyService,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    Sse::new(live_event_stream(proxy.state.subscribe_live_events()))
+        .keep_alive(KeepAlive::default())
+}
+
+fn live_event_stream(
+    receiver: Receiver<LiveEvent>,
+) -> impl Stream<Item = Result<Event, Infallible>> {
+    stream::unfold(receiver, |mut receiver| async move {
+        let event = match receiver.recv().await {
+            Ok(event) => sse_event(event.kind(), &event),
+            // The subscriber fell behind the bounded channel; report the gap and keep going.
+            Err(RecvError::Lagged(skipped)) => sse_event(
+                "lagged",
+                &serde_json::json!({ "type": "lagged", "skipped": skipped }),
+            ),
+            Err(RecvError::Closed) => return None,
+        };
+        Some((Ok(event), receiver))
+    })
+}
+
+fn sse_event(kind: &'static str, payload: &impl serde::Serialize) -> Event {
+    let data = serde_json::to_string(payload).unwrap_or_else(|_| "{}".to_string());
+    Event::default().event(kind).data(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::StreamExt;
+    use tokio::sync::broadcast;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn slow_subscriber_gets_a_lagged_marker_instead_of_blocking_publishers() {
+        let (sender, receiver) = broadcast::channel(2);
+        for request_id in 1..=3 {
+            sender
+                .send(LiveEvent::HealthChanged {
+                    service: "codex".to_string(),
+                    provider_id: format!("p{request_id}"),
+                    endpoint_id: "default".to_string(),
+                    breaker_open: true,
+                    at_ms: request_id,
+                })
+                .expect("send without waiting for the subscriber");
+        }
+        drop(sender);
+
+        let rendered = live_event_stream(receiver)
+            .map(|event| format!("{:?}", event.expect("infallible")))
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(rendered.len(), 3);
+        assert!(rendered[0].contains("lagged") && rendered[0].contains(r#"\"skipped\":1"#));
+        assert!(rendered[1].contains("health_changed") && rendered[1].contains("p2"));
+        assert!(rendered[2].contains("p3"));
+    }
+}
@@ -1,5 +1,8 @@
 pub(super) const API_V1_OPERATOR_READ_MODEL: &str = "/__codex_helper/api/v1/operator/read-model";
 pub(super) const API_V1_REQUEST_LEDGER_CHAIN: &str = "/__codex_helper/api/v1/request-ledger/chain";
+pub(super) const API_V1_EVENTS: &str = "/__codex_helper/api/v1/events";
+pub(super) const API_V1_OPERATOR_ROUTING_EXPLAIN: &str =
+    "/__codex_helper/api/v1/operator/routing-explain";
 
 pub(crate) const LOCAL_V1_BALANCE_REFRESH: &str =
     "/__codex_helper/local/v1/operator/balances/refresh";
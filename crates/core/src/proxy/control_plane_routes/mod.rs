@@ -4,19 +4,32 @@ use axum::routing::get;
 
 use super::ProxyService;
 use super::admin::{AdminAccessConfig, require_admin_access};
-use super::control_plane::api_operator_read_model;
-use super::control_plane_manifest::{API_V1_OPERATOR_READ_MODEL, API_V1_REQUEST_LEDGER_CHAIN};
-use super::runtime_admin_api::get_request_ledger_chain;
+use super::control_plane::{api_live_events, api_operator_read_model};
+use super::control_plane_manifest::{
+    API_V1_EVENTS, API_V1_OPERATOR_READ_MODEL, API_V1_OPERATOR_ROUTING_EXPLAIN,
+    API_V1_REQUEST_LEDGER_CHAIN,
+};
+use super::runtime_admin_api::{get_operator_routing_explain, get_request_ledger_chain};
 
 pub(super) fn control_plane_routes(proxy: ProxyService) -> Router {
     let admin_access = AdminAccessConfig::from_env();
     let read_model_proxy = proxy.clone();
+    let events_proxy = proxy.clone();
+    let explain_proxy = proxy.clone();
 
     Router::new()
         .route(
             API_V1_OPERATOR_READ_MODEL,
             get(move || api_operator_read_model(read_model_proxy.clone())),
         )
+        .route(
+            API_V1_EVENTS,
+            get(move || api_live_events(events_proxy.clone())),
+        )
+        .route(
+            API_V1_OPERATOR_ROUTING_EXPLAIN,
+            get(move |query| get_operator_routing_explain(explain_proxy.clone(), query)),
+        )
         .route(
             API_V1_REQUEST_LEDGER_CHAIN,
             get(move |query| get_request_ledger_chain(proxy.clone(), query)),
@@ -17,6 +17,20 @@ pub(super) fn service_route_config<'a>(
 
 pub(super) async fn prune_runtime_observability_after_reload(proxy: &ProxyService) {
     let snapshot = proxy.config.capture().await;
+    proxy.state.clear_get_response_cache().await;
+    proxy
+        .state
+        .set_recent_finished_max(
+            snapshot
+                .config()
+                .dashboard
+                .max_recent
+                .unwrap_or_else(crate::state::default_recent_finished_max),
+        )
+        .await;
+    proxy
+        .concurrency_limiter
+        .set_adaptive_floor(snapshot.config().lb.adaptive_concurrency.active_floor());
     let Some(graph) = snapshot.route_graph(proxy.service_name) else {
         return;
     };
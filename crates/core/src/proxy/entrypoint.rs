@@ -5,11 +5,14 @@ use axum::http::{Request, Response, StatusCode};
 use tracing::instrument;
 
 use super::ProxyService;
+use super::maintenance::maintenance_response;
 use super::provider_execution::{
     ExecuteProviderChainParams, ProviderExecutionOutcome,
     execute_provider_chain_with_route_executor, log_retry_options,
 };
 use super::request_context::prepare_proxy_request;
+use super::request_quota::request_quota_exceeded_response;
+use super::response_cache::cacheable_get_request;
 use super::retry::retry_info_for_failed_attempts;
 use super::route_unavailability::route_unavailable_response_for_request;
 
@@ -24,6 +27,11 @@ pub(crate) async fn handle_proxy(
         .map(|d| d.as_millis() as u64)
         .unwrap_or(0);
 
+    if let Some(response) = maintenance_response(&proxy).await {
+        return Ok(response);
+    }
+    let response_cache =
+        cacheable_get_request(&proxy, req.method(), req.uri(), req.headers()).await;
     let prepared = prepare_proxy_request(&proxy, req, &start, started_at_ms).await?;
     log_retry_options(proxy.service_name, prepared.request_id, &prepared.plan);
     let provider_chain_params = ExecuteProviderChainParams {
@@ -59,14 +67,18 @@ pub(crate) async fn handle_proxy(
         client_body_warn: prepared.client_body_warn.as_ref(),
         plan: &prepared.plan,
         cooldown_backoff: prepared.cooldown_backoff,
+        response_cache: response_cache.as_ref(),
     };
     let provider_execution =
         execute_provider_chain_with_route_executor(provider_chain_params).await;
-    let (route_attempts, last_err, last_http_debug) = match provider_execution {
+    let (route_attempts, last_err, last_http_debug, quota_exceeded) = match provider_execution {
         ProviderExecutionOutcome::Return(response) => return Ok(response),
-        ProviderExecutionOutcome::Exhausted(state) => {
-            (state.route_attempts, state.last_err, state.last_http_debug)
-        }
+        ProviderExecutionOutcome::Exhausted(state) => (
+            state.route_attempts,
+            state.last_err,
+            state.last_http_debug,
+            state.quota_exceeded,
+        ),
     };
 
     let dur = start.elapsed().as_millis() as u64;
@@ -102,6 +114,9 @@ pub(crate) async fn handle_proxy(
     )
     .await;
 
+    if let Some(exceeded) = quota_exceeded {
+        return Ok(request_quota_exceeded_response(&exceeded));
+    }
     if let Some(response) = route_unavailable_response_for_request(
         &prepared.request_flavor,
         failure.1.as_str(),
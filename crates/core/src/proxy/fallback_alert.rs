@@ -0,0 +1,169 @@
+use std::time::Duration;
+
+use super::ProxyService;
+use super::control_plane_service::service_route_config;
+use super::service_core::wait_for_proxy_shutdown;
+use crate::dashboard_core::WindowStats;
+use crate::dashboard_core::window_stats::compute_window_stats;
+
+const FALLBACK_ALERT_CHECK_INTERVAL: Duration = Duration::from_secs(15);
+/// Successful requests needed in the window before the share is trusted enough to alert on.
+const FALLBACK_ALERT_MIN_REQUESTS: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FallbackAlertTransition {
+    Raised,
+    Cleared,
+}
+
+/// Fires once per threshold crossing instead of on every check while the share stays high.
+#[derive(Debug, Default)]
+struct FallbackAlertLatch {
+    raised: bool,
+}
+
+impl FallbackAlertLatch {
+    fn observe(
+        &mut self,
+        stats: &WindowStats,
+        threshold_pct: u8,
+    ) -> Option<FallbackAlertTransition> {
+        let exceeded = stats.ok_2xx >= FALLBACK_ALERT_MIN_REQUESTS
+            && stats
+                .fallback_share
+                .is_some_and(|share| share * 100.0 > f64::from(threshold_pct));
+        match (self.raised, exceeded) {
+            (false, true) => {
+                self.raised = true;
+                Some(FallbackAlertTransition::Raised)
+            }
+            (true, false) => {
+                self.raised = false;
+                Some(FallbackAlertTransition::Cleared)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl ProxyService {
+    pub(super) async fn run_fallback_alert_driver(
+        &self,
+        mut shutdown_rx: tokio::sync::watch::Receiver<bool>,
+    ) {
+        let mut latch = FallbackAlertLatch::default();
+        loop {
+            self.check_fallback_alert(&mut latch).await;
+            tokio::select! {
+                biased;
+                _ = wait_for_proxy_shutdown(&mut shutdown_rx) => return,
+                () = tokio::time::sleep(FALLBACK_ALERT_CHECK_INTERVAL) => {}
+            }
+        }
+    }
+
+    async fn check_fallback_alert(&self, latch: &mut FallbackAlertLatch) {
+        let config = self.captured_runtime_config().await;
+        let routing =
+            crate::config::effective_routing(service_route_config(&config, self.service_name));
+        let Some((threshold_pct, window_secs)) = routing.fallback_alert() else {
+            *latch = FallbackAlertLatch::default();
+            return;
+        };
+        let recent = self
+            .state
+            .list_recent_finished(self.state.recent_finished_max())
+            .await;
+        let stats = compute_window_stats(
+            &recent,
+            crate::logging::now_ms(),
+            window_secs.saturating_mul(1000),
+            |request| request.service == self.service_name,
+        );
+        let Some(transition) = latch.observe(&stats, threshold_pct) else {
+            return;
+        };
+        let share_pct = stats.fallback_share.unwrap_or_default() * 100.0;
+        crate::logging::log_control_trace_event(serde_json::json!({
+            "event": match transition {
+                FallbackAlertTransition::Raised => "fallback_share_alert_raised",
+                FallbackAlertTransition::Cleared => "fallback_share_alert_cleared",
+            },
+            "service": self.service_name,
+            "fallback_requests": stats.fallback_2xx,
+            "successful_requests": stats.ok_2xx,
+            "share_pct": share_pct,
+            "threshold_pct": threshold_pct,
+            "window_secs": window_secs,
+        }));
+        if transition == FallbackAlertTransition::Cleared {
+            tracing::info!(
+                "[{}] fallback share back to {:.0}% (threshold {}%) over the last {}s",
+                self.service_name,
+                share_pct,
+                threshold_pct,
+                window_secs
+            );
+            return;
+        }
+
+        let summary = format!(
+            "{:.0}% of successful requests ({}/{}) were served by a fallback provider over the last {}s (threshold {}%)",
+            share_pct, stats.fallback_2xx, stats.ok_2xx, window_secs, threshold_pct
+        );
+        tracing::warn!(
+            "[{}] FALLBACK ALERT: {}; check the primary provider",
+            self.service_name,
+            summary
+        );
+        if config.notify.enabled && config.notify.system.enabled {
+            let title = format!("codex-helper: {} is running on fallback", self.service_name);
+            tokio::task::spawn_blocking(move || {
+                if let Err(error) = crate::notify::send_system_notification(&title, &summary) {
+                    tracing::warn!("failed to send fallback alert notification: {error}");
+                }
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(ok_2xx: usize, fallback_2xx: usize) -> WindowStats {
+        WindowStats {
+            total: ok_2xx,
+            ok_2xx,
+            fallback_2xx,
+            fallback_share: (ok_2xx > 0).then(|| fallback_2xx as f64 / ok_2xx as f64),
+            ..WindowStats::default()
+        }
+    }
+
+    #[test]
+    fn fallback_alert_latch_fires_once_per_crossing() {
+        let mut latch = FallbackAlertLatch::default();
+
+        assert_eq!(latch.observe(&stats(20, 5), 30), None);
+        assert_eq!(
+            latch.observe(&stats(20, 8), 30),
+            Some(FallbackAlertTransition::Raised)
+        );
+        assert_eq!(latch.observe(&stats(20, 12), 30), None);
+        assert_eq!(
+            latch.observe(&stats(20, 6), 30),
+            Some(FallbackAlertTransition::Cleared)
+        );
+    }
+
+    #[test]
+    fn fallback_alert_latch_ignores_small_samples() {
+        let mut latch = FallbackAlertLatch::default();
+
+        assert_eq!(
+            latch.observe(&stats(FALLBACK_ALERT_MIN_REQUESTS - 1, 9), 10),
+            None
+        );
+    }
+}
@@ -2,7 +2,7 @@ use axum::http::{HeaderMap, HeaderName};
 use zeroize::Zeroizing;
 
 use crate::codex_switch::{CODEX_CLIENT_FACADE_ACTOR_HEADER, CODEX_CLIENT_FACADE_ACTOR_VALUE};
-use crate::config::CODEX_CLIENT_RUNTIME_PATCH_HEADER;
+use crate::config::{CODEX_CLIENT_RUNTIME_PATCH_HEADER, ServiceRouteConfig};
 use crate::logging::HeaderEntry;
 
 fn is_hop_by_hop_header(name_lower: &str) -> bool {
@@ -30,6 +30,7 @@ fn is_request_header_to_strip(name_lower: &str) -> bool {
             | CODEX_CLIENT_RUNTIME_PATCH_HEADER
             | "x-forwarded-api-key"
             | "x-codex-helper-admin-token"
+            | super::TENANT_HEADER
     ) || is_hop_by_hop_header(name_lower)
 }
 
@@ -115,7 +116,58 @@ pub(super) fn strip_codex_client_facade_marker(headers: &mut HeaderMap) {
     }
 }
 
-pub(super) fn filter_response_headers(src: &HeaderMap) -> HeaderMap {
+/// Operator allow/deny lists for upstream response headers, from `[codex]`/`[claude]`.
+///
+/// Entries are case-insensitive names; a trailing `*` matches a prefix (`x-ratelimit-*`).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(super) struct ResponseHeaderPolicy {
+    allowlist: Vec<String>,
+    denylist: Vec<String>,
+}
+
+impl ResponseHeaderPolicy {
+    pub(super) fn from_service_config(config: &ServiceRouteConfig) -> Self {
+        let normalize = |names: &[String]| {
+            names
+                .iter()
+                .map(|name| name.trim().to_ascii_lowercase())
+                .filter(|name| !name.is_empty())
+                .collect::<Vec<_>>()
+        };
+        Self {
+            allowlist: normalize(&config.response_header_allowlist),
+            denylist: normalize(&config.response_header_denylist),
+        }
+    }
+
+    fn permits(&self, name_lower: &str) -> bool {
+        if header_pattern_matches(&self.denylist, name_lower) {
+            return false;
+        }
+        self.allowlist.is_empty()
+            || is_response_header_always_allowed(name_lower)
+            || header_pattern_matches(&self.allowlist, name_lower)
+    }
+}
+
+fn header_pattern_matches(patterns: &[String], name_lower: &str) -> bool {
+    patterns
+        .iter()
+        .any(|pattern| match pattern.strip_suffix('*') {
+            Some(prefix) => name_lower.starts_with(prefix),
+            None => pattern == name_lower,
+        })
+}
+
+/// Headers an allowlist never removes: body framing and rate-limit hints Codex relies on.
+fn is_response_header_always_allowed(name_lower: &str) -> bool {
+    matches!(
+        name_lower,
+        "content-type" | "content-encoding" | "retry-after" | "retry-after-ms"
+    ) || name_lower.starts_with("x-ratelimit-")
+}
+
+pub(super) fn filter_response_headers(src: &HeaderMap, policy: &ResponseHeaderPolicy) -> HeaderMap {
     let extra = hop_by_hop_connection_tokens(src);
     let mut out = HeaderMap::new();
     for (name, value) in src.iter() {
@@ -129,6 +181,9 @@ pub(super) fn filter_response_headers(src: &HeaderMap) -> HeaderMap {
         if extra.iter().any(|token| token == &name_lower) {
             continue;
         }
+        if !policy.permits(&name_lower) {
+            continue;
+        }
         out.append(name.clone(), value.clone());
     }
     out
@@ -185,18 +240,63 @@ pub(super) fn sensitive_header_values(headers: &HeaderMap) -> Vec<Zeroizing<Vec<
     values
 }
 
+/// Response headers providers put their own request id in, most specific first. `cf-ray` comes
+/// last: it only identifies the Cloudflare edge hop, but is often all a relay returns.
+const UPSTREAM_REQUEST_ID_HEADERS: &[&str] = &[
+    "x-request-id",
+    "request-id",
+    "openai-request-id",
+    "x-oai-request-id",
+    "cf-ray",
+];
+
+/// The provider's id for this response, which its support team asks for in tickets.
+pub(super) fn upstream_request_id(headers: &HeaderMap) -> Option<String> {
+    UPSTREAM_REQUEST_ID_HEADERS.iter().find_map(|name| {
+        headers
+            .get(*name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(ToOwned::to_owned)
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use axum::http::{HeaderMap, HeaderValue};
 
     use crate::codex_switch::{CODEX_CLIENT_FACADE_ACTOR_HEADER, CODEX_CLIENT_FACADE_ACTOR_VALUE};
-    use crate::config::CODEX_CLIENT_RUNTIME_PATCH_HEADER;
+    use crate::config::{CODEX_CLIENT_RUNTIME_PATCH_HEADER, ServiceRouteConfig};
 
     use super::{
-        filter_request_headers, filter_response_headers, header_map_to_entries,
-        strip_codex_client_facade_marker,
+        ResponseHeaderPolicy, filter_request_headers, filter_response_headers,
+        header_map_to_entries, strip_codex_client_facade_marker, upstream_request_id,
     };
 
+    #[test]
+    fn upstream_request_id_prefers_provider_ids_over_cf_ray() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(upstream_request_id(&headers), None);
+
+        headers.insert("cf-ray", HeaderValue::from_static("8f1c2a-SJC"));
+        assert_eq!(upstream_request_id(&headers).as_deref(), Some("8f1c2a-SJC"));
+
+        headers.insert("request-id", HeaderValue::from_static(" req_anthropic "));
+        assert_eq!(
+            upstream_request_id(&headers).as_deref(),
+            Some("req_anthropic")
+        );
+
+        headers.insert("x-request-id", HeaderValue::from_static(""));
+        assert_eq!(
+            upstream_request_id(&headers).as_deref(),
+            Some("req_anthropic")
+        );
+        headers.insert("x-request-id", HeaderValue::from_static("req_relay"));
+        assert_eq!(upstream_request_id(&headers).as_deref(), Some("req_relay"));
+    }
+
     #[test]
     fn request_header_filter_removes_hop_by_hop_and_connection_targets() {
         let mut headers = HeaderMap::new();
@@ -223,6 +323,10 @@ mod tests {
             CODEX_CLIENT_RUNTIME_PATCH_HEADER,
             HeaderValue::from_static("v1;models=1;hosted=disabled"),
         );
+        headers.insert(
+            crate::proxy::TENANT_HEADER,
+            HeaderValue::from_static("alice"),
+        );
         headers.insert("x-keep-me", HeaderValue::from_static("ok"));
 
         let filtered = filter_request_headers(&headers);
@@ -237,6 +341,7 @@ mod tests {
         assert!(!filtered.contains_key("x-forwarded-api-key"));
         assert!(!filtered.contains_key("x-codex-helper-admin-token"));
         assert!(!filtered.contains_key(CODEX_CLIENT_RUNTIME_PATCH_HEADER));
+        assert!(!filtered.contains_key(crate::proxy::TENANT_HEADER));
         assert_eq!(
             filtered.get("authorization"),
             Some(&HeaderValue::from_static("Bearer secret"))
@@ -261,7 +366,7 @@ mod tests {
         );
         headers.insert("content-type", HeaderValue::from_static("application/json"));
 
-        let filtered = filter_response_headers(&headers);
+        let filtered = filter_response_headers(&headers, &ResponseHeaderPolicy::default());
 
         assert!(!filtered.contains_key("content-length"));
         assert_eq!(
@@ -278,6 +383,51 @@ mod tests {
         );
     }
 
+    #[test]
+    fn response_header_policy_applies_denylist_and_keeps_rate_limit_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "content-type",
+            HeaderValue::from_static("text/event-stream"),
+        );
+        headers.insert("retry-after", HeaderValue::from_static("30"));
+        headers.insert(
+            "x-ratelimit-remaining-requests",
+            HeaderValue::from_static("12"),
+        );
+        headers.insert("x-provider-debug", HeaderValue::from_static("node-7"));
+        headers.insert("x-request-id", HeaderValue::from_static("req_1"));
+        headers.insert("keep-alive", HeaderValue::from_static("timeout=5"));
+
+        let deny = ResponseHeaderPolicy::from_service_config(&ServiceRouteConfig {
+            response_header_denylist: vec!["X-Provider-*".to_string()],
+            ..ServiceRouteConfig::default()
+        });
+        let filtered = filter_response_headers(&headers, &deny);
+        assert!(!filtered.contains_key("x-provider-debug"));
+        assert!(!filtered.contains_key("keep-alive"));
+        assert_eq!(
+            filtered.get("retry-after"),
+            Some(&HeaderValue::from_static("30"))
+        );
+        assert!(filtered.contains_key("x-ratelimit-remaining-requests"));
+        assert!(filtered.contains_key("x-request-id"));
+
+        let allow = ResponseHeaderPolicy::from_service_config(&ServiceRouteConfig {
+            response_header_allowlist: vec!["x-request-id".to_string()],
+            ..ServiceRouteConfig::default()
+        });
+        let filtered = filter_response_headers(&headers, &allow);
+        assert!(!filtered.contains_key("x-provider-debug"));
+        assert!(filtered.contains_key("x-request-id"));
+        assert!(filtered.contains_key("content-type"));
+        assert_eq!(
+            filtered.get("retry-after"),
+            Some(&HeaderValue::from_static("30"))
+        );
+        assert!(filtered.contains_key("x-ratelimit-remaining-requests"));
+    }
+
     #[test]
     fn header_map_to_entries_redacts_sensitive_values() {
         let mut headers = HeaderMap::new();
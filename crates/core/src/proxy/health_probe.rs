@@ -0,0 +1,455 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use super::ProxyService;
+use super::service_core::wait_for_proxy_shutdown;
+use super::upstream_warmup::{UpstreamWarmupOutcome, UpstreamWarmupResult};
+use crate::config::{HealthCheckConfig, HelperConfig};
+use crate::endpoint_health::{CooldownBackoff, RuntimeHealthDomain};
+use crate::runtime_identity::RuntimeUpstreamIdentity;
+
+/// How often a proxy without `healthcheck.interval_ms` looks again, so a reload can enable it.
+const HEALTH_PROBE_DISABLED_RECHECK: Duration = Duration::from_secs(15);
+/// Unhealthy endpoints are probed at most every `interval << MAX_BACKOFF_SHIFT`.
+const HEALTH_PROBE_MAX_BACKOFF_SHIFT: u32 = 5;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum HealthProbeTransition {
+    Down { error: String },
+    Recovered,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct EndpointHealth {
+    healthy: bool,
+    consecutive_failures: u32,
+    next_probe_at_ms: u64,
+}
+
+/// Per provider endpoint health seen by periodic probes, with exponential backoff for
+/// unhealthy endpoints.
+#[derive(Debug, Default)]
+pub(super) struct HealthProbeTracker {
+    endpoints: BTreeMap<String, EndpointHealth>,
+}
+
+impl HealthProbeTracker {
+    fn is_due(&self, provider_endpoint_key: &str, now_ms: u64) -> bool {
+        self.endpoints
+            .get(provider_endpoint_key)
+            .is_none_or(|health| now_ms >= health.next_probe_at_ms)
+    }
+
+    fn observe(
+        &mut self,
+        provider_endpoint_key: &str,
+        outcome: &UpstreamWarmupOutcome,
+        now_ms: u64,
+        interval_ms: u64,
+    ) -> Option<HealthProbeTransition> {
+        let previous = self.endpoints.get(provider_endpoint_key).copied();
+        match outcome {
+            UpstreamWarmupOutcome::Connected { .. } => {
+                self.endpoints.insert(
+                    provider_endpoint_key.to_string(),
+                    EndpointHealth {
+                        healthy: true,
+                        consecutive_failures: 0,
+                        next_probe_at_ms: 0,
+                    },
+                );
+                previous
+                    .is_some_and(|health| !health.healthy)
+                    .then_some(HealthProbeTransition::Recovered)
+            }
+            UpstreamWarmupOutcome::Failed { error } => {
+                let consecutive_failures = previous
+                    .map_or(0, |health| health.consecutive_failures)
+                    .saturating_add(1);
+                let shift = (consecutive_failures - 1).min(HEALTH_PROBE_MAX_BACKOFF_SHIFT);
+                self.endpoints.insert(
+                    provider_endpoint_key.to_string(),
+                    EndpointHealth {
+                        healthy: false,
+                        consecutive_failures,
+                        next_probe_at_ms: now_ms
+                            .saturating_add(interval_ms.saturating_mul(1 << shift)),
+                    },
+                );
+                previous
+                    .is_none_or(|health| health.healthy)
+                    .then(|| HealthProbeTransition::Down {
+                        error: error.clone(),
+                    })
+            }
+            UpstreamWarmupOutcome::SharedOrigin | UpstreamWarmupOutcome::Skipped { .. } => None,
+        }
+    }
+}
+
+impl ProxyService {
+    pub(super) async fn run_health_probe_driver(
+        &self,
+        mut shutdown_rx: tokio::sync::watch::Receiver<bool>,
+    ) {
+        let mut tracker = HealthProbeTracker::default();
+        loop {
+            let interval = self.captured_runtime_config().await.healthcheck.interval();
+            tokio::select! {
+                biased;
+                _ = wait_for_proxy_shutdown(&mut shutdown_rx) => return,
+                () = tokio::time::sleep(interval.unwrap_or(HEALTH_PROBE_DISABLED_RECHECK)) => {}
+            }
+            // Re-read after sleeping so a reload that turned probes off takes effect first.
+            match self.captured_runtime_config().await.healthcheck.interval() {
+                Some(interval) => self.run_periodic_health_probe(&mut tracker, interval).await,
+                None => tracker = HealthProbeTracker::default(),
+            }
+        }
+    }
+
+    pub(super) async fn run_periodic_health_probe(
+        &self,
+        tracker: &mut HealthProbeTracker,
+        interval: Duration,
+    ) {
+        let interval_ms = interval.as_millis() as u64;
+        let started_ms = crate::logging::now_ms();
+        let criteria = self.captured_runtime_config().await.healthcheck.clone();
+        let results = self
+            .probe_routable_upstreams(|key| tracker.is_due(key, started_ms), Some(&criteria))
+            .await;
+        let now_ms = crate::logging::now_ms();
+        // Every routable endpoint is reported, probed or not; anything else left the config.
+        tracker.endpoints.retain(|key, _| {
+            results
+                .iter()
+                .any(|result| result.provider_endpoint_key == *key)
+        });
+        let runtime = self.config.capture().await;
+        let identities = runtime
+            .route_graph(self.service_name)
+            .and_then(|graph| graph.candidate_identities().ok())
+            .unwrap_or_default();
+        let (cooldown_secs, cooldown_backoff) = health_probe_penalty(runtime.config().as_ref());
+        for result in &results {
+            let origin = result.upstream_origin.as_deref().unwrap_or("-");
+            let transition = tracker.observe(
+                &result.provider_endpoint_key,
+                &result.outcome,
+                now_ms,
+                interval_ms,
+            );
+            let identity = identities.iter().find(|identity| {
+                identity.provider_endpoint.stable_key() == result.provider_endpoint_key
+            });
+            if let Some(identity) = identity {
+                self.record_health_probe_outcome(
+                    identity,
+                    result,
+                    transition.as_ref(),
+                    cooldown_secs,
+                    cooldown_backoff,
+                )
+                .await;
+            }
+            if let Some(transition) = transition {
+                self.report_health_probe_transition(result, origin, transition)
+                    .await;
+            }
+        }
+    }
+
+    /// Feeds a probe into the runtime health that routing reads: each failed probe puts the
+    /// endpoint into a transport cooldown, and a recovery closes it again. Both publish
+    /// `health_changed` when the breaker flips.
+    async fn record_health_probe_outcome(
+        &self,
+        identity: &RuntimeUpstreamIdentity,
+        result: &UpstreamWarmupResult,
+        transition: Option<&HealthProbeTransition>,
+        cooldown_secs: u64,
+        cooldown_backoff: CooldownBackoff,
+    ) {
+        match (&result.outcome, transition) {
+            (UpstreamWarmupOutcome::Failed { .. }, _) => {
+                self.state
+                    .penalize_runtime_upstream_attempt_for_domain(
+                        self.service_name,
+                        identity,
+                        RuntimeHealthDomain::EndpointTransport,
+                        cooldown_secs,
+                        cooldown_backoff,
+                    )
+                    .await;
+            }
+            (UpstreamWarmupOutcome::Connected { .. }, Some(HealthProbeTransition::Recovered)) => {
+                self.state
+                    .record_runtime_upstream_attempt_success(
+                        self.service_name,
+                        identity,
+                        crate::logging::now_ms(),
+                    )
+                    .await;
+            }
+            _ => {}
+        }
+    }
+
+    async fn report_health_probe_transition(
+        &self,
+        result: &UpstreamWarmupResult,
+        origin: &str,
+        transition: HealthProbeTransition,
+    ) {
+        let (event, summary) = match &transition {
+            HealthProbeTransition::Down { error } => (
+                "upstream_health_probe_down",
+                format!(
+                    "{} ({origin}) failed its health probe: {error}",
+                    result.provider_endpoint_key
+                ),
+            ),
+            HealthProbeTransition::Recovered => (
+                "upstream_health_probe_recovered",
+                format!(
+                    "{} ({origin}) passes its health probe again",
+                    result.provider_endpoint_key
+                ),
+            ),
+        };
+        crate::logging::log_control_trace_event(serde_json::json!({
+            "event": event,
+            "service": self.service_name,
+            "provider_endpoint_key": result.provider_endpoint_key,
+            "upstream_origin": origin,
+            "error": match &transition {
+                HealthProbeTransition::Down { error } => Some(error.as_str()),
+                HealthProbeTransition::Recovered => None,
+            },
+        }));
+        match transition {
+            HealthProbeTransition::Down { .. } => {
+                tracing::warn!("[{}] health probe: {}", self.service_name, summary)
+            }
+            HealthProbeTransition::Recovered => {
+                tracing::info!("[{}] health probe: {}", self.service_name, summary)
+            }
+        }
+
+        let config = self.captured_runtime_config().await;
+        if config.notify.enabled && config.notify.system.enabled {
+            let title = format!(
+                "codex-helper: {} upstream health changed",
+                self.service_name
+            );
+            tokio::task::spawn_blocking(move || {
+                if let Err(error) = crate::notify::send_system_notification(&title, &summary) {
+                    tracing::warn!("failed to send health probe notification: {error}");
+                }
+            });
+        }
+    }
+}
+
+fn health_probe_penalty(config: &HelperConfig) -> (u64, CooldownBackoff) {
+    let retry = config.retry.resolve();
+    (
+        retry.transport_cooldown_secs,
+        CooldownBackoff {
+            factor: retry.cooldown_backoff_factor,
+            max_secs: retry.cooldown_backoff_max_secs,
+            probe_back_success_threshold: retry.probe_back_success_threshold,
+        },
+    )
+}
+
+/// Why a probe response misses the `healthcheck` criteria, or `None` when it counts as healthy.
+pub(super) fn probe_response_mismatch(
+    criteria: &HealthCheckConfig,
+    status: u16,
+    body: &str,
+) -> Option<String> {
+    if !criteria.status_matches(status) {
+        return Some(format!("unexpected status {status}"));
+    }
+    if let Some(needle) = criteria.expect_body_contains.as_deref()
+        && !body.contains(needle)
+    {
+        return Some(format!("status {status}, body does not contain {needle:?}"));
+    }
+    if let Some(path) = criteria.expect_json_path.as_deref() {
+        let Ok(json) = serde_json::from_str::<serde_json::Value>(body) else {
+            return Some(format!("status {status}, body is not JSON"));
+        };
+        if json_path_value(&json, path).is_none_or(serde_json::Value::is_null) {
+            return Some(format!("status {status}, body has no value at {path:?}"));
+        }
+    }
+    None
+}
+
+/// Follows a dotted path (`data.0.id`, optionally prefixed with `$.`) through objects and arrays.
+fn json_path_value<'a>(value: &'a serde_json::Value, path: &str) -> Option<&'a serde_json::Value> {
+    let path = path.trim();
+    let path = path
+        .strip_prefix("$.")
+        .or_else(|| path.strip_prefix('$'))
+        .unwrap_or(path);
+    path.split('.')
+        .filter(|segment| !segment.is_empty())
+        .try_fold(value, |value, segment| match value {
+            serde_json::Value::Array(items) => items.get(segment.parse::<usize>().ok()?),
+            serde_json::Value::Object(map) => map.get(segment),
+            _ => None,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ENDPOINT: &str = "codex/relay/default";
+
+    fn connected() -> UpstreamWarmupOutcome {
+        UpstreamWarmupOutcome::Connected {
+            status: 404,
+            elapsed_ms: 12,
+        }
+    }
+
+    fn failed() -> UpstreamWarmupOutcome {
+        UpstreamWarmupOutcome::Failed {
+            error: "connection refused".to_string(),
+        }
+    }
+
+    #[test]
+    fn health_probe_tracker_reports_only_transitions() {
+        let mut tracker = HealthProbeTracker::default();
+
+        assert_eq!(tracker.observe(ENDPOINT, &connected(), 0, 1_000), None);
+        assert_eq!(
+            tracker.observe(ENDPOINT, &failed(), 1_000, 1_000),
+            Some(HealthProbeTransition::Down {
+                error: "connection refused".to_string()
+            })
+        );
+        assert_eq!(tracker.observe(ENDPOINT, &failed(), 2_000, 1_000), None);
+        assert_eq!(
+            tracker.observe(ENDPOINT, &connected(), 4_000, 1_000),
+            Some(HealthProbeTransition::Recovered)
+        );
+        assert_eq!(
+            tracker.observe(ENDPOINT, &UpstreamWarmupOutcome::SharedOrigin, 5_000, 1_000),
+            None
+        );
+    }
+
+    #[test]
+    fn health_probe_tracker_reports_an_endpoint_that_starts_down() {
+        let mut tracker = HealthProbeTracker::default();
+
+        assert!(matches!(
+            tracker.observe(ENDPOINT, &failed(), 0, 1_000),
+            Some(HealthProbeTransition::Down { .. })
+        ));
+    }
+
+    #[test]
+    fn health_probe_tracker_backs_off_down_endpoints() {
+        let mut tracker = HealthProbeTracker::default();
+        assert!(tracker.is_due(ENDPOINT, 0));
+
+        tracker.observe(ENDPOINT, &failed(), 0, 1_000);
+        assert!(!tracker.is_due(ENDPOINT, 999));
+        assert!(tracker.is_due(ENDPOINT, 1_000));
+
+        tracker.observe(ENDPOINT, &failed(), 1_000, 1_000);
+        assert!(!tracker.is_due(ENDPOINT, 2_999));
+        assert!(tracker.is_due(ENDPOINT, 3_000));
+
+        for _ in 0..10 {
+            tracker.observe(ENDPOINT, &failed(), 0, 1_000);
+        }
+        assert!(
+            !tracker.is_due(ENDPOINT, 31_999),
+            "backoff is capped at 32x"
+        );
+        assert!(tracker.is_due(ENDPOINT, 32_000));
+
+        tracker.observe(ENDPOINT, &connected(), 0, 1_000);
+        assert!(
+            tracker.is_due(ENDPOINT, 0),
+            "a healthy endpoint is probed every tick"
+        );
+    }
+
+    fn criteria(toml: &str) -> HealthCheckConfig {
+        let criteria: HealthCheckConfig = toml::from_str(toml).expect("healthcheck config");
+        criteria.validate().expect("valid criteria");
+        criteria
+    }
+
+    #[test]
+    fn probe_criteria_match_status_codes_ranges_and_classes() {
+        let any = HealthCheckConfig::default();
+        assert_eq!(probe_response_mismatch(&any, 404, ""), None);
+
+        let status_only = criteria(r#"expect_status = [204, "200-201", "3xx"]"#);
+        for status in [200, 201, 204, 302] {
+            assert_eq!(probe_response_mismatch(&status_only, status, ""), None);
+        }
+        assert_eq!(
+            probe_response_mismatch(&status_only, 401, ""),
+            Some("unexpected status 401".to_string())
+        );
+
+        for invalid in [
+            r#"expect_status = ["6xx"]"#,
+            r#"expect_status = ["299-200"]"#,
+        ] {
+            let config: HealthCheckConfig = toml::from_str(invalid).expect("parses");
+            assert!(config.validate().is_err(), "{invalid}");
+        }
+        let relative: HealthCheckConfig = toml::from_str(r#"path = "v1/models""#).expect("parses");
+        assert!(relative.validate().is_err());
+    }
+
+    #[test]
+    fn probe_criteria_require_a_matching_body() {
+        let contains = criteria(
+            r#"
+expect_status = ["2xx"]
+expect_body_contains = "\"object\":\"list\""
+"#,
+        );
+        assert!(contains.probes_body());
+        assert_eq!(
+            probe_response_mismatch(&contains, 200, r#"{"object":"list","data":[]}"#),
+            None
+        );
+        assert_eq!(
+            probe_response_mismatch(&contains, 200, r#"{"error":"invalid api key"}"#),
+            Some(r#"status 200, body does not contain "\"object\":\"list\"""#.to_string())
+        );
+
+        let json_path = criteria(r#"expect_json_path = "$.data.0.id""#);
+        assert_eq!(
+            probe_response_mismatch(&json_path, 200, r#"{"data":[{"id":"gpt-5"}]}"#),
+            None
+        );
+        assert_eq!(
+            probe_response_mismatch(&json_path, 200, r#"{"data":[]}"#),
+            Some(r#"status 200, body has no value at "$.data.0.id""#.to_string())
+        );
+        assert_eq!(
+            probe_response_mismatch(&json_path, 200, r#"{"data":[{"id":null}]}"#),
+            Some(r#"status 200, body has no value at "$.data.0.id""#.to_string())
+        );
+        assert_eq!(
+            probe_response_mismatch(&json_path, 200, "<html>ok</html>"),
+            Some("status 200, body is not JSON".to_string())
+        );
+    }
+}
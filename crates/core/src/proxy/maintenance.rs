@@ -0,0 +1,34 @@
+use axum::Json;
+use axum::body::Body;
+use axum::http::{Response, StatusCode};
+use axum::response::IntoResponse;
+
+use super::ProxyService;
+use super::control_plane_service::service_route_config;
+
+/// Answers with the service's `maintenance` reply when it is configured and no provider is
+/// enabled, so clients see a deliberate outage instead of an exhausted route.
+pub(super) async fn maintenance_response(proxy: &ProxyService) -> Option<Response<Body>> {
+    let config = proxy.captured_runtime_config().await;
+    let service = service_route_config(config.as_ref(), proxy.service_name);
+    let maintenance = service.maintenance.as_ref()?;
+    if service.providers.values().any(|provider| provider.enabled) {
+        return None;
+    }
+
+    let status =
+        StatusCode::from_u16(maintenance.status()).unwrap_or(StatusCode::SERVICE_UNAVAILABLE);
+    tracing::debug!(
+        service = proxy.service_name,
+        status = status.as_u16(),
+        "answered with the maintenance response; every provider is disabled"
+    );
+    let body = serde_json::json!({
+        "error": {
+            "type": "service_maintenance",
+            "code": "service_maintenance",
+            "message": maintenance.message(proxy.service_name),
+        }
+    });
+    Some((status, Json(body)).into_response())
+}
@@ -20,15 +20,19 @@ mod codex_relay_live_smoke;
 mod codex_relay_probe;
 mod codex_relay_target;
 mod concurrency_limits;
+mod config_reload;
 mod control_plane;
 mod control_plane_manifest;
 mod control_plane_routes;
 mod control_plane_service;
 mod entrypoint;
 mod failure_summary;
+mod fallback_alert;
 mod headers;
+mod health_probe;
 mod http_debug;
 mod local_operator_routes;
+mod maintenance;
 mod models_compat;
 mod openai_images;
 mod profile_defaults;
@@ -43,6 +47,8 @@ mod request_encoding;
 mod request_failures;
 mod request_observer;
 mod request_preparation;
+mod request_quota;
+mod response_cache;
 mod response_entity;
 mod response_finalization;
 mod response_fixer;
@@ -64,9 +70,12 @@ mod session_affinity_control;
 mod session_binding_control;
 mod settings_control;
 mod stream;
+mod stream_buffer;
 mod target_builder;
 #[cfg(test)]
 mod tests;
+mod upstream_model_lists;
+mod upstream_warmup;
 
 use crate::filter::RequestFilter;
 use crate::state::{ProviderBalanceSnapshot, ProxyState};
@@ -98,6 +107,7 @@ pub use self::codex_relay_probe::{
     classify_codex_relay_probe_response,
 };
 use self::concurrency_limits::ConcurrencyLimiter;
+pub use self::config_reload::{RuntimeConfigReloadSummary, reload_runtime_config_on_sighup};
 pub(crate) use self::control_plane_manifest::{
     LOCAL_V1_BALANCE_REFRESH, LOCAL_V1_CREDENTIAL_REFRESH, LOCAL_V1_DEFAULT_PROFILE_MUTATION,
     LOCAL_V1_OPERATOR_SESSION, LOCAL_V1_RELAY_CAPABILITIES, LOCAL_V1_RELAY_LIVE_SMOKE,
@@ -111,6 +121,7 @@ pub(crate) use self::local_operator_routes::{
     LOCAL_OPERATOR_TIMESTAMP_HEADER,
 };
 pub use self::response_entity::upstream_http_client_builder;
+pub use self::retry::response_penalty_cooldown_secs;
 #[cfg(test)]
 pub(crate) use self::router_setup::router;
 pub(crate) use self::router_setup::{admin_listener_router, proxy_only_router};
@@ -133,10 +144,12 @@ pub use self::settings_control::{
     OperatorDefaultProfileScope, OperatorRuntimeReloadRequest, OperatorRuntimeReloadResponse,
     RuntimeDefaultProfileControlSnapshot,
 };
+pub use self::upstream_warmup::{UpstreamWarmupOutcome, UpstreamWarmupResult};
 
 pub const ADMIN_TOKEN_ENV_VAR: &str = "CODEX_HELPER_ADMIN_TOKEN";
 pub const ADMIN_TOKEN_HEADER: &str = "x-codex-helper-admin-token";
 pub const CLIENT_NAME_HEADER: &str = "x-codex-helper-client-name";
+pub const TENANT_HEADER: &str = "x-codex-helper-tenant";
 pub const ADMIN_PORT_OFFSET: u16 = 1000;
 
 #[cfg(test)]
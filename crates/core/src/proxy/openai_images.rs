@@ -436,8 +436,13 @@ fn build_json_proxy_request(
     body: Vec<u8>,
 ) -> Result<Request<Body>, axum::http::Error> {
     let mut builder = Request::builder().method(parts.method).uri(uri);
+    // The translated Responses request is the helper's own; the image client's User-Agent
+    // would not describe it.
     for (name, value) in &parts.headers {
-        if name != header::CONTENT_LENGTH && name != header::CONTENT_TYPE {
+        if name != header::CONTENT_LENGTH
+            && name != header::CONTENT_TYPE
+            && name != header::USER_AGENT
+        {
             builder = builder.header(name, value);
         }
     }
@@ -158,6 +158,7 @@ mod tests {
             preference_group: 0,
             stable_index: 0,
             concurrency: RouteCandidateConcurrency::default(),
+            schedule: None,
         };
         CapturedRouteCandidate::capture_for_service("codex", &candidate)
     }
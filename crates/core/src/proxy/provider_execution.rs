@@ -1,7 +1,7 @@
 use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::sync::OnceLock;
 #[cfg(test)]
 use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::{Arc, OnceLock};
 use std::time::Instant;
 
 use axum::body::{Body, Bytes};
@@ -19,7 +19,9 @@ use crate::routing_ir::{
 };
 use crate::runtime_identity::ProviderEndpointKey;
 use crate::runtime_store::ProviderPolicySnapshot;
-use crate::state::{RuntimeHealthHalfOpenProbeLease, SessionBinding, SessionIdentitySource};
+use crate::state::{
+    RequestQuotaExceeded, RuntimeHealthHalfOpenProbeLease, SessionBinding, SessionIdentitySource,
+};
 
 use super::ProxyService;
 use super::attempt_execution::{
@@ -31,23 +33,28 @@ use super::request_continuity::{RequestContinuityContract, RouteContinuityDecisi
 use super::request_preparation::RequestFlavor;
 #[cfg(test)]
 use super::request_preparation::SharedRouteStateImpact;
+use super::request_quota::admit_request_quota;
+use super::response_cache::{
+    CacheableGetRequest, ServeCachedResponseParams, serve_cached_response,
+};
 use super::response_semantics::ResponseSemanticContract;
-use super::retry::{RetryLayerOptions, RetryPlan};
+use super::retry::{RetryLayerOptions, RetryPlan, upstream_budget_exhausted, upstreams_tried};
 use super::route_affinity::{
     SessionRouteReservationDecision, apply_session_route_reservation_to_runtime,
     claim_session_route_reservation, lock_session_route_reservation_selection,
 };
 use super::route_attempts::{UnsupportedModelSkipParams, record_unsupported_model_skip};
+use super::route_provenance::{RouteDecisionProvenanceParams, build_route_decision_provenance};
 use super::route_target_selection::{
-    acquire_candidate_concurrency_permit, log_route_continuity_blocked,
-    restrict_route_state_to_affinity_continuity_domain,
+    acquire_candidate_concurrency_permit, hold_candidate_after_failover,
+    log_route_continuity_blocked, restrict_route_state_to_affinity_continuity_domain,
     route_graph_request_requires_existing_affinity, route_graph_runtime_for_request,
     runtime_for_acquired_candidate_revalidation, runtime_for_capacity_wait_selection,
     runtime_for_transient_half_open_selection, select_route_graph_candidate,
 };
 use super::route_unavailability::route_unavailable_report;
 use super::runtime_config::CapturedRoutePlan;
-use crate::routing_ir::CapturedRouteCandidate;
+use crate::routing_ir::{CapturedRouteCandidate, UpstreamPinScope};
 
 const COMPACT_ROUTE_UNAVAILABLE_WAIT_MAX_SECS: u64 = 10;
 const DEGRADED_SELECTION_BALANCE_REPROBE_LIMIT: usize = 16;
@@ -135,6 +142,7 @@ pub(super) struct ExecuteProviderChainParams<'a> {
     pub(super) client_body_warn: Option<&'a BodyPreview>,
     pub(super) plan: &'a RetryPlan,
     pub(super) cooldown_backoff: CooldownBackoff,
+    pub(super) response_cache: Option<&'a CacheableGetRequest>,
 }
 
 pub(super) enum ProviderExecutionOutcome {
@@ -146,11 +154,14 @@ pub(super) struct ProviderExecutionState {
     pub(super) route_attempts: Vec<RouteAttemptLog>,
     pub(super) last_err: Option<(StatusCode, String)>,
     pub(super) last_http_debug: Option<HttpDebugLog>,
+    /// Set when the request ended because the last candidate's provider was over its `quota`.
+    pub(super) quota_exceeded: Option<RequestQuotaExceeded>,
 }
 
 #[derive(Clone, Copy)]
 struct ProviderExecutionContext<'a> {
     proxy: &'a ProxyService,
+    route_plan: &'a CapturedRoutePlan,
     method: &'a Method,
     uri: &'a Uri,
     client_headers: &'a HeaderMap,
@@ -181,10 +192,12 @@ struct ProviderExecutionContext<'a> {
     client_body_warn: Option<&'a BodyPreview>,
     plan: &'a RetryPlan,
     cooldown_backoff: CooldownBackoff,
+    response_cache: Option<&'a CacheableGetRequest>,
 }
 
 struct SelectedAttemptExecutionParams<'a> {
     target: &'a CapturedRouteCandidate,
+    pinned_by: Option<UpstreamPinScope>,
     route_graph_key: Option<&'a str>,
     allow_provider_failover: bool,
     provider_attempt: u32,
@@ -203,6 +216,7 @@ impl<'a> ProviderExecutionContext<'a> {
     fn from_params(params: &ExecuteProviderChainParams<'a>) -> Self {
         Self {
             proxy: params.proxy,
+            route_plan: params.route_plan,
             method: params.method,
             uri: params.uri,
             client_headers: params.client_headers,
@@ -233,6 +247,7 @@ impl<'a> ProviderExecutionContext<'a> {
             client_body_warn: params.client_body_warn,
             plan: params.plan,
             cooldown_backoff: params.cooldown_backoff,
+            response_cache: params.response_cache,
         }
     }
 
@@ -254,6 +269,10 @@ impl<'a> ProviderExecutionContext<'a> {
         let mut half_open_upstream_opt = self.upstream_opt().clone();
         let mut half_open_provider_opt = self.provider_opt().clone();
         let is_half_open_probe = params.half_open_probe.is_some();
+        let is_probe_back = params
+            .half_open_probe
+            .as_ref()
+            .is_some_and(RuntimeHealthHalfOpenProbeLease::is_probe_back);
         if is_half_open_probe {
             half_open_upstream_opt.max_attempts = 1;
             half_open_provider_opt.max_attempts = 1;
@@ -269,9 +288,19 @@ impl<'a> ProviderExecutionContext<'a> {
             self.provider_opt()
         };
 
+        let upstream_client = self
+            .route_plan
+            .upstream_client(params.target)
+            .unwrap_or(&self.proxy.client);
+        let upstream_user_agent = self.route_plan.upstream_user_agent(params.target);
+        let upstream_strip_headers = self.route_plan.upstream_strip_headers(params.target);
         execute_selected_upstream(ExecuteSelectedUpstreamParams {
             proxy: self.proxy,
+            upstream_client,
+            upstream_user_agent: &upstream_user_agent,
+            upstream_strip_headers: &upstream_strip_headers,
             target: params.target,
+            pinned_by: params.pinned_by,
             method: self.method,
             uri: self.uri,
             client_headers: self.client_headers,
@@ -304,7 +333,8 @@ impl<'a> ProviderExecutionContext<'a> {
             route_graph_key: params.route_graph_key,
             upstream_opt,
             provider_opt,
-            allow_provider_failover: params.allow_provider_failover && !is_half_open_probe,
+            allow_provider_failover: params.allow_provider_failover
+                && (!is_half_open_probe || is_probe_back),
             provider_attempt: params.provider_attempt,
             total_upstreams: params.total_upstreams,
             cooldown_backoff: self.cooldown_backoff,
@@ -316,6 +346,7 @@ impl<'a> ProviderExecutionContext<'a> {
             route_attempts: params.route_attempts,
             concurrency_permit: params.concurrency_permit,
             half_open_probe: params.half_open_probe,
+            response_cache: self.response_cache,
         })
         .await
     }
@@ -362,6 +393,9 @@ pub(super) fn log_retry_options(service_name: &str, request_id: u64, plan: &Retr
         "transport_cooldown_secs": plan.transport_cooldown_secs,
         "cooldown_backoff_factor": plan.cooldown_backoff_factor,
         "cooldown_backoff_max_secs": plan.cooldown_backoff_max_secs,
+        "probe_back_success_threshold": plan.probe_back_success_threshold,
+        "connection_reset_fast_retries": plan.connection_reset_fast_retries,
+        "max_upstreams_tried": plan.max_upstreams_tried,
     }));
 }
 
@@ -409,6 +443,7 @@ pub(super) async fn execute_provider_chain_with_route_executor(
                     "configured upstream credentials are unavailable".to_string(),
                 )),
                 last_http_debug: None,
+                quota_exceeded: None,
             }));
         }
     };
@@ -419,6 +454,7 @@ pub(super) async fn execute_provider_chain_with_route_executor(
     let mut global_attempt: u32 = 0;
     let mut last_err: Option<(StatusCode, String)> = None;
     let mut last_http_debug: Option<HttpDebugLog> = None;
+    let mut quota_exceeded: Option<RequestQuotaExceeded> = None;
 
     if route_graph_request_requires_existing_affinity(
         provider_chain_policy.continuity,
@@ -439,6 +475,7 @@ pub(super) async fn execute_provider_chain_with_route_executor(
             route_attempts,
             last_err,
             last_http_debug,
+            quota_exceeded,
         }));
     }
     restrict_route_state_to_affinity_continuity_domain(
@@ -466,6 +503,7 @@ pub(super) async fn execute_provider_chain_with_route_executor(
             global_attempt: &mut global_attempt,
             last_err: &mut last_err,
             last_http_debug: &mut last_http_debug,
+            quota_exceeded: &mut quota_exceeded,
             route_attempts: &mut route_attempts,
             policy: provider_chain_policy,
         },
@@ -475,10 +513,17 @@ pub(super) async fn execute_provider_chain_with_route_executor(
         return ProviderExecutionOutcome::Return(response);
     }
 
+    // A later candidate may have failed for another reason after a quota refusal.
+    let quota_exceeded = quota_exceeded.filter(|exceeded| {
+        last_err.as_ref().is_some_and(|(status, message)| {
+            *status == StatusCode::TOO_MANY_REQUESTS && *message == exceeded.message()
+        })
+    });
     ProviderExecutionOutcome::Exhausted(Box::new(ProviderExecutionState {
         route_attempts,
         last_err,
         last_http_debug,
+        quota_exceeded,
     }))
 }
 
@@ -497,6 +542,7 @@ struct ExecuteRouteGraphExecutorParams<'a, 'route> {
     global_attempt: &'a mut u32,
     last_err: &'a mut Option<(StatusCode, String)>,
     last_http_debug: &'a mut Option<HttpDebugLog>,
+    quota_exceeded: &'a mut Option<RequestQuotaExceeded>,
     route_attempts: &'a mut Vec<RouteAttemptLog>,
     policy: ProviderChainAttemptPolicy,
 }
@@ -527,6 +573,7 @@ impl<'a, 'route> RouteGraphAttemptLoop<'a, 'route> {
             global_attempt,
             last_err,
             last_http_debug,
+            quota_exceeded,
             route_attempts,
             policy,
         } = params;
@@ -534,8 +581,20 @@ impl<'a, 'route> RouteGraphAttemptLoop<'a, 'route> {
             .request_flavor
             .shared_route_state_impact
             .allows_shared_updates();
+        // Providers this request already counts against; retries on them are not charged again.
+        let mut quota_admitted_providers = BTreeSet::new();
 
         loop {
+            if upstream_budget_exhausted(ctx.plan, route_attempts) {
+                log_control_trace_event(serde_json::json!({
+                    "event": "retry_upstream_budget_exhausted",
+                    "service": ctx.proxy.service_name,
+                    "request_id": ctx.request_id,
+                    "upstreams_tried": upstreams_tried(route_attempts),
+                    "max_upstreams_tried": ctx.plan.max_upstreams_tried,
+                }));
+                break;
+            }
             let revalidation_affinity_policy = if !shared_route_updates_allowed {
                 RouteAffinityPolicy::Off
             } else if executor.template().affinity_policy == RouteAffinityPolicy::Hard
@@ -624,22 +683,34 @@ impl<'a, 'route> RouteGraphAttemptLoop<'a, 'route> {
             };
 
             let mut half_open_probe = None;
-            if selection.selected.is_none()
-                && compact_short_cooldown_wait_secs.is_none()
+            if compact_short_cooldown_wait_secs.is_none()
                 && shared_route_updates_allowed
                 && !ctx.request_flavor.is_remote_compaction_v2_request
                 && let Some(capability) = ctx.request_flavor.transient_health_capability()
                 && let Ok(identities) = executor.template().candidate_identities()
             {
-                let eligible_provider_endpoints = ctx
-                    .proxy
-                    .state
-                    .half_open_probe_eligible_provider_endpoints(
-                        ctx.proxy.service_name,
-                        identities.as_slice(),
-                        capability,
-                    )
-                    .await;
+                // With nothing routable, any open breaker may be probed; otherwise only
+                // upstreams owed probe-back successes may preempt the current selection.
+                let eligible_provider_endpoints = if selection.selected.is_none() {
+                    ctx.proxy
+                        .state
+                        .half_open_probe_eligible_provider_endpoints(
+                            ctx.proxy.service_name,
+                            identities.as_slice(),
+                            capability,
+                        )
+                        .await
+                } else {
+                    ctx.proxy
+                        .state
+                        .probe_back_eligible_provider_endpoints(
+                            ctx.proxy.service_name,
+                            identities.as_slice(),
+                            capability,
+                            ctx.cooldown_backoff.probe_back_success_threshold,
+                        )
+                        .await
+                };
                 if !eligible_provider_endpoints.is_empty() {
                     let half_open_runtime = runtime_for_transient_half_open_selection(
                         executor.template(),
@@ -656,6 +727,7 @@ impl<'a, 'route> RouteGraphAttemptLoop<'a, 'route> {
                         policy.continuity,
                     );
                     if let Some(selected) = half_open_selection.selected.as_ref()
+                        && eligible_provider_endpoints.contains(&selected.provider_endpoint)
                         && let Ok(identity) =
                             executor.template().candidate_identity(selected.candidate)
                         && let Some(probe) = ctx
@@ -673,6 +745,7 @@ impl<'a, 'route> RouteGraphAttemptLoop<'a, 'route> {
                             "service": ctx.proxy.service_name,
                             "request_id": ctx.request_id,
                             "provider_endpoint_key": selected.provider_endpoint.stable_key(),
+                            "probe_back": probe.is_probe_back(),
                         }));
                         selection = half_open_selection;
                         half_open_probe = Some(probe);
@@ -858,6 +931,57 @@ impl<'a, 'route> RouteGraphAttemptLoop<'a, 'route> {
                 )
                 .await;
             }
+            let provider_id = selected.provider_endpoint.provider_id.as_str();
+            if !quota_admitted_providers.contains(provider_id) {
+                match admit_request_quota(ctx.proxy, provider_id, ctx.client_headers).await {
+                    Ok(()) => {
+                        quota_admitted_providers.insert(provider_id.to_string());
+                    }
+                    Err(exceeded) => {
+                        route_state.avoid_provider_endpoint(selected.provider_endpoint.clone());
+                        *last_err = Some((StatusCode::TOO_MANY_REQUESTS, exceeded.message()));
+                        *quota_exceeded = Some(exceeded);
+                        continue;
+                    }
+                }
+            }
+            // Answered before any permit or attempt: a hit must not look like upstream traffic.
+            // Half-open probes always reach the upstream, since a hit would prove nothing.
+            if let Some(cache) = ctx.response_cache
+                && half_open_probe.is_none()
+                && let Some(response) = serve_cached_response(
+                    ctx.proxy,
+                    ServeCachedResponseParams {
+                        request: cache,
+                        target: &target,
+                        method: ctx.method,
+                        path: ctx.uri.path(),
+                        request_id: ctx.request_id,
+                        start: ctx.start,
+                        started_at_ms: ctx.started_at_ms,
+                        session_id: ctx.session_id,
+                        session_identity_source: ctx.session_identity_source,
+                        cwd: ctx.cwd,
+                        service_tier: ctx.base_service_tier,
+                        route_decision: build_route_decision_provenance(
+                            RouteDecisionProvenanceParams {
+                                decided_at_ms: crate::logging::now_ms(),
+                                session_binding: ctx.session_binding,
+                                request_model: ctx.request_model,
+                                effective_effort: ctx.effective_effort,
+                                effective_service_tier: ctx.effective_service_tier,
+                                target: &target,
+                                provider_id: None,
+                            },
+                        ),
+                        response_header_policy: &ctx.request_flavor.response_header_policy,
+                        terminal_accounting: ctx.request_flavor.terminal_accounting,
+                    },
+                )
+                .await
+            {
+                return Some(response);
+            }
             let mut avoid_set = hash_set_from_indices(&avoided_candidate_indices);
             let concurrency_permit = match acquire_candidate_concurrency_permit(
                 ctx.proxy,
@@ -981,6 +1105,11 @@ impl<'a, 'route> RouteGraphAttemptLoop<'a, 'route> {
             match ctx
                 .execute_selected_attempt(SelectedAttemptExecutionParams {
                     target: &target,
+                    pinned_by: runtime.upstream_pin_scope_for(
+                        &executor
+                            .template()
+                            .candidate_provider_endpoint_key(selected_candidate),
+                    ),
                     route_graph_key,
                     allow_provider_failover: policy
                         .allow_provider_failover_with_route_state(route_state),
@@ -1008,6 +1137,15 @@ impl<'a, 'route> RouteGraphAttemptLoop<'a, 'route> {
 
             if avoid_set.contains(&selected_candidate.stable_index) {
                 route_state.avoid_candidate(executor.template(), selected_candidate);
+                if shared_route_updates_allowed {
+                    hold_candidate_after_failover(
+                        ctx.proxy,
+                        ctx.request_id,
+                        executor.template(),
+                        selected_candidate,
+                    )
+                    .await;
+                }
             }
             if policy
                 .continuity
@@ -1252,7 +1390,7 @@ async fn enqueue_usage_probes_for_provider_endpoints<'a>(
     }
 
     let topology = template.continuity_topology();
-    let provider_catalog = proxy.config.capture().await.usage_provider_catalog();
+    let runtime = proxy.config.capture().await;
     for provider_endpoint in provider_endpoints {
         let Some(candidate) = topology.find_candidate_by_provider_endpoint(&provider_endpoint)
         else {
@@ -1261,12 +1399,7 @@ async fn enqueue_usage_probes_for_provider_endpoints<'a>(
         let Ok(target) = template.capture_candidate(candidate) else {
             continue;
         };
-        super::providers_api::enqueue_provider_balance_probe(
-            proxy.client.clone(),
-            proxy.state.clone(),
-            target,
-            Arc::clone(&provider_catalog),
-        );
+        super::providers_api::enqueue_provider_balance_probe(proxy, &runtime, target);
     }
 }
 
@@ -1347,6 +1480,7 @@ mod tests {
                 crate::proxy::request_preparation::StreamTerminalPolicy::ProtocolEvent,
             replay_policy: crate::proxy::request_preparation::RequestReplayPolicy::RouteFacing,
             codex_bridge_log: None,
+            response_header_policy: Default::default(),
         }
     }
 
@@ -1366,6 +1500,7 @@ mod tests {
             preference_group,
             stable_index: preference_group as usize,
             concurrency: RouteCandidateConcurrency::default(),
+            schedule: None,
         }
     }
 
@@ -1377,6 +1512,9 @@ mod tests {
             scheduling_preset: crate::config::SchedulingPreset::Balanced,
             fallback_ttl_ms: None,
             reprobe_preferred_after_ms: None,
+            auto_active_by_health: false,
+            prefer_cheapest: false,
+            primary_sticky_ms: None,
             nodes: BTreeMap::new(),
             expanded_provider_order: groups.iter().map(|provider| provider.to_string()).collect(),
             candidates: groups
@@ -1,5 +1,4 @@
 use std::collections::HashMap;
-use std::sync::Arc;
 
 use axum::http::StatusCode;
 
@@ -21,6 +20,7 @@ use super::concurrency_limits::ConcurrencyLimit;
 use super::control_plane_service::service_route_config;
 use super::route_target_selection::{
     apply_auth_resolution_to_runtime, apply_concurrency_snapshots_to_runtime,
+    apply_primary_sticky_holds_to_runtime,
 };
 use super::runtime_config::RuntimeSnapshot;
 
@@ -28,17 +28,22 @@ fn format_provider_balance_refresh_error(error: &anyhow::Error) -> String {
     format!("failed to refresh provider balances: {error:#}")
 }
 
+/// Balance probes reach the same upstream host as requests, so they go through the provider's
+/// own `[tls]` or address-family client when it has one.
 pub(super) fn enqueue_provider_balance_probe(
-    client: reqwest::Client,
-    state: Arc<crate::state::ProxyState>,
+    proxy: &ProxyService,
+    runtime: &RuntimeSnapshot,
     target: CapturedRouteCandidate,
-    provider_catalog: Arc<crate::usage_providers::UsageProviderCatalog>,
 ) {
+    let client = runtime
+        .provider_client(proxy.service_name, target.provider_id())
+        .unwrap_or(&proxy.client)
+        .clone();
     crate::usage_providers::enqueue_poll_for_captured_route_candidate(
         client,
-        state,
+        proxy.state.clone(),
         target,
-        provider_catalog,
+        runtime.usage_provider_catalog(),
     );
 }
 
@@ -308,12 +313,14 @@ pub(super) async fn build_provider_options_for_runtime_snapshot(
             )
         },
     )?;
+    runtime.apply_provider_schedules(&template, crate::logging::now_ms());
     apply_concurrency_snapshots_to_runtime(
         proxy,
         &template,
         runtime_snapshot.revision(),
         &mut runtime,
     );
+    apply_primary_sticky_holds_to_runtime(proxy, &template, &mut runtime).await;
     let source = runtime_snapshot.config();
     let view = service_route_config(source.as_ref(), proxy.service_name);
     let mut providers =
@@ -336,6 +343,7 @@ pub(super) async fn refresh_provider_balances_for_proxy(
             runtime_snapshot.config(),
             runtime_snapshot.credential_generation(),
             runtime_snapshot.usage_provider_catalog(),
+            runtime_snapshot.provider_tls_clients(),
         ),
         proxy.state.clone(),
         proxy.service_name,
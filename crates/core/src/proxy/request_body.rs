@@ -622,7 +622,8 @@ mod tests {
         extract_reasoning_effort_from_value, extract_service_tier_from_response_body,
         extract_service_tier_from_value, is_stale_previous_response_error,
         normalize_codex_compact_request_value, remove_hosted_image_generation_tools_value,
-        remove_previous_response_id_from_body, scan_response_metadata_from_sse_bytes_incremental,
+        remove_previous_response_id_from_body, remove_reasoning_effort_value,
+        scan_response_metadata_from_sse_bytes_incremental,
         scan_service_tier_from_sse_bytes_incremental,
     };
     use crate::provider_catalog::{
@@ -982,6 +983,85 @@ mod tests {
         );
     }
 
+    #[test]
+    fn responses_and_chat_paths_read_and_write_their_own_request_fields() {
+        // A Codex Responses request: `input` items instead of `messages`, nested `reasoning`.
+        let responses_body = br#"{
+            "model":"gpt-5-codex",
+            "instructions":"You are Codex.",
+            "input":[{"type":"message","role":"user","content":[{"type":"input_text","text":"hi"}]}],
+            "reasoning":{"effort":"high","summary":"auto"},
+            "reasoning_effort":"low",
+            "stream":true
+        }"#;
+        let chat_body = br#"{
+            "model":"gpt-5",
+            "messages":[{"role":"user","content":"hi"}],
+            "reasoning_effort":"low"
+        }"#;
+        let responses = RequestDialect::from_http_path("/v1/responses");
+        let chat = RequestDialect::from_http_path("/v1/chat/completions");
+
+        let mut value: serde_json::Value =
+            serde_json::from_slice(responses_body).expect("responses json");
+        assert_eq!(
+            extract_model_from_value(&value).as_deref(),
+            Some("gpt-5-codex")
+        );
+        assert_eq!(
+            extract_reasoning_effort_from_value(&value, responses).as_deref(),
+            Some("high"),
+            "a stray chat-style field must not shadow reasoning.effort"
+        );
+        assert!(apply_reasoning_effort_override_value(
+            &mut value, responses, "medium"
+        ));
+        assert_eq!(value["reasoning"]["effort"].as_str(), Some("medium"));
+        assert_eq!(value["reasoning"]["summary"].as_str(), Some("auto"));
+        assert_eq!(value["reasoning_effort"].as_str(), Some("low"));
+        assert!(remove_reasoning_effort_value(&mut value, responses));
+        assert!(value["reasoning"].get("effort").is_none());
+        assert!(value["input"].is_array(), "input items are preserved");
+
+        let mut value: serde_json::Value = serde_json::from_slice(chat_body).expect("chat json");
+        assert_eq!(
+            extract_reasoning_effort_from_value(&value, chat).as_deref(),
+            Some("low")
+        );
+        assert!(apply_reasoning_effort_override_value(
+            &mut value, chat, "medium"
+        ));
+        assert_eq!(value["reasoning_effort"].as_str(), Some("medium"));
+        assert!(value.get("reasoning").is_none());
+    }
+
+    #[test]
+    fn scans_model_from_a_representative_responses_stream() {
+        // Responses events carry the model inside the `response` envelope; deltas carry none.
+        let stream = concat!(
+            "event: response.created\n",
+            "data: {\"type\":\"response.created\",\"sequence_number\":0,\"response\":{\"id\":\"resp_1\",\"object\":\"response\",\"status\":\"in_progress\",\"model\":\"gpt-5-codex\",\"usage\":null}}\n\n",
+            "event: response.output_text.delta\n",
+            "data: {\"type\":\"response.output_text.delta\",\"sequence_number\":4,\"item_id\":\"msg_1\",\"output_index\":0,\"content_index\":0,\"delta\":\"Hello\"}\n\n",
+            "event: response.completed\n",
+            "data: {\"type\":\"response.completed\",\"sequence_number\":9,\"response\":{\"id\":\"resp_1\",\"status\":\"completed\",\"model\":\"gpt-5-codex-2026-09\",\"service_tier\":\"default\"}}\n\n",
+        );
+        let mut scan_pos = 0;
+        let mut model = None;
+        let mut tier = None;
+
+        scan_response_metadata_from_sse_bytes_incremental(
+            stream.as_bytes(),
+            &mut scan_pos,
+            &mut model,
+            &mut tier,
+        );
+
+        assert_eq!(scan_pos, stream.len());
+        assert_eq!(model.as_deref(), Some("gpt-5-codex-2026-09"));
+        assert_eq!(tier.as_deref(), Some("default"));
+    }
+
     #[test]
     fn detects_responses_stream_flag_from_body() {
         assert!(codex_responses_body_requests_stream(
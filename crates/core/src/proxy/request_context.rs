@@ -11,6 +11,8 @@ use super::ProxyService;
 use super::client_identity::{
     extract_client_addr, extract_client_name, extract_session_identity_with_body_fallback,
 };
+use super::control_plane_service::service_route_config;
+use super::headers::ResponseHeaderPolicy;
 use super::request_body::{
     ReasoningOrchestrationIntent, RequestDialect, codex_session_identity_and_completed_body,
 };
@@ -21,8 +23,8 @@ use super::request_failures::{
 };
 use super::request_preparation::{
     CommonRequestPreparationError, CommonRequestPreparationParams, RequestFlavor, RequestOrigin,
-    codex_path_is_responses_or_compact, detect_request_flavor, load_request_config_context,
-    prepare_http_request,
+    codex_path_is_responses_or_compact, detect_request_flavor, effort_above_ceiling_message,
+    load_request_config_context, prepare_http_request,
 };
 use super::response_semantics::ResponseSemanticContract;
 use super::retry::RetryPlan;
@@ -131,15 +133,23 @@ pub(super) async fn prepare_proxy_request(
             Some(ResponseSemanticContract::HostedImageGeneration)
         ));
     let config = load_request_config_context(proxy, session_identity_hint.as_ref()).await;
-    let request_flavor = request_flavor.with_remote_v2_downgrade_enabled(
-        proxy.service_name == "codex"
-            && config
-                .runtime_snapshot
-                .config()
-                .codex
-                .remote_v2_downgrade_enabled(),
-    );
-    let request_body_previews = crate::logging::should_log_request_body_preview();
+    let request_flavor = request_flavor
+        .with_remote_v2_downgrade_enabled(
+            proxy.service_name == "codex"
+                && config
+                    .runtime_snapshot
+                    .config()
+                    .codex
+                    .remote_v2_downgrade_enabled(),
+        )
+        .with_response_header_policy(ResponseHeaderPolicy::from_service_config(
+            service_route_config(
+                config.runtime_snapshot.config().as_ref(),
+                proxy.service_name,
+            ),
+        ));
+    let request_body_previews = crate::logging::should_log_request_body_preview()
+        && !config.runtime_snapshot.config().privacy.redact_prompts;
     let prepared = match prepare_http_request(
         CommonRequestPreparationParams {
             proxy,
@@ -168,6 +178,12 @@ pub(super) async fn prepare_proxy_request(
                 "failed to begin durable request lifecycle".to_string(),
             ));
         }
+        Err(CommonRequestPreparationError::EffortAboveCeiling { requested, ceiling }) => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                effort_above_ceiling_message(&requested, &ceiling),
+            ));
+        }
         Err(CommonRequestPreparationError::NoRoutableCandidate {
             request_id,
             session_id,
@@ -3,9 +3,12 @@ use std::sync::Arc;
 use axum::body::Bytes;
 use axum::http::{HeaderMap, Method, Uri};
 
-use crate::config::{CODEX_CLIENT_RUNTIME_PATCH_HEADER, CodexClientRuntimePatch};
+use crate::config::{
+    CODEX_CLIENT_RUNTIME_PATCH_HEADER, CodexClientRuntimePatch, EffortGuardConfig,
+};
 use crate::endpoint_health::{CooldownBackoff, RouteCapability};
 use crate::logging::{BodyPreview, CodexBridgeLog, ServiceTierLog, make_body_preview};
+use crate::model_routing::{EffortClamp, clamp_reasoning_effort};
 use crate::routing_ir::RouteRequestContext;
 use crate::runtime_store::RequestAccountingScope;
 use crate::state::{
@@ -14,6 +17,7 @@ use crate::state::{
 
 use super::ProxyService;
 use super::client_identity::ClientSessionIdentity;
+use super::headers::ResponseHeaderPolicy;
 use super::request_body::{
     ReasoningOrchestrationIntent, RequestDialect, apply_model_override_value,
     apply_reasoning_effort_override_value, apply_service_tier_override_value,
@@ -236,6 +240,7 @@ pub(super) struct RequestFlavor {
     pub stream_terminal_policy: StreamTerminalPolicy,
     pub replay_policy: RequestReplayPolicy,
     pub codex_bridge_log: Option<CodexBridgeLog>,
+    pub response_header_policy: ResponseHeaderPolicy,
 }
 
 impl RequestFlavor {
@@ -248,6 +253,11 @@ impl RequestFlavor {
         self
     }
 
+    pub(super) fn with_response_header_policy(mut self, policy: ResponseHeaderPolicy) -> Self {
+        self.response_header_policy = policy;
+        self
+    }
+
     pub(super) fn route_state_session_id<'a>(
         &self,
         session_id: Option<&'a str>,
@@ -324,6 +334,26 @@ pub(super) struct PreparedRequestBody {
     pub deferred_reasoning_intent: Option<ReasoningOrchestrationIntent>,
     pub base_service_tier: ServiceTierLog,
     pub request_body_len: usize,
+    pub effort_guard: EffortGuardOutcome,
+}
+
+pub(super) fn effort_above_ceiling_message(requested: &str, ceiling: &str) -> String {
+    format!("reasoning effort `{requested}` exceeds effort_guard.max_effort `{ceiling}`")
+}
+
+/// What `effort_guard` did to a request's reasoning effort.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(super) enum EffortGuardOutcome {
+    #[default]
+    PassThrough,
+    Downgraded {
+        requested: String,
+        ceiling: String,
+    },
+    Rejected {
+        requested: String,
+        ceiling: String,
+    },
 }
 
 #[derive(Debug, Clone, Default)]
@@ -369,6 +399,10 @@ pub(super) enum CommonRequestPreparationError {
     LifecycleStoreUnavailable {
         message: String,
     },
+    EffortAboveCeiling {
+        requested: String,
+        ceiling: String,
+    },
     NoRoutableCandidate {
         request_id: u64,
         session_id: Option<String>,
@@ -524,10 +558,17 @@ async fn prepare_common_request_inner(
             binding_model,
             binding_service_tier,
             filter_hosted_image_generation_tools,
+            effort_guard: Some(&config_snapshot.effort_guard),
         })
     } else {
         inspect_passthrough_request_body(raw_body, request_dialect)
     };
+    if let EffortGuardOutcome::Rejected { requested, ceiling } = &prepared_request.effort_guard {
+        return Err(CommonRequestPreparationError::EffortAboveCeiling {
+            requested: requested.clone(),
+            ceiling: ceiling.clone(),
+        });
+    }
     let body_for_upstream = prepared_request.body_for_upstream.clone();
     let request_model = prepared_request.request_model.clone();
     let effective_effort = prepared_request.effective_effort.clone();
@@ -567,8 +608,14 @@ async fn prepare_common_request_inner(
             uri.path(),
             session_identity_source,
             client_name,
+            super::client_identity::extract_tenant(client_headers),
             client_addr,
-            cwd.clone(),
+            // Redact before the request enters shared state so logs, usage, and UIs agree.
+            config
+                .runtime_snapshot
+                .config()
+                .privacy
+                .apply_to_cwd(cwd.clone()),
             request_model.clone(),
             prepared_request.requested_model.clone(),
             effective_effort.clone(),
@@ -588,10 +635,32 @@ async fn prepare_common_request_inner(
             },
         )?;
 
+    if !raw_body.is_empty() {
+        warn_on_possible_duplicate_request(proxy, request_id, raw_body, started_at_ms).await;
+    }
+
+    if let EffortGuardOutcome::Downgraded { requested, ceiling } = &prepared_request.effort_guard {
+        tracing::warn!(
+            request_id,
+            service = proxy.service_name,
+            requested = %requested,
+            effective = %ceiling,
+            "reasoning effort downgraded by effort_guard"
+        );
+        crate::logging::log_control_trace_event(serde_json::json!({
+            "event": "effort_downgraded",
+            "service": proxy.service_name,
+            "request_id": request_id,
+            "requested_effort": requested,
+            "effective_effort": ceiling,
+        }));
+    }
+
     let plan = retry_plan(&config_snapshot.retry.resolve());
     let cooldown_backoff = CooldownBackoff {
         factor: plan.cooldown_backoff_factor,
         max_secs: plan.cooldown_backoff_max_secs,
+        probe_back_success_threshold: plan.probe_back_success_threshold,
     };
 
     let route_plan = if select_route {
@@ -658,6 +727,44 @@ async fn prepare_common_request_inner(
     })
 }
 
+/// Hashes the client body onto the active request and warns when the same session sent an
+/// identical body moments ago. Detection only; the request is still forwarded.
+async fn warn_on_possible_duplicate_request(
+    proxy: &ProxyService,
+    request_id: u64,
+    raw_body: &[u8],
+    now_ms: u64,
+) {
+    let body_hash = request_body_hash(raw_body);
+    let Some(previous_request_id) = proxy
+        .state
+        .record_request_body_hash(request_id, body_hash.clone(), now_ms)
+        .await
+    else {
+        return;
+    };
+    tracing::warn!(
+        request_id,
+        previous_request_id,
+        service = proxy.service_name,
+        body_hash = %body_hash,
+        "possible duplicate request: same session and body as request {previous_request_id}"
+    );
+    crate::logging::log_control_trace_event(serde_json::json!({
+        "event": "possible_duplicate_request",
+        "service": proxy.service_name,
+        "request_id": request_id,
+        "previous_request_id": previous_request_id,
+        "body_hash": body_hash,
+    }));
+}
+
+fn request_body_hash(raw_body: &[u8]) -> String {
+    use sha2::{Digest, Sha256};
+
+    format!("{:x}", Sha256::digest(raw_body))
+}
+
 fn client_runtime_patch(headers: &HeaderMap) -> Option<CodexClientRuntimePatch> {
     let mut values = headers.get_all(CODEX_CLIENT_RUNTIME_PATCH_HEADER).iter();
     let value = values.next()?;
@@ -719,6 +826,7 @@ pub(super) fn detect_request_flavor(
         stream_terminal_policy: endpoint_surface.stream_terminal_policy(),
         replay_policy: endpoint_surface.replay_policy(method),
         codex_bridge_log,
+        response_header_policy: ResponseHeaderPolicy::default(),
     }
 }
 
@@ -823,6 +931,7 @@ pub(super) struct PrepareRequestBodyParams<'a> {
     binding_model: Option<&'a str>,
     binding_service_tier: Option<&'a str>,
     filter_hosted_image_generation_tools: bool,
+    effort_guard: Option<&'a EffortGuardConfig>,
 }
 
 pub(super) fn prepare_request_body(params: PrepareRequestBodyParams<'_>) -> PreparedRequestBody {
@@ -833,6 +942,7 @@ pub(super) fn prepare_request_body(params: PrepareRequestBodyParams<'_>) -> Prep
         binding_model,
         binding_service_tier,
         filter_hosted_image_generation_tools,
+        effort_guard,
     } = params;
     let mut request_json = serde_json::from_slice::<serde_json::Value>(raw_body).ok();
     let original_effort = request_json
@@ -846,14 +956,34 @@ pub(super) fn prepare_request_body(params: PrepareRequestBodyParams<'_>) -> Prep
     let is_object_root = request_json
         .as_ref()
         .is_some_and(serde_json::Value::is_object);
-    let selected_effort = if dialect.supports_reasoning_effort() {
+    let mut selected_effort = if dialect.supports_reasoning_effort() {
         binding_effort
     } else {
         None
     };
-    let requested_effort = selected_effort
+    let mut requested_effort = selected_effort
         .map(str::to_owned)
         .or_else(|| original_effort.clone());
+    let effort_guard = match (requested_effort.as_deref(), effort_guard) {
+        (Some(requested), Some(guard)) if dialect.supports_reasoning_effort() => {
+            match clamp_reasoning_effort(requested, guard) {
+                EffortClamp::PassThrough => EffortGuardOutcome::PassThrough,
+                EffortClamp::Downgrade { ceiling } => EffortGuardOutcome::Downgraded {
+                    requested: requested.to_string(),
+                    ceiling: ceiling.to_string(),
+                },
+                EffortClamp::Reject { ceiling } => EffortGuardOutcome::Rejected {
+                    requested: requested.to_string(),
+                    ceiling: ceiling.to_string(),
+                },
+            }
+        }
+        _ => EffortGuardOutcome::PassThrough,
+    };
+    if let EffortGuardOutcome::Downgraded { ceiling, .. } = &effort_guard {
+        selected_effort = Some(ceiling.as_str());
+        requested_effort = Some(ceiling.clone());
+    }
     let deferred_reasoning_intent = requested_effort
         .as_deref()
         .filter(|effort| effort.eq_ignore_ascii_case("ultra"))
@@ -911,6 +1041,7 @@ pub(super) fn prepare_request_body(params: PrepareRequestBodyParams<'_>) -> Prep
             actual: None,
         },
         body_for_upstream,
+        effort_guard,
     }
 }
 
@@ -939,6 +1070,7 @@ fn inspect_passthrough_request_body(
             actual: None,
         },
         request_body_len: raw_body.len(),
+        effort_guard: EffortGuardOutcome::PassThrough,
     }
 }
 
@@ -1372,6 +1504,7 @@ mod tests {
             binding_model: Some("gpt-5.4"),
             binding_service_tier: Some("flex"),
             filter_hosted_image_generation_tools: false,
+            effort_guard: None,
         });
 
         assert_eq!(prepared.request_model.as_deref(), Some("gpt-5.4"));
@@ -1400,6 +1533,7 @@ mod tests {
             binding_model: None,
             binding_service_tier: None,
             filter_hosted_image_generation_tools: false,
+            effort_guard: None,
         });
 
         let value: serde_json::Value =
@@ -1431,6 +1565,7 @@ mod tests {
             binding_model: None,
             binding_service_tier: None,
             filter_hosted_image_generation_tools: false,
+            effort_guard: None,
         });
 
         let value: serde_json::Value =
@@ -1458,6 +1593,7 @@ mod tests {
             binding_model: None,
             binding_service_tier: None,
             filter_hosted_image_generation_tools: false,
+            effort_guard: None,
         });
 
         let value: serde_json::Value =
@@ -1468,6 +1604,94 @@ mod tests {
         assert_eq!(prepared.effective_effort, None);
     }
 
+    #[test]
+    fn effort_guard_downgrades_profile_and_client_efforts_above_the_ceiling() {
+        let guard = EffortGuardConfig {
+            max_effort: Some("high".to_string()),
+            action: crate::config::EffortGuardAction::Downgrade,
+        };
+        let raw_body = Bytes::from_static(br#"{"model":"gpt-5","reasoning":{"effort":"low"}}"#);
+        let prepared = prepare_request_body(PrepareRequestBodyParams {
+            raw_body: &raw_body,
+            dialect: RequestDialect::ResponsesHttp,
+            binding_effort: Some("xhigh"),
+            binding_model: None,
+            binding_service_tier: None,
+            filter_hosted_image_generation_tools: false,
+            effort_guard: Some(&guard),
+        });
+        let value: serde_json::Value =
+            serde_json::from_slice(prepared.body_for_upstream.as_ref()).expect("json body");
+        assert_eq!(value["reasoning"]["effort"].as_str(), Some("high"));
+        assert_eq!(prepared.effective_effort.as_deref(), Some("high"));
+        assert_eq!(
+            prepared.effort_guard,
+            EffortGuardOutcome::Downgraded {
+                requested: "xhigh".to_string(),
+                ceiling: "high".to_string(),
+            }
+        );
+
+        let raw_body =
+            Bytes::from_static(br#"{"model":"gpt-5","messages":[],"reasoning_effort":"ultra"}"#);
+        let prepared = prepare_request_body(PrepareRequestBodyParams {
+            raw_body: &raw_body,
+            dialect: RequestDialect::ChatCompletions,
+            binding_effort: None,
+            binding_model: None,
+            binding_service_tier: None,
+            filter_hosted_image_generation_tools: false,
+            effort_guard: Some(&guard),
+        });
+        let value: serde_json::Value =
+            serde_json::from_slice(prepared.body_for_upstream.as_ref()).expect("json body");
+        assert_eq!(value["reasoning_effort"].as_str(), Some("high"));
+        assert_eq!(prepared.deferred_reasoning_intent, None);
+        assert_eq!(prepared.effective_effort.as_deref(), Some("high"));
+    }
+
+    #[test]
+    fn effort_guard_passes_through_within_ceiling_and_rejects_when_configured() {
+        let raw_body = Bytes::from_static(br#"{"model":"gpt-5","reasoning":{"effort":"medium"}}"#);
+        let downgrade = EffortGuardConfig {
+            max_effort: Some("high".to_string()),
+            action: crate::config::EffortGuardAction::Downgrade,
+        };
+        let prepared = prepare_request_body(PrepareRequestBodyParams {
+            raw_body: &raw_body,
+            dialect: RequestDialect::ResponsesHttp,
+            binding_effort: None,
+            binding_model: None,
+            binding_service_tier: None,
+            filter_hosted_image_generation_tools: false,
+            effort_guard: Some(&downgrade),
+        });
+        assert_eq!(prepared.body_for_upstream, raw_body);
+        assert_eq!(prepared.effective_effort.as_deref(), Some("medium"));
+        assert_eq!(prepared.effort_guard, EffortGuardOutcome::PassThrough);
+
+        let reject = EffortGuardConfig {
+            max_effort: Some("low".to_string()),
+            action: crate::config::EffortGuardAction::Reject,
+        };
+        let prepared = prepare_request_body(PrepareRequestBodyParams {
+            raw_body: &raw_body,
+            dialect: RequestDialect::ResponsesHttp,
+            binding_effort: None,
+            binding_model: None,
+            binding_service_tier: None,
+            filter_hosted_image_generation_tools: false,
+            effort_guard: Some(&reject),
+        });
+        assert_eq!(
+            prepared.effort_guard,
+            EffortGuardOutcome::Rejected {
+                requested: "medium".to_string(),
+                ceiling: "low".to_string(),
+            }
+        );
+    }
+
     #[test]
     fn ultra_is_deferred_as_orchestration_intent_without_global_mapping() {
         let raw_body = Bytes::from_static(
@@ -1481,6 +1705,7 @@ mod tests {
             binding_model: None,
             binding_service_tier: None,
             filter_hosted_image_generation_tools: false,
+            effort_guard: None,
         });
 
         let value: serde_json::Value =
@@ -1503,6 +1728,7 @@ mod tests {
             binding_model: None,
             binding_service_tier: None,
             filter_hosted_image_generation_tools: false,
+            effort_guard: None,
         });
         let value: serde_json::Value =
             serde_json::from_slice(prepared.body_for_upstream.as_ref()).expect("json body");
@@ -1528,6 +1754,7 @@ mod tests {
             binding_model: None,
             binding_service_tier: None,
             filter_hosted_image_generation_tools: false,
+            effort_guard: None,
         });
 
         let value: serde_json::Value =
@@ -1563,6 +1790,7 @@ mod tests {
             binding_model: None,
             binding_service_tier: None,
             filter_hosted_image_generation_tools: true,
+            effort_guard: None,
         });
 
         let value: serde_json::Value =
@@ -1667,6 +1895,9 @@ mod tests {
                 routing: Some(RouteGraphConfig::ordered_failover(vec![
                     provider_id.to_string(),
                 ])),
+                response_header_allowlist: Vec::new(),
+                response_header_denylist: Vec::new(),
+                maintenance: None,
             },
             ..HelperConfig::default()
         }
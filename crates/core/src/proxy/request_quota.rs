@@ -0,0 +1,60 @@
+use axum::body::Body;
+use axum::http::{HeaderMap, HeaderValue, Response, StatusCode, header};
+use axum::response::IntoResponse;
+
+use super::ProxyService;
+use super::client_identity::extract_tenant;
+use crate::state::RequestQuotaExceeded;
+
+/// Admits the request under the `quota` budgets of the provider it is about to be sent to; a
+/// refusal is not counted.
+pub(super) async fn admit_request_quota(
+    proxy: &ProxyService,
+    provider_id: &str,
+    headers: &HeaderMap,
+) -> Result<(), RequestQuotaExceeded> {
+    let config = proxy.captured_runtime_config().await;
+    if !config.quota.is_enabled() {
+        return Ok(());
+    }
+    let tenant = extract_tenant(headers);
+    let result = proxy.state.try_admit_request_quota(
+        proxy.service_name,
+        provider_id,
+        tenant.as_deref(),
+        &config.quota,
+    );
+    if let Err(exceeded) = &result {
+        tracing::warn!(
+            service = proxy.service_name,
+            provider_id = exceeded.provider_id.as_str(),
+            tenant = exceeded.tenant.as_deref(),
+            dimension = exceeded.dimension.as_str(),
+            used = exceeded.used,
+            limit = exceeded.limit,
+            retry_after_secs = exceeded.retry_after_secs,
+            "request refused by quota"
+        );
+        crate::logging::log_control_trace_event(serde_json::json!({
+            "event": "request_quota_exceeded",
+            "service": proxy.service_name,
+            "provider_id": exceeded.provider_id,
+            "tenant": exceeded.tenant,
+            "dimension": exceeded.dimension.as_str(),
+            "used": exceeded.used,
+            "limit": exceeded.limit,
+            "window_secs": exceeded.window_secs,
+            "retry_after_secs": exceeded.retry_after_secs,
+        }));
+    }
+    result
+}
+
+pub(super) fn request_quota_exceeded_response(exceeded: &RequestQuotaExceeded) -> Response<Body> {
+    let mut response = (StatusCode::TOO_MANY_REQUESTS, exceeded.message()).into_response();
+    response.headers_mut().insert(
+        header::RETRY_AFTER,
+        HeaderValue::from(exceeded.retry_after_secs),
+    );
+    response
+}
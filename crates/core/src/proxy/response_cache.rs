@@ -0,0 +1,205 @@
+use std::time::Instant;
+
+use axum::body::{Body, Bytes};
+use axum::http::{HeaderMap, HeaderValue, Method, Response, StatusCode, Uri, header};
+
+use super::ProxyService;
+use super::headers::{ResponseHeaderPolicy, filter_response_headers};
+use super::models_compat::codex_path_is_models;
+use super::response_finalization::{
+    FinalizeForwardResponseParams, finish_and_build_forward_response,
+};
+use crate::logging::{ServiceTierLog, upstream_origin};
+use crate::routing_ir::CapturedRouteCandidate;
+use crate::runtime_store::RequestAccountingScope;
+use crate::state::{CachedGetResponse, RouteDecisionProvenance, SessionIdentitySource};
+
+pub(super) const CACHE_STATUS_HEADER: &str = "x-codex-helper-cache";
+
+/// A request that may be answered from, or stored into, the `cache.models_ttl_ms` cache.
+pub(super) struct CacheableGetRequest {
+    path_and_query: String,
+    revision: u64,
+    ttl_ms: u64,
+}
+
+impl CacheableGetRequest {
+    /// Entries are per selected provider endpoint, so two providers never share a catalog.
+    /// The config revision keeps a reload from serving a response from the old catalog.
+    fn key_for(&self, service_name: &str, target: &CapturedRouteCandidate) -> String {
+        format!(
+            "{service_name}|{}|{}|{}|{}",
+            self.revision,
+            target.provider_endpoint_key(),
+            target.base_url(),
+            self.path_and_query
+        )
+    }
+}
+
+/// Only plain `GET .../models` requests are cached, and only while `cache.models_ttl_ms` is set.
+/// The lookup itself happens after routing, once the upstream endpoint is known.
+pub(super) async fn cacheable_get_request(
+    proxy: &ProxyService,
+    method: &Method,
+    uri: &Uri,
+    headers: &HeaderMap,
+) -> Option<CacheableGetRequest> {
+    if method != Method::GET || !codex_path_is_models(uri.path()) {
+        return None;
+    }
+    let accepts_event_stream = headers
+        .get(header::ACCEPT)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.contains("text/event-stream"));
+    if accepts_event_stream {
+        return None;
+    }
+    let path_and_query = uri
+        .path_and_query()
+        .map(|value| value.as_str())
+        .unwrap_or_else(|| uri.path());
+    let snapshot = proxy.config.capture().await;
+    let ttl_ms = snapshot.config().cache.models_ttl_ms;
+    if ttl_ms == 0 {
+        return None;
+    }
+    Some(CacheableGetRequest {
+        path_and_query: path_and_query.to_string(),
+        revision: snapshot.revision(),
+        ttl_ms,
+    })
+}
+
+pub(super) struct ServeCachedResponseParams<'a> {
+    pub(super) request: &'a CacheableGetRequest,
+    pub(super) target: &'a CapturedRouteCandidate,
+    pub(super) method: &'a Method,
+    pub(super) path: &'a str,
+    pub(super) request_id: u64,
+    pub(super) start: &'a Instant,
+    pub(super) started_at_ms: u64,
+    pub(super) session_id: Option<&'a str>,
+    pub(super) session_identity_source: Option<SessionIdentitySource>,
+    pub(super) cwd: Option<&'a str>,
+    pub(super) service_tier: &'a ServiceTierLog,
+    pub(super) route_decision: RouteDecisionProvenance,
+    pub(super) response_header_policy: &'a ResponseHeaderPolicy,
+    pub(super) terminal_accounting: RequestAccountingScope,
+}
+
+/// Answers from the cache for `target` without contacting it. The request is logged with
+/// `cache_hit`, but no upstream attempt, concurrency signal, or health outcome is recorded: the
+/// upstream was never asked, so a hit says nothing about its state.
+pub(super) async fn serve_cached_response(
+    proxy: &ProxyService,
+    params: ServeCachedResponseParams<'_>,
+) -> Option<Response<Body>> {
+    let ServeCachedResponseParams {
+        request,
+        target,
+        method,
+        path,
+        request_id,
+        start,
+        started_at_ms,
+        session_id,
+        session_identity_source,
+        cwd,
+        service_tier,
+        route_decision,
+        response_header_policy,
+        terminal_accounting,
+    } = params;
+    let key = request.key_for(proxy.service_name, target);
+    let cached = proxy
+        .state
+        .cached_get_response(&key, request.ttl_ms, crate::logging::now_ms())
+        .await?;
+    tracing::debug!(
+        service = proxy.service_name,
+        request_id,
+        key = key.as_str(),
+        "serving GET response from cache"
+    );
+    proxy
+        .state
+        .update_request_route(request_id, route_decision.clone())
+        .await;
+    let mut response_headers = filter_response_headers(&cached.headers, response_header_policy);
+    mark_cache_status(&mut response_headers, true);
+    let duration_ms = start.elapsed().as_millis() as u64;
+    let finalized = finish_and_build_forward_response(
+        proxy,
+        method,
+        path,
+        FinalizeForwardResponseParams {
+            request_id,
+            winning_attempt: None,
+            status: StatusCode::OK,
+            duration_ms,
+            started_at_ms,
+            ttfb_ms: duration_ms,
+            provider_id: Some(target.provider_id().to_owned()),
+            endpoint_id: Some(target.endpoint_id().to_owned()),
+            provider_endpoint_key: Some(target.provider_endpoint_key()),
+            upstream_origin: upstream_origin(target.base_url()),
+            session_id: session_id.map(ToOwned::to_owned),
+            session_identity_source,
+            cwd: cwd.map(ToOwned::to_owned),
+            effective_effort: None,
+            service_tier: service_tier.clone(),
+            reported_model: None,
+            codex_bridge: None,
+            usage: None,
+            upstream_request_id: None,
+            cache_hit: true,
+            route_decision: Some(route_decision),
+            retry: None,
+            route_affinity_success: None,
+            terminal_accounting,
+            http_debug: None,
+            response_headers,
+            response_body: cached.body,
+        },
+    )
+    .await;
+    Some(finalized.response)
+}
+
+/// Stores a successful, non-streaming upstream response for `target`.
+pub(super) async fn store_upstream_response(
+    proxy: &ProxyService,
+    request: &CacheableGetRequest,
+    target: &CapturedRouteCandidate,
+    status: StatusCode,
+    headers: &HeaderMap,
+    body: &Bytes,
+    now_ms: u64,
+) {
+    let is_event_stream = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("text/event-stream"));
+    if status != StatusCode::OK || is_event_stream {
+        return;
+    }
+    proxy
+        .state
+        .store_get_response(
+            request.key_for(proxy.service_name, target),
+            CachedGetResponse {
+                headers: headers.clone(),
+                body: body.clone(),
+                stored_at_ms: now_ms,
+            },
+        )
+        .await;
+}
+
+pub(super) fn mark_cache_status(headers: &mut HeaderMap, hit: bool) {
+    headers.insert(
+        CACHE_STATUS_HEADER,
+        HeaderValue::from_static(if hit { "hit" } else { "miss" }),
+    );
+}
@@ -15,7 +15,8 @@ pub(super) struct FinalizeForwardResponseParams {
     pub status: StatusCode,
     pub duration_ms: u64,
     pub started_at_ms: u64,
-    pub upstream_headers_ms: u64,
+    /// Request-relative time the winning upstream's response headers arrived.
+    pub ttfb_ms: u64,
     pub provider_id: Option<String>,
     pub endpoint_id: Option<String>,
     pub provider_endpoint_key: Option<String>,
@@ -28,6 +29,8 @@ pub(super) struct FinalizeForwardResponseParams {
     pub reported_model: Option<String>,
     pub codex_bridge: Option<CodexBridgeLog>,
     pub usage: Option<UsageMetrics>,
+    pub upstream_request_id: Option<String>,
+    pub cache_hit: bool,
     pub route_decision: Option<RouteDecisionProvenance>,
     pub retry: Option<RetryInfo>,
     pub route_affinity_success: Option<SessionRouteAffinitySuccess>,
@@ -54,7 +57,7 @@ pub(super) async fn finish_and_build_forward_response(
         status,
         duration_ms,
         started_at_ms,
-        upstream_headers_ms,
+        ttfb_ms,
         provider_id,
         endpoint_id,
         provider_endpoint_key,
@@ -67,6 +70,8 @@ pub(super) async fn finish_and_build_forward_response(
         reported_model,
         codex_bridge,
         usage,
+        upstream_request_id,
+        cache_hit,
         route_decision,
         retry,
         route_affinity_success,
@@ -88,7 +93,7 @@ pub(super) async fn finish_and_build_forward_response(
         started_at_ms,
         streaming,
     );
-    publication.ttfb_ms = Some(upstream_headers_ms);
+    publication.ttfb_ms = Some(ttfb_ms);
     publication.winning_attempt = winning_attempt;
     publication.provider_id = provider_id;
     publication.endpoint_id = endpoint_id;
@@ -102,6 +107,8 @@ pub(super) async fn finish_and_build_forward_response(
     publication.reported_model = reported_model;
     publication.codex_bridge = codex_bridge;
     publication.usage = usage;
+    publication.upstream_request_id = upstream_request_id;
+    publication.cache_hit = cache_hit;
     publication.route_decision = route_decision;
     publication.retry = retry;
     publication.route_affinity_success = route_affinity_success;
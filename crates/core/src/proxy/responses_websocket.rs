@@ -68,7 +68,7 @@ use super::request_failures::{
 use super::request_observer::{RequestObserver, RequestPublication};
 use super::request_preparation::{
     CommonRequestPreparationError, CommonRequestPreparationParams, RequestOrigin,
-    load_request_config_context, prepare_common_request,
+    effort_above_ceiling_message, load_request_config_context, prepare_common_request,
 };
 use super::retry::{RetryPlan, retry_info_for_failed_attempts, retry_info_for_observed_attempts};
 use super::route_affinity::{
@@ -82,7 +82,7 @@ use super::route_attempts::{
     start_selected_route_attempt,
 };
 use super::route_target_selection::{
-    acquire_candidate_concurrency_permit, apply_auth_resolution_to_runtime,
+    AutoActiveUpdate, acquire_candidate_concurrency_permit, apply_auth_resolution_to_runtime,
     apply_routing_operator_control_to_runtime, restrict_route_state_to_affinity_continuity_domain,
     route_graph_request_requires_existing_affinity, route_graph_runtime_for_request,
     runtime_for_acquired_candidate_revalidation, runtime_for_capacity_wait_selection,
@@ -209,6 +209,8 @@ impl WebSocketRelayTermination {
 
 struct PrepareResponsesWebSocketParams {
     uri: Uri,
+    /// Provider of the upstream socket the request will be relayed to.
+    provider_id: String,
     client_headers: HeaderMap,
     first_message: AxumWsMessage,
     start: Instant,
@@ -453,6 +455,7 @@ fn websocket_handshake_health_policy(
         CooldownBackoff {
             factor: retry.cooldown_backoff_factor,
             max_secs: retry.cooldown_backoff_max_secs,
+            probe_back_success_threshold: retry.probe_back_success_threshold,
         },
     )
 }
@@ -591,6 +594,7 @@ async fn prepare_responses_websocket(
 ) -> Result<ResponsesWebSocketPrepared, (StatusCode, String)> {
     let PrepareResponsesWebSocketParams {
         uri,
+        provider_id,
         client_headers,
         first_message,
         start,
@@ -627,6 +631,11 @@ async fn prepare_responses_websocket(
         .and_then(|value| value.get("generate"))
         .and_then(serde_json::Value::as_bool)
         == Some(false);
+    if !is_warmup {
+        super::request_quota::admit_request_quota(proxy, &provider_id, &client_headers)
+            .await
+            .map_err(|exceeded| (StatusCode::TOO_MANY_REQUESTS, exceeded.message()))?;
+    }
     let (session_identity_hint, raw_body) =
         codex_session_identity_and_completed_body(&mut client_headers, &raw_body);
     let config = load_request_config_context(proxy, session_identity_hint.as_ref()).await;
@@ -665,6 +674,12 @@ async fn prepare_responses_websocket(
                 "failed to begin durable request lifecycle".to_string(),
             ));
         }
+        Err(CommonRequestPreparationError::EffortAboveCeiling { requested, ceiling }) => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                effort_above_ceiling_message(&requested, &ceiling),
+            ));
+        }
         Err(CommonRequestPreparationError::NoRoutableCandidate {
             request_id,
             session_id,
@@ -772,10 +787,13 @@ async fn prepare_responses_websocket_handshake_route(
             )
         },
     )?;
+    runtime.apply_provider_schedules(&template, crate::logging::now_ms());
     apply_routing_operator_control_to_runtime(
         proxy,
+        &template,
         routing_control_graph_key.as_str(),
         &mut runtime,
+        AutoActiveUpdate::Record,
     )
     .await;
 
@@ -1334,10 +1352,12 @@ async fn build_selected(
             upstream_attempt: 0,
             provider_max_attempts: prepared.plan.route.max_attempts,
             upstream_max_attempts: 1,
+            max_upstreams_tried: prepared.plan.max_upstreams_tried,
             model_note: model_note.as_str(),
             avoid_set: &avoid_set,
             avoided_total,
             total_upstreams,
+            pinned_by: None,
         },
     );
 
@@ -1690,6 +1710,7 @@ async fn relay_websocket_streams(
                                 &proxy,
                                 PrepareResponsesWebSocketParams {
                                     uri: uri.clone(),
+                                    provider_id: route.target.provider_id().to_string(),
                                     client_headers: client_headers.clone(),
                                     first_message: message,
                                     start: Instant::now(),
@@ -3043,10 +3064,11 @@ fn tungstenite_to_axum_message(message: tungstenite::Message) -> AxumWsMessage {
 }
 
 fn close_code_for_status(status: StatusCode) -> u16 {
-    if status == StatusCode::BAD_REQUEST {
-        1008
-    } else {
-        1011
+    match status {
+        StatusCode::BAD_REQUEST => 1008,
+        // 1013 "Try Again Later": the client may reconnect once the quota window frees up.
+        StatusCode::TOO_MANY_REQUESTS => 1013,
+        _ => 1011,
     }
 }
 
@@ -3072,6 +3094,7 @@ mod tests {
             preference_group: 0,
             stable_index: 0,
             concurrency: crate::routing_ir::RouteCandidateConcurrency::default(),
+            schedule: None,
         };
         CapturedRouteCandidate::capture_for_service("codex", &candidate)
     }
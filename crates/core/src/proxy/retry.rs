@@ -9,7 +9,9 @@ use crate::config::ResolvedRetryLayerConfig;
 use crate::config::RetryStrategy;
 use crate::logging::{RetryInfo, RouteAttemptLog};
 
-use super::classify::{UPSTREAM_OVERLOADED_CLASS, UPSTREAM_RATE_LIMITED_CLASS};
+use super::classify::{
+    UPSTREAM_OVERLOADED_CLASS, UPSTREAM_RATE_LIMITED_CLASS, retry_after_secs_from_header,
+};
 use super::reasoning_guard::REASONING_GUARD_TRIGGERED_CLASS;
 
 #[derive(Clone)]
@@ -35,6 +37,9 @@ pub(super) struct RetryPlan {
     pub(super) transport_cooldown_secs: u64,
     pub(super) cooldown_backoff_factor: u64,
     pub(super) cooldown_backoff_max_secs: u64,
+    pub(super) probe_back_success_threshold: u32,
+    pub(super) connection_reset_fast_retries: u32,
+    pub(super) max_upstreams_tried: Option<u32>,
 }
 
 pub(super) fn parse_status_ranges(spec: &str) -> Vec<(u16, u16)> {
@@ -95,6 +100,9 @@ pub(super) fn retry_plan(cfg: &ResolvedRetryConfig) -> RetryPlan {
     let transport_cooldown_secs = cfg.transport_cooldown_secs;
     let cooldown_backoff_factor = cfg.cooldown_backoff_factor.clamp(1, 16);
     let cooldown_backoff_max_secs = cfg.cooldown_backoff_max_secs.clamp(0, 24 * 60 * 60);
+    let probe_back_success_threshold = cfg.probe_back_success_threshold.clamp(1, 32);
+    let connection_reset_fast_retries = cfg.connection_reset_fast_retries.min(3);
+    let max_upstreams_tried = cfg.max_upstreams_tried.map(|max| max.clamp(1, 32));
 
     RetryPlan {
         upstream,
@@ -107,6 +115,9 @@ pub(super) fn retry_plan(cfg: &ResolvedRetryConfig) -> RetryPlan {
         transport_cooldown_secs,
         cooldown_backoff_factor,
         cooldown_backoff_max_secs,
+        probe_back_success_threshold,
+        connection_reset_fast_retries,
+        max_upstreams_tried,
     }
 }
 
@@ -139,6 +150,26 @@ fn retry_info_from_route_attempts(route_attempts: &[RouteAttemptLog]) -> Option<
     })
 }
 
+/// Distinct upstreams that received at least one real attempt, for `retry.max_upstreams_tried`.
+pub(super) fn upstreams_tried(route_attempts: &[RouteAttemptLog]) -> u32 {
+    let mut tried = route_attempts
+        .iter()
+        .filter(|attempt| !attempt.skipped && attempt.decision != "all_upstreams_avoided")
+        .filter_map(|attempt| attempt.provider_endpoint_key.as_deref())
+        .collect::<Vec<_>>();
+    tried.sort_unstable();
+    tried.dedup();
+    tried.len() as u32
+}
+
+pub(super) fn upstream_budget_exhausted(
+    plan: &RetryPlan,
+    route_attempts: &[RouteAttemptLog],
+) -> bool {
+    plan.max_upstreams_tried
+        .is_some_and(|max| upstreams_tried(route_attempts) >= max)
+}
+
 fn observed_attempt_count(route_attempts: &[RouteAttemptLog]) -> u32 {
     route_attempts
         .iter()
@@ -190,7 +221,8 @@ pub(super) fn should_never_retry(plan: &RetryPlan, status_code: u16, class: Opti
     !class_is_explicitly_retryable
 }
 
-pub(super) fn response_penalty_cooldown_secs(
+/// Cooldown applied to an upstream after a failover-worthy failure of the given error class.
+pub fn response_penalty_cooldown_secs(
     cloudflare_challenge_cooldown_secs: u64,
     cloudflare_timeout_cooldown_secs: u64,
     transport_cooldown_secs: u64,
@@ -212,14 +244,8 @@ pub(super) fn response_penalty_cooldown_secs(
 }
 
 fn retry_after_ms(headers: &HeaderMap, opt: &RetryLayerOptions) -> Option<u64> {
-    let raw = headers.get("retry-after")?.to_str().ok()?.trim();
-    if raw.is_empty() {
-        return None;
-    }
-    let seconds = raw.parse::<u64>().ok()?;
-    let ms = seconds.saturating_mul(1000);
-    let cap = opt.max_backoff_ms.max(opt.base_backoff_ms);
-    Some(ms.min(cap))
+    retry_after_secs_from_header(headers, "retry-after")
+        .and_then(|secs| retry_after_ms_from_secs(secs, opt))
 }
 
 fn retry_after_ms_from_secs(secs: u64, opt: &RetryLayerOptions) -> Option<u64> {
@@ -231,6 +257,18 @@ fn retry_after_ms_from_secs(secs: u64, opt: &RetryLayerOptions) -> Option<u64> {
     Some(ms.min(cap))
 }
 
+/// Wait before retrying the same upstream when it sent `Retry-After` (seconds or HTTP-date),
+/// clamped by the layer's `backoff_max_ms`; `None` falls back to exponential backoff.
+pub(super) fn honored_retry_after_ms(
+    opt: &RetryLayerOptions,
+    resp_headers: &HeaderMap,
+    retry_after_secs: Option<u64>,
+) -> Option<u64> {
+    retry_after_secs
+        .and_then(|secs| retry_after_ms_from_secs(secs, opt))
+        .or_else(|| retry_after_ms(resp_headers, opt))
+}
+
 pub(super) async fn backoff_sleep(opt: &RetryLayerOptions, attempt_index: u32) {
     if opt.base_backoff_ms == 0 {
         return;
@@ -252,13 +290,9 @@ pub(super) async fn backoff_sleep(opt: &RetryLayerOptions, attempt_index: u32) {
 pub(super) async fn retry_sleep(
     opt: &RetryLayerOptions,
     attempt_index: u32,
-    resp_headers: &HeaderMap,
-    retry_after_secs: Option<u64>,
+    honored_retry_after_ms: Option<u64>,
 ) {
-    if let Some(mut ms) = retry_after_secs
-        .and_then(|secs| retry_after_ms_from_secs(secs, opt))
-        .or_else(|| retry_after_ms(resp_headers, opt))
-    {
+    if let Some(mut ms) = honored_retry_after_ms {
         if opt.jitter_ms > 0 {
             let jitter = rand::rng().random_range(0..=opt.jitter_ms);
             let cap = opt.max_backoff_ms.max(opt.base_backoff_ms);
@@ -305,6 +339,39 @@ mod tests {
         assert_eq!(retry_after_ms(&headers, &opt), Some(2_000));
     }
 
+    #[test]
+    fn honored_retry_after_accepts_http_dates_and_classified_seconds() {
+        let opt = RetryLayerOptions {
+            max_attempts: 3,
+            base_backoff_ms: 200,
+            max_backoff_ms: 60_000,
+            jitter_ms: 0,
+            retry_status_ranges: vec![(429, 429)],
+            retry_error_classes: Vec::new(),
+            strategy: RetryStrategy::Failover,
+        };
+        let mut headers = HeaderMap::new();
+        let when = std::time::SystemTime::now() + std::time::Duration::from_secs(20);
+        headers.insert(
+            "retry-after",
+            HeaderValue::from_str(&httpdate::fmt_http_date(when)).expect("http date"),
+        );
+        let honored = honored_retry_after_ms(&opt, &headers, None).expect("date honored");
+        assert!((19_000..=20_000).contains(&honored), "{honored}");
+
+        assert_eq!(
+            honored_retry_after_ms(&opt, &headers, Some(5)),
+            Some(5_000),
+            "classified seconds take precedence over the raw header"
+        );
+        assert_eq!(
+            honored_retry_after_ms(&opt, &HeaderMap::new(), Some(600)),
+            Some(60_000),
+            "backoff_max_ms caps the honored wait"
+        );
+        assert_eq!(honored_retry_after_ms(&opt, &HeaderMap::new(), None), None);
+    }
+
     #[test]
     fn retry_info_attempts_excludes_skipped_route_decisions() {
         let route_attempts = vec![
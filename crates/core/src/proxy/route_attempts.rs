@@ -6,8 +6,9 @@ use crate::logging::{HttpDebugLog, RouteAttemptLog};
 use crate::policy_actions::PolicyAction;
 use crate::provider_signals::ProviderSignal;
 
-use super::classify::CLIENT_ERROR_NON_RETRYABLE_CLASS;
-use crate::routing_ir::CapturedRouteCandidate;
+use super::classify::{CLIENT_ERROR_NON_RETRYABLE_CLASS, UPSTREAM_TLS_CERTIFICATE_CLASS};
+use super::retry::upstreams_tried;
+use crate::routing_ir::{CapturedRouteCandidate, UpstreamPinScope};
 
 pub(super) struct StartRouteAttemptParams<'a> {
     pub(super) target: &'a CapturedRouteCandidate,
@@ -16,10 +17,12 @@ pub(super) struct StartRouteAttemptParams<'a> {
     pub(super) upstream_attempt: u32,
     pub(super) provider_max_attempts: u32,
     pub(super) upstream_max_attempts: u32,
+    pub(super) max_upstreams_tried: Option<u32>,
     pub(super) model_note: &'a str,
     pub(super) avoid_set: &'a HashSet<usize>,
     pub(super) avoided_total: usize,
     pub(super) total_upstreams: usize,
+    pub(super) pinned_by: Option<UpstreamPinScope>,
 }
 
 pub(super) struct UnsupportedModelSkipParams<'a> {
@@ -147,6 +150,7 @@ pub(super) enum RouteAttemptErrorKind {
     HalfOpenInvalidated,
     TargetBuild,
     Transport,
+    TlsCertificate,
     BodyRead,
     BodyTooLarge,
 }
@@ -157,7 +161,9 @@ impl RouteAttemptErrorKind {
             RouteAttemptErrorKind::Lifecycle => "failed_lifecycle_store",
             RouteAttemptErrorKind::HalfOpenInvalidated => "skipped_half_open_invalidated",
             RouteAttemptErrorKind::TargetBuild => "failed_target_build",
-            RouteAttemptErrorKind::Transport => "failed_transport",
+            RouteAttemptErrorKind::Transport | RouteAttemptErrorKind::TlsCertificate => {
+                "failed_transport"
+            }
             RouteAttemptErrorKind::BodyRead => "failed_body_read",
             RouteAttemptErrorKind::BodyTooLarge => "failed_body_too_large",
         }
@@ -169,6 +175,7 @@ impl RouteAttemptErrorKind {
             RouteAttemptErrorKind::HalfOpenInvalidated => "transient_half_open_invalidated",
             RouteAttemptErrorKind::TargetBuild => "target_build_error",
             RouteAttemptErrorKind::Transport => "upstream_transport_error",
+            RouteAttemptErrorKind::TlsCertificate => UPSTREAM_TLS_CERTIFICATE_CLASS,
             RouteAttemptErrorKind::BodyRead => "upstream_body_read_error",
             RouteAttemptErrorKind::BodyTooLarge => "upstream_response_body_too_large",
         }
@@ -180,24 +187,34 @@ pub(super) fn start_selected_route_attempt(
     params: StartRouteAttemptParams<'_>,
 ) -> usize {
     let attempt_index = route_attempts.len() as u32;
+    let provider_endpoint_key = params.target.provider_endpoint_key();
+    let upstream_seen = route_attempts.iter().any(|attempt| {
+        !attempt.skipped
+            && attempt.provider_endpoint_key.as_deref() == Some(provider_endpoint_key.as_str())
+    });
+    let upstreams_tried = upstreams_tried(route_attempts) + u32::from(!upstream_seen);
     let mut attempt = RouteAttemptLog {
         attempt_index,
         provider_id: non_dash(params.provider_id)
             .map(ToOwned::to_owned)
             .or_else(|| Some(params.target.provider_id().to_owned())),
         endpoint_id: Some(params.target.endpoint_id().to_owned()),
-        provider_endpoint_key: Some(params.target.provider_endpoint_key()),
+        provider_endpoint_key: Some(provider_endpoint_key),
         preference_group: Some(params.target.preference_group()),
+        below_top_provider: params.target.below_top_provider(),
         route_path: params.target.route_path().to_vec(),
         provider_attempt: Some(params.provider_attempt + 1),
         upstream_attempt: Some(params.upstream_attempt + 1),
         provider_max_attempts: Some(params.provider_max_attempts),
         upstream_max_attempts: Some(params.upstream_max_attempts),
+        upstreams_tried: Some(upstreams_tried),
+        max_upstreams_tried: params.max_upstreams_tried,
         avoided_candidate_indices: sorted_avoid_set(params.avoid_set),
         avoided_total: Some(params.avoided_total),
         total_upstreams: Some(params.total_upstreams),
         decision: "selected".to_string(),
         model: normalize_model(params.model_note),
+        pinned_by: params.pinned_by,
         ..Default::default()
     };
     attempt.refresh_code();
@@ -292,6 +309,7 @@ fn fill_attempt_identity(attempt: &mut RouteAttemptLog, target: &CapturedRouteCa
     if attempt.preference_group.is_none() {
         attempt.preference_group = Some(target.preference_group());
     }
+    attempt.below_top_provider |= target.below_top_provider();
     if attempt.route_path.is_empty() {
         attempt.route_path = target.route_path().to_vec();
     }
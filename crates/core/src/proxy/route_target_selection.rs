@@ -1,13 +1,16 @@
 use std::collections::HashSet;
 use std::time::Duration;
 
+use crate::active_history::{
+    ActiveChangeKind, ActiveChangeRecord, ActiveChangeSource, record_active_change,
+};
 use crate::auth_resolution::target_credential_readiness;
 use crate::config::SchedulingPreset;
 use crate::endpoint_health::RouteCapability;
 use crate::logging::log_control_trace_event;
 use crate::routing_ir::{
     RouteCandidate, RoutePlanAttemptSelection, RoutePlanAttemptState, RoutePlanExecutor,
-    RoutePlanRuntimeState, RoutePlanTemplate,
+    RoutePlanRuntimeState, RoutePlanTemplate, RoutePricing,
 };
 use crate::runtime_identity::ProviderEndpointKey;
 use crate::runtime_store::ProviderPolicySnapshot;
@@ -41,25 +44,169 @@ pub(super) async fn route_graph_runtime_for_request(
         )
         .await;
     apply_auth_resolution_to_runtime(proxy.service_name, template, &mut runtime)?;
+    runtime.apply_provider_schedules(template, crate::logging::now_ms());
+    apply_primary_sticky_holds_to_runtime(proxy, template, &mut runtime).await;
+    if template.prefer_cheapest {
+        runtime.set_pricing(Some(RoutePricing::new(
+            crate::pricing::effective_pricing_catalog_snapshot().shared_catalog(),
+        )));
+    }
     apply_concurrency_snapshots_to_runtime(proxy, template, runtime_revision, &mut runtime);
     apply_session_route_affinity_for_template(proxy, session_id, template, &mut runtime).await;
-    apply_routing_operator_control_to_runtime(proxy, routing_control_graph_key, &mut runtime).await;
+    apply_routing_operator_control_to_runtime(
+        proxy,
+        template,
+        routing_control_graph_key,
+        &mut runtime,
+        AutoActiveUpdate::Record,
+    )
+    .await;
+    apply_upstream_pin_to_runtime(proxy, routing_control_graph_key, session_id, &mut runtime).await;
     Ok(runtime)
 }
 
+pub(super) async fn apply_primary_sticky_holds_to_runtime(
+    proxy: &ProxyService,
+    template: &RoutePlanTemplate,
+    runtime: &mut RoutePlanRuntimeState,
+) {
+    if template.primary_sticky_ms.is_none() {
+        return;
+    }
+    let now_ms = crate::logging::now_ms();
+    let holds = proxy
+        .state
+        .primary_sticky_holds(proxy.service_name, now_ms)
+        .await;
+    runtime.apply_primary_sticky_holds(template, &holds, now_ms);
+}
+
+/// Starts the `routing.primary_sticky_ms` hold on a candidate the request is failing over
+/// away from, so later requests stay on the backup instead of bouncing straight back.
+pub(super) async fn hold_candidate_after_failover(
+    proxy: &ProxyService,
+    request_id: u64,
+    template: &RoutePlanTemplate,
+    candidate: &RouteCandidate,
+) {
+    let Some(hold_ms) = template.primary_sticky_ms else {
+        return;
+    };
+    let provider_endpoint = template.candidate_provider_endpoint_key(candidate);
+    let now_ms = crate::logging::now_ms();
+    let stable_key = provider_endpoint.stable_key();
+    if proxy
+        .state
+        .hold_provider_endpoint_after_failover(
+            provider_endpoint,
+            now_ms.saturating_add(hold_ms),
+            now_ms,
+        )
+        .await
+    {
+        log_control_trace_event(serde_json::json!({
+            "event": "route_primary_sticky_hold_started",
+            "service": proxy.service_name,
+            "request_id": request_id,
+            "provider_endpoint_key": stable_key,
+            "hold_ms": hold_ms,
+        }));
+    }
+}
+
+/// Whether re-evaluating `routing.auto_active_by_health` may move the stored selection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum AutoActiveUpdate {
+    /// Request paths: store the pick and record any move in the active-change history.
+    Record,
+    /// Read-only callers such as routing explain: compute the pick without storing it.
+    Preview,
+}
+
 pub(super) async fn apply_routing_operator_control_to_runtime(
     proxy: &ProxyService,
+    template: &RoutePlanTemplate,
     route_graph_key: &str,
     runtime: &mut RoutePlanRuntimeState,
+    update: AutoActiveUpdate,
 ) {
     let control = proxy.state.capture_routing_operator_control().await;
-    runtime.set_new_session_preference(
+    let preference = control
+        .new_session_preference(proxy.service_name, route_graph_key)
+        .cloned();
+    if preference.is_none()
+        && template.auto_active_by_health
+        && !template.entry_is_manual_sticky()
+        && let Some(target) =
+            refresh_auto_active_selection(proxy, template, route_graph_key, runtime, update).await
+    {
+        runtime.set_auto_active_preference(target);
+        return;
+    }
+    runtime.set_new_session_preference(preference);
+}
+
+/// Applies the session's upstream pin, or the service-wide one. WebSocket handshakes skip
+/// this and keep their captured endpoint.
+pub(super) async fn apply_upstream_pin_to_runtime(
+    proxy: &ProxyService,
+    route_graph_key: &str,
+    session_id: Option<&str>,
+    runtime: &mut RoutePlanRuntimeState,
+) {
+    let control = proxy.state.capture_routing_operator_control().await;
+    runtime.set_upstream_pin(
         control
-            .new_session_preference(proxy.service_name, route_graph_key)
+            .upstream_pin(proxy.service_name, route_graph_key, session_id)
             .cloned(),
     );
 }
 
+/// Re-evaluates `routing.auto_active_by_health` against the health already loaded into
+/// `runtime`. With [`AutoActiveUpdate::Record`] a move is stored and recorded in the
+/// active-change history; a preview leaves both untouched.
+async fn refresh_auto_active_selection(
+    proxy: &ProxyService,
+    template: &RoutePlanTemplate,
+    route_graph_key: &str,
+    runtime: &RoutePlanRuntimeState,
+    update: AutoActiveUpdate,
+) -> Option<ProviderEndpointKey> {
+    let now_ms = crate::logging::now_ms();
+    let current = proxy
+        .state
+        .auto_active_selection(proxy.service_name, route_graph_key)
+        .await;
+    let target = template.auto_active_target(
+        runtime,
+        current
+            .as_ref()
+            .map(|selection| (&selection.target, selection.selected_at_ms)),
+        now_ms,
+    )?;
+    if update == AutoActiveUpdate::Preview {
+        return Some(target);
+    }
+    if let Some(previous) = proxy
+        .state
+        .replace_auto_active_selection(proxy.service_name, route_graph_key, target.clone(), now_ms)
+        .await
+    {
+        let label = |key: &ProviderEndpointKey| format!("{}/{}", key.provider_id, key.endpoint_id);
+        let record = ActiveChangeRecord::new(
+            proxy.service_name,
+            ActiveChangeKind::AutoActive,
+            previous.as_ref().map(label),
+            Some(label(&target)),
+            ActiveChangeSource::Auto,
+        );
+        if let Err(error) = record_active_change(&record) {
+            tracing::warn!("failed to record active-change history: {error}");
+        }
+    }
+    Some(target)
+}
+
 pub(super) fn apply_auth_resolution_to_runtime(
     service_name: &str,
     template: &RoutePlanTemplate,
@@ -9,6 +9,7 @@ use crate::routing_ir::{
 };
 use crate::runtime_identity::ProviderEndpointKey;
 
+use super::classify::UPSTREAM_TLS_CERTIFICATE_CLASS;
 use super::codex_failure::{CodexFailureKind, CodexFailureSse};
 use super::request_preparation::RequestFlavor;
 
@@ -211,13 +212,15 @@ fn codex_stream_failure_kind(route_attempts: &[RouteAttemptLog]) -> Option<Codex
         return Some(CodexFailureKind::RouteUnavailable);
     }
 
+    // Certificate rejections are not transient; let Codex see the plain error instead of a
+    // retryable rate-limit envelope.
     route_attempts
         .iter()
         .any(|attempt| {
             matches!(
                 attempt.decision.as_str(),
                 "failed_status" | "failed_transport" | "failed_body_read" | "failed_body_too_large"
-            )
+            ) && attempt.error_class.as_deref() != Some(UPSTREAM_TLS_CERTIFICATE_CLASS)
         })
         .then_some(CodexFailureKind::UpstreamFailure)
 }
@@ -292,6 +295,9 @@ mod tests {
             scheduling_preset: SchedulingPreset::Balanced,
             fallback_ttl_ms: None,
             reprobe_preferred_after_ms: None,
+            auto_active_by_health: false,
+            prefer_cheapest: false,
+            primary_sticky_ms: None,
             nodes: BTreeMap::new(),
             expanded_provider_order: provider_ids
                 .iter()
@@ -315,6 +321,7 @@ mod tests {
                     preference_group: index as u32,
                     stable_index: index,
                     concurrency: RouteCandidateConcurrency::default(),
+                    schedule: None,
                 })
                 .collect(),
             credential_generation: CredentialGeneration::empty(),
@@ -3,9 +3,13 @@ use std::sync::Arc;
 use anyhow::{Context, Result};
 use axum::http::StatusCode;
 
+use crate::active_history::{
+    ActiveChangeKind, ActiveChangeRecord, ActiveChangeSource, record_active_change,
+};
 use crate::dashboard_core::{
     OperatorRoutingControlView, OperatorRoutingSummary, build_operator_routing_summary,
 };
+use crate::routing_ir::{UpstreamPin, UpstreamPinScope};
 use crate::runtime_identity::ProviderEndpointKey;
 use crate::runtime_store::{ProviderManualEligibility, ProviderPolicySnapshot};
 use crate::state::{
@@ -53,6 +57,24 @@ pub enum OperatorRoutingCommand {
         endpoint_id: String,
         mode: OperatorEndpointMode,
     },
+    /// Sends requests to one endpoint ahead of load balancing; `session_key` limits the pin
+    /// to that session. With `failover` off, requests fail instead of leaving the endpoint.
+    PinUpstream {
+        provider_id: String,
+        endpoint_id: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        session_key: Option<String>,
+        #[serde(default = "default_pin_failover")]
+        failover: bool,
+    },
+    ClearUpstreamPin {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        session_key: Option<String>,
+    },
+}
+
+fn default_pin_failover() -> bool {
+    true
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
@@ -61,6 +83,9 @@ pub struct OperatorRoutingMutationRequest {
     pub expected_control_revision: u64,
     pub expected_policy_revision: u64,
     pub command: OperatorRoutingCommand,
+    /// Which console issued the request; recorded in the active-change history.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<ActiveChangeSource>,
 }
 
 #[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
@@ -92,12 +117,17 @@ pub(super) async fn mutate_operator_routing(
     let template = graph.handshake_plan();
     let control = proxy.state.capture_routing_operator_control().await;
     let provider_policy = proxy.state.capture_provider_policy_snapshot().await;
+    let auto_active = proxy
+        .state
+        .auto_active_selection(proxy.service_name, route_graph_key)
+        .await;
     let current = summarize_routing(
         view,
         &template,
         route_graph_key,
         &control,
         provider_policy.as_ref(),
+        auto_active.as_ref().map(|selection| &selection.target),
         proxy.service_name,
     )?;
 
@@ -176,6 +206,48 @@ pub(super) async fn mutate_operator_routing(
             }
             commit.status.into()
         }
+        OperatorRoutingCommand::PinUpstream {
+            provider_id,
+            endpoint_id,
+            session_key,
+            failover,
+        } => {
+            let target = candidate_key(proxy.service_name, &template, provider_id, endpoint_id)?;
+            let session_id = pin_session_id(proxy, session_key.as_deref()).await?;
+            proxy
+                .state
+                .compare_and_set_upstream_pin(
+                    proxy.service_name,
+                    route_graph_key,
+                    request.expected_control_revision,
+                    session_id.as_deref(),
+                    Some(UpstreamPin {
+                        target,
+                        failover: *failover,
+                        scope: UpstreamPinScope::Global,
+                    }),
+                )
+                .await
+                .map_err(|error| control_error(error.to_string()))?
+                .status
+                .into()
+        }
+        OperatorRoutingCommand::ClearUpstreamPin { session_key } => {
+            let session_id = pin_session_id(proxy, session_key.as_deref()).await?;
+            proxy
+                .state
+                .compare_and_set_upstream_pin(
+                    proxy.service_name,
+                    route_graph_key,
+                    request.expected_control_revision,
+                    session_id.as_deref(),
+                    None,
+                )
+                .await
+                .map_err(|error| control_error(error.to_string()))?
+                .status
+                .into()
+        }
     };
 
     let refreshed = current_routing_summary(proxy).await?;
@@ -184,12 +256,74 @@ pub(super) async fn mutate_operator_routing(
     } else {
         OperatorRoutingMutationStatus::Conflict
     };
+    if status == OperatorRoutingMutationStatus::Applied
+        && matches!(
+            request.command,
+            OperatorRoutingCommand::SetNewSessionPreference { .. }
+                | OperatorRoutingCommand::ClearNewSessionPreference
+        )
+    {
+        record_new_session_preference_change(
+            proxy.service_name,
+            &current,
+            &refreshed,
+            request.source.unwrap_or(ActiveChangeSource::Api),
+        );
+    }
     Ok(OperatorRoutingMutationResponse {
         status,
         routing: refreshed,
     })
 }
 
+/// Resolves an operator session key to the raw session id pins are stored under.
+async fn pin_session_id(
+    proxy: &ProxyService,
+    session_key: Option<&str>,
+) -> Result<Option<String>, ProxyControlError> {
+    let Some(session_key) = session_key.map(str::trim) else {
+        return Ok(None);
+    };
+    if session_key.is_empty() {
+        return Err(invalid_request_error("session_key is empty"));
+    }
+    let capture = proxy.operator_read_capture().await?;
+    capture
+        .local_sessions
+        .get(session_key)
+        .map(|session| Some(session.raw_session_id.clone()))
+        .ok_or_else(|| {
+            ProxyControlError::new(
+                StatusCode::NOT_FOUND,
+                "operator session key is not present in the current local read model",
+            )
+        })
+}
+
+fn record_new_session_preference_change(
+    service_name: &str,
+    before: &OperatorRoutingSummary,
+    after: &OperatorRoutingSummary,
+    source: ActiveChangeSource,
+) {
+    let label = |routing: &OperatorRoutingSummary| {
+        routing
+            .new_session_preference
+            .as_ref()
+            .map(|target| format!("{}/{}", target.provider_id, target.endpoint_id))
+    };
+    let record = ActiveChangeRecord::new(
+        service_name,
+        ActiveChangeKind::NewSessionPreference,
+        label(before),
+        label(after),
+        source,
+    );
+    if let Err(error) = record_active_change(&record) {
+        tracing::warn!("failed to record active-change history: {error}");
+    }
+}
+
 async fn current_routing_summary(
     proxy: &ProxyService,
 ) -> Result<OperatorRoutingSummary, ProxyControlError> {
@@ -203,12 +337,17 @@ async fn current_routing_summary(
     let template = graph.handshake_plan();
     let control = proxy.state.capture_routing_operator_control().await;
     let provider_policy = proxy.state.capture_provider_policy_snapshot().await;
+    let auto_active = proxy
+        .state
+        .auto_active_selection(proxy.service_name, graph.digest())
+        .await;
     summarize_routing(
         view,
         &template,
         graph.digest(),
         &control,
         provider_policy.as_ref(),
+        auto_active.as_ref().map(|selection| &selection.target),
         proxy.service_name,
     )
 }
@@ -219,6 +358,7 @@ fn summarize_routing(
     route_graph_key: &str,
     control: &RoutingOperatorControlSnapshot,
     provider_policy: &ProviderPolicySnapshot,
+    auto_active: Option<&ProviderEndpointKey>,
     service_name: &str,
 ) -> Result<OperatorRoutingSummary, ProxyControlError> {
     build_operator_routing_summary(
@@ -229,6 +369,9 @@ fn summarize_routing(
             control_revision: control.revision(),
             provider_policy_revision: provider_policy.policy_revision,
             new_session_preference: control.new_session_preference(service_name, route_graph_key),
+            auto_active,
+            upstream_pin: control.global_upstream_pin(service_name, route_graph_key),
+            session_upstream_pins: &control.session_upstream_pins(service_name, route_graph_key),
         },
     )
     .map_err(|error| control_error(format!("build routing summary failed: {error:#}")))
@@ -362,6 +505,7 @@ mod tests {
             expected_control_revision: routing.control_revision,
             expected_policy_revision: routing.provider_policy_revision,
             command,
+            source: None,
         }
     }
 
@@ -390,6 +534,15 @@ mod tests {
             &original_config,
             &proxy.config.capture().await.config()
         ));
+        assert!(
+            crate::active_history::read_recent_active_changes(usize::MAX, Some("codex"))
+                .iter()
+                .any(
+                    |record| record.kind == ActiveChangeKind::NewSessionPreference
+                        && record.source == ActiveChangeSource::Api
+                        && record.new.as_deref() == Some("input/fast")
+                )
+        );
 
         let repeated = mutate_operator_routing(&proxy, request(&applied.routing, command))
             .await
@@ -397,6 +550,44 @@ mod tests {
         assert_eq!(repeated.status, OperatorRoutingMutationStatus::Unchanged);
     }
 
+    #[tokio::test]
+    async fn global_upstream_pin_round_trips_through_the_routing_summary() {
+        let (proxy, routing) = proxy_and_routing().await;
+        let command: OperatorRoutingCommand = serde_json::from_value(serde_json::json!({
+            "command": "pin_upstream",
+            "provider_id": "ciii",
+            "endpoint_id": "default",
+        }))
+        .expect("pin command");
+
+        let applied = mutate_operator_routing(&proxy, request(&routing, command))
+            .await
+            .expect("pin upstream");
+        assert_eq!(applied.status, OperatorRoutingMutationStatus::Applied);
+        assert_eq!(
+            applied.routing.upstream_pin,
+            Some(crate::dashboard_core::OperatorUpstreamPinSummary {
+                provider_id: "ciii".to_string(),
+                endpoint_id: "default".to_string(),
+                failover: true,
+                session_key: None,
+            })
+        );
+        assert!(applied.routing.new_session_preference.is_none());
+
+        let cleared = mutate_operator_routing(
+            &proxy,
+            request(
+                &applied.routing,
+                OperatorRoutingCommand::ClearUpstreamPin { session_key: None },
+            ),
+        )
+        .await
+        .expect("clear pin");
+        assert_eq!(cleared.status, OperatorRoutingMutationStatus::Applied);
+        assert!(cleared.routing.upstream_pin.is_none());
+    }
+
     #[tokio::test]
     async fn stale_route_graph_key_conflicts_without_mutation() {
         let (proxy, routing) = proxy_and_routing().await;
@@ -2,14 +2,15 @@ use axum::Json;
 use axum::extract::Query;
 
 use crate::routing_explain::{RoutingExplainResponse, build_routing_explain_response_with_request};
-use crate::routing_ir::RouteRequestContext;
+use crate::routing_ir::{RoutePricing, RouteRequestContext};
 
 use super::ProxyService;
 use super::admin_api_error::{AdminApiHttpError, AdminApiResult};
 use super::route_affinity::apply_session_route_affinity_for_template;
 use super::route_target_selection::{
-    apply_auth_resolution_to_runtime, apply_concurrency_snapshots_to_runtime,
-    apply_routing_operator_control_to_runtime,
+    AutoActiveUpdate, apply_auth_resolution_to_runtime, apply_concurrency_snapshots_to_runtime,
+    apply_primary_sticky_holds_to_runtime, apply_routing_operator_control_to_runtime,
+    apply_upstream_pin_to_runtime,
 };
 
 #[derive(serde::Deserialize)]
@@ -32,6 +33,29 @@ impl RequestLedgerChainQuery {
     }
 }
 
+#[derive(serde::Deserialize)]
+pub(super) struct RoutingExplainQuery {
+    model: Option<String>,
+    service_tier: Option<String>,
+    reasoning_effort: Option<String>,
+    path: Option<String>,
+    method: Option<String>,
+    session: Option<String>,
+}
+
+impl RoutingExplainQuery {
+    fn request_context(&self) -> RouteRequestContext {
+        RouteRequestContext {
+            model: clean_filter(self.model.clone()),
+            service_tier: clean_filter(self.service_tier.clone()),
+            reasoning_effort: clean_filter(self.reasoning_effort.clone()),
+            path: clean_filter(self.path.clone()),
+            method: clean_filter(self.method.clone()),
+            ..RouteRequestContext::default()
+        }
+    }
+}
+
 fn clean_filter(value: Option<String>) -> Option<String> {
     value
         .map(|value| value.trim().to_string())
@@ -80,6 +104,13 @@ pub(super) async fn routing_explain_for_proxy(
             )
         },
     )?;
+    runtime.apply_provider_schedules(&template, crate::logging::now_ms());
+    apply_primary_sticky_holds_to_runtime(proxy, &template, &mut runtime).await;
+    if template.prefer_cheapest {
+        runtime.set_pricing(Some(RoutePricing::new(
+            crate::pricing::effective_pricing_catalog_snapshot().shared_catalog(),
+        )));
+    }
     apply_concurrency_snapshots_to_runtime(
         proxy,
         &template,
@@ -94,8 +125,17 @@ pub(super) async fn routing_explain_for_proxy(
     )
     .await;
     apply_routing_operator_control_to_runtime(
+        proxy,
+        &template,
+        routing_control_graph_key.as_str(),
+        &mut runtime,
+        AutoActiveUpdate::Preview,
+    )
+    .await;
+    apply_upstream_pin_to_runtime(
         proxy,
         routing_control_graph_key.as_str(),
+        session_id.as_deref(),
         &mut runtime,
     )
     .await;
@@ -109,6 +149,19 @@ pub(super) async fn routing_explain_for_proxy(
     ))
 }
 
+pub(super) async fn get_operator_routing_explain(
+    proxy: ProxyService,
+    Query(query): Query<RoutingExplainQuery>,
+) -> AdminApiResult<RoutingExplainResponse> {
+    routing_explain_for_proxy(
+        &proxy,
+        query.request_context(),
+        clean_filter(query.session.clone()),
+    )
+    .await
+    .map(Json)
+}
+
 pub(super) async fn get_request_ledger_chain(
     proxy: ProxyService,
     Query(query): Query<RequestLedgerChainQuery>,
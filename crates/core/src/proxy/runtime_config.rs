@@ -23,7 +23,10 @@ use crate::credentials::{
 use crate::logging::log_control_trace_event;
 use crate::pricing::{CapturedModelPriceCatalog, try_capture_operator_model_price_catalog};
 use crate::provider_catalog::ProviderCatalogSnapshot;
-use crate::routing_ir::{CompiledRouteGraph, RoutePlanTemplate, RouteRequestContext};
+use crate::provider_tls::ProviderTlsClients;
+use crate::routing_ir::{
+    CapturedRouteCandidate, CompiledRouteGraph, RoutePlanTemplate, RouteRequestContext,
+};
 use crate::runtime_identity::{RuntimeUpstreamIdentity, diff_runtime_upstream_identities};
 use crate::runtime_store::{ProviderPolicySnapshot, RuntimeStore};
 use crate::service_target::LocalCredentialRefreshStatus;
@@ -33,6 +36,8 @@ use crate::usage_providers::{
     usage_provider_source_revision_from_disk,
 };
 
+use super::attempt_request::UpstreamUserAgent;
+use super::control_plane_service::service_route_config;
 use super::settings_control::{
     OperatorDefaultProfileMutationStatus, RuntimeDefaultProfileControlSnapshot,
     RuntimeDefaultProfileControls, RuntimeDefaultProfileMutationError,
@@ -100,6 +105,7 @@ struct PreparedRuntimeSnapshot {
     operator_pricing_catalog: Arc<CapturedModelPriceCatalog>,
     credential_generation: Arc<CredentialGeneration>,
     usage_provider_catalog: Arc<UsageProviderCatalog>,
+    provider_tls_clients: Arc<ProviderTlsClients>,
     default_profile_controls: RuntimeDefaultProfileControls,
     digest_seed: Sha256,
     loaded_at_ms: u64,
@@ -223,6 +229,7 @@ impl PreparedRuntimeSnapshot {
                 named_catalog_revision.as_str(),
             )?,
         };
+        let provider_tls_clients = Arc::new(ProviderTlsClients::build(&source_config.0)?);
         let config = Arc::new(redacted_runtime_config(source_config.0.clone()));
         let codex_route_graph = Arc::new(
             CompiledRouteGraph::compile("codex", &config.codex)
@@ -259,6 +266,7 @@ impl PreparedRuntimeSnapshot {
             operator_pricing_catalog,
             credential_generation,
             usage_provider_catalog,
+            provider_tls_clients,
             default_profile_controls,
             digest_seed,
             loaded_at_ms: now_ms(),
@@ -302,6 +310,7 @@ impl PreparedRuntimeSnapshot {
             operator_pricing_catalog: Arc::clone(&snapshot.operator_pricing_catalog),
             credential_generation,
             usage_provider_catalog: Arc::clone(&snapshot.usage_provider_catalog),
+            provider_tls_clients: Arc::clone(&snapshot.provider_tls_clients),
             default_profile_controls: snapshot.default_profile_controls.clone(),
             digest_seed,
             loaded_at_ms: now_ms(),
@@ -327,6 +336,7 @@ impl PreparedRuntimeSnapshot {
             operator_pricing_catalog: self.operator_pricing_catalog,
             credential_generation: self.credential_generation,
             usage_provider_catalog: self.usage_provider_catalog,
+            provider_tls_clients: self.provider_tls_clients,
             provider_policy,
             default_profile_controls: self.default_profile_controls,
             revision,
@@ -347,6 +357,7 @@ pub(super) struct RuntimeSnapshot {
     operator_pricing_catalog: Arc<CapturedModelPriceCatalog>,
     credential_generation: Arc<CredentialGeneration>,
     usage_provider_catalog: Arc<UsageProviderCatalog>,
+    provider_tls_clients: Arc<ProviderTlsClients>,
     provider_policy: Arc<ProviderPolicySnapshot>,
     default_profile_controls: RuntimeDefaultProfileControls,
     revision: u64,
@@ -383,6 +394,56 @@ impl CapturedRoutePlan {
         Arc::clone(&self.snapshot)
     }
 
+    pub(super) fn upstream_user_agent(&self, target: &CapturedRouteCandidate) -> UpstreamUserAgent {
+        let config = self.snapshot.config.as_ref();
+        let provider_endpoint = target.provider_endpoint();
+        let provider_user_agent =
+            service_route_config(config, provider_endpoint.service_name.as_str())
+                .providers
+                .get(provider_endpoint.provider_id.as_str())
+                .and_then(|provider| provider.user_agent.as_deref());
+        UpstreamUserAgent {
+            configured: provider_user_agent
+                .or(config.http.user_agent.as_deref())
+                .and_then(|value| axum::http::HeaderValue::from_str(value).ok()),
+        }
+    }
+
+    /// Client headers the candidate's provider drops via `strip_request_headers`.
+    pub(super) fn upstream_strip_headers(
+        &self,
+        target: &CapturedRouteCandidate,
+    ) -> Vec<axum::http::HeaderName> {
+        let provider_endpoint = target.provider_endpoint();
+        service_route_config(
+            self.snapshot.config.as_ref(),
+            provider_endpoint.service_name.as_str(),
+        )
+        .providers
+        .get(provider_endpoint.provider_id.as_str())
+        .map(|provider| {
+            provider
+                .strip_request_headers
+                .iter()
+                .filter_map(|name| axum::http::HeaderName::from_bytes(name.trim().as_bytes()).ok())
+                .collect()
+        })
+        .unwrap_or_default()
+    }
+
+    /// Client for the candidate's provider when it configures `[tls]` or an `address_family`
+    /// restricts resolution; `None` means the shared client.
+    pub(super) fn upstream_client(
+        &self,
+        target: &CapturedRouteCandidate,
+    ) -> Option<&reqwest::Client> {
+        let provider_endpoint = target.provider_endpoint();
+        self.snapshot.provider_tls_clients.client(
+            provider_endpoint.service_name.as_str(),
+            provider_endpoint.provider_id.as_str(),
+        )
+    }
+
     pub(super) fn is_empty(&self) -> bool {
         self.template.candidates.is_empty()
     }
@@ -447,6 +508,7 @@ impl RuntimeSnapshot {
             operator_pricing_catalog: Arc::clone(&self.operator_pricing_catalog),
             credential_generation: Arc::clone(&self.credential_generation),
             usage_provider_catalog: Arc::clone(&self.usage_provider_catalog),
+            provider_tls_clients: Arc::clone(&self.provider_tls_clients),
             provider_policy,
             default_profile_controls: self.default_profile_controls.clone(),
             revision,
@@ -484,6 +546,7 @@ impl RuntimeSnapshot {
             operator_pricing_catalog,
             credential_generation: Arc::clone(&self.credential_generation),
             usage_provider_catalog: Arc::clone(&self.usage_provider_catalog),
+            provider_tls_clients: Arc::clone(&self.provider_tls_clients),
             provider_policy: Arc::clone(&self.provider_policy),
             default_profile_controls: self.default_profile_controls.clone(),
             revision,
@@ -518,6 +581,30 @@ impl RuntimeSnapshot {
         Arc::clone(&self.credential_generation)
     }
 
+    /// Client for a provider with its own `[tls]` or address-family settings; `None` means the
+    /// shared client.
+    pub(super) fn provider_client(
+        &self,
+        service_name: &str,
+        provider_id: &str,
+    ) -> Option<&reqwest::Client> {
+        self.provider_tls_clients.client(service_name, provider_id)
+    }
+
+    pub(super) fn provider_tls_clients(&self) -> Arc<ProviderTlsClients> {
+        Arc::clone(&self.provider_tls_clients)
+    }
+
+    /// `buffer_responses` for the provider, falling back to `stream.buffer_responses`.
+    pub(super) fn buffer_responses(&self, service_name: &str, provider_id: &str) -> bool {
+        let config = self.config.as_ref();
+        service_route_config(config, service_name)
+            .providers
+            .get(provider_id)
+            .and_then(|provider| provider.buffer_responses)
+            .unwrap_or(config.stream.buffer_responses)
+    }
+
     pub(super) fn usage_provider_catalog(&self) -> Arc<UsageProviderCatalog> {
         Arc::clone(&self.usage_provider_catalog)
     }
@@ -552,6 +639,7 @@ impl RuntimeSnapshot {
             operator_pricing_catalog: Arc::clone(&self.operator_pricing_catalog),
             credential_generation: Arc::clone(&self.credential_generation),
             usage_provider_catalog: Arc::clone(&self.usage_provider_catalog),
+            provider_tls_clients: Arc::clone(&self.provider_tls_clients),
             provider_policy: Arc::clone(&self.provider_policy),
             default_profile_controls,
             revision,
@@ -693,6 +781,7 @@ impl RuntimeConfig {
             .map_err(anyhow::Error::msg)
             .context("load initial operator pricing catalog")?;
         let source_stamp = runtime_source_stamp_from_disk_sync();
+        let max_recent = initial_config.dashboard.max_recent;
         let prepared = PreparedRuntimeSnapshot::build(
             initial_config,
             operator_pricing_catalog,
@@ -704,7 +793,7 @@ impl RuntimeConfig {
                 .reconcile_runtime_upstream_identities(&prepared.candidate_identities()?, now_ms())
                 .context("reconcile initial runtime upstream identities")?,
         );
-        let state = ProxyState::new_with_runtime_store(runtime_store)?;
+        let state = ProxyState::new_with_runtime_store_and_recent_max(runtime_store, max_recent)?;
         let initial = prepared.finish(provider_policy, 1);
         #[cfg(not(test))]
         let automatic_reload = RuntimeAutomaticReload::disk();
@@ -236,6 +236,7 @@ mod tests {
             preference_group: 0,
             stable_index: 0,
             concurrency: RouteCandidateConcurrency::default(),
+            schedule: None,
         }
     }
 
@@ -47,9 +47,15 @@ impl ProxyService {
         let credential_config = Arc::clone(&self.config);
         let automatic_reload_proxy = self.clone();
         let credential_shutdown_rx = shutdown_rx.clone();
+        let fallback_alert_shutdown_rx = shutdown_rx.clone();
+        let health_probe_shutdown_rx = shutdown_rx.clone();
+        let upstream_models_shutdown_rx = shutdown_rx.clone();
         tokio::spawn(async move {
             tokio::select! {
                 _ = credential_config.run_credential_refresh_driver(credential_shutdown_rx) => {}
+                _ = automatic_reload_proxy.run_fallback_alert_driver(fallback_alert_shutdown_rx) => {}
+                _ = automatic_reload_proxy.run_health_probe_driver(health_probe_shutdown_rx) => {}
+                _ = automatic_reload_proxy.run_upstream_models_driver(upstream_models_shutdown_rx) => {}
                 _ = automatic_reload_proxy.run_automatic_reload_driver(shutdown_rx) => {}
             }
         })
@@ -144,6 +150,7 @@ impl ProxyService {
         credential_sources: CredentialSourceCapabilities,
         spawn_cleanup_task: bool,
     ) -> anyhow::Result<Self> {
+        let adaptive_floor = config.lb.adaptive_concurrency.active_floor();
         let (runtime_config, state) = RuntimeConfig::new_with_runtime_store_and_credential_sources(
             config,
             runtime_store,
@@ -157,7 +164,9 @@ impl ProxyService {
             client,
             config: runtime_config,
             service_name,
-            concurrency_limiter: Arc::new(super::concurrency_limits::ConcurrencyLimiter::default()),
+            concurrency_limiter: Arc::new(
+                super::concurrency_limits::ConcurrencyLimiter::with_adaptive_floor(adaptive_floor),
+            ),
             filter: RequestFilter::new(),
             state,
             service_install_generation: None,
@@ -459,7 +468,7 @@ impl ProxyService {
     }
 }
 
-async fn wait_for_proxy_shutdown(shutdown_rx: &mut tokio::sync::watch::Receiver<bool>) {
+pub(super) async fn wait_for_proxy_shutdown(shutdown_rx: &mut tokio::sync::watch::Receiver<bool>) {
     loop {
         if *shutdown_rx.borrow() || shutdown_rx.changed().await.is_err() {
             return;
@@ -526,6 +535,11 @@ fn persisted_routing_spec_from_config_for_service(
         scheduling_preset: routing.scheduling_preset,
         fallback_ttl_ms: routing.fallback_ttl_ms,
         reprobe_preferred_after_ms: routing.reprobe_preferred_after_ms,
+        auto_active_by_health: routing.auto_active_by_health,
+        prefer_cheapest: routing.prefer_cheapest,
+        fallback_alert_threshold_pct: routing.fallback_alert_threshold_pct,
+        fallback_alert_window_secs: routing.fallback_alert_window_secs,
+        primary_sticky_ms: routing.primary_sticky_ms,
         routes: routing.routes.clone(),
         policy: entry_node
             .map(|node| node.strategy)
@@ -294,6 +294,7 @@ async fn validate_target_available(
             format!("captured runtime credential binding is invalid: {error}"),
         )
     })?;
+    runtime.apply_provider_schedules(template, crate::logging::now_ms());
     super::route_target_selection::apply_concurrency_snapshots_to_runtime(
         proxy,
         template,
@@ -6,7 +6,7 @@ use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
 use axum::body::{Body, Bytes};
-use axum::http::{HeaderMap, Method, Response, StatusCode};
+use axum::http::{HeaderMap, Method, Response, StatusCode, header};
 use futures_util::{Stream, StreamExt, stream};
 use tracing::{info, warn};
 
@@ -28,6 +28,7 @@ use super::attempt_health::{
     settle_or_record_and_penalize_attempt_target, settle_or_record_attempt_failure,
     settle_or_record_attempt_success,
 };
+use super::attempt_transport::upstream_response_body_max_bytes;
 use super::classify::{
     ProtocolFailureDisposition, UPSTREAM_OVERLOADED_CLASS, UPSTREAM_RATE_LIMITED_CLASS,
     class_is_health_neutral, classify_observed_upstream_response,
@@ -44,6 +45,7 @@ use super::request_observer::{RequestObserver, RequestPublication, RequestPublic
 use super::request_preparation::{SharedRouteStateImpact, StreamTerminalPolicy};
 use super::retry::response_penalty_cooldown_secs;
 use super::runtime_config::RuntimeConfig;
+use super::stream_buffer::collapse_buffered_sse;
 use crate::routing_ir::CapturedRouteCandidate;
 use crate::state::SessionRouteAffinitySuccess;
 
@@ -139,6 +141,8 @@ struct StreamUsageState {
     body_complete: bool,
     warned_non_success: bool,
     first_chunk_ms: Option<u64>,
+    /// Request-relative time the first body byte was handed to the client; the request's TTFB.
+    first_forwarded_ms: Option<u64>,
     usage: Option<crate::usage::UsageMetrics>,
     reported_model: Option<String>,
     service_tier: Option<String>,
@@ -454,7 +458,7 @@ impl StreamFinalize {
             return None;
         }
         let usage = guard.usage.clone();
-        let ttfb_ms = guard.first_chunk_ms;
+        let ttfb_ms = guard.first_forwarded_ms;
         let service_tier = ServiceTierLog {
             actual: guard.service_tier.clone(),
             ..self.service_tier.clone()
@@ -564,6 +568,7 @@ impl StreamFinalize {
         let cooldown_backoff = self.cooldown_backoff;
         let observer = RequestObserver::from_parts(
             state.clone(),
+            self.runtime_config.clone(),
             self.service_name.clone(),
             self.method.clone(),
             self.path.clone(),
@@ -589,6 +594,7 @@ impl StreamFinalize {
         publication.reported_model = reported_model;
         publication.codex_bridge = self.codex_bridge.clone();
         publication.usage = usage;
+        publication.upstream_request_id = super::headers::upstream_request_id(&self.resp_headers);
         publication.route_decision = self.route_decision.clone();
         publication.retry = self.retry.clone();
         if let Some(retry) = publication.retry.as_mut()
@@ -1156,6 +1162,7 @@ impl StreamForwardState {
         };
         if guard.first_chunk_ms.is_none() {
             guard.first_chunk_ms = Some(finalize.upstream_start.elapsed().as_millis() as u64);
+            guard.first_forwarded_ms = Some(finalize.start.elapsed().as_millis() as u64);
         }
 
         guard.response_body_len = guard.response_body_len.saturating_add(chunk.len());
@@ -1400,6 +1407,13 @@ pub(super) async fn build_sse_success_response(
         .unwrap_or(true);
     let gate_success_terminal =
         status.is_success() && stream_terminal_policy == StreamTerminalPolicy::ProtocolEvent;
+    let buffer_responses = status.is_success()
+        && is_sse
+        && proxy
+            .config
+            .capture()
+            .await
+            .buffer_responses(proxy.service_name, target.provider_id());
 
     let finalize = StreamFinalize {
         service_name: service_name.clone(),
@@ -1443,15 +1457,8 @@ pub(super) async fn build_sse_success_response(
     };
 
     if is_user_turn && is_codex_service {
-        let state = proxy.state.clone();
-        let client = proxy.client.clone();
-        let provider_catalog = proxy.config.capture().await.usage_provider_catalog();
-        super::providers_api::enqueue_provider_balance_probe(
-            client,
-            state,
-            target.clone(),
-            provider_catalog,
-        );
+        let runtime = proxy.config.capture().await;
+        super::providers_api::enqueue_provider_balance_probe(proxy, &runtime, target.clone());
     }
 
     let stream_state = StreamForwardState {
@@ -1473,6 +1480,19 @@ pub(super) async fn build_sse_success_response(
         |state| async move { state.next_body_item().await },
     );
 
+    if buffer_responses {
+        // Drained on its own task so the whole upstream read does not nest inside the
+        // request handler's poll stack.
+        let buffering = tokio::spawn(buffer_sse_response(
+            Box::pin(stream),
+            status,
+            resp_headers_filtered,
+        ));
+        return buffering.await.unwrap_or_else(|error| {
+            buffered_stream_failure(format!("buffering upstream stream aborted: {error}"))
+        });
+    }
+
     let body = Body::from_stream(stream);
     let mut builder = Response::builder().status(status);
     for (name, value) in resp_headers_filtered.iter() {
@@ -1484,6 +1504,70 @@ pub(super) async fn build_sse_success_response(
     builder.body(body).unwrap()
 }
 
+/// Drains the forwarded stream (so accounting and finalization run unchanged) and answers
+/// with the collapsed non-streamed body; see `stream.buffer_responses`.
+async fn buffer_sse_response(
+    mut stream: Pin<Box<impl Stream<Item = Result<Bytes, io::Error>>>>,
+    status: StatusCode,
+    resp_headers_filtered: HeaderMap,
+) -> Response<Body> {
+    let max = upstream_response_body_max_bytes();
+    let mut buffered = Vec::new();
+    while let Some(item) = stream.next().await {
+        let chunk = match item {
+            Ok(chunk) => chunk,
+            Err(error) => {
+                return buffered_stream_failure(format!(
+                    "upstream stream failed while buffering: {error}"
+                ));
+            }
+        };
+        let next_len = buffered.len().saturating_add(chunk.len());
+        if next_len > max {
+            return buffered_stream_failure(format!(
+                "buffered upstream stream too large: observed={next_len} limit={max}"
+            ));
+        }
+        buffered.extend_from_slice(&chunk);
+    }
+
+    let mut builder = Response::builder();
+    let Some(completion) = collapse_buffered_sse(&buffered) else {
+        warn!("buffered upstream stream has no recognizable terminal event; forwarding it as-is");
+        builder = builder.status(status);
+        for (name, value) in resp_headers_filtered.iter() {
+            builder = builder.header(name, value);
+        }
+        if resp_headers_filtered.get("content-type").is_none() {
+            builder = builder.header("content-type", "text/event-stream");
+        }
+        return builder.body(Body::from(buffered)).unwrap();
+    };
+    builder = builder.status(if completion.failed {
+        StatusCode::BAD_GATEWAY
+    } else {
+        status
+    });
+    for (name, value) in resp_headers_filtered.iter() {
+        if name != header::CONTENT_TYPE && name != header::CONTENT_LENGTH {
+            builder = builder.header(name, value);
+        }
+    }
+    builder
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(completion.body.to_string()))
+        .unwrap()
+}
+
+fn buffered_stream_failure(message: String) -> Response<Body> {
+    warn!("{message}");
+    Response::builder()
+        .status(StatusCode::BAD_GATEWAY)
+        .header(header::CONTENT_TYPE, "text/plain; charset=utf-8")
+        .body(Body::from(message))
+        .unwrap()
+}
+
 pub(super) struct SseSuccessMeta {
     pub(super) status: StatusCode,
     pub(super) resp_headers: HeaderMap,
@@ -0,0 +1,403 @@
+use std::collections::BTreeMap;
+
+use serde_json::{Map, Value, json};
+
+use crate::sse::visit_sse_json_values;
+
+/// A fully buffered upstream event stream collapsed into the equivalent non-streamed body.
+#[derive(Debug, PartialEq)]
+pub(super) struct BufferedCompletion {
+    pub(super) body: Value,
+    /// The stream ended with a protocol-level failure (`response.failed` or an `error` event).
+    pub(super) failed: bool,
+}
+
+/// Collapses a buffered SSE body into the JSON a non-streamed request would have returned.
+///
+/// Understands OpenAI Responses, Anthropic Messages and Chat Completions streams; returns
+/// `None` when the stream never reached a recognizable terminal event.
+pub(super) fn collapse_buffered_sse(body: &[u8]) -> Option<BufferedCompletion> {
+    let mut responses = ResponsesAccumulator::default();
+    let mut messages = MessagesAccumulator::default();
+    let mut chat = ChatAccumulator::default();
+    visit_sse_json_values(body, |value| {
+        let event_type = value.get("type").and_then(Value::as_str).unwrap_or("");
+        if event_type.starts_with("response.") {
+            responses.push(event_type, value);
+        } else if value.get("object").and_then(Value::as_str) == Some("chat.completion.chunk") {
+            chat.push(value);
+        } else {
+            messages.push(event_type, value);
+        }
+    });
+    responses
+        .finish()
+        .or_else(|| messages.finish())
+        .or_else(|| chat.finish())
+}
+
+#[derive(Default)]
+struct ResponsesAccumulator {
+    terminal: Option<(Value, bool)>,
+    output_items: BTreeMap<u64, Value>,
+}
+
+impl ResponsesAccumulator {
+    fn push(&mut self, event_type: &str, value: &Value) {
+        match event_type {
+            "response.output_item.done" => {
+                if let Some(item) = value.get("item") {
+                    let index = value
+                        .get("output_index")
+                        .and_then(Value::as_u64)
+                        .unwrap_or(self.output_items.len() as u64);
+                    self.output_items.insert(index, item.clone());
+                }
+            }
+            "response.completed" | "response.incomplete" | "response.failed" => {
+                if let Some(response) = value.get("response") {
+                    self.terminal = Some((response.clone(), event_type == "response.failed"));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn finish(self) -> Option<BufferedCompletion> {
+        let (mut body, failed) = self.terminal?;
+        // Some relays send a terminal response without `output`; rebuild it from the items.
+        let output_missing = body
+            .get("output")
+            .and_then(Value::as_array)
+            .is_none_or(Vec::is_empty);
+        if output_missing
+            && !self.output_items.is_empty()
+            && let Some(object) = body.as_object_mut()
+        {
+            object.insert(
+                "output".to_string(),
+                Value::Array(self.output_items.into_values().collect()),
+            );
+        }
+        Some(BufferedCompletion { body, failed })
+    }
+}
+
+#[derive(Default)]
+struct MessagesAccumulator {
+    message: Option<Value>,
+    blocks: BTreeMap<u64, Value>,
+    partial_json: BTreeMap<u64, String>,
+    stopped: bool,
+    error: Option<Value>,
+}
+
+impl MessagesAccumulator {
+    fn push(&mut self, event_type: &str, value: &Value) {
+        match event_type {
+            "message_start" => self.message = value.get("message").cloned(),
+            "content_block_start" => {
+                if let (Some(index), Some(block)) = (block_index(value), value.get("content_block"))
+                {
+                    self.blocks.insert(index, block.clone());
+                }
+            }
+            "content_block_delta" => {
+                let (Some(index), Some(delta)) = (block_index(value), value.get("delta")) else {
+                    return;
+                };
+                if delta.get("type").and_then(Value::as_str) == Some("input_json_delta") {
+                    if let Some(partial) = delta.get("partial_json").and_then(Value::as_str) {
+                        self.partial_json
+                            .entry(index)
+                            .or_default()
+                            .push_str(partial);
+                    }
+                    return;
+                }
+                let Some(block) = self.blocks.get_mut(&index).and_then(Value::as_object_mut) else {
+                    return;
+                };
+                for (delta_field, block_field, append) in [
+                    ("text", "text", true),
+                    ("thinking", "thinking", true),
+                    ("signature", "signature", false),
+                ] {
+                    if let Some(piece) = delta.get(delta_field).and_then(Value::as_str) {
+                        let slot = block
+                            .entry(block_field)
+                            .or_insert_with(|| Value::String(String::new()));
+                        match slot {
+                            Value::String(existing) if append => existing.push_str(piece),
+                            _ => *slot = Value::String(piece.to_string()),
+                        }
+                    }
+                }
+            }
+            "message_delta" => {
+                let Some(message) = self.message.as_mut().and_then(Value::as_object_mut) else {
+                    return;
+                };
+                if let Some(delta) = value.get("delta").and_then(Value::as_object) {
+                    for (key, field) in delta {
+                        message.insert(key.clone(), field.clone());
+                    }
+                }
+                if let Some(usage) = value.get("usage").and_then(Value::as_object) {
+                    let target = message
+                        .entry("usage")
+                        .or_insert_with(|| Value::Object(Map::new()));
+                    if let Some(target) = target.as_object_mut() {
+                        for (key, field) in usage {
+                            if !field.is_null() {
+                                target.insert(key.clone(), field.clone());
+                            }
+                        }
+                    }
+                }
+            }
+            "message_stop" => self.stopped = true,
+            "error" => self.error = Some(value.clone()),
+            _ => {}
+        }
+    }
+
+    fn finish(mut self) -> Option<BufferedCompletion> {
+        if let Some(error) = self.error {
+            return Some(BufferedCompletion {
+                body: error,
+                failed: true,
+            });
+        }
+        if !self.stopped {
+            return None;
+        }
+        let mut message = self.message?;
+        for (index, partial) in std::mem::take(&mut self.partial_json) {
+            let input = serde_json::from_str(&partial).unwrap_or(Value::String(partial));
+            if let Some(block) = self.blocks.get_mut(&index).and_then(Value::as_object_mut) {
+                block.insert("input".to_string(), input);
+            }
+        }
+        message.as_object_mut()?.insert(
+            "content".to_string(),
+            Value::Array(self.blocks.into_values().collect()),
+        );
+        Some(BufferedCompletion {
+            body: message,
+            failed: false,
+        })
+    }
+}
+
+fn block_index(value: &Value) -> Option<u64> {
+    value.get("index").and_then(Value::as_u64)
+}
+
+#[derive(Default)]
+struct ChatAccumulator {
+    head: Option<Value>,
+    choices: BTreeMap<u64, ChatChoice>,
+    usage: Option<Value>,
+    finished: bool,
+}
+
+#[derive(Default)]
+struct ChatChoice {
+    role: Option<String>,
+    content: String,
+    tool_calls: BTreeMap<u64, (Value, String)>,
+    finish_reason: Option<Value>,
+}
+
+impl ChatAccumulator {
+    fn push(&mut self, value: &Value) {
+        if self.head.is_none() {
+            self.head = Some(value.clone());
+        }
+        if let Some(usage) = value.get("usage").filter(|usage| !usage.is_null()) {
+            self.usage = Some(usage.clone());
+        }
+        for choice in value
+            .get("choices")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            let index = choice.get("index").and_then(Value::as_u64).unwrap_or(0);
+            let entry = self.choices.entry(index).or_default();
+            if let Some(reason) = choice
+                .get("finish_reason")
+                .filter(|reason| !reason.is_null())
+            {
+                entry.finish_reason = Some(reason.clone());
+                self.finished = true;
+            }
+            let Some(delta) = choice.get("delta") else {
+                continue;
+            };
+            if let Some(role) = delta.get("role").and_then(Value::as_str) {
+                entry.role = Some(role.to_string());
+            }
+            if let Some(content) = delta.get("content").and_then(Value::as_str) {
+                entry.content.push_str(content);
+            }
+            for call in delta
+                .get("tool_calls")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+            {
+                let call_index = call.get("index").and_then(Value::as_u64).unwrap_or(0);
+                let (head, arguments) = entry
+                    .tool_calls
+                    .entry(call_index)
+                    .or_insert_with(|| (json!({"type": "function"}), String::new()));
+                for key in ["id", "type"] {
+                    if let Some(field) = call.get(key) {
+                        head[key] = field.clone();
+                    }
+                }
+                if let Some(name) = call.pointer("/function/name") {
+                    head["function"]["name"] = name.clone();
+                }
+                if let Some(piece) = call.pointer("/function/arguments").and_then(Value::as_str) {
+                    arguments.push_str(piece);
+                }
+            }
+        }
+    }
+
+    fn finish(self) -> Option<BufferedCompletion> {
+        if !self.finished {
+            return None;
+        }
+        let head = self.head?;
+        let choices = self
+            .choices
+            .into_iter()
+            .map(|(index, choice)| {
+                let mut message = json!({
+                    "role": choice.role.unwrap_or_else(|| "assistant".to_string()),
+                    "content": choice.content,
+                });
+                if !choice.tool_calls.is_empty() {
+                    message["tool_calls"] = Value::Array(
+                        choice
+                            .tool_calls
+                            .into_values()
+                            .map(|(mut call, arguments)| {
+                                call["function"]["arguments"] = Value::String(arguments);
+                                call
+                            })
+                            .collect(),
+                    );
+                }
+                json!({
+                    "index": index,
+                    "message": message,
+                    "finish_reason": choice.finish_reason.unwrap_or(Value::Null),
+                })
+            })
+            .collect::<Vec<_>>();
+        let mut body = json!({
+            "id": head.get("id").cloned().unwrap_or(Value::Null),
+            "object": "chat.completion",
+            "created": head.get("created").cloned().unwrap_or(Value::Null),
+            "model": head.get("model").cloned().unwrap_or(Value::Null),
+            "choices": choices,
+        });
+        if let Some(usage) = self.usage {
+            body["usage"] = usage;
+        }
+        Some(BufferedCompletion {
+            body,
+            failed: false,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn responses_stream_collapses_to_the_terminal_response() {
+        let body = concat!(
+            "event: response.created\n",
+            "data: {\"type\":\"response.created\",\"response\":{\"id\":\"resp_1\",\"status\":\"in_progress\"}}\n\n",
+            "event: response.output_item.done\n",
+            "data: {\"type\":\"response.output_item.done\",\"output_index\":0,\"item\":{\"type\":\"message\",\"content\":[{\"type\":\"output_text\",\"text\":\"hi\"}]}}\n\n",
+            "event: response.completed\n",
+            "data: {\"type\":\"response.completed\",\"response\":{\"id\":\"resp_1\",\"status\":\"completed\",\"output\":[],\"usage\":{\"input_tokens\":3,\"output_tokens\":1,\"total_tokens\":4}}}\n\n",
+        );
+
+        let collapsed = collapse_buffered_sse(body.as_bytes()).expect("terminal response");
+
+        assert!(!collapsed.failed);
+        assert_eq!(collapsed.body["status"], "completed");
+        assert_eq!(collapsed.body["usage"]["total_tokens"], 4);
+        assert_eq!(collapsed.body["output"][0]["content"][0]["text"], "hi");
+    }
+
+    #[test]
+    fn messages_stream_collapses_blocks_and_final_usage() {
+        let body = concat!(
+            "event: message_start\n",
+            "data: {\"type\":\"message_start\",\"message\":{\"id\":\"msg_1\",\"role\":\"assistant\",\"content\":[],\"stop_reason\":null,\"usage\":{\"input_tokens\":7,\"output_tokens\":1}}}\n\n",
+            "event: content_block_start\n",
+            "data: {\"type\":\"content_block_start\",\"index\":0,\"content_block\":{\"type\":\"text\",\"text\":\"\"}}\n\n",
+            "event: content_block_delta\n",
+            "data: {\"type\":\"content_block_delta\",\"index\":0,\"delta\":{\"type\":\"text_delta\",\"text\":\"Hel\"}}\n\n",
+            "event: content_block_delta\n",
+            "data: {\"type\":\"content_block_delta\",\"index\":0,\"delta\":{\"type\":\"text_delta\",\"text\":\"lo\"}}\n\n",
+            "event: content_block_start\n",
+            "data: {\"type\":\"content_block_start\",\"index\":1,\"content_block\":{\"type\":\"tool_use\",\"id\":\"tu_1\",\"name\":\"ls\",\"input\":{}}}\n\n",
+            "event: content_block_delta\n",
+            "data: {\"type\":\"content_block_delta\",\"index\":1,\"delta\":{\"type\":\"input_json_delta\",\"partial_json\":\"{\\\"path\\\":\"}}\n\n",
+            "event: content_block_delta\n",
+            "data: {\"type\":\"content_block_delta\",\"index\":1,\"delta\":{\"type\":\"input_json_delta\",\"partial_json\":\"\\\".\\\"}\"}}\n\n",
+            "event: message_delta\n",
+            "data: {\"type\":\"message_delta\",\"delta\":{\"stop_reason\":\"tool_use\",\"stop_sequence\":null},\"usage\":{\"output_tokens\":12}}\n\n",
+            "event: message_stop\n",
+            "data: {\"type\":\"message_stop\"}\n\n",
+        );
+
+        let collapsed = collapse_buffered_sse(body.as_bytes()).expect("terminal message");
+
+        assert!(!collapsed.failed);
+        assert_eq!(collapsed.body["stop_reason"], "tool_use");
+        assert_eq!(collapsed.body["content"][0]["text"], "Hello");
+        assert_eq!(collapsed.body["content"][1]["input"]["path"], ".");
+        assert_eq!(collapsed.body["usage"]["input_tokens"], 7);
+        assert_eq!(collapsed.body["usage"]["output_tokens"], 12);
+    }
+
+    #[test]
+    fn chat_completion_chunks_collapse_into_one_completion() {
+        let body = concat!(
+            "data: {\"id\":\"c1\",\"object\":\"chat.completion.chunk\",\"created\":1,\"model\":\"m\",\"choices\":[{\"index\":0,\"delta\":{\"role\":\"assistant\",\"content\":\"Hi\"}}]}\n\n",
+            "data: {\"id\":\"c1\",\"object\":\"chat.completion.chunk\",\"created\":1,\"model\":\"m\",\"choices\":[{\"index\":0,\"delta\":{\"content\":\" there\"},\"finish_reason\":\"stop\"}]}\n\n",
+            "data: {\"id\":\"c1\",\"object\":\"chat.completion.chunk\",\"created\":1,\"model\":\"m\",\"choices\":[],\"usage\":{\"prompt_tokens\":2,\"completion_tokens\":2,\"total_tokens\":4}}\n\n",
+            "data: [DONE]\n\n",
+        );
+
+        let collapsed = collapse_buffered_sse(body.as_bytes()).expect("finished completion");
+
+        assert_eq!(collapsed.body["object"], "chat.completion");
+        assert_eq!(
+            collapsed.body["choices"][0]["message"]["content"],
+            "Hi there"
+        );
+        assert_eq!(collapsed.body["choices"][0]["finish_reason"], "stop");
+        assert_eq!(collapsed.body["usage"]["total_tokens"], 4);
+    }
+
+    #[test]
+    fn truncated_stream_is_not_collapsed() {
+        let body =
+            "event: response.created\ndata: {\"type\":\"response.created\",\"response\":{}}\n\n";
+
+        assert_eq!(collapse_buffered_sse(body.as_bytes()), None);
+    }
+}
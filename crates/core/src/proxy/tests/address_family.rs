@@ -0,0 +1,92 @@
+use super::harness::{
+    post_responses_json, single_provider_config, spawn_test_proxy, spawn_test_upstream,
+};
+use super::*;
+use crate::config::{AddressFamily, HttpConfig};
+
+fn localhost_provider_config(
+    port: u16,
+    address_family: AddressFamily,
+    provider_family: Option<AddressFamily>,
+) -> HelperConfig {
+    let mut config = HelperConfig {
+        http: HttpConfig {
+            address_family,
+            ..HttpConfig::default()
+        },
+        ..single_provider_config(format!("http://localhost:{port}/v1"))
+    };
+    config
+        .codex
+        .providers
+        .get_mut("primary")
+        .expect("primary provider")
+        .address_family = provider_family;
+    config
+}
+
+#[tokio::test]
+async fn address_family_limits_which_resolved_addresses_are_dialed() {
+    let hits = Arc::new(AtomicUsize::new(0));
+    let hits_for_route = hits.clone();
+    // The test upstream only listens on 127.0.0.1.
+    let upstream = spawn_test_upstream(axum::Router::new().route(
+        "/v1/responses",
+        post(move || {
+            let hits = hits_for_route.clone();
+            async move {
+                hits.fetch_add(1, Ordering::SeqCst);
+                (StatusCode::OK, Json(serde_json::json!({ "ok": true })))
+            }
+        }),
+    ));
+    let port = upstream.addr.port();
+    let client = reqwest::Client::new();
+
+    let proxy = spawn_test_proxy(localhost_provider_config(port, AddressFamily::V4, None));
+    let resp = post_responses_json(&client, &proxy, r#"{"input":"hi"}"#).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(hits.load(Ordering::SeqCst), 1);
+
+    // Whether localhost has no IPv6 address or nothing listens on ::1, v6 never reaches it.
+    let proxy = spawn_test_proxy(localhost_provider_config(port, AddressFamily::V6, None));
+    let resp = post_responses_json(&client, &proxy, r#"{"input":"hi"}"#).await;
+    assert_eq!(resp.status(), StatusCode::BAD_GATEWAY);
+    assert_eq!(hits.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn provider_address_family_overrides_the_global_setting() {
+    let hits = Arc::new(AtomicUsize::new(0));
+    let hits_for_route = hits.clone();
+    let upstream = spawn_test_upstream(axum::Router::new().route(
+        "/v1/responses",
+        post(move || {
+            let hits = hits_for_route.clone();
+            async move {
+                hits.fetch_add(1, Ordering::SeqCst);
+                (StatusCode::OK, Json(serde_json::json!({ "ok": true })))
+            }
+        }),
+    ));
+    let port = upstream.addr.port();
+    let client = reqwest::Client::new();
+
+    let proxy = spawn_test_proxy(localhost_provider_config(
+        port,
+        AddressFamily::V6,
+        Some(AddressFamily::V4),
+    ));
+    let resp = post_responses_json(&client, &proxy, r#"{"input":"hi"}"#).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(hits.load(Ordering::SeqCst), 1);
+
+    let proxy = spawn_test_proxy(localhost_provider_config(
+        port,
+        AddressFamily::V4,
+        Some(AddressFamily::V6),
+    ));
+    let resp = post_responses_json(&client, &proxy, r#"{"input":"hi"}"#).await;
+    assert_eq!(resp.status(), StatusCode::BAD_GATEWAY);
+    assert_eq!(hits.load(Ordering::SeqCst), 1);
+}
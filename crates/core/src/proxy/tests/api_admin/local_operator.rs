@@ -215,6 +215,7 @@ async fn observe_idle_session(proxy: &ProxyService, session_id: &str) {
                 retry: None,
                 ttfb_ms: Some(2),
                 streaming: false,
+                upstream_request_id: None,
             })
             .await
     );
@@ -259,6 +260,7 @@ fn routing_mutation_request(
         expected_control_revision: routing.control_revision,
         expected_policy_revision: routing.provider_policy_revision,
         command,
+        source: None,
     }
 }
 
@@ -28,6 +28,8 @@ async fn proxy_control_plane_exposes_only_get_and_head_routes() {
         "/__codex_helper/api/v1/request-ledger/recent",
         "/__codex_helper/api/v1/request-ledger/summary",
         "/__codex_helper/api/v1/request-ledger/chain",
+        "/__codex_helper/api/v1/events",
+        "/__codex_helper/api/v1/operator/routing-explain",
         "/__codex_helper/api/v1/control-trace",
         "/__codex_helper/api/v1/retry/config",
         "/__codex_helper/api/v1/pricing/catalog",
@@ -119,6 +121,8 @@ async fn control_plane_read_surface_matches_canonical_allowlist() {
     let canonical_paths = [
         "/__codex_helper/api/v1/operator/read-model",
         "/__codex_helper/api/v1/request-ledger/chain",
+        "/__codex_helper/api/v1/events",
+        "/__codex_helper/api/v1/operator/routing-explain",
     ];
     let removed_paths = [
         "/__codex_helper/api/v1/capabilities",
@@ -88,6 +88,7 @@ async fn finish_request(proxy: &ProxyService, id: u64, ended_at_ms: u64) {
                 retry: None,
                 ttfb_ms: Some(2),
                 streaming: false,
+                upstream_request_id: None,
             })
             .await
     );
@@ -183,7 +184,7 @@ async fn operator_read_model_uses_the_runtime_recent_request_retention_limit() {
         .await
         .expect("build retained operator read model");
     let data = model.data.expect("ready operator read data");
-    let expected = REQUEST_COUNT.min(crate::state::recent_finished_max());
+    let expected = REQUEST_COUNT.min(proxy.state.recent_finished_max());
     assert_eq!(data.recent_requests.len(), expected);
     assert_eq!(data.summary.counts.recent_requests, expected);
 }
@@ -24,6 +24,7 @@ fn price_reload_terminal(id: u64, ended_at_ms: u64) -> FinishRequestParams {
         retry: None,
         ttfb_ms: Some(4),
         streaming: false,
+        upstream_request_id: None,
     }
 }
 
@@ -42,6 +43,7 @@ async fn finish_chain_request(state: &crate::state::ProxyState, id: u64, ended_a
                 retry: None,
                 ttfb_ms: None,
                 streaming: false,
+                upstream_request_id: None,
             })
             .await
     );
@@ -150,6 +152,7 @@ async fn operator_read_model_and_request_chain_project_committed_runtime_store_t
                 retry: None,
                 ttfb_ms: Some(5),
                 streaming: false,
+                upstream_request_id: None,
             })
             .await
     );
@@ -1,5 +1,8 @@
 use super::*;
 use crate::config::ProviderEndpointConfig;
+use crate::proxy::tests::harness::{
+    post_responses_json, proxy_service, spawn_proxy_service, spawn_test_upstream,
+};
 
 fn provider_endpoint(base_url: String, supported_model: &str) -> ProviderEndpointConfig {
     ProviderEndpointConfig {
@@ -72,6 +75,7 @@ async fn proxy_routing_explain_returns_selected_route_and_structured_skip_reason
             crate::endpoint_health::CooldownBackoff {
                 factor: 1,
                 max_secs: 0,
+                probe_back_success_threshold: 1,
             },
         )
         .await;
@@ -135,6 +139,7 @@ async fn proxy_routing_explain_returns_selected_route_and_structured_skip_reason
         Some("codex/new/modern")
     );
     assert_eq!(explain["affinity_policy"].as_str(), Some("fallback_sticky"));
+    assert_eq!(explain["selection_reason"].as_str(), Some("auto"));
     assert_eq!(
         explain["selected_route"]["preference_group"].as_u64(),
         Some(1)
@@ -286,6 +291,7 @@ async fn proxy_routing_explain_uses_session_affinity() {
         explain["selected_route"]["endpoint_id"].as_str(),
         Some("modern")
     );
+    assert_eq!(explain["selection_reason"].as_str(), Some("affinity"));
 }
 
 #[tokio::test]
@@ -405,3 +411,393 @@ async fn proxy_routing_explain_uses_provider_endpoint_runtime_health_for_routes(
         Some("cooldown")
     );
 }
+
+#[tokio::test]
+async fn operator_routing_explain_endpoint_reports_manual_pin_over_http() {
+    let _env_lock = env_lock().await;
+    let temp_dir = make_temp_test_dir();
+    let mut scoped = ScopedEnv::default();
+    unsafe {
+        scoped.set_path("CODEX_HELPER_HOME", temp_dir.as_path());
+    }
+
+    let cfg = HelperConfig {
+        codex: ServiceRouteConfig {
+            providers: std::collections::BTreeMap::from([
+                (
+                    "old".to_string(),
+                    ProviderConfig {
+                        endpoints: std::collections::BTreeMap::from([(
+                            "legacy".to_string(),
+                            provider_endpoint("http://127.0.0.1:9/v1".to_string(), "gpt-5"),
+                        )]),
+                        ..ProviderConfig::default()
+                    },
+                ),
+                (
+                    "new".to_string(),
+                    ProviderConfig {
+                        endpoints: std::collections::BTreeMap::from([(
+                            "modern".to_string(),
+                            provider_endpoint("http://127.0.0.1:10/v1".to_string(), "gpt-5"),
+                        )]),
+                        ..ProviderConfig::default()
+                    },
+                ),
+            ]),
+            routing: Some(RouteGraphConfig::manual_sticky(
+                "new".to_string(),
+                vec!["old".to_string(), "new".to_string()],
+            )),
+            ..ServiceRouteConfig::default()
+        },
+        ..HelperConfig::default()
+    };
+    let proxy = ProxyService::new(Client::new(), Arc::new(cfg), "codex");
+    let app = crate::proxy::router(proxy);
+
+    let mut request = Request::builder()
+        .uri("/__codex_helper/api/v1/operator/routing-explain?model=gpt-5&reasoning_effort=high&session=sid-pin")
+        .body(Body::empty())
+        .expect("build routing explain request");
+    request
+        .extensions_mut()
+        .insert(ConnectInfo(std::net::SocketAddr::from((
+            [127, 0, 0, 1],
+            42_111,
+        ))));
+    let response = app
+        .clone()
+        .oneshot(request)
+        .await
+        .expect("routing explain response");
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = to_bytes(response.into_body(), 256 * 1024)
+        .await
+        .expect("read routing explain response");
+    let explain: crate::routing_explain::RoutingExplainResponse =
+        serde_json::from_slice(&body).expect("decode routing explain response");
+
+    assert_eq!(explain.session_id.as_deref(), Some("sid-pin"));
+    assert_eq!(
+        explain.request_context.reasoning_effort.as_deref(),
+        Some("high")
+    );
+    assert_eq!(
+        explain.selection_reason,
+        Some(crate::routing_explain::RoutingExplainSelectionReason::ManualPin)
+    );
+    let selected = explain.selected_route.expect("selected route");
+    assert_eq!(selected.provider_endpoint_key, "codex/new/modern");
+    assert_eq!(selected.upstream_base_url, "http://127.0.0.1:10/v1");
+}
+
+#[tokio::test]
+async fn operator_routing_explain_reports_health_auto_active_selection() {
+    let _env_lock = env_lock().await;
+    let temp_dir = make_temp_test_dir();
+    let mut scoped = ScopedEnv::default();
+    unsafe {
+        scoped.set_path("CODEX_HELPER_HOME", temp_dir.as_path());
+    }
+
+    let mut routing =
+        RouteGraphConfig::ordered_failover(vec!["old".to_string(), "new".to_string()]);
+    routing.auto_active_by_health = true;
+    let cfg = HelperConfig {
+        codex: ServiceRouteConfig {
+            providers: std::collections::BTreeMap::from([
+                (
+                    "old".to_string(),
+                    ProviderConfig {
+                        endpoints: std::collections::BTreeMap::from([(
+                            "legacy".to_string(),
+                            provider_endpoint("http://127.0.0.1:9/v1".to_string(), "gpt-5"),
+                        )]),
+                        ..ProviderConfig::default()
+                    },
+                ),
+                (
+                    "new".to_string(),
+                    ProviderConfig {
+                        endpoints: std::collections::BTreeMap::from([(
+                            "modern".to_string(),
+                            provider_endpoint("http://127.0.0.1:10/v1".to_string(), "gpt-5"),
+                        )]),
+                        ..ProviderConfig::default()
+                    },
+                ),
+            ]),
+            routing: Some(routing),
+            ..ServiceRouteConfig::default()
+        },
+        ..HelperConfig::default()
+    };
+    let proxy = ProxyService::new(Client::new(), Arc::new(cfg), "codex");
+    let app = crate::proxy::router(proxy);
+
+    let mut request = Request::builder()
+        .uri("/__codex_helper/api/v1/operator/routing-explain?model=gpt-5")
+        .body(Body::empty())
+        .expect("build routing explain request");
+    request
+        .extensions_mut()
+        .insert(ConnectInfo(std::net::SocketAddr::from((
+            [127, 0, 0, 1],
+            42_111,
+        ))));
+    let response = app
+        .clone()
+        .oneshot(request)
+        .await
+        .expect("routing explain response");
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = to_bytes(response.into_body(), 256 * 1024)
+        .await
+        .expect("read routing explain response");
+    let explain: crate::routing_explain::RoutingExplainResponse =
+        serde_json::from_slice(&body).expect("decode routing explain response");
+
+    assert_eq!(
+        explain.selection_reason,
+        Some(crate::routing_explain::RoutingExplainSelectionReason::AutoActive)
+    );
+    assert_eq!(
+        explain
+            .selected_route
+            .expect("selected route")
+            .provider_endpoint_key,
+        "codex/old/legacy"
+    );
+    assert!(
+        crate::active_history::read_recent_active_changes(10, Some("codex")).is_empty(),
+        "routing explain previews the auto-active pick without recording it"
+    );
+}
+
+#[tokio::test]
+async fn operator_routing_explain_matches_the_request_path_under_prefer_cheapest() {
+    let _env_lock = env_lock().await;
+    let temp_dir = make_temp_test_dir();
+    let mut scoped = ScopedEnv::default();
+    unsafe {
+        scoped.set_path("CODEX_HELPER_HOME", temp_dir.as_path());
+    }
+    std::fs::write(
+        temp_dir.join("pricing_overrides.toml"),
+        r#"version = 2
+
+[providers.pricey.models.gpt-5]
+input_per_1m_usd = "2"
+output_per_1m_usd = "8"
+
+[providers.cheap.models.gpt-5]
+input_per_1m_usd = "1"
+output_per_1m_usd = "4"
+"#,
+    )
+    .expect("write pricing overrides");
+    crate::pricing::refresh_effective_pricing_catalog();
+
+    let upstream = |name: &'static str| {
+        spawn_test_upstream(axum::Router::new().route(
+            "/v1/responses",
+            post(
+                move || async move { Json(serde_json::json!({"id": name, "status": "completed"})) },
+            ),
+        ))
+    };
+    let pricey = upstream("pricey");
+    let cheap = upstream("cheap");
+    let mut routing =
+        RouteGraphConfig::round_robin(vec!["pricey".to_string(), "cheap".to_string()]);
+    routing.prefer_cheapest = true;
+    let cfg = HelperConfig {
+        codex: ServiceRouteConfig {
+            providers: std::collections::BTreeMap::from([
+                (
+                    "pricey".to_string(),
+                    ProviderConfig {
+                        base_url: Some(pricey.base_url()),
+                        ..ProviderConfig::default()
+                    },
+                ),
+                (
+                    "cheap".to_string(),
+                    ProviderConfig {
+                        base_url: Some(cheap.base_url()),
+                        ..ProviderConfig::default()
+                    },
+                ),
+            ]),
+            routing: Some(routing),
+            ..ServiceRouteConfig::default()
+        },
+        ..HelperConfig::default()
+    };
+    let proxy = proxy_service(cfg);
+
+    let mut request = Request::builder()
+        .uri("/__codex_helper/api/v1/operator/routing-explain?model=gpt-5")
+        .body(Body::empty())
+        .expect("build routing explain request");
+    request
+        .extensions_mut()
+        .insert(ConnectInfo(std::net::SocketAddr::from((
+            [127, 0, 0, 1],
+            42_112,
+        ))));
+    let response = crate::proxy::router(proxy.clone())
+        .oneshot(request)
+        .await
+        .expect("routing explain response");
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = to_bytes(response.into_body(), 256 * 1024)
+        .await
+        .expect("read routing explain response");
+    let explain: crate::routing_explain::RoutingExplainResponse =
+        serde_json::from_slice(&body).expect("decode routing explain response");
+    assert_eq!(
+        explain.selection_reason,
+        Some(crate::routing_explain::RoutingExplainSelectionReason::Cheapest)
+    );
+    let selected = explain.selected_route.expect("selected route");
+    assert_eq!(selected.provider_id, "cheap");
+
+    let server = spawn_proxy_service(proxy);
+    let client = reqwest::Client::new();
+    for _ in 0..2 {
+        let response = post_responses_json(
+            &client,
+            &server,
+            r#"{"model":"gpt-5","input":"hi","stream":false}"#,
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response
+            .json::<serde_json::Value>()
+            .await
+            .expect("decode upstream response");
+        assert_eq!(body["id"], selected.provider_id.as_str());
+    }
+
+    std::fs::remove_file(temp_dir.join("pricing_overrides.toml")).expect("remove overrides");
+    crate::pricing::refresh_effective_pricing_catalog();
+}
+
+#[tokio::test]
+async fn operator_routing_explain_reports_session_upstream_pin() {
+    let _env_lock = env_lock().await;
+    let temp_dir = make_temp_test_dir();
+    let mut scoped = ScopedEnv::default();
+    unsafe {
+        scoped.set_path("CODEX_HELPER_HOME", temp_dir.as_path());
+    }
+
+    let cfg = HelperConfig {
+        codex: ServiceRouteConfig {
+            providers: std::collections::BTreeMap::from([
+                (
+                    "old".to_string(),
+                    ProviderConfig {
+                        base_url: Some("http://127.0.0.1:9/v1".to_string()),
+                        ..ProviderConfig::default()
+                    },
+                ),
+                (
+                    "new".to_string(),
+                    ProviderConfig {
+                        base_url: Some("http://127.0.0.1:10/v1".to_string()),
+                        ..ProviderConfig::default()
+                    },
+                ),
+            ]),
+            routing: Some(RouteGraphConfig::ordered_failover(vec![
+                "old".to_string(),
+                "new".to_string(),
+            ])),
+            ..ServiceRouteConfig::default()
+        },
+        ..HelperConfig::default()
+    };
+    let proxy = proxy_service(cfg);
+    let route_graph_key = proxy
+        .config
+        .capture()
+        .await
+        .route_graph("codex")
+        .expect("codex route graph")
+        .digest()
+        .to_string();
+    let revision = proxy
+        .state
+        .capture_routing_operator_control()
+        .await
+        .revision();
+    proxy
+        .state
+        .compare_and_set_upstream_pin(
+            "codex",
+            route_graph_key.as_str(),
+            revision,
+            Some("sid-pinned"),
+            Some(crate::routing_ir::UpstreamPin {
+                target: crate::runtime_identity::ProviderEndpointKey::new(
+                    "codex", "new", "default",
+                ),
+                failover: false,
+                scope: crate::routing_ir::UpstreamPinScope::Session,
+            }),
+        )
+        .await
+        .expect("pin session");
+
+    let app = crate::proxy::router(proxy);
+    let explain = |query: &'static str| {
+        let app = app.clone();
+        async move {
+            let mut request = Request::builder()
+                .uri(format!(
+                    "/__codex_helper/api/v1/operator/routing-explain?{query}"
+                ))
+                .body(Body::empty())
+                .expect("build routing explain request");
+            request
+                .extensions_mut()
+                .insert(ConnectInfo(std::net::SocketAddr::from((
+                    [127, 0, 0, 1],
+                    42_113,
+                ))));
+            let response = app
+                .oneshot(request)
+                .await
+                .expect("routing explain response");
+            assert_eq!(response.status(), StatusCode::OK);
+            let body = to_bytes(response.into_body(), 256 * 1024)
+                .await
+                .expect("read routing explain response");
+            serde_json::from_slice::<crate::routing_explain::RoutingExplainResponse>(&body)
+                .expect("decode routing explain response")
+        }
+    };
+
+    let pinned = explain("model=gpt-5&session=sid-pinned").await;
+    assert_eq!(
+        pinned.selection_reason,
+        Some(crate::routing_explain::RoutingExplainSelectionReason::UpstreamPin)
+    );
+    assert_eq!(
+        pinned.selected_route.expect("selected route").provider_id,
+        "new"
+    );
+
+    let unpinned = explain("model=gpt-5&session=sid-other").await;
+    assert_eq!(
+        unpinned.selection_reason,
+        Some(crate::routing_explain::RoutingExplainSelectionReason::Auto)
+    );
+    assert_eq!(
+        unpinned.selected_route.expect("selected route").provider_id,
+        "old"
+    );
+}
@@ -265,12 +265,14 @@ fn direct_terminal_payload(
             session_id: Some(DIRECT_CRASH_SESSION_ID.to_string()),
             session_identity_source: None,
             client_name: None,
+            tenant: None,
             client_addr: None,
             cwd: None,
             model: Some("gpt-5".to_string()),
             reasoning_effort: None,
             service_tier: None,
             provider_id: Some("test".to_string()),
+            body_hash: None,
             route_decision: None,
             usage: Some(usage),
             cost,
@@ -287,6 +289,7 @@ fn direct_terminal_payload(
             ttfb_ms: Some(10),
             streaming: false,
             ended_at_ms: terminal_at_unix_ms,
+            upstream_request_id: None,
         },
         winning_attempt_id: Some(winning_attempt_id),
         runtime_revision: 1,
@@ -1,5 +1,8 @@
 use super::*;
 use crate::dashboard_core::{OperatorReadModel, OperatorReadStatus};
+use crate::proxy::tests::harness::{
+    TestProxyServer, find_finished_request, proxy_service, spawn_proxy_service,
+};
 
 fn two_provider_failover_config(
     first_provider: &str,
@@ -330,3 +333,388 @@ async fn proxy_operator_summary_reports_retry_profile_and_attempt_limits() {
 
     proxy_handle.abort();
 }
+
+#[tokio::test]
+async fn proxy_fast_retries_connection_reset_on_same_upstream_before_failover() {
+    use tokio::io::{AsyncReadExt as _, AsyncWriteExt as _};
+
+    async fn read_request(socket: &mut tokio::net::TcpStream) {
+        let mut request = Vec::new();
+        loop {
+            if let Some(index) = request.windows(4).position(|bytes| bytes == b"\r\n\r\n") {
+                let headers = String::from_utf8_lossy(&request[..index]).to_ascii_lowercase();
+                let content_length = headers
+                    .lines()
+                    .find_map(|line| line.strip_prefix("content-length:"))
+                    .and_then(|value| value.trim().parse::<usize>().ok())
+                    .unwrap_or(0);
+                if request.len() >= index + 4 + content_length {
+                    return;
+                }
+            }
+            let read = socket.read_buf(&mut request).await.expect("read request");
+            assert!(read > 0, "request ended before it was complete");
+        }
+    }
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind raw upstream");
+    let primary_addr = listener.local_addr().expect("primary addr");
+    let primary_connections = Arc::new(AtomicUsize::new(0));
+    let primary_connections_for_task = primary_connections.clone();
+    let primary_handle = tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.expect("accept first connection");
+        primary_connections_for_task.fetch_add(1, Ordering::SeqCst);
+        read_request(&mut socket).await;
+        // A zero linger turns the close into a TCP reset; it does not block the thread.
+        #[allow(deprecated)]
+        socket
+            .set_linger(Some(std::time::Duration::ZERO))
+            .expect("set linger");
+        drop(socket);
+
+        let (mut socket, _) = listener.accept().await.expect("accept retry connection");
+        primary_connections_for_task.fetch_add(1, Ordering::SeqCst);
+        read_request(&mut socket).await;
+        let body = r#"{"ok":true}"#;
+        let response = format!(
+            "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+            body.len()
+        );
+        socket
+            .write_all(response.as_bytes())
+            .await
+            .expect("write response");
+        socket.shutdown().await.expect("shutdown");
+    });
+
+    let backup_hits = Arc::new(AtomicUsize::new(0));
+    let backup_hits_for_route = backup_hits.clone();
+    let backup = axum::Router::new().route(
+        "/v1/responses",
+        post(move || async move {
+            backup_hits_for_route.fetch_add(1, Ordering::SeqCst);
+            (StatusCode::OK, Json(serde_json::json!({ "backup": true })))
+        }),
+    );
+    let (backup_addr, backup_handle) = spawn_axum_server(backup);
+
+    let mut retry = retry_config(
+        1,
+        "502",
+        vec!["upstream_transport_error".to_string()],
+        RetryStrategy::Failover,
+    );
+    retry.provider = Some(retry_layer_config(
+        2,
+        "502",
+        vec!["upstream_transport_error".to_string()],
+        RetryStrategy::Failover,
+    ));
+    let cfg = two_provider_failover_config("primary", primary_addr, "backup", backup_addr, retry);
+    let proxy_service = proxy_service(cfg);
+    let state = proxy_service.state_handle();
+    let proxy = spawn_proxy_service(proxy_service);
+
+    let resp = Client::new()
+        .post(proxy.responses_url())
+        .header("content-type", "application/json")
+        .body(r#"{"model":"gpt","input":"hi"}"#)
+        .send()
+        .await
+        .expect("send");
+
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(
+        resp.json::<serde_json::Value>().await.expect("json"),
+        serde_json::json!({ "ok": true })
+    );
+    assert_eq!(primary_connections.load(Ordering::SeqCst), 2);
+    assert_eq!(backup_hits.load(Ordering::SeqCst), 0);
+
+    let finished = find_finished_request(&state, 10, |request| request.path == "/v1/responses")
+        .await
+        .expect("finished request");
+    let attempts = &finished.retry.as_ref().expect("retry trace").route_attempts;
+    assert_eq!(attempts.len(), 2, "{attempts:?}");
+    assert_eq!(attempts[0].decision, "failed_transport");
+    assert!(attempts[0].fast_retry);
+    assert_eq!(attempts[0].cooldown_secs, None);
+    assert_eq!(attempts[1].decision, "completed");
+    assert!(!attempts[1].fast_retry);
+    assert_eq!(attempts[1].provider_id.as_deref(), Some("primary"));
+
+    proxy.handle.abort();
+    primary_handle.abort();
+    backup_handle.abort();
+}
+
+fn counting_upstream(
+    status: StatusCode,
+) -> (
+    Arc<AtomicUsize>,
+    std::net::SocketAddr,
+    tokio::task::JoinHandle<()>,
+) {
+    let hits = Arc::new(AtomicUsize::new(0));
+    let hits_for_route = hits.clone();
+    let app = axum::Router::new().route(
+        "/v1/responses",
+        post(move || async move {
+            hits_for_route.fetch_add(1, Ordering::SeqCst);
+            (
+                status,
+                Json(serde_json::json!({ "status": status.as_u16() })),
+            )
+        }),
+    );
+    let (addr, handle) = spawn_axum_server(app);
+    (hits, addr, handle)
+}
+
+fn three_provider_failover_config(
+    addrs: [std::net::SocketAddr; 3],
+    max_upstreams_tried: Option<u32>,
+) -> HelperConfig {
+    let names = ["first", "second", "third"];
+    let mut retry = retry_config(2, "502", Vec::new(), RetryStrategy::SameUpstream);
+    retry.provider = Some(retry_layer_config(
+        3,
+        "502",
+        Vec::new(),
+        RetryStrategy::Failover,
+    ));
+    retry.max_upstreams_tried = max_upstreams_tried;
+    HelperConfig {
+        codex: ServiceRouteConfig {
+            providers: names
+                .iter()
+                .zip(addrs)
+                .map(|(name, addr)| {
+                    (
+                        name.to_string(),
+                        ProviderConfig {
+                            base_url: Some(format!("http://{addr}/v1")),
+                            ..ProviderConfig::default()
+                        },
+                    )
+                })
+                .collect(),
+            routing: Some(RouteGraphConfig::ordered_failover(
+                names.iter().map(|name| name.to_string()).collect(),
+            )),
+            ..ServiceRouteConfig::default()
+        },
+        retry,
+        ..HelperConfig::default()
+    }
+}
+
+async fn post_and_finish(
+    cfg: HelperConfig,
+) -> (StatusCode, crate::state::FinishedRequest, TestProxyServer) {
+    let proxy_service = proxy_service(cfg);
+    let state = proxy_service.state_handle();
+    let proxy = spawn_proxy_service(proxy_service);
+    let resp = Client::new()
+        .post(proxy.responses_url())
+        .header("content-type", "application/json")
+        .body(r#"{"model":"gpt","input":"hi"}"#)
+        .send()
+        .await
+        .expect("send");
+    let status = resp.status();
+    let finished = find_finished_request(&state, 10, |request| request.path == "/v1/responses")
+        .await
+        .expect("finished request");
+    (status, finished, proxy)
+}
+
+#[tokio::test]
+async fn retry_exhausts_each_upstream_before_moving_to_the_next() {
+    let (first_hits, first_addr, first_handle) = counting_upstream(StatusCode::BAD_GATEWAY);
+    let (second_hits, second_addr, second_handle) = counting_upstream(StatusCode::BAD_GATEWAY);
+    let (third_hits, third_addr, third_handle) = counting_upstream(StatusCode::OK);
+
+    let (status, finished, proxy) = post_and_finish(three_provider_failover_config(
+        [first_addr, second_addr, third_addr],
+        None,
+    ))
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(first_hits.load(Ordering::SeqCst), 2);
+    assert_eq!(second_hits.load(Ordering::SeqCst), 2);
+    assert_eq!(third_hits.load(Ordering::SeqCst), 1);
+    let attempts = &finished.retry.as_ref().expect("retry trace").route_attempts;
+    let counters = attempts
+        .iter()
+        .map(|attempt| (attempt.upstream_attempt, attempt.upstreams_tried))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        counters,
+        vec![
+            (Some(1), Some(1)),
+            (Some(2), Some(1)),
+            (Some(1), Some(2)),
+            (Some(2), Some(2)),
+            (Some(1), Some(3)),
+        ]
+    );
+    assert!(
+        attempts
+            .iter()
+            .all(|attempt| attempt.max_upstreams_tried.is_none())
+    );
+
+    proxy.handle.abort();
+    first_handle.abort();
+    second_handle.abort();
+    third_handle.abort();
+}
+
+#[tokio::test]
+async fn retry_stops_after_max_upstreams_tried() {
+    let (first_hits, first_addr, first_handle) = counting_upstream(StatusCode::BAD_GATEWAY);
+    let (second_hits, second_addr, second_handle) = counting_upstream(StatusCode::BAD_GATEWAY);
+    let (third_hits, third_addr, third_handle) = counting_upstream(StatusCode::OK);
+
+    let (status, finished, proxy) = post_and_finish(three_provider_failover_config(
+        [first_addr, second_addr, third_addr],
+        Some(2),
+    ))
+    .await;
+
+    assert_eq!(status, StatusCode::BAD_GATEWAY);
+    assert_eq!(first_hits.load(Ordering::SeqCst), 2);
+    assert_eq!(second_hits.load(Ordering::SeqCst), 2);
+    assert_eq!(third_hits.load(Ordering::SeqCst), 0);
+    let retry = finished.retry.as_ref().expect("retry trace");
+    assert_eq!(retry.attempts, 4);
+    let last = retry.route_attempts.last().expect("last attempt");
+    assert_eq!(last.upstreams_tried, Some(2));
+    assert_eq!(last.max_upstreams_tried, Some(2));
+
+    proxy.handle.abort();
+    first_handle.abort();
+    second_handle.abort();
+    third_handle.abort();
+}
+
+/// Answers 502 to its first request and 200 afterwards: a single blip.
+fn blip_upstream() -> (
+    Arc<AtomicUsize>,
+    std::net::SocketAddr,
+    tokio::task::JoinHandle<()>,
+) {
+    let hits = Arc::new(AtomicUsize::new(0));
+    let hits_for_route = hits.clone();
+    let app = axum::Router::new().route(
+        "/v1/responses",
+        post(move || async move {
+            if hits_for_route.fetch_add(1, Ordering::SeqCst) == 0 {
+                StatusCode::BAD_GATEWAY
+            } else {
+                StatusCode::OK
+            }
+        }),
+    );
+    let (addr, handle) = spawn_axum_server(app);
+    (hits, addr, handle)
+}
+
+fn primary_sticky_config(
+    primary_addr: std::net::SocketAddr,
+    backup_addr: std::net::SocketAddr,
+    primary_sticky_ms: u64,
+) -> HelperConfig {
+    let mut retry = retry_config(1, "502", Vec::new(), RetryStrategy::SameUpstream);
+    retry.provider = Some(retry_layer_config(
+        2,
+        "502",
+        Vec::new(),
+        RetryStrategy::Failover,
+    ));
+    let mut cfg =
+        two_provider_failover_config("primary", primary_addr, "backup", backup_addr, retry);
+    if let Some(routing) = cfg.codex.routing.as_mut() {
+        routing.primary_sticky_ms = Some(primary_sticky_ms);
+    }
+    cfg
+}
+
+async fn post_responses_status(client: &Client, proxy: &TestProxyServer) -> StatusCode {
+    client
+        .post(proxy.responses_url())
+        .header("content-type", "application/json")
+        .body(r#"{"model":"gpt","input":"hi"}"#)
+        .send()
+        .await
+        .expect("send")
+        .status()
+}
+
+#[tokio::test]
+async fn primary_sticky_hold_keeps_traffic_on_the_backup_after_a_blip() {
+    let (primary_hits, primary_addr, primary_handle) = blip_upstream();
+    let (backup_hits, backup_addr, backup_handle) = counting_upstream(StatusCode::OK);
+    let proxy_service = proxy_service(primary_sticky_config(primary_addr, backup_addr, 60_000));
+    let state = proxy_service.state_handle();
+    let proxy = spawn_proxy_service(proxy_service);
+    let client = Client::new();
+
+    assert_eq!(post_responses_status(&client, &proxy).await, StatusCode::OK);
+    assert_eq!(primary_hits.load(Ordering::SeqCst), 1);
+    assert_eq!(backup_hits.load(Ordering::SeqCst), 1);
+
+    // The primary is healthy again (one failure is below the breaker threshold), but the hold
+    // keeps new requests on the backup.
+    for _ in 0..3 {
+        assert_eq!(post_responses_status(&client, &proxy).await, StatusCode::OK);
+    }
+    assert_eq!(primary_hits.load(Ordering::SeqCst), 1);
+    assert_eq!(backup_hits.load(Ordering::SeqCst), 4);
+
+    let holds = state
+        .primary_sticky_holds("codex", crate::logging::now_ms())
+        .await;
+    assert_eq!(
+        holds.keys().cloned().collect::<Vec<_>>(),
+        vec![crate::runtime_identity::ProviderEndpointKey::new(
+            "codex", "primary", "default"
+        )]
+    );
+
+    proxy.handle.abort();
+    primary_handle.abort();
+    backup_handle.abort();
+}
+
+#[tokio::test]
+async fn primary_sticky_hold_expires_and_traffic_returns_to_the_primary() {
+    let (primary_hits, primary_addr, primary_handle) = blip_upstream();
+    let (backup_hits, backup_addr, backup_handle) = counting_upstream(StatusCode::OK);
+    let proxy_service = proxy_service(primary_sticky_config(primary_addr, backup_addr, 300));
+    let state = proxy_service.state_handle();
+    let proxy = spawn_proxy_service(proxy_service);
+    let client = Client::new();
+
+    assert_eq!(post_responses_status(&client, &proxy).await, StatusCode::OK);
+    assert_eq!(backup_hits.load(Ordering::SeqCst), 1);
+
+    tokio::time::sleep(Duration::from_millis(400)).await;
+    assert!(
+        state
+            .primary_sticky_holds("codex", crate::logging::now_ms())
+            .await
+            .is_empty()
+    );
+    assert_eq!(post_responses_status(&client, &proxy).await, StatusCode::OK);
+    assert_eq!(primary_hits.load(Ordering::SeqCst), 2);
+    assert_eq!(backup_hits.load(Ordering::SeqCst), 1);
+
+    proxy.handle.abort();
+    primary_handle.abort();
+    backup_handle.abort();
+}
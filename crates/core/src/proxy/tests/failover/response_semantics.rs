@@ -2446,6 +2446,7 @@ async fn models_success_does_not_clear_existing_inference_failure_state() {
             crate::endpoint_health::CooldownBackoff {
                 factor: 1,
                 max_secs: 0,
+                probe_back_success_threshold: 1,
             },
         )
         .await;
@@ -2830,6 +2831,7 @@ async fn buffered_half_open_probe_singleflights_and_clears_on_success() {
             crate::endpoint_health::CooldownBackoff {
                 factor: 1,
                 max_secs: 0,
+                probe_back_success_threshold: 1,
             },
         )
         .await;
@@ -2967,6 +2969,7 @@ async fn failed_buffered_half_open_probe_is_not_repeated_in_the_same_epoch() {
             crate::endpoint_health::CooldownBackoff {
                 factor: 1,
                 max_secs: 0,
+                probe_back_success_threshold: 1,
             },
         )
         .await;
@@ -3104,6 +3107,7 @@ data: {\"type\":\"response.completed\",\"response\":{\"id\":\"resp-recovered\"}}
             crate::endpoint_health::CooldownBackoff {
                 factor: 1,
                 max_secs: 0,
+                probe_back_success_threshold: 1,
             },
         )
         .await;
@@ -3272,6 +3276,7 @@ data: {\"type\":\"response.failed\",\"response\":{\"id\":\"resp-failed-probe\",\
             crate::endpoint_health::CooldownBackoff {
                 factor: 1,
                 max_secs: 0,
+                probe_back_success_threshold: 1,
             },
         )
         .await;
@@ -3405,6 +3410,7 @@ async fn proxy_codex_stream_route_unavailable_returns_retryable_response_failed_
             crate::endpoint_health::CooldownBackoff {
                 factor: 1,
                 max_secs: 0,
+                probe_back_success_threshold: 1,
             },
         )
         .await;
@@ -3424,6 +3430,7 @@ async fn proxy_codex_stream_route_unavailable_returns_retryable_response_failed_
             crate::endpoint_health::CooldownBackoff {
                 factor: 1,
                 max_secs: 0,
+                probe_back_success_threshold: 1,
             },
         )
         .await;
@@ -3547,6 +3554,7 @@ async fn proxy_codex_body_stream_route_unavailable_returns_retryable_response_fa
             crate::endpoint_health::CooldownBackoff {
                 factor: 1,
                 max_secs: 0,
+                probe_back_success_threshold: 1,
             },
         )
         .await;
@@ -3881,6 +3889,7 @@ async fn proxy_codex_stream_mixed_upstream_failure_and_cooldown_reports_route_un
             crate::endpoint_health::CooldownBackoff {
                 factor: 1,
                 max_secs: 0,
+                probe_back_success_threshold: 1,
             },
         )
         .await;
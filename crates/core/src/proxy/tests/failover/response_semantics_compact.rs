@@ -3508,6 +3508,7 @@ async fn proxy_waits_short_affinity_cooldown_before_responses_compact_under_hard
             crate::endpoint_health::CooldownBackoff {
                 factor: 1,
                 max_secs: 0,
+                probe_back_success_threshold: 1,
             },
         )
         .await;
@@ -1415,6 +1415,7 @@ async fn responses_websocket_incomplete_half_open_probe_is_neutral_and_once_per_
             crate::endpoint_health::CooldownBackoff {
                 factor: 1,
                 max_secs: 0,
+                probe_back_success_threshold: 1,
             },
         )
         .await;
@@ -1824,6 +1825,7 @@ supports_websockets = true
             crate::endpoint_health::CooldownBackoff {
                 factor: 1,
                 max_secs: 0,
+                probe_back_success_threshold: 1,
             },
         )
         .await;
@@ -1934,6 +1936,7 @@ async fn responses_websocket_client_close_during_active_half_open_create_is_neut
             crate::endpoint_health::CooldownBackoff {
                 factor: 1,
                 max_secs: 0,
+                probe_back_success_threshold: 1,
             },
         )
         .await;
@@ -2456,6 +2459,7 @@ supports_websockets = true
             crate::endpoint_health::CooldownBackoff {
                 factor: 1,
                 max_secs: 0,
+                probe_back_success_threshold: 1,
             },
         )
         .await;
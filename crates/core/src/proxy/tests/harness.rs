@@ -91,6 +91,27 @@ pub(super) fn upstream_config(base_url: impl Into<String>) -> UpstreamConfig {
     }
 }
 
+/// A `codex` config with one `primary` provider at `base_url` and nothing else set; tests
+/// override the field they exercise on the result.
+pub(super) fn single_provider_config(base_url: impl Into<String>) -> HelperConfig {
+    HelperConfig {
+        codex: ServiceRouteConfig {
+            providers: std::collections::BTreeMap::from([(
+                "primary".to_string(),
+                ProviderConfig {
+                    base_url: Some(base_url.into()),
+                    ..ProviderConfig::default()
+                },
+            )]),
+            routing: Some(RouteGraphConfig::ordered_failover(vec![
+                "primary".to_string(),
+            ])),
+            ..ServiceRouteConfig::default()
+        },
+        ..HelperConfig::default()
+    }
+}
+
 pub(super) async fn post_responses_json(
     client: &reqwest::Client,
     proxy: &TestProxyServer,
@@ -0,0 +1,68 @@
+use super::harness::{
+    post_responses_json, single_provider_config, spawn_test_proxy, spawn_test_upstream,
+};
+use super::*;
+use crate::config::MaintenanceResponseConfig;
+
+fn maintenance_config(
+    base_url: String,
+    enabled: bool,
+    maintenance: Option<MaintenanceResponseConfig>,
+) -> HelperConfig {
+    let mut config = single_provider_config(base_url);
+    config
+        .codex
+        .providers
+        .get_mut("primary")
+        .expect("primary provider")
+        .enabled = enabled;
+    config.codex.maintenance = maintenance;
+    config
+}
+
+#[tokio::test]
+async fn maintenance_response_replaces_the_routing_error_when_every_provider_is_disabled() {
+    let hits = Arc::new(AtomicUsize::new(0));
+    let hits_for_route = hits.clone();
+    let upstream = spawn_test_upstream(axum::Router::new().route(
+        "/v1/responses",
+        post(move || {
+            let hits = hits_for_route.clone();
+            async move {
+                hits.fetch_add(1, Ordering::SeqCst);
+                (StatusCode::OK, Json(serde_json::json!({ "ok": true })))
+            }
+        }),
+    ));
+    let client = reqwest::Client::new();
+    let maintenance = MaintenanceResponseConfig {
+        status: None,
+        message: Some("relay upgrade until 18:00 UTC".to_string()),
+    };
+
+    let proxy = spawn_test_proxy(maintenance_config(
+        upstream.base_url(),
+        false,
+        Some(maintenance.clone()),
+    ));
+    let resp = post_responses_json(&client, &proxy, r#"{"input":"hi"}"#).await;
+    assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+    let body: serde_json::Value = resp.json().await.expect("maintenance json body");
+    assert_eq!(body["error"]["type"], "service_maintenance");
+    assert_eq!(body["error"]["message"], "relay upgrade until 18:00 UTC");
+
+    // Without the opt-in, a disabled service keeps failing as a routing error.
+    let proxy = spawn_test_proxy(maintenance_config(upstream.base_url(), false, None));
+    let resp = post_responses_json(&client, &proxy, r#"{"input":"hi"}"#).await;
+    assert_eq!(resp.status(), StatusCode::BAD_GATEWAY);
+
+    // An enabled provider is used as usual even with a maintenance reply configured.
+    let proxy = spawn_test_proxy(maintenance_config(
+        upstream.base_url(),
+        true,
+        Some(maintenance),
+    ));
+    let resp = post_responses_json(&client, &proxy, r#"{"input":"hi"}"#).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(hits.load(Ordering::SeqCst), 1);
+}
@@ -2,7 +2,6 @@ use std::collections::HashMap;
 use std::convert::Infallible;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use std::sync::OnceLock;
 use std::sync::atomic::{AtomicUsize, Ordering};
 
 use axum::Json;
@@ -43,12 +42,7 @@ fn spawn_axum_server(app: axum::Router) -> (std::net::SocketAddr, tokio::task::J
     (addr, handle)
 }
 
-async fn env_lock() -> tokio::sync::MutexGuard<'static, ()> {
-    static LOCK: OnceLock<tokio::sync::Mutex<()>> = OnceLock::new();
-    LOCK.get_or_init(|| tokio::sync::Mutex::new(()))
-        .lock()
-        .await
-}
+use crate::test_support::env_lock;
 
 #[derive(Default)]
 struct ScopedEnv {
@@ -189,10 +183,21 @@ async fn send_responses_json(
         .expect("request json")
 }
 
+mod address_family;
 mod api_admin;
 mod crash_recovery;
 mod failover;
 mod harness;
 mod http_debug;
+mod maintenance;
 mod openai_images_generation;
+mod request_quota;
+mod response_cache;
 mod routing_profiles;
+mod sse_passthrough;
+mod stream_buffer;
+mod ttfb;
+mod upstream_models;
+mod upstream_request_id;
+mod upstream_warmup;
+mod user_agent;
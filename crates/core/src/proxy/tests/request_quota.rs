@@ -0,0 +1,128 @@
+use super::harness::{
+    post_responses_json, single_provider_config, spawn_test_proxy, spawn_test_upstream,
+};
+use super::*;
+use crate::config::RequestQuotaConfig;
+
+#[tokio::test]
+async fn proxy_request_quota_returns_429_with_retry_after_without_forwarding() {
+    let hits = Arc::new(AtomicUsize::new(0));
+    let hits_for_route = hits.clone();
+    let upstream = spawn_test_upstream(axum::Router::new().route(
+        "/v1/responses",
+        post(move || {
+            let hits = hits_for_route.clone();
+            async move {
+                hits.fetch_add(1, Ordering::SeqCst);
+                (StatusCode::OK, Json(serde_json::json!({ "ok": true })))
+            }
+        }),
+    ));
+    let proxy = spawn_test_proxy(HelperConfig {
+        quota: RequestQuotaConfig {
+            window_secs: Some(3_600),
+            max_requests: Some(2),
+            max_output_tokens: None,
+            per_tenant: true,
+        },
+        ..single_provider_config(upstream.base_url())
+    });
+    let client = reqwest::Client::new();
+    let send_as = |tenant: &'static str| {
+        client
+            .post(proxy.responses_url())
+            .header("content-type", "application/json")
+            .header(crate::proxy::TENANT_HEADER, tenant)
+            .body(r#"{"input":"hi"}"#)
+            .send()
+    };
+
+    for _ in 0..2 {
+        let resp = send_as("alice").await.expect("send admitted request");
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+    let rejected = send_as("alice").await.expect("send over-quota request");
+    assert_eq!(rejected.status(), StatusCode::TOO_MANY_REQUESTS);
+    let retry_after = rejected
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<u64>().ok())
+        .expect("numeric Retry-After");
+    assert!((3_599..=3_600).contains(&retry_after), "{retry_after}");
+    let message = rejected.text().await.expect("rejection body");
+    assert!(message.contains("tenant `alice`"), "{message}");
+    assert_eq!(hits.load(Ordering::SeqCst), 2);
+
+    // Each tenant has its own budget, and untagged traffic shares one more.
+    let resp = send_as("bob").await.expect("send other tenant request");
+    assert_eq!(resp.status(), StatusCode::OK);
+    let resp = post_responses_json(&client, &proxy, r#"{"input":"hi"}"#).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(hits.load(Ordering::SeqCst), 4);
+}
+
+#[tokio::test]
+async fn proxy_request_quota_fails_over_to_a_provider_with_budget_left() {
+    let spawn_counting_upstream = || {
+        let hits = Arc::new(AtomicUsize::new(0));
+        let hits_for_route = hits.clone();
+        let upstream = spawn_test_upstream(axum::Router::new().route(
+            "/v1/responses",
+            post(move || {
+                let hits = hits_for_route.clone();
+                async move {
+                    hits.fetch_add(1, Ordering::SeqCst);
+                    (StatusCode::OK, Json(serde_json::json!({ "ok": true })))
+                }
+            }),
+        ));
+        (upstream, hits)
+    };
+    let (primary, primary_hits) = spawn_counting_upstream();
+    let (backup, backup_hits) = spawn_counting_upstream();
+    let provider = |base_url: String| ProviderConfig {
+        base_url: Some(base_url),
+        ..ProviderConfig::default()
+    };
+    let proxy = spawn_test_proxy(HelperConfig {
+        codex: ServiceRouteConfig {
+            providers: std::collections::BTreeMap::from([
+                ("primary".to_string(), provider(primary.base_url())),
+                ("backup".to_string(), provider(backup.base_url())),
+            ]),
+            routing: Some(RouteGraphConfig::ordered_failover(vec![
+                "primary".to_string(),
+                "backup".to_string(),
+            ])),
+            ..ServiceRouteConfig::default()
+        },
+        quota: RequestQuotaConfig {
+            window_secs: Some(3_600),
+            max_requests: Some(1),
+            max_output_tokens: None,
+            per_tenant: false,
+        },
+        ..HelperConfig::default()
+    });
+    let client = reqwest::Client::new();
+
+    for _ in 0..2 {
+        let resp = post_responses_json(&client, &proxy, r#"{"input":"hi"}"#).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+    assert_eq!(primary_hits.load(Ordering::SeqCst), 1);
+    assert_eq!(backup_hits.load(Ordering::SeqCst), 1);
+
+    let rejected = post_responses_json(&client, &proxy, r#"{"input":"hi"}"#).await;
+    assert_eq!(rejected.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert!(
+        rejected
+            .headers()
+            .contains_key(reqwest::header::RETRY_AFTER)
+    );
+    let message = rejected.text().await.expect("rejection body");
+    assert!(message.contains("provider `backup`"), "{message}");
+    assert_eq!(primary_hits.load(Ordering::SeqCst), 1);
+    assert_eq!(backup_hits.load(Ordering::SeqCst), 1);
+}
@@ -0,0 +1,275 @@
+use super::harness::{
+    TestUpstreamServer, proxy_service, single_provider_config, spawn_proxy_service,
+    spawn_test_upstream,
+};
+use super::*;
+use crate::config::{RequestQuotaConfig, ResponseCacheConfig};
+use crate::routing_ir::{UpstreamPin, UpstreamPinScope};
+use crate::runtime_identity::ProviderEndpointKey;
+
+fn models_counting_upstream() -> (TestUpstreamServer, Arc<AtomicUsize>) {
+    let hits = Arc::new(AtomicUsize::new(0));
+    let hits_for_route = hits.clone();
+    let upstream = spawn_test_upstream(axum::Router::new().route(
+        "/v1/models",
+        get(move || {
+            let hits_for_route = hits_for_route.clone();
+            async move {
+                let n = hits_for_route.fetch_add(1, Ordering::SeqCst) + 1;
+                Json(serde_json::json!({
+                    "object": "list",
+                    "data": [{"id": format!("model-{n}"), "object": "model"}],
+                }))
+            }
+        }),
+    ));
+    (upstream, hits)
+}
+
+fn models_cache_config(upstream: &TestUpstreamServer, models_ttl_ms: u64) -> HelperConfig {
+    HelperConfig {
+        cache: ResponseCacheConfig { models_ttl_ms },
+        ..single_provider_config(upstream.base_url())
+    }
+}
+
+async fn get_models(url: &str) -> (Option<String>, serde_json::Value) {
+    let response = Client::new()
+        .get(url)
+        .send()
+        .await
+        .expect("send models request");
+    assert_eq!(response.status(), StatusCode::OK);
+    let cache = response
+        .headers()
+        .get("x-codex-helper-cache")
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    let body = response.json().await.expect("models json");
+    (cache, body)
+}
+
+#[tokio::test]
+async fn models_requests_are_not_cached_by_default() {
+    let (upstream, hits) = models_counting_upstream();
+    let proxy = spawn_proxy_service(proxy_service(models_cache_config(&upstream, 0)));
+
+    let (first_cache, _) = get_models(&proxy.url("/v1/models")).await;
+    let (second_cache, _) = get_models(&proxy.url("/v1/models")).await;
+
+    assert_eq!(first_cache, None);
+    assert_eq!(second_cache, None);
+    assert_eq!(hits.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn models_cache_serves_hits_per_path_until_reload() {
+    let (upstream, hits) = models_counting_upstream();
+    let service = proxy_service(models_cache_config(&upstream, 60_000));
+    let proxy = spawn_proxy_service(service.clone());
+
+    let (first_cache, first_body) = get_models(&proxy.url("/v1/models")).await;
+    let (second_cache, second_body) = get_models(&proxy.url("/v1/models")).await;
+    assert_eq!(first_cache.as_deref(), Some("miss"));
+    assert_eq!(second_cache.as_deref(), Some("hit"));
+    assert_eq!(second_body, first_body);
+    assert_eq!(hits.load(Ordering::SeqCst), 1);
+
+    let (other_query_cache, _) = get_models(&proxy.url("/v1/models?client_version=2")).await;
+    assert_eq!(other_query_cache.as_deref(), Some("miss"));
+    assert_eq!(hits.load(Ordering::SeqCst), 2);
+
+    crate::proxy::control_plane_service::prune_runtime_observability_after_reload(&service).await;
+    let (after_reload_cache, _) = get_models(&proxy.url("/v1/models")).await;
+    assert_eq!(after_reload_cache.as_deref(), Some("miss"));
+    assert_eq!(hits.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn models_cache_entries_expire_after_ttl() {
+    let (upstream, hits) = models_counting_upstream();
+    let proxy = spawn_proxy_service(proxy_service(models_cache_config(&upstream, 100)));
+
+    let (first_cache, first_body) = get_models(&proxy.url("/v1/models")).await;
+    sleep(Duration::from_millis(250)).await;
+    let (second_cache, second_body) = get_models(&proxy.url("/v1/models")).await;
+
+    assert_eq!(first_cache.as_deref(), Some("miss"));
+    assert_eq!(second_cache.as_deref(), Some("miss"));
+    assert_ne!(second_body, first_body);
+    assert_eq!(hits.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn models_cache_is_keyed_by_the_selected_provider_endpoint() {
+    let (primary, primary_hits) = models_counting_upstream();
+    let (backup, backup_hits) = models_counting_upstream();
+    let mut config = models_cache_config(&primary, 60_000);
+    config.codex.providers.insert(
+        "backup".to_string(),
+        ProviderConfig {
+            base_url: Some(backup.base_url()),
+            ..ProviderConfig::default()
+        },
+    );
+    config.codex.routing = Some(RouteGraphConfig::ordered_failover(vec![
+        "primary".to_string(),
+        "backup".to_string(),
+    ]));
+    let service = proxy_service(config);
+    let proxy = spawn_proxy_service(service.clone());
+
+    let (first_cache, _) = get_models(&proxy.url("/v1/models")).await;
+    assert_eq!(first_cache.as_deref(), Some("miss"));
+    assert_eq!(primary_hits.load(Ordering::SeqCst), 1);
+
+    let route_graph_key = service
+        .config
+        .capture()
+        .await
+        .route_graph("codex")
+        .expect("codex route graph")
+        .digest()
+        .to_string();
+    let revision = service
+        .state
+        .capture_routing_operator_control()
+        .await
+        .revision();
+    service
+        .state
+        .compare_and_set_upstream_pin(
+            "codex",
+            route_graph_key.as_str(),
+            revision,
+            None,
+            Some(UpstreamPin {
+                target: ProviderEndpointKey::new("codex", "backup", "default"),
+                failover: false,
+                scope: UpstreamPinScope::Global,
+            }),
+        )
+        .await
+        .expect("pin backup");
+
+    let (pinned_cache, _) = get_models(&proxy.url("/v1/models")).await;
+    let (repeat_cache, _) = get_models(&proxy.url("/v1/models")).await;
+    assert_eq!(pinned_cache.as_deref(), Some("miss"));
+    assert_eq!(repeat_cache.as_deref(), Some("hit"));
+    assert_eq!(primary_hits.load(Ordering::SeqCst), 1);
+    assert_eq!(backup_hits.load(Ordering::SeqCst), 1);
+
+    let finished = service.state.list_recent_finished(10).await;
+    let providers = finished
+        .iter()
+        .map(|request| request.provider_id.as_deref())
+        .collect::<Vec<_>>();
+    assert_eq!(providers.len(), 3, "cache hits are logged like any request");
+    assert_eq!(
+        providers
+            .iter()
+            .filter(|provider| **provider == Some("backup"))
+            .count(),
+        2
+    );
+}
+
+#[tokio::test]
+async fn models_cache_hits_still_count_against_the_request_quota() {
+    let (upstream, hits) = models_counting_upstream();
+    let proxy = spawn_proxy_service(proxy_service(HelperConfig {
+        quota: RequestQuotaConfig {
+            window_secs: Some(3_600),
+            max_requests: Some(1),
+            max_output_tokens: None,
+            per_tenant: false,
+        },
+        ..models_cache_config(&upstream, 60_000)
+    }));
+
+    let (first_cache, _) = get_models(&proxy.url("/v1/models")).await;
+    assert_eq!(first_cache.as_deref(), Some("miss"));
+    let rejected = Client::new()
+        .get(proxy.url("/v1/models"))
+        .send()
+        .await
+        .expect("send over-quota models request");
+    assert_eq!(rejected.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(hits.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn models_cache_hits_leave_concurrency_and_health_state_unchanged() {
+    let (upstream, hits) = models_counting_upstream();
+    let mut config = models_cache_config(&upstream, 60_000);
+    config.lb.adaptive_concurrency.enabled = true;
+    config
+        .codex
+        .providers
+        .get_mut("primary")
+        .expect("primary provider")
+        .limits
+        .max_concurrent_requests = Some(8);
+    let service = proxy_service(config);
+    let proxy = spawn_proxy_service(service.clone());
+
+    let (first_cache, _) = get_models(&proxy.url("/v1/models")).await;
+    assert_eq!(first_cache.as_deref(), Some("miss"));
+
+    let provider_endpoint = ProviderEndpointKey::new("codex", "primary", "default");
+    let limit = crate::proxy::concurrency_limits::ConcurrencyLimit::new(
+        8,
+        service.config.capture().await.revision(),
+    )
+    .expect("non-zero limit");
+    let key = format!("endpoint:{}", provider_endpoint.stable_key());
+    let permit = service
+        .concurrency_limiter
+        .try_acquire(key.clone(), limit)
+        .expect("acquire test permit");
+    permit.record(crate::proxy::concurrency_limits::ConcurrencySignal::Pressure);
+    drop(permit);
+    assert_eq!(service.concurrency_limiter.snapshot(&key, limit).limit, 4);
+    let identity = service
+        .runtime_identity_for_provider_endpoint_for_test(&provider_endpoint)
+        .await;
+    service
+        .state
+        .record_runtime_upstream_attempt_failure(
+            "codex",
+            &identity,
+            30,
+            crate::endpoint_health::CooldownBackoff {
+                factor: 1,
+                max_secs: 0,
+                probe_back_success_threshold: 1,
+            },
+        )
+        .await;
+
+    for _ in 0..12 {
+        let (cache, _) = get_models(&proxy.url("/v1/models")).await;
+        assert_eq!(cache.as_deref(), Some("hit"));
+    }
+    assert_eq!(hits.load(Ordering::SeqCst), 1);
+
+    assert_eq!(
+        service.concurrency_limiter.snapshot(&key, limit).limit,
+        4,
+        "cache hits must not grow the adaptive limit"
+    );
+    let runtime = service
+        .state
+        .route_plan_runtime_state_for_provider_endpoints("codex")
+        .await;
+    assert_eq!(
+        runtime.provider_endpoint(&provider_endpoint).failure_count,
+        1,
+        "cache hits must not record an upstream success"
+    );
+    let finished = service.state.list_recent_finished(1).await;
+    assert!(
+        finished[0].retry.is_none(),
+        "a hit makes no upstream attempt"
+    );
+}
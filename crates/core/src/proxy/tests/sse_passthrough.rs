@@ -0,0 +1,84 @@
+use super::harness::{
+    find_finished_request, single_provider_config, spawn_proxy_service, spawn_test_upstream,
+};
+use super::*;
+
+const ANTHROPIC_STREAM: &str = concat!(
+    "event: message_start\n",
+    "data: {\"type\":\"message_start\",\"message\":{\"id\":\"msg_1\",\"model\":\"claude-sonnet-4-5\",\"usage\":{\"input_tokens\":25,\"output_tokens\":1}}}\n",
+    "\n",
+    ": keep-alive comment\n",
+    "event: ping\n",
+    "data: {\"type\": \"ping\"}\n",
+    "\n",
+    "event: content_block_delta\n",
+    "data: {\"type\":\"content_block_delta\",\n",
+    "data:  \"index\":0,\"delta\":{\"type\":\"text_delta\",\"text\":\"Hi\"}}\n",
+    "\n",
+    "event: ping\n",
+    "data: {\"type\": \"ping\"}\n",
+    "\n",
+    "event: message_delta\n",
+    "data: {\"type\":\"message_delta\",\"delta\":{\"stop_reason\":\"end_turn\",\"stop_sequence\":null},\"usage\":{\"output_tokens\":15}}\n",
+    "\n",
+    "event: message_stop\n",
+    "data: {\"type\":\"message_stop\"}\n",
+    "\n",
+);
+
+#[tokio::test]
+async fn anthropic_stream_events_pass_through_verbatim_with_usage() {
+    let upstream = spawn_test_upstream(axum::Router::new().route(
+        "/v1/messages",
+        post(|| async {
+            // Uneven chunks split events, lines, and the multi-line data field mid-way.
+            let chunks = ANTHROPIC_STREAM
+                .as_bytes()
+                .chunks(37)
+                .map(|chunk| Ok::<Bytes, Infallible>(Bytes::copy_from_slice(chunk)))
+                .collect::<Vec<_>>();
+            let mut response = Response::new(Body::from_stream(stream::iter(chunks)));
+            response.headers_mut().insert(
+                axum::http::header::CONTENT_TYPE,
+                HeaderValue::from_static("text/event-stream"),
+            );
+            response
+        }),
+    ));
+    let service = ProxyService::new(
+        crate::proxy::upstream_http_client_builder()
+            .build()
+            .expect("build test proxy client"),
+        Arc::new(HelperConfig {
+            claude: single_provider_config(upstream.base_url()).codex,
+            ..HelperConfig::default()
+        }),
+        "claude",
+    );
+    let state = Arc::clone(&service.state);
+    let proxy = spawn_proxy_service(service);
+
+    let resp = reqwest::Client::new()
+        .post(proxy.url("/v1/messages"))
+        .header("content-type", "application/json")
+        .body(r#"{"model":"claude-sonnet-4-5","stream":true,"messages":[]}"#)
+        .send()
+        .await
+        .expect("send messages request");
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body = resp.bytes().await.expect("read streamed body");
+    assert_eq!(
+        String::from_utf8_lossy(&body),
+        ANTHROPIC_STREAM,
+        "every event, comment, and data line must reach the client unchanged"
+    );
+
+    let finished = find_finished_request(&state, 10, |request| request.path == "/v1/messages")
+        .await
+        .expect("finished streamed request");
+    assert_eq!(finished.status_code, 200);
+    let usage = finished.usage.as_ref().expect("stream usage");
+    assert_eq!(usage.input_tokens, 25);
+    assert_eq!(usage.output_tokens, 15);
+    assert_eq!(usage.total_tokens, 40);
+}
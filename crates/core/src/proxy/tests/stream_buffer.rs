@@ -0,0 +1,106 @@
+use super::harness::{
+    TestUpstreamServer, find_finished_request, post_responses_json, proxy_service,
+    single_provider_config, spawn_proxy_service, spawn_test_upstream,
+};
+use super::*;
+use crate::config::StreamConfig;
+
+fn spawn_sse_upstream() -> TestUpstreamServer {
+    spawn_test_upstream(axum::Router::new().route(
+        "/v1/responses",
+        post(|| async {
+            let events = stream::iter([
+                Ok::<Bytes, Infallible>(Bytes::from_static(
+                    b"event: response.created\ndata: {\"type\":\"response.created\",\"response\":{\"id\":\"resp_1\"}}\n\n",
+                )),
+                Ok(Bytes::from_static(
+                    b"event: response.completed\ndata: {\"type\":\"response.completed\",\"response\":{\"id\":\"resp_1\",\"status\":\"completed\",\"usage\":{\"input_tokens\":3,\"output_tokens\":2,\"total_tokens\":5}}}\n\n",
+                )),
+            ]);
+            let mut response = Response::new(Body::from_stream(events));
+            response.headers_mut().insert(
+                axum::http::header::CONTENT_TYPE,
+                HeaderValue::from_static("text/event-stream"),
+            );
+            response
+        }),
+    ))
+}
+
+fn buffered_config(
+    base_url: String,
+    global: bool,
+    provider_buffer_responses: Option<bool>,
+) -> HelperConfig {
+    let mut config = HelperConfig {
+        stream: StreamConfig {
+            buffer_responses: global,
+        },
+        ..single_provider_config(base_url)
+    };
+    config
+        .codex
+        .providers
+        .get_mut("primary")
+        .expect("primary provider")
+        .buffer_responses = provider_buffer_responses;
+    config
+}
+
+#[tokio::test]
+async fn buffered_stream_is_returned_as_one_json_response() {
+    let upstream = spawn_sse_upstream();
+    let service = proxy_service(buffered_config(upstream.base_url(), true, None));
+    let state = Arc::clone(&service.state);
+    let proxy = spawn_proxy_service(service);
+    let client = reqwest::Client::new();
+
+    let resp = post_responses_json(&client, &proxy, r#"{"input":"hi","stream":true}"#).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(
+        resp.headers()
+            .get("content-type")
+            .and_then(|value| value.to_str().ok()),
+        Some("application/json")
+    );
+    let body: serde_json::Value = resp.json().await.expect("buffered json body");
+    assert_eq!(body["id"], "resp_1");
+    assert_eq!(body["usage"]["total_tokens"], 5);
+
+    let finished = find_finished_request(&state, 10, |request| request.path == "/v1/responses")
+        .await
+        .expect("finished buffered request");
+    assert_eq!(finished.status_code, 200);
+    assert_eq!(
+        finished.usage.as_ref().map(|usage| usage.total_tokens),
+        Some(5)
+    );
+}
+
+#[tokio::test]
+async fn provider_buffer_responses_overrides_the_stream_setting() {
+    let upstream = spawn_sse_upstream();
+    let client = reqwest::Client::new();
+    for (global, provider, expected_content_type) in [
+        (false, Some(true), "application/json"),
+        (true, Some(false), "text/event-stream"),
+    ] {
+        let proxy = spawn_proxy_service(proxy_service(buffered_config(
+            upstream.base_url(),
+            global,
+            provider,
+        )));
+        let resp = post_responses_json(&client, &proxy, r#"{"input":"hi","stream":true}"#).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let content_type = resp
+            .headers()
+            .get("content-type")
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default()
+            .to_string();
+        assert!(
+            content_type.starts_with(expected_content_type),
+            "global={global} provider={provider:?} content-type={content_type}"
+        );
+    }
+}
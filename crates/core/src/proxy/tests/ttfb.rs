@@ -0,0 +1,57 @@
+use super::harness::{
+    find_finished_request, post_responses_json, proxy_service, single_provider_config,
+    spawn_proxy_service, spawn_test_upstream,
+};
+use super::*;
+
+#[tokio::test]
+async fn streamed_response_ttfb_is_first_forwarded_byte_within_duration() {
+    let upstream = spawn_test_upstream(axum::Router::new().route(
+        "/v1/responses",
+        post(|| async {
+            // Headers go out immediately; the first body byte follows after a pause.
+            let first = stream::once(async {
+                sleep(Duration::from_millis(80)).await;
+                Ok::<Bytes, Infallible>(Bytes::from_static(
+                    b"event: response.created\ndata: {\"type\":\"response.created\"}\n\n",
+                ))
+            });
+            let rest = stream::once(async {
+                sleep(Duration::from_millis(40)).await;
+                Ok::<Bytes, Infallible>(Bytes::from_static(
+                    b"event: response.completed\ndata: {\"type\":\"response.completed\",\"response\":{\"usage\":{\"input_tokens\":3,\"output_tokens\":2,\"total_tokens\":5}}}\n\n",
+                ))
+            });
+            let mut response = Response::new(Body::from_stream(first.chain(rest)));
+            response.headers_mut().insert(
+                axum::http::header::CONTENT_TYPE,
+                HeaderValue::from_static("text/event-stream"),
+            );
+            response
+        }),
+    ));
+    let service = proxy_service(single_provider_config(upstream.base_url()));
+    let state = Arc::clone(&service.state);
+    let proxy = spawn_proxy_service(service);
+    let client = reqwest::Client::new();
+
+    let resp = post_responses_json(&client, &proxy, r#"{"input":"hi","stream":true}"#).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let _ = resp.bytes().await.expect("read streamed body");
+
+    let finished = find_finished_request(&state, 10, |request| request.path == "/v1/responses")
+        .await
+        .expect("finished streamed request");
+    assert!(finished.streaming);
+    let ttfb_ms = finished.ttfb_ms.expect("streamed request records ttfb");
+    assert!(
+        ttfb_ms >= 80,
+        "ttfb {ttfb_ms}ms should include the wait for the first byte"
+    );
+    assert!(
+        ttfb_ms <= finished.duration_ms,
+        "ttfb {ttfb_ms}ms exceeds duration {}ms",
+        finished.duration_ms
+    );
+    assert_eq!(finished.observability_view().ttfb_ms, Some(ttfb_ms));
+}
@@ -0,0 +1,88 @@
+use super::harness::{spawn_test_upstream, upstream_config};
+use super::*;
+use crate::upstream_models::{
+    UpstreamModelCache, UpstreamModelList, load_upstream_model_cache, save_upstream_model_cache,
+};
+
+fn models_upstream(hits: Arc<AtomicUsize>) -> axum::Router {
+    axum::Router::new().route(
+        "/v1/models",
+        get(move || {
+            let hits = hits.clone();
+            async move {
+                hits.fetch_add(1, Ordering::SeqCst);
+                Json(serde_json::json!({
+                    "object": "list",
+                    "data": [{ "id": "gpt-5.5" }, { "id": "gpt-5.4-mini" }]
+                }))
+            }
+        }),
+    )
+}
+
+fn named_upstream(base_url: String, provider_id: &str) -> UpstreamConfig {
+    let mut upstream = upstream_config(base_url);
+    upstream
+        .tags
+        .insert("provider_id".to_string(), provider_id.to_string());
+    upstream
+}
+
+#[tokio::test]
+async fn upstream_model_lists_are_fetched_cached_and_pruned() {
+    let _env_lock = env_lock().await;
+    let temp_dir = make_temp_test_dir();
+    let mut scoped = ScopedEnv::default();
+    unsafe {
+        scoped.set_path("CODEX_HELPER_HOME", temp_dir.as_path());
+    }
+    save_upstream_model_cache(
+        "codex",
+        &UpstreamModelCache {
+            providers: [(
+                "removed".to_string(),
+                UpstreamModelList {
+                    fetched_at_ms: 1,
+                    models: ["old-model".to_string()].into(),
+                },
+            )]
+            .into(),
+        },
+    )
+    .expect("seed cache");
+
+    let hits = Arc::new(AtomicUsize::new(0));
+    let relay = spawn_test_upstream(models_upstream(hits.clone()));
+    let broken = spawn_test_upstream(axum::Router::new().route(
+        "/v1/models",
+        get(|| async { StatusCode::INTERNAL_SERVER_ERROR }),
+    ));
+    let config = make_helper_config(
+        vec![
+            named_upstream(relay.base_url(), "relay"),
+            named_upstream(broken.base_url(), "broken"),
+        ],
+        RetryConfig::default(),
+    );
+    let proxy = super::harness::proxy_service(config);
+
+    let cache = proxy.refresh_upstream_model_lists().await;
+
+    assert_eq!(
+        cache.providers.keys().collect::<Vec<_>>(),
+        vec!["relay"],
+        "failed fetches are not cached and removed providers are pruned"
+    );
+    assert_eq!(
+        cache.providers["relay"].models,
+        ["gpt-5.4-mini".to_string(), "gpt-5.5".to_string()].into()
+    );
+    assert_eq!(load_upstream_model_cache("codex"), cache);
+
+    proxy.refresh_upstream_model_lists().await;
+    assert_eq!(
+        hits.load(Ordering::SeqCst),
+        1,
+        "a fresh list is not fetched again"
+    );
+}
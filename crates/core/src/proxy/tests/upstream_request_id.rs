@@ -0,0 +1,59 @@
+use axum::response::IntoResponse;
+
+use super::harness::{
+    find_finished_request, post_responses_json, proxy_service, single_provider_config,
+    spawn_proxy_service, spawn_test_upstream,
+};
+use super::*;
+
+#[tokio::test]
+async fn finished_requests_record_the_upstream_request_id() {
+    let upstream = spawn_test_upstream(axum::Router::new().route(
+        "/v1/responses",
+        post(|body: Bytes| async move {
+            if body.windows(13).any(|window| window == b"\"stream\":true") {
+                let mut response = Response::new(Body::from(
+                    "event: response.completed\ndata: {\"type\":\"response.completed\",\"response\":{}}\n\n",
+                ));
+                let headers = response.headers_mut();
+                headers.insert(
+                    axum::http::header::CONTENT_TYPE,
+                    HeaderValue::from_static("text/event-stream"),
+                );
+                headers.insert("request-id", HeaderValue::from_static("req_stream_1"));
+                headers.insert("cf-ray", HeaderValue::from_static("8f1c2a-SJC"));
+                return response;
+            }
+            let mut response = Json(serde_json::json!({ "ok": true })).into_response();
+            response
+                .headers_mut()
+                .insert("x-request-id", HeaderValue::from_static("req_buffered_1"));
+            response
+        }),
+    ));
+    let service = proxy_service(single_provider_config(upstream.base_url()));
+    let state = Arc::clone(&service.state);
+    let proxy = spawn_proxy_service(service);
+    let client = reqwest::Client::new();
+
+    let resp = post_responses_json(&client, &proxy, r#"{"input":"hi"}"#).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let buffered = find_finished_request(&state, 10, |request| !request.streaming)
+        .await
+        .expect("finished buffered request");
+    assert_eq!(
+        buffered.upstream_request_id.as_deref(),
+        Some("req_buffered_1")
+    );
+
+    let resp = post_responses_json(&client, &proxy, r#"{"input":"hi","stream":true}"#).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let _ = resp.bytes().await.expect("read streamed body");
+    let streamed = find_finished_request(&state, 10, |request| request.streaming)
+        .await
+        .expect("finished streamed request");
+    assert_eq!(
+        streamed.upstream_request_id.as_deref(),
+        Some("req_stream_1")
+    );
+}
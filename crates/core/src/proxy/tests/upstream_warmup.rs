@@ -0,0 +1,265 @@
+use std::sync::atomic::AtomicBool;
+
+use super::harness::{spawn_test_upstream, upstream_config};
+use super::*;
+use crate::proxy::health_probe::HealthProbeTracker;
+use crate::proxy::{UpstreamWarmupOutcome, UpstreamWarmupResult};
+use crate::routing_ir::RouteRequestContext;
+use crate::state::LiveEvent;
+
+fn head_counting_upstream(hits: Arc<AtomicUsize>) -> axum::Router {
+    axum::Router::new().route(
+        "/",
+        axum::routing::head(move || {
+            let hits = hits.clone();
+            async move {
+                hits.fetch_add(1, Ordering::SeqCst);
+                StatusCode::NO_CONTENT
+            }
+        }),
+    )
+}
+
+fn named_upstream(base_url: String, provider_id: &str) -> UpstreamConfig {
+    let mut upstream = upstream_config(base_url);
+    upstream
+        .tags
+        .insert("provider_id".to_string(), provider_id.to_string());
+    upstream
+}
+
+fn outcome_for<'a>(
+    results: &'a [UpstreamWarmupResult],
+    provider_id: &str,
+) -> &'a UpstreamWarmupOutcome {
+    &results
+        .iter()
+        .find(|result| {
+            result
+                .provider_endpoint_key
+                .contains(&format!("/{provider_id}/"))
+        })
+        .unwrap_or_else(|| panic!("warmup result for {provider_id}"))
+        .outcome
+}
+
+async fn cooldown_active(proxy: &ProxyService) -> bool {
+    proxy
+        .routing_explain(RouteRequestContext::default(), None)
+        .await
+        .expect("routing explain")
+        .candidates[0]
+        .availability
+        .cooldown_active
+}
+
+#[tokio::test]
+async fn warmup_primes_each_routable_origin_once_and_skips_cooled_down_upstreams() {
+    let _env_lock = env_lock().await;
+    let temp_dir = make_temp_test_dir();
+    let mut scoped = ScopedEnv::default();
+    unsafe {
+        scoped.set_path("CODEX_HELPER_HOME", temp_dir.as_path());
+    }
+
+    let warm_hits = Arc::new(AtomicUsize::new(0));
+    let cold_hits = Arc::new(AtomicUsize::new(0));
+    let warm = spawn_test_upstream(head_counting_upstream(warm_hits.clone()));
+    let cold = spawn_test_upstream(head_counting_upstream(cold_hits.clone()));
+    let config = make_helper_config(
+        vec![
+            named_upstream(warm.base_url(), "primary"),
+            named_upstream(format!("{}/alt", warm.base_url()), "mirror"),
+            named_upstream(cold.base_url(), "cooling"),
+        ],
+        RetryConfig::default(),
+    );
+    let proxy = super::harness::proxy_service(config);
+    let cooling = crate::runtime_identity::ProviderEndpointKey::new("codex", "cooling", "default");
+    let cooling_identity = proxy
+        .runtime_identity_for_provider_endpoint_for_test(&cooling)
+        .await;
+    proxy
+        .state
+        .penalize_runtime_upstream_attempt(
+            "codex",
+            &cooling_identity,
+            30,
+            crate::endpoint_health::CooldownBackoff {
+                factor: 1,
+                max_secs: 0,
+                probe_back_success_threshold: 1,
+            },
+        )
+        .await;
+
+    let results = proxy.warmup_upstreams().await;
+
+    assert_eq!(results.len(), 3);
+    assert!(matches!(
+        outcome_for(&results, "primary"),
+        UpstreamWarmupOutcome::Connected { status: 204, .. }
+    ));
+    assert_eq!(
+        outcome_for(&results, "mirror"),
+        &UpstreamWarmupOutcome::SharedOrigin
+    );
+    assert_eq!(
+        outcome_for(&results, "cooling"),
+        &UpstreamWarmupOutcome::Skipped { reason: "cooldown" }
+    );
+    assert_eq!(warm_hits.load(Ordering::SeqCst), 1);
+    assert_eq!(cold_hits.load(Ordering::SeqCst), 0);
+}
+
+#[tokio::test]
+async fn health_probes_each_endpoint_with_its_credentials_and_provider_criteria() {
+    let _env_lock = env_lock().await;
+    let temp_dir = make_temp_test_dir();
+    let mut scoped = ScopedEnv::default();
+    unsafe {
+        scoped.set_path("CODEX_HELPER_HOME", temp_dir.as_path());
+    }
+
+    let hits = Arc::new(AtomicUsize::new(0));
+    let hits_for_route = hits.clone();
+    let upstream = spawn_test_upstream(axum::Router::new().route(
+        "/health",
+        get(move |headers: HeaderMap| {
+            let hits = hits_for_route.clone();
+            async move {
+                hits.fetch_add(1, Ordering::SeqCst);
+                let authorized = headers
+                    .get("authorization")
+                    .and_then(|value| value.to_str().ok())
+                    == Some("Bearer good-token");
+                if authorized {
+                    (StatusCode::OK, Json(serde_json::json!({ "ok": true })))
+                } else {
+                    (
+                        StatusCode::UNAUTHORIZED,
+                        Json(serde_json::json!({ "error": "bad key" })),
+                    )
+                }
+            }
+        }),
+    ));
+    let with_token = |base_url: String, provider_id: &str, token: &str| {
+        let mut upstream = named_upstream(base_url, provider_id);
+        upstream.auth.auth_token = Some(token.to_string().into());
+        upstream
+    };
+    let mut config = make_helper_config(
+        vec![
+            with_token(upstream.base_url(), "primary", "good-token"),
+            with_token(
+                format!("{}/alt", upstream.base_url()),
+                "mirror",
+                "bad-token",
+            ),
+            with_token(
+                format!("{}/other", upstream.base_url()),
+                "strict",
+                "bad-token",
+            ),
+        ],
+        RetryConfig::default(),
+    );
+    let global: crate::config::HealthCheckConfig = toml::from_str(
+        r#"
+path = "/health"
+expect_status = ["2xx"]
+expect_json_path = "ok"
+"#,
+    )
+    .expect("global criteria");
+    config
+        .codex
+        .providers
+        .get_mut("mirror")
+        .expect("mirror provider")
+        .healthcheck = Some(toml::from_str("expect_status = [401]\npath = \"/health\"").unwrap());
+    let proxy = super::harness::proxy_service(config);
+
+    let results = proxy
+        .probe_routable_upstreams(|_| true, Some(&global))
+        .await;
+
+    assert_eq!(results.len(), 3);
+    assert!(matches!(
+        outcome_for(&results, "primary"),
+        UpstreamWarmupOutcome::Connected { status: 200, .. }
+    ));
+    assert!(matches!(
+        outcome_for(&results, "mirror"),
+        UpstreamWarmupOutcome::Connected { status: 401, .. }
+    ));
+    assert_eq!(
+        outcome_for(&results, "strict"),
+        &UpstreamWarmupOutcome::Failed {
+            error: "unexpected status 401".to_string()
+        }
+    );
+    assert_eq!(hits.load(Ordering::SeqCst), 3, "no shared-origin shortcut");
+}
+
+#[tokio::test]
+async fn failed_health_probes_cool_the_endpoint_down_until_it_recovers() {
+    let _env_lock = env_lock().await;
+    let temp_dir = make_temp_test_dir();
+    let mut scoped = ScopedEnv::default();
+    unsafe {
+        scoped.set_path("CODEX_HELPER_HOME", temp_dir.as_path());
+    }
+
+    let healthy = Arc::new(AtomicBool::new(false));
+    let healthy_for_route = healthy.clone();
+    let upstream = spawn_test_upstream(axum::Router::new().route(
+        "/health",
+        get(move || {
+            let healthy = healthy_for_route.clone();
+            async move {
+                if healthy.load(Ordering::SeqCst) {
+                    StatusCode::OK
+                } else {
+                    StatusCode::SERVICE_UNAVAILABLE
+                }
+            }
+        }),
+    ));
+    let mut config = make_helper_config(
+        vec![named_upstream(upstream.base_url(), "primary")],
+        RetryConfig::default(),
+    );
+    config.healthcheck = toml::from_str(
+        r#"
+interval_ms = 1000
+path = "/health"
+expect_status = ["2xx"]
+"#,
+    )
+    .expect("healthcheck");
+    let proxy = super::harness::proxy_service(config);
+    let mut events = proxy.state.subscribe_live_events();
+    let mut tracker = HealthProbeTracker::default();
+
+    proxy
+        .run_periodic_health_probe(&mut tracker, Duration::from_millis(1))
+        .await;
+    assert!(cooldown_active(&proxy).await, "a failed probe cools down");
+
+    healthy.store(true, Ordering::SeqCst);
+    tokio::time::sleep(Duration::from_millis(5)).await;
+    proxy
+        .run_periodic_health_probe(&mut tracker, Duration::from_millis(1))
+        .await;
+    assert!(!cooldown_active(&proxy).await, "a recovery closes it again");
+
+    let breaker_states = std::iter::from_fn(|| events.try_recv().ok())
+        .filter_map(|event| match event {
+            LiveEvent::HealthChanged { breaker_open, .. } => Some(breaker_open),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(breaker_states, vec![true, false]);
+}
@@ -0,0 +1,88 @@
+use super::harness::{
+    TestUpstreamServer, proxy_service, single_provider_config, spawn_proxy_service,
+    spawn_test_upstream,
+};
+use super::*;
+use crate::config::{HttpConfig, default_upstream_user_agent};
+use std::sync::Mutex;
+
+fn user_agent_recording_upstream() -> (TestUpstreamServer, Arc<Mutex<Vec<String>>>) {
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let seen_for_route = seen.clone();
+    let upstream = spawn_test_upstream(axum::Router::new().route(
+        "/v1/responses",
+        post(move |headers: HeaderMap| {
+            let seen_for_route = seen_for_route.clone();
+            async move {
+                let user_agent = headers
+                    .get(axum::http::header::USER_AGENT)
+                    .and_then(|value| value.to_str().ok())
+                    .unwrap_or_default()
+                    .to_string();
+                seen_for_route.lock().expect("lock").push(user_agent);
+                (StatusCode::OK, Json(serde_json::json!({"id": "resp_ok"})))
+            }
+        }),
+    ));
+    (upstream, seen)
+}
+
+async fn send_with_user_agent(cfg: HelperConfig, client_user_agent: Option<&str>) {
+    let proxy = spawn_proxy_service(proxy_service(cfg));
+    let mut request = Client::new()
+        .post(proxy.responses_url())
+        .header("content-type", "application/json");
+    if let Some(client_user_agent) = client_user_agent {
+        request = request.header(axum::http::header::USER_AGENT, client_user_agent);
+    }
+    let response = request
+        .body(r#"{"input":"hi"}"#)
+        .send()
+        .await
+        .expect("send responses request");
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn upstream_receives_client_user_agent_by_default() {
+    let (upstream, seen) = user_agent_recording_upstream();
+    let cfg = single_provider_config(upstream.base_url());
+
+    send_with_user_agent(cfg.clone(), Some("codex_cli_rs/0.1")).await;
+    send_with_user_agent(cfg, None).await;
+
+    let seen = seen.lock().expect("lock").clone();
+    assert_eq!(
+        seen,
+        vec![
+            "codex_cli_rs/0.1".to_string(),
+            default_upstream_user_agent()
+        ]
+    );
+    assert!(seen[1].starts_with("codex-helper/"));
+}
+
+#[tokio::test]
+async fn upstream_receives_configured_user_agent_with_provider_override() {
+    let (upstream, seen) = user_agent_recording_upstream();
+    let mut cfg = HelperConfig {
+        http: HttpConfig {
+            user_agent: Some("acme-gateway/2".to_string()),
+            ..HttpConfig::default()
+        },
+        ..single_provider_config(upstream.base_url())
+    };
+    send_with_user_agent(cfg.clone(), Some("codex_cli_rs/0.1")).await;
+
+    cfg.codex
+        .providers
+        .get_mut("primary")
+        .expect("primary provider")
+        .user_agent = Some("acme-provider/3".to_string());
+    send_with_user_agent(cfg, Some("codex_cli_rs/0.1")).await;
+
+    assert_eq!(
+        seen.lock().expect("lock").clone(),
+        vec!["acme-gateway/2".to_string(), "acme-provider/3".to_string()]
+    );
+}
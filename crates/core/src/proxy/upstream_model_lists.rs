@@ -0,0 +1,207 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::time::Duration;
+
+use axum::http::{HeaderMap, HeaderValue};
+
+use super::ProxyService;
+use super::control_plane_service::service_route_config;
+use super::service_core::wait_for_proxy_shutdown;
+use crate::routing_ir::RouteRequestContext;
+use crate::runtime_identity::ProviderEndpointKey;
+use crate::upstream_models::{
+    UpstreamModelCache, UpstreamModelList, load_upstream_model_cache, model_validation_warnings,
+    parse_models_response, save_upstream_model_cache,
+};
+
+/// How often the driver looks for stale lists; each list itself is refetched once per TTL.
+const UPSTREAM_MODELS_RECHECK: Duration = Duration::from_secs(5 * 60);
+const UPSTREAM_MODELS_TIMEOUT: Duration = Duration::from_secs(15);
+const MAX_MODELS_RESPONSE_BYTES: usize = 4 * 1024 * 1024;
+
+impl ProxyService {
+    /// Keeps `upstream_models_<service>.json` current while `routing.validate_models_online`
+    /// is on, and logs each validation warning the first time it appears.
+    pub(super) async fn run_upstream_models_driver(
+        &self,
+        mut shutdown_rx: tokio::sync::watch::Receiver<bool>,
+    ) {
+        let mut reported = BTreeSet::new();
+        loop {
+            let config = self.captured_runtime_config().await;
+            let view = service_route_config(&config, self.service_name);
+            if view
+                .routing
+                .as_ref()
+                .is_some_and(|routing| routing.validate_models_online)
+            {
+                let cache = self.refresh_upstream_model_lists().await;
+                let warnings = model_validation_warnings(view, &cache.providers)
+                    .into_iter()
+                    .collect::<BTreeSet<_>>();
+                for warning in warnings.difference(&reported) {
+                    tracing::warn!("[{}] upstream model check: {}", self.service_name, warning);
+                }
+                reported = warnings;
+            } else {
+                reported.clear();
+            }
+            tokio::select! {
+                biased;
+                _ = wait_for_proxy_shutdown(&mut shutdown_rx) => return,
+                () = tokio::time::sleep(UPSTREAM_MODELS_RECHECK) => {}
+            }
+        }
+    }
+
+    /// Fetches `/v1/models` from every routable provider whose cached list is missing or older
+    /// than the TTL, drops providers that left the config, and saves the cache when it changed.
+    /// A provider whose fetch fails keeps its previous list.
+    pub(super) async fn refresh_upstream_model_lists(&self) -> UpstreamModelCache {
+        let service_name = self.service_name;
+        let previous = load_upstream_model_cache(service_name);
+        let config = self.captured_runtime_config().await;
+        let view = service_route_config(&config, service_name);
+        let mut cache = previous.clone();
+        cache
+            .providers
+            .retain(|provider_id, _| view.providers.contains_key(provider_id));
+
+        let explain = match self
+            .routing_explain(RouteRequestContext::default(), None)
+            .await
+        {
+            Ok(explain) => explain,
+            Err(error) => {
+                tracing::warn!(
+                    "[{}] upstream model lists skipped: {}",
+                    service_name,
+                    error.message()
+                );
+                return cache;
+            }
+        };
+        let now_ms = crate::logging::now_ms();
+        let mut targets = BTreeMap::new();
+        for candidate in explain.candidates {
+            if candidate.availability.hard_unavailable
+                || cache
+                    .providers
+                    .get(&candidate.provider_id)
+                    .is_some_and(|list| list.is_fresh(now_ms))
+            {
+                continue;
+            }
+            targets
+                .entry(candidate.provider_id)
+                .or_insert((candidate.endpoint_id, candidate.upstream_base_url));
+        }
+
+        for (provider_id, (endpoint_id, base_url)) in targets {
+            match self
+                .fetch_upstream_models(&provider_id, &endpoint_id, &base_url)
+                .await
+            {
+                Ok(models) => {
+                    cache.providers.insert(
+                        provider_id,
+                        UpstreamModelList {
+                            fetched_at_ms: crate::logging::now_ms(),
+                            models,
+                        },
+                    );
+                }
+                Err(error) => tracing::warn!(
+                    "[{}] failed to fetch /v1/models from {}: {}",
+                    service_name,
+                    provider_id,
+                    error
+                ),
+            }
+        }
+
+        if cache != previous
+            && let Err(error) = save_upstream_model_cache(service_name, &cache)
+        {
+            tracing::warn!(
+                "[{}] failed to save upstream model lists: {}",
+                service_name,
+                error
+            );
+        }
+        cache
+    }
+
+    async fn fetch_upstream_models(
+        &self,
+        provider_id: &str,
+        endpoint_id: &str,
+        base_url: &str,
+    ) -> Result<BTreeSet<String>, String> {
+        let service_name = self.service_name;
+        let runtime = self.config.capture().await;
+        let provider_endpoint = ProviderEndpointKey::new(service_name, provider_id, endpoint_id);
+        let credential = runtime
+            .credential_generation()
+            .capture_bound(&provider_endpoint)
+            .map_err(|error| error.to_string())?;
+        let url = models_url(base_url);
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            axum::http::header::ACCEPT_ENCODING,
+            HeaderValue::from_static("identity"),
+        );
+        if service_name == "claude" {
+            headers.insert("anthropic-version", HeaderValue::from_static("2023-06-01"));
+        }
+        super::attempt_request::inject_auth_headers(service_name, &credential, &url, &mut headers)
+            .map_err(|error| error.to_string())?;
+
+        let client = runtime
+            .provider_client(service_name, provider_id)
+            .unwrap_or(&self.client);
+        let response = client
+            .get(&url)
+            .headers(headers)
+            .timeout(UPSTREAM_MODELS_TIMEOUT)
+            .send()
+            .await
+            .map_err(|error| super::http_debug::format_reqwest_error_for_retry_chain(&error))?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(format!("HTTP {}", status.as_u16()));
+        }
+        let body = super::codex_relay_probe::read_limited_body(response, MAX_MODELS_RESPONSE_BYTES)
+            .await?;
+        let value = serde_json::from_slice::<serde_json::Value>(&body)
+            .map_err(|_| "response is not JSON".to_string())?;
+        parse_models_response(&value).ok_or_else(|| "response is not a model list".to_string())
+    }
+}
+
+/// `<base>/models` for a base URL that already ends in `/v1`, `<base>/v1/models` otherwise.
+fn models_url(base_url: &str) -> String {
+    let base = base_url.trim_end_matches('/');
+    if base.ends_with("/v1") {
+        format!("{base}/models")
+    } else {
+        format!("{base}/v1/models")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn models_url_appends_the_v1_prefix_only_when_missing() {
+        assert_eq!(
+            models_url("https://relay.example/v1/"),
+            "https://relay.example/v1/models"
+        );
+        assert_eq!(
+            models_url("https://api.anthropic.com"),
+            "https://api.anthropic.com/v1/models"
+        );
+    }
+}
@@ -0,0 +1,308 @@
+use std::collections::BTreeSet;
+use std::time::{Duration, Instant};
+
+use axum::http::{HeaderMap, HeaderValue};
+use futures_util::future::join_all;
+
+use super::ProxyService;
+use super::codex_relay_probe::read_limited_body;
+use super::control_plane_service::service_route_config;
+use super::health_probe::probe_response_mismatch;
+use super::runtime_config::RuntimeSnapshot;
+use crate::config::HealthCheckConfig;
+use crate::routing_explain::RoutingExplainSkipReason;
+use crate::routing_ir::RouteRequestContext;
+use crate::runtime_identity::ProviderEndpointKey;
+
+const UPSTREAM_WARMUP_TIMEOUT: Duration = Duration::from_secs(10);
+const MAX_PROBE_RESPONSE_BYTES: usize = 1024 * 1024;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpstreamWarmupResult {
+    pub provider_endpoint_key: String,
+    pub upstream_origin: Option<String>,
+    pub outcome: UpstreamWarmupOutcome,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UpstreamWarmupOutcome {
+    /// The upstream answered (any HTTP status), so a pooled connection is now open.
+    Connected {
+        status: u16,
+        elapsed_ms: u64,
+    },
+    /// Another candidate already warmed the same origin through the same client.
+    SharedOrigin,
+    Skipped {
+        reason: &'static str,
+    },
+    Failed {
+        error: String,
+    },
+}
+
+impl ProxyService {
+    /// Best-effort connection priming for every routable upstream of this service.
+    ///
+    /// Each distinct upstream origin receives one `HEAD /` through its provider's upstream
+    /// client, so the TLS handshake is paid before the first real request. Candidates that are
+    /// disabled, cooling down, or otherwise hard-unavailable are skipped and never contacted.
+    pub async fn warmup_upstreams(&self) -> Vec<UpstreamWarmupResult> {
+        let results = self.probe_routable_upstreams(|_| true, None).await;
+        for result in &results {
+            log_upstream_warmup_result(self.service_name, result);
+        }
+        results
+    }
+
+    /// Probes the routable upstreams whose provider endpoint key `should_probe` accepts.
+    ///
+    /// Endpoints rejected by `should_probe` are reported as skipped with reason `probe_backoff`.
+    /// Without `criteria` this is a warmup: one `HEAD /` per distinct origin and client. With
+    /// `criteria` (the global `[healthcheck]`, replaced by a provider's own `healthcheck`), every
+    /// endpoint is probed on its own with the provider's credentials, and a response that misses
+    /// the criteria counts as failed. Health probes also reach endpoints that are only cooling
+    /// down, so a recovered endpoint can be returned to rotation.
+    pub(super) async fn probe_routable_upstreams(
+        &self,
+        should_probe: impl Fn(&str) -> bool,
+        criteria: Option<&HealthCheckConfig>,
+    ) -> Vec<UpstreamWarmupResult> {
+        let explain = match self
+            .routing_explain(RouteRequestContext::default(), None)
+            .await
+        {
+            Ok(explain) => explain,
+            Err(error) => {
+                tracing::warn!(
+                    "[{}] upstream probe skipped: {}",
+                    self.service_name,
+                    error.message()
+                );
+                return Vec::new();
+            }
+        };
+        let runtime = self.config.capture().await;
+        let config = runtime.config();
+        let view = service_route_config(config.as_ref(), self.service_name);
+
+        let mut results = Vec::new();
+        let mut probes = Vec::new();
+        let mut seen_origins = BTreeSet::new();
+        for candidate in explain.candidates {
+            let upstream_origin = crate::logging::upstream_origin(&candidate.upstream_base_url);
+            let cooling_down = criteria.is_some()
+                && matches!(
+                    candidate.availability.dominant_reason,
+                    Some(
+                        RoutingExplainSkipReason::Cooldown
+                            | RoutingExplainSkipReason::BreakerOpen { .. }
+                    )
+                );
+            let skip_reason = if candidate.availability.hard_unavailable && !cooling_down {
+                Some(
+                    candidate
+                        .availability
+                        .dominant_reason
+                        .as_ref()
+                        .map_or("unavailable", warmup_skip_reason_code),
+                )
+            } else if candidate.availability.runtime_disabled {
+                Some("runtime_disabled")
+            } else if upstream_origin.is_none() {
+                Some("invalid_base_url")
+            } else {
+                None
+            };
+            if let Some(reason) = skip_reason {
+                results.push(UpstreamWarmupResult {
+                    provider_endpoint_key: candidate.provider_endpoint_key,
+                    upstream_origin,
+                    outcome: UpstreamWarmupOutcome::Skipped { reason },
+                });
+                continue;
+            }
+            let origin = upstream_origin.expect("checked above");
+            if !should_probe(&candidate.provider_endpoint_key) {
+                results.push(UpstreamWarmupResult {
+                    provider_endpoint_key: candidate.provider_endpoint_key,
+                    upstream_origin: Some(origin),
+                    outcome: UpstreamWarmupOutcome::Skipped {
+                        reason: "probe_backoff",
+                    },
+                });
+                continue;
+            }
+            let provider_client =
+                runtime.provider_client(self.service_name, &candidate.provider_id);
+            let criteria = criteria.map(|global| {
+                view.providers
+                    .get(&candidate.provider_id)
+                    .and_then(|provider| provider.healthcheck.as_ref())
+                    .unwrap_or(global)
+                    .clone()
+            });
+            // Warmups only open connections, so endpoints sharing an origin and client share one.
+            if criteria.is_none() {
+                let client_scope = provider_client.map(|_| candidate.provider_id.as_str());
+                if !seen_origins.insert((origin.clone(), client_scope.map(str::to_owned))) {
+                    results.push(UpstreamWarmupResult {
+                        provider_endpoint_key: candidate.provider_endpoint_key,
+                        upstream_origin: Some(origin),
+                        outcome: UpstreamWarmupOutcome::SharedOrigin,
+                    });
+                    continue;
+                }
+            }
+            let probe = match criteria {
+                Some(criteria) => self
+                    .probe_request(
+                        &runtime,
+                        &candidate.provider_id,
+                        &candidate.endpoint_id,
+                        &origin,
+                        &criteria,
+                    )
+                    .map(|request| (request, Some(criteria))),
+                None => Ok((
+                    provider_client
+                        .unwrap_or(&self.client)
+                        .head(format!("{origin}/")),
+                    None,
+                )),
+            };
+            match probe {
+                Ok((request, criteria)) => probes.push(probe_origin(
+                    candidate.provider_endpoint_key,
+                    origin,
+                    request,
+                    criteria,
+                )),
+                Err(error) => results.push(UpstreamWarmupResult {
+                    provider_endpoint_key: candidate.provider_endpoint_key,
+                    upstream_origin: Some(origin),
+                    outcome: UpstreamWarmupOutcome::Failed { error },
+                }),
+            }
+        }
+        results.extend(join_all(probes).await);
+        results
+    }
+
+    /// A health-probe request for one endpoint, authenticated like a real request and sent
+    /// through the provider's own client.
+    fn probe_request(
+        &self,
+        runtime: &RuntimeSnapshot,
+        provider_id: &str,
+        endpoint_id: &str,
+        origin: &str,
+        criteria: &HealthCheckConfig,
+    ) -> Result<reqwest::RequestBuilder, String> {
+        let provider_endpoint =
+            ProviderEndpointKey::new(self.service_name, provider_id, endpoint_id);
+        let credential = runtime
+            .credential_generation()
+            .capture_bound(&provider_endpoint)
+            .map_err(|error| error.to_string())?;
+        let url = format!("{origin}{}", criteria.probe_path());
+        let mut headers = HeaderMap::new();
+        if self.service_name == "claude" {
+            headers.insert("anthropic-version", HeaderValue::from_static("2023-06-01"));
+        }
+        super::attempt_request::inject_auth_headers(
+            self.service_name,
+            &credential,
+            &url,
+            &mut headers,
+        )
+        .map_err(|error| error.to_string())?;
+        let client = runtime
+            .provider_client(self.service_name, provider_id)
+            .unwrap_or(&self.client);
+        let request = if criteria.probes_body() {
+            client.get(url)
+        } else {
+            client.head(url)
+        };
+        Ok(request.headers(headers))
+    }
+}
+
+async fn probe_origin(
+    provider_endpoint_key: String,
+    origin: String,
+    request: reqwest::RequestBuilder,
+    criteria: Option<HealthCheckConfig>,
+) -> UpstreamWarmupResult {
+    let started = Instant::now();
+    let response = request.timeout(UPSTREAM_WARMUP_TIMEOUT).send().await;
+    let outcome = match response {
+        Ok(response) => {
+            let status = response.status().as_u16();
+            let elapsed_ms = started.elapsed().as_millis() as u64;
+            let mismatch = match criteria.as_ref() {
+                Some(criteria) if criteria.probes_body() => {
+                    match read_limited_body(response, MAX_PROBE_RESPONSE_BYTES).await {
+                        Ok(body) => probe_response_mismatch(
+                            criteria,
+                            status,
+                            &String::from_utf8_lossy(&body),
+                        ),
+                        Err(error) => Some(format!("status {status}, {error}")),
+                    }
+                }
+                Some(criteria) => probe_response_mismatch(criteria, status, ""),
+                None => None,
+            };
+            match mismatch {
+                Some(error) => UpstreamWarmupOutcome::Failed { error },
+                None => UpstreamWarmupOutcome::Connected { status, elapsed_ms },
+            }
+        }
+        Err(error) => UpstreamWarmupOutcome::Failed {
+            error: error.to_string(),
+        },
+    };
+    UpstreamWarmupResult {
+        provider_endpoint_key,
+        upstream_origin: Some(origin),
+        outcome,
+    }
+}
+
+fn warmup_skip_reason_code(reason: &RoutingExplainSkipReason) -> &'static str {
+    match reason {
+        RoutingExplainSkipReason::UnsupportedModel { .. } => "unsupported_model",
+        RoutingExplainSkipReason::RuntimeDisabled => "runtime_disabled",
+        RoutingExplainSkipReason::OutsideSchedule => "outside_schedule",
+        RoutingExplainSkipReason::Draining => "draining",
+        RoutingExplainSkipReason::Cooldown => "cooldown",
+        RoutingExplainSkipReason::BreakerOpen { .. } => "breaker_open",
+        RoutingExplainSkipReason::UsageExhausted => "usage_exhausted",
+        RoutingExplainSkipReason::MissingAuth => "missing_auth",
+        RoutingExplainSkipReason::ConcurrencySaturated { .. } => "concurrency_saturated",
+    }
+}
+
+fn log_upstream_warmup_result(service_name: &str, result: &UpstreamWarmupResult) {
+    let origin = result.upstream_origin.as_deref().unwrap_or("-");
+    match &result.outcome {
+        UpstreamWarmupOutcome::Connected { status, elapsed_ms } => tracing::info!(
+            "[{service_name}] upstream warmup {} ({origin}): connected, status={status}, {elapsed_ms}ms",
+            result.provider_endpoint_key
+        ),
+        UpstreamWarmupOutcome::SharedOrigin => tracing::info!(
+            "[{service_name}] upstream warmup {} ({origin}): shares an already warmed origin",
+            result.provider_endpoint_key
+        ),
+        UpstreamWarmupOutcome::Skipped { reason } => tracing::info!(
+            "[{service_name}] upstream warmup {} ({origin}): skipped ({reason})",
+            result.provider_endpoint_key
+        ),
+        UpstreamWarmupOutcome::Failed { error } => tracing::warn!(
+            "[{service_name}] upstream warmup {} ({origin}): failed: {error}",
+            result.provider_endpoint_key
+        ),
+    }
+}
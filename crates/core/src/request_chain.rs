@@ -591,12 +591,14 @@ mod tests {
             session_id: Some("session-a".to_string()),
             session_identity_source: None,
             client_name: Some("codex".to_string()),
+            tenant: None,
             client_addr: Some("127.0.0.1:5555".to_string()),
             cwd: Some("C:/Users/Frankorz/private-project".to_string()),
             model: Some("gpt-5.6".to_string()),
             reasoning_effort: Some("high".to_string()),
             service_tier: Some("priority".to_string()),
             provider_id: Some("legacy-provider".to_string()),
+            body_hash: None,
             route_decision: Some(crate::state::RouteDecisionProvenance {
                 effective_upstream_base_url: Some(crate::state::ResolvedRouteValue::new(
                     "https://raw-upstream.invalid/v1?credential=url-secret",
@@ -643,6 +645,7 @@ mod tests {
             ttfb_ms: Some(250),
             streaming: true,
             ended_at_ms: 200,
+            upstream_request_id: None,
         };
         request.refresh_observability();
         request
@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 
+use crate::pricing::CostSummary;
 use crate::request_chain::{
     REQUEST_CHAIN_EXPORT_MAX_LIMIT, RequestChainExport, RequestChainSelector,
 };
@@ -11,6 +12,7 @@ use crate::runtime_store::{
 };
 use crate::state::FinishedRequest;
 use crate::usage::{CanonicalUsageBuckets, EconomicsStatus, UsageMetrics};
+use crate::usage_format::DisplayFormat;
 
 const LEDGER_SCAN_PAGE_SIZE: usize = 1_024;
 
@@ -149,13 +151,21 @@ impl<'source, Source: RequestLedgerSource + ?Sized> RequestLedger<'source, Sourc
         Ok(finished)
     }
 
+    /// Aggregates every committed economic request, with the terminal window it covered.
     pub fn summarize(
         &self,
         group: RequestUsageSummaryGroup,
         filters: &RequestLogFilters,
         limit: usize,
-    ) -> Result<Vec<RequestUsageSummaryRow>, RuntimeStoreError> {
+    ) -> Result<RequestUsageSummary, RuntimeStoreError> {
         let mut aggregate: HashMap<String, RequestUsageAggregate> = HashMap::new();
+        let mut coverage = RequestUsageSummaryCoverage {
+            source: "runtime_store_committed_terminals".to_string(),
+            first_terminal_at_ms: None,
+            last_terminal_at_ms: None,
+            requests: 0,
+            all_history: true,
+        };
         let mut cursor = None;
         loop {
             let page = self
@@ -170,6 +180,18 @@ impl<'source, Source: RequestLedgerSource + ?Sized> RequestLedger<'source, Sourc
                 if projection.payload.accounting_scope != RequestAccountingScope::Economic {
                     continue;
                 }
+                let terminal_at_ms = projection.terminal_at_unix_ms;
+                coverage.first_terminal_at_ms = Some(
+                    coverage
+                        .first_terminal_at_ms
+                        .map_or(terminal_at_ms, |first| first.min(terminal_at_ms)),
+                );
+                coverage.last_terminal_at_ms = Some(
+                    coverage
+                        .last_terminal_at_ms
+                        .map_or(terminal_at_ms, |last| last.max(terminal_at_ms)),
+                );
+                coverage.requests = coverage.requests.saturating_add(1);
                 let request = &projection.payload.finished_request;
                 aggregate
                     .entry(group.key(request))
@@ -190,7 +212,11 @@ impl<'source, Source: RequestLedgerSource + ?Sized> RequestLedger<'source, Sourc
             })
             .collect::<Vec<_>>();
         sort_usage_summary_rows(&mut rows, limit);
-        Ok(rows)
+        Ok(RequestUsageSummary {
+            group,
+            coverage,
+            rows,
+        })
     }
 
     pub fn export_request_chain(
@@ -232,6 +258,13 @@ pub struct RequestUsageAggregate {
     pub cache_read_input_tokens: i64,
     pub cache_creation_input_tokens: i64,
     pub total_tokens: i64,
+    #[serde(default)]
+    pub ttfb_ms_total: u64,
+    /// Requests that reported a TTFB; the denominator for `average_ttfb_ms`.
+    #[serde(default)]
+    pub ttfb_requests: u64,
+    #[serde(default)]
+    pub cost: CostSummary,
 }
 
 impl RequestUsageAggregate {
@@ -250,6 +283,9 @@ impl RequestUsageAggregate {
             .cache_creation_input_tokens
             .saturating_add(other.cache_creation_input_tokens);
         self.total_tokens = self.total_tokens.saturating_add(other.total_tokens);
+        self.ttfb_ms_total = self.ttfb_ms_total.saturating_add(other.ttfb_ms_total);
+        self.ttfb_requests = self.ttfb_requests.saturating_add(other.ttfb_requests);
+        self.cost.add_assign(&other.cost);
     }
 
     pub fn record_request(
@@ -258,6 +294,11 @@ impl RequestUsageAggregate {
         billable_usage: Option<&CanonicalUsageBuckets>,
     ) {
         self.record(request.duration_ms, request.usage.as_ref(), billable_usage);
+        self.cost.record_usage_cost(&request.cost);
+        if let Some(ttfb_ms) = request.observability_view().ttfb_ms {
+            self.ttfb_ms_total = self.ttfb_ms_total.saturating_add(ttfb_ms);
+            self.ttfb_requests = self.ttfb_requests.saturating_add(1);
+        }
     }
 
     pub fn record(
@@ -302,9 +343,13 @@ impl RequestUsageAggregate {
             .unwrap_or(0)
     }
 
+    pub fn average_ttfb_ms(&self) -> Option<u64> {
+        self.ttfb_ms_total.checked_div(self.ttfb_requests)
+    }
+
     pub fn summary_line(&self, group_value: &str) -> String {
         format!(
-            "{} | {} | {} | {} | {} | {} | {} | {} | {}",
+            "{} | {} | {} | {} | {} | {} | {} | {} | {} | {}",
             group_value,
             self.requests,
             self.input_tokens,
@@ -313,7 +358,10 @@ impl RequestUsageAggregate {
             self.cache_creation_input_tokens,
             self.reasoning_tokens,
             self.total_tokens,
-            self.average_duration_ms()
+            self.average_duration_ms(),
+            self.average_ttfb_ms()
+                .map(|ttfb_ms| ttfb_ms.to_string())
+                .unwrap_or_else(|| "-".to_string())
         )
     }
 }
@@ -328,14 +376,19 @@ pub enum RequestUsageSummaryGroup {
     Provider,
     Model,
     Session,
+    Tenant,
 }
 
+/// Group value for requests that did not send `x-codex-helper-tenant`.
+pub const UNTAGGED_TENANT: &str = "<untagged>";
+
 impl RequestUsageSummaryGroup {
-    pub const ALL: [Self; 4] = [
+    pub const ALL: [Self; 5] = [
         Self::ProviderEndpoint,
         Self::Provider,
         Self::Model,
         Self::Session,
+        Self::Tenant,
     ];
 
     pub fn column_name(self) -> &'static str {
@@ -344,6 +397,7 @@ impl RequestUsageSummaryGroup {
             Self::Provider => "provider_id",
             Self::Model => "model",
             Self::Session => "session_id",
+            Self::Tenant => "tenant",
         }
     }
 
@@ -355,6 +409,10 @@ impl RequestUsageSummaryGroup {
             Self::Provider => display_value(final_route_provider_id(request)),
             Self::Model => display_value(request.model.as_deref()),
             Self::Session => display_value(request.session_id.as_deref()),
+            Self::Tenant => request
+                .tenant
+                .clone()
+                .unwrap_or_else(|| UNTAGGED_TENANT.to_string()),
         }
     }
 }
@@ -415,7 +473,9 @@ pub fn format_finished_request_lines(request: &FinishedRequest) -> Vec<String> {
         request.duration_ms,
         format_optional_ms(observability.ttfb_ms),
         format_optional_speed(observability.output_tokens_per_second),
-        request.cost.display_total_with_confidence(),
+        request
+            .cost
+            .display_total_with_confidence(&DisplayFormat::default()),
     ));
     if let Some(usage) = request.usage.as_ref() {
         lines.push(format!(
@@ -528,12 +588,14 @@ mod tests {
             session_id: Some("session-7".to_string()),
             session_identity_source: None,
             client_name: None,
+            tenant: None,
             client_addr: None,
             cwd: None,
             model: Some("gpt-5".to_string()),
             reasoning_effort: None,
             service_tier: Some("priority".to_string()),
             provider_id: Some("legacy-top-level-provider".to_string()),
+            body_hash: None,
             route_decision: Some(RouteDecisionProvenance {
                 provider_id: Some("provider-a".to_string()),
                 endpoint_id: Some("endpoint-a".to_string()),
@@ -559,6 +621,7 @@ mod tests {
             ttfb_ms: Some(20),
             streaming: false,
             ended_at_ms: 1_000,
+            upstream_request_id: None,
         }
     }
 
@@ -598,6 +661,25 @@ mod tests {
         assert!(serde_json::from_str::<RequestUsageSummaryGroup>("\"station\"").is_err());
     }
 
+    #[test]
+    fn usage_summary_groups_by_tenant_with_an_untagged_fallback() {
+        let mut request = finished_request();
+        assert_eq!(
+            RequestUsageSummaryGroup::Tenant.key(&request),
+            UNTAGGED_TENANT
+        );
+
+        request.tenant = Some("alice".to_string());
+        assert_eq!(RequestUsageSummaryGroup::Tenant.key(&request), "alice");
+        assert_eq!(RequestUsageSummaryGroup::Tenant.column_name(), "tenant");
+
+        let value = serde_json::to_value(&request).expect("serialize tagged terminal");
+        assert_eq!(value["tenant"], "alice");
+        request.tenant = None;
+        let value = serde_json::to_value(&request).expect("serialize untagged terminal");
+        assert!(value.get("tenant").is_none());
+    }
+
     #[test]
     fn typed_endpoint_filter_preserves_structured_identity() {
         let provider_endpoint = ProviderEndpointKey::new("codex", "provider-a", "endpoint-a");
@@ -658,6 +740,19 @@ mod tests {
         assert_eq!(aggregate.output_tokens, 2);
         assert_eq!(aggregate.total_tokens, 12);
         assert_eq!(aggregate.average_duration_ms(), 100);
+        assert_eq!(aggregate.average_ttfb_ms(), Some(20));
+
+        let mut without_ttfb = finished_request();
+        without_ttfb.ttfb_ms = None;
+        aggregate.record_request(&without_ttfb, billable_usage.as_ref());
+        let mut streamed = finished_request();
+        streamed.streaming = true;
+        streamed.ttfb_ms = Some(40);
+        aggregate.record_request(&streamed, billable_usage.as_ref());
+        assert_eq!(aggregate.requests, 3);
+        assert_eq!(aggregate.ttfb_requests, 2);
+        assert_eq!(aggregate.average_ttfb_ms(), Some(30));
+        assert!(aggregate.summary_line("codex").ends_with("| 100 | 30"));
     }
 
     #[test]
@@ -6,7 +6,7 @@ use crate::dashboard_core::ProviderCapacity;
 use crate::routing_ir::{
     RouteCandidate, RoutePlanAttemptState, RoutePlanCandidateRuntimeSnapshot, RoutePlanExecutor,
     RoutePlanRuntimeState, RoutePlanSkipReason, RoutePlanTemplate, RouteRef, RouteRequestContext,
-    request_matches_condition,
+    SelectedRouteCandidate, request_matches_condition,
 };
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
@@ -22,6 +22,8 @@ pub struct RoutingExplainResponse {
     )]
     pub request_context: RoutingExplainRequestContext,
     pub selected_route: Option<RoutingExplainCandidate>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selection_reason: Option<RoutingExplainSelectionReason>,
     pub candidates: Vec<RoutingExplainCandidate>,
     pub affinity_policy: String,
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -36,6 +38,40 @@ impl RoutingExplainResponse {
     }
 }
 
+/// Why the selected route won over the other eligible candidates.
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RoutingExplainSelectionReason {
+    /// An operator upstream pin, for the session or the whole service, sends traffic here.
+    UpstreamPin,
+    /// The session is already bound to this provider endpoint.
+    Affinity,
+    /// The operator set a new-session preference for this provider endpoint.
+    OperatorPreference,
+    /// The entry route is manual-sticky and pins this target.
+    ManualPin,
+    /// `routing.auto_active_by_health` currently points new sessions here.
+    AutoActive,
+    /// `routing.prefer_cheapest` picked the lowest-priced candidate of the best group.
+    Cheapest,
+    /// Ordinary policy order picked the first eligible candidate.
+    Auto,
+}
+
+impl RoutingExplainSelectionReason {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RoutingExplainSelectionReason::UpstreamPin => "upstream_pin",
+            RoutingExplainSelectionReason::Affinity => "affinity",
+            RoutingExplainSelectionReason::OperatorPreference => "operator_preference",
+            RoutingExplainSelectionReason::ManualPin => "manual_pin",
+            RoutingExplainSelectionReason::AutoActive => "auto_active",
+            RoutingExplainSelectionReason::Cheapest => "cheapest",
+            RoutingExplainSelectionReason::Auto => "auto",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
 pub struct RoutingExplainRequestContext {
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -138,6 +174,8 @@ pub struct RoutingExplainAvailability {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub dominant_reason: Option<RoutingExplainSkipReason>,
     pub runtime_disabled: bool,
+    #[serde(default)]
+    pub outside_schedule: bool,
     pub cooldown_active: bool,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub cooldown_remaining_secs: Option<u64>,
@@ -156,6 +194,10 @@ pub struct RoutingExplainAvailability {
     pub effective_max_concurrent_requests: Option<u32>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub effective_limit_group: Option<String>,
+    /// Seconds left on the `routing.primary_sticky_ms` hold after a failover away from this
+    /// candidate. Held candidates stay available but are picked only when nothing else is.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub primary_sticky_remaining_secs: Option<u64>,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
@@ -179,6 +221,7 @@ pub enum RoutingExplainSkipReason {
         requested_model: String,
     },
     RuntimeDisabled,
+    OutsideSchedule,
     Draining,
     Cooldown,
     BreakerOpen {
@@ -271,6 +314,9 @@ pub fn build_routing_explain_response_with_request(
         .iter()
         .find(|candidate| candidate.selected)
         .cloned();
+    let selection_reason = selection.selected.as_ref().map(|selected| {
+        routing_explain_selection_reason(&executor, runtime, selected, request.model.as_deref())
+    });
 
     RoutingExplainResponse {
         api_version: 1,
@@ -280,6 +326,7 @@ pub fn build_routing_explain_response_with_request(
         session_id,
         request_context: RoutingExplainRequestContext::from(&request),
         selected_route,
+        selection_reason,
         candidates,
         affinity_policy: routing_affinity_policy_label(template.affinity_policy).to_string(),
         affinity: runtime
@@ -296,6 +343,47 @@ pub fn build_routing_explain_response_with_request(
     }
 }
 
+fn routing_explain_selection_reason(
+    executor: &RoutePlanExecutor<'_>,
+    runtime: &RoutePlanRuntimeState,
+    selected: &SelectedRouteCandidate<'_>,
+    request_model: Option<&str>,
+) -> RoutingExplainSelectionReason {
+    let template = executor.template();
+    let selected_key = selected.provider_endpoint.stable_key();
+    let selected_key = selected_key.as_str();
+    if runtime
+        .upstream_pin_scope_for(&selected.provider_endpoint)
+        .is_some()
+    {
+        return RoutingExplainSelectionReason::UpstreamPin;
+    }
+    if runtime
+        .affinity_provider_endpoint()
+        .is_some_and(|key| key.stable_key() == selected_key)
+    {
+        return RoutingExplainSelectionReason::Affinity;
+    }
+    if runtime.affinity_provider_endpoint().is_none()
+        && runtime
+            .new_session_preference()
+            .is_some_and(|key| key.stable_key() == selected_key)
+    {
+        return if runtime.new_session_preference_is_auto_active() {
+            RoutingExplainSelectionReason::AutoActive
+        } else {
+            RoutingExplainSelectionReason::OperatorPreference
+        };
+    }
+    if template.entry_is_manual_sticky() {
+        return RoutingExplainSelectionReason::ManualPin;
+    }
+    if executor.selection_won_on_price(runtime, selected.candidate, request_model) {
+        return RoutingExplainSelectionReason::Cheapest;
+    }
+    RoutingExplainSelectionReason::Auto
+}
+
 fn routing_affinity_policy_label(policy: RouteAffinityPolicy) -> &'static str {
     match policy {
         RouteAffinityPolicy::Off => "off",
@@ -331,6 +419,7 @@ impl From<&RoutePlanSkipReason> for RoutingExplainSkipReason {
                 }
             }
             RoutePlanSkipReason::RuntimeDisabled => RoutingExplainSkipReason::RuntimeDisabled,
+            RoutePlanSkipReason::OutsideSchedule => RoutingExplainSkipReason::OutsideSchedule,
             RoutePlanSkipReason::Draining => RoutingExplainSkipReason::Draining,
             RoutePlanSkipReason::Cooldown => RoutingExplainSkipReason::Cooldown,
             RoutePlanSkipReason::BreakerOpen { failure_count } => {
@@ -365,6 +454,7 @@ impl RoutingExplainAvailability {
             hard_unavailable: snapshot.hard_unavailable,
             dominant_reason: skip_reasons.first().cloned(),
             runtime_disabled: snapshot.runtime_disabled,
+            outside_schedule: snapshot.outside_schedule,
             cooldown_active: snapshot.cooldown_active,
             cooldown_remaining_secs: snapshot.cooldown_remaining_secs,
             breaker_open: snapshot.breaker_open,
@@ -377,6 +467,7 @@ impl RoutingExplainAvailability {
             concurrency_limit: snapshot.concurrency_limit,
             effective_max_concurrent_requests: snapshot.effective_max_concurrent_requests,
             effective_limit_group: snapshot.effective_limit_group.clone(),
+            primary_sticky_remaining_secs: snapshot.primary_sticky_remaining_secs,
         }
     }
 
@@ -398,6 +489,7 @@ impl RoutingExplainSkipReason {
         match self {
             RoutingExplainSkipReason::UnsupportedModel { .. } => "unsupported_model",
             RoutingExplainSkipReason::RuntimeDisabled => "runtime_disabled",
+            RoutingExplainSkipReason::OutsideSchedule => "outside_schedule",
             RoutingExplainSkipReason::Draining => "draining",
             RoutingExplainSkipReason::Cooldown => "cooldown",
             RoutingExplainSkipReason::BreakerOpen { .. } => "breaker_open",
@@ -14,6 +14,8 @@ use crate::credentials::{
 };
 use crate::endpoint_health::FAILURE_THRESHOLD;
 use crate::model_routing;
+use crate::pricing::ModelPriceCatalog;
+use crate::provider_schedule::ProviderSchedule;
 use crate::runtime_identity::{ContinuityDomainKey, ProviderEndpointKey, RuntimeUpstreamIdentity};
 
 #[derive(Debug, Clone)]
@@ -24,6 +26,9 @@ pub struct RoutePlanTemplate {
     pub scheduling_preset: SchedulingPreset,
     pub fallback_ttl_ms: Option<u64>,
     pub reprobe_preferred_after_ms: Option<u64>,
+    pub auto_active_by_health: bool,
+    pub prefer_cheapest: bool,
+    pub primary_sticky_ms: Option<u64>,
     pub nodes: BTreeMap<String, RouteNodePlan>,
     pub expanded_provider_order: Vec<String>,
     pub candidates: Vec<RouteCandidate>,
@@ -94,20 +99,75 @@ impl RoutePlanTemplate {
             candidate.base_url.clone(),
             candidate.continuity_domain.clone(),
         )?;
-        Ok(CapturedRouteCandidate::from_candidate(
+        let mut captured = CapturedRouteCandidate::from_candidate(
             self.service_name.as_str(),
             candidate,
             runtime_identity,
             self.credential_generation
                 .capture_bound(&provider_endpoint)?,
-        ))
+        );
+        captured.below_top_provider = self
+            .candidates
+            .first()
+            .is_some_and(|top| top.provider_id != candidate.provider_id);
+        Ok(captured)
     }
 
     pub fn continuity_topology(&self) -> RoutePlanContinuityTopology<'_> {
         RoutePlanContinuityTopology { template: self }
     }
+
+    /// Whether the entry node is a manual-sticky pin.
+    pub fn entry_is_manual_sticky(&self) -> bool {
+        self.nodes
+            .get(self.entry.as_str())
+            .is_some_and(|node| node.strategy == RouteStrategy::ManualSticky)
+    }
+
+    /// Picks the `routing.auto_active_by_health` target from `current` (the previous pick and
+    /// when it was made).
+    ///
+    /// The highest-priority healthy candidate wins, ordered by preference group and then route
+    /// order. A healthy current pick is held for [`AUTO_ACTIVE_MIN_HOLD_MS`] before moving back
+    /// up, so a recovering endpoint does not flap; an unhealthy one is replaced immediately.
+    /// A current pick outside this plan's candidates, or with no healthy replacement, is kept.
+    pub fn auto_active_target(
+        &self,
+        runtime: &RoutePlanRuntimeState,
+        current: Option<(&ProviderEndpointKey, u64)>,
+        now_ms: u64,
+    ) -> Option<ProviderEndpointKey> {
+        let healthy = |candidate: &RouteCandidate| {
+            let snapshot = runtime.candidate_runtime_snapshot(self, candidate);
+            !snapshot.hard_unavailable && !snapshot.draining && !snapshot.usage_exhausted
+        };
+        let best = self
+            .candidates
+            .iter()
+            .filter(|candidate| healthy(candidate))
+            .min_by_key(|candidate| (candidate.preference_group, candidate.stable_index))
+            .map(|candidate| self.candidate_provider_endpoint_key(candidate));
+        let Some((current, selected_at_ms)) = current else {
+            return best;
+        };
+        let Some(current_candidate) = self
+            .candidates
+            .iter()
+            .find(|candidate| self.candidate_provider_endpoint_key(candidate) == *current)
+        else {
+            return Some(current.clone());
+        };
+        let within_hold = now_ms.saturating_sub(selected_at_ms) < AUTO_ACTIVE_MIN_HOLD_MS;
+        if healthy(current_candidate) && within_hold {
+            return Some(current.clone());
+        }
+        best.or_else(|| Some(current.clone()))
+    }
 }
 
+/// Minimum time a healthy auto-active pick is kept before returning to a higher-priority one.
+pub const AUTO_ACTIVE_MIN_HOLD_MS: u64 = 60_000;
+
 #[derive(Debug, Clone, Copy)]
 pub struct RoutePlanContinuityTopology<'a> {
     template: &'a RoutePlanTemplate,
@@ -237,6 +297,7 @@ pub struct RouteCandidate {
     pub preference_group: u32,
     pub stable_index: usize,
     pub concurrency: RouteCandidateConcurrency,
+    pub schedule: Option<Arc<ProviderSchedule>>,
 }
 
 #[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
@@ -285,6 +346,7 @@ pub(crate) struct CapturedRouteCandidate {
     continuity_domain: ContinuityDomainKey,
     runtime_identity: RuntimeUpstreamIdentity,
     credential: CapturedUpstreamCredential,
+    below_top_provider: bool,
 }
 
 impl CapturedRouteCandidate {
@@ -311,6 +373,7 @@ impl CapturedRouteCandidate {
             continuity_domain,
             runtime_identity,
             credential,
+            below_top_provider: false,
         }
     }
 
@@ -387,6 +450,11 @@ impl CapturedRouteCandidate {
         self.candidate.preference_group
     }
 
+    /// Whether the plan's first candidate belongs to another provider.
+    pub(crate) fn below_top_provider(&self) -> bool {
+        self.below_top_provider
+    }
+
     pub(crate) fn route_path(&self) -> &[String] {
         &self.candidate.route_path
     }
@@ -495,6 +563,25 @@ impl RoutePlanAttemptState {
     }
 }
 
+/// Where an [`UpstreamPin`] was set.
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum UpstreamPinScope {
+    Session,
+    Global,
+}
+
+/// An operator pin that sends requests to one provider endpoint ahead of load balancing,
+/// affinity and the new-session preference.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpstreamPin {
+    pub target: ProviderEndpointKey,
+    /// Fall back to normal selection when the pinned endpoint is unavailable or has already
+    /// failed this request. Without it such requests fail instead.
+    pub failover: bool,
+    pub scope: UpstreamPinScope,
+}
+
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct RoutePlanRuntimeState {
     provider_endpoints: BTreeMap<ProviderEndpointKey, RoutePlanUpstreamRuntimeState>,
@@ -502,6 +589,52 @@ pub struct RoutePlanRuntimeState {
     affinity_last_selected_at_ms: Option<u64>,
     affinity_last_changed_at_ms: Option<u64>,
     new_session_preference: Option<ProviderEndpointKey>,
+    new_session_preference_is_auto_active: bool,
+    upstream_pin: Option<UpstreamPin>,
+    pricing: Option<RoutePricing>,
+}
+
+/// Model prices consulted by `routing.prefer_cheapest`, captured once per request.
+///
+/// Equality is by identity so runtime snapshots stay cheap to compare.
+#[derive(Clone)]
+pub struct RoutePricing(Arc<ModelPriceCatalog>);
+
+impl RoutePricing {
+    pub fn new(catalog: Arc<ModelPriceCatalog>) -> Self {
+        Self(catalog)
+    }
+
+    /// Input plus output price per million tokens, in femto-USD, of the model `candidate` would
+    /// send upstream for `requested_model`. Only prices filed under the provider id count.
+    fn unit_cost(&self, candidate: &RouteCandidate, requested_model: &str) -> Option<i128> {
+        let price = self.0.price_for_provider_model(
+            candidate.provider_id.as_str(),
+            candidate.effective_model(requested_model).as_str(),
+        )?;
+        Some(
+            price
+                .input_per_1m
+                .femto_usd()
+                .saturating_add(price.output_per_1m.femto_usd()),
+        )
+    }
+}
+
+impl PartialEq for RoutePricing {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for RoutePricing {}
+
+impl std::fmt::Debug for RoutePricing {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RoutePricing")
+            .field("models", &self.0.len())
+            .finish()
+    }
 }
 
 impl RoutePlanRuntimeState {
@@ -557,18 +690,64 @@ impl RoutePlanRuntimeState {
 
     pub fn set_new_session_preference(&mut self, key: Option<ProviderEndpointKey>) {
         self.new_session_preference = key;
+        self.new_session_preference_is_auto_active = false;
+    }
+
+    /// Uses the `routing.auto_active_by_health` pick as the new-session preference.
+    pub fn set_auto_active_preference(&mut self, key: ProviderEndpointKey) {
+        self.new_session_preference = Some(key);
+        self.new_session_preference_is_auto_active = true;
+    }
+
+    pub fn new_session_preference_is_auto_active(&self) -> bool {
+        self.new_session_preference_is_auto_active
     }
 
     pub fn new_session_preference(&self) -> Option<&ProviderEndpointKey> {
         self.new_session_preference.as_ref()
     }
 
+    pub fn set_upstream_pin(&mut self, pin: Option<UpstreamPin>) {
+        self.upstream_pin = pin;
+    }
+
+    pub fn upstream_pin(&self) -> Option<&UpstreamPin> {
+        self.upstream_pin.as_ref()
+    }
+
+    pub fn set_pricing(&mut self, pricing: Option<RoutePricing>) {
+        self.pricing = pricing;
+    }
+
+    /// The pin scope when `key` is the pinned endpoint, for request-log provenance.
+    pub fn upstream_pin_scope_for(&self, key: &ProviderEndpointKey) -> Option<UpstreamPinScope> {
+        self.upstream_pin
+            .as_ref()
+            .filter(|pin| pin.target == *key)
+            .map(|pin| pin.scope)
+    }
+
     fn runtime_state_for_candidate(
         &self,
         template: &RoutePlanTemplate,
         candidate: &RouteCandidate,
     ) -> RoutePlanUpstreamRuntimeState {
-        self.provider_endpoint(&candidate_provider_endpoint_key(template, candidate))
+        let key = candidate_provider_endpoint_key(template, candidate);
+        let mut state = self.provider_endpoint(&key);
+        if state.outside_schedule && self.is_operator_choice(&key) {
+            state.outside_schedule = false;
+        }
+        state
+    }
+
+    /// Whether `key` is pinned (session or global) or is the operator's own new-session
+    /// preference. Provider schedules never override such an explicit choice.
+    fn is_operator_choice(&self, key: &ProviderEndpointKey) -> bool {
+        self.upstream_pin
+            .as_ref()
+            .is_some_and(|pin| pin.target == *key)
+            || (!self.new_session_preference_is_auto_active
+                && self.new_session_preference.as_ref() == Some(key))
     }
 
     pub fn candidate_runtime_snapshot(
@@ -581,6 +760,54 @@ impl RoutePlanRuntimeState {
             self.runtime_state_for_candidate(template, candidate),
         )
     }
+
+    /// Marks candidates whose provider `enabled_schedule` window is closed at `now_ms`.
+    ///
+    /// A manual-sticky entry is an explicit operator pin, so schedules do not apply to it. Nor
+    /// do they apply to an upstream pin or an operator-set new-session preference, whenever
+    /// those are set.
+    pub fn apply_provider_schedules(&mut self, template: &RoutePlanTemplate, now_ms: u64) {
+        if template.entry_is_manual_sticky() {
+            return;
+        }
+        let Some(now) = chrono::DateTime::from_timestamp_millis(now_ms as i64) else {
+            return;
+        };
+        for candidate in &template.candidates {
+            let Some(schedule) = candidate.schedule.as_ref() else {
+                continue;
+            };
+            if schedule.is_active_at(now) {
+                continue;
+            }
+            let key = template.candidate_provider_endpoint_key(candidate);
+            let mut state = self.provider_endpoint(&key);
+            state.outside_schedule = true;
+            self.set_provider_endpoint(key, state);
+        }
+    }
+
+    /// Marks candidates still inside their `routing.primary_sticky_ms` hold. `holds` maps
+    /// provider endpoints to the wall-clock ms at which their hold ends.
+    pub fn apply_primary_sticky_holds(
+        &mut self,
+        template: &RoutePlanTemplate,
+        holds: &BTreeMap<ProviderEndpointKey, u64>,
+        now_ms: u64,
+    ) {
+        if template.primary_sticky_ms.is_none() {
+            return;
+        }
+        for candidate in &template.candidates {
+            let key = template.candidate_provider_endpoint_key(candidate);
+            let Some(until_ms) = holds.get(&key).copied().filter(|until| *until > now_ms) else {
+                continue;
+            };
+            let mut state = self.provider_endpoint(&key);
+            state.primary_sticky_remaining_ms = Some(until_ms - now_ms);
+            self.set_provider_endpoint(key, state);
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
@@ -595,6 +822,9 @@ pub struct RoutePlanUpstreamRuntimeState {
     pub concurrency_saturated: bool,
     pub concurrency_active: Option<u32>,
     pub concurrency_limit: Option<u32>,
+    pub outside_schedule: bool,
+    /// Time left on the `routing.primary_sticky_ms` hold after a failover away from it.
+    pub primary_sticky_remaining_ms: Option<u64>,
 }
 
 impl RoutePlanUpstreamRuntimeState {
@@ -603,7 +833,10 @@ impl RoutePlanUpstreamRuntimeState {
     }
 
     fn hard_unavailable(self) -> bool {
-        self.runtime_disabled || !self.credential_readiness.is_routable() || self.breaker_open()
+        self.runtime_disabled
+            || self.outside_schedule
+            || !self.credential_readiness.is_routable()
+            || self.breaker_open()
     }
 }
 
@@ -627,6 +860,8 @@ pub struct RoutePlanCandidateRuntimeSnapshot {
     pub concurrency_limit: Option<u32>,
     pub effective_max_concurrent_requests: Option<u32>,
     pub effective_limit_group: Option<String>,
+    pub outside_schedule: bool,
+    pub primary_sticky_remaining_secs: Option<u64>,
 }
 
 impl RoutePlanCandidateRuntimeSnapshot {
@@ -662,6 +897,10 @@ impl RoutePlanCandidateRuntimeSnapshot {
             concurrency_limit: runtime_state.concurrency_limit,
             effective_max_concurrent_requests: candidate.concurrency.max_concurrent_requests,
             effective_limit_group: candidate.concurrency.limit_group.clone(),
+            outside_schedule: runtime_state.outside_schedule,
+            primary_sticky_remaining_secs: runtime_state
+                .primary_sticky_remaining_ms
+                .map(|remaining_ms| remaining_ms.div_ceil(1_000)),
         }
     }
 
@@ -687,6 +926,9 @@ impl RoutePlanCandidateRuntimeSnapshot {
         if self.runtime_disabled {
             reasons.push(RoutePlanSkipReason::RuntimeDisabled);
         }
+        if self.outside_schedule {
+            reasons.push(RoutePlanSkipReason::OutsideSchedule);
+        }
         if self.draining {
             reasons.push(RoutePlanSkipReason::Draining);
         }
@@ -723,6 +965,7 @@ pub enum RoutePlanSkipReason {
         requested_model: String,
     },
     RuntimeDisabled,
+    OutsideSchedule,
     Draining,
     Cooldown,
     BreakerOpen {
@@ -741,6 +984,7 @@ impl RoutePlanSkipReason {
         match self {
             RoutePlanSkipReason::UnsupportedModel { .. } => "unsupported_model",
             RoutePlanSkipReason::RuntimeDisabled => "runtime_disabled",
+            RoutePlanSkipReason::OutsideSchedule => "outside_schedule",
             RoutePlanSkipReason::Draining => "draining",
             RoutePlanSkipReason::Cooldown => "cooldown",
             RoutePlanSkipReason::BreakerOpen { .. } => "breaker_open",
@@ -821,6 +1065,31 @@ impl<'a> RoutePlanExecutor<'a> {
             .collect()
     }
 
+    /// Whether `routing.prefer_cheapest` decided the pick: `selected` is among the cheapest
+    /// priced candidates of its preference group and the price filter dropped another one.
+    pub fn selection_won_on_price(
+        &self,
+        runtime: &RoutePlanRuntimeState,
+        selected: &RouteCandidate,
+        request_model: Option<&str>,
+    ) -> bool {
+        let group = self
+            .template
+            .candidates
+            .iter()
+            .filter(|candidate| {
+                candidate.preference_group == selected.preference_group
+                    && candidate_available_in_runtime(self.template, runtime, candidate)
+            })
+            .collect::<Vec<_>>();
+        let group_len = group.len();
+        let cheapest = cheapest_priced_candidates(self.template, runtime, group, request_model);
+        cheapest.len() < group_len
+            && cheapest
+                .iter()
+                .any(|candidate| std::ptr::eq(*candidate, selected))
+    }
+
     pub fn select_supported_candidate(
         &self,
         state: &mut RoutePlanAttemptState,
@@ -891,6 +1160,14 @@ impl<'a> RoutePlanExecutor<'a> {
         }) {
             return false;
         }
+        if let Some(pin) = runtime.upstream_pin() {
+            let pinned_available = available.iter().any(|available| {
+                candidate_provider_endpoint_key(self.template, available) == pin.target
+            });
+            if pinned_available || !pin.failover {
+                return candidate_provider_endpoint_key(self.template, candidate) == pin.target;
+            }
+        }
 
         let best_group = available
             .iter()
@@ -1108,6 +1385,18 @@ fn best_candidate_by_affinity_selection_mode<'a>(
     affinity_mode: RoutePlanAffinitySelectionMode,
     request_model: Option<&str>,
 ) -> Option<&'a RouteCandidate> {
+    if let Some(pin) = runtime.upstream_pin() {
+        let pinned = candidates.iter().copied().find(|candidate| {
+            candidate_provider_endpoint_key(template, candidate) == pin.target
+                && request_model.is_none_or(|model| candidate_supports_model(candidate, model))
+                && candidate_available_in_runtime(template, runtime, candidate)
+        });
+        if pinned.is_some() || !pin.failover {
+            return pinned;
+        }
+    }
+    let unheld = candidates_outside_primary_sticky_hold(template, runtime, candidates);
+    let candidates = unheld.as_deref().unwrap_or(candidates);
     if runtime.affinity_provider_endpoint().is_none()
         && let Some(preferred) =
             new_session_preference_candidate(template, runtime, candidates, request_model)
@@ -1151,6 +1440,33 @@ fn best_candidate_by_affinity_selection_mode<'a>(
     }
 }
 
+/// Drops candidates held by `routing.primary_sticky_ms` while another candidate is routable,
+/// so a recovered primary is not retried before its hold ends. `None` leaves the list as is.
+fn candidates_outside_primary_sticky_hold<'a>(
+    template: &RoutePlanTemplate,
+    runtime: &RoutePlanRuntimeState,
+    candidates: &[&'a RouteCandidate],
+) -> Option<Vec<&'a RouteCandidate>> {
+    let held = |candidate: &RouteCandidate| {
+        runtime
+            .runtime_state_for_candidate(template, candidate)
+            .primary_sticky_remaining_ms
+            .is_some()
+    };
+    if !candidates.iter().any(|candidate| held(candidate)) {
+        return None;
+    }
+    let unheld = candidates
+        .iter()
+        .copied()
+        .filter(|candidate| !held(candidate))
+        .collect::<Vec<_>>();
+    unheld
+        .iter()
+        .any(|candidate| candidate_available_in_runtime(template, runtime, candidate))
+        .then_some(unheld)
+}
+
 fn affinity_policy_for_selection(
     configured: RouteAffinityPolicy,
     affinity_mode: RoutePlanAffinitySelectionMode,
@@ -1176,14 +1492,19 @@ fn first_candidate_in_best_preference_group<'a>(
             && candidate_available_in_runtime(template, runtime, candidate)
     });
     let best_group_candidates = best_group_candidates.collect::<Vec<_>>();
-    weighted_round_robin_candidate(
-        template,
-        runtime,
-        best_group,
-        &best_group_candidates,
-        request_model,
-    )
-    .or_else(|| best_group_candidates.into_iter().next())
+    let best_group_candidates =
+        cheapest_priced_candidates(template, runtime, best_group_candidates, request_model);
+    least_recent_errors_candidate(template, runtime, &best_group_candidates, request_model)
+        .or_else(|| {
+            weighted_round_robin_candidate(
+                template,
+                runtime,
+                best_group,
+                &best_group_candidates,
+                request_model,
+            )
+        })
+        .or_else(|| best_group_candidates.into_iter().next())
 }
 
 fn best_candidate_in_preference_group<'a>(
@@ -1204,14 +1525,54 @@ fn best_candidate_in_preference_group<'a>(
             && candidate_available_in_runtime(template, runtime, candidate)
     });
     let best_group_candidates = best_group_candidates.collect::<Vec<_>>();
-    weighted_round_robin_candidate(
-        template,
-        runtime,
-        best_group,
-        &best_group_candidates,
+    let best_group_candidates =
+        cheapest_priced_candidates(template, runtime, best_group_candidates, request_model);
+    least_recent_errors_candidate(template, runtime, &best_group_candidates, request_model)
+        .or_else(|| {
+            weighted_round_robin_candidate(
+                template,
+                runtime,
+                best_group,
+                &best_group_candidates,
+                request_model,
+            )
+        })
+        .or_else(|| best_group_candidates.into_iter().next())
+}
+
+/// Keeps only the cheapest priced candidates when `routing.prefer_cheapest` is on.
+///
+/// Unpriced candidates survive only when nothing in the group is priced, so they are tried after
+/// every priced one. The usual tie-breakers then run on what is left.
+fn cheapest_priced_candidates<'a>(
+    template: &RoutePlanTemplate,
+    runtime: &RoutePlanRuntimeState,
+    candidates: Vec<&'a RouteCandidate>,
+    request_model: Option<&str>,
+) -> Vec<&'a RouteCandidate> {
+    let (true, Some(pricing), Some(model)) = (
+        template.prefer_cheapest,
+        runtime.pricing.as_ref(),
         request_model,
-    )
-    .or_else(|| best_group_candidates.into_iter().next())
+    ) else {
+        return candidates;
+    };
+    let costs = candidates
+        .iter()
+        .map(|candidate| {
+            candidate_supports_model(candidate, model)
+                .then(|| pricing.unit_cost(candidate, model))
+                .flatten()
+        })
+        .collect::<Vec<_>>();
+    let Some(cheapest) = costs.iter().flatten().min().copied() else {
+        return candidates;
+    };
+    candidates
+        .into_iter()
+        .zip(costs)
+        .filter_map(|(candidate, cost)| (cost == Some(cheapest)).then_some(candidate))
+        .collect()
 }
 
 fn best_available_preference_group(
@@ -1379,6 +1740,43 @@ fn candidate_uses_round_robin(template: &RoutePlanTemplate, candidate: &RouteCan
     })
 }
 
+fn candidate_uses_least_recent_errors(
+    template: &RoutePlanTemplate,
+    candidate: &RouteCandidate,
+) -> bool {
+    candidate.route_path.iter().any(|route_name| {
+        template
+            .nodes
+            .get(route_name)
+            .is_some_and(|node| node.strategy == RouteStrategy::LeastRecentErrors)
+    })
+}
+
+/// Picks the candidate with the fewest consecutive failures among the
+/// already-available best group, keeping config order as the tie breaker.
+fn least_recent_errors_candidate<'a>(
+    template: &RoutePlanTemplate,
+    runtime: &RoutePlanRuntimeState,
+    candidates: &[&'a RouteCandidate],
+    request_model: Option<&str>,
+) -> Option<&'a RouteCandidate> {
+    candidates
+        .iter()
+        .copied()
+        .filter(|candidate| {
+            candidate_uses_least_recent_errors(template, candidate)
+                && request_model.is_none_or(|model| candidate_supports_model(candidate, model))
+        })
+        .min_by_key(|candidate| {
+            (
+                runtime
+                    .runtime_state_for_candidate(template, candidate)
+                    .failure_count,
+                candidate.stable_index,
+            )
+        })
+}
+
 fn affinity_candidate<'a>(
     template: &RoutePlanTemplate,
     runtime: &RoutePlanRuntimeState,
@@ -1600,6 +1998,10 @@ impl CompiledRouteGraph {
     pub fn compile(service_name: &str, view: &ServiceRouteConfig) -> Result<Self> {
         let routing = effective_routing(view);
         validate_route_provider_name_conflicts(service_name, view, &routing)?;
+        validate_fallback_alert(service_name, &routing)?;
+        if routing.primary_sticky_ms == Some(0) {
+            anyhow::bail!("[{service_name}] routing.primary_sticky_ms must be positive");
+        }
         let nodes = normalize_route_nodes(service_name, view, &routing)?;
         let expansion = RouteExpansionContext {
             request: &RouteRequestContext::default(),
@@ -1743,6 +2145,9 @@ impl CompiledRouteGraph {
             scheduling_preset: self.routing.scheduling_preset,
             fallback_ttl_ms: self.routing.fallback_ttl_ms,
             reprobe_preferred_after_ms: self.routing.reprobe_preferred_after_ms,
+            auto_active_by_health: self.routing.auto_active_by_health,
+            prefer_cheapest: self.routing.prefer_cheapest,
+            primary_sticky_ms: self.routing.primary_sticky_ms,
             nodes: self.nodes.clone(),
             expanded_provider_order: leaves.iter().map(|leaf| leaf.provider_id.clone()).collect(),
             candidates,
@@ -1788,6 +2193,20 @@ fn validate_route_provider_name_conflicts(
     Ok(())
 }
 
+fn validate_fallback_alert(service_name: &str, routing: &RouteGraphConfig) -> Result<()> {
+    if let Some(threshold_pct) = routing.fallback_alert_threshold_pct
+        && !(1..=100).contains(&threshold_pct)
+    {
+        anyhow::bail!(
+            "[{service_name}] routing.fallback_alert_threshold_pct must be between 1 and 100, got {threshold_pct}"
+        );
+    }
+    if routing.fallback_alert_window_secs == Some(0) {
+        anyhow::bail!("[{service_name}] routing.fallback_alert_window_secs must be positive");
+    }
+    Ok(())
+}
+
 fn normalize_route_nodes(
     service_name: &str,
     view: &ServiceRouteConfig,
@@ -2070,7 +2489,7 @@ fn expand_route_node(
         RouteStrategy::OrderedFailover => {
             expand_ordered_route_children(service_name, view, routing, &frame, expansion, stack)
         }
-        RouteStrategy::RoundRobin => {
+        RouteStrategy::RoundRobin | RouteStrategy::LeastRecentErrors => {
             expand_round_robin_route_children(service_name, view, routing, &frame, expansion, stack)
         }
         RouteStrategy::ManualSticky => {
@@ -2129,7 +2548,8 @@ fn expand_round_robin_route_children(
 ) -> Result<Vec<RouteLeaf>> {
     if frame.node.children.is_empty() {
         anyhow::bail!(
-            "[{service_name}] round-robin route '{}' requires at least one child",
+            "[{service_name}] {} route '{}' requires at least one child",
+            routing_policy_name(frame.node.strategy),
             frame.route_name
         );
     }
@@ -2834,6 +3254,7 @@ fn routing_policy_name(policy: RouteStrategy) -> &'static str {
         RouteStrategy::ManualSticky => "manual-sticky",
         RouteStrategy::OrderedFailover => "ordered-failover",
         RouteStrategy::RoundRobin => "round-robin",
+        RouteStrategy::LeastRecentErrors => "least-recent-errors",
         RouteStrategy::TagPreferred => "tag-preferred",
         RouteStrategy::Conditional => "conditional",
     }
@@ -2864,6 +3285,7 @@ fn route_candidates_from_leaves(
         }
 
         let auth = provider.effective_auth();
+        let schedule = ProviderSchedule::from_provider(provider).map(Arc::new);
         for endpoint in
             ordered_provider_endpoints(service_name, leaf.provider_id.as_str(), provider)?
         {
@@ -2916,6 +3338,7 @@ fn route_candidates_from_leaves(
                 preference_group: leaf.preference_group,
                 stable_index,
                 concurrency: effective_candidate_concurrency(&provider.limits, &endpoint.limits),
+                schedule: schedule.clone(),
             });
         }
     }
@@ -4529,6 +4952,9 @@ mod tests {
             scheduling_preset: SchedulingPreset::Balanced,
             fallback_ttl_ms: None,
             reprobe_preferred_after_ms: None,
+            auto_active_by_health: false,
+            prefer_cheapest: false,
+            primary_sticky_ms: None,
             nodes: BTreeMap::new(),
             expanded_provider_order: vec!["relay".to_string()],
             candidates: vec![
@@ -4547,6 +4973,7 @@ mod tests {
                     preference_group: 0,
                     stable_index: 0,
                     concurrency: RouteCandidateConcurrency::default(),
+                    schedule: None,
                 },
                 RouteCandidate {
                     provider_id: "relay".to_string(),
@@ -4563,6 +4990,7 @@ mod tests {
                     preference_group: 1,
                     stable_index: 1,
                     concurrency: RouteCandidateConcurrency::default(),
+                    schedule: None,
                 },
             ],
             credential_generation: CredentialGeneration::empty(),
@@ -4987,6 +5415,307 @@ mod tests {
         assert_eq!(selected.candidate.provider_id, "healthy");
     }
 
+    #[test]
+    fn prefer_cheapest_orders_the_best_group_by_price_and_keeps_unpriced_last() {
+        use crate::pricing::{ModelPrice, ModelPriceCatalog};
+
+        let price = |provider: &str, model: &str, input: &str, output: &str| {
+            ModelPrice::from_per_million_usd_for_provider(
+                provider, model, None, input, output, None, None, "test",
+            )
+            .expect("valid test price")
+        };
+        let pricing = RoutePricing::new(Arc::new(ModelPriceCatalog::with_prices([
+            price("pricey", "gpt-5", "2", "8"),
+            price("cheap", "gpt-5", "1", "4"),
+            price("mapped", "gpt-5-mini", "0.1", "0.4"),
+        ])));
+        let mut mapped = provider("https://mapped.example/v1");
+        mapped
+            .model_mapping
+            .insert("gpt-5".to_string(), "gpt-5-mini".to_string());
+        let mut routing = RouteGraphConfig::round_robin(vec![
+            "unpriced".to_string(),
+            "pricey".to_string(),
+            "cheap".to_string(),
+            "mapped".to_string(),
+        ]);
+        routing.prefer_cheapest = true;
+        let view = ServiceRouteConfig {
+            providers: BTreeMap::from([
+                (
+                    "unpriced".to_string(),
+                    provider("https://unpriced.example/v1"),
+                ),
+                ("pricey".to_string(), provider("https://pricey.example/v1")),
+                ("cheap".to_string(), provider("https://cheap.example/v1")),
+                ("mapped".to_string(), mapped),
+            ]),
+            routing: Some(routing),
+            ..ServiceRouteConfig::default()
+        };
+        let template = compile_route_plan_template("codex", &view).expect("route template");
+        assert!(template.prefer_cheapest);
+        let executor = RoutePlanExecutor::new(&template);
+        let mut runtime = RoutePlanRuntimeState::default();
+        runtime.set_pricing(Some(pricing));
+
+        let mut state = RoutePlanAttemptState::default();
+        let mut tried = Vec::new();
+        while let Some(selected) = executor
+            .select_supported_candidate_with_runtime_state(&mut state, &runtime, Some("gpt-5"))
+            .selected
+        {
+            tried.push(selected.candidate.provider_id.clone());
+            state.avoid_selected(&selected);
+        }
+        assert_eq!(tried, vec!["mapped", "cheap", "pricey", "unpriced"]);
+    }
+
+    #[test]
+    fn prefer_cheapest_never_crosses_preference_groups() {
+        use crate::pricing::{ModelPrice, ModelPriceCatalog};
+
+        let pricing = RoutePricing::new(Arc::new(ModelPriceCatalog::with_prices([
+            ModelPrice::from_per_million_usd_for_provider(
+                "backup", "gpt-5", None, "0.1", "0.1", None, None, "test",
+            )
+            .expect("valid test price"),
+        ])));
+        let mut routing =
+            RouteGraphConfig::ordered_failover(vec!["primary".to_string(), "backup".to_string()]);
+        routing.prefer_cheapest = true;
+        let view = ServiceRouteConfig {
+            providers: BTreeMap::from([
+                (
+                    "primary".to_string(),
+                    provider("https://primary.example/v1"),
+                ),
+                ("backup".to_string(), provider("https://backup.example/v1")),
+            ]),
+            routing: Some(routing),
+            ..ServiceRouteConfig::default()
+        };
+        let template = compile_route_plan_template("codex", &view).expect("route template");
+        let executor = RoutePlanExecutor::new(&template);
+        let mut runtime = RoutePlanRuntimeState::default();
+        runtime.set_pricing(Some(pricing));
+
+        let selected = executor
+            .select_supported_candidate_with_runtime_state(
+                &mut RoutePlanAttemptState::default(),
+                &runtime,
+                Some("gpt-5"),
+            )
+            .selected
+            .expect("primary selected");
+        assert_eq!(selected.candidate.provider_id, "primary");
+    }
+
+    #[test]
+    fn primary_sticky_hold_prefers_the_backup_until_it_is_the_only_choice() {
+        let mut routing =
+            RouteGraphConfig::ordered_failover(vec!["primary".to_string(), "backup".to_string()]);
+        routing.primary_sticky_ms = Some(30_000);
+        let view = ServiceRouteConfig {
+            providers: BTreeMap::from([
+                (
+                    "primary".to_string(),
+                    provider("https://primary.example/v1"),
+                ),
+                ("backup".to_string(), provider("https://backup.example/v1")),
+            ]),
+            routing: Some(routing),
+            ..ServiceRouteConfig::default()
+        };
+        let template = compile_route_plan_template("codex", &view).expect("route template");
+        let executor = RoutePlanExecutor::new(&template);
+        let primary = ProviderEndpointKey::new("codex", "primary", "default");
+        let backup = ProviderEndpointKey::new("codex", "backup", "default");
+        let select = |runtime: &RoutePlanRuntimeState| {
+            executor
+                .select_supported_candidate_with_runtime_state(
+                    &mut RoutePlanAttemptState::default(),
+                    runtime,
+                    None,
+                )
+                .selected
+                .expect("candidate selected")
+                .candidate
+                .provider_id
+                .clone()
+        };
+
+        let mut runtime = RoutePlanRuntimeState::default();
+        let holds = BTreeMap::from([(primary.clone(), 10_500)]);
+        runtime.apply_primary_sticky_holds(&template, &holds, 1_000);
+        assert_eq!(select(&runtime), "backup");
+        let primary_candidate = &template.candidates[0];
+        assert_eq!(
+            runtime
+                .candidate_runtime_snapshot(&template, primary_candidate)
+                .primary_sticky_remaining_secs,
+            Some(10)
+        );
+
+        // A held primary is still used when the backup cannot take the request.
+        let mut backup_state = runtime.provider_endpoint(&backup);
+        backup_state.runtime_disabled = true;
+        runtime.set_provider_endpoint(backup, backup_state);
+        assert_eq!(select(&runtime), "primary");
+
+        // An expired hold no longer applies.
+        let mut runtime = RoutePlanRuntimeState::default();
+        runtime.apply_primary_sticky_holds(&template, &holds, 10_500);
+        assert_eq!(select(&runtime), "primary");
+    }
+
+    #[test]
+    fn provider_schedule_gates_automatic_routing_but_not_manual_sticky() {
+        // 2026-06-01T12:00:00Z is a Monday.
+        let monday_noon_ms = 1_780_315_200_000;
+        let off_peak = ProviderConfig {
+            enabled_schedule: vec!["Mon-Fri 20:00-08:00".to_string()],
+            schedule_timezone: Some("UTC".to_string()),
+            ..provider("https://cheap.example/v1")
+        };
+        let mut view = ServiceRouteConfig {
+            providers: BTreeMap::from([
+                ("cheap".to_string(), off_peak),
+                ("steady".to_string(), provider("https://steady.example/v1")),
+            ]),
+            routing: Some(RouteGraphConfig::ordered_failover(vec![
+                "cheap".to_string(),
+                "steady".to_string(),
+            ])),
+            ..ServiceRouteConfig::default()
+        };
+        let template = compile_route_plan_template("codex", &view).expect("route template");
+        let executor = RoutePlanExecutor::new(&template);
+
+        let mut runtime = RoutePlanRuntimeState::default();
+        runtime.apply_provider_schedules(&template, monday_noon_ms);
+        let explanations =
+            executor.explain_candidate_skip_reasons_with_runtime_state(&runtime, None);
+        assert_eq!(explanations.len(), 1);
+        assert_eq!(explanations[0].candidate.provider_id, "cheap");
+        assert_eq!(
+            explanations[0].reasons,
+            vec![RoutePlanSkipReason::OutsideSchedule]
+        );
+        let selection = executor.select_supported_candidate_with_runtime_state(
+            &mut RoutePlanAttemptState::default(),
+            &runtime,
+            None,
+        );
+        assert_eq!(
+            selection
+                .selected
+                .expect("steady selected")
+                .candidate
+                .provider_id,
+            "steady"
+        );
+
+        let mut runtime = RoutePlanRuntimeState::default();
+        runtime.apply_provider_schedules(&template, monday_noon_ms + 9 * 3_600_000);
+        let selection = executor.select_supported_candidate_with_runtime_state(
+            &mut RoutePlanAttemptState::default(),
+            &runtime,
+            None,
+        );
+        assert_eq!(
+            selection
+                .selected
+                .expect("cheap selected")
+                .candidate
+                .provider_id,
+            "cheap"
+        );
+
+        view.routing = Some(RouteGraphConfig::manual_sticky(
+            "cheap".to_string(),
+            vec!["cheap".to_string(), "steady".to_string()],
+        ));
+        let template = compile_route_plan_template("codex", &view).expect("route template");
+        let mut runtime = RoutePlanRuntimeState::default();
+        runtime.apply_provider_schedules(&template, monday_noon_ms);
+        let executor = RoutePlanExecutor::new(&template);
+        let selection = executor.select_supported_candidate_with_runtime_state(
+            &mut RoutePlanAttemptState::default(),
+            &runtime,
+            None,
+        );
+        assert_eq!(
+            selection
+                .selected
+                .expect("pinned provider selected")
+                .candidate
+                .provider_id,
+            "cheap"
+        );
+    }
+
+    #[test]
+    fn provider_schedule_does_not_override_operator_pins_or_preference() {
+        // 2026-06-01T12:00:00Z is a Monday.
+        let monday_noon_ms = 1_780_315_200_000;
+        let off_peak = ProviderConfig {
+            enabled_schedule: vec!["Mon-Fri 20:00-08:00".to_string()],
+            schedule_timezone: Some("UTC".to_string()),
+            ..provider("https://cheap.example/v1")
+        };
+        let view = ServiceRouteConfig {
+            providers: BTreeMap::from([
+                ("cheap".to_string(), off_peak),
+                ("steady".to_string(), provider("https://steady.example/v1")),
+            ]),
+            routing: Some(RouteGraphConfig::ordered_failover(vec![
+                "steady".to_string(),
+                "cheap".to_string(),
+            ])),
+            ..ServiceRouteConfig::default()
+        };
+        let template = compile_route_plan_template("codex", &view).expect("route template");
+        let executor = RoutePlanExecutor::new(&template);
+        let cheap = ProviderEndpointKey::new("codex", "cheap", "default");
+        let selected = |runtime: &RoutePlanRuntimeState| {
+            executor
+                .select_supported_candidate_with_runtime_state(
+                    &mut RoutePlanAttemptState::default(),
+                    runtime,
+                    None,
+                )
+                .selected
+                .expect("candidate selected")
+                .candidate
+                .provider_id
+                .clone()
+        };
+
+        for scope in [UpstreamPinScope::Session, UpstreamPinScope::Global] {
+            let mut runtime = RoutePlanRuntimeState::default();
+            runtime.apply_provider_schedules(&template, monday_noon_ms);
+            runtime.set_upstream_pin(Some(UpstreamPin {
+                target: cheap.clone(),
+                failover: false,
+                scope,
+            }));
+            assert_eq!(selected(&runtime), "cheap", "{scope:?} pin");
+        }
+
+        let mut runtime = RoutePlanRuntimeState::default();
+        runtime.set_new_session_preference(Some(cheap.clone()));
+        runtime.apply_provider_schedules(&template, monday_noon_ms);
+        assert_eq!(selected(&runtime), "cheap", "operator preference");
+
+        // The health-driven pick is automatic, so the schedule still gates it.
+        let mut runtime = RoutePlanRuntimeState::default();
+        runtime.set_auto_active_preference(cheap);
+        runtime.apply_provider_schedules(&template, monday_noon_ms);
+        assert_eq!(selected(&runtime), "steady", "auto-active preference");
+    }
+
     #[test]
     fn route_plan_executor_skips_saturated_candidate_without_failure_penalty() {
         let view = ServiceRouteConfig {
@@ -6027,6 +6756,159 @@ mod tests {
         assert_eq!(selected.candidate.provider_id, "input");
     }
 
+    #[test]
+    fn upstream_pin_overrides_affinity_and_only_fails_over_when_allowed() {
+        let routing =
+            RouteGraphConfig::ordered_failover(vec!["input".to_string(), "ciii".to_string()]);
+        let view = ServiceRouteConfig {
+            providers: BTreeMap::from([
+                (
+                    "input".to_string(),
+                    limited_provider("https://pin-input.example/v1", 20),
+                ),
+                (
+                    "ciii".to_string(),
+                    limited_provider("https://pin-ciii.example/v1", 15),
+                ),
+            ]),
+            routing: Some(routing),
+            ..ServiceRouteConfig::default()
+        };
+        let template = compile_route_plan_template("codex", &view).expect("route template");
+        let executor = RoutePlanExecutor::new(&template);
+        let pinned = endpoint_key("codex", "ciii", "default");
+        let mut runtime = RoutePlanRuntimeState::default();
+        runtime.set_affinity_provider_endpoint(Some(endpoint_key("codex", "input", "default")));
+        runtime.set_upstream_pin(Some(UpstreamPin {
+            target: pinned.clone(),
+            failover: false,
+            scope: UpstreamPinScope::Session,
+        }));
+
+        let mut attempt = RoutePlanAttemptState::default();
+        let selected = executor
+            .select_supported_candidate_with_runtime_state(&mut attempt, &runtime, None)
+            .selected
+            .expect("pinned candidate");
+        assert_eq!(selected.provider_endpoint, pinned);
+        assert_eq!(
+            runtime.upstream_pin_scope_for(&pinned),
+            Some(UpstreamPinScope::Session)
+        );
+
+        attempt.avoid_selected(&selected);
+        assert!(
+            executor
+                .select_supported_candidate_with_runtime_state(&mut attempt, &runtime, None)
+                .selected
+                .is_none(),
+            "a strict pin must not fail over"
+        );
+
+        runtime.set_upstream_pin(Some(UpstreamPin {
+            target: pinned,
+            failover: true,
+            scope: UpstreamPinScope::Global,
+        }));
+        let selected = executor
+            .select_supported_candidate_with_runtime_state(&mut attempt, &runtime, None)
+            .selected
+            .expect("failover candidate");
+        assert_eq!(selected.candidate.provider_id, "input");
+    }
+
+    #[test]
+    fn fallback_alert_threshold_must_be_a_percentage() {
+        let mut routing =
+            RouteGraphConfig::ordered_failover(vec!["input".to_string(), "ciii".to_string()]);
+        routing.fallback_alert_threshold_pct = Some(101);
+        let mut view = ServiceRouteConfig {
+            providers: BTreeMap::from([
+                (
+                    "input".to_string(),
+                    limited_provider("https://fallback-alert-input.example/v1", 20),
+                ),
+                (
+                    "ciii".to_string(),
+                    limited_provider("https://fallback-alert-ciii.example/v1", 15),
+                ),
+            ]),
+            routing: Some(routing),
+            ..ServiceRouteConfig::default()
+        };
+
+        let error = compile_route_plan_template("codex", &view).expect_err("invalid threshold");
+        assert!(
+            error
+                .to_string()
+                .contains("fallback_alert_threshold_pct must be between 1 and 100")
+        );
+
+        let routing = view.routing.as_mut().expect("routing");
+        routing.fallback_alert_threshold_pct = Some(30);
+        assert_eq!(routing.fallback_alert(), Some((30, 600)));
+        compile_route_plan_template("codex", &view).expect("valid threshold");
+    }
+
+    #[test]
+    fn auto_active_target_follows_health_with_a_minimum_hold() {
+        let mut routing =
+            RouteGraphConfig::ordered_failover(vec!["input".to_string(), "ciii".to_string()]);
+        routing.auto_active_by_health = true;
+        let view = ServiceRouteConfig {
+            providers: BTreeMap::from([
+                (
+                    "input".to_string(),
+                    limited_provider("https://auto-active-input.example/v1", 20),
+                ),
+                (
+                    "ciii".to_string(),
+                    limited_provider("https://auto-active-ciii.example/v1", 15),
+                ),
+            ]),
+            routing: Some(routing),
+            ..ServiceRouteConfig::default()
+        };
+        let template = compile_route_plan_template("codex", &view).expect("route template");
+        let primary = endpoint_key("codex", "input", "default");
+        let backup = endpoint_key("codex", "ciii", "default");
+        let mut runtime = RoutePlanRuntimeState::default();
+
+        assert!(template.auto_active_by_health);
+        assert_eq!(
+            template.auto_active_target(&runtime, None, 0),
+            Some(primary.clone())
+        );
+
+        runtime.set_provider_endpoint(
+            primary.clone(),
+            RoutePlanUpstreamRuntimeState {
+                cooldown_active: true,
+                ..RoutePlanUpstreamRuntimeState::default()
+            },
+        );
+        assert_eq!(
+            template.auto_active_target(&runtime, Some((&primary, 0)), 1_000),
+            Some(backup.clone()),
+            "an unhealthy pick is replaced immediately"
+        );
+
+        runtime.set_provider_endpoint(primary.clone(), RoutePlanUpstreamRuntimeState::default());
+        assert_eq!(
+            template.auto_active_target(&runtime, Some((&backup, 1_000)), 2_000),
+            Some(backup.clone()),
+            "a recovered higher-priority endpoint waits out the hold"
+        );
+        assert_eq!(
+            template.auto_active_target(
+                &runtime,
+                Some((&backup, 1_000)),
+                1_000 + AUTO_ACTIVE_MIN_HOLD_MS
+            ),
+            Some(primary)
+        );
+    }
+
     #[test]
     fn draining_endpoint_keeps_existing_affinity_but_rejects_new_sessions() {
         let mut routing =
@@ -6420,6 +7302,143 @@ mod tests {
         }
     }
 
+    #[test]
+    fn least_recent_errors_prefers_fewest_failures_and_skips_cooldown() {
+        let view = ServiceRouteConfig {
+            providers: BTreeMap::from([
+                (
+                    "alpha".to_string(),
+                    provider("https://lre-alpha.example/v1"),
+                ),
+                ("beta".to_string(), provider("https://lre-beta.example/v1")),
+                (
+                    "gamma".to_string(),
+                    provider("https://lre-gamma.example/v1"),
+                ),
+            ]),
+            routing: Some(RouteGraphConfig::least_recent_errors(vec![
+                "alpha".to_string(),
+                "beta".to_string(),
+                "gamma".to_string(),
+            ])),
+            ..ServiceRouteConfig::default()
+        };
+        let template = compile_route_plan_template("codex", &view).expect("route template");
+        assert_eq!(
+            provider_preference_groups(&template),
+            vec![
+                ("alpha".to_string(), 0),
+                ("beta".to_string(), 0),
+                ("gamma".to_string(), 0)
+            ]
+        );
+        let executor = RoutePlanExecutor::new(&template);
+        let select = |runtime: &RoutePlanRuntimeState| {
+            executor
+                .select_supported_candidate_with_runtime_state(
+                    &mut RoutePlanAttemptState::default(),
+                    runtime,
+                    None,
+                )
+                .selected
+                .expect("least-recent-errors candidate")
+                .candidate
+                .provider_id
+                .clone()
+        };
+
+        let mut runtime = RoutePlanRuntimeState::default();
+        assert_eq!(select(&runtime), "alpha");
+
+        runtime.set_provider_endpoint(
+            endpoint_key("codex", "alpha", "default"),
+            RoutePlanUpstreamRuntimeState {
+                failure_count: 2,
+                ..RoutePlanUpstreamRuntimeState::default()
+            },
+        );
+        runtime.set_provider_endpoint(
+            endpoint_key("codex", "beta", "default"),
+            RoutePlanUpstreamRuntimeState {
+                failure_count: 1,
+                ..RoutePlanUpstreamRuntimeState::default()
+            },
+        );
+        runtime.set_provider_endpoint(
+            endpoint_key("codex", "gamma", "default"),
+            RoutePlanUpstreamRuntimeState {
+                failure_count: 1,
+                ..RoutePlanUpstreamRuntimeState::default()
+            },
+        );
+        assert_eq!(select(&runtime), "beta");
+
+        runtime.set_provider_endpoint(
+            endpoint_key("codex", "beta", "default"),
+            RoutePlanUpstreamRuntimeState {
+                cooldown_active: true,
+                ..RoutePlanUpstreamRuntimeState::default()
+            },
+        );
+        assert_eq!(select(&runtime), "gamma");
+    }
+
+    #[test]
+    fn each_strategy_picks_its_expected_upstream_from_the_same_runtime_state() {
+        let providers = BTreeMap::from([
+            (
+                "alpha".to_string(),
+                provider("https://cmp-alpha.example/v1"),
+            ),
+            ("beta".to_string(), provider("https://cmp-beta.example/v1")),
+        ]);
+        let children = vec!["alpha".to_string(), "beta".to_string()];
+        let mut runtime = RoutePlanRuntimeState::default();
+        runtime.set_provider_endpoint(
+            endpoint_key("codex", "alpha", "default"),
+            RoutePlanUpstreamRuntimeState {
+                failure_count: 2,
+                ..RoutePlanUpstreamRuntimeState::default()
+            },
+        );
+        let selections = |routing: RouteGraphConfig, rounds: usize| {
+            let view = ServiceRouteConfig {
+                providers: providers.clone(),
+                routing: Some(routing),
+                ..ServiceRouteConfig::default()
+            };
+            let template = compile_route_plan_template("codex", &view).expect("route template");
+            let executor = RoutePlanExecutor::new(&template);
+            (0..rounds)
+                .map(|_| {
+                    executor
+                        .select_supported_candidate_with_runtime_state(
+                            &mut RoutePlanAttemptState::default(),
+                            &runtime,
+                            None,
+                        )
+                        .selected
+                        .expect("selected candidate")
+                        .candidate
+                        .provider_id
+                        .clone()
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            selections(RouteGraphConfig::ordered_failover(children.clone()), 2),
+            vec!["alpha", "alpha"]
+        );
+        let mut round_robin = selections(RouteGraphConfig::round_robin(children.clone()), 2);
+        round_robin.sort();
+        assert_eq!(round_robin, vec!["alpha", "beta"]);
+        assert_eq!(
+            selections(RouteGraphConfig::least_recent_errors(children), 2),
+            vec!["beta", "beta"]
+        );
+    }
+
     #[test]
     fn round_robin_revalidation_does_not_advance_cursor() {
         fn compile_round_robin_view(prefix: &str) -> RoutePlanTemplate {
@@ -1,6 +1,7 @@
 #[cfg(test)]
 use std::future::Future;
 use std::net::{IpAddr, SocketAddr};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -24,6 +25,10 @@ use crate::runtime_store::RuntimeStore;
 use crate::service_target::ServiceRuntimeIdentity;
 use crate::state::ProxyState;
 
+mod shutdown_drain;
+
+use shutdown_drain::ShutdownDrain;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ProxyLifetimeMode {
     Ephemeral,
@@ -41,6 +46,7 @@ pub struct ProxyRuntime {
     pub host: IpAddr,
     pub port: u16,
     pub admin_addr: SocketAddr,
+    pub unix_socket: Option<PathBuf>,
     pub config: Arc<HelperConfig>,
     pub proxy: ProxyService,
     pub state: Arc<ProxyState>,
@@ -49,6 +55,7 @@ pub struct ProxyRuntime {
     runtime_store: Arc<RuntimeStore>,
     listener: Option<tokio::net::TcpListener>,
     admin_listener: Option<tokio::net::TcpListener>,
+    unix_listener: Option<UnixSocketListener>,
     app: Option<Router>,
     admin_app: Option<Router>,
 }
@@ -58,6 +65,7 @@ pub struct RunningProxyRuntime {
     pub host: IpAddr,
     pub port: u16,
     pub admin_addr: SocketAddr,
+    pub unix_socket: Option<PathBuf>,
     pub shutdown_tx: watch::Sender<bool>,
     runtime_join_handle: JoinHandle<RuntimeTaskJoinResults>,
     server_abort_handle: AbortHandle,
@@ -78,6 +86,8 @@ pub struct ProxyRuntimeOptions {
     pub credential_sources: CredentialSourceCapabilities,
     pub service_runtime_identity: Option<ServiceRuntimeIdentity>,
     pub local_runtime_shutdown_policy: crate::local_operator::LocalRuntimeShutdownPolicy,
+    /// Also serve the proxy router on this Unix domain socket (Unix only).
+    pub unix_socket: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -127,6 +137,7 @@ impl ProxyRuntimeOptions {
             service_runtime_identity: None,
             local_runtime_shutdown_policy:
                 crate::local_operator::LocalRuntimeShutdownPolicy::ForegroundProcess,
+            unix_socket: None,
         }
     }
 
@@ -158,6 +169,11 @@ impl ProxyRuntimeOptions {
         self.local_runtime_shutdown_policy = policy;
         self
     }
+
+    pub fn with_unix_socket(mut self, unix_socket: Option<PathBuf>) -> Self {
+        self.unix_socket = unix_socket;
+        self
+    }
 }
 
 impl ProxyRuntime {
@@ -174,6 +190,7 @@ impl ProxyRuntime {
             .admin_listener
             .take()
             .expect("proxy runtime admin listener should exist before start");
+        let unix_listener = self.unix_listener.take();
         let app = self
             .app
             .take()
@@ -207,8 +224,15 @@ impl ProxyRuntime {
             self.proxy.clone(),
             self.shutdown_rx.clone(),
         );
-        let server_handle =
-            spawn_proxy_runtime_servers(listener, admin_listener, app, admin_app, shutdown_rx);
+        let server_handle = spawn_proxy_runtime_servers(
+            listener,
+            admin_listener,
+            unix_listener,
+            app,
+            admin_app,
+            shutdown_rx,
+            self.config.serve.shutdown_drain_timeout(),
+        );
         let server_abort_handle = server_handle.abort_handle();
         let quota_sampler_abort_handle = quota_sampler_handle.abort_handle();
         let runtime_join_handle = spawn_runtime_task_joiner(
@@ -224,6 +248,7 @@ impl ProxyRuntime {
             host: self.host,
             port: self.port,
             admin_addr: self.admin_addr,
+            unix_socket: self.unix_socket,
             shutdown_tx: self.shutdown_tx,
             runtime_join_handle,
             server_abort_handle,
@@ -469,8 +494,15 @@ async fn build_proxy_runtime_from_loaded_with_options_and_runtime_store(
         credential_sources,
         service_runtime_identity,
         local_runtime_shutdown_policy,
+        unix_socket,
     } = options;
     validate_service_has_upstream(service_name, &loaded.source)?;
+    for warning in crate::model_routing::effort_override_warnings(&loaded.source) {
+        tracing::warn!("{warning}");
+    }
+    if let Some(warning) = crate::doctor::inline_secret_warning(&loaded.source) {
+        tracing::warn!("{warning}");
+    }
     let client = crate::proxy::upstream_http_client_builder()
         .connect_timeout(std::time::Duration::from_secs(10))
         .tcp_keepalive(std::time::Duration::from_secs(30))
@@ -509,12 +541,17 @@ async fn build_proxy_runtime_from_loaded_with_options_and_runtime_store(
     let addr: SocketAddr = SocketAddr::from((host, port));
     let listener = bind_listener(addr, ProxyListenerKind::Proxy).await?;
     let admin_listener = bind_listener(admin_addr, ProxyListenerKind::Admin).await?;
+    let unix_listener = unix_socket
+        .as_deref()
+        .map(bind_unix_socket_listener)
+        .transpose()?;
 
     Ok(ProxyRuntime {
         service_name,
         host,
         port,
         admin_addr,
+        unix_socket,
         config: runtime_config,
         proxy,
         state,
@@ -523,6 +560,7 @@ async fn build_proxy_runtime_from_loaded_with_options_and_runtime_store(
         runtime_store,
         listener: Some(listener),
         admin_listener: Some(admin_listener),
+        unix_listener,
         app: Some(app),
         admin_app: Some(admin_app),
     })
@@ -625,6 +663,64 @@ async fn bind_listener(
         .map_err(|source| ProxyListenerBindError { kind, addr, source }.into())
 }
 
+/// A bound proxy Unix domain socket together with the guard that removes its socket file.
+#[cfg(unix)]
+struct UnixSocketListener {
+    listener: tokio::net::UnixListener,
+    cleanup: UnixSocketCleanup,
+}
+
+#[cfg(not(unix))]
+enum UnixSocketListener {}
+
+/// Removes the socket file on drop, so it is cleaned up on graceful shutdown and on abort.
+#[cfg(unix)]
+struct UnixSocketCleanup(PathBuf);
+
+#[cfg(unix)]
+impl Drop for UnixSocketCleanup {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+#[cfg(unix)]
+fn bind_unix_socket_listener(path: &Path) -> Result<UnixSocketListener> {
+    use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+
+    if let Ok(metadata) = std::fs::symlink_metadata(path) {
+        anyhow::ensure!(
+            metadata.file_type().is_socket(),
+            "refusing to replace {}: it exists and is not a Unix socket",
+            path.display()
+        );
+        anyhow::ensure!(
+            std::os::unix::net::UnixStream::connect(path).is_err(),
+            "Unix socket {} is already in use by another process",
+            path.display()
+        );
+        std::fs::remove_file(path)
+            .with_context(|| format!("remove stale Unix socket {}", path.display()))?;
+    }
+    let listener = tokio::net::UnixListener::bind(path)
+        .with_context(|| format!("failed to bind proxy Unix socket {}", path.display()))?;
+    let listener = UnixSocketListener {
+        listener,
+        cleanup: UnixSocketCleanup(path.to_path_buf()),
+    };
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))
+        .with_context(|| format!("restrict permissions of Unix socket {}", path.display()))?;
+    Ok(listener)
+}
+
+#[cfg(not(unix))]
+fn bind_unix_socket_listener(path: &Path) -> Result<UnixSocketListener> {
+    anyhow::bail!(
+        "cannot listen on {}: Unix domain sockets are only supported on Unix platforms",
+        path.display()
+    )
+}
+
 pub fn service_name_for_kind(service_kind: ServiceKind) -> &'static str {
     match service_kind {
         ServiceKind::Codex => "codex",
@@ -656,16 +752,23 @@ pub fn validate_service_has_upstream(service_name: &str, cfg: &HelperConfig) ->
 fn spawn_proxy_runtime_servers(
     listener: tokio::net::TcpListener,
     admin_listener: tokio::net::TcpListener,
+    unix_listener: Option<UnixSocketListener>,
     app: Router,
     admin_app: Router,
     shutdown_rx: watch::Receiver<bool>,
+    drain_timeout: Duration,
 ) -> JoinHandle<Result<()>> {
+    let drain = ShutdownDrain::new();
+    let app = drain.layer(app);
+    let admin_app = drain.layer(admin_app);
+    let drain_shutdown_rx = shutdown_rx.clone();
     let proxy_server_shutdown = {
         let mut rx = shutdown_rx.clone();
         async move {
             let _ = rx.changed().await;
         }
     };
+    let unix_server = serve_unix_socket(unix_listener, app.clone(), shutdown_rx.clone());
     let admin_server_shutdown = {
         let mut rx = shutdown_rx;
         async move {
@@ -673,7 +776,7 @@ fn spawn_proxy_runtime_servers(
         }
     };
 
-    tokio::spawn(async move {
+    let servers = async move {
         tokio::try_join!(
             axum::serve(
                 listener,
@@ -685,16 +788,51 @@ fn spawn_proxy_runtime_servers(
                 admin_app.into_make_service_with_connect_info::<SocketAddr>(),
             )
             .with_graceful_shutdown(admin_server_shutdown),
+            unix_server,
         )?;
         Ok(())
-    })
+    };
+
+    tokio::spawn(drain.run(servers, drain_shutdown_rx, drain_timeout))
+}
+
+#[cfg(unix)]
+async fn serve_unix_socket(
+    unix_listener: Option<UnixSocketListener>,
+    app: Router,
+    mut shutdown_rx: watch::Receiver<bool>,
+) -> std::io::Result<()> {
+    let Some(UnixSocketListener {
+        listener,
+        cleanup: _cleanup,
+    }) = unix_listener
+    else {
+        return Ok(());
+    };
+    axum::serve(listener, app.into_make_service())
+        .with_graceful_shutdown(async move {
+            let _ = shutdown_rx.changed().await;
+        })
+        .await
+}
+
+#[cfg(not(unix))]
+async fn serve_unix_socket(
+    unix_listener: Option<UnixSocketListener>,
+    _app: Router,
+    _shutdown_rx: watch::Receiver<bool>,
+) -> std::io::Result<()> {
+    match unix_listener {
+        None => Ok(()),
+        Some(listener) => match listener {},
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::path::Path;
-    use std::sync::{Condvar, Mutex as StdMutex, OnceLock};
+    use std::sync::{Condvar, Mutex as StdMutex};
 
     struct TaskDropSignal(Option<tokio::sync::oneshot::Sender<()>>);
 
@@ -764,13 +902,7 @@ mod tests {
         }
     }
 
-    fn env_lock() -> std::sync::MutexGuard<'static, ()> {
-        static LOCK: OnceLock<StdMutex<()>> = OnceLock::new();
-        match LOCK.get_or_init(|| StdMutex::new(())).lock() {
-            Ok(guard) => guard,
-            Err(error) => error.into_inner(),
-        }
-    }
+    use crate::test_support::env_lock_blocking as env_lock;
 
     #[test]
     fn quota_sampler_failure_dominates_a_suppressed_target() {
@@ -1247,6 +1379,7 @@ env_key = "EXTERNAL_API_KEY"
             host: IpAddr::V4(std::net::Ipv4Addr::LOCALHOST),
             port: 0,
             admin_addr: SocketAddr::from(([127, 0, 0, 1], 0)),
+            unix_socket: None,
             shutdown_tx,
             runtime_join_handle,
             server_abort_handle,
@@ -1425,6 +1558,100 @@ env_key = "EXTERNAL_API_KEY"
         let _ = std::fs::remove_dir_all(root);
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn unix_socket_listener_serves_proxy_and_is_removed_on_shutdown() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let _lock = env_lock();
+        let root = std::env::temp_dir().join(format!(
+            "codex-helper-runtime-unix-socket-test-{}",
+            uuid::Uuid::new_v4()
+        ));
+        let helper_home = root.join(".codex-helper");
+        std::fs::create_dir_all(&root).expect("create test root");
+        let socket_path = root.join("proxy.sock");
+        // A leftover socket file from a crashed run must be replaced, not rejected.
+        drop(std::os::unix::net::UnixListener::bind(&socket_path).expect("bind stale socket"));
+        let mut env = ScopedEnv::new();
+        unsafe {
+            env.set_path("CODEX_HELPER_HOME", &helper_home);
+        }
+
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("build tokio runtime");
+        runtime.block_on(async {
+            let proxy_runtime = build_proxy_runtime_from_loaded_with_options(
+                "codex",
+                IpAddr::V4(std::net::Ipv4Addr::LOCALHOST),
+                0,
+                ProxyRuntimeOptions::for_proxy_port(0)
+                    .with_admin_addr(SocketAddr::from(([127, 0, 0, 1], 0)))
+                    .with_unix_socket(Some(socket_path.clone())),
+                loaded_runtime_test_config(),
+            )
+            .await
+            .expect("build proxy runtime with unix socket");
+            let shutdown_tx = proxy_runtime.shutdown_tx.clone();
+            let mut running = proxy_runtime.start();
+            assert_eq!(running.unix_socket.as_deref(), Some(socket_path.as_path()));
+
+            let mut stream = tokio::net::UnixStream::connect(&socket_path)
+                .await
+                .expect("connect proxy unix socket");
+            stream
+                .write_all(b"GET /__codex_helper/missing HTTP/1.1\r\nhost: localhost\r\nconnection: close\r\n\r\n")
+                .await
+                .expect("write request");
+            let mut response = Vec::new();
+            stream
+                .read_to_end(&mut response)
+                .await
+                .expect("read response");
+            assert!(
+                response.starts_with(b"HTTP/1.1 "),
+                "unexpected response: {}",
+                String::from_utf8_lossy(&response)
+            );
+
+            shutdown_tx.send(true).expect("request runtime shutdown");
+            running.wait().await.expect("wait for runtime shutdown");
+        });
+
+        assert!(!socket_path.exists(), "socket file should be removed");
+        drop(env);
+        let _ = std::fs::remove_dir_all(root);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn unix_socket_listener_refuses_to_replace_regular_files() {
+        let root = std::env::temp_dir().join(format!(
+            "codex-helper-runtime-unix-socket-file-test-{}",
+            uuid::Uuid::new_v4()
+        ));
+        std::fs::create_dir_all(&root).expect("create test root");
+        let path = root.join("proxy.sock");
+        std::fs::write(&path, "keep").expect("write regular file");
+
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("build tokio runtime");
+        let error = match runtime.block_on(async { bind_unix_socket_listener(&path) }) {
+            Ok(_) => panic!("regular file must not be replaced"),
+            Err(error) => error,
+        };
+        assert!(
+            error.to_string().contains("is not a Unix socket"),
+            "{error:#}"
+        );
+        assert_eq!(std::fs::read_to_string(&path).expect("file kept"), "keep");
+        let _ = std::fs::remove_dir_all(root);
+    }
+
     async fn run_runtime_config_driver_early_exit_case(
         runtime_config_driver_handle: JoinHandle<()>,
     ) -> anyhow::Error {
@@ -1485,4 +1712,63 @@ env_key = "EXTERNAL_API_KEY"
                 .contains("runtime config driver exited before runtime shutdown")
         );
     }
+
+    #[tokio::test]
+    async fn shutdown_force_closes_a_stalled_handler_after_the_drain_timeout() {
+        let (handler_started_tx, handler_started_rx) = tokio::sync::oneshot::channel();
+        let handler_started_tx = Arc::new(StdMutex::new(Some(handler_started_tx)));
+        let app = Router::new().route(
+            "/stall",
+            axum::routing::get(move || {
+                let handler_started_tx = Arc::clone(&handler_started_tx);
+                async move {
+                    if let Some(sender) = handler_started_tx
+                        .lock()
+                        .unwrap_or_else(std::sync::PoisonError::into_inner)
+                        .take()
+                    {
+                        let _ = sender.send(());
+                    }
+                    std::future::pending::<&'static str>().await
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind proxy listener");
+        let proxy_addr = listener.local_addr().expect("proxy listener address");
+        let admin_listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind admin listener");
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        let server_handle = spawn_proxy_runtime_servers(
+            listener,
+            admin_listener,
+            None,
+            app,
+            Router::new(),
+            shutdown_rx,
+            Duration::from_millis(100),
+        );
+
+        let request = tokio::spawn(async move {
+            reqwest::Client::new()
+                .get(format!("http://{proxy_addr}/stall"))
+                .send()
+                .await
+        });
+        handler_started_rx.await.expect("stalled handler started");
+        shutdown_tx.send(true).expect("request shutdown");
+
+        tokio::time::timeout(Duration::from_secs(10), server_handle)
+            .await
+            .expect("drain timeout must bound the graceful shutdown")
+            .expect("join server task")
+            .expect("force-closed shutdown still succeeds");
+        let response = request
+            .await
+            .expect("join stalled request")
+            .expect("stalled request gets an answer");
+        assert_eq!(response.status(), reqwest::StatusCode::SERVICE_UNAVAILABLE);
+    }
 }
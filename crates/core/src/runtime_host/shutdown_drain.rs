@@ -0,0 +1,152 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use anyhow::Result;
+use axum::Router;
+use axum::body::{Body, Bytes, HttpBody};
+use axum::extract::Request;
+use axum::http::StatusCode;
+use axum::middleware::{self, Next};
+use axum::response::IntoResponse;
+use http_body::{Frame, SizeHint};
+use tokio::sync::watch;
+
+use super::wait_for_runtime_shutdown;
+
+/// How long listeners get to wind down after their remaining requests were force-closed.
+const FORCE_CLOSE_GRACE: Duration = Duration::from_secs(1);
+
+/// Bounds the graceful shutdown of the proxy listeners.
+///
+/// Routers passed through [`ShutdownDrain::layer`] count their in-flight requests and, once the
+/// drain timeout elapses, answer stalled handlers with `503` and end open response bodies with
+/// an error so hyper closes those connections instead of waiting on them forever.
+pub(super) struct ShutdownDrain {
+    in_flight: Arc<AtomicUsize>,
+    force_close_tx: watch::Sender<bool>,
+}
+
+impl ShutdownDrain {
+    pub(super) fn new() -> Self {
+        let (force_close_tx, _) = watch::channel(false);
+        Self {
+            in_flight: Arc::new(AtomicUsize::new(0)),
+            force_close_tx,
+        }
+    }
+
+    pub(super) fn layer(&self, router: Router) -> Router {
+        let in_flight = Arc::clone(&self.in_flight);
+        let force_close_rx = self.force_close_tx.subscribe();
+        router.layer(middleware::from_fn(move |request: Request, next: Next| {
+            let in_flight = InFlightRequest::enter(&in_flight);
+            let force_close_rx = force_close_rx.clone();
+            async move {
+                let mut force_closed = Box::pin(wait_for_force_close(force_close_rx));
+                tokio::select! {
+                    response = next.run(request) => response.map(|body| {
+                        Body::new(DrainBody {
+                            inner: body,
+                            force_closed: Some(force_closed),
+                            _in_flight: in_flight,
+                        })
+                    }),
+                    () = &mut force_closed => StatusCode::SERVICE_UNAVAILABLE.into_response(),
+                }
+            }
+        }))
+    }
+
+    /// Runs `servers` to completion, force-closing whatever is still in flight once shutdown
+    /// has been requested and `timeout` has elapsed.
+    pub(super) async fn run(
+        self,
+        servers: impl Future<Output = Result<()>>,
+        mut shutdown_rx: watch::Receiver<bool>,
+        timeout: Duration,
+    ) -> Result<()> {
+        tokio::pin!(servers);
+        tokio::select! {
+            result = &mut servers => return result,
+            () = wait_for_runtime_shutdown(&mut shutdown_rx) => {}
+        }
+        if let Ok(result) = tokio::time::timeout(timeout, &mut servers).await {
+            return result;
+        }
+
+        let active = self.in_flight.load(Ordering::SeqCst);
+        tracing::warn!(
+            active_requests = active,
+            "shutdown drain timed out after {}ms; force-closing {active} active request(s)",
+            timeout.as_millis()
+        );
+        let _ = self.force_close_tx.send(true);
+        match tokio::time::timeout(FORCE_CLOSE_GRACE, &mut servers).await {
+            Ok(result) => result,
+            Err(_) => {
+                tracing::warn!("listeners did not stop after force-close; abandoning them");
+                Ok(())
+            }
+        }
+    }
+}
+
+async fn wait_for_force_close(mut force_close_rx: watch::Receiver<bool>) {
+    // A dropped sender means the drain itself was torn down, which also ends the request.
+    let _ = force_close_rx.wait_for(|closed| *closed).await;
+}
+
+struct InFlightRequest(Arc<AtomicUsize>);
+
+impl InFlightRequest {
+    fn enter(in_flight: &Arc<AtomicUsize>) -> Self {
+        in_flight.fetch_add(1, Ordering::SeqCst);
+        Self(Arc::clone(in_flight))
+    }
+}
+
+impl Drop for InFlightRequest {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Response body that stays counted as in flight until it ends and errors out on force-close.
+struct DrainBody {
+    inner: Body,
+    force_closed: Option<Pin<Box<dyn Future<Output = ()> + Send>>>,
+    _in_flight: InFlightRequest,
+}
+
+impl HttpBody for DrainBody {
+    type Data = Bytes;
+    type Error = axum::Error;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let Some(force_closed) = self.force_closed.as_mut() else {
+            return Poll::Ready(None);
+        };
+        if force_closed.as_mut().poll(cx).is_ready() {
+            self.force_closed = None;
+            return Poll::Ready(Some(Err(axum::Error::new(std::io::Error::other(
+                "response force-closed after the shutdown drain timeout",
+            )))));
+        }
+        Pin::new(&mut self.inner).poll_frame(cx)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.force_closed.is_none() || self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
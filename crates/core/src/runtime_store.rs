@@ -3057,12 +3057,15 @@ mod tests {
                 session_id: None,
                 session_identity_source: None,
                 client_name: None,
+                tenant: None,
                 client_addr: None,
                 cwd: None,
                 model: Some("gpt-5".to_string()),
                 reasoning_effort: None,
                 service_tier: None,
                 provider_id: None,
+                body_hash: None,
+                upstream_request_id: None,
                 route_decision: None,
                 usage: None,
                 cost: crate::pricing::CostBreakdown::unknown(),
@@ -1,5 +1,6 @@
 use std::cmp::{Ordering, Reverse};
 use std::collections::HashMap;
+use std::ops::ControlFlow;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
@@ -19,7 +20,10 @@ mod stats_cache;
 mod transcript;
 
 use stats_cache::{SessionStatsCache, SessionStatsSnapshot};
-pub use transcript::{codex_session_transcript_tail_contains_query, read_codex_session_transcript};
+pub use transcript::{
+    codex_session_transcript_tail_contains_query, estimate_transcript_tokens,
+    read_codex_session_transcript, read_codex_session_transcript_with_tool_calls,
+};
 
 /// Summary information for a Codex conversation session.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -75,6 +79,37 @@ pub struct RecentSession {
     pub mtime_ms: u64,
 }
 
+/// A session whose user messages matched a cross-project search.
+#[derive(Debug, Clone)]
+pub struct SessionSearchHit {
+    pub id: String,
+    pub path: PathBuf,
+    pub cwd: Option<String>,
+    pub mtime_ms: u64,
+    /// Whitespace-collapsed excerpt around the first matching user message.
+    pub snippet: String,
+}
+
+/// A session JSONL line that `session watch` should alert on.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SessionAlertEvent {
+    /// Codex recorded an `error` event for the turn.
+    Error {
+        timestamp: Option<String>,
+        message: String,
+    },
+    /// The turn was aborted for a reason other than a user interrupt.
+    TurnAborted {
+        timestamp: Option<String>,
+        reason: String,
+    },
+    /// Codex recorded `task_complete`: the agent finished the turn.
+    TaskComplete {
+        timestamp: Option<String>,
+        last_agent_message: Option<String>,
+    },
+}
+
 #[derive(
     Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord, Default,
 )]
@@ -204,22 +239,73 @@ pub async fn find_codex_sessions_for_dir(
     limit: usize,
 ) -> Result<Vec<SessionSummary>> {
     let sessions_dir = codex_sessions_dir();
-    find_codex_sessions_for_dir_in_sessions_dir(&sessions_dir, root_dir, limit).await
+    find_codex_sessions_for_dir_in_sessions_dir(&sessions_dir, root_dir, limit, MAX_SCAN_FILES)
+        .await
+}
+
+/// Every user-facing Codex session whose cwd matches `root_dir`, newest first by updated_at.
+///
+/// Unlike `find_codex_sessions_for_dir`, the scan is not capped at the newest `MAX_SCAN_FILES`
+/// session files, so bulk exports cover the whole history.
+pub async fn find_all_codex_sessions_for_dir(root_dir: &Path) -> Result<Vec<SessionSummary>> {
+    let sessions_dir = codex_sessions_dir();
+    find_codex_sessions_for_dir_in_sessions_dir(&sessions_dir, root_dir, usize::MAX, usize::MAX)
+        .await
 }
 
 async fn find_codex_sessions_for_dir_in_sessions_dir(
     sessions_dir: &Path,
     root_dir: &Path,
     limit: usize,
+    max_scan_files: usize,
 ) -> Result<Vec<SessionSummary>> {
-    if !sessions_dir.exists() {
+    let mut out = find_codex_sessions_page_for_dir_in_sessions_dir(
+        sessions_dir,
+        root_dir,
+        0,
+        limit,
+        max_scan_files,
+    )
+    .await?;
+    sort_by_updated_desc(&mut out);
+    Ok(out)
+}
+
+/// Page through user-facing Codex sessions whose cwd matches `root_dir`.
+///
+/// Pages are ordered by session file mtime (newest first), with the file path breaking ties, so
+/// consecutive calls with increasing `offset` neither overlap nor skip sessions. Only file
+/// metadata is read for the whole history; session headers are parsed lazily in that order until
+/// `offset + limit` matches have been found.
+pub async fn find_codex_sessions_page_for_dir(
+    root_dir: &Path,
+    offset: usize,
+    limit: usize,
+) -> Result<Vec<SessionSummary>> {
+    let sessions_dir = codex_sessions_dir();
+    find_codex_sessions_page_for_dir_in_sessions_dir(
+        &sessions_dir,
+        root_dir,
+        offset,
+        limit,
+        MAX_SCAN_FILES,
+    )
+    .await
+}
+
+/// Only the newest `max_scan_files` session files (by directory date) are considered.
+async fn find_codex_sessions_page_for_dir_in_sessions_dir(
+    sessions_dir: &Path,
+    root_dir: &Path,
+    offset: usize,
+    limit: usize,
+    max_scan_files: usize,
+) -> Result<Vec<SessionSummary>> {
+    if limit == 0 || !sessions_dir.exists() {
         return Ok(Vec::new());
     }
 
-    let mut matched: Vec<SessionHeader> = Vec::new();
-    let mut scanned_files: usize = 0;
-    let mut cwd_matcher = SessionCwdMatcher::new(root_dir);
-
+    let mut files: Vec<(u64, PathBuf)> = Vec::new();
     let year_dirs = collect_dirs_desc(sessions_dir, |s| s.parse::<u32>().ok()).await?;
 
     'outer: for (_year, year_path) in year_dirs {
@@ -229,26 +315,47 @@ async fn find_codex_sessions_for_dir_in_sessions_dir(
             for (_day, day_path) in day_dirs {
                 let day_files = collect_rollout_files_sorted(&day_path).await?;
                 for path in day_files {
-                    if scanned_files >= MAX_SCAN_FILES {
+                    if files.len() >= max_scan_files {
                         break 'outer;
                     }
-                    scanned_files += 1;
-
-                    let header_opt =
-                        read_session_header_with_cwd_matcher(&path, Some(&mut cwd_matcher)).await?;
-                    let Some(header) = header_opt else {
-                        continue;
-                    };
-
-                    if header.is_cwd_match {
-                        matched.push(header);
-                    }
+                    let mtime_ms = fs::metadata(&path)
+                        .await
+                        .ok()
+                        .and_then(|meta| meta.modified().ok())
+                        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                        .map(|d| d.as_millis() as u64)
+                        .unwrap_or(0);
+                    files.push((mtime_ms, path));
                 }
             }
         }
     }
+    files.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(&b.1)));
 
-    select_and_expand_headers(matched, Vec::new(), limit).await
+    let wanted = offset.saturating_add(limit);
+    let mut cwd_matcher = SessionCwdMatcher::new(root_dir);
+    let mut matched: Vec<SessionHeader> = Vec::new();
+    let mut skipped = 0usize;
+    for (_mtime_ms, path) in files {
+        if skipped + matched.len() >= wanted {
+            break;
+        }
+        let header_opt =
+            read_session_header_with_cwd_matcher(&path, Some(&mut cwd_matcher)).await?;
+        let Some(header) = header_opt else {
+            continue;
+        };
+        if !header.is_cwd_match {
+            continue;
+        }
+        if skipped < offset {
+            skipped += 1;
+            continue;
+        }
+        matched.push(header);
+    }
+
+    expand_headers_in_order(matched).await
 }
 
 /// Search Codex sessions for user messages containing the given substring.
@@ -322,6 +429,175 @@ pub async fn search_codex_sessions_for_current_dir(
     search_codex_sessions_for_dir(&cwd, query, limit).await
 }
 
+/// Search user messages across every project's Codex sessions updated within `since`.
+///
+/// Day directories are walked newest-first and `on_hit` is called as soon as a session matches,
+/// so callers can print early results before the bounded scan finishes.
+pub async fn search_codex_sessions_all_projects(
+    query: &str,
+    since: Duration,
+    limit: usize,
+    on_hit: &mut (dyn FnMut(&SessionSearchHit) + Send),
+) -> Result<Vec<SessionSearchHit>> {
+    let root = codex_sessions_dir();
+    search_codex_sessions_all_projects_in_dir(&root, query, since, limit, on_hit).await
+}
+
+async fn search_codex_sessions_all_projects_in_dir(
+    sessions_dir: &Path,
+    query: &str,
+    since: Duration,
+    limit: usize,
+    on_hit: &mut (dyn FnMut(&SessionSearchHit) + Send),
+) -> Result<Vec<SessionSearchHit>> {
+    let needle = query.trim().to_lowercase();
+    if needle.is_empty() || limit == 0 || since.is_zero() || !sessions_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let now_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis()
+        .min(u64::MAX as u128) as u64;
+    let threshold_ms = now_ms.saturating_sub(since.as_millis().min(u64::MAX as u128) as u64);
+
+    let mut hits: Vec<SessionSearchHit> = Vec::new();
+    let mut scanned_files: usize = 0;
+
+    let year_dirs = collect_dirs_desc(sessions_dir, |s| s.parse::<u32>().ok()).await?;
+    'outer: for (_year, year_path) in year_dirs {
+        let month_dirs = collect_dirs_desc(&year_path, |s| s.parse::<u8>().ok()).await?;
+        for (_month, month_path) in month_dirs {
+            let day_dirs = collect_dirs_desc(&month_path, |s| s.parse::<u8>().ok()).await?;
+            for (_day, day_path) in day_dirs {
+                let day_files = collect_rollout_files_sorted(&day_path).await?;
+                for path in day_files {
+                    if scanned_files >= MAX_SCAN_FILES_RECENT {
+                        break 'outer;
+                    }
+                    scanned_files += 1;
+
+                    let mtime_ms = match fs::metadata(&path).await {
+                        Ok(meta) => meta
+                            .modified()
+                            .ok()
+                            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                            .map(|d| d.as_millis().min(u64::MAX as u128) as u64)
+                            .unwrap_or(0),
+                        Err(_) => continue,
+                    };
+                    if mtime_ms < threshold_ms {
+                        continue;
+                    }
+
+                    let Some(hit) = search_session_user_messages(&path, &needle, mtime_ms).await?
+                    else {
+                        continue;
+                    };
+                    on_hit(&hit);
+                    hits.push(hit);
+                    if hits.len() >= limit {
+                        break 'outer;
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(hits)
+}
+
+async fn search_session_user_messages(
+    path: &Path,
+    needle: &str,
+    mtime_ms: u64,
+) -> Result<Option<SessionSearchHit>> {
+    let file = fs::File::open(path)
+        .await
+        .with_context(|| format!("failed to open session file {:?}", path))?;
+    let mut lines = BufReader::new(file).lines();
+
+    let mut meta: Option<SessionMetaInfo> = None;
+    let mut snippet: Option<String> = None;
+    while let Some(line) = lines.next_line().await? {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        let value: Value = match serde_json::from_str(trimmed) {
+            Ok(v) => v,
+            Err(_) => continue,
+        };
+        if meta.is_none()
+            && let Some(parsed) = parse_session_meta(&value)
+        {
+            if parsed.is_subagent {
+                return Ok(None);
+            }
+            meta = Some(parsed);
+        }
+        if let Some(text) = user_message_text(&value)
+            && let Some(excerpt) = match_snippet(text, needle)
+        {
+            snippet = Some(excerpt);
+        }
+        if meta.is_some() && snippet.is_some() {
+            break;
+        }
+    }
+
+    let Some(snippet) = snippet else {
+        return Ok(None);
+    };
+    let file_id = path
+        .file_name()
+        .and_then(|s| s.to_str())
+        .and_then(parse_timestamp_and_uuid)
+        .map(|(_, uuid)| uuid);
+    let (id, cwd) = match meta {
+        Some(meta) => (meta.id, meta.cwd),
+        None => match file_id {
+            Some(id) => (id, None),
+            None => return Ok(None),
+        },
+    };
+    Ok(Some(SessionSearchHit {
+        id,
+        path: path.to_path_buf(),
+        cwd,
+        mtime_ms,
+        snippet,
+    }))
+}
+
+const SNIPPET_CONTEXT_BEFORE_CHARS: usize = 40;
+const SNIPPET_CONTEXT_AFTER_CHARS: usize = 80;
+
+/// Returns an excerpt around the first case-insensitive occurrence of `needle` (already lowercased).
+fn match_snippet(text: &str, needle: &str) -> Option<String> {
+    let lowered = text.to_lowercase();
+    let byte_pos = lowered.find(needle)?;
+    // Lowercasing can change byte lengths for some scripts; fall back to the message head then.
+    let match_char = if lowered.len() == text.len() && text.is_char_boundary(byte_pos) {
+        text[..byte_pos].chars().count()
+    } else {
+        0
+    };
+    let chars: Vec<char> = text.chars().collect();
+    let start = match_char.saturating_sub(SNIPPET_CONTEXT_BEFORE_CHARS);
+    let end = (match_char + needle.chars().count() + SNIPPET_CONTEXT_AFTER_CHARS).min(chars.len());
+    let excerpt = chars[start..end]
+        .iter()
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    let prefix = if start > 0 { "..." } else { "" };
+    let suffix = if end < chars.len() { "..." } else { "" };
+    Some(format!("{prefix}{excerpt}{suffix}"))
+}
+
 /// List recent user-facing Codex sessions across all projects, filtered by session file mtime.
 ///
 /// This is optimized for "resume" workflows: it avoids counting turns/timestamps and only reads the
@@ -334,6 +610,20 @@ pub async fn find_recent_codex_sessions(
     find_recent_codex_sessions_in_dir(&root, since, limit).await
 }
 
+/// Streaming variant of [`find_recent_codex_sessions`] for very large histories.
+///
+/// Each matching session is handed to `visit` as soon as it is found. The scan stops after
+/// `limit` sessions or when `visit` breaks, so sessions arrive newest day directory first
+/// instead of sorted by mtime.
+pub async fn scan_recent_codex_sessions(
+    since: Duration,
+    limit: usize,
+    visit: impl FnMut(RecentSession) -> Result<ControlFlow<()>>,
+) -> Result<()> {
+    let root = codex_sessions_dir();
+    scan_recent_codex_sessions_in_dir(&root, since, limit, visit).await
+}
+
 async fn find_recent_codex_sessions_in_dir(
     sessions_dir: &Path,
     since: Duration,
@@ -342,11 +632,52 @@ async fn find_recent_codex_sessions_in_dir(
     if limit == 0 {
         return Ok(Vec::new());
     }
+    let mut out: Vec<RecentSession> = Vec::new();
+    visit_recent_codex_sessions_in_dir(sessions_dir, since, |session| {
+        out.push(session);
+        Ok(ControlFlow::Continue(()))
+    })
+    .await?;
+
+    out.sort_by_key(|item| Reverse((item.mtime_ms, item.id.clone())));
+    out.truncate(limit);
+    Ok(out)
+}
+
+async fn scan_recent_codex_sessions_in_dir(
+    sessions_dir: &Path,
+    since: Duration,
+    limit: usize,
+    mut visit: impl FnMut(RecentSession) -> Result<ControlFlow<()>>,
+) -> Result<()> {
+    if limit == 0 {
+        return Ok(());
+    }
+    let mut visited = 0;
+    visit_recent_codex_sessions_in_dir(sessions_dir, since, |session| {
+        let flow = visit(session)?;
+        visited += 1;
+        Ok(if visited >= limit {
+            ControlFlow::Break(())
+        } else {
+            flow
+        })
+    })
+    .await
+}
+
+/// Walks session files newest day directory first, passing each user-facing session updated
+/// within `since` to `visit` until it breaks.
+async fn visit_recent_codex_sessions_in_dir(
+    sessions_dir: &Path,
+    since: Duration,
+    mut visit: impl FnMut(RecentSession) -> Result<ControlFlow<()>>,
+) -> Result<()> {
     if since.is_zero() {
-        return Ok(Vec::new());
+        return Ok(());
     }
     if !sessions_dir.exists() {
-        return Ok(Vec::new());
+        return Ok(());
     }
 
     let now_ms = SystemTime::now()
@@ -357,7 +688,6 @@ async fn find_recent_codex_sessions_in_dir(
     let since_ms = since.as_millis().min(u64::MAX as u128) as u64;
     let threshold_ms = now_ms.saturating_sub(since_ms);
 
-    let mut out: Vec<RecentSession> = Vec::new();
     let mut scanned_files: usize = 0;
 
     let year_dirs = collect_dirs_desc(sessions_dir, |s| s.parse::<u32>().ok()).await?;
@@ -405,15 +735,14 @@ async fn find_recent_codex_sessions_in_dir(
                         continue;
                     };
 
-                    out.push(RecentSession { id, cwd, mtime_ms });
+                    if visit(RecentSession { id, cwd, mtime_ms })?.is_break() {
+                        break 'outer;
+                    }
                 }
             }
         }
     }
-
-    out.sort_by_key(|item| Reverse((item.mtime_ms, item.id.clone())));
-    out.truncate(limit);
-    Ok(out)
+    Ok(())
 }
 
 /// Find a Codex session's cwd by its session id (UUID suffix in rollout filename).
@@ -706,6 +1035,50 @@ fn user_message_text(value: &Value) -> Option<&str> {
     payload.get("message").and_then(|v| v.as_str())
 }
 
+/// Classify one session JSONL line; `None` for anything that is not an error, abnormal abort,
+/// or completed turn.
+pub fn session_line_alert(line: &str) -> Option<SessionAlertEvent> {
+    let value: Value = serde_json::from_str(line.trim()).ok()?;
+    let obj = value.as_object()?;
+    if obj.get("type")?.as_str()? != "event_msg" {
+        return None;
+    }
+    let timestamp = obj
+        .get("timestamp")
+        .and_then(|v| v.as_str())
+        .map(str::to_string);
+    let payload = obj.get("payload")?.as_object()?;
+    match payload.get("type")?.as_str()? {
+        "error" => Some(SessionAlertEvent::Error {
+            timestamp,
+            message: payload
+                .get("message")
+                .and_then(|v| v.as_str())
+                .unwrap_or("unknown error")
+                .to_string(),
+        }),
+        "turn_aborted" => {
+            let reason = payload
+                .get("reason")
+                .and_then(|v| v.as_str())
+                .unwrap_or("unknown");
+            (reason != "interrupted").then(|| SessionAlertEvent::TurnAborted {
+                timestamp,
+                reason: reason.to_string(),
+            })
+        }
+        "task_complete" => Some(SessionAlertEvent::TaskComplete {
+            timestamp,
+            last_agent_message: payload
+                .get("last_agent_message")
+                .and_then(|v| v.as_str())
+                .filter(|message| !message.trim().is_empty())
+                .map(str::to_string),
+        }),
+        _ => None,
+    }
+}
+
 fn contains_bytes(haystack: &[u8], needle: &[u8]) -> bool {
     if needle.is_empty() {
         return true;
@@ -859,6 +1232,30 @@ async fn select_and_expand_headers(
     Ok(out)
 }
 
+/// Expand headers to summaries, keeping the caller's order.
+async fn expand_headers_in_order(headers: Vec<SessionHeader>) -> Result<Vec<SessionSummary>> {
+    let cache = Arc::new(Mutex::new(SessionStatsCache::load_default().await));
+    let mut out: Vec<SessionSummary> = Vec::with_capacity(headers.len());
+    let mut stream = stream::iter(headers)
+        .map(|header| {
+            let cache = Arc::clone(&cache);
+            async move { expand_header_to_summary_cached(cache, header).await }
+        })
+        .buffered(SESSION_IO_CONCURRENCY);
+
+    while let Some(summary) = stream.next().await {
+        out.push(summary?);
+    }
+
+    drop(stream);
+    let mut cache = Arc::try_unwrap(cache)
+        .map_err(|_| anyhow!("session stats cache still has active workers"))?
+        .into_inner()
+        .map_err(|_| anyhow!("session stats cache lock poisoned"))?;
+    cache.save_if_dirty().await?;
+    Ok(out)
+}
+
 fn build_summary_from_stats(
     header: SessionHeader,
     user_turns: usize,
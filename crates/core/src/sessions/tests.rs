@@ -281,10 +281,14 @@ async fn find_codex_sessions_for_dir_filters_subagents_without_global_fallback()
         "other project user session",
     );
 
-    let current_only =
-        find_codex_sessions_for_dir_in_sessions_dir(&tmp.join("sessions"), &current, 10)
-            .await
-            .expect("current project history scan ok");
+    let current_only = find_codex_sessions_for_dir_in_sessions_dir(
+        &tmp.join("sessions"),
+        &current,
+        10,
+        MAX_SCAN_FILES,
+    )
+    .await
+    .expect("current project history scan ok");
 
     let ids = current_only
         .iter()
@@ -297,6 +301,112 @@ async fn find_codex_sessions_for_dir_filters_subagents_without_global_fallback()
     );
 }
 
+#[tokio::test]
+async fn find_codex_sessions_page_for_dir_pages_are_disjoint_and_ordered() {
+    let tmp = std::env::temp_dir().join(format!("codex-helper-test-{}", uuid::Uuid::new_v4()));
+    let sessions = tmp.join("sessions").join("2026").join("05").join("18");
+    let current = tmp.join("current-project");
+    let other = tmp.join("other-project");
+    std::fs::create_dir_all(&current).expect("create current project");
+    std::fs::create_dir_all(&other).expect("create other project");
+
+    for index in 0..5 {
+        write_test_session_file(
+            &sessions,
+            &format!("2026-05-18T00-00-0{index}"),
+            &format!(
+                "{index}{index}{index}{index}{index}{index}{index}{index}-1111-1111-1111-111111111111"
+            ),
+            &current,
+            "current project user session",
+        );
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        if index == 2 {
+            write_test_session_file(
+                &sessions,
+                "2026-05-18T00-00-09",
+                "99999999-9999-9999-9999-999999999999",
+                &other,
+                "other project user session",
+            );
+            std::thread::sleep(std::time::Duration::from_millis(20));
+        }
+    }
+
+    let sessions_dir = tmp.join("sessions");
+    let mut paged = Vec::new();
+    for offset in [0, 2, 4, 6] {
+        let page = find_codex_sessions_page_for_dir_in_sessions_dir(
+            &sessions_dir,
+            &current,
+            offset,
+            2,
+            MAX_SCAN_FILES,
+        )
+        .await
+        .expect("page scan ok");
+        assert!(page.len() <= 2);
+        paged.extend(page.into_iter().map(|s| s.id));
+    }
+
+    assert_eq!(
+        paged,
+        vec![
+            "44444444-1111-1111-1111-111111111111",
+            "33333333-1111-1111-1111-111111111111",
+            "22222222-1111-1111-1111-111111111111",
+            "11111111-1111-1111-1111-111111111111",
+            "00000000-1111-1111-1111-111111111111",
+        ],
+        "pages should cover every project session exactly once, newest first"
+    );
+}
+
+#[tokio::test]
+async fn find_codex_sessions_for_dir_scans_past_the_file_cap_when_uncapped() {
+    let tmp = std::env::temp_dir().join(format!("codex-helper-test-{}", uuid::Uuid::new_v4()));
+    let sessions = tmp.join("sessions").join("2026").join("05").join("18");
+    let current = tmp.join("current-project");
+    std::fs::create_dir_all(&current).expect("create current project");
+
+    for index in 0..3 {
+        write_test_session_file(
+            &sessions,
+            &format!("2026-05-18T00-00-0{index}"),
+            &format!(
+                "{index}{index}{index}{index}{index}{index}{index}{index}-1111-1111-1111-111111111111"
+            ),
+            &current,
+            "current project user session",
+        );
+        std::thread::sleep(std::time::Duration::from_millis(20));
+    }
+
+    let sessions_dir = tmp.join("sessions");
+    let capped =
+        find_codex_sessions_for_dir_in_sessions_dir(&sessions_dir, &current, usize::MAX, 2)
+            .await
+            .expect("capped scan ok");
+    assert_eq!(capped.len(), 2);
+
+    let all = find_codex_sessions_for_dir_in_sessions_dir(
+        &sessions_dir,
+        &current,
+        usize::MAX,
+        usize::MAX,
+    )
+    .await
+    .expect("uncapped scan ok");
+    assert_eq!(
+        all.iter().map(|s| s.id.as_str()).collect::<Vec<_>>(),
+        vec![
+            "22222222-1111-1111-1111-111111111111",
+            "11111111-1111-1111-1111-111111111111",
+            "00000000-1111-1111-1111-111111111111",
+        ]
+    );
+}
+
 #[tokio::test]
 async fn find_codex_sessions_for_dir_does_not_fallback_when_project_has_no_user_sessions() {
     let tmp = std::env::temp_dir().join(format!("codex-helper-test-{}", uuid::Uuid::new_v4()));
@@ -314,10 +424,14 @@ async fn find_codex_sessions_for_dir_does_not_fallback_when_project_has_no_user_
         "other project user session",
     );
 
-    let current_only =
-        find_codex_sessions_for_dir_in_sessions_dir(&tmp.join("sessions"), &current, 10)
-            .await
-            .expect("current project history scan ok");
+    let current_only = find_codex_sessions_for_dir_in_sessions_dir(
+        &tmp.join("sessions"),
+        &current,
+        10,
+        MAX_SCAN_FILES,
+    )
+    .await
+    .expect("current project history scan ok");
 
     assert!(
         current_only.is_empty(),
@@ -382,6 +496,99 @@ async fn read_codex_session_transcript_extracts_messages_and_tail() {
     );
 }
 
+#[tokio::test]
+async fn read_codex_session_transcript_renders_attachment_placeholders() {
+    let dir = std::env::temp_dir().join(format!("codex-helper-test-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&dir).expect("create tmp dir");
+    let path = dir.join("rollout-2025-12-22T00-00-00-00000000-0000-0000-0000-000000000001.jsonl");
+
+    let lines = [
+            r#"{"timestamp":"2025-12-22T00:00:01.000Z","type":"event_msg","payload":{"type":"user_message","message":"what is this?","images":["https://example.com/shots/diagram.png"],"local_images":["C:/Users/me/Pictures/screenshot.png"]}}"#,
+            r#"{"timestamp":"2025-12-22T00:00:02.000Z","type":"response_item","payload":{"type":"message","role":"user","content":[{"type":"input_image","image_url":"data:image/png;base64,iVBORw0KGgo="},{"type":"input_text","text":"compare with"},{"type":"input_file","filename":"report.pdf","file_data":"JVBERi0="}]}}"#,
+            r#"{"timestamp":"2025-12-22T00:00:03.000Z","type":"response_item","payload":{"type":"message","role":"user","content":[{"type":"input_image","image_url":""}]}}"#,
+            r#"{"timestamp":"2025-12-22T00:00:04.000Z","type":"response_item","payload":{"type":"message","role":"assistant","content":[{"type":"output_text","text":"It is a diagram."}]}}"#,
+            r#"{"timestamp":"2025-12-22T00:00:05.000Z","type":"response_item","payload":{"type":"message","role":"user","content":[{"type":"input_text","text":"- [x]"},{"type":"input_text","text":"done"}]}}"#,
+        ]
+        .join("\n");
+    std::fs::write(&path, lines).expect("write session file");
+
+    let all = read_codex_session_transcript(&path, None)
+        .await
+        .expect("read transcript ok");
+    let texts = all.iter().map(|m| m.text.as_str()).collect::<Vec<_>>();
+    assert_eq!(
+        texts,
+        vec![
+            "what is this? [image: https://example.com/shots/diagram.png] [image: screenshot.png]",
+            "[image: inline image/png] compare with [file: report.pdf]",
+            "[image]",
+            "It is a diagram.",
+            "- [x]done",
+        ]
+    );
+
+    let tail = read_codex_session_transcript(&path, Some(3))
+        .await
+        .expect("read tail ok");
+    assert_eq!(tail[0].text, "[image]");
+}
+
+#[tokio::test]
+async fn read_codex_session_transcript_with_tool_calls_keeps_calls_but_not_outputs() {
+    let dir = std::env::temp_dir().join(format!("codex-helper-test-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&dir).expect("create tmp dir");
+    let path = dir.join("rollout-2025-12-22T00-00-00-00000000-0000-0000-0000-000000000002.jsonl");
+
+    let lines = [
+            r#"{"timestamp":"2025-12-22T00:00:01.000Z","type":"event_msg","payload":{"type":"user_message","message":"run the tests"}}"#,
+            r#"{"timestamp":"2025-12-22T00:00:02.000Z","type":"response_item","payload":{"type":"function_call","name":"shell","arguments":"{\"command\":[\"cargo\",\"test\"]}","call_id":"call_1"}}"#,
+            r#"{"timestamp":"2025-12-22T00:00:03.000Z","type":"response_item","payload":{"type":"function_call_output","call_id":"call_1","output":"test result: ok"}}"#,
+            r#"{"timestamp":"2025-12-22T00:00:04.000Z","type":"response_item","payload":{"type":"local_shell_call","action":{"type":"exec","command":["git","status"]}}}"#,
+            r#"{"timestamp":"2025-12-22T00:00:05.000Z","type":"response_item","payload":{"type":"message","role":"assistant","content":[{"type":"output_text","text":"All green."}]}}"#,
+        ]
+        .join("\n");
+    std::fs::write(&path, lines).expect("write session file");
+
+    let with_tools = read_codex_session_transcript_with_tool_calls(&path)
+        .await
+        .expect("read transcript ok");
+    let rendered = with_tools
+        .iter()
+        .map(|m| format!("{}: {}", m.role, m.text))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        rendered,
+        vec![
+            "User: run the tests",
+            r#"Tool: shell {"command":["cargo","test"]}"#,
+            "Tool: shell git status",
+            "Assistant: All green.",
+        ]
+    );
+
+    let without_tools = read_codex_session_transcript(&path, None)
+        .await
+        .expect("read transcript ok");
+    assert_eq!(without_tools.len(), 2);
+}
+
+#[test]
+fn estimate_transcript_tokens_rounds_characters_up_to_quarter_tokens() {
+    let message = |text: &str| SessionTranscriptMessage {
+        timestamp: None,
+        role: "User".to_string(),
+        text: text.to_string(),
+    };
+
+    assert_eq!(estimate_transcript_tokens(&[]), 0);
+    assert_eq!(
+        estimate_transcript_tokens(&[message("hello"), message("abc")]),
+        2
+    );
+    // Characters, not bytes: multi-byte text is not inflated.
+    assert_eq!(estimate_transcript_tokens(&[message("你好世界")]), 1);
+}
+
 #[tokio::test]
 async fn recent_sessions_filters_by_mtime_and_prefers_meta_id() {
     let tmp = std::env::temp_dir().join(format!("codex-helper-test-{}", uuid::Uuid::new_v4()));
@@ -438,6 +645,59 @@ async fn recent_sessions_filters_by_mtime_and_prefers_meta_id() {
     assert_eq!(none.len(), 0, "since=0 should filter everything out");
 }
 
+#[tokio::test]
+async fn scan_recent_sessions_streams_newest_days_first_and_stops_early() {
+    let tmp = std::env::temp_dir().join(format!("codex-helper-test-{}", uuid::Uuid::new_v4()));
+    let sessions = tmp.join("sessions");
+    let project = tmp.join("project");
+    std::fs::create_dir_all(&project).expect("create project dir");
+    for (day, file_ts, id) in [
+        (
+            "01",
+            "2026-02-01T00-00-00",
+            "11111111-1111-1111-1111-111111111111",
+        ),
+        (
+            "02",
+            "2026-02-02T00-00-00",
+            "22222222-2222-2222-2222-222222222222",
+        ),
+        (
+            "02",
+            "2026-02-02T00-00-01",
+            "33333333-3333-3333-3333-333333333333",
+        ),
+    ] {
+        let day_dir = sessions.join("2026").join("02").join(day);
+        write_test_session_file(&day_dir, file_ts, id, &project, "hello");
+    }
+
+    let mut seen = Vec::new();
+    scan_recent_codex_sessions_in_dir(&sessions, Duration::from_secs(24 * 3600), 2, |session| {
+        seen.push(session.id);
+        Ok(ControlFlow::Continue(()))
+    })
+    .await
+    .expect("scan ok");
+    assert_eq!(
+        seen,
+        vec![
+            "33333333-3333-3333-3333-333333333333".to_string(),
+            "22222222-2222-2222-2222-222222222222".to_string(),
+        ]
+    );
+
+    let mut visits = 0;
+    scan_recent_codex_sessions_in_dir(&sessions, Duration::from_secs(24 * 3600), 10, |_| {
+        visits += 1;
+        Ok(ControlFlow::Break(()))
+    })
+    .await
+    .expect("scan ok");
+    assert_eq!(visits, 1, "a visitor that breaks ends the scan");
+    let _ = std::fs::remove_dir_all(&tmp);
+}
+
 #[tokio::test]
 async fn recent_sessions_filter_subagent_threads() {
     let tmp = std::env::temp_dir().join(format!("codex-helper-test-{}", uuid::Uuid::new_v4()));
@@ -541,3 +801,136 @@ async fn batch_find_session_files_resolves_uuid_and_meta_ids() {
         "missing session ids should not be included"
     );
 }
+
+#[tokio::test]
+async fn search_all_projects_streams_hits_across_projects_with_snippets() {
+    let tmp = std::env::temp_dir().join(format!("codex-helper-test-{}", uuid::Uuid::new_v4()));
+    let older_day = tmp.join("sessions").join("2026").join("05").join("17");
+    let newer_day = tmp.join("sessions").join("2026").join("05").join("18");
+    let alpha = tmp.join("alpha-project");
+    let beta = tmp.join("beta-project");
+    std::fs::create_dir_all(&alpha).expect("create alpha project");
+    std::fs::create_dir_all(&beta).expect("create beta project");
+
+    write_test_session_file(
+        &older_day,
+        "2026-05-17T00-00-00",
+        "11111111-1111-1111-1111-111111111111",
+        &alpha,
+        "Please fix the Flaky Retry test in the proxy",
+    );
+    write_test_session_file(
+        &newer_day,
+        "2026-05-18T00-00-00",
+        "22222222-2222-2222-2222-222222222222",
+        &beta,
+        "unrelated question",
+    );
+    write_test_session_file(
+        &newer_day,
+        "2026-05-18T00-00-01",
+        "33333333-3333-3333-3333-333333333333",
+        &beta,
+        "why is the flaky retry so slow",
+    );
+
+    let mut streamed = Vec::new();
+    let hits = search_codex_sessions_all_projects_in_dir(
+        &tmp.join("sessions"),
+        "FLAKY retry",
+        Duration::from_secs(3600),
+        10,
+        &mut |hit: &SessionSearchHit| streamed.push(hit.id.clone()),
+    )
+    .await
+    .expect("global search ok");
+
+    let ids = hits.iter().map(|hit| hit.id.as_str()).collect::<Vec<_>>();
+    assert_eq!(
+        ids,
+        vec![
+            "33333333-3333-3333-3333-333333333333",
+            "11111111-1111-1111-1111-111111111111"
+        ],
+        "newest day dirs are scanned first and every project is included"
+    );
+    assert_eq!(streamed, ids);
+    assert_eq!(hits[0].cwd.as_deref(), beta.to_str());
+    assert_eq!(
+        hits[1].snippet,
+        "Please fix the Flaky Retry test in the proxy"
+    );
+
+    let limited = search_codex_sessions_all_projects_in_dir(
+        &tmp.join("sessions"),
+        "flaky",
+        Duration::from_secs(3600),
+        1,
+        &mut |_: &SessionSearchHit| {},
+    )
+    .await
+    .expect("limited search ok");
+    assert_eq!(limited.len(), 1);
+
+    let none = search_codex_sessions_all_projects_in_dir(
+        &tmp.join("sessions"),
+        "flaky",
+        Duration::from_secs(0),
+        10,
+        &mut |_: &SessionSearchHit| {},
+    )
+    .await
+    .expect("empty window ok");
+    assert!(none.is_empty(), "since=0 should skip every session");
+}
+
+#[test]
+fn match_snippet_trims_long_messages_around_the_match() {
+    let text = format!("{} needle {}", "a ".repeat(60), "b ".repeat(60));
+    let snippet = match_snippet(&text, "needle").expect("match");
+
+    assert!(snippet.starts_with("..."));
+    assert!(snippet.ends_with("..."));
+    assert!(snippet.contains("needle"));
+    assert!(match_snippet("nothing here", "needle").is_none());
+}
+
+#[test]
+fn session_line_alert_flags_errors_abnormal_aborts_and_completions_only() {
+    assert_eq!(
+        session_line_alert(
+            r#"{"timestamp":"2025-12-22T00:00:05.000Z","type":"event_msg","payload":{"type":"error","message":"stream disconnected before completion"}}"#
+        ),
+        Some(SessionAlertEvent::Error {
+            timestamp: Some("2025-12-22T00:00:05.000Z".to_string()),
+            message: "stream disconnected before completion".to_string(),
+        })
+    );
+    assert_eq!(
+        session_line_alert(
+            r#"{"type":"event_msg","payload":{"type":"turn_aborted","reason":"replaced"}}"#
+        ),
+        Some(SessionAlertEvent::TurnAborted {
+            timestamp: None,
+            reason: "replaced".to_string(),
+        })
+    );
+    assert_eq!(
+        session_line_alert(
+            r#"{"type":"event_msg","payload":{"type":"task_complete","turn_id":"1","last_agent_message":"Done."}}"#
+        ),
+        Some(SessionAlertEvent::TaskComplete {
+            timestamp: None,
+            last_agent_message: Some("Done.".to_string()),
+        })
+    );
+    for line in [
+        r#"{"type":"event_msg","payload":{"type":"turn_aborted","reason":"interrupted"}}"#,
+        r#"{"type":"event_msg","payload":{"type":"user_message","message":"error"}}"#,
+        r#"{"type":"response_item","payload":{"type":"error","message":"not an event"}}"#,
+        "not json",
+        "",
+    ] {
+        assert_eq!(session_line_alert(line), None, "{line}");
+    }
+}
@@ -12,10 +12,29 @@ pub async fn read_codex_session_transcript(
     match tail {
         Some(0) => Ok(Vec::new()),
         Some(n) => read_codex_session_transcript_tail(path, n).await,
-        None => read_codex_session_transcript_full(path).await,
+        None => read_codex_session_transcript_full(path, false).await,
     }
 }
 
+/// Read the whole transcript like [`read_codex_session_transcript`], additionally keeping each
+/// tool call as a `Tool` message of the form `name arguments`. Tool outputs are left out.
+pub async fn read_codex_session_transcript_with_tool_calls(
+    path: &Path,
+) -> Result<Vec<SessionTranscriptMessage>> {
+    read_codex_session_transcript_full(path, true).await
+}
+
+/// Approximate token count of transcript text, at roughly four characters per token.
+///
+/// A cheap size hint for sessions without request-log usage; never use it for billing.
+pub fn estimate_transcript_tokens(messages: &[SessionTranscriptMessage]) -> u64 {
+    let chars = messages
+        .iter()
+        .map(|message| message.text.chars().count() as u64)
+        .sum::<u64>();
+    chars.div_ceil(4)
+}
+
 /// Best-effort, case-insensitive substring search within the last `tail` transcript messages.
 ///
 /// This is intended for interactive UIs (history/session manager). It trades completeness for speed:
@@ -38,7 +57,10 @@ pub async fn codex_session_transcript_tail_contains_query(
         .any(|m| m.text.to_lowercase().contains(needle.as_str())))
 }
 
-async fn read_codex_session_transcript_full(path: &Path) -> Result<Vec<SessionTranscriptMessage>> {
+async fn read_codex_session_transcript_full(
+    path: &Path,
+    include_tool_calls: bool,
+) -> Result<Vec<SessionTranscriptMessage>> {
     let file = fs::File::open(path)
         .await
         .with_context(|| format!("failed to open session file {:?}", path))?;
@@ -56,7 +78,12 @@ async fn read_codex_session_transcript_full(path: &Path) -> Result<Vec<SessionTr
             Err(_) => continue,
         };
 
-        let Some(msg) = extract_transcript_message(&value) else {
+        let msg = extract_transcript_message(&value).or_else(|| {
+            include_tool_calls
+                .then(|| tool_call_message(&value))
+                .flatten()
+        });
+        let Some(msg) = msg else {
             continue;
         };
         if msg.text.trim().is_empty() {
@@ -186,34 +213,173 @@ fn assistant_or_user_message_from_response_item(value: &Value) -> Option<(String
 
 fn extract_text_from_content_items(items: &[Value]) -> Option<String> {
     let mut out = String::new();
+    let mut after_placeholder = false;
     for item in items {
         let obj = match item.as_object() {
             Some(o) => o,
             None => continue,
         };
         let t = obj.get("type").and_then(|v| v.as_str()).unwrap_or("");
+        if let Some(placeholder) = attachment_placeholder_from_content_item(t, obj) {
+            push_attachment_placeholder(&mut out, &placeholder);
+            after_placeholder = true;
+            continue;
+        }
         if !t.ends_with("_text") && t != "text" {
             continue;
         }
         let Some(text) = obj.get("text").and_then(|v| v.as_str()) else {
             continue;
         };
+        if after_placeholder && !text.starts_with(char::is_whitespace) {
+            out.push(' ');
+        }
         out.push_str(text);
+        after_placeholder = false;
     }
     if out.is_empty() { None } else { Some(out) }
 }
 
+/// Renders image and file content parts as `[image: ...]` / `[file: ...]` so turns that carried
+/// attachments keep that context instead of disappearing from the transcript.
+fn attachment_placeholder_from_content_item(
+    content_type: &str,
+    obj: &serde_json::Map<String, Value>,
+) -> Option<String> {
+    match content_type {
+        "input_image" | "image_url" | "image" => {
+            let reference = match obj.get("image_url") {
+                Some(Value::String(url)) => Some(url.as_str()),
+                Some(Value::Object(image)) => image.get("url").and_then(|v| v.as_str()),
+                _ => None,
+            }
+            .or_else(|| obj.get("file_id").and_then(|v| v.as_str()));
+            Some(attachment_placeholder("image", reference))
+        }
+        "input_file" | "file" => {
+            let file = obj.get("file").and_then(|v| v.as_object()).unwrap_or(obj);
+            let reference = ["filename", "file_url", "file_id"]
+                .iter()
+                .find_map(|key| file.get(*key).and_then(|v| v.as_str()));
+            Some(attachment_placeholder("file", reference))
+        }
+        _ => None,
+    }
+}
+
+fn attachment_placeholder(kind: &str, reference: Option<&str>) -> String {
+    let label = reference
+        .map(str::trim)
+        .filter(|reference| !reference.is_empty())
+        .map(attachment_label);
+    match label {
+        Some(label) => format!("[{kind}: {label}]"),
+        None => format!("[{kind}]"),
+    }
+}
+
+fn attachment_label(reference: &str) -> String {
+    // Inline data URLs are base64 payloads; the media type is the only useful part.
+    if let Some(rest) = reference.strip_prefix("data:") {
+        let media_type = rest.split([';', ',']).next().unwrap_or_default();
+        return if media_type.is_empty() {
+            "inline data".to_string()
+        } else {
+            format!("inline {media_type}")
+        };
+    }
+    reference.to_string()
+}
+
+fn push_attachment_placeholder(out: &mut String, placeholder: &str) {
+    if !out.is_empty() && !out.ends_with(char::is_whitespace) {
+        out.push(' ');
+    }
+    out.push_str(placeholder);
+}
+
+/// `event_msg` user messages carry attachments next to the text: `images` holds URLs or data
+/// URLs and `local_images` holds paths on the machine that ran Codex.
+fn user_message_with_attachments(value: &Value) -> Option<String> {
+    let mut text = user_message_text(value)?.to_string();
+    let payload = value.get("payload")?;
+    let references = |key: &str| {
+        payload
+            .get(key)
+            .and_then(|v| v.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|v| v.as_str())
+    };
+    for image in references("images") {
+        push_attachment_placeholder(&mut text, &attachment_placeholder("image", Some(image)));
+    }
+    for path in references("local_images") {
+        let name = Path::new(path)
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or(path);
+        push_attachment_placeholder(&mut text, &attachment_placeholder("image", Some(name)));
+    }
+    Some(text)
+}
+
+/// `function_call`, `custom_tool_call` and `local_shell_call` response items as `name arguments`.
+fn tool_call_message(value: &Value) -> Option<SessionTranscriptMessage> {
+    if value.get("type")?.as_str()? != "response_item" {
+        return None;
+    }
+    let payload = value.get("payload")?;
+    let text = match payload.get("type")?.as_str()? {
+        "function_call" => {
+            let name = payload.get("name")?.as_str()?;
+            let arguments = payload
+                .get("arguments")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default();
+            format!("{name} {arguments}")
+        }
+        "custom_tool_call" => {
+            let name = payload.get("name")?.as_str()?;
+            let input = payload
+                .get("input")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default();
+            format!("{name} {input}")
+        }
+        "local_shell_call" => {
+            let command = payload
+                .pointer("/action/command")?
+                .as_array()?
+                .iter()
+                .filter_map(|part| part.as_str())
+                .collect::<Vec<_>>()
+                .join(" ");
+            format!("shell {command}")
+        }
+        _ => return None,
+    };
+    Some(SessionTranscriptMessage {
+        timestamp: value
+            .get("timestamp")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string()),
+        role: "Tool".to_string(),
+        text: text.trim_end().to_string(),
+    })
+}
+
 fn extract_transcript_message(value: &Value) -> Option<SessionTranscriptMessage> {
     let timestamp = value
         .get("timestamp")
         .and_then(|v| v.as_str())
         .map(|s| s.to_string());
 
-    if let Some(msg) = user_message_text(value) {
+    if let Some(text) = user_message_with_attachments(value) {
         return Some(SessionTranscriptMessage {
             timestamp,
             role: "User".to_string(),
-            text: msg.to_string(),
+            text,
         });
     }
 
@@ -1,10 +1,10 @@
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 #[cfg(test)]
 use std::path::PathBuf;
-use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::{Arc, OnceLock, RwLock as SyncRwLock, Weak};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex as SyncMutex, OnceLock, RwLock as SyncRwLock, Weak};
 
-use tokio::sync::{Mutex as AsyncMutex, OwnedMutexGuard, RwLock, watch};
+use tokio::sync::{Mutex as AsyncMutex, OwnedMutexGuard, RwLock, broadcast, watch};
 use tokio::time::Duration;
 
 pub use crate::balance::{
@@ -38,7 +38,7 @@ use crate::request_ledger::{
     RequestUsageAggregate, RequestUsageSummary, RequestUsageSummaryCoverage,
     RequestUsageSummaryGroup, RequestUsageSummaryRow, sort_usage_summary_rows,
 };
-use crate::routing_ir::{RoutePlanRuntimeState, RoutePlanUpstreamRuntimeState};
+use crate::routing_ir::{RoutePlanRuntimeState, RoutePlanUpstreamRuntimeState, UpstreamPin};
 use crate::runtime_identity::{ProviderEndpointKey, RuntimeUpstreamIdentity};
 use crate::runtime_store::{
     AttemptHandle, AttemptId, AttemptOutcome, AttemptPendingEvidence, AttemptRouteEvidence,
@@ -64,6 +64,9 @@ use crate::usage_day;
 use crate::usage_providers::ProviderBalanceRefreshCoordinator;
 
 mod attribution_index;
+mod duplicate_requests;
+mod live_events;
+mod request_quota;
 mod routing_control;
 mod runtime_types;
 mod session_affinity_control;
@@ -74,11 +77,15 @@ pub use self::attribution_index::{
     AttributionAggregate, AttributionBucket, AttributionBucketKey, AttributionCoverage,
     AttributionPoolKey, AttributionQuery, AttributionQueryResult,
 };
+use self::duplicate_requests::DuplicateRequestWindow;
+pub use self::live_events::{LIVE_EVENT_CHANNEL_CAPACITY, LiveEvent};
+use self::request_quota::RequestQuotaLedger;
+pub use self::request_quota::{RequestQuotaDimension, RequestQuotaExceeded, RequestQuotaUsage};
 
 pub(crate) use self::routing_control::PreparedRoutingOperatorRouteGraph;
 pub use self::routing_control::{
-    NewSessionPreference, RoutingOperatorControlCommit, RoutingOperatorControlError,
-    RoutingOperatorControlSnapshot, RoutingOperatorControlUpdate,
+    AutoActiveSelection, NewSessionPreference, RoutingOperatorControlCommit,
+    RoutingOperatorControlError, RoutingOperatorControlSnapshot, RoutingOperatorControlUpdate,
 };
 use self::runtime_types::UsageRollup;
 pub use self::runtime_types::{
@@ -167,6 +174,10 @@ struct ProviderEndpointRuntimeHealth {
     breaker_epoch: u64,
     half_open_probe_attempted_epoch: Option<u64>,
     half_open_probe_owner: Weak<()>,
+    /// Successful probes required before the open breaker closes; 0 closes on cooldown expiry.
+    probe_back_required: u32,
+    probe_back_successes: u32,
+    probe_back_interval_secs: u64,
 }
 
 impl Default for ProviderEndpointRuntimeHealth {
@@ -179,6 +190,9 @@ impl Default for ProviderEndpointRuntimeHealth {
             breaker_epoch: 0,
             half_open_probe_attempted_epoch: None,
             half_open_probe_owner: Weak::new(),
+            probe_back_required: 0,
+            probe_back_successes: 0,
+            probe_back_interval_secs: 0,
         }
     }
 }
@@ -211,6 +225,15 @@ pub(crate) struct RuntimeHealthHalfOpenProbeLease {
     capability: RouteCapability,
     owner: Arc<()>,
     buckets: Vec<RuntimeHealthHalfOpenProbeBucketLease>,
+    probe_back: bool,
+}
+
+impl RuntimeHealthHalfOpenProbeLease {
+    /// Whether this probe re-tests an upstream whose cooldown already expired while healthier
+    /// peers kept serving traffic, so a failed probe may still fail over.
+    pub(crate) fn is_probe_back(&self) -> bool {
+        self.probe_back
+    }
 }
 
 pub(crate) struct DispatchedRuntimeHealthHalfOpenProbe {
@@ -253,16 +276,19 @@ struct ProviderEndpointRuntimeHealthState {
     health: HashMap<ProviderEndpointRuntimeHealthBucketKey, ProviderEndpointRuntimeHealth>,
 }
 
+/// Projects breaker state into `runtime` and returns the upstreams whose breaker closed
+/// because a cooldown expired since it was last observed.
 fn project_provider_endpoint_runtime_health(
     runtime: &mut RoutePlanRuntimeState,
     state: &mut ProviderEndpointRuntimeHealthState,
     identities: &[ProviderEndpointRuntimeHealthKey],
     capability: Option<RouteCapability>,
     now: std::time::Instant,
-) {
+) -> Vec<ProviderEndpointKey> {
     let Some(capability) = capability else {
-        return;
+        return Vec::new();
     };
+    let mut closed_breakers = Vec::new();
     let mut affinity: Option<(ProviderEndpointKey, u64)> = None;
     for identity in identities {
         let domains = [
@@ -275,13 +301,14 @@ fn project_provider_endpoint_runtime_health(
         let mut cooldown_until = None;
         let mut projected = false;
         let mut capability_last_good_at_ms = None;
+        let mut expired_breaker_closed = false;
         for domain in domains {
             let key = ProviderEndpointRuntimeHealthBucketKey::new(identity.clone(), domain);
             let Some(health) = state.health.get_mut(&key) else {
                 continue;
             };
             projected = true;
-            reset_expired_runtime_health_breaker(health, now);
+            expired_breaker_closed |= reset_expired_runtime_health_breaker(health, now);
             failure_count = failure_count.max(health.failure_count);
             if let Some(until) = health.cooldown_until
                 && cooldown_until.is_none_or(|current| until > current)
@@ -296,6 +323,9 @@ fn project_provider_endpoint_runtime_health(
             continue;
         }
         let cooldown_active = cooldown_until.is_some_and(|until| now < until);
+        if expired_breaker_closed && failure_count < FAILURE_THRESHOLD && !cooldown_active {
+            closed_breakers.push(identity.provider_endpoint.clone());
+        }
         let cooldown_remaining_secs = cooldown_until
             .and_then(|until| (now < until).then(|| until.duration_since(now).as_secs().max(1)));
         runtime.set_provider_endpoint(
@@ -311,6 +341,8 @@ fn project_provider_endpoint_runtime_health(
                 concurrency_saturated: false,
                 concurrency_active: None,
                 concurrency_limit: None,
+                outside_schedule: false,
+                primary_sticky_remaining_ms: None,
             },
         );
         if let Some(last_good_at_ms) = capability_last_good_at_ms
@@ -324,16 +356,52 @@ fn project_provider_endpoint_runtime_health(
     if let Some((provider_endpoint, _)) = affinity {
         runtime.set_affinity_provider_endpoint(Some(provider_endpoint));
     }
+    closed_breakers
 }
 
+/// Clears an expired cooldown. Returns true when that closed a breaker the failure count had
+/// kept open, so the caller can publish the transition.
 fn reset_expired_runtime_health_breaker(
     health: &mut ProviderEndpointRuntimeHealth,
     now: std::time::Instant,
-) {
+) -> bool {
     if health.cooldown_until.is_some_and(|until| now >= until) {
-        health.failure_count = 0;
         health.cooldown_until = None;
+        if runtime_health_probe_back_pending(health) {
+            // Probe-back: keep the breaker open and arm a fresh half-open probe instead of
+            // handing the upstream back to normal selection.
+            begin_runtime_health_breaker_epoch(health);
+            return false;
+        }
+        let was_open = health.failure_count >= FAILURE_THRESHOLD;
+        health.failure_count = 0;
+        return was_open;
     }
+    false
+}
+
+fn runtime_health_probe_back_pending(health: &ProviderEndpointRuntimeHealth) -> bool {
+    health.probe_back_successes < health.probe_back_required
+}
+
+fn runtime_health_in_probe_back(
+    health: &ProviderEndpointRuntimeHealth,
+    now: std::time::Instant,
+) -> bool {
+    runtime_health_probe_back_pending(health)
+        && health.failure_count >= FAILURE_THRESHOLD
+        && health.cooldown_until.is_none_or(|until| now >= until)
+}
+
+fn arm_runtime_health_probe_back(
+    health: &mut ProviderEndpointRuntimeHealth,
+    base_cooldown_secs: u64,
+    cooldown_backoff: CooldownBackoff,
+) {
+    let threshold = cooldown_backoff.probe_back_success_threshold;
+    health.probe_back_required = if threshold > 1 { threshold } else { 0 };
+    health.probe_back_successes = 0;
+    health.probe_back_interval_secs = base_cooldown_secs;
 }
 
 fn runtime_health_breaker_is_open(
@@ -361,6 +429,8 @@ fn record_runtime_health_success(
     health.penalty_streak = 0;
     health.half_open_probe_attempted_epoch = None;
     health.half_open_probe_owner = Weak::new();
+    health.probe_back_required = 0;
+    health.probe_back_successes = 0;
     health.last_good_at_ms =
         (domain == RuntimeHealthDomain::Capability(capability)).then_some(now_ms);
 }
@@ -395,9 +465,30 @@ fn record_runtime_health_failure(
         health.cooldown_until = Some(new_until);
     }
     health.penalty_streak = health.penalty_streak.saturating_add(1);
+    arm_runtime_health_probe_back(health, base_secs, cooldown_backoff);
     health.last_good_at_ms = None;
 }
 
+fn settle_runtime_health_probe_success(
+    health: &mut ProviderEndpointRuntimeHealth,
+    domain: RuntimeHealthDomain,
+    capability: RouteCapability,
+    now_ms: u64,
+    now: std::time::Instant,
+) {
+    if health.probe_back_required > 0 {
+        health.probe_back_successes = health.probe_back_successes.saturating_add(1);
+        if runtime_health_probe_back_pending(health) {
+            // Not yet trusted: hold the breaker open and space out the next probe.
+            begin_runtime_health_breaker_epoch(health);
+            health.cooldown_until =
+                Some(now + std::time::Duration::from_secs(health.probe_back_interval_secs));
+            return;
+        }
+    }
+    record_runtime_health_success(health, domain, capability, now_ms);
+}
+
 fn penalize_runtime_health(
     health: &mut ProviderEndpointRuntimeHealth,
     cooldown_secs: u64,
@@ -413,6 +504,7 @@ fn penalize_runtime_health(
     health.failure_count = FAILURE_THRESHOLD;
     health.cooldown_until = Some(now + std::time::Duration::from_secs(effective_secs));
     health.penalty_streak = health.penalty_streak.saturating_add(1);
+    arm_runtime_health_probe_back(health, cooldown_secs, cooldown_backoff);
     health.last_good_at_ms = None;
 }
 
@@ -469,6 +561,82 @@ fn half_open_probe_bucket_specs(
     (!specs.is_empty()).then_some(specs)
 }
 
+/// `runtime_health_breaker_is_open` as it reads once `reset_expired_runtime_health_breaker`
+/// has run, without mutating the bucket.
+fn runtime_health_breaker_is_open_after_reset(
+    health: &ProviderEndpointRuntimeHealth,
+    now: std::time::Instant,
+) -> bool {
+    if health.cooldown_until.is_some_and(|until| now >= until) {
+        return runtime_health_probe_back_pending(health)
+            && health.failure_count >= FAILURE_THRESHOLD;
+    }
+    runtime_health_breaker_is_open(health, now)
+}
+
+/// Read-only counterpart of `half_open_probe_bucket_specs` + `half_open_probe_specs_are_probe_back`:
+/// whether a half-open probe could be acquired for `identity` and every open bucket it would
+/// cover is owed probe-back successes.
+fn runtime_health_probe_back_probe_available(
+    state: &ProviderEndpointRuntimeHealthState,
+    identity: &ProviderEndpointRuntimeHealthKey,
+    capability: RouteCapability,
+    now: std::time::Instant,
+) -> bool {
+    let bucket = |domain| {
+        state
+            .health
+            .get(&ProviderEndpointRuntimeHealthBucketKey::new(
+                identity.clone(),
+                domain,
+            ))
+    };
+    let blocked = [
+        RuntimeHealthDomain::Credential,
+        RuntimeHealthDomain::Capacity(capability),
+    ]
+    .into_iter()
+    .filter_map(bucket)
+    .any(|health| runtime_health_breaker_is_open_after_reset(health, now));
+    if blocked {
+        return false;
+    }
+
+    let mut open = [
+        RuntimeHealthDomain::EndpointTransport,
+        RuntimeHealthDomain::Capability(capability),
+    ]
+    .into_iter()
+    .filter_map(bucket)
+    .filter(|health| runtime_health_breaker_is_open_after_reset(health, now))
+    .peekable();
+    open.peek().is_some()
+        && open.all(|health| {
+            if !runtime_health_in_probe_back(health, now) {
+                return false;
+            }
+            // An expired cooldown starts a fresh breaker epoch when the probe is acquired.
+            if health.cooldown_until.is_some() {
+                return true;
+            }
+            health.half_open_probe_attempted_epoch != Some(health.breaker_epoch.max(1))
+                && health.half_open_probe_owner.upgrade().is_none()
+        })
+}
+
+fn half_open_probe_specs_are_probe_back(
+    state: &ProviderEndpointRuntimeHealthState,
+    specs: &[RuntimeHealthHalfOpenProbeBucketSpec],
+    now: std::time::Instant,
+) -> bool {
+    specs.iter().all(|spec| {
+        state
+            .health
+            .get(&spec.key)
+            .is_some_and(|health| runtime_health_in_probe_back(health, now))
+    })
+}
+
 fn apply_provider_policy_to_route_runtime(
     runtime: &mut RoutePlanRuntimeState,
     service_name: &str,
@@ -526,6 +694,17 @@ fn active_provider_endpoint_runtime_health<'a>(
     )
 }
 
+/// Upper bound on distinct paths held by the GET response cache.
+const GET_RESPONSE_CACHE_MAX_ENTRIES: usize = 64;
+
+/// A buffered successful upstream response served by the short-TTL GET cache.
+#[derive(Debug, Clone)]
+pub struct CachedGetResponse {
+    pub headers: http::HeaderMap,
+    pub body: axum::body::Bytes,
+    pub stored_at_ms: u64,
+}
+
 #[derive(Debug, Clone)]
 struct SessionTranscriptPathCacheEntry {
     path: Option<String>,
@@ -542,6 +721,7 @@ struct RuntimePolicy {
     session_route_affinity_max_entries: usize,
     session_transcript_path_cache_ttl_ms: u64,
     session_transcript_path_cache_max_entries: usize,
+    recent_finished_max: usize,
 }
 
 #[cfg(test)]
@@ -558,7 +738,8 @@ struct OperatorAggregationPause {
     resume: tokio::sync::oneshot::Receiver<()>,
 }
 
-pub fn recent_finished_max() -> usize {
+/// Recent-request buffer size when `dashboard.max_recent` is unset.
+pub fn default_recent_finished_max() -> usize {
     static MAX: OnceLock<usize> = OnceLock::new();
     *MAX.get_or_init(|| {
         recent_finished_max_from_env(std::env::var("CODEX_HELPER_RECENT_FINISHED_MAX").ok())
@@ -1175,6 +1356,7 @@ fn snapshot_usage_rollup(rollup: &UsageRollup) -> UsageRollup {
 
 fn hydrate_runtime_projections(
     runtime_store: &RuntimeStore,
+    recent_finished_max: usize,
 ) -> Result<RequestLifecycleProjectionState, RuntimeStoreError> {
     let metadata = runtime_store.committed_request_projection_metadata()?;
     let mut hydrated = RequestLifecycleProjectionState {
@@ -1187,7 +1369,7 @@ fn hydrate_runtime_projections(
     };
 
     let recent = runtime_store.query_committed_requests(&CommittedRequestQuery {
-        limit: recent_finished_max(),
+        limit: recent_finished_max,
         ..CommittedRequestQuery::default()
     })?;
     hydrated.recent_finished.extend(
@@ -1623,19 +1805,32 @@ pub struct ProxyState {
     session_route_control_owner: Arc<()>,
     session_transcript_path_cache_ttl_ms: u64,
     session_transcript_path_cache_max_entries: usize,
+    // Capacity of `recent_finished`; `dashboard.max_recent` can change it on reload.
+    recent_finished_max: AtomicUsize,
     session_bindings: RwLock<HashMap<String, SessionBindingEntry>>,
     session_transcript_path_cache: RwLock<HashMap<String, SessionTranscriptPathCacheEntry>>,
+    // Keyed by service, config revision and request path; cleared on config reload.
+    get_response_cache: RwLock<HashMap<String, CachedGetResponse>>,
     request_lifecycle_projection: RwLock<RequestLifecycleProjectionState>,
     provider_balances: RwLock<ProviderBalanceMap>,
     provider_balance_refresh_coordinator: Arc<ProviderBalanceRefreshCoordinator>,
     quota_pool_registry: SyncRwLock<QuotaPoolRegistry>,
     quota_registry_document_revision: AtomicU64,
     quota_identity: RuntimeQuotaIdentity,
+    request_quotas: SyncMutex<RequestQuotaLedger>,
+    duplicate_requests: SyncMutex<DuplicateRequestWindow>,
     provider_endpoint_runtime_health: RwLock<HashMap<String, ProviderEndpointRuntimeHealthState>>,
     provider_policy_updates: AsyncMutex<()>,
     provider_policy_snapshot: RwLock<Arc<ProviderPolicySnapshot>>,
     routing_operator_control: RwLock<RoutingOperatorControlSnapshot>,
+    routing_auto_active: RwLock<HashMap<String, AutoActiveSelection>>,
+    // Wall-clock ms until which `routing.primary_sticky_ms` keeps selections off an endpoint.
+    primary_sticky_holds: RwLock<HashMap<ProviderEndpointKey, u64>>,
     state_version_tx: watch::Sender<u64>,
+    // Bounded and lossy: publishing never waits for slow `/api/v1/events` subscribers.
+    live_events_tx: broadcast::Sender<LiveEvent>,
+    // Wall-clock ms of the last logical request begin/finish; drives `serve --idle-timeout`.
+    last_request_activity_ms: AtomicU64,
     operator_capture: RwLock<()>,
     #[cfg(test)]
     terminal_publication_pause: AsyncMutex<Option<TerminalPublicationPause>>,
@@ -1657,6 +1852,14 @@ impl ProxyState {
 
     pub fn new_with_runtime_store(
         runtime_store: Arc<RuntimeStore>,
+    ) -> Result<Arc<Self>, RuntimeStoreError> {
+        Self::new_with_runtime_store_and_recent_max(runtime_store, None)
+    }
+
+    /// Like [`Self::new_with_runtime_store`], hydrating `max_recent` (or the default) recent requests.
+    pub fn new_with_runtime_store_and_recent_max(
+        runtime_store: Arc<RuntimeStore>,
+        max_recent: Option<usize>,
     ) -> Result<Arc<Self>, RuntimeStoreError> {
         let session_stats_ttl_ms = 30_u64 * 60 * 1000;
         let binding_ttl_secs = std::env::var("CODEX_HELPER_SESSION_BINDING_TTL_SECS")
@@ -1700,6 +1903,7 @@ impl ProxyState {
                 session_route_affinity_max_entries: route_affinity_max_entries,
                 session_transcript_path_cache_ttl_ms: transcript_path_cache_ttl_ms,
                 session_transcript_path_cache_max_entries: transcript_path_cache_max_entries,
+                recent_finished_max: max_recent.unwrap_or_else(default_recent_finished_max),
             },
             runtime_store,
         )
@@ -1717,7 +1921,7 @@ impl ProxyState {
     ) -> Result<Arc<Self>, RuntimeStoreError> {
         let (quota_pool_registry, quota_identity, quota_registry_document_revision) =
             load_quota_runtime_state(runtime_store.as_ref())?;
-        let hydrated = hydrate_runtime_projections(&runtime_store)?;
+        let hydrated = hydrate_runtime_projections(&runtime_store, policy.recent_finished_max)?;
         runtime_store.prune_session_affinities(
             unix_now_ms(),
             policy.session_route_affinity_ttl_ms,
@@ -1738,8 +1942,10 @@ impl ProxyState {
             session_transcript_path_cache_ttl_ms: policy.session_transcript_path_cache_ttl_ms,
             session_transcript_path_cache_max_entries: policy
                 .session_transcript_path_cache_max_entries,
+            recent_finished_max: AtomicUsize::new(policy.recent_finished_max),
             session_bindings: RwLock::new(HashMap::new()),
             session_transcript_path_cache: RwLock::new(HashMap::new()),
+            get_response_cache: RwLock::new(HashMap::new()),
             request_lifecycle_projection: RwLock::new(hydrated),
             provider_balances: RwLock::new(HashMap::new()),
             provider_balance_refresh_coordinator: Arc::new(
@@ -1750,11 +1956,17 @@ impl ProxyState {
                 quota_registry_document_revision.unwrap_or(0),
             ),
             quota_identity,
+            request_quotas: SyncMutex::new(RequestQuotaLedger::default()),
+            duplicate_requests: SyncMutex::new(DuplicateRequestWindow::default()),
             provider_endpoint_runtime_health: RwLock::new(HashMap::new()),
             provider_policy_updates: AsyncMutex::new(()),
             provider_policy_snapshot: RwLock::new(provider_policy_snapshot),
             routing_operator_control: RwLock::new(RoutingOperatorControlSnapshot::default()),
+            routing_auto_active: RwLock::new(HashMap::new()),
+            primary_sticky_holds: RwLock::new(HashMap::new()),
             state_version_tx: watch::channel(0).0,
+            live_events_tx: live_events::live_event_channel(),
+            last_request_activity_ms: AtomicU64::new(unix_now_ms()),
             operator_capture: RwLock::new(()),
             #[cfg(test)]
             terminal_publication_pause: AsyncMutex::new(None),
@@ -1858,6 +2070,24 @@ impl ProxyState {
             .len()
     }
 
+    pub fn subscribe_live_events(&self) -> broadcast::Receiver<LiveEvent> {
+        self.live_events_tx.subscribe()
+    }
+
+    /// Breakers close silently when a cooldown simply runs out; publish that the first time
+    /// it is observed.
+    fn publish_expired_breaker_closes(&self, provider_endpoints: &[ProviderEndpointKey]) {
+        let now_ms = unix_now_ms();
+        for provider_endpoint in provider_endpoints {
+            self.publish_live_event(LiveEvent::health_changed(provider_endpoint, false, now_ms));
+        }
+    }
+
+    fn publish_live_event(&self, event: LiveEvent) {
+        // Sending only fails when nobody is subscribed.
+        let _ = self.live_events_tx.send(event);
+    }
+
     pub fn subscribe_state_changes(&self) -> watch::Receiver<u64> {
         self.state_version_tx.subscribe()
     }
@@ -2457,7 +2687,7 @@ impl ProxyState {
         let policy_snapshot = self.capture_provider_policy_snapshot().await;
         let mut runtime = RoutePlanRuntimeState::default();
         let now = std::time::Instant::now();
-        {
+        let closed_breakers = {
             let mut guard = self.provider_endpoint_runtime_health.write().await;
             if let Some(per_service) = guard.get_mut(service_name) {
                 let keys = per_service
@@ -2473,9 +2703,12 @@ impl ProxyState {
                     keys.as_slice(),
                     Some(RouteCapability::Inference),
                     now,
-                );
+                )
+            } else {
+                Vec::new()
             }
-        }
+        };
+        self.publish_expired_breaker_closes(&closed_breakers);
         apply_provider_policy_to_route_runtime(
             &mut runtime,
             service_name,
@@ -2518,7 +2751,7 @@ impl ProxyState {
             .collect::<HashSet<_>>();
         let mut runtime = RoutePlanRuntimeState::default();
         let now = std::time::Instant::now();
-        {
+        let closed_breakers = {
             let mut guard = self.provider_endpoint_runtime_health.write().await;
             let per_service = guard.entry(service_name.to_string()).or_default();
             if per_service.identities_authoritative {
@@ -2556,8 +2789,9 @@ impl ProxyState {
                 active_keys.as_slice(),
                 capability,
                 now,
-            );
-        }
+            )
+        };
+        self.publish_expired_breaker_closes(&closed_breakers);
 
         apply_provider_policy_to_route_runtime(
             &mut runtime,
@@ -2614,6 +2848,83 @@ impl ProxyState {
         self.routing_operator_control.read().await.clone()
     }
 
+    pub async fn auto_active_selection(
+        &self,
+        service_name: &str,
+        route_graph_key: &str,
+    ) -> Option<AutoActiveSelection> {
+        self.routing_auto_active
+            .read()
+            .await
+            .get(service_name)
+            .filter(|selection| selection.route_graph_key == route_graph_key)
+            .cloned()
+    }
+
+    /// Records a new auto-active pick and returns the previous target for this route graph,
+    /// or `None` when `target` was already selected.
+    pub async fn replace_auto_active_selection(
+        &self,
+        service_name: &str,
+        route_graph_key: &str,
+        target: ProviderEndpointKey,
+        now_ms: u64,
+    ) -> Option<Option<ProviderEndpointKey>> {
+        let mut selections = self.routing_auto_active.write().await;
+        let previous = selections
+            .get(service_name)
+            .filter(|selection| selection.route_graph_key == route_graph_key)
+            .map(|selection| selection.target.clone());
+        if previous.as_ref() == Some(&target) {
+            return None;
+        }
+        selections.insert(
+            service_name.to_string(),
+            AutoActiveSelection {
+                route_graph_key: route_graph_key.to_string(),
+                target,
+                selected_at_ms: now_ms,
+            },
+        );
+        drop(selections);
+        self.notify_state_changed();
+        Some(previous)
+    }
+
+    /// Starts (or extends) the `routing.primary_sticky_ms` hold on an endpoint a request just
+    /// failed over away from. Returns whether a new hold began.
+    pub async fn hold_provider_endpoint_after_failover(
+        &self,
+        provider_endpoint: ProviderEndpointKey,
+        until_ms: u64,
+        now_ms: u64,
+    ) -> bool {
+        let mut holds = self.primary_sticky_holds.write().await;
+        holds.retain(|_, held_until_ms| *held_until_ms > now_ms);
+        let held_until_ms = holds.entry(provider_endpoint).or_insert(0);
+        let started = *held_until_ms == 0;
+        *held_until_ms = (*held_until_ms).max(until_ms);
+        drop(holds);
+        self.notify_state_changed();
+        started
+    }
+
+    /// Active `routing.primary_sticky_ms` holds for `service_name`, keyed by endpoint, with the
+    /// wall-clock ms at which each ends.
+    pub async fn primary_sticky_holds(
+        &self,
+        service_name: &str,
+        now_ms: u64,
+    ) -> BTreeMap<ProviderEndpointKey, u64> {
+        self.primary_sticky_holds
+            .read()
+            .await
+            .iter()
+            .filter(|(key, until_ms)| key.service_name == service_name && **until_ms > now_ms)
+            .map(|(key, until_ms)| (key.clone(), *until_ms))
+            .collect()
+    }
+
     pub async fn compare_and_set_new_session_preference(
         &self,
         service_name: &str,
@@ -2679,6 +2990,48 @@ impl ProxyState {
         .await
     }
 
+    /// Compare-and-set for a session pin (`session_id` set) or the service-wide pin.
+    pub async fn compare_and_set_upstream_pin(
+        &self,
+        service_name: &str,
+        route_graph_key: &str,
+        expected_revision: u64,
+        session_id: Option<&str>,
+        pin: Option<UpstreamPin>,
+    ) -> Result<RoutingOperatorControlCommit, RoutingOperatorControlError> {
+        let service_name = service_name.trim();
+        if service_name.is_empty() {
+            return Err(RoutingOperatorControlError::EmptyServiceName);
+        }
+        let route_graph_key = route_graph_key.trim();
+        if route_graph_key.is_empty() {
+            return Err(RoutingOperatorControlError::EmptyRouteGraphKey);
+        }
+        if let Some(pin) = pin.as_ref()
+            && pin.target.service_name != service_name
+        {
+            return Err(RoutingOperatorControlError::ServiceMismatch {
+                expected: service_name.to_string(),
+                actual: pin.target.service_name.clone(),
+            });
+        }
+
+        let mut control = self.routing_operator_control.write().await;
+        if control.revision() != expected_revision {
+            return Ok(RoutingOperatorControlCommit {
+                status: RoutingOperatorControlUpdate::Conflict,
+                snapshot: control.clone(),
+            });
+        }
+        let status = control.apply_upstream_pin(service_name, route_graph_key, session_id, pin);
+        let snapshot = control.clone();
+        drop(control);
+        if status == RoutingOperatorControlUpdate::Applied {
+            self.notify_state_changed();
+        }
+        Ok(RoutingOperatorControlCommit { status, snapshot })
+    }
+
     pub(crate) async fn commit_runtime_reload<T>(
         &self,
         route_graphs: &[PreparedRoutingOperatorRouteGraph],
@@ -3060,6 +3413,44 @@ impl ProxyState {
             .collect()
     }
 
+    /// Upstreams whose cooldown has expired but which still owe probe-back successes.
+    pub(crate) async fn probe_back_eligible_provider_endpoints(
+        &self,
+        service_name: &str,
+        identities: &[RuntimeUpstreamIdentity],
+        capability: RouteCapability,
+        probe_back_success_threshold: u32,
+    ) -> HashSet<ProviderEndpointKey> {
+        // A threshold of one closes breakers on the first success, so nothing is ever owed.
+        if probe_back_success_threshold <= 1 {
+            return HashSet::new();
+        }
+        let guard = self.provider_endpoint_runtime_health.read().await;
+        let Some(per_service) = guard.get(service_name) else {
+            return HashSet::new();
+        };
+        let now = std::time::Instant::now();
+        identities
+            .iter()
+            .filter_map(|identity| {
+                let identity_key =
+                    ProviderEndpointRuntimeHealthKey::for_service(service_name, identity)?;
+                if per_service.active_revision.is_some()
+                    && !per_service.active_identities.contains(&identity_key)
+                {
+                    return None;
+                }
+                runtime_health_probe_back_probe_available(
+                    per_service,
+                    &identity_key,
+                    capability,
+                    now,
+                )
+                .then(|| identity.provider_endpoint.clone())
+            })
+            .collect()
+    }
+
     pub(crate) async fn try_acquire_runtime_half_open_probe(
         &self,
         service_name: &str,
@@ -3075,13 +3466,10 @@ impl ProxyState {
         {
             return None;
         }
-        let specs = half_open_probe_bucket_specs(
-            per_service,
-            &identity_key,
-            capability,
-            std::time::Instant::now(),
-            None,
-        )?;
+        let now = std::time::Instant::now();
+        let specs =
+            half_open_probe_bucket_specs(per_service, &identity_key, capability, now, None)?;
+        let probe_back = half_open_probe_specs_are_probe_back(per_service, &specs, now);
         for spec in &specs {
             let health = per_service.health.get_mut(&spec.key)?;
             health.half_open_probe_owner = Arc::downgrade(&owner);
@@ -3098,6 +3486,7 @@ impl ProxyState {
             capability,
             owner,
             buckets,
+            probe_back,
         })
     }
 
@@ -3236,11 +3625,12 @@ impl ProxyState {
                     let Some(health) = per_service.health.get_mut(&bucket.key) else {
                         return RuntimeHealthHalfOpenSettlement::Stale;
                     };
-                    record_runtime_health_success(
+                    settle_runtime_health_probe_success(
                         health,
                         bucket.key.domain,
                         probe.capability,
                         now_ms,
+                        std::time::Instant::now(),
                     );
                 }
             }
@@ -3311,6 +3701,8 @@ impl ProxyState {
     ) {
         let mut guard = self.provider_endpoint_runtime_health.write().await;
         let per_service = guard.entry(service_name.to_string()).or_default();
+        let now = std::time::Instant::now();
+        let mut closed_breaker = false;
         for domain in [
             RuntimeHealthDomain::EndpointTransport,
             RuntimeHealthDomain::Credential,
@@ -3325,8 +3717,16 @@ impl ProxyState {
             ) else {
                 return;
             };
+            closed_breaker |= runtime_health_breaker_is_open(entry, now);
             record_runtime_health_success(entry, domain, capability, now_ms);
         }
+        if closed_breaker {
+            self.publish_live_event(LiveEvent::health_changed(
+                &identity.provider_endpoint,
+                false,
+                now_ms,
+            ));
+        }
     }
 
     pub async fn record_runtime_upstream_attempt_failure(
@@ -3362,12 +3762,30 @@ impl ProxyState {
             return;
         };
 
+        let now = std::time::Instant::now();
+        let expired_breaker_closed = reset_expired_runtime_health_breaker(entry, now);
+        let was_open = runtime_health_breaker_is_open(entry, now);
         record_runtime_health_failure(
             entry,
             failure_threshold_cooldown_secs,
             cooldown_backoff,
-            std::time::Instant::now(),
+            now,
         );
+        let is_open = runtime_health_breaker_is_open(entry, now);
+        if expired_breaker_closed {
+            self.publish_live_event(LiveEvent::health_changed(
+                &identity.provider_endpoint,
+                false,
+                unix_now_ms(),
+            ));
+        }
+        if !was_open && is_open {
+            self.publish_live_event(LiveEvent::health_changed(
+                &identity.provider_endpoint,
+                true,
+                unix_now_ms(),
+            ));
+        }
     }
 
     pub async fn penalize_runtime_upstream_attempt(
@@ -3402,12 +3820,23 @@ impl ProxyState {
         else {
             return;
         };
-        penalize_runtime_health(
-            entry,
-            cooldown_secs,
-            cooldown_backoff,
-            std::time::Instant::now(),
-        );
+        let now = std::time::Instant::now();
+        if reset_expired_runtime_health_breaker(entry, now) {
+            self.publish_live_event(LiveEvent::health_changed(
+                &identity.provider_endpoint,
+                false,
+                unix_now_ms(),
+            ));
+        }
+        let was_open = runtime_health_breaker_is_open(entry, now);
+        penalize_runtime_health(entry, cooldown_secs, cooldown_backoff, now);
+        if !was_open {
+            self.publish_live_event(LiveEvent::health_changed(
+                &identity.provider_endpoint,
+                true,
+                unix_now_ms(),
+            ));
+        }
     }
 
     #[cfg(test)]
@@ -4282,6 +4711,7 @@ impl ProxyState {
         policy_revision: u64,
         started_at_ms: u64,
     ) -> Result<u64, RuntimeStoreError> {
+        self.touch_request_activity();
         let session_id = session_id.and_then(|session_id| {
             let canonical = session_id.trim();
             (!canonical.is_empty()).then(|| canonical.to_string())
@@ -4297,6 +4727,7 @@ impl ProxyState {
             path,
             session_identity_source,
             client_name,
+            None,
             client_addr,
             cwd,
             model,
@@ -4323,6 +4754,7 @@ impl ProxyState {
         path: &str,
         session_identity_source: Option<SessionIdentitySource>,
         client_name: Option<String>,
+        tenant: Option<String>,
         client_addr: Option<String>,
         cwd: Option<String>,
         model: Option<String>,
@@ -4366,6 +4798,7 @@ impl ProxyState {
             session_id,
             session_identity_source,
             client_name,
+            tenant,
             client_addr,
             cwd,
             model,
@@ -4375,6 +4808,7 @@ impl ProxyState {
             requested_service_tier,
             provider_id: None,
             route_decision: None,
+            body_hash: None,
             service: service.to_string(),
             method: method.to_string(),
             path: path.to_string(),
@@ -4385,6 +4819,7 @@ impl ProxyState {
         request_state
             .pricing_catalogs
             .insert(id, operator_pricing_catalog);
+        self.publish_live_event(LiveEvent::request_started(&req));
         request_state.active_requests.insert(id, req);
         self.notify_state_changed();
         Ok(id)
@@ -4439,6 +4874,35 @@ impl ProxyState {
         BeginRequestTestBuilder::new(self)
     }
 
+    /// Attaches the client body hash to an active request. Returns the id of an identical
+    /// request from the same session seen within the duplicate window, if any.
+    pub(crate) async fn record_request_body_hash(
+        &self,
+        request_id: u64,
+        body_hash: String,
+        now_ms: u64,
+    ) -> Option<u64> {
+        let (service, session_id) = {
+            let mut request_state = self.request_lifecycle_projection.write().await;
+            let request = request_state.active_requests.get_mut(&request_id)?;
+            request.body_hash = Some(body_hash.clone());
+            (request.service.clone(), request.session_id.clone()?)
+        };
+        self.duplicate_requests
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .observe(&service, &session_id, &body_hash, request_id, now_ms)
+    }
+
+    pub(crate) async fn active_request_tenant(&self, request_id: u64) -> Option<String> {
+        self.request_lifecycle_projection
+            .read()
+            .await
+            .active_requests
+            .get(&request_id)
+            .and_then(|request| request.tenant.clone())
+    }
+
     pub async fn update_request_route(
         &self,
         request_id: u64,
@@ -4625,6 +5089,7 @@ impl ProxyState {
         include_in_economics: bool,
         route_affinity_success: Option<SessionRouteAffinitySuccess>,
     ) -> bool {
+        self.touch_request_activity();
         let _operator_capture = self.operator_capture.write().await;
         let winning_attempt = match params.winning_attempt {
             Some(attempt) if attempt.store_id() == self.runtime_store.identity().store_id() => {
@@ -4682,6 +5147,20 @@ impl ProxyState {
         let Some(req) = request_state.active_requests.get(&params.id).cloned() else {
             return false;
         };
+        if let Some(usage) = params.usage.as_ref()
+            && let Some(provider_id) = req.provider_id.as_deref()
+        {
+            self.request_quotas
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner)
+                .record_output_tokens(
+                    &req.service,
+                    provider_id,
+                    req.tenant.as_deref(),
+                    u64::try_from(usage.output_tokens).unwrap_or(0),
+                    unix_now_ms(),
+                );
+        }
         if let Some(success) = route_affinity_success.as_ref()
             && (success.request_id != params.id
                 || req.session_id.as_deref() != Some(success.session_id.as_str()))
@@ -4832,6 +5311,7 @@ impl ProxyState {
             session_id: req.session_id,
             session_identity_source: req.session_identity_source,
             client_name: req.client_name,
+            tenant: req.tenant,
             client_addr: req.client_addr,
             cwd: req.cwd,
             model: req.model,
@@ -4841,6 +5321,8 @@ impl ProxyState {
                 .or_else(|| effective_service_tier.clone()),
             provider_id,
             route_decision,
+            body_hash: req.body_hash,
+            upstream_request_id: params.upstream_request_id,
             usage: params.usage.clone(),
             cost,
             accounting: RequestAccountingFacts::default(),
@@ -5081,24 +5563,115 @@ impl ProxyState {
             entry.last_seen_ms = finished.ended_at_ms;
         }
 
+        self.publish_live_event(LiveEvent::request_finished(&finished));
         request_state.recent_finished.push_front(finished);
-        while request_state.recent_finished.len() > recent_finished_max() {
+        while request_state.recent_finished.len() > self.recent_finished_max() {
             request_state.recent_finished.pop_back();
         }
         self.notify_state_changed();
         true
     }
 
-    pub async fn list_active_requests(&self) -> Vec<ActiveRequest> {
-        let request_state = self.request_lifecycle_projection.read().await;
-        let mut vec = request_state
-            .active_requests
-            .values()
-            .cloned()
-            .collect::<Vec<_>>();
-        vec.sort_by_key(|r| r.started_at_ms);
-        vec
-    }
+    /// Counts a request against the provider's `quota` budgets, or explains why it must be refused.
+    pub fn try_admit_request_quota(
+        &self,
+        service: &str,
+        provider_id: &str,
+        tenant: Option<&str>,
+        config: &crate::config::RequestQuotaConfig,
+    ) -> Result<(), RequestQuotaExceeded> {
+        self.request_quotas
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .try_admit(service, provider_id, tenant, config, unix_now_ms())
+    }
+
+    pub fn request_quota_usage(
+        &self,
+        service: &str,
+        config: &crate::config::RequestQuotaConfig,
+        now_ms: u64,
+    ) -> Vec<RequestQuotaUsage> {
+        self.request_quotas
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .usage(service, config, now_ms)
+    }
+
+    fn touch_request_activity(&self) {
+        self.last_request_activity_ms
+            .fetch_max(unix_now_ms(), Ordering::Relaxed);
+    }
+
+    /// Wall-clock time of the most recent request begin or finish (runtime start if none).
+    pub fn last_request_activity_ms(&self) -> u64 {
+        self.last_request_activity_ms.load(Ordering::Relaxed)
+    }
+
+    pub async fn has_active_requests(&self) -> bool {
+        !self
+            .request_lifecycle_projection
+            .read()
+            .await
+            .active_requests
+            .is_empty()
+    }
+
+    pub async fn list_active_requests(&self) -> Vec<ActiveRequest> {
+        let request_state = self.request_lifecycle_projection.read().await;
+        let mut vec = request_state
+            .active_requests
+            .values()
+            .cloned()
+            .collect::<Vec<_>>();
+        vec.sort_by_key(|r| r.started_at_ms);
+        vec
+    }
+
+    /// Number of finished requests retained for dashboards.
+    pub fn recent_finished_max(&self) -> usize {
+        self.recent_finished_max.load(Ordering::Relaxed)
+    }
+
+    /// Resizes the recent-request buffer, dropping the oldest entries when it shrinks.
+    pub async fn set_recent_finished_max(&self, max: usize) {
+        let max = max.max(1);
+        let mut request_state = self.request_lifecycle_projection.write().await;
+        if self.recent_finished_max.swap(max, Ordering::Relaxed) == max {
+            return;
+        }
+        if request_state.recent_finished.len() > max {
+            request_state.recent_finished.truncate(max);
+            drop(request_state);
+            self.notify_state_changed();
+        }
+    }
+
+    /// Cached response for `key` when it was stored at most `ttl_ms` before `now_ms`.
+    pub async fn cached_get_response(
+        &self,
+        key: &str,
+        ttl_ms: u64,
+        now_ms: u64,
+    ) -> Option<CachedGetResponse> {
+        let cache = self.get_response_cache.read().await;
+        cache
+            .get(key)
+            .filter(|entry| now_ms.saturating_sub(entry.stored_at_ms) < ttl_ms)
+            .cloned()
+    }
+
+    pub async fn store_get_response(&self, key: String, response: CachedGetResponse) {
+        let mut cache = self.get_response_cache.write().await;
+        cache.insert(key, response);
+        prune_lru_cache(&mut cache, GET_RESPONSE_CACHE_MAX_ENTRIES, |entry| {
+            entry.stored_at_ms
+        });
+    }
+
+    pub async fn clear_get_response_cache(&self) {
+        self.get_response_cache.write().await.clear();
+    }
 
     pub async fn list_recent_finished(&self, limit: usize) -> Vec<FinishedRequest> {
         self.request_lifecycle_projection
@@ -5111,6 +5684,18 @@ impl ProxyState {
             .collect()
     }
 
+    /// Total cost frozen on a just-finished request, so the request log records the price that
+    /// applied when it ran. `None` when the request was unpriced or is no longer recent.
+    pub(crate) async fn recent_finished_cost_usd(&self, request_id: u64) -> Option<String> {
+        self.request_lifecycle_projection
+            .read()
+            .await
+            .recent_finished
+            .iter()
+            .find(|request| request.id == request_id)
+            .and_then(|request| request.cost.total_cost_usd.clone())
+    }
+
     pub async fn list_session_stats(&self, service_name: &str) -> HashMap<String, SessionStats> {
         self.request_lifecycle_projection
             .read()
@@ -5126,10 +5711,10 @@ impl ProxyState {
         service_name: &str,
         recent_limit: usize,
     ) -> Vec<SessionIdentityCard> {
-        let recent_limit = recent_limit.clamp(1, recent_finished_max());
+        let recent_limit = recent_limit.clamp(1, self.recent_finished_max());
         let (active, recent, bindings, route_affinities, stats) = tokio::join!(
             self.list_active_requests(),
-            self.list_recent_finished(recent_finished_max()),
+            self.list_recent_finished(self.recent_finished_max()),
             self.list_session_bindings(),
             self.list_session_route_affinities(),
             self.list_session_stats(service_name),
@@ -5382,6 +5967,26 @@ impl ProxyState {
                 });
                 !stats.is_empty()
             });
+            let live_sessions = request_state
+                .session_stats
+                .iter()
+                .map(|(service, stats)| (service.clone(), stats.keys().cloned().collect()))
+                .collect::<HashMap<String, HashSet<String>>>();
+            drop(request_state);
+
+            // A session pin outlives its session only until the session stats expire.
+            let removed = self
+                .routing_operator_control
+                .write()
+                .await
+                .prune_session_upstream_pins(cutoff_stats, |service, sid| {
+                    live_sessions
+                        .get(service)
+                        .is_some_and(|sessions| sessions.contains(sid))
+                });
+            if removed > 0 {
+                self.notify_state_changed();
+            }
         }
     }
 
@@ -5495,7 +6100,6 @@ mod tests {
     use crate::config::{ProviderConfig, RouteGraphConfig, ServiceRouteConfig};
     use crate::runtime_identity::ProviderEndpointKey;
     use std::path::Path;
-    use std::sync::OnceLock;
 
     fn route_view(providers: &[(&str, &str)]) -> ServiceRouteConfig {
         ServiceRouteConfig {
@@ -5549,12 +6153,14 @@ mod tests {
             session_id: Some(session_id.to_string()),
             session_identity_source: Some(SessionIdentitySource::Header),
             client_name: None,
+            tenant: None,
             client_addr: None,
             cwd: None,
             model: Some("gpt-test".to_string()),
             reasoning_effort: None,
             service_tier: None,
             provider_id: None,
+            body_hash: None,
             route_decision: None,
             usage: Some(UsageMetrics {
                 input_tokens: 1,
@@ -5575,6 +6181,7 @@ mod tests {
             ttfb_ms: None,
             streaming: false,
             ended_at_ms: id,
+            upstream_request_id: None,
         }
     }
 
@@ -5634,12 +6241,7 @@ mod tests {
             .expect("begin provider attempt")
     }
 
-    async fn env_lock() -> tokio::sync::MutexGuard<'static, ()> {
-        static LOCK: OnceLock<tokio::sync::Mutex<()>> = OnceLock::new();
-        LOCK.get_or_init(|| tokio::sync::Mutex::new(()))
-            .lock()
-            .await
-    }
+    use crate::test_support::env_lock;
 
     #[derive(Default)]
     struct ScopedEnv {
@@ -5691,6 +6293,7 @@ mod tests {
             session_route_affinity_max_entries: 5_000,
             session_transcript_path_cache_ttl_ms: 30_000,
             session_transcript_path_cache_max_entries: 5_000,
+            recent_finished_max: default_recent_finished_max(),
         }
     }
 
@@ -5937,6 +6540,7 @@ mod tests {
                         retry: None,
                         ttfb_ms: Some(4),
                         streaming: false,
+                        upstream_request_id: None,
                     })
                     .await
             });
@@ -6110,6 +6714,7 @@ mod tests {
                     retry: None,
                     ttfb_ms: Some(4),
                     streaming: false,
+                    upstream_request_id: None,
                 })
                 .await;
 
@@ -6124,11 +6729,166 @@ mod tests {
         });
     }
 
+    #[test]
+    fn request_lifecycle_is_published_to_live_event_subscribers() {
+        let runtime = tokio::runtime::Runtime::new().expect("runtime");
+        runtime.block_on(async {
+            let state = ProxyState::new();
+            let mut events = state.subscribe_live_events();
+            let request_id = state
+                .begin_request_for_test()
+                .model("gpt-5")
+                .started_at_ms(100)
+                .begin()
+                .await;
+            state
+                .finish_request(FinishRequestParams {
+                    id: request_id,
+                    winning_attempt: None,
+                    status_code: 502,
+                    duration_ms: 10,
+                    ended_at_ms: 110,
+                    observed_service_tier: None,
+                    reported_model: None,
+                    usage: None,
+                    retry: None,
+                    ttfb_ms: None,
+                    streaming: true,
+                    upstream_request_id: None,
+                })
+                .await;
+
+            let started = events.try_recv().expect("request_started event");
+            assert_eq!(started.kind(), "request_started");
+            let finished = events.try_recv().expect("request_finished event");
+            let LiveEvent::RequestFinished {
+                request_id: finished_id,
+                status_code,
+                streaming,
+                ..
+            } = finished
+            else {
+                panic!("expected request_finished, got {finished:?}");
+            };
+            assert_eq!(finished_id, request_id);
+            assert_eq!(status_code, 502);
+            assert!(streaming);
+        });
+    }
+
+    #[test]
+    fn request_begin_and_finish_refresh_activity_timestamp() {
+        let runtime = tokio::runtime::Runtime::new().expect("runtime");
+        runtime.block_on(async {
+            let state = ProxyState::new();
+            state.last_request_activity_ms.store(0, Ordering::Relaxed);
+            assert!(!state.has_active_requests().await);
+
+            let request_id = state
+                .begin_request_for_test()
+                .started_at_ms(100)
+                .begin()
+                .await;
+            let after_begin = state.last_request_activity_ms();
+            assert!(after_begin > 0);
+            assert!(state.has_active_requests().await);
+
+            state.last_request_activity_ms.store(0, Ordering::Relaxed);
+            state
+                .finish_request(FinishRequestParams {
+                    id: request_id,
+                    winning_attempt: None,
+                    status_code: 200,
+                    duration_ms: 10,
+                    ended_at_ms: 110,
+                    observed_service_tier: None,
+                    reported_model: None,
+                    usage: None,
+                    retry: None,
+                    ttfb_ms: None,
+                    streaming: false,
+                    upstream_request_id: None,
+                })
+                .await;
+            assert!(state.last_request_activity_ms() >= after_begin);
+            assert!(!state.has_active_requests().await);
+        });
+    }
+
     #[test]
     fn recent_finished_max_defaults_to_one_thousand() {
         assert_eq!(recent_finished_max_from_env(None), 1_000);
     }
 
+    #[test]
+    fn resizing_recent_finished_keeps_newest_entries_in_order() {
+        let runtime = tokio::runtime::Runtime::new().expect("runtime");
+        runtime.block_on(async {
+            let state = ProxyState::new();
+            let mut ids = Vec::new();
+            for started_at_ms in [100, 200, 300, 400] {
+                let request_id = state
+                    .begin_request_for_test()
+                    .started_at_ms(started_at_ms)
+                    .begin()
+                    .await;
+                state
+                    .finish_request(FinishRequestParams {
+                        id: request_id,
+                        winning_attempt: None,
+                        status_code: 200,
+                        duration_ms: 10,
+                        ended_at_ms: started_at_ms + 10,
+                        observed_service_tier: None,
+                        reported_model: None,
+                        usage: None,
+                        retry: None,
+                        ttfb_ms: None,
+                        streaming: false,
+                        upstream_request_id: None,
+                    })
+                    .await;
+                ids.push(request_id);
+            }
+
+            state.set_recent_finished_max(2).await;
+            assert_eq!(state.recent_finished_max(), 2);
+            let recent = state.list_recent_finished(10).await;
+            assert_eq!(
+                recent.iter().map(|request| request.id).collect::<Vec<_>>(),
+                vec![ids[3], ids[2]]
+            );
+
+            state.set_recent_finished_max(3).await;
+            let request_id = state
+                .begin_request_for_test()
+                .started_at_ms(500)
+                .begin()
+                .await;
+            state
+                .finish_request(FinishRequestParams {
+                    id: request_id,
+                    winning_attempt: None,
+                    status_code: 200,
+                    duration_ms: 10,
+                    ended_at_ms: 510,
+                    observed_service_tier: None,
+                    reported_model: None,
+                    usage: None,
+                    retry: None,
+                    ttfb_ms: None,
+                    streaming: false,
+                    upstream_request_id: None,
+                })
+                .await;
+            let recent = state.list_recent_finished(10).await;
+            assert_eq!(
+                recent.iter().map(|request| request.id).collect::<Vec<_>>(),
+                vec![request_id, ids[3], ids[2]]
+            );
+        });
+    }
+
     #[test]
     fn finish_request_reports_exactly_once_publication() {
         let runtime = tokio::runtime::Runtime::new().expect("runtime");
@@ -6153,6 +6913,7 @@ mod tests {
                     retry: None,
                     ttfb_ms: Some(4),
                     streaming: false,
+                    upstream_request_id: None,
                 })
                 .await;
             let second = state
@@ -6168,6 +6929,7 @@ mod tests {
                     retry: None,
                     ttfb_ms: None,
                     streaming: false,
+                    upstream_request_id: None,
                 })
                 .await;
 
@@ -6213,6 +6975,7 @@ mod tests {
                     retry: None,
                     ttfb_ms: Some(4),
                     streaming: false,
+                    upstream_request_id: None,
                 })
                 .await;
 
@@ -6265,6 +7028,7 @@ mod tests {
                         retry: None,
                         ttfb_ms: Some(4),
                         streaming: false,
+                        upstream_request_id: None,
                     })
                     .await
             });
@@ -6419,6 +7183,7 @@ confidence = "exact"
                         retry: None,
                         ttfb_ms: Some(4),
                         streaming: false,
+                        upstream_request_id: None,
                     })
                     .await
             });
@@ -6551,6 +7316,7 @@ confidence = "exact"
                         retry: None,
                         ttfb_ms: Some(4),
                         streaming: false,
+                        upstream_request_id: None,
                     })
                     .await
             );
@@ -6579,6 +7345,7 @@ confidence = "exact"
                         retry: None,
                         ttfb_ms: None,
                         streaming: false,
+                        upstream_request_id: None,
                     })
                     .await
             );
@@ -6674,6 +7441,7 @@ confidence = "exact"
                         retry: None,
                         ttfb_ms: None,
                         streaming: false,
+                        upstream_request_id: None,
                     })
                     .await
             );
@@ -6702,6 +7470,7 @@ confidence = "exact"
                         retry: None,
                         ttfb_ms: None,
                         streaming: false,
+                        upstream_request_id: None,
                     })
                     .await
             );
@@ -6721,8 +7490,8 @@ confidence = "exact"
             assert_eq!(live_provider_summary.rows[0].aggregate.requests, 1);
             assert_eq!(live_provider_summary.rows[0].aggregate.input_tokens, 200);
 
-            let hydrated =
-                hydrate_runtime_projections(&store).expect("rehydrate bounded runtime projections");
+            let hydrated = hydrate_runtime_projections(&store, default_recent_finished_max())
+                .expect("rehydrate bounded runtime projections");
             assert_eq!(hydrated.committed_terminal_count, 2);
             assert_eq!(hydrated.next_request_id, current_id + 1);
             assert_eq!(hydrated.usage_rollups["codex"].loaded.requests_total, 1);
@@ -6802,6 +7571,7 @@ confidence = "exact"
                             retry: None,
                             ttfb_ms: None,
                             streaming: false,
+                            upstream_request_id: None,
                         })
                         .await
                 );
@@ -6903,6 +7673,7 @@ confidence = "exact"
                     retry: None,
                     ttfb_ms: Some(500),
                     streaming: true,
+                    upstream_request_id: None,
                 })
                 .await;
 
@@ -6929,6 +7700,7 @@ confidence = "exact"
                     retry: None,
                     ttfb_ms: Some(500),
                     streaming: true,
+                    upstream_request_id: None,
                 })
                 .await;
 
@@ -7004,6 +7776,7 @@ confidence = "exact"
                     retry: None,
                     ttfb_ms: Some(5),
                     streaming: false,
+                    upstream_request_id: None,
                 })
                 .await;
             changes.changed().await.expect("finish request change");
@@ -7080,6 +7853,7 @@ confidence = "exact"
                 retry: None,
                 ttfb_ms: Some(4),
                 streaming: false,
+                upstream_request_id: None,
             };
 
             assert!(state.finish_request(finish_params(request_id, 110)).await);
@@ -7211,6 +7985,7 @@ confidence = "exact"
                         retry: None,
                         ttfb_ms: Some(4),
                         streaming: false,
+                        upstream_request_id: None,
                     })
                     .await
             );
@@ -7312,6 +8087,7 @@ confidence = "exact"
                         retry: None,
                         ttfb_ms: Some(4),
                         streaming: false,
+                        upstream_request_id: None,
                     })
                     .await
             );
@@ -7392,6 +8168,7 @@ confidence = "exact"
                             retry: None,
                             ttfb_ms: Some(4),
                             streaming: false,
+                            upstream_request_id: None,
                         })
                         .await,
                     "actual tier: {actual_tier:?}"
@@ -7449,6 +8226,7 @@ confidence = "exact"
                     retry: None,
                     ttfb_ms: Some(4),
                     streaming: false,
+                    upstream_request_id: None,
                 })
                 .await;
 
@@ -7519,6 +8297,7 @@ confidence = "exact"
                     retry: None,
                     ttfb_ms: Some(3),
                     streaming: false,
+                    upstream_request_id: None,
                 })
                 .await;
 
@@ -7602,6 +8381,7 @@ confidence = "exact"
                     retry: None,
                     ttfb_ms: Some(10),
                     streaming: false,
+                    upstream_request_id: None,
                 })
                 .await;
 
@@ -7641,6 +8421,7 @@ confidence = "exact"
                     retry: None,
                     ttfb_ms: Some(20),
                     streaming: false,
+                    upstream_request_id: None,
                 })
                 .await;
 
@@ -7710,6 +8491,7 @@ confidence = "exact"
                     retry: None,
                     ttfb_ms: Some(5),
                     streaming: false,
+                    upstream_request_id: None,
                 })
                 .await;
 
@@ -7745,6 +8527,7 @@ confidence = "exact"
                     retry: None,
                     ttfb_ms: Some(3),
                     streaming: false,
+                    upstream_request_id: None,
                 })
                 .await;
 
@@ -7779,6 +8562,7 @@ confidence = "exact"
             session_id: Some("sid-active".to_string()),
             session_identity_source: Some(SessionIdentitySource::Header),
             client_name: Some("Frank-Laptop".to_string()),
+            tenant: None,
             client_addr: Some("100.64.0.8".to_string()),
             cwd: Some("G:/codes/project".to_string()),
             model: Some("gpt-5.4".to_string()),
@@ -7787,6 +8571,7 @@ confidence = "exact"
             service_tier: Some("priority".to_string()),
             requested_service_tier: Some("priority".to_string()),
             provider_id: Some("right".to_string()),
+            body_hash: None,
             route_decision: None,
             service: "codex".to_string(),
             method: "POST".to_string(),
@@ -7800,12 +8585,14 @@ confidence = "exact"
                 session_id: Some("sid-recent".to_string()),
                 session_identity_source: Some(SessionIdentitySource::PromptCacheKey),
                 client_name: Some("Studio-Mini".to_string()),
+                tenant: None,
                 client_addr: Some("100.64.0.9".to_string()),
                 cwd: Some("G:/codes/other".to_string()),
                 model: Some("gpt-5.3".to_string()),
                 reasoning_effort: Some("high".to_string()),
                 service_tier: Some("default".to_string()),
                 provider_id: Some("vibe".to_string()),
+                body_hash: None,
                 route_decision: None,
                 usage: Some(UsageMetrics {
                     input_tokens: 1,
@@ -7828,6 +8615,7 @@ confidence = "exact"
                 ttfb_ms: Some(100),
                 streaming: false,
                 ended_at_ms: 2_000,
+                upstream_request_id: None,
             },
             FinishedRequest {
                 id: 3,
@@ -7835,12 +8623,14 @@ confidence = "exact"
                 session_id: Some("sid-active".to_string()),
                 session_identity_source: Some(SessionIdentitySource::Header),
                 client_name: Some("Frank-Laptop".to_string()),
+                tenant: None,
                 client_addr: Some("100.64.0.8".to_string()),
                 cwd: Some("G:/codes/project".to_string()),
                 model: Some("gpt-5.4".to_string()),
                 reasoning_effort: Some("low".to_string()),
                 service_tier: Some("flex".to_string()),
                 provider_id: Some("right".to_string()),
+                body_hash: None,
                 route_decision: None,
                 usage: None,
                 cost: CostBreakdown::default(),
@@ -7857,6 +8647,7 @@ confidence = "exact"
                 ttfb_ms: None,
                 streaming: false,
                 ended_at_ms: 1_000,
+                upstream_request_id: None,
             },
         ];
         let stats = HashMap::from([(
@@ -7967,6 +8758,7 @@ confidence = "exact"
             session_id: Some("sid-bound".to_string()),
             session_identity_source: Some(SessionIdentitySource::Header),
             client_name: Some("Workstation".to_string()),
+            tenant: None,
             client_addr: Some("100.64.0.10".to_string()),
             cwd: None,
             model: Some("gpt-observed".to_string()),
@@ -7975,6 +8767,7 @@ confidence = "exact"
             service_tier: Some("default".to_string()),
             requested_service_tier: Some("default".to_string()),
             provider_id: None,
+            body_hash: None,
             route_decision: None,
             service: "codex".to_string(),
             method: "POST".to_string(),
@@ -8284,6 +9077,7 @@ confidence = "exact"
                     retry: None,
                     ttfb_ms: None,
                     streaming: false,
+                    upstream_request_id: None,
                 })
                 .await;
 
@@ -8368,6 +9162,7 @@ confidence = "exact"
                     retry: None,
                     ttfb_ms: None,
                     streaming: false,
+                    upstream_request_id: None,
                 })
                 .await;
 
@@ -8407,6 +9202,7 @@ confidence = "exact"
                     crate::endpoint_health::CooldownBackoff {
                         factor: 1,
                         max_secs: 0,
+                        probe_back_success_threshold: 1,
                     },
                 )
                 .await;
@@ -8418,6 +9214,7 @@ confidence = "exact"
                     crate::endpoint_health::CooldownBackoff {
                         factor: 1,
                         max_secs: 0,
+                        probe_back_success_threshold: 1,
                     },
                 )
                 .await;
@@ -8429,6 +9226,7 @@ confidence = "exact"
                     crate::endpoint_health::CooldownBackoff {
                         factor: 1,
                         max_secs: 0,
+                        probe_back_success_threshold: 1,
                     },
                 )
                 .await;
@@ -8483,6 +9281,7 @@ confidence = "exact"
             let cooldown_backoff = CooldownBackoff {
                 factor: 1,
                 max_secs: 0,
+                probe_back_success_threshold: 1,
             };
 
             state
@@ -8581,6 +9380,7 @@ confidence = "exact"
             let cooldown_backoff = CooldownBackoff {
                 factor: 1,
                 max_secs: 0,
+                probe_back_success_threshold: 1,
             };
 
             state
@@ -8632,6 +9432,177 @@ confidence = "exact"
         });
     }
 
+    #[test]
+    fn providers_sharing_a_base_url_keep_separate_health_and_usage() {
+        let runtime = tokio::runtime::Runtime::new().expect("runtime");
+        runtime.block_on(async {
+            let state = ProxyState::new();
+            let base_url = "https://relay.example/v1";
+            let work = ProviderEndpointKey::new("codex", "work", "default");
+            let personal = ProviderEndpointKey::new("codex", "personal", "default");
+            let identities = [
+                RuntimeUpstreamIdentity::new(work.clone(), base_url),
+                RuntimeUpstreamIdentity::new(personal.clone(), base_url),
+            ];
+            let policy = state.capture_provider_policy_snapshot().await;
+            state
+                .reconcile_runtime_upstream_identities(&identities, 1)
+                .await
+                .expect("publish runtime identities");
+            state
+                .penalize_runtime_upstream_attempt(
+                    "codex",
+                    &identities[0],
+                    30,
+                    CooldownBackoff {
+                        factor: 1,
+                        max_secs: 0,
+                        probe_back_success_threshold: 1,
+                    },
+                )
+                .await;
+
+            let runtime_state = state
+                .route_plan_runtime_state_with_provider_policy_for_capability(
+                    "codex",
+                    policy.as_ref(),
+                    1,
+                    &identities,
+                    Some(RouteCapability::Inference),
+                )
+                .await;
+            assert!(runtime_state.provider_endpoint(&work).cooldown_active);
+            assert!(!runtime_state.provider_endpoint(&personal).cooldown_active);
+
+            for (provider_id, total_tokens) in [("work", 10), ("personal", 32)] {
+                let request_id = state.begin_request_for_test().model("gpt-5").begin().await;
+                state
+                    .update_request_route(
+                        request_id,
+                        provider_route_decision(provider_id, "default", base_url),
+                    )
+                    .await;
+                state
+                    .finish_request(FinishRequestParams {
+                        id: request_id,
+                        winning_attempt: None,
+                        status_code: 200,
+                        duration_ms: 10,
+                        ended_at_ms: 1_704_038_400_000,
+                        observed_service_tier: None,
+                        reported_model: None,
+                        usage: Some(UsageMetrics {
+                            total_tokens,
+                            ..UsageMetrics::default()
+                        }),
+                        retry: None,
+                        ttfb_ms: None,
+                        streaming: false,
+                        upstream_request_id: None,
+                    })
+                    .await;
+            }
+
+            let request_state = state.request_lifecycle_projection.read().await;
+            let rollup = &request_state.usage_rollups["codex"];
+            assert_eq!(rollup.by_provider["work"].usage.total_tokens, 10);
+            assert_eq!(rollup.by_provider["personal"].usage.total_tokens, 32);
+            assert_eq!(
+                rollup.by_provider_endpoint[&personal.stable_key()]
+                    .usage
+                    .total_tokens,
+                32
+            );
+        });
+    }
+
+    #[test]
+    fn breaker_transitions_are_published_once_per_change() {
+        let runtime = tokio::runtime::Runtime::new().expect("runtime");
+        runtime.block_on(async {
+            let state = ProxyState::new();
+            let endpoint = ProviderEndpointKey::new("codex", "relay", "default");
+            let identity =
+                RuntimeUpstreamIdentity::new(endpoint.clone(), "https://relay.example/v1");
+            let cooldown_backoff = CooldownBackoff {
+                factor: 1,
+                max_secs: 0,
+                probe_back_success_threshold: 1,
+            };
+            state
+                .reconcile_runtime_upstream_identities(std::slice::from_ref(&identity), 1)
+                .await
+                .expect("publish runtime identity");
+            let mut events = state.subscribe_live_events();
+
+            for _ in 0..2 {
+                state
+                    .penalize_runtime_upstream_attempt("codex", &identity, 30, cooldown_backoff)
+                    .await;
+            }
+            state
+                .record_runtime_upstream_attempt_success("codex", &identity, 1_000)
+                .await;
+            state
+                .record_runtime_upstream_attempt_success("codex", &identity, 2_000)
+                .await;
+
+            let breaker_states = std::iter::from_fn(|| events.try_recv().ok())
+                .map(|event| match event {
+                    LiveEvent::HealthChanged {
+                        provider_id,
+                        breaker_open,
+                        ..
+                    } => {
+                        assert_eq!(provider_id, "relay");
+                        breaker_open
+                    }
+                    other => panic!("unexpected event {other:?}"),
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(breaker_states, vec![true, false]);
+        });
+    }
+
+    #[test]
+    fn expired_cooldown_publishes_the_breaker_close_when_observed() {
+        let runtime = tokio::runtime::Runtime::new().expect("runtime");
+        runtime.block_on(async {
+            let state = ProxyState::new();
+            let endpoint = ProviderEndpointKey::new("codex", "relay", "default");
+            let identity =
+                RuntimeUpstreamIdentity::new(endpoint.clone(), "https://relay.example/v1");
+            let cooldown_backoff = CooldownBackoff {
+                factor: 1,
+                max_secs: 0,
+                probe_back_success_threshold: 1,
+            };
+            state
+                .reconcile_runtime_upstream_identities(std::slice::from_ref(&identity), 1)
+                .await
+                .expect("publish runtime identity");
+            let mut events = state.subscribe_live_events();
+
+            // A zero-second cooldown has already expired when routing next looks at it.
+            state
+                .penalize_runtime_upstream_attempt("codex", &identity, 0, cooldown_backoff)
+                .await;
+            for _ in 0..2 {
+                state
+                    .route_plan_runtime_state_for_provider_endpoints("codex")
+                    .await;
+            }
+
+            let breaker_states = std::iter::from_fn(|| events.try_recv().ok())
+                .map(|event| match event {
+                    LiveEvent::HealthChanged { breaker_open, .. } => breaker_open,
+                    other => panic!("unexpected event {other:?}"),
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(breaker_states, vec![true, false]);
+        });
+    }
+
     #[test]
     fn half_open_probe_is_singleflight_and_once_per_breaker_epoch() {
         let runtime = tokio::runtime::Runtime::new().expect("runtime");
@@ -8643,6 +9614,7 @@ confidence = "exact"
             let cooldown_backoff = CooldownBackoff {
                 factor: 1,
                 max_secs: 0,
+                probe_back_success_threshold: 1,
             };
             state
                 .reconcile_runtime_upstream_identities(std::slice::from_ref(&identity), 1)
@@ -8753,6 +9725,7 @@ confidence = "exact"
             let cooldown_backoff = CooldownBackoff {
                 factor: 1,
                 max_secs: 0,
+                probe_back_success_threshold: 1,
             };
             state
                 .reconcile_runtime_upstream_identities(std::slice::from_ref(&identity), 1)
@@ -8835,6 +9808,7 @@ confidence = "exact"
             let cooldown_backoff = CooldownBackoff {
                 factor: 1,
                 max_secs: 0,
+                probe_back_success_threshold: 1,
             };
             state
                 .reconcile_runtime_upstream_identities(std::slice::from_ref(&identity), 1)
@@ -8912,6 +9886,161 @@ confidence = "exact"
         });
     }
 
+    #[test]
+    fn probe_back_threshold_keeps_primary_open_until_consecutive_probe_successes() {
+        let runtime = tokio::runtime::Runtime::new().expect("runtime");
+        runtime.block_on(async {
+            let state = ProxyState::new();
+            let primary = ProviderEndpointKey::new("codex", "primary", "default");
+            let backup = ProviderEndpointKey::new("codex", "backup", "default");
+            let identities = vec![
+                RuntimeUpstreamIdentity::new(primary.clone(), "https://primary.example/v1"),
+                RuntimeUpstreamIdentity::new(backup.clone(), "https://backup.example/v1"),
+            ];
+            let cooldown_backoff = CooldownBackoff {
+                factor: 1,
+                max_secs: 0,
+                probe_back_success_threshold: 3,
+            };
+            state
+                .reconcile_runtime_upstream_identities(identities.as_slice(), 1)
+                .await
+                .expect("publish runtime identities");
+            // A zero-second cooldown expires immediately, leaving only the probe-back gate.
+            state
+                .penalize_runtime_upstream_attempt("codex", &identities[0], 0, cooldown_backoff)
+                .await;
+
+            for success in 1..=3 {
+                let projected = state
+                    .route_plan_runtime_state_for_provider_endpoints("codex")
+                    .await;
+                assert_eq!(
+                    projected.provider_endpoint(&primary).failure_count,
+                    FAILURE_THRESHOLD,
+                    "primary should stay behind the backup before probe {success}"
+                );
+                assert_eq!(projected.provider_endpoint(&backup).failure_count, 0);
+
+                let eligible = state
+                    .probe_back_eligible_provider_endpoints(
+                        "codex",
+                        identities.as_slice(),
+                        RouteCapability::Inference,
+                        cooldown_backoff.probe_back_success_threshold,
+                    )
+                    .await;
+                assert_eq!(eligible, HashSet::from([primary.clone()]));
+                let acquired = state
+                    .try_acquire_runtime_half_open_probe(
+                        "codex",
+                        &identities[0],
+                        RouteCapability::Inference,
+                    )
+                    .await
+                    .expect("acquire probe-back probe");
+                assert!(acquired.is_probe_back());
+                let dispatched = state
+                    .dispatch_runtime_half_open_probe(acquired)
+                    .await
+                    .expect("dispatch probe-back probe");
+                assert_eq!(
+                    state
+                        .settle_runtime_half_open_probe(
+                            dispatched,
+                            RuntimeHealthHalfOpenTerminal::Success { now_ms: 20 },
+                        )
+                        .await,
+                    RuntimeHealthHalfOpenSettlement::Applied
+                );
+            }
+
+            let projected = state
+                .route_plan_runtime_state_for_provider_endpoints("codex")
+                .await
+                .provider_endpoint(&primary);
+            assert_eq!(projected.failure_count, 0);
+            assert!(!projected.cooldown_active);
+            assert!(
+                state
+                    .probe_back_eligible_provider_endpoints(
+                        "codex",
+                        identities.as_slice(),
+                        RouteCapability::Inference,
+                        cooldown_backoff.probe_back_success_threshold,
+                    )
+                    .await
+                    .is_empty()
+            );
+        });
+    }
+
+    #[test]
+    fn probe_back_failure_restarts_the_consecutive_success_count() {
+        let runtime = tokio::runtime::Runtime::new().expect("runtime");
+        runtime.block_on(async {
+            let state = ProxyState::new();
+            let endpoint = ProviderEndpointKey::new("codex", "primary", "default");
+            let identity =
+                RuntimeUpstreamIdentity::new(endpoint.clone(), "https://primary.example/v1");
+            let cooldown_backoff = CooldownBackoff {
+                factor: 1,
+                max_secs: 0,
+                probe_back_success_threshold: 2,
+            };
+            state
+                .reconcile_runtime_upstream_identities(std::slice::from_ref(&identity), 1)
+                .await
+                .expect("publish runtime identity");
+            state
+                .penalize_runtime_upstream_attempt("codex", &identity, 0, cooldown_backoff)
+                .await;
+
+            let settle = |terminal| {
+                let state = &state;
+                let identity = &identity;
+                async move {
+                    let acquired = state
+                        .try_acquire_runtime_half_open_probe(
+                            "codex",
+                            identity,
+                            RouteCapability::Inference,
+                        )
+                        .await
+                        .expect("acquire probe-back probe");
+                    let dispatched = state
+                        .dispatch_runtime_half_open_probe(acquired)
+                        .await
+                        .expect("dispatch probe-back probe");
+                    state
+                        .settle_runtime_half_open_probe(dispatched, terminal)
+                        .await
+                }
+            };
+            settle(RuntimeHealthHalfOpenTerminal::Success { now_ms: 20 }).await;
+            settle(RuntimeHealthHalfOpenTerminal::Penalty {
+                domain: RuntimeHealthDomain::Capability(RouteCapability::Inference),
+                cooldown_secs: 0,
+                cooldown_backoff,
+            })
+            .await;
+            settle(RuntimeHealthHalfOpenTerminal::Success { now_ms: 30 }).await;
+
+            let projected = state
+                .route_plan_runtime_state_for_provider_endpoints("codex")
+                .await
+                .provider_endpoint(&endpoint);
+            assert_eq!(projected.failure_count, FAILURE_THRESHOLD);
+
+            settle(RuntimeHealthHalfOpenTerminal::Success { now_ms: 40 }).await;
+            let projected = state
+                .route_plan_runtime_state_for_provider_endpoints("codex")
+                .await
+                .provider_endpoint(&endpoint);
+            assert_eq!(projected.failure_count, 0);
+        });
+    }
+
     #[test]
     fn half_open_probe_rejects_credential_and_capacity_domains() {
         let runtime = tokio::runtime::Runtime::new().expect("runtime");
@@ -8919,6 +10048,7 @@ confidence = "exact"
             let cooldown_backoff = CooldownBackoff {
                 factor: 1,
                 max_secs: 0,
+                probe_back_success_threshold: 1,
             };
             for (case, domains) in [
                 ("credential_only", vec![RuntimeHealthDomain::Credential]),
@@ -8997,6 +10127,7 @@ confidence = "exact"
             let cooldown_backoff = CooldownBackoff {
                 factor: 1,
                 max_secs: 0,
+                probe_back_success_threshold: 1,
             };
             state
                 .reconcile_runtime_upstream_identities(std::slice::from_ref(&identity_a), 1)
@@ -9055,6 +10186,7 @@ confidence = "exact"
             let cooldown_backoff = CooldownBackoff {
                 factor: 1,
                 max_secs: 0,
+                probe_back_success_threshold: 1,
             };
             state
                 .reconcile_runtime_upstream_identities(std::slice::from_ref(&identity), 1)
@@ -9251,6 +10383,7 @@ confidence = "exact"
                         retry: None,
                         ttfb_ms: Some(10),
                         streaming: false,
+                        upstream_request_id: None,
                     })
                     .await
             );
@@ -9815,6 +10948,7 @@ confidence = "exact"
                     retry: None,
                     ttfb_ms: None,
                     streaming: false,
+                    upstream_request_id: None,
                 })
                 .await
         );
@@ -9900,6 +11034,7 @@ confidence = "exact"
                         retry: None,
                         ttfb_ms: None,
                         streaming: false,
+                        upstream_request_id: None,
                     },
                     SessionRouteAffinitySuccess {
                         request_id: owner_request_id,
@@ -9982,6 +11117,7 @@ confidence = "exact"
                     retry: None,
                     ttfb_ms: None,
                     streaming: false,
+                    upstream_request_id: None,
                 },
                 SessionRouteAffinitySuccess {
                     request_id,
@@ -579,12 +579,14 @@ mod tests {
             session_id: None,
             session_identity_source: None,
             client_name: None,
+            tenant: None,
             client_addr: None,
             cwd: Some(project.to_string()),
             model: Some("gpt-5".to_string()),
             reasoning_effort: None,
             service_tier: None,
             provider_id: Some("input20".to_string()),
+            body_hash: None,
             route_decision: None,
             usage: None,
             cost: serde_json::from_value(serde_json::json!({
@@ -626,6 +628,7 @@ mod tests {
             ttfb_ms: None,
             streaming: false,
             ended_at_ms,
+            upstream_request_id: None,
         }
     }
 
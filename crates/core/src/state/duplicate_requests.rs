@@ -0,0 +1,87 @@
+use std::collections::HashMap;
+
+/// How long a `(session, body hash)` pair stays eligible for a "possible duplicate" warning.
+const DUPLICATE_REQUEST_WINDOW_MS: u64 = 30_000;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct DuplicateRequestKey {
+    service: String,
+    session_id: String,
+    body_hash: String,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct SeenRequest {
+    request_id: u64,
+    at_ms: u64,
+}
+
+/// Recently seen request bodies per session, used only to flag accidental client retry loops.
+#[derive(Debug, Default)]
+pub(super) struct DuplicateRequestWindow {
+    seen: HashMap<DuplicateRequestKey, SeenRequest>,
+}
+
+impl DuplicateRequestWindow {
+    /// Remembers the request and returns the id of an identical one from the same session that
+    /// arrived within the window, if any.
+    pub(super) fn observe(
+        &mut self,
+        service: &str,
+        session_id: &str,
+        body_hash: &str,
+        request_id: u64,
+        now_ms: u64,
+    ) -> Option<u64> {
+        self.seen
+            .retain(|_, seen| seen.at_ms.saturating_add(DUPLICATE_REQUEST_WINDOW_MS) > now_ms);
+        self.seen
+            .insert(
+                DuplicateRequestKey {
+                    service: service.to_string(),
+                    session_id: session_id.to_string(),
+                    body_hash: body_hash.to_string(),
+                },
+                SeenRequest {
+                    request_id,
+                    at_ms: now_ms,
+                },
+            )
+            .map(|previous| previous.request_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn duplicate_window_flags_identical_bodies_from_the_same_session_only() {
+        let mut window = DuplicateRequestWindow::default();
+
+        assert_eq!(window.observe("codex", "s1", "aa", 1, 0), None);
+        assert_eq!(window.observe("codex", "s1", "aa", 2, 5_000), Some(1));
+        // Each repeat is compared against the latest occurrence.
+        assert_eq!(window.observe("codex", "s1", "aa", 3, 20_000), Some(2));
+
+        assert_eq!(window.observe("codex", "s2", "aa", 4, 20_000), None);
+        assert_eq!(window.observe("codex", "s1", "bb", 5, 20_000), None);
+        assert_eq!(window.observe("claude", "s1", "aa", 6, 20_000), None);
+    }
+
+    #[test]
+    fn duplicate_window_forgets_requests_older_than_the_window() {
+        let mut window = DuplicateRequestWindow::default();
+
+        assert_eq!(window.observe("codex", "s1", "aa", 1, 0), None);
+        assert_eq!(
+            window.observe("codex", "s1", "aa", 2, DUPLICATE_REQUEST_WINDOW_MS),
+            None
+        );
+        assert_eq!(
+            window.observe("codex", "s1", "aa", 3, DUPLICATE_REQUEST_WINDOW_MS + 1),
+            Some(2)
+        );
+        assert_eq!(window.seen.len(), 1);
+    }
+}
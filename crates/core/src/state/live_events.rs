@@ -0,0 +1,123 @@
+use serde::Serialize;
+use tokio::sync::broadcast;
+
+use crate::runtime_identity::ProviderEndpointKey;
+use crate::usage::UsageMetrics;
+
+use super::{ActiveRequest, FinishedRequest};
+
+/// Events buffered per subscriber before the oldest ones are dropped.
+///
+/// Publishing never waits on subscribers: a subscriber that falls this far behind skips ahead
+/// and is told how many events it missed.
+pub const LIVE_EVENT_CHANNEL_CAPACITY: usize = 256;
+
+/// One entry of the live event stream served at `GET /__codex_helper/api/v1/events`.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum LiveEvent {
+    RequestStarted {
+        request_id: u64,
+        service: String,
+        method: String,
+        path: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        trace_id: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        session_id: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        model: Option<String>,
+        started_at_ms: u64,
+    },
+    RequestFinished {
+        request_id: u64,
+        service: String,
+        method: String,
+        path: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        trace_id: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        session_id: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        model: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        provider_id: Option<String>,
+        status_code: u16,
+        duration_ms: u64,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        ttfb_ms: Option<u64>,
+        streaming: bool,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        usage: Option<Box<UsageMetrics>>,
+        ended_at_ms: u64,
+    },
+    /// An endpoint's circuit breaker opened or closed.
+    HealthChanged {
+        service: String,
+        provider_id: String,
+        endpoint_id: String,
+        breaker_open: bool,
+        at_ms: u64,
+    },
+}
+
+impl LiveEvent {
+    /// SSE `event:` name; matches the `type` field of the JSON payload.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            LiveEvent::RequestStarted { .. } => "request_started",
+            LiveEvent::RequestFinished { .. } => "request_finished",
+            LiveEvent::HealthChanged { .. } => "health_changed",
+        }
+    }
+
+    pub(super) fn request_started(request: &ActiveRequest) -> Self {
+        LiveEvent::RequestStarted {
+            request_id: request.id,
+            service: request.service.clone(),
+            method: request.method.clone(),
+            path: request.path.clone(),
+            trace_id: request.trace_id.clone(),
+            session_id: request.session_id.clone(),
+            model: request.model.clone(),
+            started_at_ms: request.started_at_ms,
+        }
+    }
+
+    pub(super) fn request_finished(request: &FinishedRequest) -> Self {
+        LiveEvent::RequestFinished {
+            request_id: request.id,
+            service: request.service.clone(),
+            method: request.method.clone(),
+            path: request.path.clone(),
+            trace_id: request.trace_id.clone(),
+            session_id: request.session_id.clone(),
+            model: request.model.clone(),
+            provider_id: request.provider_id.clone(),
+            status_code: request.status_code,
+            duration_ms: request.duration_ms,
+            ttfb_ms: request.ttfb_ms,
+            streaming: request.streaming,
+            usage: request.usage.clone().map(Box::new),
+            ended_at_ms: request.ended_at_ms,
+        }
+    }
+
+    pub(super) fn health_changed(
+        endpoint: &ProviderEndpointKey,
+        breaker_open: bool,
+        at_ms: u64,
+    ) -> Self {
+        LiveEvent::HealthChanged {
+            service: endpoint.service_name.clone(),
+            provider_id: endpoint.provider_id.clone(),
+            endpoint_id: endpoint.endpoint_id.clone(),
+            breaker_open,
+            at_ms,
+        }
+    }
+}
+
+pub(super) fn live_event_channel() -> broadcast::Sender<LiveEvent> {
+    broadcast::channel(LIVE_EVENT_CHANNEL_CAPACITY).0
+}
@@ -0,0 +1,506 @@
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::RequestQuotaConfig;
+
+/// Distinct `x-codex-helper-tenant` values tracked per provider; later tenants share the untagged
+/// budget until an existing tenant's window empties, so arbitrary header values cannot grow the
+/// ledger without bound.
+pub(super) const MAX_REQUEST_QUOTA_TENANTS_PER_PROVIDER: usize = 1024;
+
+/// Which `quota` limit refused a request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RequestQuotaDimension {
+    Requests,
+    OutputTokens,
+}
+
+impl RequestQuotaDimension {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Requests => "requests",
+            Self::OutputTokens => "output_tokens",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestQuotaExceeded {
+    pub provider_id: String,
+    pub tenant: Option<String>,
+    pub dimension: RequestQuotaDimension,
+    pub used: u64,
+    pub limit: u64,
+    pub window_secs: u64,
+    /// Whole seconds until enough of the window expires to admit one more request.
+    pub retry_after_secs: u64,
+}
+
+impl RequestQuotaExceeded {
+    pub fn message(&self) -> String {
+        let scope = self
+            .tenant
+            .as_deref()
+            .map(|tenant| format!(", tenant `{tenant}`"))
+            .unwrap_or_default();
+        format!(
+            "quota exceeded for provider `{}`{scope}: {} {} of {} in the last {}s; retry after {}s",
+            self.provider_id,
+            self.used,
+            self.dimension.as_str(),
+            self.limit,
+            self.window_secs,
+            self.retry_after_secs
+        )
+    }
+}
+
+/// Current consumption of one `quota` budget, as shown by the Stats view.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RequestQuotaUsage {
+    #[serde(default)]
+    pub provider_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tenant: Option<String>,
+    pub window_secs: u64,
+    pub requests: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_requests: Option<u64>,
+    pub output_tokens: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_output_tokens: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct RequestQuotaKey {
+    service: String,
+    provider_id: String,
+    tenant: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct RequestQuotaEvent {
+    at_ms: u64,
+    requests: u64,
+    output_tokens: u64,
+}
+
+#[derive(Debug, Default)]
+struct RequestQuotaWindow {
+    window_ms: u64,
+    events: VecDeque<RequestQuotaEvent>,
+    requests: u64,
+    output_tokens: u64,
+}
+
+impl RequestQuotaWindow {
+    fn prune(&mut self, now_ms: u64) {
+        while let Some(event) = self.events.front().copied() {
+            if event.at_ms.saturating_add(self.window_ms) > now_ms {
+                break;
+            }
+            self.events.pop_front();
+            self.requests = self.requests.saturating_sub(event.requests);
+            self.output_tokens = self.output_tokens.saturating_sub(event.output_tokens);
+        }
+    }
+
+    fn push(&mut self, event: RequestQuotaEvent) {
+        self.requests = self.requests.saturating_add(event.requests);
+        self.output_tokens = self.output_tokens.saturating_add(event.output_tokens);
+        self.events.push_back(event);
+    }
+
+    /// Milliseconds until the oldest events holding `used` at or above `limit` fall out of the window.
+    fn retry_after_ms(
+        &self,
+        now_ms: u64,
+        mut used: u64,
+        limit: u64,
+        amount: fn(&RequestQuotaEvent) -> u64,
+    ) -> u64 {
+        for event in &self.events {
+            used = used.saturating_sub(amount(event));
+            if used < limit {
+                return (event.at_ms + self.window_ms).saturating_sub(now_ms);
+            }
+        }
+        self.window_ms
+    }
+}
+
+/// Sliding-window counters behind the top-level `quota` section, keyed by service, provider and
+/// tenant.
+#[derive(Debug, Default)]
+pub(super) struct RequestQuotaLedger {
+    windows: BTreeMap<RequestQuotaKey, RequestQuotaWindow>,
+    per_tenant_services: BTreeSet<String>,
+}
+
+impl RequestQuotaLedger {
+    /// Counts the request against the budget of the provider it is about to be sent to, or refuses
+    /// it without counting it. Every admission also drops windows that have fully expired.
+    pub(super) fn try_admit(
+        &mut self,
+        service: &str,
+        provider_id: &str,
+        tenant: Option<&str>,
+        config: &RequestQuotaConfig,
+        now_ms: u64,
+    ) -> Result<(), RequestQuotaExceeded> {
+        if !config.is_enabled() {
+            return Ok(());
+        }
+        if config.per_tenant {
+            self.per_tenant_services.insert(service.to_string());
+        } else {
+            self.per_tenant_services.remove(service);
+        }
+        self.prune_expired(now_ms);
+        let window_secs = config.window_secs();
+        let mut key = RequestQuotaKey {
+            service: service.to_string(),
+            provider_id: provider_id.to_string(),
+            tenant: tenant.filter(|_| config.per_tenant).map(str::to_string),
+        };
+        if key.tenant.is_some()
+            && !self.windows.contains_key(&key)
+            && self.tracked_tenants(service, provider_id) >= MAX_REQUEST_QUOTA_TENANTS_PER_PROVIDER
+        {
+            key.tenant = None;
+        }
+        let tenant = key.tenant.clone();
+        let window = self.windows.entry(key).or_default();
+        window.window_ms = window_secs.saturating_mul(1000);
+        window.prune(now_ms);
+
+        let exceeded = [
+            (
+                RequestQuotaDimension::Requests,
+                config.max_requests,
+                window.requests,
+                (|event| event.requests) as fn(&RequestQuotaEvent) -> u64,
+            ),
+            (
+                RequestQuotaDimension::OutputTokens,
+                config.max_output_tokens,
+                window.output_tokens,
+                |event| event.output_tokens,
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(dimension, limit, used, amount)| {
+            let limit = limit.filter(|limit| used >= *limit)?;
+            Some((dimension, limit, used, amount))
+        })
+        .map(|(dimension, limit, used, amount)| RequestQuotaExceeded {
+            provider_id: provider_id.to_string(),
+            tenant: tenant.clone(),
+            dimension,
+            used,
+            limit,
+            window_secs,
+            retry_after_secs: window
+                .retry_after_ms(now_ms, used, limit, amount)
+                .div_ceil(1000)
+                .max(1),
+        })
+        .max_by_key(|exceeded| exceeded.retry_after_secs);
+        if let Some(exceeded) = exceeded {
+            return Err(exceeded);
+        }
+
+        window.push(RequestQuotaEvent {
+            at_ms: now_ms,
+            requests: 1,
+            output_tokens: 0,
+        });
+        Ok(())
+    }
+
+    /// Charges output tokens to the budget the request was admitted under, if it is still live.
+    pub(super) fn record_output_tokens(
+        &mut self,
+        service: &str,
+        provider_id: &str,
+        tenant: Option<&str>,
+        output_tokens: u64,
+        now_ms: u64,
+    ) {
+        if output_tokens == 0 {
+            return;
+        }
+        let mut key = RequestQuotaKey {
+            service: service.to_string(),
+            provider_id: provider_id.to_string(),
+            tenant: tenant
+                .filter(|_| self.per_tenant_services.contains(service))
+                .map(str::to_string),
+        };
+        if key.tenant.is_some() && !self.windows.contains_key(&key) {
+            // Admitted under the shared budget because the tenant cap was reached.
+            key.tenant = None;
+        }
+        if let Some(window) = self.windows.get_mut(&key) {
+            window.push(RequestQuotaEvent {
+                at_ms: now_ms,
+                requests: 0,
+                output_tokens,
+            });
+        }
+    }
+
+    pub(super) fn usage(
+        &mut self,
+        service: &str,
+        config: &RequestQuotaConfig,
+        now_ms: u64,
+    ) -> Vec<RequestQuotaUsage> {
+        if !config.is_enabled() {
+            self.windows.retain(|key, _| key.service != service);
+            return Vec::new();
+        }
+        self.windows.retain(|key, window| {
+            if key.service != service {
+                return true;
+            }
+            window.prune(now_ms);
+            !window.events.is_empty()
+        });
+        self.windows
+            .iter()
+            .filter(|(key, _)| key.service == service)
+            .map(|(key, window)| RequestQuotaUsage {
+                provider_id: key.provider_id.clone(),
+                tenant: key.tenant.clone(),
+                window_secs: config.window_secs(),
+                requests: window.requests,
+                max_requests: config.max_requests,
+                output_tokens: window.output_tokens,
+                max_output_tokens: config.max_output_tokens,
+            })
+            .collect()
+    }
+
+    fn prune_expired(&mut self, now_ms: u64) {
+        self.windows.retain(|_, window| {
+            window.prune(now_ms);
+            !window.events.is_empty()
+        });
+    }
+
+    fn tracked_tenants(&self, service: &str, provider_id: &str) -> usize {
+        self.windows
+            .keys()
+            .filter(|key| {
+                key.service == service && key.provider_id == provider_id && key.tenant.is_some()
+            })
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quota(max_requests: Option<u64>, max_output_tokens: Option<u64>) -> RequestQuotaConfig {
+        RequestQuotaConfig {
+            window_secs: Some(60),
+            max_requests,
+            max_output_tokens,
+            per_tenant: false,
+        }
+    }
+
+    #[test]
+    fn request_quota_window_rolls_over_oldest_requests_first() {
+        let config = quota(Some(2), None);
+        let mut ledger = RequestQuotaLedger::default();
+
+        ledger
+            .try_admit("codex", "relay", None, &config, 1_000)
+            .unwrap();
+        ledger
+            .try_admit("codex", "relay", None, &config, 21_000)
+            .unwrap();
+        let exceeded = ledger
+            .try_admit("codex", "relay", None, &config, 30_000)
+            .unwrap_err();
+        assert_eq!(exceeded.dimension, RequestQuotaDimension::Requests);
+        assert_eq!((exceeded.used, exceeded.limit), (2, 2));
+        assert_eq!(exceeded.retry_after_secs, 31);
+
+        // The first request leaves the 60s window at 61s; the second still counts.
+        ledger
+            .try_admit("codex", "relay", None, &config, 61_000)
+            .unwrap();
+        assert!(
+            ledger
+                .try_admit("codex", "relay", None, &config, 62_000)
+                .is_err()
+        );
+        assert_eq!(ledger.usage("codex", &config, 62_000)[0].requests, 2);
+        assert_eq!(ledger.usage("codex", &config, 200_000), Vec::new());
+    }
+
+    #[test]
+    fn request_quota_counts_output_tokens_and_waits_until_enough_expire() {
+        let config = quota(None, Some(1_000));
+        let mut ledger = RequestQuotaLedger::default();
+
+        ledger
+            .try_admit("codex", "relay", None, &config, 0)
+            .unwrap();
+        ledger.record_output_tokens("codex", "relay", None, 600, 5_000);
+        ledger
+            .try_admit("codex", "relay", None, &config, 10_000)
+            .unwrap();
+        ledger.record_output_tokens("codex", "relay", None, 600, 15_000);
+        ledger.record_output_tokens("claude", "relay", None, 5_000, 15_000);
+
+        let exceeded = ledger
+            .try_admit("codex", "relay", None, &config, 20_000)
+            .unwrap_err();
+        assert_eq!(exceeded.dimension, RequestQuotaDimension::OutputTokens);
+        assert_eq!(exceeded.used, 1_200);
+        // Dropping the first 600 tokens (recorded at 5s) brings the window back under 1000.
+        assert_eq!(exceeded.retry_after_secs, 45);
+        ledger
+            .try_admit("codex", "relay", None, &config, 65_000)
+            .unwrap();
+    }
+
+    #[test]
+    fn request_quota_per_tenant_budgets_are_independent() {
+        let config = RequestQuotaConfig {
+            per_tenant: true,
+            ..quota(Some(1), None)
+        };
+        let mut ledger = RequestQuotaLedger::default();
+
+        ledger
+            .try_admit("codex", "relay", Some("alice"), &config, 0)
+            .unwrap();
+        ledger
+            .try_admit("codex", "relay", Some("bob"), &config, 0)
+            .unwrap();
+        let exceeded = ledger
+            .try_admit("codex", "relay", Some("alice"), &config, 1_000)
+            .unwrap_err();
+        assert_eq!(exceeded.tenant.as_deref(), Some("alice"));
+
+        let usage = ledger.usage("codex", &config, 1_000);
+        assert_eq!(
+            usage
+                .iter()
+                .map(|usage| usage.tenant.as_deref())
+                .collect::<Vec<_>>(),
+            vec![Some("alice"), Some("bob")]
+        );
+    }
+
+    #[test]
+    fn request_quota_budgets_are_per_provider() {
+        let config = quota(Some(1), None);
+        let mut ledger = RequestQuotaLedger::default();
+
+        ledger
+            .try_admit("codex", "relay", None, &config, 0)
+            .unwrap();
+        ledger
+            .try_admit("codex", "backup", None, &config, 0)
+            .unwrap();
+        let exceeded = ledger
+            .try_admit("codex", "relay", None, &config, 1_000)
+            .unwrap_err();
+        assert_eq!(exceeded.provider_id, "relay");
+        assert!(exceeded.message().contains("provider `relay`"));
+
+        ledger.record_output_tokens("codex", "backup", None, 40, 2_000);
+        let usage = ledger.usage("codex", &config, 2_000);
+        assert_eq!(
+            usage
+                .iter()
+                .map(|usage| (usage.provider_id.as_str(), usage.output_tokens))
+                .collect::<Vec<_>>(),
+            vec![("backup", 40), ("relay", 0)]
+        );
+    }
+
+    #[test]
+    fn request_quota_admission_prunes_expired_windows() {
+        let config = RequestQuotaConfig {
+            per_tenant: true,
+            ..quota(Some(10), None)
+        };
+        let mut ledger = RequestQuotaLedger::default();
+
+        for tenant in ["alice", "bob", "carol"] {
+            ledger
+                .try_admit("codex", "relay", Some(tenant), &config, 0)
+                .unwrap();
+        }
+        ledger
+            .try_admit("claude", "relay", None, &config, 0)
+            .unwrap();
+        assert_eq!(ledger.windows.len(), 4);
+
+        ledger
+            .try_admit("codex", "relay", Some("dave"), &config, 60_000)
+            .unwrap();
+        assert_eq!(
+            ledger.windows.keys().collect::<Vec<_>>(),
+            vec![&RequestQuotaKey {
+                service: "codex".to_string(),
+                provider_id: "relay".to_string(),
+                tenant: Some("dave".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn request_quota_tenants_past_the_cap_share_the_untagged_budget() {
+        let config = RequestQuotaConfig {
+            per_tenant: true,
+            ..quota(Some(1), None)
+        };
+        let mut ledger = RequestQuotaLedger::default();
+
+        for index in 0..MAX_REQUEST_QUOTA_TENANTS_PER_PROVIDER {
+            ledger
+                .try_admit(
+                    "codex",
+                    "relay",
+                    Some(&format!("tenant-{index}")),
+                    &config,
+                    0,
+                )
+                .unwrap();
+        }
+        ledger
+            .try_admit("codex", "relay", Some("overflow-a"), &config, 0)
+            .unwrap();
+        let exceeded = ledger
+            .try_admit("codex", "relay", Some("overflow-b"), &config, 0)
+            .unwrap_err();
+        assert_eq!(exceeded.tenant, None);
+        assert_eq!(
+            ledger.windows.len(),
+            MAX_REQUEST_QUOTA_TENANTS_PER_PROVIDER + 1
+        );
+
+        // The cap is per provider.
+        ledger
+            .try_admit("codex", "backup", Some("overflow-a"), &config, 0)
+            .unwrap();
+        assert_eq!(
+            ledger
+                .usage("codex", &config, 0)
+                .first()
+                .map(|usage| (usage.provider_id.as_str(), usage.tenant.as_deref())),
+            Some(("backup", Some("overflow-a")))
+        );
+    }
+}
@@ -1,5 +1,6 @@
 use std::collections::BTreeMap;
 
+use crate::routing_ir::{UpstreamPin, UpstreamPinScope};
 use crate::runtime_identity::ProviderEndpointKey;
 
 #[derive(Debug, thiserror::Error, PartialEq, Eq)]
@@ -56,6 +57,24 @@ pub struct NewSessionPreference {
 struct RoutingOperatorServiceControl {
     route_graph_key: String,
     new_session_preference: Option<ProviderEndpointKey>,
+    upstream_pin: Option<UpstreamPin>,
+    /// Per-session pins keyed by raw session id; they win over `upstream_pin`.
+    session_upstream_pins: BTreeMap<String, UpstreamPin>,
+    /// When each session pin was set, so a pin placed before the session's first request
+    /// survives until the session-stats TTL has passed.
+    session_upstream_pinned_at_ms: BTreeMap<String, u64>,
+}
+
+impl RoutingOperatorServiceControl {
+    fn new(route_graph_key: &str) -> Self {
+        Self {
+            route_graph_key: route_graph_key.to_string(),
+            new_session_preference: None,
+            upstream_pin: None,
+            session_upstream_pins: BTreeMap::new(),
+            session_upstream_pinned_at_ms: BTreeMap::new(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
@@ -87,6 +106,49 @@ impl RoutingOperatorControlSnapshot {
         })
     }
 
+    /// The pin for `session_id`, or the service-wide pin when the session has none.
+    pub fn upstream_pin(
+        &self,
+        service_name: &str,
+        route_graph_key: &str,
+        session_id: Option<&str>,
+    ) -> Option<&UpstreamPin> {
+        let control = self
+            .services
+            .get(service_name)
+            .filter(|control| control.route_graph_key == route_graph_key)?;
+        session_id
+            .and_then(|session_id| control.session_upstream_pins.get(session_id))
+            .or(control.upstream_pin.as_ref())
+    }
+
+    pub fn global_upstream_pin(
+        &self,
+        service_name: &str,
+        route_graph_key: &str,
+    ) -> Option<&UpstreamPin> {
+        self.upstream_pin(service_name, route_graph_key, None)
+    }
+
+    /// Session pins as `(raw session id, pin)` pairs.
+    pub fn session_upstream_pins(
+        &self,
+        service_name: &str,
+        route_graph_key: &str,
+    ) -> Vec<(&str, &UpstreamPin)> {
+        self.services
+            .get(service_name)
+            .filter(|control| control.route_graph_key == route_graph_key)
+            .map(|control| {
+                control
+                    .session_upstream_pins
+                    .iter()
+                    .map(|(session_id, pin)| (session_id.as_str(), pin))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
     pub fn configured_new_session_preference(
         &self,
         service_name: &str,
@@ -113,10 +175,7 @@ impl RoutingOperatorControlSnapshot {
 
         self.services.insert(
             service_name.to_string(),
-            RoutingOperatorServiceControl {
-                route_graph_key: route_graph_key.to_string(),
-                new_session_preference: None,
-            },
+            RoutingOperatorServiceControl::new(route_graph_key),
         );
         self.revision = self.revision.wrapping_add(1);
         RoutingOperatorControlUpdate::Applied
@@ -131,10 +190,7 @@ impl RoutingOperatorControlSnapshot {
         let control = self
             .services
             .entry(service_name.to_string())
-            .or_insert_with(|| RoutingOperatorServiceControl {
-                route_graph_key: route_graph_key.to_string(),
-                new_session_preference: None,
-            });
+            .or_insert_with(|| RoutingOperatorServiceControl::new(route_graph_key));
         if control.route_graph_key != route_graph_key {
             return RoutingOperatorControlUpdate::Conflict;
         }
@@ -146,6 +202,96 @@ impl RoutingOperatorControlSnapshot {
         self.revision = self.revision.wrapping_add(1);
         RoutingOperatorControlUpdate::Applied
     }
+
+    /// Sets or clears the pin for one session, or the service-wide pin when `session_id` is
+    /// `None`. The stored scope follows `session_id`.
+    pub(super) fn apply_upstream_pin(
+        &mut self,
+        service_name: &str,
+        route_graph_key: &str,
+        session_id: Option<&str>,
+        pin: Option<UpstreamPin>,
+    ) -> RoutingOperatorControlUpdate {
+        let control = self
+            .services
+            .entry(service_name.to_string())
+            .or_insert_with(|| RoutingOperatorServiceControl::new(route_graph_key));
+        if control.route_graph_key != route_graph_key {
+            return RoutingOperatorControlUpdate::Conflict;
+        }
+        let pin = pin.map(|pin| UpstreamPin {
+            scope: if session_id.is_some() {
+                UpstreamPinScope::Session
+            } else {
+                UpstreamPinScope::Global
+            },
+            ..pin
+        });
+        let current = match session_id {
+            Some(session_id) => control.session_upstream_pins.get(session_id),
+            None => control.upstream_pin.as_ref(),
+        };
+        if current == pin.as_ref() {
+            return RoutingOperatorControlUpdate::Unchanged;
+        }
+
+        match (session_id, pin) {
+            (Some(session_id), Some(pin)) => {
+                control
+                    .session_upstream_pins
+                    .insert(session_id.to_string(), pin);
+                control
+                    .session_upstream_pinned_at_ms
+                    .insert(session_id.to_string(), crate::logging::now_ms());
+            }
+            (Some(session_id), None) => {
+                control.session_upstream_pins.remove(session_id);
+                control.session_upstream_pinned_at_ms.remove(session_id);
+            }
+            (None, pin) => control.upstream_pin = pin,
+        }
+        self.revision = self.revision.wrapping_add(1);
+        RoutingOperatorControlUpdate::Applied
+    }
+
+    /// Drops session pins whose session has expired: `is_live(service, session)` is false and
+    /// the pin was set before `cutoff_ms`. Returns how many pins were removed.
+    pub(super) fn prune_session_upstream_pins(
+        &mut self,
+        cutoff_ms: u64,
+        is_live: impl Fn(&str, &str) -> bool,
+    ) -> usize {
+        let mut removed = 0;
+        for (service_name, control) in &mut self.services {
+            let pinned_at_ms = &mut control.session_upstream_pinned_at_ms;
+            control.session_upstream_pins.retain(|session_id, _| {
+                let keep = is_live(service_name, session_id)
+                    || pinned_at_ms
+                        .get(session_id)
+                        .is_some_and(|pinned_at_ms| *pinned_at_ms >= cutoff_ms);
+                if !keep {
+                    pinned_at_ms.remove(session_id);
+                    removed += 1;
+                }
+                keep
+            });
+        }
+        if removed > 0 {
+            self.revision = self.revision.wrapping_add(1);
+        }
+        removed
+    }
+}
+
+/// The candidate `routing.auto_active_by_health` currently points new sessions at.
+///
+/// Kept outside [`RoutingOperatorControlSnapshot`] so automatic moves never bump the
+/// revision operators compare-and-set against.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AutoActiveSelection {
+    pub route_graph_key: String,
+    pub target: ProviderEndpointKey,
+    pub selected_at_ms: u64,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -202,6 +348,105 @@ mod tests {
         );
     }
 
+    #[test]
+    fn expired_session_pins_are_pruned_but_live_or_fresh_ones_stay() {
+        let mut snapshot = RoutingOperatorControlSnapshot::default();
+        let pin = UpstreamPin {
+            target: ProviderEndpointKey::new("codex", "input", "default"),
+            failover: false,
+            scope: UpstreamPinScope::Session,
+        };
+        for session_id in ["sid-live", "sid-gone"] {
+            snapshot.apply_upstream_pin(
+                "codex",
+                "route:v1:pins",
+                Some(session_id),
+                Some(pin.clone()),
+            );
+        }
+        let revision = snapshot.revision();
+
+        assert_eq!(snapshot.prune_session_upstream_pins(0, |_, _| false), 0);
+        assert_eq!(snapshot.revision(), revision);
+
+        let cutoff_ms = crate::logging::now_ms() + 1;
+        assert_eq!(
+            snapshot.prune_session_upstream_pins(cutoff_ms, |service_name, session_id| {
+                service_name == "codex" && session_id == "sid-live"
+            }),
+            1
+        );
+        assert_eq!(snapshot.revision(), revision + 1);
+        let remaining = snapshot.session_upstream_pins("codex", "route:v1:pins");
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].0, "sid-live");
+    }
+
+    #[test]
+    fn session_upstream_pin_wins_over_global_pin() {
+        let mut snapshot = RoutingOperatorControlSnapshot::default();
+        let pin = |provider_id: &str, failover: bool| UpstreamPin {
+            target: ProviderEndpointKey::new("codex", provider_id, "default"),
+            failover,
+            scope: UpstreamPinScope::Global,
+        };
+
+        assert_eq!(
+            snapshot.apply_upstream_pin("codex", "route:v1:pins", None, Some(pin("input", true))),
+            RoutingOperatorControlUpdate::Applied
+        );
+        assert_eq!(
+            snapshot.apply_upstream_pin(
+                "codex",
+                "route:v1:pins",
+                Some("session-a"),
+                Some(pin("ciii", false))
+            ),
+            RoutingOperatorControlUpdate::Applied
+        );
+        assert_eq!(snapshot.revision(), 2);
+
+        let session_pin = snapshot
+            .upstream_pin("codex", "route:v1:pins", Some("session-a"))
+            .expect("session pin");
+        assert_eq!(session_pin.target.provider_id, "ciii");
+        assert_eq!(session_pin.scope, UpstreamPinScope::Session);
+        let other = snapshot
+            .upstream_pin("codex", "route:v1:pins", Some("session-b"))
+            .expect("global pin");
+        assert_eq!(other.target.provider_id, "input");
+        assert_eq!(other.scope, UpstreamPinScope::Global);
+        assert!(
+            snapshot
+                .upstream_pin("codex", "route:v1:other", Some("session-a"))
+                .is_none()
+        );
+
+        assert_eq!(
+            snapshot.apply_upstream_pin("codex", "route:v1:pins", Some("session-a"), None),
+            RoutingOperatorControlUpdate::Applied
+        );
+        assert_eq!(
+            snapshot.apply_upstream_pin("codex", "route:v1:pins", Some("session-a"), None),
+            RoutingOperatorControlUpdate::Unchanged
+        );
+        assert!(
+            snapshot
+                .session_upstream_pins("codex", "route:v1:pins")
+                .is_empty()
+        );
+
+        assert_eq!(
+            snapshot.reconcile_route_graph("codex", "route:v1:replacement"),
+            RoutingOperatorControlUpdate::Applied
+        );
+        assert!(
+            snapshot
+                .global_upstream_pin("codex", "route:v1:replacement")
+                .is_none()
+        );
+    }
+
     #[test]
     fn route_graph_reconciliation_clears_old_preference_permanently() {
         let mut snapshot = RoutingOperatorControlSnapshot::default();
@@ -250,6 +250,7 @@ mod tests {
                 None,
                 None,
                 None,
+                None,
                 Some("gpt-5".to_string()),
                 Some("gpt-5".to_string()),
                 None,
@@ -41,6 +41,9 @@ fn generation_ms_from_duration(duration_ms: u64, ttfb_ms: Option<u64>) -> Option
     }
 }
 
+/// TTFB measured from the request start, for streaming and buffered responses alike. Older
+/// records measured it from the final attempt's start (first chunk when streaming, response
+/// headers when buffered); those get the time spent before that attempt added back.
 fn effective_ttfb_ms_for_request(
     duration_ms: u64,
     ttfb_ms: Option<u64>,
@@ -182,6 +185,9 @@ pub struct ActiveRequest {
     pub session_identity_source: Option<SessionIdentitySource>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub client_name: Option<String>,
+    /// Value of the `x-codex-helper-tenant` request header, used for per-person accounting.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tenant: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub client_addr: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -200,6 +206,9 @@ pub struct ActiveRequest {
     pub provider_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub route_decision: Option<RouteDecisionProvenance>,
+    /// Hex SHA-256 of the client request body, used to spot accidental retry loops.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body_hash: Option<String>,
     pub service: String,
     pub method: String,
     pub path: String,
@@ -338,6 +347,9 @@ pub struct FinishedRequest {
     pub session_identity_source: Option<SessionIdentitySource>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub client_name: Option<String>,
+    /// Value of the `x-codex-helper-tenant` request header, used for per-person accounting.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tenant: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub client_addr: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -352,6 +364,12 @@ pub struct FinishedRequest {
     pub provider_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub route_decision: Option<RouteDecisionProvenance>,
+    /// Hex SHA-256 of the client request body; the body itself is never kept.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body_hash: Option<String>,
+    /// The provider's id for the final upstream response, e.g. its `x-request-id`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upstream_request_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub usage: Option<UsageMetrics>,
     #[serde(default, skip_serializing_if = "CostBreakdown::is_unknown")]
@@ -517,6 +535,7 @@ pub struct FinishRequestParams {
     pub retry: Option<crate::logging::RetryInfo>,
     pub ttfb_ms: Option<u64>,
     pub streaming: bool,
+    pub upstream_request_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
@@ -1224,12 +1243,14 @@ mod tests {
             session_id: Some("sid".to_string()),
             session_identity_source: Some(SessionIdentitySource::Header),
             client_name: None,
+            tenant: None,
             client_addr: None,
             cwd: None,
             model: Some("gpt-5".to_string()),
             reasoning_effort: None,
             service_tier: Some("default".to_string()),
             provider_id: Some("primary-provider".to_string()),
+            body_hash: None,
             route_decision: Some(RouteDecisionProvenance {
                 effective_service_tier: Some(ResolvedRouteValue {
                     value: "priority".to_string(),
@@ -1276,6 +1297,7 @@ mod tests {
             ttfb_ms: Some(500),
             streaming: true,
             ended_at_ms: 2_000,
+            upstream_request_id: None,
         }
     }
 
@@ -1370,6 +1392,7 @@ mod tests {
     #[test]
     fn finished_request_corrects_legacy_attempt_relative_stream_ttfb() {
         let mut request = sample_finished_request();
+        request.streaming = true;
         request.duration_ms = 10_000;
         request.ttfb_ms = Some(1_210);
         request.usage = Some(UsageMetrics {
@@ -1410,6 +1433,7 @@ mod tests {
     #[test]
     fn finished_request_does_not_double_correct_global_stream_ttfb() {
         let mut request = sample_finished_request();
+        request.streaming = true;
         request.duration_ms = 10_000;
         request.ttfb_ms = Some(2_210);
         request.retry = Some(RetryInfo {
@@ -1429,4 +1453,30 @@ mod tests {
         assert_eq!(observability.ttfb_ms, Some(2_210));
         assert_eq!(observability.generation_ms, Some(7_790));
     }
+
+    #[test]
+    fn finished_request_corrects_legacy_attempt_relative_buffered_ttfb() {
+        let mut request = sample_finished_request();
+        request.streaming = false;
+        request.duration_ms = 10_000;
+        // Older buffered records stored the final attempt's own response-headers time.
+        request.ttfb_ms = Some(1_200);
+        request.retry = Some(RetryInfo {
+            attempts: 2,
+            route_attempts: vec![RouteAttemptLog {
+                attempt_index: 1,
+                decision: "completed".to_string(),
+                status_code: Some(200),
+                upstream_headers_ms: Some(1_200),
+                duration_ms: Some(2_200),
+                ..RouteAttemptLog::default()
+            }],
+        });
+
+        assert_eq!(request.observability_view().ttfb_ms, Some(2_200));
+
+        // Current records already measure from the request start and are left alone.
+        request.ttfb_ms = Some(2_200);
+        assert_eq!(request.observability_view().ttfb_ms, Some(2_200));
+    }
 }
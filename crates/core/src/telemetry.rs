@@ -0,0 +1,470 @@
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use serde_json::{Value, json};
+use sha2::{Digest, Sha256};
+use tokio::sync::mpsc;
+
+use crate::config::TelemetryConfig;
+use crate::logging::{RetryInfo, RouteAttemptLog, ServiceTierLog};
+use crate::usage::UsageMetrics;
+
+pub const OTLP_ENDPOINT_ENV: &str = "CODEX_HELPER_OTLP_ENDPOINT";
+
+const OTLP_TRACES_PATH: &str = "/v1/traces";
+const EXPORT_QUEUE_CAPACITY: usize = 2048;
+const EXPORT_BATCH_MAX_SPANS: usize = 256;
+const EXPORT_TIMEOUT: Duration = Duration::from_secs(5);
+
+// OTLP enum values (opentelemetry-proto trace.proto).
+const SPAN_KIND_SERVER: u8 = 2;
+const SPAN_KIND_CLIENT: u8 = 3;
+const STATUS_CODE_ERROR: u8 = 2;
+
+/// One finished proxy request, as handed to the exporter.
+#[derive(Debug, Clone, Default)]
+pub struct RequestSpan {
+    pub service: String,
+    pub request_id: u64,
+    pub method: String,
+    pub path: String,
+    pub status_code: u16,
+    pub duration_ms: u64,
+    pub ended_at_ms: u64,
+    pub ttfb_ms: Option<u64>,
+    pub profile: Option<String>,
+    pub provider_id: Option<String>,
+    pub endpoint_id: Option<String>,
+    pub provider_endpoint_key: Option<String>,
+    pub model: Option<String>,
+    pub reasoning_effort: Option<String>,
+    pub service_tier: ServiceTierLog,
+    pub usage: Option<UsageMetrics>,
+    pub retry: Option<RetryInfo>,
+}
+
+static OTLP_ENDPOINT_FROM_ENV: OnceLock<Option<String>> = OnceLock::new();
+static EXPORT_QUEUE: OnceLock<mpsc::Sender<(String, Value)>> = OnceLock::new();
+
+/// `CODEX_HELPER_OTLP_ENDPOINT`, read and validated once per process.
+fn otlp_endpoint_from_env() -> Option<&'static str> {
+    OTLP_ENDPOINT_FROM_ENV
+        .get_or_init(|| {
+            let endpoint = std::env::var(OTLP_ENDPOINT_ENV)
+                .ok()
+                .filter(|value| !value.trim().is_empty())?;
+            match validate_otlp_endpoint(&endpoint) {
+                Ok(()) => Some(endpoint),
+                Err(error) => {
+                    tracing::warn!("ignoring {OTLP_ENDPOINT_ENV}: {error}");
+                    None
+                }
+            }
+        })
+        .as_deref()
+}
+
+/// The OTLP traces URL spans are exported to, or `None` while export is off.
+///
+/// A non-empty `CODEX_HELPER_OTLP_ENDPOINT` wins over `telemetry.otlp_endpoint`.
+pub fn otlp_traces_endpoint(config: &TelemetryConfig) -> Option<String> {
+    otlp_endpoint_from_env()
+        .or(config.otlp_endpoint.as_deref())
+        .map(otlp_traces_url)
+}
+
+pub fn validate_otlp_endpoint(endpoint: &str) -> anyhow::Result<()> {
+    let url = reqwest::Url::parse(endpoint.trim())
+        .map_err(|error| anyhow::anyhow!("invalid URL `{endpoint}`: {error}"))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        anyhow::bail!("`{endpoint}` must be an http or https URL");
+    }
+    Ok(())
+}
+
+/// Base URLs get `/v1/traces` appended; a URL that already names the traces path is kept.
+fn otlp_traces_url(endpoint: &str) -> String {
+    let endpoint = endpoint.trim().trim_end_matches('/');
+    if endpoint.ends_with(OTLP_TRACES_PATH) {
+        endpoint.to_string()
+    } else {
+        format!("{endpoint}{OTLP_TRACES_PATH}")
+    }
+}
+
+/// Queues the request span (and one child span per route attempt) for export to `traces_url`.
+///
+/// Never blocks the request path: when the queue is full the spans are dropped, and export
+/// failures only reach the debug log.
+pub fn record_request_span(traces_url: &str, span: &RequestSpan) {
+    let Some(queue) = export_queue() else {
+        return;
+    };
+    for span in request_span_json(span) {
+        if queue.try_send((traces_url.to_string(), span)).is_err() {
+            tracing::debug!("OTLP export queue is full; dropping span");
+            return;
+        }
+    }
+}
+
+fn export_queue() -> Option<&'static mpsc::Sender<(String, Value)>> {
+    if let Some(queue) = EXPORT_QUEUE.get() {
+        return Some(queue);
+    }
+    let runtime = tokio::runtime::Handle::try_current().ok()?;
+    Some(EXPORT_QUEUE.get_or_init(|| {
+        let (tx, rx) = mpsc::channel(EXPORT_QUEUE_CAPACITY);
+        runtime.spawn(run_exporter(rx));
+        tx
+    }))
+}
+
+async fn run_exporter(mut rx: mpsc::Receiver<(String, Value)>) {
+    let client = match reqwest::Client::builder().timeout(EXPORT_TIMEOUT).build() {
+        Ok(client) => client,
+        Err(error) => {
+            tracing::debug!("OTLP exporter disabled: failed to build HTTP client: {error}");
+            return;
+        }
+    };
+    while let Some(first) = rx.recv().await {
+        let mut queued = vec![first];
+        while queued.len() < EXPORT_BATCH_MAX_SPANS {
+            match rx.try_recv() {
+                Ok(span) => queued.push(span),
+                Err(_) => break,
+            }
+        }
+        // A config reload can move the endpoint mid-batch; each span goes where it was sent.
+        let mut batches = BTreeMap::<String, Vec<Value>>::new();
+        for (url, span) in queued {
+            batches.entry(url).or_default().push(span);
+        }
+        for (url, batch) in batches {
+            let body = export_request_json(batch);
+            match client.post(&url).json(&body).send().await {
+                Ok(response) if response.status().is_success() => {}
+                Ok(response) => {
+                    tracing::debug!(url, status = %response.status(), "OTLP span export rejected");
+                }
+                Err(error) => tracing::debug!(url, "OTLP span export failed: {error}"),
+            }
+        }
+    }
+}
+
+fn export_request_json(spans: Vec<Value>) -> Value {
+    json!({
+        "resourceSpans": [{
+            "resource": {
+                "attributes": [
+                    string_attribute("service.name", "codex-helper"),
+                    string_attribute("service.version", env!("CARGO_PKG_VERSION")),
+                ],
+            },
+            "scopeSpans": [{
+                "scope": { "name": "codex-helper", "version": env!("CARGO_PKG_VERSION") },
+                "spans": spans,
+            }],
+        }],
+    })
+}
+
+/// The request span first, then its attempt spans.
+///
+/// Attempts are laid out back to back from the request start using their own durations;
+/// backoff waits between attempts are not represented.
+fn request_span_json(span: &RequestSpan) -> Vec<Value> {
+    let log_trace_id = crate::logging::request_trace_id(&span.service, span.request_id);
+    let trace_id = hex_digest(&log_trace_id, 16);
+    let root_span_id = hex_digest(&format!("{log_trace_id}:request"), 8);
+    let end_ms = span.ended_at_ms;
+    let start_ms = end_ms.saturating_sub(span.duration_ms);
+
+    let mut attributes = vec![
+        string_attribute("http.request.method", &span.method),
+        string_attribute("url.path", &span.path),
+        int_attribute("http.response.status_code", u64::from(span.status_code)),
+        string_attribute("codex_helper.service", &span.service),
+        int_attribute("codex_helper.request_id", span.request_id),
+        string_attribute("codex_helper.trace_id", &log_trace_id),
+    ];
+    push_optional_string(&mut attributes, "codex_helper.profile", &span.profile);
+    push_optional_string(
+        &mut attributes,
+        "codex_helper.provider_id",
+        &span.provider_id,
+    );
+    push_optional_string(
+        &mut attributes,
+        "codex_helper.endpoint_id",
+        &span.endpoint_id,
+    );
+    push_optional_string(
+        &mut attributes,
+        "codex_helper.provider_endpoint_key",
+        &span.provider_endpoint_key,
+    );
+    push_optional_string(&mut attributes, "codex_helper.model", &span.model);
+    push_optional_string(
+        &mut attributes,
+        "codex_helper.reasoning_effort",
+        &span.reasoning_effort,
+    );
+    let service_tier = span
+        .service_tier
+        .actual
+        .as_ref()
+        .or(span.service_tier.effective.as_ref())
+        .cloned();
+    push_optional_string(&mut attributes, "codex_helper.service_tier", &service_tier);
+    if let Some(retry) = span.retry.as_ref() {
+        attributes.push(int_attribute(
+            "codex_helper.attempts",
+            u64::from(retry.attempts),
+        ));
+    }
+    if let Some(ttfb_ms) = span.ttfb_ms {
+        attributes.push(int_attribute("codex_helper.ttfb_ms", ttfb_ms));
+    }
+    if let Some(usage) = span.usage.as_ref() {
+        attributes.push(int_attribute(
+            "codex_helper.input_tokens",
+            usage.input_tokens.max(0) as u64,
+        ));
+        attributes.push(int_attribute(
+            "codex_helper.output_tokens",
+            usage.output_tokens.max(0) as u64,
+        ));
+    }
+
+    let mut root = json!({
+        "traceId": trace_id,
+        "spanId": root_span_id,
+        "name": format!("{} {}", span.method, span.path),
+        "kind": SPAN_KIND_SERVER,
+        "startTimeUnixNano": unix_nanos(start_ms),
+        "endTimeUnixNano": unix_nanos(end_ms),
+        "attributes": attributes,
+    });
+    if span.status_code >= 500 {
+        root["status"] = json!({ "code": STATUS_CODE_ERROR });
+    }
+
+    let mut spans = vec![root];
+    let mut attempt_start_ms = start_ms;
+    let attempts = span
+        .retry
+        .as_ref()
+        .map(|retry| retry.route_attempts.as_slice())
+        .unwrap_or_default();
+    for attempt in attempts.iter().filter(|attempt| !attempt.skipped) {
+        let attempt_end_ms = attempt_start_ms
+            .saturating_add(attempt.duration_ms.unwrap_or_default())
+            .min(end_ms);
+        spans.push(attempt_span_json(
+            attempt,
+            &trace_id,
+            &root_span_id,
+            &log_trace_id,
+            attempt_start_ms,
+            attempt_end_ms,
+        ));
+        attempt_start_ms = attempt_end_ms;
+    }
+    spans
+}
+
+fn attempt_span_json(
+    attempt: &RouteAttemptLog,
+    trace_id: &str,
+    parent_span_id: &str,
+    log_trace_id: &str,
+    start_ms: u64,
+    end_ms: u64,
+) -> Value {
+    let mut attributes = vec![
+        int_attribute(
+            "codex_helper.attempt_index",
+            u64::from(attempt.attempt_index),
+        ),
+        string_attribute("codex_helper.decision", &attempt.decision),
+    ];
+    push_optional_string(
+        &mut attributes,
+        "codex_helper.provider_id",
+        &attempt.provider_id,
+    );
+    push_optional_string(
+        &mut attributes,
+        "codex_helper.endpoint_id",
+        &attempt.endpoint_id,
+    );
+    push_optional_string(&mut attributes, "codex_helper.model", &attempt.model);
+    if let Some(status_code) = attempt.status_code {
+        attributes.push(int_attribute(
+            "http.response.status_code",
+            u64::from(status_code),
+        ));
+    }
+    push_optional_string(&mut attributes, "error.type", &attempt.error_class);
+    if let Some(headers_ms) = attempt.upstream_headers_ms {
+        attributes.push(int_attribute(
+            "codex_helper.upstream_headers_ms",
+            headers_ms,
+        ));
+    }
+    if attempt.fast_retry {
+        attributes
+            .push(json!({ "key": "codex_helper.fast_retry", "value": { "boolValue": true } }));
+    }
+
+    let name = match attempt.provider_id.as_deref() {
+        Some(provider_id) => format!("upstream {provider_id}"),
+        None => "upstream".to_string(),
+    };
+    let mut span = json!({
+        "traceId": trace_id,
+        "spanId": hex_digest(&format!("{log_trace_id}:attempt:{}", attempt.attempt_index), 8),
+        "parentSpanId": parent_span_id,
+        "name": name,
+        "kind": SPAN_KIND_CLIENT,
+        "startTimeUnixNano": unix_nanos(start_ms),
+        "endTimeUnixNano": unix_nanos(end_ms),
+        "attributes": attributes,
+    });
+    if attempt.error_class.is_some() || attempt.status_code.is_some_and(|code| code >= 500) {
+        span["status"] = json!({ "code": STATUS_CODE_ERROR });
+    }
+    span
+}
+
+/// Stable OTLP ids derived from the request trace id, so the exported trace can be matched
+/// with `requests.jsonl` through `codex_helper.trace_id`.
+fn hex_digest(input: &str, bytes: usize) -> String {
+    Sha256::digest(input.as_bytes())
+        .iter()
+        .take(bytes)
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+fn unix_nanos(ms: u64) -> String {
+    (u128::from(ms) * 1_000_000).to_string()
+}
+
+fn string_attribute(key: &str, value: &str) -> Value {
+    json!({ "key": key, "value": { "stringValue": value } })
+}
+
+/// OTLP JSON encodes 64-bit integers as strings.
+fn int_attribute(key: &str, value: u64) -> Value {
+    json!({ "key": key, "value": { "intValue": value.to_string() } })
+}
+
+fn push_optional_string(attributes: &mut Vec<Value>, key: &str, value: &Option<String>) {
+    if let Some(value) = value.as_deref() {
+        attributes.push(string_attribute(key, value));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attribute<'a>(span: &'a Value, key: &str) -> Option<&'a Value> {
+        span["attributes"]
+            .as_array()?
+            .iter()
+            .find(|attribute| attribute["key"] == key)
+            .map(|attribute| &attribute["value"])
+    }
+
+    #[test]
+    fn traces_url_appends_path_once() {
+        assert_eq!(
+            otlp_traces_url("http://127.0.0.1:4318/"),
+            "http://127.0.0.1:4318/v1/traces"
+        );
+        assert_eq!(
+            otlp_traces_url("https://otel.example/v1/traces"),
+            "https://otel.example/v1/traces"
+        );
+        assert!(validate_otlp_endpoint("grpc://127.0.0.1:4317").is_err());
+        assert!(validate_otlp_endpoint("not a url").is_err());
+    }
+
+    #[test]
+    fn request_span_carries_attributes_and_attempt_children() {
+        let span = RequestSpan {
+            service: "codex".to_string(),
+            request_id: 7,
+            method: "POST".to_string(),
+            path: "/v1/responses".to_string(),
+            status_code: 200,
+            duration_ms: 1_000,
+            ended_at_ms: 10_000,
+            profile: Some("daily".to_string()),
+            provider_id: Some("backup".to_string()),
+            model: Some("gpt-5".to_string()),
+            retry: Some(RetryInfo {
+                attempts: 2,
+                route_attempts: vec![
+                    RouteAttemptLog {
+                        attempt_index: 0,
+                        provider_id: Some("primary".to_string()),
+                        decision: "failed_status".to_string(),
+                        status_code: Some(502),
+                        error_class: Some("upstream_transport_error".to_string()),
+                        duration_ms: Some(300),
+                        ..Default::default()
+                    },
+                    RouteAttemptLog {
+                        attempt_index: 1,
+                        provider_id: Some("backup".to_string()),
+                        decision: "completed".to_string(),
+                        status_code: Some(200),
+                        duration_ms: Some(600),
+                        ..Default::default()
+                    },
+                ],
+            }),
+            ..Default::default()
+        };
+
+        let spans = request_span_json(&span);
+        assert_eq!(spans.len(), 3);
+        let root = &spans[0];
+        assert_eq!(root["name"], "POST /v1/responses");
+        assert_eq!(root["kind"], SPAN_KIND_SERVER);
+        assert_eq!(root["startTimeUnixNano"], "9000000000");
+        assert_eq!(root["traceId"].as_str().map(str::len), Some(32));
+        assert!(root.get("status").is_none());
+        assert_eq!(
+            attribute(root, "codex_helper.profile").unwrap()["stringValue"],
+            "daily"
+        );
+        assert_eq!(
+            attribute(root, "codex_helper.attempts").unwrap()["intValue"],
+            "2"
+        );
+        assert_eq!(
+            attribute(root, "http.response.status_code").unwrap()["intValue"],
+            "200"
+        );
+
+        let failed = &spans[1];
+        assert_eq!(failed["parentSpanId"], root["spanId"]);
+        assert_eq!(failed["traceId"], root["traceId"]);
+        assert_eq!(failed["status"]["code"], STATUS_CODE_ERROR);
+        assert_eq!(failed["endTimeUnixNano"], "9300000000");
+        assert_eq!(
+            attribute(failed, "error.type").unwrap()["stringValue"],
+            "upstream_transport_error"
+        );
+        assert_eq!(spans[2]["startTimeUnixNano"], "9300000000");
+        assert_ne!(spans[1]["spanId"], spans[2]["spanId"]);
+    }
+}
@@ -0,0 +1,21 @@
+//! Helpers shared by unit tests across the crate.
+
+use std::sync::OnceLock;
+
+use tokio::sync::{Mutex, MutexGuard};
+
+fn env_mutex() -> &'static Mutex<()> {
+    static LOCK: OnceLock<Mutex<()>> = OnceLock::new();
+    LOCK.get_or_init(|| Mutex::new(()))
+}
+
+/// Serializes every test that reads or writes the process environment, so one test's
+/// `set_var` never races another test's lookup.
+pub(crate) async fn env_lock() -> MutexGuard<'static, ()> {
+    env_mutex().lock().await
+}
+
+/// [`env_lock`] for synchronous tests; must not be called from inside a runtime.
+pub(crate) fn env_lock_blocking() -> MutexGuard<'static, ()> {
+    env_mutex().blocking_lock()
+}
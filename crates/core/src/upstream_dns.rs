@@ -0,0 +1,158 @@
+use std::net::SocketAddr;
+
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+
+use crate::config::AddressFamily;
+
+/// Resolves upstream hosts with the system resolver and keeps only addresses of one family.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct AddressFamilyResolver {
+    family: AddressFamily,
+}
+
+impl AddressFamilyResolver {
+    pub(crate) fn new(family: AddressFamily) -> Self {
+        Self { family }
+    }
+}
+
+impl Resolve for AddressFamilyResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let family = self.family;
+        Box::pin(async move {
+            let host = name.as_str().to_string();
+            let resolved = tokio::net::lookup_host((host.as_str(), 0))
+                .await?
+                .collect::<Vec<_>>();
+            let addrs = addresses_for_family(family, resolved);
+            if addrs.is_empty() {
+                return Err(Box::new(NoAddressForFamily { host, family }) as _);
+            }
+            tracing::debug!(
+                host = %host,
+                address_family = family.as_str(),
+                addresses = addrs.len(),
+                "resolved upstream host"
+            );
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+/// Keeps the addresses `family` allows, in resolver order.
+pub(crate) fn addresses_for_family(
+    family: AddressFamily,
+    addrs: impl IntoIterator<Item = SocketAddr>,
+) -> Vec<SocketAddr> {
+    addrs
+        .into_iter()
+        .filter(|addr| match family {
+            AddressFamily::Any => true,
+            AddressFamily::V4 => addr.is_ipv4(),
+            AddressFamily::V6 => addr.is_ipv6(),
+        })
+        .collect()
+}
+
+/// The host resolved, but not to any address of the configured family.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct NoAddressForFamily {
+    host: String,
+    family: AddressFamily,
+}
+
+impl std::fmt::Display for NoAddressForFamily {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let family = match self.family {
+            AddressFamily::V6 => "IPv6",
+            _ => "IPv4",
+        };
+        write!(
+            f,
+            "upstream host {} has no {family} address (address_family = \"{}\")",
+            self.host,
+            self.family.as_str()
+        )
+    }
+}
+
+impl std::error::Error for NoAddressForFamily {}
+
+/// Finds a [`NoAddressForFamily`] anywhere in a transport error's source chain.
+pub(crate) fn no_address_for_family<'a>(
+    error: &'a (dyn std::error::Error + 'static),
+) -> Option<&'a NoAddressForFamily> {
+    let mut current = Some(error);
+    while let Some(error) = current {
+        if let Some(problem) = error.downcast_ref::<NoAddressForFamily>() {
+            return Some(problem);
+        }
+        current = error.source();
+    }
+    None
+}
+
+/// Routes host resolution through [`AddressFamilyResolver`] unless every family is allowed.
+pub(crate) fn with_address_family(
+    builder: reqwest::ClientBuilder,
+    family: AddressFamily,
+) -> reqwest::ClientBuilder {
+    match family {
+        AddressFamily::Any => builder,
+        family => builder.dns_resolver(AddressFamilyResolver::new(family)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolved() -> Vec<SocketAddr> {
+        [
+            "[2001:db8::1]:0",
+            "192.0.2.1:0",
+            "[2001:db8::2]:0",
+            "192.0.2.2:0",
+        ]
+        .into_iter()
+        .map(|addr| addr.parse().expect("socket addr"))
+        .collect()
+    }
+
+    #[test]
+    fn address_family_keeps_only_matching_addresses_in_order() {
+        let v4 = addresses_for_family(AddressFamily::V4, resolved());
+        assert_eq!(v4, vec![resolved()[1], resolved()[3]]);
+        let v6 = addresses_for_family(AddressFamily::V6, resolved());
+        assert_eq!(v6, vec![resolved()[0], resolved()[2]]);
+        assert_eq!(
+            addresses_for_family(AddressFamily::Any, resolved()),
+            resolved()
+        );
+    }
+
+    #[tokio::test]
+    async fn resolver_reports_a_missing_family_by_host_and_setting() {
+        let name = |host: &str| host.parse::<Name>().expect("dns name");
+
+        let addrs = AddressFamilyResolver::new(AddressFamily::V4)
+            .resolve(name("127.0.0.1"))
+            .await
+            .expect("v4 address")
+            .collect::<Vec<_>>();
+        assert_eq!(addrs, vec!["127.0.0.1:0".parse().unwrap()]);
+
+        let error = match AddressFamilyResolver::new(AddressFamily::V6)
+            .resolve(name("127.0.0.1"))
+            .await
+        {
+            Ok(_) => panic!("no IPv6 address expected"),
+            Err(error) => error,
+        };
+        let problem = no_address_for_family(error.as_ref()).expect("typed error");
+        assert_eq!(
+            problem.to_string(),
+            "upstream host 127.0.0.1 has no IPv6 address (address_family = \"v6\")"
+        );
+    }
+}
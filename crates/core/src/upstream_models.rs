@@ -0,0 +1,248 @@
+//! Upstream `/v1/models` lists for `routing.validate_models_online`.
+//!
+//! The running proxy fetches the lists and stores them per service in
+//! `~/.codex-helper/upstream_models_<service>.json`; `config lint` and `config show` only read
+//! that cache, so neither makes network calls.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::PathBuf;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::config::{ServiceRouteConfig, proxy_home_dir};
+use crate::model_routing::effective_model;
+
+/// How long a fetched list is trusted before the proxy asks the provider again.
+pub const UPSTREAM_MODELS_TTL_MS: u64 = 60 * 60 * 1000;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UpstreamModelList {
+    pub fetched_at_ms: u64,
+    pub models: BTreeSet<String>,
+}
+
+impl UpstreamModelList {
+    pub fn is_fresh(&self, now_ms: u64) -> bool {
+        now_ms.saturating_sub(self.fetched_at_ms) < UPSTREAM_MODELS_TTL_MS
+    }
+}
+
+/// Model lists of one service, keyed by provider id.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UpstreamModelCache {
+    #[serde(default)]
+    pub providers: BTreeMap<String, UpstreamModelList>,
+}
+
+pub fn upstream_models_cache_path(service_name: &str) -> PathBuf {
+    proxy_home_dir().join(format!("upstream_models_{service_name}.json"))
+}
+
+/// A missing or unreadable cache is an empty one; the proxy rewrites it on its next fetch.
+pub fn load_upstream_model_cache(service_name: &str) -> UpstreamModelCache {
+    std::fs::read_to_string(upstream_models_cache_path(service_name))
+        .ok()
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_upstream_model_cache(service_name: &str, cache: &UpstreamModelCache) -> Result<()> {
+    let path = upstream_models_cache_path(service_name);
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    crate::write_text_file(&path, &serde_json::to_string_pretty(cache)?)
+}
+
+/// Model ids from an OpenAI/Anthropic `{"data": [{"id": ..}]}` list or a Codex
+/// `{"models": [{"slug": ..}]}` catalog; `None` for anything else.
+pub fn parse_models_response(value: &Value) -> Option<BTreeSet<String>> {
+    let (items, field) = if let Some(items) = value.get("data").and_then(Value::as_array) {
+        (items, "id")
+    } else {
+        (value.get("models").and_then(Value::as_array)?, "slug")
+    };
+    Some(
+        items
+            .iter()
+            .filter_map(|item| {
+                item.get(field)
+                    .or_else(|| item.get("id"))
+                    .and_then(Value::as_str)
+            })
+            .map(ToOwned::to_owned)
+            .collect(),
+    )
+}
+
+/// Model names in the config that the fetched lists show no provider can serve.
+///
+/// Only providers with a list are judged. A model requested by a profile or matched by a route
+/// condition is reported only when every enabled provider has a list and none of them offers
+/// it, directly or through its `model_mapping`. Wildcard patterns are never checked.
+pub fn model_validation_warnings(
+    view: &ServiceRouteConfig,
+    lists: &BTreeMap<String, UpstreamModelList>,
+) -> Vec<String> {
+    let mut warnings = Vec::new();
+    for (provider_id, provider) in &view.providers {
+        let Some(list) = lists.get(provider_id) else {
+            continue;
+        };
+        for (from, to) in &provider.model_mapping {
+            if !to.contains('*') && !list.models.contains(to) {
+                warnings.push(format!(
+                    "provider {provider_id} maps {from} to {to}, which its /v1/models does not list"
+                ));
+            }
+        }
+        let mapping = hash_mapping(&provider.model_mapping);
+        for (model, supported) in &provider.supported_models {
+            // A mapped name is already reported through its mapping entry above.
+            if *supported
+                && !model.contains('*')
+                && !provider.model_mapping.contains_key(model)
+                && !list.models.contains(&effective_model(&mapping, model))
+            {
+                warnings.push(format!(
+                    "provider {provider_id} lists {model} in supported_models, but its /v1/models does not"
+                ));
+            }
+        }
+    }
+
+    let enabled = view
+        .providers
+        .iter()
+        .filter(|(_, provider)| provider.enabled)
+        .collect::<Vec<_>>();
+    if enabled.is_empty() || enabled.iter().any(|(id, _)| !lists.contains_key(*id)) {
+        return warnings;
+    }
+    let offered_anywhere = |model: &str| {
+        enabled.iter().any(|(id, provider)| {
+            lists[*id].models.contains(&effective_model(
+                &hash_mapping(&provider.model_mapping),
+                model,
+            ))
+        })
+    };
+    let mut requested = Vec::new();
+    for (name, profile) in &view.profiles {
+        if let Some(model) = profile.model.as_deref() {
+            requested.push((format!("profile {name}"), model));
+        }
+    }
+    if let Some(routing) = view.routing.as_ref() {
+        for (name, node) in &routing.routes {
+            if let Some(model) = node.when.as_ref().and_then(|when| when.model.as_deref()) {
+                requested.push((format!("route {name}"), model));
+            }
+        }
+    }
+    for (owner, model) in requested {
+        if !model.contains('*') && !offered_anywhere(model) {
+            warnings.push(format!(
+                "{owner} uses model {model}, which no provider's /v1/models offers"
+            ));
+        }
+    }
+    warnings
+}
+
+fn hash_mapping(mapping: &BTreeMap<String, String>) -> HashMap<String, String> {
+    mapping
+        .iter()
+        .map(|(from, to)| (from.clone(), to.clone()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn list(models: &[&str]) -> UpstreamModelList {
+        UpstreamModelList {
+            fetched_at_ms: 1,
+            models: models.iter().map(|model| model.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn parse_models_response_reads_openai_and_codex_shapes() {
+        let openai = serde_json::json!({
+            "object": "list",
+            "data": [{ "id": "gpt-5.5" }, { "id": "gpt-5.4-mini" }]
+        });
+        assert_eq!(
+            parse_models_response(&openai),
+            Some(BTreeSet::from([
+                "gpt-5.4-mini".to_string(),
+                "gpt-5.5".to_string()
+            ]))
+        );
+        let codex = serde_json::json!({ "models": [{ "slug": "gpt-5.5-codex" }] });
+        assert_eq!(
+            parse_models_response(&codex),
+            Some(BTreeSet::from(["gpt-5.5-codex".to_string()]))
+        );
+        assert_eq!(
+            parse_models_response(&serde_json::json!({"error": "no"})),
+            None
+        );
+    }
+
+    #[test]
+    fn model_validation_flags_unlisted_mappings_profiles_and_route_models() {
+        let view: ServiceRouteConfig = toml::from_str(
+            r#"
+[profiles.fast]
+model = "gpt-5.4-mini"
+
+[profiles.deep]
+model = "gpt-5.5"
+
+[providers.relay]
+base_url = "https://relay.example/v1"
+model_mapping = { "gpt-5.5" = "relay/gpt-5.5", "gpt-*" = "relay/gpt-*" }
+supported_models = { "gpt-5.5" = true, "o9" = true }
+
+[providers.backup]
+base_url = "https://backup.example/v1"
+
+[routing]
+entry = "main"
+
+[routing.routes.main]
+strategy = "conditional"
+when = { model = "claude-opus" }
+then = "relay"
+default = "backup"
+"#,
+        )
+        .expect("view");
+        let lists = BTreeMap::from([
+            ("relay".to_string(), list(&["gpt-5.5"])),
+            ("backup".to_string(), list(&["gpt-5.5"])),
+        ]);
+
+        assert_eq!(
+            model_validation_warnings(&view, &lists),
+            vec![
+                "provider relay maps gpt-5.5 to relay/gpt-5.5, which its /v1/models does not list",
+                "provider relay lists o9 in supported_models, but its /v1/models does not",
+                "profile fast uses model gpt-5.4-mini, which no provider's /v1/models offers",
+                "route main uses model claude-opus, which no provider's /v1/models offers",
+            ]
+        );
+
+        let partial = BTreeMap::from([("relay".to_string(), list(&["relay/gpt-5.5"]))]);
+        assert_eq!(
+            model_validation_warnings(&view, &partial),
+            vec!["provider relay lists o9 in supported_models, but its /v1/models does not"],
+            "requested models are not judged while a provider's list is unknown"
+        );
+    }
+}
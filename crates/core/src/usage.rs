@@ -1,3 +1,5 @@
+pub mod request_log;
+
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
@@ -1324,6 +1326,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn captures_usage_from_a_representative_responses_stream() {
+        // `response.created` reports `usage: null`; only the terminal event carries totals.
+        let stream = concat!(
+            "event: response.created\n",
+            "data: {\"type\":\"response.created\",\"response\":{\"id\":\"resp_1\",\"model\":\"gpt-5-codex\",\"usage\":null}}\n\n",
+            "event: response.output_text.delta\n",
+            "data: {\"type\":\"response.output_text.delta\",\"item_id\":\"msg_1\",\"delta\":\"Hello\"}\n\n",
+            "event: response.completed\n",
+            "data: {\"type\":\"response.completed\",\"response\":{\"id\":\"resp_1\",\"model\":\"gpt-5-codex\",",
+            "\"usage\":{\"input_tokens\":1200,\"input_tokens_details\":{\"cached_tokens\":1024},",
+            "\"output_tokens\":300,\"output_tokens_details\":{\"reasoning_tokens\":128},\"total_tokens\":1500}}}\n\n",
+        );
+
+        let usage = extract_usage_from_bytes(stream.as_bytes()).expect("responses stream usage");
+
+        assert_eq!(usage.input_tokens, 1200);
+        assert_eq!(usage.output_tokens, 300);
+        assert_eq!(usage.reasoning_tokens, 128);
+        assert_eq!(usage.cached_input_tokens, 1024);
+        assert_eq!(usage.total_tokens, 1500);
+        assert_eq!(
+            extract_usage_from_sse_bytes(stream.as_bytes()),
+            Some(usage),
+            "buffered and streamed parsing agree"
+        );
+    }
+
     #[test]
     fn responses_nested_cache_write_zero_wins_over_positive_alias_and_keeps_conflict() {
         let json = r#"{
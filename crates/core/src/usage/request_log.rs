@@ -0,0 +1,411 @@
+//! Version-tolerant reader for `requests.jsonl`.
+//!
+//! Every line carries a `v` schema version (see [`REQUEST_LOG_SCHEMA_VERSION`]):
+//!
+//! - `0` (no `v` field): written before versioning. `trace_id` may be missing; it is filled with
+//!   the legacy `<service>-<request_id>` form the attribution index already understands.
+//! - `1`: adds `v`. All other fields are unchanged from version 0.
+//!
+//! Lines from a newer helper are read on a best-effort basis: unknown fields are ignored and
+//! missing fields fall back to their defaults, so an older binary can still summarize them.
+
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::Path;
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::UsageMetrics;
+use crate::logging::{
+    REQUEST_LOG_SCHEMA_VERSION, RetryInfo, ServiceTierLog, legacy_request_trace_id,
+};
+
+/// One `requests.jsonl` line, migrated to the current schema.
+#[derive(Debug, Clone, Default, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct RequestLogRecord {
+    /// Schema version the line was written with; `0` for lines written before versioning.
+    pub v: u32,
+    pub timestamp_ms: u64,
+    pub request_id: Option<u64>,
+    pub trace_id: Option<String>,
+    pub service: String,
+    pub method: Option<String>,
+    pub path: Option<String>,
+    pub status_code: u16,
+    pub duration_ms: u64,
+    pub ttfb_ms: Option<u64>,
+    pub provider_id: Option<String>,
+    pub endpoint_id: Option<String>,
+    pub provider_endpoint_key: Option<String>,
+    pub session_id: Option<String>,
+    pub tenant: Option<String>,
+    pub model: Option<String>,
+    pub reasoning_effort: Option<String>,
+    pub service_tier: ServiceTierLog,
+    pub usage: Option<UsageMetrics>,
+    /// Cost frozen when the request finished; absent on unpriced requests and older lines.
+    pub cost_usd: Option<String>,
+    /// The provider's id for the final upstream response, e.g. its `x-request-id`.
+    pub upstream_request_id: Option<String>,
+    pub retry: Option<RetryInfo>,
+    pub log_sample_rate: Option<f64>,
+}
+
+impl RequestLogRecord {
+    /// Parses one line and migrates it to the current schema. Returns `None` for blank or
+    /// malformed lines.
+    pub fn parse_line(line: &str) -> Option<Self> {
+        let line = line.trim();
+        if line.is_empty() {
+            return None;
+        }
+        let mut record = serde_json::from_str::<Self>(line).ok()?;
+        record.migrate();
+        Some(record)
+    }
+
+    /// Whether the line came from a helper newer than this one; its newer fields were dropped.
+    pub fn is_newer_than_known(&self) -> bool {
+        self.v > REQUEST_LOG_SCHEMA_VERSION
+    }
+
+    /// How many requests this line stands for: a line kept by `logging.sample_rate` represents
+    /// `1 / log_sample_rate` requests, every other line just itself.
+    pub fn sample_weight(&self) -> f64 {
+        match self.log_sample_rate {
+            Some(rate) if rate > 0.0 && rate < 1.0 => 1.0 / rate,
+            _ => 1.0,
+        }
+    }
+
+    fn migrate(&mut self) {
+        if self.v == 0
+            && self.trace_id.is_none()
+            && let Some(request_id) = self.request_id
+        {
+            self.trace_id = Some(legacy_request_trace_id(&self.service, request_id));
+        }
+    }
+}
+
+/// Reads every parseable line of a request log, oldest first. A missing file reads as empty.
+pub fn read_request_log(path: &Path) -> io::Result<Vec<RequestLogRecord>> {
+    let text = match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(error) => return Err(error),
+    };
+    Ok(text
+        .lines()
+        .filter_map(RequestLogRecord::parse_line)
+        .collect())
+}
+
+/// Like [`read_request_log`], but starts with the rotated `requests.*.jsonl[.gz]` files next to
+/// `path`, oldest first, so the result covers everything retention still keeps.
+pub fn read_request_log_with_rotated(path: &Path) -> io::Result<Vec<RequestLogRecord>> {
+    let mut records = Vec::new();
+    for rotated in crate::local_log_store::collect_rotated_logs(path) {
+        let text = if rotated.path.extension().is_some_and(|ext| ext == "gz") {
+            let mut text = String::new();
+            flate2::read::GzDecoder::new(std::fs::File::open(&rotated.path)?)
+                .read_to_string(&mut text)?;
+            text
+        } else {
+            std::fs::read_to_string(&rotated.path)?
+        };
+        records.extend(text.lines().filter_map(RequestLogRecord::parse_line));
+    }
+    records.extend(read_request_log(path)?);
+    Ok(records)
+}
+
+/// How far back from the end of a log [`read_upstream_error_body`] looks. The TUI calls it from
+/// its key handler for a request it just showed, so the record is recent.
+const NEWEST_LINE_SCAN_LIMIT_BYTES: u64 = 8 * 1024 * 1024;
+const NEWEST_LINE_SCAN_CHUNK_BYTES: u64 = 64 * 1024;
+
+/// The upstream response body captured for a failed request while HTTP debug bodies are on
+/// (`CODEX_HELPER_HTTP_DEBUG`), following the `http_debug_ref` into `requests_debug.jsonl` when
+/// the record was split out. Falls back to the transport error text; `None` when the request
+/// was logged without a debug record or is not within the newest
+/// `NEWEST_LINE_SCAN_LIMIT_BYTES` of the active file.
+pub fn read_upstream_error_body(
+    path: &Path,
+    service: &str,
+    request_id: u64,
+) -> io::Result<Option<String>> {
+    let Some(entry) = find_newest_json_line(path, |entry| {
+        entry.get("service").and_then(Value::as_str) == Some(service)
+            && entry.get("request_id").and_then(Value::as_u64) == Some(request_id)
+    })?
+    else {
+        return Ok(None);
+    };
+    let http_debug = match entry.get("http_debug") {
+        Some(http_debug) => Some(http_debug.clone()),
+        None => match entry.get("http_debug_ref") {
+            Some(reference) => {
+                let debug_id = reference.get("id").and_then(Value::as_str);
+                let file = reference
+                    .get("file")
+                    .and_then(Value::as_str)
+                    .unwrap_or("requests_debug.jsonl");
+                find_newest_json_line(&path.with_file_name(file), |debug| {
+                    debug_id.is_some() && debug.get("id").and_then(Value::as_str) == debug_id
+                })?
+                .and_then(|mut debug| debug.get_mut("http_debug").map(Value::take))
+            }
+            None => None,
+        },
+    };
+    let Some(http_debug) = http_debug else {
+        return Ok(None);
+    };
+    let body = http_debug
+        .get("upstream_response_body")
+        .filter(|body| body.get("encoding").and_then(Value::as_str) == Some("utf8"))
+        .and_then(|body| {
+            let data = body.get("data").and_then(Value::as_str)?;
+            let truncated = body.get("truncated").and_then(Value::as_bool) == Some(true);
+            Some(if truncated {
+                format!("{data}…")
+            } else {
+                data.to_string()
+            })
+        })
+        .filter(|body| !body.trim().is_empty());
+    Ok(body.or_else(|| {
+        http_debug
+            .get("upstream_error")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+    }))
+}
+
+/// Reads `path` backwards a chunk at a time and returns the newest line `matches` accepts,
+/// without looking further back than `NEWEST_LINE_SCAN_LIMIT_BYTES`.
+fn find_newest_json_line(
+    path: &Path,
+    matches: impl Fn(&Value) -> bool,
+) -> io::Result<Option<Value>> {
+    let mut file = match File::open(path) {
+        Ok(file) => file,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(error) => return Err(error),
+    };
+    let parse = |line: &[u8]| {
+        std::str::from_utf8(line)
+            .ok()
+            .and_then(|line| serde_json::from_str::<Value>(line.trim()).ok())
+            .filter(|value| matches(value))
+    };
+    let len = file.metadata()?.len();
+    let floor = len.saturating_sub(NEWEST_LINE_SCAN_LIMIT_BYTES);
+    let mut end = len;
+    // Bytes of a line whose start lies before the chunk read last.
+    let mut partial = Vec::new();
+    while end > floor {
+        let start = end.saturating_sub(NEWEST_LINE_SCAN_CHUNK_BYTES).max(floor);
+        let mut chunk = vec![0; (end - start) as usize];
+        file.seek(SeekFrom::Start(start))?;
+        file.read_exact(&mut chunk)?;
+        chunk.append(&mut partial);
+        let mut line_end = chunk.len();
+        while let Some(newline) = chunk[..line_end].iter().rposition(|byte| *byte == b'\n') {
+            if let Some(value) = parse(&chunk[newline + 1..line_end]) {
+                return Ok(Some(value));
+            }
+            line_end = newline;
+        }
+        chunk.truncate(line_end);
+        partial = chunk;
+        end = start;
+    }
+    // At the start of the file the remainder is the first line; past the limit it is cut.
+    Ok(if floor == 0 { parse(&partial) } else { None })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn reads_mixed_version_request_log() {
+        let dir = std::env::temp_dir().join(format!(
+            "codex-helper-request-log-schema-{}",
+            uuid::Uuid::new_v4()
+        ));
+        std::fs::create_dir_all(&dir).expect("create temp dir");
+        let path = dir.join("requests.jsonl");
+        let lines = [
+            r#"{"timestamp_ms":1,"request_id":7,"service":"codex","method":"POST","path":"/v1/responses","status_code":200,"duration_ms":10}"#,
+            "not json",
+            r#"{"v":1,"timestamp_ms":2,"request_id":8,"trace_id":"t-8","service":"codex","method":"POST","path":"/v1/responses","status_code":502,"duration_ms":20,"provider_id":"primary","usage":{"input_tokens":3,"output_tokens":4,"total_tokens":7}}"#,
+            r#"{"v":99,"timestamp_ms":3,"service":"claude","status_code":200,"duration_ms":30,"model":"m","field_from_the_future":{"nested":true}}"#,
+            "",
+        ];
+        std::fs::write(&path, lines.join("\n")).expect("write request log");
+
+        let records = read_request_log(&path).expect("read request log");
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(records.len(), 3);
+
+        let legacy = &records[0];
+        assert_eq!(legacy.v, 0);
+        assert_eq!(legacy.trace_id.as_deref(), Some("codex-7"));
+        assert_eq!(legacy.log_sample_rate, None);
+        assert!(!legacy.is_newer_than_known());
+
+        let current = &records[1];
+        assert_eq!(current.v, REQUEST_LOG_SCHEMA_VERSION);
+        assert_eq!(current.trace_id.as_deref(), Some("t-8"));
+        assert_eq!(current.provider_id.as_deref(), Some("primary"));
+        assert_eq!(
+            current.usage.as_ref().map(|usage| usage.total_tokens),
+            Some(7)
+        );
+
+        let future = &records[2];
+        assert!(future.is_newer_than_known());
+        assert_eq!(future.service, "claude");
+        assert_eq!(future.model.as_deref(), Some("m"));
+        assert_eq!(future.trace_id, None);
+    }
+
+    #[test]
+    fn truncated_final_line_is_skipped() {
+        let dir = std::env::temp_dir().join(format!(
+            "codex-helper-request-log-truncated-{}",
+            uuid::Uuid::new_v4()
+        ));
+        std::fs::create_dir_all(&dir).expect("create temp dir");
+        let path = dir.join("requests.jsonl");
+        std::fs::write(
+            &path,
+            concat!(
+                r#"{"v":1,"timestamp_ms":1,"request_id":1,"service":"codex","status_code":200,"duration_ms":10}"#,
+                "\n",
+                r#"{"v":1,"timestamp_ms":2,"request_id":2,"service":"codex","status_co"#,
+            ),
+        )
+        .expect("write request log");
+
+        let records = read_request_log(&path).expect("read request log");
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].request_id, Some(1));
+    }
+
+    #[test]
+    fn missing_request_log_reads_as_empty() {
+        let path = std::env::temp_dir()
+            .join(format!("codex-helper-missing-{}", uuid::Uuid::new_v4()))
+            .join("requests.jsonl");
+
+        assert_eq!(read_request_log(&path).expect("read"), Vec::new());
+    }
+
+    #[test]
+    fn reads_rotated_logs_before_the_active_file() {
+        use std::io::Write;
+
+        let dir = std::env::temp_dir().join(format!(
+            "codex-helper-request-log-rotated-{}",
+            uuid::Uuid::new_v4()
+        ));
+        std::fs::create_dir_all(&dir).expect("create temp dir");
+        let path = dir.join("requests.jsonl");
+        let line = |timestamp_ms: u64| {
+            format!(
+                r#"{{"timestamp_ms":{timestamp_ms},"service":"codex","status_code":200,"duration_ms":1,"log_sample_rate":0.25}}"#
+            )
+        };
+        let mut gz = flate2::write::GzEncoder::new(
+            std::fs::File::create(dir.join("requests.1.jsonl.gz")).expect("create gz"),
+            flate2::Compression::default(),
+        );
+        writeln!(gz, "{}", line(1)).expect("write gz");
+        gz.finish().expect("finish gz");
+        std::fs::write(&path, format!("{}\n", line(2))).expect("write request log");
+
+        let records = read_request_log_with_rotated(&path).expect("read request logs");
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(
+            records
+                .iter()
+                .map(|record| record.timestamp_ms)
+                .collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+        assert_eq!(records[0].sample_weight(), 4.0);
+    }
+
+    #[test]
+    fn upstream_error_body_follows_the_split_debug_record() {
+        let dir = std::env::temp_dir().join(format!(
+            "codex-helper-request-log-error-body-{}",
+            uuid::Uuid::new_v4()
+        ));
+        std::fs::create_dir_all(&dir).expect("create temp dir");
+        let path = dir.join("requests.jsonl");
+        std::fs::write(
+            &path,
+            [
+                r#"{"request_id":7,"service":"codex","status_code":502,"http_debug_ref":{"id":"d-1","file":"requests_debug.jsonl"}}"#,
+                r#"{"request_id":8,"service":"codex","status_code":500,"http_debug":{"upstream_error":"connection reset"}}"#,
+                r#"{"request_id":9,"service":"codex","status_code":429}"#,
+            ]
+            .join("\n"),
+        )
+        .expect("write request log");
+        std::fs::write(
+            dir.join("requests_debug.jsonl"),
+            r#"{"id":"d-1","http_debug":{"upstream_response_body":{"encoding":"utf8","data":"{\"error\":\"overloaded\"}","truncated":false,"original_len":22}}}"#,
+        )
+        .expect("write debug log");
+
+        let body = |request_id| read_upstream_error_body(&path, "codex", request_id).expect("read");
+        let results = (body(7), body(8), body(9));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(
+            results,
+            (
+                Some(r#"{"error":"overloaded"}"#.to_string()),
+                Some("connection reset".to_string()),
+                None
+            )
+        );
+    }
+
+    #[test]
+    fn upstream_error_body_is_found_across_scan_chunks() {
+        let dir = std::env::temp_dir().join(format!(
+            "codex-helper-request-log-error-body-chunks-{}",
+            uuid::Uuid::new_v4()
+        ));
+        std::fs::create_dir_all(&dir).expect("create temp dir");
+        let path = dir.join("requests.jsonl");
+        let mut lines = vec![
+            r#"{"request_id":1,"service":"codex","status_code":502,"http_debug":{"upstream_error":"first line"}}"#.to_string(),
+        ];
+        lines.extend((2..4000).map(|request_id| {
+            format!(r#"{{"request_id":{request_id},"service":"codex","status_code":200}}"#)
+        }));
+        std::fs::write(&path, lines.join("\n")).expect("write request log");
+        assert!(std::fs::metadata(&path).expect("stat").len() > 2 * NEWEST_LINE_SCAN_CHUNK_BYTES);
+
+        let result = read_upstream_error_body(&path, "codex", 1).expect("read");
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(result.as_deref(), Some("first line"));
+    }
+}
@@ -1,18 +1,143 @@
+use crate::config::UiConfig;
 use crate::usage::UsageMetrics;
 
-pub fn tokens_short(value: i64) -> String {
-    let value = value.max(0) as f64;
-    if value >= 1_000_000_000.0 {
-        format!("{:.1}b", value / 1_000_000_000.0)
-    } else if value >= 1_000_000.0 {
-        format!("{:.1}m", value / 1_000_000.0)
-    } else if value >= 1_000.0 {
-        format!("{:.1}k", value / 1_000.0)
-    } else {
-        format!("{value:.0}")
+/// Display-only number and currency conventions resolved from `[ui]`.
+///
+/// The default reproduces the historical output: `$` prefix, `.` decimals, no grouping.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DisplayFormat {
+    pub currency_symbol: String,
+    pub currency_code: Option<String>,
+    pub decimal_separator: char,
+    pub group_separator: Option<char>,
+}
+
+impl Default for DisplayFormat {
+    fn default() -> Self {
+        Self {
+            currency_symbol: "$".to_string(),
+            currency_code: None,
+            decimal_separator: '.',
+            group_separator: None,
+        }
     }
 }
 
+impl DisplayFormat {
+    pub fn from_ui_config(ui: &UiConfig) -> Self {
+        let mut format = Self::default();
+        if let Some((group, decimal)) = ui.number_locale.as_deref().and_then(locale_separators) {
+            format.group_separator = Some(group);
+            format.decimal_separator = decimal;
+        }
+        if let Some(currency) = ui.currency.as_ref() {
+            let code = currency
+                .code
+                .as_deref()
+                .map(str::trim)
+                .filter(|code| !code.is_empty())
+                .map(str::to_ascii_uppercase);
+            let symbol = currency.symbol.trim();
+            if !symbol.is_empty() || code.is_some() {
+                format.currency_symbol = symbol.to_string();
+                format.currency_code = code;
+            }
+        }
+        format
+    }
+
+    /// Regroups a plain `1234.5` decimal string; non-numeric input is returned unchanged.
+    pub fn decimal(&self, raw: &str) -> String {
+        let (sign, unsigned) = match raw.strip_prefix('-') {
+            Some(rest) => ("-", rest),
+            None => ("", raw),
+        };
+        let (integer, fraction) = match unsigned.split_once('.') {
+            Some((integer, fraction)) => (integer, Some(fraction)),
+            None => (unsigned, None),
+        };
+        if integer.is_empty()
+            || !integer.bytes().all(|byte| byte.is_ascii_digit())
+            || fraction.is_some_and(|fraction| !fraction.bytes().all(|byte| byte.is_ascii_digit()))
+        {
+            return raw.to_string();
+        }
+        let mut out = String::with_capacity(raw.len() + integer.len() / 3);
+        out.push_str(sign);
+        out.push_str(&self.group_digits(integer));
+        if let Some(fraction) = fraction {
+            out.push(self.decimal_separator);
+            out.push_str(fraction);
+        }
+        out
+    }
+
+    pub fn cost(&self, raw_usd_decimal: &str) -> String {
+        let amount = self.decimal(raw_usd_decimal);
+        match (
+            self.currency_symbol.is_empty(),
+            self.currency_code.as_deref(),
+        ) {
+            (true, Some(code)) => format!("{amount} {code}"),
+            _ => format!("{}{amount}", self.currency_symbol),
+        }
+    }
+
+    pub fn count(&self, value: u64) -> String {
+        self.group_digits(&value.to_string())
+    }
+
+    pub fn tokens_short(&self, value: i64) -> String {
+        let value = value.max(0) as f64;
+        let (scaled, suffix) = if value >= 1_000_000_000.0 {
+            (value / 1_000_000_000.0, "b")
+        } else if value >= 1_000_000.0 {
+            (value / 1_000_000.0, "m")
+        } else if value >= 1_000.0 {
+            (value / 1_000.0, "k")
+        } else {
+            return self.count(value as u64);
+        };
+        format!("{}{suffix}", self.decimal(&format!("{scaled:.1}")))
+    }
+
+    fn group_digits(&self, digits: &str) -> String {
+        let Some(separator) = self.group_separator else {
+            return digits.to_string();
+        };
+        let mut out = String::with_capacity(digits.len() + digits.len() / 3);
+        for (idx, ch) in digits.chars().enumerate() {
+            if idx > 0 && (digits.len() - idx).is_multiple_of(3) {
+                out.push(separator);
+            }
+            out.push(ch);
+        }
+        out
+    }
+}
+
+/// Maps a locale tag to `(group, decimal)` separators; unknown locales keep the plain format.
+fn locale_separators(locale: &str) -> Option<(char, char)> {
+    let language = locale
+        .trim()
+        .split(['-', '_', '.'])
+        .next()?
+        .to_ascii_lowercase();
+    match language.as_str() {
+        "en" | "zh" | "ja" | "ko" | "th" | "he" => Some((',', '.')),
+        "de" | "es" | "it" | "nl" | "pt" | "id" | "tr" | "da" | "el" => Some(('.', ',')),
+        "fr" | "ru" | "pl" | "sv" | "fi" | "nb" | "no" | "cs" | "sk" | "uk" | "hu" => {
+            Some((' ', ','))
+        }
+        _ => None,
+    }
+}
+
+/// Token count in the default format; `DisplayFormat::tokens_short` applies `[ui]`.
+pub fn tokens_short(value: i64) -> String {
+    DisplayFormat::default().tokens_short(value)
+}
+
 pub fn tokens_per_second(value: Option<f64>) -> String {
     value
         .filter(|value| value.is_finite() && *value > 0.0)
@@ -21,22 +146,23 @@ pub fn tokens_per_second(value: Option<f64>) -> String {
 }
 
 pub fn usage_line_with_labels(
+    format: &DisplayFormat,
     usage: &UsageMetrics,
     usage_label: &str,
     cache_label: &str,
 ) -> String {
     let mut line = format!(
         "{usage_label}: {}/{}/{}/{}",
-        tokens_short(usage.input_tokens),
-        tokens_short(usage.output_tokens),
-        tokens_short(usage.reasoning_output_tokens_total()),
-        tokens_short(usage.total_tokens)
+        format.tokens_short(usage.input_tokens),
+        format.tokens_short(usage.output_tokens),
+        format.tokens_short(usage.reasoning_output_tokens_total()),
+        format.tokens_short(usage.total_tokens)
     );
     if usage.has_cache_tokens() {
         line.push_str(&format!(
             " {cache_label}: {}/{}",
-            tokens_short(usage.cache_read_tokens_total()),
-            tokens_short(usage.cache_creation_tokens_total())
+            format.tokens_short(usage.cache_read_tokens_total()),
+            format.tokens_short(usage.cache_creation_tokens_total())
         ));
     }
     line
@@ -45,6 +171,7 @@ pub fn usage_line_with_labels(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::CurrencyDisplayConfig;
 
     #[test]
     fn tokens_short_scales_counts() {
@@ -55,6 +182,50 @@ mod tests {
         assert_eq!(tokens_short(1_500_000_000), "1.5b");
     }
 
+    #[test]
+    fn display_format_defaults_to_plain_usd() {
+        let format = DisplayFormat::from_ui_config(&UiConfig::default());
+        assert_eq!(format, DisplayFormat::default());
+        assert_eq!(format.cost("1234.5"), "$1234.5");
+        assert_eq!(format.count(1_234_567), "1234567");
+        assert_eq!(format.tokens_short(1_500), "1.5k");
+    }
+
+    #[test]
+    fn display_format_applies_locale_grouping_and_currency() {
+        let ui = UiConfig {
+            currency: Some(CurrencyDisplayConfig {
+                symbol: "€".to_string(),
+                code: Some("eur".to_string()),
+            }),
+            number_locale: Some("de-DE".to_string()),
+            ..UiConfig::default()
+        };
+        let format = DisplayFormat::from_ui_config(&ui);
+        assert_eq!(format.cost("1234567.125"), "€1.234.567,125");
+        assert_eq!(format.cost("-12.5"), "€-12,5");
+        assert_eq!(format.count(999), "999");
+        assert_eq!(format.count(1_234_567), "1.234.567");
+        assert_eq!(format.tokens_short(1_500), "1,5k");
+        assert_eq!(format.tokens_short(12_345), "12,3k");
+    }
+
+    #[test]
+    fn display_format_uses_code_suffix_without_symbol_and_ignores_unknown_locales() {
+        let ui = UiConfig {
+            currency: Some(CurrencyDisplayConfig {
+                symbol: String::new(),
+                code: Some("CNY".to_string()),
+            }),
+            number_locale: Some("xx".to_string()),
+            ..UiConfig::default()
+        };
+        let format = DisplayFormat::from_ui_config(&ui);
+        assert_eq!(format.cost("0.42"), "0.42 CNY");
+        assert_eq!(format.cost("n/a"), "n/a CNY");
+        assert_eq!(format.count(12_000), "12000");
+    }
+
     #[test]
     fn tokens_per_second_formats_positive_finite_values() {
         assert_eq!(tokens_per_second(None), "-");
@@ -76,7 +247,12 @@ mod tests {
         };
 
         assert_eq!(
-            usage_line_with_labels(&usage, "tok in/out/rsn/ttl", "cache read/create"),
+            usage_line_with_labels(
+                &DisplayFormat::default(),
+                &usage,
+                "tok in/out/rsn/ttl",
+                "cache read/create"
+            ),
             "tok in/out/rsn/ttl: 1.0k/20/3/1.0k cache read/create: 10/5"
         );
     }
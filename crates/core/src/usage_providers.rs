@@ -31,6 +31,7 @@ use crate::credentials::{
     NamedCredentialReference, SecretValue,
 };
 use crate::pricing::UsdAmount;
+use crate::provider_tls::ProviderTlsClients;
 use crate::quota_pool::{
     ConversionSource, QuotaConversion, QuotaCounterKind, QuotaObservationContext, QuotaQuantity,
     QuotaScope, QuotaUnit, QuotaWindowSemantics, RemoteIdentityProof,
@@ -302,6 +303,7 @@ pub(crate) struct UsageProviderRuntimeCapture {
     config: Arc<HelperConfig>,
     credentials: Arc<CredentialGeneration>,
     provider_catalog: Arc<UsageProviderCatalog>,
+    provider_tls_clients: Arc<ProviderTlsClients>,
 }
 
 impl UsageProviderRuntimeCapture {
@@ -309,13 +311,28 @@ impl UsageProviderRuntimeCapture {
         config: Arc<HelperConfig>,
         credentials: Arc<CredentialGeneration>,
         provider_catalog: Arc<UsageProviderCatalog>,
+        provider_tls_clients: Arc<ProviderTlsClients>,
     ) -> Self {
         Self {
             config,
             credentials,
             provider_catalog,
+            provider_tls_clients,
         }
     }
+
+    /// Balance probes reach the same upstream host as requests, so they use the provider's own
+    /// `[tls]` or address-family client when it has one.
+    fn client_for(&self, target: &UsageProviderTarget, shared: &Client) -> Client {
+        let provider_endpoint = &target.endpoint.provider_endpoint;
+        self.provider_tls_clients
+            .client(
+                provider_endpoint.service_name.as_str(),
+                provider_endpoint.provider_id.as_str(),
+            )
+            .unwrap_or(shared)
+            .clone()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -5442,6 +5459,7 @@ async fn refresh_provider_target(
 
 #[derive(Clone)]
 struct ConfiguredRefreshJob {
+    client: Client,
     provider: UsageProviderConfig,
     target: UsageProviderTarget,
     token: Option<SecretValue>,
@@ -5465,6 +5483,7 @@ impl std::fmt::Debug for ConfiguredRefreshJob {
 
 #[derive(Clone)]
 struct AutoRefreshJob {
+    client: Client,
     target: UsageProviderTarget,
     token: Option<SecretValue>,
     force: bool,
@@ -5489,13 +5508,12 @@ pub struct UsageProviderRefreshOptions<'a> {
 }
 
 async fn execute_configured_refresh_job(
-    client: &Client,
     job: &ConfiguredRefreshJob,
     state: &Arc<ProxyState>,
     _service_name: &str,
 ) -> UsageProviderRefreshOutcome {
     refresh_provider_target(RefreshProviderTargetParams {
-        client,
+        client: &job.client,
         provider: &job.provider,
         target: &job.target,
         token: job.token.as_ref(),
@@ -5507,13 +5525,12 @@ async fn execute_configured_refresh_job(
 }
 
 async fn execute_auto_refresh_job(
-    client: &Client,
     job: &AutoRefreshJob,
     state: &Arc<ProxyState>,
     service_name: &str,
 ) -> UsageProviderRefreshOutcome {
     auto_probe_provider_target_with_token(
-        client,
+        &job.client,
         &job.target,
         job.token.as_ref(),
         state,
@@ -5524,7 +5541,6 @@ async fn execute_auto_refresh_job(
 }
 
 async fn run_configured_refresh_job(
-    client: &Client,
     job: ConfiguredRefreshJob,
     state: &Arc<ProxyState>,
     service_name: &str,
@@ -5535,7 +5551,7 @@ async fn run_configured_refresh_job(
         return (
             cooldown_key,
             CoordinatedProviderBalanceRefresh {
-                outcome: execute_configured_refresh_job(client, &job, state, service_name).await,
+                outcome: execute_configured_refresh_job(&job, state, service_name).await,
                 deduplicated: false,
             },
         );
@@ -5546,7 +5562,7 @@ async fn run_configured_refresh_job(
         return (
             cooldown_key,
             CoordinatedProviderBalanceRefresh {
-                outcome: execute_configured_refresh_job(client, &job, state, service_name).await,
+                outcome: execute_configured_refresh_job(&job, state, service_name).await,
                 deduplicated: false,
             },
         );
@@ -5555,18 +5571,16 @@ async fn run_configured_refresh_job(
     let force = job.force;
     let coordinator = state.provider_balance_refresh_coordinator();
     let job_template = job;
-    let client = client.clone();
     let state = Arc::clone(state);
     let service_name = service_name.to_string();
     let coordinated = coordinator
         .coordinate(key, force, move |round_force| {
             let mut job = job_template.clone();
-            let client = client.clone();
             let state = Arc::clone(&state);
             let service_name = service_name.clone();
             async move {
                 job.force = round_force;
-                execute_configured_refresh_job(&client, &job, &state, &service_name).await
+                execute_configured_refresh_job(&job, &state, &service_name).await
             }
         })
         .await;
@@ -5589,20 +5603,19 @@ fn auto_refresh_target_key(
 }
 
 async fn run_auto_refresh_job(
-    client: &Client,
     job: AutoRefreshJob,
     state: &Arc<ProxyState>,
     service_name: &str,
 ) -> CoordinatedProviderBalanceRefresh {
     let Some(token) = job.token.as_ref() else {
         return CoordinatedProviderBalanceRefresh {
-            outcome: execute_auto_refresh_job(client, &job, state, service_name).await,
+            outcome: execute_auto_refresh_job(&job, state, service_name).await,
             deduplicated: false,
         };
     };
     let Some(key) = auto_refresh_target_key(state, &job.target, token) else {
         return CoordinatedProviderBalanceRefresh {
-            outcome: execute_auto_refresh_job(client, &job, state, service_name).await,
+            outcome: execute_auto_refresh_job(&job, state, service_name).await,
             deduplicated: false,
         };
     };
@@ -5610,25 +5623,22 @@ async fn run_auto_refresh_job(
     let force = job.force;
     let coordinator = state.provider_balance_refresh_coordinator();
     let job_template = job;
-    let client = client.clone();
     let state = Arc::clone(state);
     let service_name = service_name.to_string();
     coordinator
         .coordinate(key, force, move |round_force| {
             let mut job = job_template.clone();
-            let client = client.clone();
             let state = Arc::clone(&state);
             let service_name = service_name.clone();
             async move {
                 job.force = round_force;
-                execute_auto_refresh_job(&client, &job, &state, &service_name).await
+                execute_auto_refresh_job(&job, &state, &service_name).await
             }
         })
         .await
 }
 
 async fn run_configured_refresh_jobs(
-    client: &Client,
     jobs: Vec<ConfiguredRefreshJob>,
     state: &Arc<ProxyState>,
     service_name: &str,
@@ -5642,13 +5652,13 @@ async fn run_configured_refresh_jobs(
         let Some(job) = pending.next() else {
             break;
         };
-        running.push(run_configured_refresh_job(client, job, state, service_name));
+        running.push(run_configured_refresh_job(job, state, service_name));
     }
 
     while let Some(result) = running.next().await {
         results.push(result);
         if let Some(job) = pending.next() {
-            running.push(run_configured_refresh_job(client, job, state, service_name));
+            running.push(run_configured_refresh_job(job, state, service_name));
         }
     }
 
@@ -5656,7 +5666,6 @@ async fn run_configured_refresh_jobs(
 }
 
 async fn run_auto_refresh_jobs(
-    client: &Client,
     jobs: Vec<AutoRefreshJob>,
     state: &Arc<ProxyState>,
     service_name: &str,
@@ -5670,13 +5679,13 @@ async fn run_auto_refresh_jobs(
         let Some(job) = pending.next() else {
             break;
         };
-        running.push(run_auto_refresh_job(client, job, state, service_name));
+        running.push(run_auto_refresh_job(job, state, service_name));
     }
 
     while let Some(result) = running.next().await {
         results.push(result);
         if let Some(job) = pending.next() {
-            running.push(run_auto_refresh_job(client, job, state, service_name));
+            running.push(run_auto_refresh_job(job, state, service_name));
         }
     }
 
@@ -6396,6 +6405,7 @@ pub(crate) async fn refresh_balances_for_service(
             summary.attempted += 1;
             configured_job_keys.insert(target_key(&target));
             configured_jobs.push(ConfiguredRefreshJob {
+                client: runtime.client_for(&target, client),
                 provider: provider.clone(),
                 token: capture_usage_token(provider, &target),
                 target,
@@ -6408,7 +6418,7 @@ pub(crate) async fn refresh_balances_for_service(
     let mut refreshed_provider_targets = HashSet::new();
     if !configured_jobs.is_empty() {
         for (cooldown_key, coordinated) in
-            run_configured_refresh_jobs(client, configured_jobs, &state, service_name).await
+            run_configured_refresh_jobs(configured_jobs, &state, service_name).await
         {
             summary.deduplicated += usize::from(coordinated.deduplicated);
             if summary.record_configured_outcome(coordinated.outcome) {
@@ -6434,6 +6444,7 @@ pub(crate) async fn refresh_balances_for_service(
             auto_usage_provider(&target, first_auto_probe_kind(&target))
         };
         auto_jobs.push(AutoRefreshJob {
+            client: runtime.client_for(&target, client),
             token: capture_usage_token(&provider, &target),
             target,
             force,
@@ -6441,7 +6452,7 @@ pub(crate) async fn refresh_balances_for_service(
     }
 
     if !auto_jobs.is_empty() {
-        for coordinated in run_auto_refresh_jobs(client, auto_jobs, &state, service_name).await {
+        for coordinated in run_auto_refresh_jobs(auto_jobs, &state, service_name).await {
             summary.deduplicated += usize::from(coordinated.deduplicated);
             summary.record_auto_outcome(coordinated.outcome);
         }
@@ -6558,6 +6569,7 @@ async fn poll_for_codex_target(
         }
 
         configured_jobs.push(ConfiguredRefreshJob {
+            client: client.clone(),
             provider: provider.clone(),
             token: capture_usage_token(provider, &current_target),
             target: current_target.clone(),
@@ -6567,9 +6579,7 @@ async fn poll_for_codex_target(
     }
 
     if !configured_jobs.is_empty() {
-        let _ =
-            run_configured_refresh_jobs(&client, configured_jobs, &state, service_name.as_str())
-                .await;
+        let _ = run_configured_refresh_jobs(configured_jobs, &state, service_name.as_str()).await;
         return RequestBalancePollOutcome::Attempted;
     }
 
@@ -6600,8 +6610,8 @@ async fn poll_for_codex_target(
     }
 
     let _ = run_auto_refresh_job(
-        &client,
         AutoRefreshJob {
+            client,
             token: capture_usage_token(&auto_provider, &current_target),
             target: current_target,
             force: false,
@@ -7247,7 +7257,12 @@ mod tests {
                 provider_catalog.revision.as_str(),
             )
             .expect("build usage credential generation");
-        UsageProviderRuntimeCapture::new(Arc::new(cfg), credentials, Arc::new(provider_catalog))
+        UsageProviderRuntimeCapture::new(
+            Arc::new(cfg),
+            credentials,
+            Arc::new(provider_catalog),
+            Arc::default(),
+        )
     }
 
     fn usage_provider_target(base_url: &str, provider_id: &str) -> UsageProviderTarget {
@@ -12011,6 +12026,7 @@ mod tests {
         );
         let configured_provider = provider("sub2api", ProviderKind::OpenAiBalanceHttpJson);
         let configured_job = ConfiguredRefreshJob {
+            client: Client::new(),
             token: capture_usage_token(&configured_provider, &target),
             provider: configured_provider.clone(),
             target: target.clone(),
@@ -12018,6 +12034,7 @@ mod tests {
             force: false,
         };
         let auto_job = AutoRefreshJob {
+            client: Client::new(),
             token: target.credential.preferred_usage_token(),
             target: target.clone(),
             force: false,
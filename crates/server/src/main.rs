@@ -2,7 +2,7 @@ mod check;
 mod config;
 
 use std::net::IpAddr;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::ExitCode;
 
 use anyhow::{Context, Result};
@@ -42,13 +42,25 @@ struct Cli {
     /// Emit the credential check as stable JSON. Requires --check.
     #[arg(long, requires = "check")]
     json: bool,
+    /// Source this .env file before resolving provider auth env vars (defaults to
+    /// $CODEX_HELPER_HOME/.env when present; process env always wins).
+    #[arg(long, value_name = "PATH")]
+    env_file: Option<PathBuf>,
 }
 
-#[tokio::main]
-async fn main() -> ExitCode {
+fn main() -> ExitCode {
     init_tracing();
     let cli = Cli::parse();
-    match run(cli).await {
+    // The env file changes the process environment, so it is read before any runtime thread
+    // exists.
+    let result = load_env_file(cli.env_file.as_deref()).and_then(|()| {
+        tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .context("create tokio runtime")?
+            .block_on(run(cli))
+    });
+    match result {
         Ok(code) => code,
         Err(error) => {
             eprintln!("Error: {error:#}");
@@ -57,6 +69,18 @@ async fn main() -> ExitCode {
     }
 }
 
+fn load_env_file(explicit: Option<&Path>) -> Result<()> {
+    if let Some(outcome) = codex_helper_core::env_file::load_env_file(explicit)? {
+        tracing::info!(
+            "loaded env file {} ({} set, {} already in process env)",
+            outcome.path.display(),
+            outcome.applied,
+            outcome.kept_from_process
+        );
+    }
+    Ok(())
+}
+
 async fn run(cli: Cli) -> Result<ExitCode> {
     let file_config = load_server_config(cli.config.as_deref())?;
     let effective = EffectiveServerConfig::from_sources(
@@ -111,6 +135,10 @@ async fn run(cli: Cli) -> Result<ExitCode> {
         wait_for_shutdown_signal().await;
         let _ = shutdown_tx.send(true);
     });
+    tokio::spawn(codex_helper_core::proxy::reload_runtime_config_on_sighup(
+        runtime.proxy.clone(),
+        runtime.shutdown_receiver(),
+    ));
 
     tracing::info!(
         "codex-helper server listening on http://{}:{} (service: {})",
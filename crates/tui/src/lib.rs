@@ -1,9 +1,9 @@
 pub use codex_helper_core::{
     codex_capability_profile, codex_integration, config, control_plane_client, credentials,
     dashboard_core, endpoint_health, filter, local_operator, logging, model_routing, notify,
-    policy_actions, pricing, provider_signals, proxy, quota_analytics, quota_pool, relay_target,
-    request_ledger, routing_explain, routing_ir, runtime_identity, service_status, sessions, state,
-    usage, usage_day, usage_format, usage_providers,
+    policy_actions, pricing, privacy, provider_signals, proxy, quota_analytics, quota_pool,
+    relay_target, request_ledger, routing_explain, routing_ir, runtime_identity, service_status,
+    sessions, state, usage, usage_day, usage_format, usage_providers,
 };
 
 pub mod tui;
@@ -10,6 +10,7 @@ use crate::control_plane_client::{
     ControlPlaneClient, ControlPlaneEndpoint, ControlPlaneError, LocalOperatorClient,
 };
 use crate::dashboard_core::{OperatorLocalSessionMetadata, OperatorReadModel};
+use crate::usage_format::DisplayFormat;
 
 use super::fleet_refresh::{
     FleetRefreshResult, FleetRefreshSource, apply_fleet_refresh_result,
@@ -278,6 +279,13 @@ async fn run_attached_dashboard_runtime(
 ) -> anyhow::Result<()> {
     let runtime = Arc::new(runtime);
     let language = resolve_attached_language().await;
+    // Display preferences come from this machine's `[ui]`, whichever runtime is attached.
+    let local_ui = crate::config::load_config()
+        .await
+        .map(|config| config.ui)
+        .unwrap_or_default();
+    let theme = local_ui.tui_theme.unwrap_or_default();
+    let display_format = DisplayFormat::from_ui_config(&local_ui);
     let timing = DashboardTiming::from_env();
 
     let mut providers = Vec::new();
@@ -294,6 +302,8 @@ async fn run_attached_dashboard_runtime(
         service_name,
         proxy_port: port,
         language,
+        theme,
+        display_format,
         runtime_connection: runtime.connection_kind,
         local_operator_transport_available: runtime.operator_client.is_some(),
         toast: Some((start_toast, Instant::now())),
@@ -316,7 +326,6 @@ async fn run_attached_dashboard_runtime(
     let (snapshot_refresh_tx, mut snapshot_refresh_rx) =
         mpsc::unbounded_channel::<AttachedSnapshotRefreshResult>();
     let mut snapshot_refresh = AttachedSnapshotRefreshController::new(snapshot_refresh_tx);
-    let palette = Palette::default();
     let mut render_invalidation = RenderInvalidation::FullClear;
 
     loop {
@@ -325,7 +334,6 @@ async fn run_attached_dashboard_runtime(
             &mut render_invalidation,
             &mut ui,
             &snapshot,
-            palette,
             service_name,
             port,
             &providers,
@@ -558,7 +566,6 @@ fn render_attached_if_needed(
     render_invalidation: &mut RenderInvalidation,
     ui: &mut UiState,
     snapshot: &Snapshot,
-    palette: Palette,
     service_name: &'static str,
     port: u16,
     providers: &[ProviderOption],
@@ -569,6 +576,7 @@ fn render_attached_if_needed(
     if matches!(render_invalidation, RenderInvalidation::FullClear) {
         terminal.clear()?;
     }
+    let palette = Palette::for_theme(ui.theme);
     terminal.draw(|f| {
         super::view::render_app(f, palette, ui, snapshot, service_name, port, providers)
     })?;
@@ -893,6 +901,7 @@ mod tests {
                 usage_rollup: Default::default(),
                 stats_5m: Default::default(),
                 stats_1h: Default::default(),
+                request_quotas: Vec::new(),
                 pricing_catalog: Default::default(),
                 service_status: None,
                 provider_balances: Vec::new(),
@@ -1383,6 +1392,8 @@ mod tests {
             pricing_catalog: Default::default(),
             stats_5m: crate::dashboard_core::WindowStats::default(),
             stats_1h: crate::dashboard_core::WindowStats::default(),
+            request_quotas: Vec::new(),
+            recent_capacity: 0,
             service_status: None,
             refreshed_at: Instant::now(),
         }
@@ -1,3 +1,5 @@
+use crate::config::TuiTheme;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Language {
     Zh,
@@ -44,6 +46,7 @@ macro_rules! define_messages {
 
 define_messages! {
     LANGUAGE_LABEL => { zh: "语言：", en: "language: " },
+    THEME_LABEL => { zh: "主题：", en: "theme: " },
 
     LANGUAGE_NAME_ZH => { zh: "中文", en: "Chinese" },
     LANGUAGE_NAME_EN => { zh: "English", en: "English" },
@@ -194,6 +197,7 @@ fn zh_label(en: &'static str) -> Option<&'static str> {
         "Requests" => "请求",
         "Requests page" => "请求页",
         "Retry / route chain" => "重试 / 路由链",
+        "Upstream error body" => "上游错误响应体",
         "Routing" => "路由",
         "Routing page" => "路由页",
         "Route" => "路由",
@@ -424,6 +428,8 @@ fn zh_label(en: &'static str) -> Option<&'static str> {
         "requests scope" => "requests 范围",
         "requests: cleared explicit session focus" => "requests: 已清除显式会话聚焦",
         "requests: focused session" => "requests: 已聚焦会话",
+        "requests: last error" => "requests: 最近错误",
+        "requests: no recent errors" => "requests: 最近没有出错的请求",
         "requests: no selection" => "requests: 未选择条目",
         "requests: selected request has no session id" => "requests: 所选请求没有 session id",
         "request payload" => "请求负载",
@@ -470,6 +476,8 @@ fn zh_label(en: &'static str) -> Option<&'static str> {
         "stale" => "过期",
         "provider mapping" => "提供商映射",
         "request id" => "请求 ID",
+        "body hash" => "请求体哈希",
+        "upstream request id" => "上游请求 ID",
         "status" => "状态",
         "strategy" => "策略",
         "stream" => "流式",
@@ -580,6 +588,31 @@ pub(crate) fn format_language_changed(current_lang: Language, selected_lang: Lan
     }
 }
 
+pub(crate) fn format_theme_saved(lang: Language, theme: TuiTheme) -> String {
+    match lang {
+        Language::Zh => format!("主题：{}（已保存）", theme.as_str()),
+        Language::En => format!("theme: {} (saved)", theme.as_str()),
+    }
+}
+
+pub(crate) fn format_theme_save_failed(
+    lang: Language,
+    theme: TuiTheme,
+    err: &dyn std::fmt::Display,
+) -> String {
+    match lang {
+        Language::Zh => format!("主题：{}（保存失败：{err}）", theme.as_str()),
+        Language::En => format!("theme: {} (save failed: {err})", theme.as_str()),
+    }
+}
+
+pub(crate) fn format_theme_changed(lang: Language, theme: TuiTheme) -> String {
+    match lang {
+        Language::Zh => format!("主题：{}（仅当前 TUI 会话）", theme.as_str()),
+        Language::En => format!("theme: {} (current TUI session only)", theme.as_str()),
+    }
+}
+
 pub(crate) fn format_history_loaded(lang: Language, count: usize) -> String {
     match lang {
         Language::Zh => format!("history: 已加载 {count} 个会话"),
@@ -14,6 +14,7 @@ use super::model::{ProviderOption, Snapshot};
 use super::operator_actions::{queue_routing_mutation, queue_session_affinity_mutation};
 use super::state::UiState;
 use super::types::{Overlay, RoutingActionChoice};
+use crate::config::TuiTheme;
 pub(in crate::tui) use normal::routing_mutation_request;
 use normal::{apply_page_shortcuts, handle_key_normal, toggle_language};
 use transcript::handle_key_session_transcript;
@@ -143,6 +144,20 @@ pub(in crate::tui) fn handle_routing_actions_key(
                     }
                     OperatorRoutingCommand::ClearNewSessionPreference
                 }
+                RoutingActionChoice::ClearUpstreamPin => {
+                    if routing.upstream_pin.is_none() {
+                        ui.overlay = Overlay::None;
+                        ui.toast = Some((
+                            match ui.language {
+                                super::Language::Zh => "当前没有全局上游固定".to_string(),
+                                super::Language::En => "no global upstream pin is set".to_string(),
+                            },
+                            std::time::Instant::now(),
+                        ));
+                        return true;
+                    }
+                    OperatorRoutingCommand::ClearUpstreamPin { session_key: None }
+                }
                 action => {
                     let Some(candidate) = ui.selected_routing_candidate(snapshot) else {
                         ui.overlay = Overlay::None;
@@ -176,7 +191,14 @@ pub(in crate::tui) fn handle_routing_actions_key(
                                 mode: OperatorEndpointMode::Disabled,
                             }
                         }
-                        RoutingActionChoice::ClearNewSessionPreference => {
+                        RoutingActionChoice::PinUpstream => OperatorRoutingCommand::PinUpstream {
+                            provider_id: candidate.provider_id.clone(),
+                            endpoint_id: candidate.endpoint_id.clone(),
+                            session_key: None,
+                            failover: true,
+                        },
+                        RoutingActionChoice::ClearNewSessionPreference
+                        | RoutingActionChoice::ClearUpstreamPin => {
                             ui.overlay = Overlay::None;
                             return true;
                         }
@@ -445,12 +467,48 @@ async fn persist_host_local_language_change(ui: &mut UiState, previous: super::L
     .await;
 }
 
+async fn persist_host_local_theme_change_with<F, Fut>(
+    ui: &mut UiState,
+    previous: TuiTheme,
+    persist: F,
+) where
+    F: FnOnce(TuiTheme) -> Fut,
+    Fut: std::future::Future<Output = anyhow::Result<()>>,
+{
+    if ui.theme == previous || ui.runtime_connection.is_remote_observer() {
+        return;
+    }
+
+    let selected = ui.theme;
+    let result = persist(selected).await;
+    ui.toast = Some((
+        match result {
+            Ok(_) => super::i18n::format_theme_saved(ui.language, selected),
+            Err(error) => super::i18n::format_theme_save_failed(ui.language, selected, &error),
+        },
+        std::time::Instant::now(),
+    ));
+}
+
+async fn persist_host_local_theme_change(ui: &mut UiState, previous: TuiTheme) {
+    persist_host_local_theme_change_with(ui, previous, |selected| async move {
+        crate::config::mutate_helper_config(|config| {
+            config.ui.tui_theme = Some(selected);
+            Ok(())
+        })
+        .await
+        .map(|_| ())
+    })
+    .await;
+}
+
 pub(in crate::tui) async fn handle_key_event(ctx: KeyEventContext<'_>, key: KeyEvent) -> bool {
     if ctx.ui.overlay == Overlay::None && apply_page_shortcuts(ctx.ui, key.code) {
         return true;
     }
 
     let previous_language = ctx.ui.language;
+    let previous_theme = ctx.ui.theme;
     let handled = match ctx.ui.overlay {
         Overlay::None => {
             handle_key_normal(
@@ -541,6 +599,7 @@ pub(in crate::tui) async fn handle_key_event(ctx: KeyEventContext<'_>, key: KeyE
     };
     if handled {
         persist_host_local_language_change(ctx.ui, previous_language).await;
+        persist_host_local_theme_change(ctx.ui, previous_theme).await;
     }
     handled
 }
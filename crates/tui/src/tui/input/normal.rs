@@ -1,9 +1,11 @@
 use std::time::Instant;
 
+use codex_helper_core::active_history::ActiveChangeSource;
 use codex_helper_core::codex_switch::{self, CodexSwitchIntent, ValidatedCodexBaseUrl};
 use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
 
 use crate::config::{CodexClientPatchConfig, CodexClientPreset, load_config, proxy_home_dir};
+use crate::dashboard_core::OperatorRequestSummary;
 use crate::proxy::{
     OperatorRoutingCommand, OperatorRoutingMutationRequest, OperatorSessionBindingCommand,
     OperatorSessionBindingMutationRequest,
@@ -13,12 +15,12 @@ use crate::tui::Language;
 use crate::tui::i18n::{self, msg};
 use crate::tui::model::{
     CODEX_RECENT_WINDOWS, Snapshot, codex_recent_window_label, codex_recent_window_threshold_ms,
-    filtered_requests_len, find_session_idx, now_ms, short_sid,
+    filtered_requests_len, find_session_idx, now_ms, recent_error_requests, short_sid,
 };
 use crate::tui::report::build_stats_report;
 use crate::tui::state::{
-    CodexHistoryExternalFocusOrigin, FleetViewMode, SessionBindingEditContext, UiState,
-    adjust_table_selection,
+    CodexHistoryExternalFocusOrigin, FleetViewMode, RequestControlFilter,
+    SessionBindingEditContext, UiState, adjust_table_selection,
 };
 use crate::tui::types::{
     Focus, Overlay, Page, SessionBindingInputKind, SessionEffortChoice, SessionServiceTierChoice,
@@ -132,7 +134,6 @@ pub(in crate::tui) async fn apply_codex_switch(
 }
 
 pub(super) fn apply_page_shortcuts(ui: &mut UiState, code: KeyCode) -> bool {
-    let previous_page = ui.page;
     let page = match code {
         KeyCode::Char('1') => Some(Page::Dashboard),
         KeyCode::Char('2') => Some(Page::Routing),
@@ -146,51 +147,56 @@ pub(super) fn apply_page_shortcuts(ui: &mut UiState, code: KeyCode) -> bool {
         KeyCode::Char('0') => Some(Page::Fleet),
         _ => None,
     };
-    if let Some(p) = page {
-        ui.page = p;
-        if previous_page != p {
-            ui.help_scroll = 0;
-        }
-        if previous_page == Page::Stats || ui.page == Page::Stats || ui.page == Page::ServiceStatus
-        {
-            ui.needs_snapshot_refresh = true;
-        }
-        if ui.page == Page::Routing {
-            ui.focus = Focus::Providers;
-            if previous_page != Page::Routing {
-                ui.routing_detail_focused = false;
-                ui.routing_detail_scroll = 0;
-                let _ = queue_balance_refresh(ui, false, false);
-            }
-        } else if ui.page == Page::Requests {
-            ui.focus = Focus::Requests;
-        } else if ui.page == Page::Dashboard && previous_page != Page::Dashboard {
-            ui.dashboard_details_scroll = 0;
-        } else if ui.page == Page::Sessions
-            || ui.page == Page::History
-            || ui.page == Page::Recent
-            || (ui.page == Page::Dashboard && ui.focus == Focus::Providers)
-        {
-            ui.focus = Focus::Sessions;
-        }
-        if ui.page == Page::History {
-            ui.needs_codex_history_refresh = true;
-            ui.codex_history_details_scroll = 0;
-            ui.sync_codex_history_selection();
-        }
-        if ui.page == Page::Recent {
-            ui.needs_codex_recent_refresh = true;
-            ui.codex_recent_details_scroll = 0;
-            ui.sync_codex_recent_selection(now_ms());
-        }
-        if ui.page == Page::Fleet {
-            ui.focus = Focus::Providers;
-            ui.needs_fleet_refresh = true;
-            ui.sync_fleet_selection();
-        }
-        return true;
+    let Some(page) = page else {
+        return false;
+    };
+    switch_page(ui, page);
+    true
+}
+
+/// Shows `p` and resets the focus, scroll and refresh state that page expects on entry.
+fn switch_page(ui: &mut UiState, p: Page) {
+    let previous_page = ui.page;
+    ui.page = p;
+    if previous_page != p {
+        ui.help_scroll = 0;
+    }
+    if previous_page == Page::Stats || ui.page == Page::Stats || ui.page == Page::ServiceStatus {
+        ui.needs_snapshot_refresh = true;
+    }
+    if ui.page == Page::Routing {
+        ui.focus = Focus::Providers;
+        if previous_page != Page::Routing {
+            ui.routing_detail_focused = false;
+            ui.routing_detail_scroll = 0;
+            let _ = queue_balance_refresh(ui, false, false);
+        }
+    } else if ui.page == Page::Requests {
+        ui.focus = Focus::Requests;
+    } else if ui.page == Page::Dashboard && previous_page != Page::Dashboard {
+        ui.dashboard_details_scroll = 0;
+    } else if ui.page == Page::Sessions
+        || ui.page == Page::History
+        || ui.page == Page::Recent
+        || (ui.page == Page::Dashboard && ui.focus == Focus::Providers)
+    {
+        ui.focus = Focus::Sessions;
+    }
+    if ui.page == Page::History {
+        ui.needs_codex_history_refresh = true;
+        ui.codex_history_details_scroll = 0;
+        ui.sync_codex_history_selection();
+    }
+    if ui.page == Page::Recent {
+        ui.needs_codex_recent_refresh = true;
+        ui.codex_recent_details_scroll = 0;
+        ui.sync_codex_recent_selection(now_ms());
+    }
+    if ui.page == Page::Fleet {
+        ui.focus = Focus::Providers;
+        ui.needs_fleet_refresh = true;
+        ui.sync_fleet_selection();
     }
-    false
 }
 
 pub(in crate::tui) fn handle_routing_operator_key(
@@ -571,6 +577,7 @@ pub(in crate::tui) fn routing_mutation_request(
         expected_control_revision: routing.control_revision,
         expected_policy_revision: routing.provider_policy_revision,
         command,
+        source: Some(ActiveChangeSource::Tui),
     }
 }
 
@@ -693,6 +700,61 @@ fn clear_request_page_focus(ui: &mut UiState) {
     ui.selected_request_page_id = None;
 }
 
+/// Opens Requests on the newest request with an error status, so its detail pane shows the
+/// retry / route chain. Session scope and control filters are dropped only when they hide it.
+fn select_last_error_request(ui: &mut UiState, snapshot: &Snapshot) {
+    let Some(request) = recent_error_requests(snapshot).next() else {
+        ui.toast = Some((
+            i18n::label(ui.language, "requests: no recent errors").to_string(),
+            Instant::now(),
+        ));
+        return;
+    };
+    if ui.page != Page::Requests {
+        switch_page(ui, Page::Requests);
+    }
+    ui.last_error_body = load_upstream_error_body(ui, request).map(|body| (request.id, body));
+    let visible_index = |ui: &UiState| {
+        ui.request_page_filtered_indices(snapshot)
+            .iter()
+            .position(|idx| snapshot.recent.get(*idx).map(|r| r.id) == Some(request.id))
+    };
+    let index = match visible_index(ui) {
+        Some(index) => index,
+        None => {
+            ui.request_page_scope_session = false;
+            ui.request_page_control_filter = RequestControlFilter::All;
+            clear_request_page_focus(ui);
+            visible_index(ui).unwrap_or(0)
+        }
+    };
+    ui.select_request_page_index(snapshot, index);
+    ui.toast = Some((
+        format!(
+            "{}: HTTP {}",
+            i18n::label(ui.language, "requests: last error"),
+            request.status_code
+        ),
+        Instant::now(),
+    ));
+}
+
+/// The captured upstream error body, read from the local request log only while HTTP debug
+/// bodies are on; without them nothing is captured to show. Request ids are per runtime, so a
+/// remote runtime's request must not be looked up in this host's log.
+fn load_upstream_error_body(ui: &UiState, request: &OperatorRequestSummary) -> Option<String> {
+    if ui.runtime_connection.is_remote_observer() || !crate::logging::http_debug_options().enabled {
+        return None;
+    }
+    crate::usage::request_log::read_upstream_error_body(
+        &crate::logging::request_log_path(),
+        &request.service,
+        request.id,
+    )
+    .ok()
+    .flatten()
+}
+
 fn move_fleet_selection(ui: &mut UiState, delta: i32) -> bool {
     let Some(snapshot) = ui.fleet_snapshot.as_ref() else {
         return false;
@@ -749,6 +811,14 @@ pub(super) fn toggle_language(ui: &mut UiState) {
     ));
 }
 
+pub(super) fn cycle_theme(ui: &mut UiState) {
+    ui.theme = ui.theme.next();
+    ui.toast = Some((
+        i18n::format_theme_changed(ui.language, ui.theme),
+        Instant::now(),
+    ));
+}
+
 fn reports_dir() -> std::path::PathBuf {
     proxy_home_dir().join("reports")
 }
@@ -1054,11 +1124,19 @@ pub(super) async fn handle_key_normal(ctx: KeyEventContext<'_>, key: KeyEvent) -
             toggle_language(ui);
             true
         }
+        KeyCode::Char('T') => {
+            cycle_theme(ui);
+            true
+        }
         KeyCode::Char('?') => {
             ui.overlay = Overlay::Help;
             ui.help_scroll = 0;
             true
         }
+        KeyCode::Char('!') => {
+            select_last_error_request(ui, snapshot);
+            true
+        }
         KeyCode::Char('i') if ui.page == Page::Routing => {
             ui.sync_selected_provider_from_routing(snapshot, providers);
             super::open_provider_info(ui);
@@ -1319,6 +1397,21 @@ pub(super) async fn handle_key_normal(ctx: KeyEventContext<'_>, key: KeyEvent) -
             true
         }
         KeyCode::Char('y') if ui.page == Page::Stats => export_selected_stats_report(ui, snapshot),
+        KeyCode::Char('w') if ui.page == Page::Stats => {
+            ui.stats_latency_window = ui.stats_latency_window.next();
+            ui.toast = Some((
+                format!(
+                    "{}: {}",
+                    match ui.language {
+                        Language::Zh => "延迟分布窗口",
+                        Language::En => "latency window",
+                    },
+                    ui.stats_latency_window.label(ui.language)
+                ),
+                Instant::now(),
+            ));
+            true
+        }
         KeyCode::PageUp if ui.page == Page::Dashboard => {
             ui.dashboard_details_scroll = ui.dashboard_details_scroll.saturating_sub(8);
             true
@@ -2367,6 +2460,73 @@ pub(super) async fn handle_key_normal(ctx: KeyEventContext<'_>, key: KeyEvent) -
             ));
             true
         }
+        KeyCode::Char('t') if ui.page == Page::Requests => {
+            let Some(request) = selected_request_page_request(snapshot, ui).cloned() else {
+                ui.toast = Some((
+                    i18n::label(ui.language, "requests: no selection").to_string(),
+                    Instant::now(),
+                ));
+                return true;
+            };
+            let Some(opaque_sid) = request.session_key.as_deref() else {
+                ui.toast = Some((
+                    i18n::label(ui.language, "requests: selected request has no session id")
+                        .to_string(),
+                    Instant::now(),
+                ));
+                return true;
+            };
+            let Some((local_sid, cwd)) = local_session_context_for_opaque_key(snapshot, opaque_sid)
+            else {
+                ui.toast = Some((
+                    i18n::label(
+                        ui.language,
+                        "requests: selected request has no local session id",
+                    )
+                    .to_string(),
+                    Instant::now(),
+                ));
+                return true;
+            };
+            match find_codex_session_file_by_id(&local_sid).await {
+                Ok(Some(path)) => {
+                    // Land on History with the session selected (inserted if the list has not
+                    // loaded it yet), so closing the transcript leaves the user next to it.
+                    let summary = request_history_summary_from_request(
+                        &request,
+                        &local_sid,
+                        cwd,
+                        Some(path.clone()),
+                    );
+                    prepare_select_history_from_external(
+                        ui,
+                        summary,
+                        CodexHistoryExternalFocusOrigin::Requests,
+                    );
+                    open_session_transcript_from_path(ui, local_sid, &path, Some(80)).await;
+                }
+                Ok(None) => {
+                    ui.toast = Some((
+                        i18n::label(
+                            ui.language,
+                            "no Codex session file found for this session id",
+                        )
+                        .to_string(),
+                        Instant::now(),
+                    ));
+                }
+                Err(e) => {
+                    ui.toast = Some((
+                        format!(
+                            "{}: {e}",
+                            i18n::label(ui.language, "failed to load transcript")
+                        ),
+                        Instant::now(),
+                    ));
+                }
+            }
+            true
+        }
         KeyCode::Up | KeyCode::Char('k') if ui.page == Page::Requests => {
             let filtered_len = ui.request_page_filtered_indices(snapshot).len();
             if let Some(next) = adjust_table_selection(&mut ui.request_page_table, -1, filtered_len)
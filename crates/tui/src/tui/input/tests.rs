@@ -7,7 +7,11 @@ use codex_helper_core::service_status::{
     ServiceStatusSnapshot,
 };
 
-use super::{KeyEventContext, handle_key_event, persist_host_local_language_change_with};
+use super::{
+    KeyEventContext, handle_key_event, persist_host_local_language_change_with,
+    persist_host_local_theme_change_with,
+};
+use crate::config::TuiTheme;
 use crate::dashboard_core::{
     ControlProfileOption, OperatorActionCapabilities, OperatorReadIssue, OperatorReadModel,
     OperatorReadStatus, OperatorRouteCandidateSummary, OperatorRouteTargetSummary,
@@ -20,7 +24,7 @@ use crate::proxy::{
 use crate::state::SessionObservationScope;
 use crate::tui::Language;
 use crate::tui::input::normal::{
-    accepts_codex_switch_key, codex_client_preset_for_key, codex_switch_intent_for_key,
+    accepts_codex_switch_key, codex_client_preset_for_key, codex_switch_intent_for_key, cycle_theme,
 };
 use crate::tui::model::{ProviderOption, SessionRouteAffinityView, SessionRow, Snapshot};
 use crate::tui::operator_actions::PendingOperatorAction;
@@ -54,10 +58,14 @@ fn routing_snapshot() -> Snapshot {
             entry_strategy: crate::config::RouteStrategy::RoundRobin,
             entry_target: None,
             new_session_preference: None,
+            auto_active_by_health: false,
+            auto_active: None,
             affinity_policy: crate::config::RouteAffinityPolicy::FallbackSticky,
             scheduling_preset: crate::config::SchedulingPreset::Balanced,
             fallback_ttl_ms: Some(60_000),
             reprobe_preferred_after_ms: Some(5_000),
+            upstream_pin: None,
+            session_upstream_pins: Vec::new(),
             candidates: vec![OperatorRouteCandidateSummary {
                 route_order: 0,
                 provider_id: "input".to_string(),
@@ -465,6 +473,123 @@ async fn local_history_and_recent_bridge_shortcuts_keep_their_navigation() {
     }
 }
 
+#[tokio::test]
+async fn requests_transcript_shortcut_reports_a_missing_local_session_file() {
+    let mut snapshot = bridge_snapshot();
+    snapshot.recent.push(
+        serde_json::from_value(serde_json::json!({
+            "id": 7,
+            "observability": {
+                "attempt_count": 1,
+                "route_attempt_count": 0,
+                "retried": false,
+                "cross_provider_failover": false,
+                "same_provider_retry": false,
+                "fast_mode": false,
+                "streaming": true
+            },
+            "service": "codex",
+            "method": "POST",
+            "path": "/v1/responses",
+            "status_code": 200,
+            "duration_ms": 12,
+            "streaming": true,
+            "ended_at_ms": 1,
+            "session_key": "session:sha256:test"
+        }))
+        .expect("request fixture"),
+    );
+    let mut ui = UiState {
+        page: Page::Requests,
+        language: Language::En,
+        runtime_connection: RuntimeConnectionKind::Integrated,
+        local_operator_transport_available: true,
+        ..UiState::default()
+    };
+
+    assert!(press(&mut ui, &snapshot, KeyCode::Char('t')).await);
+
+    assert_eq!(ui.page, Page::Requests);
+    assert_ne!(ui.overlay, Overlay::SessionTranscript);
+    assert!(
+        ui.toast
+            .as_ref()
+            .is_some_and(|(message, _)| message.contains("no Codex session file found")),
+        "toast={:?}",
+        ui.toast
+    );
+}
+
+#[tokio::test]
+async fn last_error_shortcut_selects_the_newest_failed_request_on_requests() {
+    let request = |id: u64, status_code: u16, session_key: &str| {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "observability": {
+                "attempt_count": 1,
+                "route_attempt_count": 0,
+                "retried": false,
+                "cross_provider_failover": false,
+                "same_provider_retry": false,
+                "fast_mode": false,
+                "streaming": true
+            },
+            "service": "codex",
+            "method": "POST",
+            "path": "/v1/responses",
+            "status_code": status_code,
+            "duration_ms": 12,
+            "streaming": true,
+            "ended_at_ms": id,
+            "session_key": session_key
+        }))
+        .expect("request fixture")
+    };
+    let mut snapshot = Snapshot {
+        recent: vec![
+            request(9, 200, "session:a"),
+            request(8, 502, "session:b"),
+            request(7, 429, "session:a"),
+        ],
+        ..Snapshot::default()
+    };
+    let mut ui = UiState {
+        page: Page::Dashboard,
+        language: Language::En,
+        request_page_scope_session: true,
+        focused_request_session_id: Some("session:a".to_string()),
+        ..UiState::default()
+    };
+
+    assert!(press(&mut ui, &snapshot, KeyCode::Char('!')).await);
+
+    assert_eq!(ui.page, Page::Requests);
+    assert_eq!(ui.focus, Focus::Requests);
+    assert_eq!(ui.selected_request_page_id, Some(8));
+    assert!(
+        !ui.request_page_scope_session,
+        "the session scope hid the error, so it is dropped"
+    );
+    assert!(
+        ui.toast
+            .as_ref()
+            .is_some_and(|(message, _)| message.contains("HTTP 502")),
+        "toast={:?}",
+        ui.toast
+    );
+
+    snapshot.recent.retain(|request| request.status_code < 400);
+    ui.toast = None;
+    assert!(press(&mut ui, &snapshot, KeyCode::Char('!')).await);
+    assert!(
+        ui.toast
+            .as_ref()
+            .is_some_and(|(message, _)| message.contains("no recent errors")),
+        "toast={:?}",
+        ui.toast
+    );
+}
+
 #[tokio::test]
 async fn history_and_recent_detail_scroll_keys_use_independent_offsets() {
     let snapshot = Snapshot::default();
@@ -1304,6 +1429,41 @@ async fn integrated_language_save_failure_keeps_selected_language_visible() {
     );
 }
 
+#[tokio::test]
+async fn theme_cycle_persists_locally_but_not_for_remote_observers() {
+    let mut ui = UiState::default();
+    cycle_theme(&mut ui);
+    assert_eq!(ui.theme, TuiTheme::Dark);
+    let mut persisted = None;
+    persist_host_local_theme_change_with(&mut ui, TuiTheme::Auto, |theme| {
+        persisted = Some(theme);
+        async { Ok(()) }
+    })
+    .await;
+    assert_eq!(persisted, Some(TuiTheme::Dark));
+    assert!(
+        ui.toast
+            .as_ref()
+            .is_some_and(|(message, _)| message == "theme: dark (saved)"),
+        "{:?}",
+        ui.toast
+    );
+
+    let mut remote = UiState {
+        runtime_connection: RuntimeConnectionKind::RemoteObserver,
+        theme: TuiTheme::Light,
+        ..UiState::default()
+    };
+    let mut remote_persisted = None;
+    persist_host_local_theme_change_with(&mut remote, TuiTheme::Dark, |theme| {
+        remote_persisted = Some(theme);
+        async { Ok(()) }
+    })
+    .await;
+    assert_eq!(remote_persisted, None);
+    assert_eq!(remote.theme, TuiTheme::Light);
+}
+
 #[tokio::test]
 async fn session_profile_binding_uses_the_visible_revision_and_typed_command() {
     let snapshot = affinity_snapshot(0, Some("affinity:v1:current"));
@@ -6,6 +6,7 @@ use std::time::Duration;
 use futures_util::StreamExt;
 use tokio::time::Instant;
 
+use crate::config::PrivacyConfig;
 use crate::control_plane_client::LocalOperatorClient;
 use crate::dashboard_core::{
     LocalOperatorSessionMetadataResponse, OperatorLocalSessionMetadata, OperatorReadModel,
@@ -357,12 +358,21 @@ impl AttachedLocalSessionEnrichment {
     }
 }
 
+/// `[privacy]` of this machine's config, which governs the session files read from its disk.
+pub(super) async fn local_privacy_config() -> PrivacyConfig {
+    crate::config::load_config()
+        .await
+        .map(|config| config.privacy)
+        .unwrap_or_default()
+}
+
 async fn locate_host_sessions(
     raw_session_ids: Vec<String>,
 ) -> Result<HashMap<String, HostSessionLocation>, ()> {
     let found = crate::sessions::find_codex_session_files_by_ids(&raw_session_ids)
         .await
         .map_err(|_| ())?;
+    let privacy = local_privacy_config().await;
 
     let resolved = futures_util::stream::iter(found)
         .map(|(raw_session_id, path)| async move {
@@ -371,6 +381,7 @@ async fn locate_host_sessions(
                 .ok()
                 .flatten()
                 .and_then(|metadata| metadata.cwd);
+            let cwd = privacy.apply_to_cwd(cwd);
             (
                 raw_session_id,
                 HostSessionLocation {
@@ -519,6 +530,7 @@ mod tests {
             quota_analytics: Default::default(),
             stats_5m: Default::default(),
             stats_1h: Default::default(),
+            request_quotas: Vec::new(),
             pricing_catalog: Default::default(),
             service_status: None,
             provider_balances: Vec::new(),
@@ -41,6 +41,7 @@ use crate::codex_integration::CodexStartupReadiness;
 use crate::config::HelperConfig;
 use crate::proxy::ProxyService;
 use crate::state::ProxyState;
+use crate::usage_format::DisplayFormat;
 
 use self::fleet_refresh::{
     FleetRefreshResult, FleetRefreshSource, apply_fleet_refresh_result,
@@ -173,7 +174,6 @@ fn render_dashboard_if_needed(
     last_drawn_page: &mut types::Page,
     ui: &mut UiState,
     snapshot: &Snapshot,
-    palette: Palette,
     service_name: &'static str,
     port: u16,
     providers: &[ProviderOption],
@@ -193,6 +193,7 @@ fn render_dashboard_if_needed(
     if matches!(render_invalidation, RenderInvalidation::FullClear) {
         terminal.clear()?;
     }
+    let palette = Palette::for_theme(ui.theme);
     terminal.draw(|f| view::render_app(f, palette, ui, snapshot, service_name, port, providers))?;
     *render_invalidation = RenderInvalidation::None;
     Ok(())
@@ -243,6 +244,9 @@ pub async fn run_dashboard(
         service_name,
         proxy_port: port,
         language,
+        theme: cfg.ui.tui_theme.unwrap_or_default(),
+        display_format: DisplayFormat::from_ui_config(&cfg.ui),
+        request_log_sample_rate: cfg.logging.active_sample_rate(),
         overlay: if show_startup_alert {
             types::Overlay::StartupAlert
         } else {
@@ -251,8 +255,6 @@ pub async fn run_dashboard(
         startup_readiness,
         ..Default::default()
     };
-    let palette = Palette::default();
-
     let mut events = EventStream::new();
     let mut ticker = tokio::time::interval(Duration::from_millis(timing.refresh_ms));
     ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
@@ -302,7 +304,6 @@ pub async fn run_dashboard(
             &mut last_drawn_page,
             &mut ui,
             &snapshot,
-            palette,
             service_name,
             port,
             &providers,
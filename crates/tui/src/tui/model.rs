@@ -8,6 +8,7 @@ use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 use codex_helper_core::balance::{ProviderUsageAlertKind, ProviderUsageWindow};
 
+use crate::config::TuiTheme;
 use crate::dashboard_core::{
     OperatorLocalSessionMetadata, OperatorProviderBalanceSummary, OperatorProviderEndpointSummary,
     OperatorProviderSummary, OperatorReadData, OperatorRequestSummary, OperatorRoutingSummary,
@@ -19,13 +20,14 @@ use crate::runtime_identity::ProviderEndpointKey;
 #[cfg(test)]
 use crate::state::SessionIdentityCard;
 use crate::state::{
-    BalanceSnapshotStatus, ProviderBalanceSnapshot, ResolvedRouteValue, RouteDecisionProvenance,
-    SessionObservationScope, UsageDayView, UsageRollupView,
+    BalanceSnapshotStatus, ProviderBalanceSnapshot, RequestQuotaUsage, ResolvedRouteValue,
+    RouteDecisionProvenance, SessionObservationScope, UsageDayView, UsageRollupView,
 };
 use crate::tui::Language;
 use crate::tui::i18n;
 use crate::tui::state::RequestControlFilter;
 use crate::usage::UsageMetrics;
+use crate::usage_format::DisplayFormat;
 
 pub type UpstreamSummary = OperatorProviderEndpointSummary;
 pub type ProviderOption = OperatorProviderSummary;
@@ -185,6 +187,9 @@ pub(in crate::tui) struct Snapshot {
     pub(in crate::tui) pricing_catalog: ModelPriceCatalogSnapshot,
     pub(in crate::tui) stats_5m: WindowStats,
     pub(in crate::tui) stats_1h: WindowStats,
+    pub(in crate::tui) request_quotas: Vec<RequestQuotaUsage>,
+    /// Runtime recent-request buffer size; 0 when the runtime does not report it.
+    pub(in crate::tui) recent_capacity: usize,
     pub(in crate::tui) service_status: Option<crate::service_status::ServiceStatusSnapshot>,
     pub(in crate::tui) refreshed_at: Instant,
 }
@@ -203,6 +208,8 @@ impl Default for Snapshot {
             pricing_catalog: ModelPriceCatalogSnapshot::default(),
             stats_5m: WindowStats::default(),
             stats_1h: WindowStats::default(),
+            request_quotas: Vec::new(),
+            recent_capacity: 0,
             service_status: None,
             refreshed_at: Instant::now(),
         }
@@ -219,6 +226,19 @@ pub(in crate::tui) fn latest_provider_balance_fetched_at_ms(snapshot: &Snapshot)
         .max()
 }
 
+/// How many of the newest requests the header error badge counts and `!` searches.
+pub(in crate::tui) const RECENT_ERROR_WINDOW: usize = 80;
+
+pub(in crate::tui) fn recent_error_requests(
+    snapshot: &Snapshot,
+) -> impl Iterator<Item = &OperatorRequestSummary> {
+    snapshot
+        .recent
+        .iter()
+        .take(RECENT_ERROR_WINDOW)
+        .filter(|request| request.status_code >= 400)
+}
+
 pub(in crate::tui) fn request_attempt_count(request: &OperatorRequestSummary) -> u32 {
     let retry_attempts = request
         .retry
@@ -252,10 +272,25 @@ pub(in crate::tui) struct Palette {
     pub(in crate::tui) good: Color,
     pub(in crate::tui) warn: Color,
     pub(in crate::tui) bad: Color,
+    pub(in crate::tui) highlight: Color,
 }
 
 impl Default for Palette {
     fn default() -> Self {
+        Self::dark()
+    }
+}
+
+impl Palette {
+    pub(in crate::tui) fn for_theme(theme: TuiTheme) -> Self {
+        match theme.resolve() {
+            TuiTheme::Light => Self::light(),
+            TuiTheme::HighContrast => Self::high_contrast(),
+            TuiTheme::Auto | TuiTheme::Dark => Self::dark(),
+        }
+    }
+
+    fn dark() -> Self {
         Self {
             bg: Color::Rgb(14, 17, 22),
             panel: Color::Rgb(18, 22, 28),
@@ -267,6 +302,40 @@ impl Default for Palette {
             good: Color::Rgb(63, 185, 80),
             warn: Color::Rgb(210, 153, 34),
             bad: Color::Rgb(248, 81, 73),
+            highlight: Color::Rgb(32, 39, 48),
+        }
+    }
+
+    fn light() -> Self {
+        Self {
+            bg: Color::Rgb(255, 255, 255),
+            panel: Color::Rgb(246, 248, 250),
+            border: Color::Rgb(175, 184, 193),
+            text: Color::Rgb(31, 35, 40),
+            muted: Color::Rgb(89, 99, 110),
+            accent: Color::Rgb(9, 105, 218),
+            focus: Color::Rgb(5, 80, 174),
+            good: Color::Rgb(26, 127, 55),
+            warn: Color::Rgb(154, 103, 0),
+            bad: Color::Rgb(207, 34, 46),
+            highlight: Color::Rgb(221, 234, 247),
+        }
+    }
+
+    /// Plain ANSI colors so terminal color schemes and accessibility settings still apply.
+    fn high_contrast() -> Self {
+        Self {
+            bg: Color::Black,
+            panel: Color::Black,
+            border: Color::White,
+            text: Color::White,
+            muted: Color::Gray,
+            accent: Color::LightCyan,
+            focus: Color::LightYellow,
+            good: Color::LightGreen,
+            warn: Color::Yellow,
+            bad: Color::LightRed,
+            highlight: Color::Blue,
         }
     }
 }
@@ -1574,16 +1643,17 @@ pub(in crate::tui) fn duration_short(ms: u64) -> String {
     }
 }
 
-pub(in crate::tui) fn tokens_short(n: i64) -> String {
-    crate::usage_format::tokens_short(n)
-}
-
 pub(in crate::tui) fn format_tok_per_second(value: Option<f64>) -> String {
     crate::usage_format::tokens_per_second(value)
 }
 
-pub(in crate::tui) fn usage_line_lang(usage: &UsageMetrics, lang: Language) -> String {
+pub(in crate::tui) fn usage_line_lang(
+    format: &DisplayFormat,
+    usage: &UsageMetrics,
+    lang: Language,
+) -> String {
     crate::usage_format::usage_line_with_labels(
+        format,
         usage,
         i18n::label(lang, "tok in/out/rsn/ttl"),
         i18n::label(lang, "cache read/create"),
@@ -1854,6 +1924,8 @@ pub(in crate::tui) fn snapshot_from_operator_data(
         pricing_catalog: data.pricing_catalog.clone(),
         stats_5m: data.stats_5m.clone(),
         stats_1h: data.stats_1h.clone(),
+        request_quotas: data.request_quotas.clone(),
+        recent_capacity: data.summary.counts.recent_requests_capacity,
         service_status: data.service_status.clone(),
         refreshed_at: Instant::now(),
     }
@@ -2333,6 +2405,8 @@ mod tests {
             id,
             trace_key: None,
             session_key: session_id.map(ToOwned::to_owned),
+            body_hash: None,
+            upstream_request_id: None,
             model: None,
             reasoning_effort: None,
             service_tier: None,
@@ -2443,6 +2517,8 @@ mod tests {
             id: 7,
             trace_key: None,
             session_key: Some(session_key.to_string()),
+            body_hash: None,
+            upstream_request_id: None,
             model: Some("gpt-5.6".to_string()),
             reasoning_effort: Some("high".to_string()),
             service_tier: Some("priority".to_string()),
@@ -2528,6 +2604,7 @@ mod tests {
             usage_rollup: UsageRollupView::default(),
             stats_5m: WindowStats::default(),
             stats_1h: WindowStats::default(),
+            request_quotas: Vec::new(),
             pricing_catalog: crate::pricing::bundled_model_price_catalog_snapshot(),
             service_status: Some(crate::service_status::ServiceStatusSnapshot {
                 generated_at_ms: 32,
@@ -3032,8 +3109,10 @@ mod tests {
                             code: "cooldown".to_string(),
                             cooldown_remaining_secs: Some(42),
                         }],
+                        primary_sticky_remaining_secs: None,
                     }],
                     capacity: OperatorProviderCapacity::default(),
+                    schedule: None,
                 }],
             },
             routing: None,
@@ -3045,6 +3124,7 @@ mod tests {
             usage_rollup: UsageRollupView::default(),
             stats_5m: WindowStats::default(),
             stats_1h: WindowStats::default(),
+            request_quotas: Vec::new(),
             pricing_catalog: Default::default(),
             service_status: None,
             provider_balances: Vec::new(),
@@ -3192,6 +3272,8 @@ mod tests {
             pricing_catalog: ModelPriceCatalogSnapshot::default(),
             stats_5m: WindowStats::default(),
             stats_1h: WindowStats::default(),
+            request_quotas: Vec::new(),
+            recent_capacity: 0,
             service_status: None,
             refreshed_at: Instant::now(),
         };
@@ -3251,6 +3333,8 @@ mod tests {
             pricing_catalog: ModelPriceCatalogSnapshot::default(),
             stats_5m: WindowStats::default(),
             stats_1h: WindowStats::default(),
+            request_quotas: Vec::new(),
+            recent_capacity: 0,
             service_status: None,
             refreshed_at: Instant::now(),
         };
@@ -3286,6 +3370,8 @@ mod tests {
             pricing_catalog: ModelPriceCatalogSnapshot::default(),
             stats_5m: WindowStats::default(),
             stats_1h: WindowStats::default(),
+            request_quotas: Vec::new(),
+            recent_capacity: 0,
             service_status: None,
             refreshed_at: Instant::now(),
         };
@@ -3307,6 +3393,8 @@ mod tests {
             pricing_catalog: ModelPriceCatalogSnapshot::default(),
             stats_5m: WindowStats::default(),
             stats_1h: WindowStats::default(),
+            request_quotas: Vec::new(),
+            recent_capacity: 0,
             service_status: None,
             refreshed_at: Instant::now(),
         };
@@ -3414,7 +3502,7 @@ mod tests {
             ..UsageMetrics::default()
         };
 
-        let line = usage_line_lang(&usage, Language::En);
+        let line = usage_line_lang(&DisplayFormat::default(), &usage, Language::En);
 
         assert!(line.contains("cache read/create: 250/50"), "{line}");
     }
@@ -842,6 +842,48 @@ fn routing_mutation_message(
                 "endpoint {provider_id}.{endpoint_id} is now {}",
                 endpoint_mode_label(lang, *mode)
             ),
+            (
+                Language::Zh,
+                OperatorRoutingCommand::PinUpstream {
+                    provider_id,
+                    endpoint_id,
+                    session_key: None,
+                    ..
+                },
+            ) => format!("所有请求已固定到 {provider_id}.{endpoint_id}"),
+            (
+                Language::En,
+                OperatorRoutingCommand::PinUpstream {
+                    provider_id,
+                    endpoint_id,
+                    session_key: None,
+                    ..
+                },
+            ) => format!("all requests are now pinned to {provider_id}.{endpoint_id}"),
+            (
+                Language::Zh,
+                OperatorRoutingCommand::PinUpstream {
+                    provider_id,
+                    endpoint_id,
+                    session_key: Some(session_key),
+                    ..
+                },
+            ) => format!("会话 {session_key} 已固定到 {provider_id}.{endpoint_id}"),
+            (
+                Language::En,
+                OperatorRoutingCommand::PinUpstream {
+                    provider_id,
+                    endpoint_id,
+                    session_key: Some(session_key),
+                    ..
+                },
+            ) => format!("session {session_key} is now pinned to {provider_id}.{endpoint_id}"),
+            (Language::Zh, OperatorRoutingCommand::ClearUpstreamPin { .. }) => {
+                "上游固定已取消".to_string()
+            }
+            (Language::En, OperatorRoutingCommand::ClearUpstreamPin { .. }) => {
+                "upstream pin cleared".to_string()
+            }
         },
     }
 }
@@ -1028,10 +1070,14 @@ mod tests {
                 entry_strategy: crate::config::RouteStrategy::RoundRobin,
                 entry_target: None,
                 new_session_preference: None,
+                auto_active_by_health: false,
+                auto_active: None,
                 affinity_policy: crate::config::RouteAffinityPolicy::FallbackSticky,
                 scheduling_preset: crate::config::SchedulingPreset::Balanced,
                 fallback_ttl_ms: None,
                 reprobe_preferred_after_ms: None,
+                upstream_pin: None,
+                session_upstream_pins: Vec::new(),
                 candidates: Vec::new(),
             },
         }
@@ -1155,6 +1201,7 @@ mod tests {
             expected_control_revision: 1,
             expected_policy_revision: 2,
             command: OperatorRoutingCommand::ClearNewSessionPreference,
+            source: None,
         };
 
         assert!(!queue_balance_refresh(&mut ui, true, true));
@@ -1185,6 +1232,7 @@ mod tests {
                 provider_id: "input".to_string(),
                 endpoint_id: "primary".to_string(),
             },
+            source: None,
         };
 
         assert!(queue_routing_mutation(&mut ui, request.clone()));
@@ -1,11 +1,11 @@
 use super::Language;
 use super::i18n;
-use super::model::tokens_short;
 use super::state::{StatsProjectRowKind, UiState};
 use super::types::StatsFocus;
 use crate::quota_analytics::{PoolQuotaAnalytics, QuotaProjectRow};
 use crate::quota_pool::{IdentityConfidence, QuotaQuantity, QuotaUnit};
 use crate::state::{UsageBucket, UsageDayDimensionRow};
+use crate::usage_format::DisplayFormat;
 
 #[derive(Debug, Clone)]
 pub(in crate::tui) enum StatsTarget {
@@ -154,9 +154,21 @@ pub(in crate::tui) fn build_stats_report(
         Language::Zh => "[今日汇总]\n",
         Language::En => "[day summary]\n",
     });
-    append_bucket(&mut out, ui.language, &usage.summary, "total");
+    append_bucket(
+        &mut out,
+        ui.language,
+        &ui.display_format,
+        &usage.summary,
+        "total",
+    );
     if let Some(target_bucket) = target_bucket {
-        append_bucket(&mut out, ui.language, target_bucket, &name);
+        append_bucket(
+            &mut out,
+            ui.language,
+            &ui.display_format,
+            target_bucket,
+            &name,
+        );
     }
     out.push('\n');
 
@@ -216,16 +228,36 @@ pub(in crate::tui) fn build_stats_report(
     }
     out.push('\n');
 
-    append_dimension(&mut out, ui.language, "providers", &usage.provider_rows);
+    let format = &ui.display_format;
+    append_dimension(
+        &mut out,
+        ui.language,
+        format,
+        "providers",
+        &usage.provider_rows,
+    );
     append_dimension(
         &mut out,
         ui.language,
+        format,
         "provider endpoints",
         &usage.provider_endpoint_rows,
     );
-    append_dimension(&mut out, ui.language, "models", &usage.model_rows);
-    append_dimension(&mut out, ui.language, "sessions", &usage.session_rows);
-    append_dimension(&mut out, ui.language, "projects", &usage.project_rows);
+    append_dimension(&mut out, ui.language, format, "models", &usage.model_rows);
+    append_dimension(
+        &mut out,
+        ui.language,
+        format,
+        "sessions",
+        &usage.session_rows,
+    );
+    append_dimension(
+        &mut out,
+        ui.language,
+        format,
+        "projects",
+        &usage.project_rows,
+    );
 
     Some(out)
 }
@@ -397,7 +429,13 @@ fn optional_u32(value: Option<u32>) -> String {
     value.map_or_else(|| "-".to_string(), |value| value.to_string())
 }
 
-fn append_bucket(out: &mut String, lang: Language, bucket: &UsageBucket, label: &str) {
+fn append_bucket(
+    out: &mut String,
+    lang: Language,
+    format: &DisplayFormat,
+    bucket: &UsageBucket,
+    label: &str,
+) {
     let l = |text| i18n::label(lang, text);
     out.push_str(&format!(
         "{label}: {}={} {}={} {}={} {}={} {}={} {}\n",
@@ -410,15 +448,15 @@ fn append_bucket(out: &mut String, lang: Language, bucket: &UsageBucket, label:
         l("avg"),
         fmt_avg_ms(bucket.duration_ms_total, bucket.requests_total),
         l("tokens"),
-        tokens_short(bucket.usage.total_tokens),
-        cost_text(bucket)
+        format.tokens_short(bucket.usage.total_tokens),
+        cost_text(format, bucket)
     ));
 }
 
-fn cost_text(bucket: &UsageBucket) -> String {
+fn cost_text(format: &DisplayFormat, bucket: &UsageBucket) -> String {
     format!(
         "cost={} priced={} unpriced={}",
-        bucket.cost.display_total(),
+        bucket.cost.display_total(format),
         bucket.cost.priced_requests,
         bucket.cost.unpriced_requests
     )
@@ -427,6 +465,7 @@ fn cost_text(bucket: &UsageBucket) -> String {
 fn append_dimension(
     out: &mut String,
     lang: Language,
+    format: &DisplayFormat,
     title: &'static str,
     rows: &[UsageDayDimensionRow],
 ) {
@@ -449,8 +488,8 @@ fn append_dimension(
             row.name,
             row.bucket.requests_total,
             row.bucket.requests_error,
-            tokens_short(row.bucket.usage.total_tokens),
-            cost_text(&row.bucket)
+            format.tokens_short(row.bucket.usage.total_tokens),
+            cost_text(format, &row.bucket)
         ));
     }
 }
@@ -6,6 +6,7 @@ use futures_util::stream;
 use tokio::sync::mpsc;
 
 use super::i18n::{self, msg};
+use super::local_session_enrichment::local_privacy_config;
 use super::model::now_ms;
 use super::state::{RecentCodexRow, UiState, merge_codex_history_external_focus};
 
@@ -43,8 +44,15 @@ pub(super) fn start_codex_history_refresh(
     ));
 
     tokio::spawn(async move {
+        let privacy = local_privacy_config().await;
         let result = crate::sessions::find_codex_sessions(300)
             .await
+            .map(|mut list| {
+                for summary in &mut list {
+                    privacy.apply_to_session_summary(summary);
+                }
+                list
+            })
             .map_err(|err| err.to_string());
         let _ = tx.send(CodexHistoryRefreshResult { generation, result });
     });
@@ -152,8 +160,9 @@ async fn load_codex_recent_rows(
     let mut rows = Vec::with_capacity(list.len());
     let mut missing_roots = Vec::new();
     let mut missing_seen = HashSet::new();
+    let privacy = local_privacy_config().await;
     for s in list {
-        let cwd_opt = s.cwd.clone();
+        let cwd_opt = privacy.apply_to_cwd(s.cwd.clone());
         let cwd = cwd_opt.as_deref().unwrap_or("-");
         let root = if raw_cwd {
             cwd.to_string()
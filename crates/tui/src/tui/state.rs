@@ -1,6 +1,7 @@
 use ratatui::widgets::TableState;
 
 use crate::codex_integration::CodexStartupReadiness;
+use crate::config::TuiTheme;
 use crate::dashboard_core::{
     ControlProfileOption, OperatorActionCapabilities, OperatorLocalSessionMetadata,
     OperatorReadModel, OperatorRetrySummary,
@@ -9,6 +10,7 @@ use crate::proxy::{OperatorRoutingMutationRequest, OperatorSessionAffinityMutati
 use crate::sessions::{
     SessionMeta, SessionSummary, SessionSummarySource, SessionTranscriptMessage,
 };
+use crate::usage_format::DisplayFormat;
 use crate::usage_providers::UsageProviderRefreshSummary;
 use codex_helper_core::fleet::FleetSnapshot;
 use std::collections::HashMap;
@@ -20,7 +22,7 @@ use super::model::{
     request_matches_page_filters, request_page_focus_session_id,
 };
 use super::operator_actions::PendingOperatorAction;
-use super::types::{Focus, Overlay, Page, StatsFocus};
+use super::types::{Focus, Overlay, Page, StatsFocus, StatsLatencyWindow};
 
 #[derive(Debug, Clone)]
 pub(in crate::tui) struct RecentCodexRow {
@@ -249,6 +251,10 @@ pub(in crate::tui) struct UiState {
     pub(in crate::tui) service_name: &'static str,
     pub(in crate::tui) proxy_port: u16,
     pub(in crate::tui) language: Language,
+    pub(in crate::tui) theme: TuiTheme,
+    pub(in crate::tui) display_format: DisplayFormat,
+    /// `logging.sample_rate` of the integrated runtime; attached dashboards never set it.
+    pub(in crate::tui) request_log_sample_rate: Option<f64>,
     pub(in crate::tui) runtime_connection: RuntimeConnectionKind,
     pub(in crate::tui) operator_read_model: Option<OperatorReadModel>,
     pub(in crate::tui) host_local_sessions: HashMap<String, OperatorLocalSessionMetadata>,
@@ -319,9 +325,12 @@ pub(in crate::tui) struct UiState {
     pub(in crate::tui) help_scroll: u16,
     pub(in crate::tui) sessions_details_scroll: u16,
     pub(in crate::tui) requests_details_scroll: u16,
+    /// Upstream error body loaded by the last-error shortcut, keyed by request id.
+    pub(in crate::tui) last_error_body: Option<(u64, String)>,
     pub(in crate::tui) codex_relay_diagnostics: super::settings_relay::CodexRelayDiagnosticsState,
     pub(in crate::tui) codex_relay_live_smoke: super::settings_relay::CodexRelayLiveSmokeState,
     pub(in crate::tui) stats_focus: StatsFocus,
+    pub(in crate::tui) stats_latency_window: StatsLatencyWindow,
     pub(in crate::tui) stats_errors_only: bool,
     pub(in crate::tui) stats_attention_only: bool,
     pub(in crate::tui) selected_stats_provider_endpoint_idx: usize,
@@ -441,6 +450,9 @@ impl Default for UiState {
             service_name: "codex",
             proxy_port: 3211,
             language: Language::En,
+            theme: TuiTheme::Auto,
+            display_format: DisplayFormat::default(),
+            request_log_sample_rate: None,
             runtime_connection: RuntimeConnectionKind::Integrated,
             operator_read_model: None,
             host_local_sessions: HashMap::new(),
@@ -510,9 +522,11 @@ impl Default for UiState {
             help_scroll: 0,
             sessions_details_scroll: 0,
             requests_details_scroll: 0,
+            last_error_body: None,
             codex_relay_diagnostics: Default::default(),
             codex_relay_live_smoke: Default::default(),
             stats_focus: StatsFocus::Pools,
+            stats_latency_window: StatsLatencyWindow::default(),
             stats_errors_only: false,
             stats_attention_only: false,
             selected_stats_provider_endpoint_idx: 0,
@@ -1835,6 +1849,8 @@ mod tests {
             pricing_catalog: Default::default(),
             stats_5m: crate::dashboard_core::WindowStats::default(),
             stats_1h: crate::dashboard_core::WindowStats::default(),
+            request_quotas: Vec::new(),
+            recent_capacity: 0,
             service_status: None,
             refreshed_at: std::time::Instant::now(),
         }
@@ -1992,6 +2008,8 @@ mod tests {
             id,
             trace_key: None,
             session_key: session_id.map(ToOwned::to_owned),
+            body_hash: None,
+            upstream_request_id: None,
             model: None,
             reasoning_effort: None,
             service_tier: None,
@@ -2429,10 +2447,14 @@ mod tests {
                 entry_strategy: crate::config::RouteStrategy::RoundRobin,
                 entry_target: None,
                 new_session_preference: None,
+                auto_active_by_health: false,
+                auto_active: None,
                 affinity_policy: crate::config::RouteAffinityPolicy::FallbackSticky,
                 scheduling_preset: crate::config::SchedulingPreset::Balanced,
                 fallback_ttl_ms: None,
                 reprobe_preferred_after_ms: None,
+                upstream_pin: None,
+                session_upstream_pins: Vec::new(),
                 candidates: candidates
                     .iter()
                     .enumerate()
@@ -16,6 +16,43 @@ pub(in crate::tui) enum StatsFocus {
     Providers,
 }
 
+/// Window the Stats latency histogram is computed over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(in crate::tui) enum StatsLatencyWindow {
+    #[default]
+    FiveMinutes,
+    OneHour,
+    /// Every request in the loaded recent-request buffer.
+    Loaded,
+}
+
+impl StatsLatencyWindow {
+    pub(in crate::tui) fn next(self) -> Self {
+        match self {
+            Self::FiveMinutes => Self::OneHour,
+            Self::OneHour => Self::Loaded,
+            Self::Loaded => Self::FiveMinutes,
+        }
+    }
+
+    pub(in crate::tui) fn window_ms(self) -> Option<u64> {
+        match self {
+            Self::FiveMinutes => Some(5 * 60_000),
+            Self::OneHour => Some(60 * 60_000),
+            Self::Loaded => None,
+        }
+    }
+
+    pub(in crate::tui) fn label(self, lang: Language) -> &'static str {
+        match (self, lang) {
+            (Self::FiveMinutes, _) => "5m",
+            (Self::OneHour, _) => "1h",
+            (Self::Loaded, Language::Zh) => "已加载",
+            (Self::Loaded, Language::En) => "loaded",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(in crate::tui) enum Page {
     Dashboard,
@@ -139,15 +176,19 @@ pub(in crate::tui) enum RoutingActionChoice {
     EnableEndpoint,
     DrainEndpoint,
     DisableEndpoint,
+    PinUpstream,
+    ClearUpstreamPin,
 }
 
 impl RoutingActionChoice {
-    pub(in crate::tui) const ALL: [Self; 5] = [
+    pub(in crate::tui) const ALL: [Self; 7] = [
         Self::PreferNewSessions,
         Self::ClearNewSessionPreference,
         Self::EnableEndpoint,
         Self::DrainEndpoint,
         Self::DisableEndpoint,
+        Self::PinUpstream,
+        Self::ClearUpstreamPin,
     ];
 }
 
@@ -188,10 +188,14 @@ mod tests {
                 entry_strategy: crate::config::RouteStrategy::RoundRobin,
                 entry_target: Some("input-light.default".to_string()),
                 new_session_preference: None,
+                auto_active_by_health: false,
+                auto_active: None,
                 affinity_policy: crate::config::RouteAffinityPolicy::FallbackSticky,
                 scheduling_preset: crate::config::SchedulingPreset::Balanced,
                 fallback_ttl_ms: Some(300_000),
                 reprobe_preferred_after_ms: Some(30_000),
+                upstream_pin: None,
+                session_upstream_pins: Vec::new(),
                 candidates: [
                     "input",
                     "input1",
@@ -213,6 +217,8 @@ mod tests {
             pricing_catalog: Default::default(),
             stats_5m: crate::dashboard_core::WindowStats::default(),
             stats_1h: crate::dashboard_core::WindowStats::default(),
+            request_quotas: Vec::new(),
+            recent_capacity: 0,
             service_status: None,
             refreshed_at: Instant::now(),
         }
@@ -266,6 +272,7 @@ mod tests {
                 } else {
                     Vec::new()
                 },
+                primary_sticky_remaining_secs: None,
             }],
             capacity: OperatorProviderCapacity {
                 configured_max_concurrent_requests: Some(if idx == 3 { 20 } else { 15 }),
@@ -275,6 +282,7 @@ mod tests {
                 saturated: false,
                 inherited_from_provider: None,
             },
+            schedule: None,
         })
         .collect()
     }
@@ -284,6 +292,8 @@ mod tests {
             id,
             trace_key: None,
             session_key: Some(session_id.to_string()),
+            body_hash: None,
+            upstream_request_id: None,
             model: None,
             reasoning_effort: None,
             service_tier: None,
@@ -7,7 +7,9 @@ use ratatui::widgets::{Block, Borders, Paragraph};
 use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 use crate::tui::i18n::{self, msg};
-use crate::tui::model::{Palette, Snapshot, now_ms, request_attempt_count, shorten_middle};
+use crate::tui::model::{
+    Palette, Snapshot, now_ms, recent_error_requests, request_attempt_count, shorten_middle,
+};
 use crate::tui::state::UiState;
 use crate::tui::types::{Focus, Overlay, Page, page_index, page_titles};
 
@@ -308,6 +310,10 @@ fn requests_footer_help_text(ui: &UiState) -> String {
         crate::tui::Language::En => "o session",
     });
     if ui.can_bridge_runtime_sessions_to_local_codex() {
+        parts.push(match ui.language {
+            crate::tui::Language::Zh => "t 对话",
+            crate::tui::Language::En => "t transcript",
+        });
         parts.push(match ui.language {
             crate::tui::Language::Zh => "h 历史",
             crate::tui::Language::En => "h history",
@@ -396,6 +402,10 @@ fn stats_footer_help_text(ui: &UiState) -> String {
         crate::tui::Language::Zh => "y 导出",
         crate::tui::Language::En => "y export",
     });
+    parts.push(match ui.language {
+        crate::tui::Language::Zh => "w 延迟窗口",
+        crate::tui::Language::En => "w latency window",
+    });
     parts.push("? help");
     parts.join("  ")
 }
@@ -517,10 +527,10 @@ fn footer_help_text(ui: &UiState) -> String {
                 "1-9/0 pages  q exit console only  Tab focus  ↑/↓ move  O/o requests/sessions  ? help"
             }
             (crate::tui::Language::Zh, Page::Requests, _, _, _, true) => {
-                "q 只退出控制台  ↑/↓ 请求  e/c/s 筛选  x 清焦点  o 会话  h 历史  ? 帮助"
+                "q 只退出控制台  ↑/↓ 请求  e/c/s 筛选  x 清焦点  o 会话  t 对话  h 历史  ? 帮助"
             }
             (crate::tui::Language::En, Page::Requests, _, _, _, true) => {
-                "q exit console only  ↑/↓ request  e/c/s filters  x clear focus  o session  h history  ? help"
+                "q exit console only  ↑/↓ request  e/c/s filters  x clear focus  o session  t transcript  h history  ? help"
             }
             (crate::tui::Language::Zh, Page::Requests, _, _, _, false) => {
                 "q 只退出控制台  ↑/↓ 请求  e/c/s 筛选  x 清焦点  o 会话  ? 帮助"
@@ -750,12 +760,7 @@ pub(super) fn render_header(
         .split(inner);
 
     let active_total = snapshot.rows.iter().map(|r| r.active_count).sum::<usize>();
-    let recent_err = snapshot
-        .recent
-        .iter()
-        .take(80)
-        .filter(|r| r.status_code >= 400)
-        .count();
+    let recent_err = recent_error_requests(snapshot).count();
     let updated = snapshot_age_ms(ui, snapshot, now_ms());
     let focus = match ui.page {
         Page::Fleet => i18n::label(ui.language, "fleet view"),
@@ -767,8 +772,9 @@ pub(super) fn render_header(
         },
     };
     let connection = ui.runtime_connection.label(ui.language);
+    let error_badge = recent_error_badge(p, ui.language, recent_err);
     let title = if inner.width >= 72 {
-        Line::from(vec![
+        let mut spans = vec![
             Span::styled(
                 "codex-helper",
                 Style::default().fg(p.text).add_modifier(Modifier::BOLD),
@@ -785,9 +791,11 @@ pub(super) fn render_header(
                 format!("{}{focus}", i18n::text(ui.language, msg::FOCUS_LABEL)),
                 Style::default().fg(p.muted),
             ),
-        ])
+        ];
+        spans.extend(error_badge);
+        Line::from(spans)
     } else if inner.width >= 46 {
-        Line::from(vec![
+        let mut spans = vec![
             Span::styled(
                 "codex-helper",
                 Style::default().fg(p.text).add_modifier(Modifier::BOLD),
@@ -801,7 +809,9 @@ pub(super) fn render_header(
             Span::styled(connection.to_string(), Style::default().fg(p.muted)),
             Span::raw("  "),
             Span::styled(focus.to_string(), Style::default().fg(p.muted)),
-        ])
+        ];
+        spans.extend(error_badge);
+        Line::from(spans)
     } else {
         Line::from(vec![
             Span::styled(
@@ -1045,6 +1055,30 @@ pub(super) fn render_header(
     f.render_widget(Paragraph::new(Text::from(tabs)), chunks[2]);
 }
 
+/// Title-line badge for errors among the last 80 requests; `!` jumps to the newest one.
+fn recent_error_badge(
+    p: Palette,
+    language: crate::tui::Language,
+    recent_err: usize,
+) -> Vec<Span<'static>> {
+    if recent_err == 0 {
+        return Vec::new();
+    }
+    let text = match language {
+        crate::tui::Language::Zh => format!(" ! {recent_err} 错误 "),
+        crate::tui::Language::En => format!(" ! {recent_err} err "),
+    };
+    vec![
+        Span::raw("  "),
+        Span::styled(
+            text,
+            Style::default()
+                .fg(p.bad)
+                .add_modifier(Modifier::BOLD | Modifier::REVERSED),
+        ),
+    ]
+}
+
 fn snapshot_age_ms(ui: &UiState, snapshot: &Snapshot, current_time_ms: u64) -> u128 {
     ui.operator_read_model
         .as_ref()
@@ -1492,6 +1526,7 @@ mod tests {
         assert!(requests.contains("x clear focus"), "{requests}");
         assert!(requests.contains("o session"), "{requests}");
         assert!(!requests.contains("h history"), "{requests}");
+        assert!(!requests.contains("t transcript"), "{requests}");
 
         let history = footer_help_text(&remote(Page::History));
         assert!(history.contains("Pg details"), "{history}");
@@ -1508,6 +1543,7 @@ mod tests {
         let stats = footer_help_text(&remote(Page::Stats));
         assert!(stats.contains("g refresh"), "{stats}");
         assert!(stats.contains("y export"), "{stats}");
+        assert!(stats.contains("w latency window"), "{stats}");
 
         let fleet = footer_help_text(&remote(Page::Fleet));
         assert!(fleet.contains("Tab focus"), "{fleet}");
@@ -1535,6 +1571,16 @@ mod tests {
         );
     }
 
+    #[test]
+    fn recent_error_badge_appears_only_with_errors() {
+        assert!(recent_error_badge(Palette::default(), crate::tui::Language::En, 0).is_empty());
+        let badge = recent_error_badge(Palette::default(), crate::tui::Language::En, 3);
+        assert_eq!(
+            badge.last().map(|span| span.content.as_ref()),
+            Some(" ! 3 err ")
+        );
+    }
+
     #[test]
     fn header_tabs_line_fits_available_width() {
         let ui = UiState {
@@ -2,7 +2,7 @@ use std::collections::BTreeMap;
 
 use ratatui::Frame;
 use ratatui::layout::{Constraint, Direction, Layout};
-use ratatui::prelude::{Color, Line, Modifier, Span, Style, Text};
+use ratatui::prelude::{Line, Modifier, Span, Style, Text};
 use ratatui::widgets::{
     Block, Borders, Clear, HighlightSpacing, List, ListItem, ListState, Paragraph, Wrap,
 };
@@ -10,6 +10,7 @@ use ratatui::widgets::{
 use crate::codex_integration::{CodexStartupReadinessIssue, CodexStartupReadinessSeverity};
 use crate::dashboard_core::ControlProfileOption;
 use crate::proxy::{OperatorEndpointMode, OperatorRoutingCommand, OperatorSessionAffinityCommand};
+use crate::sessions::estimate_transcript_tokens;
 use crate::tui::Language;
 use crate::tui::i18n::{self, msg};
 use crate::tui::model::{Palette, shorten_middle};
@@ -218,7 +219,7 @@ fn render_selection_menu(
             .style(Style::default().fg(p.text))
             .highlight_style(
                 Style::default()
-                    .bg(Color::Rgb(32, 39, 48))
+                    .bg(p.highlight)
                     .fg(p.text)
                     .add_modifier(Modifier::BOLD),
             )
@@ -510,6 +511,10 @@ pub(super) fn render_routing_actions_modal(
             (Language::En, RoutingActionChoice::DrainEndpoint) => "Drain endpoint",
             (Language::Zh, RoutingActionChoice::DisableEndpoint) => "禁用端点",
             (Language::En, RoutingActionChoice::DisableEndpoint) => "Disable endpoint",
+            (Language::Zh, RoutingActionChoice::PinUpstream) => "固定所有请求到此端点",
+            (Language::En, RoutingActionChoice::PinUpstream) => "Pin all requests to endpoint",
+            (Language::Zh, RoutingActionChoice::ClearUpstreamPin) => "取消上游固定",
+            (Language::En, RoutingActionChoice::ClearUpstreamPin) => "Clear upstream pin",
         };
         let selected = index == ui.routing_action_selected_idx;
         lines.push(Line::from(Span::styled(
@@ -629,6 +634,61 @@ pub(super) fn render_routing_confirmation_modal(f: &mut Frame<'_>, p: Palette, u
                 }
             }));
         }
+        Some(OperatorRoutingCommand::PinUpstream {
+            provider_id,
+            endpoint_id,
+            session_key,
+            failover,
+        }) => {
+            lines.push(Line::from(Span::styled(
+                match (ui.language, session_key) {
+                    (Language::Zh, None) => format!("所有请求固定到 {provider_id}.{endpoint_id}"),
+                    (Language::En, None) => {
+                        format!("Pin all requests to {provider_id}.{endpoint_id}")
+                    }
+                    (Language::Zh, Some(session_key)) => {
+                        format!("会话 {session_key} 固定到 {provider_id}.{endpoint_id}")
+                    }
+                    (Language::En, Some(session_key)) => {
+                        format!("Pin session {session_key} to {provider_id}.{endpoint_id}")
+                    }
+                },
+                Style::default().fg(p.text).add_modifier(Modifier::BOLD),
+            )));
+            lines.push(Line::from(""));
+            lines.push(Line::from(match (ui.language, failover) {
+                (Language::Zh, true) => {
+                    "覆盖会话 affinity 和新会话偏好；目标不可用时按配置策略回退。"
+                }
+                (Language::En, true) => {
+                    "Overrides session affinity and the new-session preference; unavailable targets fall back through the configured policy."
+                }
+                (Language::Zh, false) => "覆盖会话 affinity 和新会话偏好；目标不可用时请求直接失败。",
+                (Language::En, false) => {
+                    "Overrides session affinity and the new-session preference; requests fail while the target is unavailable."
+                }
+            }));
+        }
+        Some(OperatorRoutingCommand::ClearUpstreamPin { session_key }) => {
+            lines.push(Line::from(Span::styled(
+                match (ui.language, session_key) {
+                    (Language::Zh, None) => "取消全局上游固定".to_string(),
+                    (Language::En, None) => "Clear the global upstream pin".to_string(),
+                    (Language::Zh, Some(session_key)) => {
+                        format!("取消会话 {session_key} 的上游固定")
+                    }
+                    (Language::En, Some(session_key)) => {
+                        format!("Clear the upstream pin for session {session_key}")
+                    }
+                },
+                Style::default().fg(p.text).add_modifier(Modifier::BOLD),
+            )));
+            lines.push(Line::from(""));
+            lines.push(Line::from(match ui.language {
+                Language::Zh => "恢复 affinity 和自动调度。",
+                Language::En => "Session affinity and automatic scheduling resume.",
+            }));
+        }
         None => {
             lines.push(Line::from(match ui.language {
                 Language::Zh => "没有待确认的路由变更。",
@@ -1218,6 +1278,21 @@ pub(super) fn render_session_transcript_modal(f: &mut Frame<'_>, p: Palette, ui:
                 ui.session_transcript_messages.len().to_string(),
                 Style::default().fg(p.text),
             ),
+            Span::raw("   "),
+            Span::styled(format!("{}: ", l("tokens")), Style::default().fg(p.muted)),
+            Span::styled(
+                format!(
+                    "~{}",
+                    ui.display_format.tokens_short(estimate_transcript_tokens(
+                        &ui.session_transcript_messages
+                    ) as i64)
+                ),
+                Style::default().fg(p.text),
+            ),
+            Span::styled(
+                format!(" ({})", l("estimated")),
+                Style::default().fg(p.muted),
+            ),
         ]));
         lines.push(Line::from(""));
         for msg in ui.session_transcript_messages.iter() {
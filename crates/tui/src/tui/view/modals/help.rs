@@ -187,7 +187,10 @@ pub(super) fn current_page_help_lines(ui: &UiState, p: Palette) -> Vec<Line<'sta
                 "  o          跳到关联 Sessions",
             ];
             if ui.can_bridge_runtime_sessions_to_local_codex() {
-                entries.push("  h          跳到 History");
+                entries.extend([
+                    "  t          打开该请求会话的全屏对话记录",
+                    "  h          跳到 History",
+                ]);
             }
             entries
         }
@@ -200,6 +203,7 @@ pub(super) fn current_page_help_lines(ui: &UiState, p: Palette) -> Vec<Line<'sta
                 "  g          仅刷新观察快照；不会请求上游余额"
             },
             "  y          导出并复制选中报告",
+            "  w          切换延迟分布窗口（5m / 1h / 已加载）",
         ],
         (Language::Zh, Page::Settings, _) => settings_help_entries(ui, Language::Zh),
         (Language::Zh, Page::History, _) => {
@@ -326,7 +330,10 @@ pub(super) fn current_page_help_lines(ui: &UiState, p: Palette) -> Vec<Line<'sta
                 "  o          jump to related Sessions",
             ];
             if ui.can_bridge_runtime_sessions_to_local_codex() {
-                entries.push("  h          jump to History");
+                entries.extend([
+                    "  t          open the request's session transcript",
+                    "  h          jump to History",
+                ]);
             }
             entries
         }
@@ -339,6 +346,7 @@ pub(super) fn current_page_help_lines(ui: &UiState, p: Palette) -> Vec<Line<'sta
                 "  g          refresh the observer snapshot only; upstream balances stay unchanged"
             },
             "  y          export and copy the selected report",
+            "  w          cycle the latency histogram window (5m / 1h / loaded)",
         ],
         (Language::En, Page::Settings, _) => settings_help_entries(ui, Language::En),
         (Language::En, Page::History, _) => {
@@ -416,6 +424,15 @@ fn language_help_line(ui: &UiState) -> &'static str {
     }
 }
 
+fn theme_help_line(ui: &UiState) -> &'static str {
+    match (ui.language, !ui.runtime_connection.is_remote_observer()) {
+        (Language::Zh, true) => "  T          切换配色主题并保存到 config.toml",
+        (Language::En, true) => "  T          cycle color theme and save it to config.toml",
+        (Language::Zh, false) => "  T          仅切换当前 TUI 会话配色主题",
+        (Language::En, false) => "  T          color theme (current TUI session only)",
+    }
+}
+
 #[cfg(test)]
 pub(super) fn help_quit_line_for_tests(lang: Language, attached: bool) -> &'static str {
     help_quit_line(lang, attached)
@@ -455,7 +472,12 @@ pub(in crate::tui::view) fn render_help_modal(f: &mut Frame<'_>, p: Palette, ui:
             Language::Zh => "  1-9/0      切换页面",
             Language::En => "  1-9/0      pages",
         }),
+        Line::from(match ui.language {
+            Language::Zh => "  !          跳到最近一条出错的请求（Requests；开启 HTTP debug 时显示上游错误响应体）",
+            Language::En => "  !          jump to the newest failed request (Requests; with HTTP debug on, shows the upstream error body)",
+        }),
         Line::from(language_help_line(ui)),
+        Line::from(theme_help_line(ui)),
         Line::from(match ui.language {
             Language::Zh => "  ? / Esc    打开 / 关闭帮助",
             Language::En => "  ? / Esc    open / close help",
@@ -159,6 +159,31 @@ pub(in crate::tui::view) fn render_provider_info_modal(
                 Style::default().fg(p.text),
             ),
         ]));
+        if let Some(schedule) = provider.schedule.as_ref() {
+            lines.push(Line::from(vec![
+                Span::styled(label("时段: ", "schedule: "), Style::default().fg(p.muted)),
+                Span::styled(
+                    format!(
+                        "{} ({})",
+                        schedule.windows.join("; "),
+                        schedule.timezone.as_deref().unwrap_or("local")
+                    ),
+                    Style::default().fg(p.text),
+                ),
+                Span::styled("  ", Style::default()),
+                Span::styled(
+                    if schedule.active {
+                        label("时段内", "in window")
+                    } else {
+                        label(
+                            "时段外，自动路由跳过",
+                            "outside window, skipped by auto-routing",
+                        )
+                    },
+                    Style::default().fg(if schedule.active { p.good } else { p.warn }),
+                ),
+            ]));
+        }
         lines.push(Line::from(vec![
             Span::styled("balance/quota: ", Style::default().fg(p.muted)),
             Span::styled(
@@ -279,6 +304,12 @@ pub(in crate::tui::view) fn render_provider_info_modal(
                     }),
                 )));
             }
+            if let Some(seconds) = endpoint.primary_sticky_remaining_secs {
+                lines.push(Line::from(Span::styled(
+                    format!("  {}={seconds}s", label("主路由保持", "sticky hold")),
+                    Style::default().fg(p.muted),
+                )));
+            }
             for action in endpoint.policy_actions.iter().take(8) {
                 let cooldown = action
                     .cooldown_remaining_secs
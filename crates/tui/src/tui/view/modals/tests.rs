@@ -169,6 +169,10 @@ fn remote_help_keeps_local_history_browsing_without_claiming_runtime_bridge_acti
         !remote_requests.contains("jump to History"),
         "{remote_requests}"
     );
+    assert!(
+        !remote_requests.contains("session transcript"),
+        "{remote_requests}"
+    );
 
     let remote_history = help_text_for_tests(&current_page_help_lines(
         &ui_for(Page::History, RuntimeConnectionKind::RemoteObserver),
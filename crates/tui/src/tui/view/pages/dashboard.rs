@@ -1,6 +1,6 @@
 use ratatui::Frame;
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
-use ratatui::prelude::{Color, Line, Modifier, Span, Style, Text};
+use ratatui::prelude::{Line, Modifier, Span, Style, Text};
 use ratatui::widgets::{
     Block, Borders, Cell, HighlightSpacing, Paragraph, Row, Scrollbar, ScrollbarOrientation,
     ScrollbarState, Table, Wrap,
@@ -14,7 +14,7 @@ use crate::tui::model::{
     request_cache_hit_rate_label, session_control_posture_lang, session_cwd_detail_lang,
     session_observation_scope_label_lang, session_observed_provider_balance_brief_lang,
     session_observed_provider_balance_snapshot, session_transcript_host_status_lang, short_sid,
-    shorten, status_style, tokens_short, usage_line_lang,
+    shorten, status_style, usage_line_lang,
 };
 use crate::tui::state::UiState;
 use crate::tui::types::{Focus, Overlay};
@@ -149,7 +149,10 @@ fn render_sessions_panel(
 
             let total_tokens = r.total_usage.as_ref().map(|u| u.total_tokens).unwrap_or(0);
             let tok = if total_tokens > 0 {
-                Span::styled(tokens_short(total_tokens), Style::default().fg(p.accent))
+                Span::styled(
+                    ui.display_format.tokens_short(total_tokens),
+                    Style::default().fg(p.accent),
+                )
             } else {
                 Span::styled("-", Style::default().fg(p.muted))
             };
@@ -226,7 +229,7 @@ fn render_sessions_panel(
     let table = Table::new(rows, widths)
         .header(header)
         .block(block)
-        .row_highlight_style(Style::default().bg(Color::Rgb(32, 39, 48)))
+        .row_highlight_style(Style::default().bg(p.highlight))
         .highlight_spacing(HighlightSpacing::Always);
 
     f.render_stateful_widget(table, area, &mut ui.sessions_table);
@@ -361,13 +364,13 @@ fn render_session_details(
 
     let last_usage = selected
         .and_then(|r| r.last_usage.as_ref())
-        .map(|usage| usage_line_lang(usage, lang))
+        .map(|usage| usage_line_lang(&ui.display_format, usage, lang))
         .unwrap_or_else(|| format!("{}: -", l("tok in/out/rsn/ttl")));
 
     let total_usage = selected
         .and_then(|r| r.total_usage.as_ref())
         .filter(|u| u.total_tokens > 0)
-        .map(|usage| usage_line_lang(usage, lang))
+        .map(|usage| usage_line_lang(&ui.display_format, usage, lang))
         .unwrap_or_else(|| format!("{}: -", l("tok in/out/rsn/ttl")));
     let posture = selected.map(|row| session_control_posture_lang(row, lang));
 
@@ -578,20 +581,23 @@ fn render_requests_panel(
             let total_dur = duration_short(r.duration_ms);
             let usage = r.usage.as_ref();
             let input = usage
-                .map(|u| tokens_short(u.input_tokens))
+                .map(|u| ui.display_format.tokens_short(u.input_tokens))
                 .unwrap_or_else(|| "-".to_string());
             let output = usage
-                .map(|u| tokens_short(u.output_tokens))
+                .map(|u| ui.display_format.tokens_short(u.output_tokens))
                 .unwrap_or_else(|| "-".to_string());
             let cache_read = usage
-                .map(|u| tokens_short(u.cache_read_tokens_total()))
+                .map(|u| ui.display_format.tokens_short(u.cache_read_tokens_total()))
                 .unwrap_or_else(|| "-".to_string());
             let cache_new = usage
-                .map(|u| tokens_short(u.cache_creation_tokens_total()))
+                .map(|u| {
+                    ui.display_format
+                        .tokens_short(u.cache_creation_tokens_total())
+                })
                 .unwrap_or_else(|| "-".to_string());
             let cache_hit = request_cache_hit_rate_label(r);
             let total_tokens = usage
-                .map(|u| tokens_short(u.total_tokens))
+                .map(|u| ui.display_format.tokens_short(u.total_tokens))
                 .unwrap_or_else(|| "-".to_string());
 
             let has_cache_hit = cache_hit != "-";
@@ -669,7 +675,7 @@ fn render_requests_panel(
     let table = Table::new(rows, widths)
         .header(header)
         .block(block)
-        .row_highlight_style(Style::default().bg(Color::Rgb(32, 39, 48)))
+        .row_highlight_style(Style::default().bg(p.highlight))
         .highlight_spacing(HighlightSpacing::Always);
 
     f.render_stateful_widget(table, area, &mut ui.requests_table);
@@ -6,17 +6,16 @@ use codex_helper_core::fleet::{
 };
 use ratatui::Frame;
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
-use ratatui::prelude::{Color, Line, Modifier, Span, Style, Text};
+use ratatui::prelude::{Line, Modifier, Span, Style, Text};
 use ratatui::widgets::{Block, Borders, Cell, HighlightSpacing, Paragraph, Row, Table, Wrap};
 
 use crate::credentials::CredentialAggregateReadiness;
 use crate::tui::Language;
 use crate::tui::i18n;
-use crate::tui::model::{
-    Palette, format_age, now_ms, short_sid, shorten, shorten_middle, tokens_short,
-};
+use crate::tui::model::{Palette, format_age, now_ms, short_sid, shorten, shorten_middle};
 use crate::tui::state::{FleetViewMode, UiState};
 use crate::tui::view::widgets::kv_line;
+use crate::usage_format::DisplayFormat;
 
 pub(super) fn render_fleet_page(f: &mut Frame<'_>, p: Palette, ui: &mut UiState, area: Rect) {
     ui.sync_fleet_selection();
@@ -145,7 +144,7 @@ fn render_nodes_table(
     )
     .header(header)
     .block(block)
-    .row_highlight_style(Style::default().bg(Color::Rgb(32, 39, 48)))
+    .row_highlight_style(Style::default().bg(p.highlight))
     .highlight_symbol("  ")
     .highlight_spacing(HighlightSpacing::Always);
     f.render_stateful_widget(table, area, &mut ui.fleet_nodes_table);
@@ -208,7 +207,7 @@ fn render_units_table(
     )
     .header(header)
     .block(block)
-    .row_highlight_style(Style::default().bg(Color::Rgb(32, 39, 48)))
+    .row_highlight_style(Style::default().bg(p.highlight))
     .highlight_symbol("  ")
     .highlight_spacing(HighlightSpacing::Always);
     f.render_stateful_widget(table, area, &mut ui.fleet_units_table);
@@ -396,13 +395,14 @@ fn push_snapshot_details(
         l("work unit"),
         Style::default().fg(p.text).add_modifier(Modifier::BOLD),
     )]));
-    push_unit_details(lines, p, lang, unit);
+    push_unit_details(lines, p, lang, &ui.display_format, unit);
 }
 
 fn push_unit_details(
     lines: &mut Vec<Line<'static>>,
     p: Palette,
     lang: Language,
+    format: &DisplayFormat,
     unit: &FleetWorkUnit,
 ) {
     let l = |text| i18n::label(lang, text);
@@ -515,7 +515,7 @@ fn push_unit_details(
             unit.usage.turns_total.unwrap_or(0),
             unit.usage.turns_with_usage.unwrap_or(0),
             total_tokens
-                .map(tokens_short)
+                .map(|tokens| format.tokens_short(tokens))
                 .unwrap_or_else(|| "-".to_string()),
             unit.usage
                 .avg_output_tokens_per_second
@@ -1,6 +1,6 @@
 use ratatui::Frame;
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
-use ratatui::prelude::{Color, Line, Modifier, Span, Style, Text};
+use ratatui::prelude::{Line, Modifier, Span, Style, Text};
 use ratatui::widgets::{
     Block, Borders, Cell, HighlightSpacing, Paragraph, Row, Scrollbar, ScrollbarOrientation,
     ScrollbarState, Table, Wrap,
@@ -121,7 +121,7 @@ pub(super) fn render_history_page(f: &mut Frame<'_>, p: Palette, ui: &mut UiStat
     let table = Table::new(rows, widths)
         .header(header)
         .block(left_block)
-        .row_highlight_style(Style::default().bg(Color::Rgb(32, 39, 48)))
+        .row_highlight_style(Style::default().bg(p.highlight))
         .highlight_symbol("  ")
         .highlight_spacing(HighlightSpacing::Always);
     f.render_stateful_widget(table, columns[0], &mut ui.codex_history_table);
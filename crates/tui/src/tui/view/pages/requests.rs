@@ -1,6 +1,6 @@
 use ratatui::Frame;
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
-use ratatui::prelude::{Color, Line, Modifier, Span, Style, Text};
+use ratatui::prelude::{Line, Modifier, Span, Style, Text};
 use ratatui::widgets::{
     Block, Borders, Cell, HighlightSpacing, Paragraph, Row, Scrollbar, ScrollbarOrientation,
     ScrollbarState, Table, Wrap,
@@ -82,9 +82,14 @@ pub(super) fn render_requests_page(
     } else {
         l("all").to_string()
     };
+    let requests_label = if snapshot.recent_capacity > 0 {
+        format!("{} (<={})", l("Requests"), snapshot.recent_capacity)
+    } else {
+        l("Requests").to_string()
+    };
     let left_title = format!(
         "{}  ({}: {}, {}: {}, {}: {})",
-        l("Requests"),
+        requests_label,
         l("scope"),
         scope_label,
         l("errors_only"),
@@ -219,7 +224,7 @@ pub(super) fn render_requests_page(
     let table = Table::new(rows, widths)
         .header(header)
         .block(left_block)
-        .row_highlight_style(Style::default().bg(Color::Rgb(32, 39, 48)))
+        .row_highlight_style(Style::default().bg(p.highlight))
         .highlight_symbol("  ")
         .highlight_spacing(HighlightSpacing::Always);
     f.render_stateful_widget(table, columns[0], &mut ui.request_page_table);
@@ -286,6 +291,25 @@ pub(super) fn render_requests_page(
             ]);
         }
         lines.push(Line::from(request_identity));
+        if let Some(body_hash) = r.body_hash.as_deref() {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("{}: ", l("body hash")),
+                    Style::default().fg(p.muted),
+                ),
+                Span::styled(shorten_middle(body_hash, 32), Style::default().fg(p.text)),
+            ]));
+        }
+        if let Some(upstream_request_id) = r.upstream_request_id.as_deref() {
+            // Shown whole: it is copied verbatim into provider support tickets.
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("{}: ", l("upstream request id")),
+                    Style::default().fg(p.muted),
+                ),
+                Span::styled(upstream_request_id.to_string(), Style::default().fg(p.text)),
+            ]));
+        }
         lines.push(Line::from(vec![
             Span::styled(format!("{}: ", l("method")), Style::default().fg(p.muted)),
             Span::styled(r.method.clone(), Style::default().fg(p.text)),
@@ -416,7 +440,10 @@ pub(super) fn render_requests_page(
         if let Some(u) = r.usage.as_ref().filter(|u| u.total_tokens > 0) {
             lines.push(Line::from(vec![
                 Span::styled(format!("{}: ", l("usage")), Style::default().fg(p.muted)),
-                Span::styled(usage_line_lang(u, lang), Style::default().fg(p.accent)),
+                Span::styled(
+                    usage_line_lang(&ui.display_format, u, lang),
+                    Style::default().fg(p.accent),
+                ),
             ]));
             let cache_hit = request_cache_hit_rate_label(r);
             if cache_hit != "-" {
@@ -431,7 +458,7 @@ pub(super) fn render_requests_page(
             let mut cost_line = vec![
                 Span::styled(format!("{}: ", l("cost")), Style::default().fg(p.muted)),
                 Span::styled(
-                    r.cost.display_total_with_confidence(),
+                    r.cost.display_total_with_confidence(&ui.display_format),
                     Style::default().fg(p.text),
                 ),
             ];
@@ -525,6 +552,27 @@ pub(super) fn render_requests_page(
             )));
         }
 
+        if let Some((_, body)) = ui.last_error_body.as_ref().filter(|(id, _)| *id == r.id) {
+            lines.push(Line::from(""));
+            lines.push(Line::from(vec![Span::styled(
+                l("Upstream error body"),
+                Style::default().fg(p.text).add_modifier(Modifier::BOLD),
+            )]));
+            let max = 20usize;
+            for line in body.lines().take(max) {
+                lines.push(Line::from(Span::styled(
+                    line.to_string(),
+                    Style::default().fg(p.muted),
+                )));
+            }
+            if body.lines().count() > max {
+                lines.push(Line::from(Span::styled(
+                    format!("... +{} more", body.lines().count() - max),
+                    Style::default().fg(p.muted),
+                )));
+            }
+        }
+
         lines.push(Line::from(""));
         lines.push(Line::from(vec![Span::styled(
             l("Keys"),
@@ -910,6 +958,8 @@ mod tests {
             pricing_catalog: Default::default(),
             stats_5m: WindowStats::default(),
             stats_1h: WindowStats::default(),
+            request_quotas: Vec::new(),
+            recent_capacity: 0,
             service_status: None,
             refreshed_at: Instant::now(),
         }
@@ -920,6 +970,8 @@ mod tests {
             id: u64::from(status_code),
             trace_key: None,
             session_key: Some(session_id.to_string()),
+            body_hash: None,
+            upstream_request_id: None,
             model: None,
             reasoning_effort: None,
             service_tier: None,
@@ -1379,4 +1431,77 @@ mod tests {
         );
         assert!(text.contains(":200"), "{text}");
     }
+
+    #[test]
+    fn request_details_show_body_hash_when_recorded() {
+        let mut snapshot = empty_snapshot();
+        let mut request = request_fixture("sid", 200);
+        request.body_hash =
+            Some("9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08".to_string());
+        snapshot.recent = vec![request];
+        let mut ui = UiState {
+            page: crate::tui::types::Page::Requests,
+            language: crate::tui::Language::En,
+            ..UiState::default()
+        };
+
+        let text = render_requests_text(140, 28, &mut ui, &snapshot);
+
+        assert!(text.contains("body hash: 9f86d08188"), "{text}");
+    }
+
+    #[test]
+    fn request_details_show_the_upstream_request_id() {
+        let mut snapshot = empty_snapshot();
+        let mut request = request_fixture("sid", 502);
+        request.upstream_request_id = Some("req_01JC9Z4Q8X2V".to_string());
+        snapshot.recent = vec![request];
+        let mut ui = UiState {
+            page: crate::tui::types::Page::Requests,
+            language: crate::tui::Language::En,
+            ..UiState::default()
+        };
+
+        let text = render_requests_text(140, 28, &mut ui, &snapshot);
+
+        assert!(
+            text.contains("upstream request id: req_01JC9Z4Q8X2V"),
+            "{text}"
+        );
+    }
+
+    #[test]
+    fn request_details_show_the_loaded_upstream_error_body() {
+        let mut snapshot = empty_snapshot();
+        let request = request_fixture("sid", 502);
+        let request_id = request.id;
+        snapshot.recent = vec![request];
+        let mut ui = UiState {
+            page: crate::tui::types::Page::Requests,
+            language: crate::tui::Language::En,
+            last_error_body: Some((request_id, r#"{"error":"overloaded"}"#.to_string())),
+            ..UiState::default()
+        };
+
+        let text = render_requests_text(140, 60, &mut ui, &snapshot);
+
+        assert!(text.contains("Upstream error body"), "{text}");
+        assert!(text.contains(r#"{"error":"overloaded"}"#), "{text}");
+    }
+
+    #[test]
+    fn requests_title_shows_configured_recent_capacity() {
+        let mut snapshot = empty_snapshot();
+        snapshot.recent = vec![request_fixture("sid", 200)];
+        snapshot.recent_capacity = 2_000;
+        let mut ui = UiState {
+            page: crate::tui::types::Page::Requests,
+            language: crate::tui::Language::En,
+            ..UiState::default()
+        };
+
+        let text = render_requests_text(140, 28, &mut ui, &snapshot);
+
+        assert!(text.contains("Requests (<=2000)"), "{text}");
+    }
 }
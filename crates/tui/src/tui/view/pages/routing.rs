@@ -1,6 +1,6 @@
 use ratatui::Frame;
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
-use ratatui::prelude::{Color, Line, Modifier, Span, Style, Text};
+use ratatui::prelude::{Line, Modifier, Span, Style, Text};
 use ratatui::widgets::{
     Block, Borders, HighlightSpacing, Paragraph, Row, Scrollbar, ScrollbarOrientation,
     ScrollbarState, Table, Wrap,
@@ -17,7 +17,8 @@ use crate::tui::model::{
     latest_provider_balance_fetched_at_ms, now_ms, provider_balance_brief_lang,
     provider_balance_compact_lang, provider_endpoint_balance_snapshot,
     provider_endpoint_current_usage_report_snapshot, provider_usage_rate_summary_lang,
-    provider_usage_source_label_lang, provider_usage_window_summary_lang, shorten_middle,
+    provider_usage_source_label_lang, provider_usage_window_summary_lang, short_sid,
+    shorten_middle,
 };
 use crate::tui::operator_actions::{PendingOperatorAction, balance_refresh_summary_has_warning};
 use crate::tui::state::UiState;
@@ -26,6 +27,8 @@ use crate::tui::{Language, ProviderOption};
 
 const MASTER_DETAIL_MIN_WIDTH: u16 = 118;
 const MASTER_DETAIL_MIN_HEIGHT: u16 = 14;
+/// Error-rate window for the load columns; matches the 5m window on the Stats page.
+const LOAD_ERROR_WINDOW_MS: u64 = 5 * 60 * 1000;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum RoutingTableLayout {
@@ -63,6 +66,7 @@ fn route_strategy_label(strategy: crate::config::RouteStrategy) -> &'static str
         crate::config::RouteStrategy::ManualSticky => "manual-sticky",
         crate::config::RouteStrategy::OrderedFailover => "ordered-failover",
         crate::config::RouteStrategy::RoundRobin => "round-robin",
+        crate::config::RouteStrategy::LeastRecentErrors => "least-recent-errors",
         crate::config::RouteStrategy::TagPreferred => "tag-preferred",
         crate::config::RouteStrategy::Conditional => "conditional",
     }
@@ -93,18 +97,81 @@ fn configured_entry_target_label(routing: &OperatorRoutingSummary, lang: Languag
     })
 }
 
+/// `None` unless `routing.auto_active_by_health` is on; kept apart from the operator's
+/// new-session preference so a health pick is never mistaken for a user pin.
+fn auto_active_label(routing: &OperatorRoutingSummary, lang: Language) -> Option<String> {
+    if !routing.auto_active_by_health {
+        return None;
+    }
+    Some(match routing.auto_active.as_ref() {
+        Some(target) => format!("{}.{}", target.provider_id, target.endpoint_id),
+        None if routing.entry_target.is_some() => match lang {
+            Language::Zh => "被固定目标覆盖".to_string(),
+            Language::En => "overridden by pin".to_string(),
+        },
+        None => match lang {
+            Language::Zh => "等待健康评估".to_string(),
+            Language::En => "awaiting health".to_string(),
+        },
+    })
+}
+
+/// Per-session operator pins; the first two are listed, the rest only counted.
+fn session_pins_label(routing: &OperatorRoutingSummary, lang: Language) -> String {
+    if routing.session_upstream_pins.is_empty() {
+        return String::new();
+    }
+    let mut listed = routing
+        .session_upstream_pins
+        .iter()
+        .take(2)
+        .map(|pin| {
+            format!(
+                "{}→{}.{}",
+                short_sid(pin.session_key.as_deref().unwrap_or("-"), 12),
+                pin.provider_id,
+                pin.endpoint_id
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let rest = routing.session_upstream_pins.len().saturating_sub(2);
+    if rest > 0 {
+        listed.push_str(&format!(" +{rest}"));
+    }
+    match lang {
+        Language::Zh => format!("  会话固定={listed}"),
+        Language::En => format!("  session pins={listed}"),
+    }
+}
+
 fn routing_summary_line(routing: &OperatorRoutingSummary, lang: Language) -> String {
     let configured_target = configured_entry_target_label(routing, lang);
     let new_session_preference = route_target_label(routing.new_session_preference.as_ref(), lang);
+    let auto_active = auto_active_label(routing, lang)
+        .map(|label| match lang {
+            Language::Zh => format!("  健康自动={label}"),
+            Language::En => format!("  health auto-active={label}"),
+        })
+        .unwrap_or_default();
+    let upstream_pin = routing
+        .upstream_pin
+        .as_ref()
+        .map(|pin| match lang {
+            Language::Zh => format!("  全局固定={}.{}", pin.provider_id, pin.endpoint_id),
+            Language::En => format!("  pinned={}.{}", pin.provider_id, pin.endpoint_id),
+        })
+        .unwrap_or_default();
+    let session_pins = session_pins_label(routing, lang);
     match lang {
         Language::Zh => format!(
-            "配置目标={configured_target}  新会话首选={new_session_preference}  策略={}  粘性={}  调度={}",
+            "配置目标={configured_target}  新会话首选={new_session_preference}{auto_active}{upstream_pin}{session_pins}  策略={}  粘性={}  调度={}",
             route_strategy_label(routing.entry_strategy),
             affinity_label(routing.affinity_policy),
             routing.scheduling_preset.as_str(),
         ),
         Language::En => format!(
-            "config target={configured_target}  new-session preference={new_session_preference}  policy={}  affinity={}  scheduling={}",
+            "config target={configured_target}  new-session preference={new_session_preference}{auto_active}{upstream_pin}{session_pins}  policy={}  affinity={}  scheduling={}",
             route_strategy_label(routing.entry_strategy),
             affinity_label(routing.affinity_policy),
             routing.scheduling_preset.as_str(),
@@ -254,6 +321,97 @@ fn capacity_label(capacity: Option<&OperatorProviderCapacity>) -> String {
     }
 }
 
+/// Recent pressure on one candidate: finished requests in the error window, cooldown state, and
+/// the `routing.primary_sticky_ms` hold left after a failover away from it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct CandidateLoad {
+    requests: usize,
+    errors: usize,
+    cooling_down: bool,
+    cooldown_remaining_secs: Option<u64>,
+    sticky_hold_secs: Option<u64>,
+}
+
+impl CandidateLoad {
+    fn error_rate_label(&self) -> String {
+        if self.requests == 0 {
+            return "-".to_string();
+        }
+        format!("{}%", self.errors * 100 / self.requests)
+    }
+
+    /// The cooldown left, or the sticky hold (`hold5m`) when the candidate is not cooling down.
+    fn cooldown_label(&self) -> String {
+        if !self.cooling_down {
+            return match self.sticky_hold_secs {
+                Some(secs) => format!("hold{}", compact_secs_label(secs)),
+                None => "-".to_string(),
+            };
+        }
+        match self.cooldown_remaining_secs {
+            Some(secs) => compact_secs_label(secs),
+            None => "on".to_string(),
+        }
+    }
+
+    /// Error rate and cooldown squeezed into one cell, e.g. `25% cd42s` or `25% hold5m`.
+    fn compact_label(&self) -> String {
+        let wait = if self.cooling_down {
+            Some(format!("cd{}", self.cooldown_label()))
+        } else {
+            self.sticky_hold_secs.map(|_| self.cooldown_label())
+        };
+        match (self.requests > 0, wait) {
+            (false, None) => "-".to_string(),
+            (true, None) => self.error_rate_label(),
+            (false, Some(wait)) => wait,
+            (true, Some(wait)) => format!("{} {wait}", self.error_rate_label()),
+        }
+    }
+}
+
+fn compact_secs_label(secs: u64) -> String {
+    if secs >= 3600 {
+        format!("{}h", secs / 3600)
+    } else if secs >= 60 {
+        format!("{}m", secs / 60)
+    } else {
+        format!("{secs}s")
+    }
+}
+
+fn candidate_load_at(
+    snapshot: &Snapshot,
+    candidate: &OperatorRouteCandidateSummary,
+    endpoint: Option<&OperatorProviderEndpointSummary>,
+    current_time_ms: u64,
+) -> CandidateLoad {
+    let cutoff = current_time_ms.saturating_sub(LOAD_ERROR_WINDOW_MS);
+    let mut load = CandidateLoad::default();
+    for request in snapshot.recent.iter().filter(|request| {
+        request.ended_at_ms >= cutoff
+            && request.provider_id.as_deref() == Some(candidate.provider_id.as_str())
+            && request.endpoint_id.as_deref() == Some(candidate.endpoint_id.as_str())
+    }) {
+        load.requests += 1;
+        if request.status_code >= 400 {
+            load.errors += 1;
+        }
+    }
+    for action in endpoint
+        .into_iter()
+        .flat_map(|endpoint| &endpoint.policy_actions)
+        .filter(|action| action.active_cooldown)
+    {
+        load.cooling_down = true;
+        load.cooldown_remaining_secs = load
+            .cooldown_remaining_secs
+            .max(action.cooldown_remaining_secs);
+    }
+    load.sticky_hold_secs = endpoint.and_then(|endpoint| endpoint.primary_sticky_remaining_secs);
+    load
+}
+
 fn runtime_state_status_label(state: RuntimeConfigState, lang: Language) -> Option<&'static str> {
     match (state, lang) {
         (RuntimeConfigState::Draining, Language::Zh) => Some("排空"),
@@ -345,15 +503,20 @@ fn candidate_style(
 ) -> Style {
     let style = if endpoint.is_none_or(|endpoint| !endpoint.effective_enabled) {
         Style::default().fg(p.muted)
-    } else if endpoint.is_some_and(|endpoint| endpoint.capacity.saturated || !endpoint.routable)
-        || balance.is_some_and(|balance| {
-            balance.stale_at(now_ms())
-                || matches!(
-                    balance.status,
-                    BalanceSnapshotStatus::Error | BalanceSnapshotStatus::Stale
-                )
-        })
-    {
+    } else if endpoint.is_some_and(|endpoint| {
+        endpoint.capacity.saturated
+            || !endpoint.routable
+            || endpoint
+                .policy_actions
+                .iter()
+                .any(|action| action.active_cooldown)
+    }) || balance.is_some_and(|balance| {
+        balance.stale_at(now_ms())
+            || matches!(
+                balance.status,
+                BalanceSnapshotStatus::Error | BalanceSnapshotStatus::Stale
+            )
+    }) {
         Style::default().fg(p.warn)
     } else if balance.is_some_and(|balance| {
         balance.status == BalanceSnapshotStatus::Exhausted && !balance.routing_ignored_exhaustion()
@@ -388,6 +551,13 @@ fn route_order_label(
 ) -> String {
     let marker = if candidate_is_new_session_preference(routing, candidate) {
         "P"
+    } else if routing.new_session_preference.is_none()
+        && routing.auto_active.as_ref().is_some_and(|target| {
+            target.provider_id == candidate.provider_id
+                && target.endpoint_id == candidate.endpoint_id
+        })
+    {
+        "A"
     } else {
         " "
     };
@@ -423,6 +593,8 @@ fn table_header_labels(layout: RoutingTableLayout, lang: Language) -> Vec<&'stat
                 "优先",
                 "状态",
                 "并发",
+                "错误率",
+                "冷却",
                 "余额/额度",
             ]
         }
@@ -435,11 +607,22 @@ fn table_header_labels(layout: RoutingTableLayout, lang: Language) -> Vec<&'stat
                 "Pri",
                 "State",
                 "Capacity",
+                "Err 5m",
+                "Cooldown",
                 "Balance/Quota",
             ]
         }
         (RoutingTableLayout::Regular, Language::Zh) => {
-            vec!["顺序", "组", "目标", "优先", "状态", "并发", "余额/额度"]
+            vec![
+                "顺序",
+                "组",
+                "目标",
+                "优先",
+                "状态",
+                "并发",
+                "错误/冷却",
+                "余额/额度",
+            ]
         }
         (RoutingTableLayout::Regular, Language::En) => {
             vec![
@@ -449,14 +632,17 @@ fn table_header_labels(layout: RoutingTableLayout, lang: Language) -> Vec<&'stat
                 "Pri",
                 "State",
                 "Capacity",
+                "Err/CD",
                 "Balance/Quota",
             ]
         }
         (RoutingTableLayout::Compact, Language::Zh) => {
-            vec!["顺序", "组", "目标", "优先", "状态", "并发", "余额"]
+            vec!["顺序", "组", "目标", "优先", "状态", "并发", "错误", "余额"]
         }
         (RoutingTableLayout::Compact, Language::En) => {
-            vec!["Order", "G", "Target", "Pri", "State", "Cap", "Balance"]
+            vec![
+                "Order", "G", "Target", "Pri", "State", "Cap", "Err", "Balance",
+            ]
         }
         (RoutingTableLayout::Tiny, Language::Zh) => vec!["顺序", "目标", "状态", "余额"],
         (RoutingTableLayout::Tiny, Language::En) => vec!["#", "Target", "State", "Balance"],
@@ -477,16 +663,19 @@ fn table_constraints(layout: RoutingTableLayout) -> Vec<Constraint> {
             Constraint::Length(6),
             Constraint::Length(10),
             Constraint::Length(11),
+            Constraint::Length(6),
+            Constraint::Length(8),
             Constraint::Length(28),
         ],
         RoutingTableLayout::Regular => vec![
             Constraint::Length(6),
             Constraint::Length(5),
-            Constraint::Min(22),
+            Constraint::Min(18),
             Constraint::Length(6),
             Constraint::Length(10),
             Constraint::Length(10),
-            Constraint::Length(24),
+            Constraint::Length(9),
+            Constraint::Length(20),
         ],
         RoutingTableLayout::Compact => vec![
             Constraint::Length(5),
@@ -495,6 +684,7 @@ fn table_constraints(layout: RoutingTableLayout) -> Vec<Constraint> {
             Constraint::Length(4),
             Constraint::Length(8),
             Constraint::Length(8),
+            Constraint::Length(9),
             Constraint::Min(10),
         ],
         RoutingTableLayout::Tiny => vec![
@@ -521,6 +711,7 @@ fn candidate_cells(
         .unwrap_or_else(|| "-".to_string());
     let status = endpoint_status_label(endpoint, lang).to_string();
     let capacity = capacity_label(endpoint.map(|endpoint| &endpoint.capacity));
+    let load = candidate_load_at(snapshot, candidate, endpoint, now_ms());
     match layout {
         RoutingTableLayout::Wide => vec![
             order,
@@ -530,16 +721,19 @@ fn candidate_cells(
             priority,
             status,
             capacity,
+            load.error_rate_label(),
+            load.cooldown_label(),
             candidate_balance_label(snapshot, candidate, 27, lang),
         ],
         RoutingTableLayout::Regular => vec![
             order,
             group,
-            candidate_target_label(candidate, 21),
+            candidate_target_label(candidate, 17),
             priority,
             status,
             capacity,
-            candidate_balance_label(snapshot, candidate, 23, lang),
+            load.compact_label(),
+            candidate_balance_label(snapshot, candidate, 19, lang),
         ],
         RoutingTableLayout::Compact => vec![
             order,
@@ -548,11 +742,18 @@ fn candidate_cells(
             priority,
             status,
             capacity,
+            load.compact_label(),
             candidate_balance_label(snapshot, candidate, 9, lang),
         ],
         RoutingTableLayout::Tiny => vec![
             format!("{order}\nG{group}"),
-            format!("{}\nPri {priority}", candidate_target_label(candidate, 17)),
+            match load.compact_label().as_str() {
+                "-" => format!("{}\nPri {priority}", candidate_target_label(candidate, 17)),
+                load => format!(
+                    "{}\nPri {priority} {load}",
+                    candidate_target_label(candidate, 17)
+                ),
+            },
             format!("{status}\n{capacity}"),
             candidate_balance_label(snapshot, candidate, 11, lang),
         ],
@@ -651,7 +852,7 @@ fn render_candidate_table(
     let table = Table::new(rows, table_constraints(layout))
         .header(table_header(layout, ui.language).style(Style::default().fg(p.muted)))
         .block(block)
-        .row_highlight_style(Style::default().bg(Color::Rgb(32, 39, 48)).fg(p.text))
+        .row_highlight_style(Style::default().bg(p.highlight).fg(p.text))
         .highlight_symbol("> ")
         .highlight_spacing(HighlightSpacing::Always);
     f.render_stateful_widget(table, area, &mut ui.routing_candidates_table);
@@ -748,6 +949,18 @@ fn push_routing_policy_lines(
             p.good
         }),
     )));
+    if let Some(auto_active) = auto_active_label(routing, ui.language) {
+        lines.push(Line::from(Span::styled(
+            shorten_middle(
+                &match ui.language {
+                    Language::Zh => format!("健康自动选择={auto_active}"),
+                    Language::En => format!("health auto-active={auto_active}"),
+                },
+                max_width,
+            ),
+            Style::default().fg(p.muted),
+        )));
+    }
     lines.push(Line::from(match ui.language {
         Language::Zh => format!(
             "策略={}  粘性={}",
@@ -945,6 +1158,15 @@ fn selected_candidate_detail_lines(
         ),
         Style::default().fg(if has_active_cooldown { p.warn } else { p.muted }),
     )));
+    if let Some(seconds) = endpoint.and_then(|endpoint| endpoint.primary_sticky_remaining_secs) {
+        lines.push(Line::from(Span::styled(
+            match ui.language {
+                Language::Zh => format!("主路由保持={seconds}s（故障转移后暂不回切）"),
+                Language::En => format!("sticky hold={seconds}s (kept off after a failover)"),
+            },
+            Style::default().fg(p.muted),
+        )));
+    }
     lines.push(Line::from(Span::styled(
         shorten_middle(
             &match ui.language {
@@ -1135,7 +1357,16 @@ fn render_legacy_provider_table(
     }
     .style(Style::default().fg(p.muted).add_modifier(Modifier::BOLD));
     let rows = providers.iter().map(|provider| {
-        let state = if provider.effective_enabled {
+        let outside_schedule = provider
+            .schedule
+            .as_ref()
+            .is_some_and(|schedule| !schedule.active);
+        let state = if provider.effective_enabled && outside_schedule {
+            match lang {
+                Language::Zh => "时段外",
+                Language::En => "off-schedule",
+            }
+        } else if provider.effective_enabled {
             match lang {
                 Language::Zh => "可用",
                 Language::En => "ready",
@@ -1189,7 +1420,7 @@ fn render_legacy_provider_table(
     )
     .header(header)
     .block(block)
-    .row_highlight_style(Style::default().bg(Color::Rgb(32, 39, 48)).fg(p.text))
+    .row_highlight_style(Style::default().bg(p.highlight).fg(p.text))
     .highlight_symbol("> ")
     .highlight_spacing(HighlightSpacing::Always);
     f.render_stateful_widget(table, area, &mut ui.providers_table);
@@ -1282,6 +1513,7 @@ mod tests {
             runtime_state_override: None,
             capacity: OperatorProviderCapacity::default(),
             policy_actions: Vec::new(),
+            primary_sticky_remaining_secs: None,
         }
     }
 
@@ -1312,14 +1544,166 @@ mod tests {
                     endpoint_id: endpoint_id.to_string(),
                 }
             }),
+            auto_active_by_health: false,
+            auto_active: None,
             affinity_policy: crate::config::RouteAffinityPolicy::FallbackSticky,
             scheduling_preset: crate::config::SchedulingPreset::Balanced,
             fallback_ttl_ms: None,
             reprobe_preferred_after_ms: None,
+            upstream_pin: None,
+            session_upstream_pins: Vec::new(),
             candidates: Vec::new(),
         }
     }
 
+    fn finished_request(
+        provider_id: &str,
+        endpoint_id: &str,
+        status_code: u16,
+        ended_at_ms: u64,
+    ) -> crate::dashboard_core::OperatorRequestSummary {
+        crate::dashboard_core::OperatorRequestSummary {
+            id: ended_at_ms,
+            trace_key: None,
+            session_key: None,
+            body_hash: None,
+            upstream_request_id: None,
+            model: None,
+            reasoning_effort: None,
+            service_tier: None,
+            provider_id: Some(provider_id.to_string()),
+            endpoint_id: Some(endpoint_id.to_string()),
+            provider_endpoint_key: None,
+            route_path: Vec::new(),
+            upstream_origin: None,
+            usage: None,
+            cache_accounting_convention: Default::default(),
+            cost: crate::pricing::CostBreakdown::default(),
+            retry: None,
+            provider_signal_codes: Vec::new(),
+            policy_action_codes: Vec::new(),
+            observability: crate::dashboard_core::OperatorRequestObservability {
+                duration_ms: Some(100),
+                ttfb_ms: None,
+                generation_ms: None,
+                output_tokens_per_second: None,
+                attempt_count: 1,
+                route_attempt_count: 0,
+                retried: false,
+                cross_provider_failover: false,
+                same_provider_retry: false,
+                fast_mode: false,
+                streaming: false,
+            },
+            service: "codex".to_string(),
+            method: "POST".to_string(),
+            path: "/v1/responses".to_string(),
+            status_code,
+            duration_ms: 100,
+            ttfb_ms: None,
+            streaming: false,
+            ended_at_ms,
+        }
+    }
+
+    #[test]
+    fn candidate_load_counts_recent_errors_and_active_cooldown() {
+        let now = 60 * 60_000;
+        let snapshot = Snapshot {
+            recent: vec![
+                finished_request("input", "default", 200, now - 1_000),
+                finished_request("input", "default", 502, now - 2_000),
+                finished_request("input", "default", 200, now - 3_000),
+                finished_request("input", "default", 429, now - 4_000),
+                finished_request("input", "default", 500, now - LOAD_ERROR_WINDOW_MS - 1),
+                finished_request("input", "backup", 500, now - 1_000),
+                finished_request("other", "default", 500, now - 1_000),
+            ],
+            ..Snapshot::default()
+        };
+        let candidate = candidate("input", "default");
+        let mut endpoint = endpoint_with_credential(CredentialReadinessCode::Ready);
+
+        let load = candidate_load_at(&snapshot, &candidate, Some(&endpoint), now);
+        assert_eq!((load.requests, load.errors), (4, 2));
+        assert_eq!(load.error_rate_label(), "50%");
+        assert_eq!(load.cooldown_label(), "-");
+        assert_eq!(load.compact_label(), "50%");
+
+        endpoint.policy_actions = vec![
+            crate::dashboard_core::OperatorPolicyActionSummary {
+                active_cooldown: true,
+                code: "upstream_429".to_string(),
+                cooldown_remaining_secs: Some(42),
+            },
+            crate::dashboard_core::OperatorPolicyActionSummary {
+                active_cooldown: false,
+                code: "stale".to_string(),
+                cooldown_remaining_secs: Some(900),
+            },
+        ];
+        let load = candidate_load_at(&snapshot, &candidate, Some(&endpoint), now);
+        assert_eq!(load.cooldown_label(), "42s");
+        assert_eq!(load.compact_label(), "50% cd42s");
+
+        let idle = candidate_load_at(&Snapshot::default(), &candidate, Some(&endpoint), now);
+        assert_eq!(idle.error_rate_label(), "-");
+        assert_eq!(idle.compact_label(), "cd42s");
+    }
+
+    #[test]
+    fn candidate_load_shows_the_primary_sticky_hold_outside_a_cooldown() {
+        let now = 60 * 60_000;
+        let snapshot = Snapshot {
+            recent: vec![
+                finished_request("input", "default", 200, now - 1_000),
+                finished_request("input", "default", 502, now - 2_000),
+            ],
+            ..Snapshot::default()
+        };
+        let candidate = candidate("input", "default");
+        let mut endpoint = endpoint_with_credential(CredentialReadinessCode::Ready);
+        endpoint.primary_sticky_remaining_secs = Some(240);
+
+        let load = candidate_load_at(&snapshot, &candidate, Some(&endpoint), now);
+        assert_eq!(load.cooldown_label(), "hold4m");
+        assert_eq!(load.compact_label(), "50% hold4m");
+
+        endpoint.policy_actions = vec![crate::dashboard_core::OperatorPolicyActionSummary {
+            active_cooldown: true,
+            code: "upstream_429".to_string(),
+            cooldown_remaining_secs: Some(42),
+        }];
+        let load = candidate_load_at(&snapshot, &candidate, Some(&endpoint), now);
+        assert_eq!(load.cooldown_label(), "42s");
+        assert_eq!(load.compact_label(), "50% cd42s");
+    }
+
+    #[test]
+    fn routing_summary_line_lists_session_pins() {
+        let pin = |session_key: &str, provider_id: &str| {
+            crate::dashboard_core::OperatorUpstreamPinSummary {
+                provider_id: provider_id.to_string(),
+                endpoint_id: "default".to_string(),
+                failover: false,
+                session_key: Some(session_key.to_string()),
+            }
+        };
+        let mut routing = routing_summary(None, None);
+        assert!(!routing_summary_line(&routing, Language::En).contains("session pins"));
+
+        routing.session_upstream_pins = vec![
+            pin("sid-a", "input"),
+            pin("sid-b", "backup"),
+            pin("sid-c", "input"),
+        ];
+        let line = routing_summary_line(&routing, Language::En);
+        assert!(
+            line.contains("session pins=sid-a→input.default, sid-b→backup.default +1"),
+            "{line}"
+        );
+    }
+
     #[test]
     fn routing_table_layout_has_stable_width_boundaries() {
         for width in [160, 132] {
@@ -1527,11 +1911,13 @@ mod tests {
 
         assert_eq!(
             table_header_labels(RoutingTableLayout::Compact, Language::Zh),
-            vec!["顺序", "组", "目标", "优先", "状态", "并发", "余额"]
+            vec!["顺序", "组", "目标", "优先", "状态", "并发", "错误", "余额"]
         );
         assert_eq!(
             table_header_labels(RoutingTableLayout::Compact, Language::En),
-            vec!["Order", "G", "Target", "Pri", "State", "Cap", "Balance"]
+            vec![
+                "Order", "G", "Target", "Pri", "State", "Cap", "Err", "Balance"
+            ]
         );
     }
 
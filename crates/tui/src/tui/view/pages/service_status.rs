@@ -4,7 +4,7 @@ use codex_helper_core::service_status::{
 };
 use ratatui::Frame;
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
-use ratatui::prelude::{Color, Line, Modifier, Span, Style, Text};
+use ratatui::prelude::{Line, Modifier, Span, Style, Text};
 use ratatui::widgets::{
     Block, Borders, Cell, HighlightSpacing, Paragraph, Row, Scrollbar, ScrollbarOrientation,
     ScrollbarState, Table, Wrap,
@@ -170,7 +170,7 @@ fn render_status_table(
     let table = Table::new(rows, service_status_table_constraints(area.width))
         .header(header)
         .block(block)
-        .row_highlight_style(Style::default().bg(Color::Rgb(32, 39, 48)))
+        .row_highlight_style(Style::default().bg(p.highlight))
         .highlight_spacing(HighlightSpacing::Always);
     f.render_stateful_widget(table, area, &mut ui.service_status_table);
     if row_count > visible_rows {
@@ -1,6 +1,6 @@
 use ratatui::Frame;
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
-use ratatui::prelude::{Color, Line, Modifier, Span, Style, Text};
+use ratatui::prelude::{Line, Modifier, Span, Style, Text};
 use ratatui::widgets::{
     Block, Borders, Cell, HighlightSpacing, Paragraph, Row, Scrollbar, ScrollbarOrientation,
     ScrollbarState, Table, Wrap,
@@ -14,7 +14,7 @@ use crate::tui::model::{
     session_cwd_detail_lang, session_observation_scope_label_lang,
     session_observed_provider_balance_brief_lang, session_observed_provider_balance_snapshot,
     session_transcript_host_status_lang, short_sid, shorten, shorten_middle, status_style,
-    tokens_short, usage_line_lang,
+    usage_line_lang,
 };
 use crate::tui::state::UiState;
 use crate::tui::view::widgets::{kv_line, master_detail_fits, max_wrapped_vertical_scroll};
@@ -128,7 +128,7 @@ pub(super) fn render_sessions_page(
             let tok = row
                 .total_usage
                 .as_ref()
-                .map(|u| tokens_short(u.total_tokens))
+                .map(|u| ui.display_format.tokens_short(u.total_tokens))
                 .unwrap_or_else(|| "-".to_string());
             let tok_per_second = format_tok_per_second(row.last_output_tokens_per_second);
             let control = session_control_table_label(row, if compact_table { 3 } else { 18 });
@@ -198,7 +198,7 @@ pub(super) fn render_sessions_page(
     let table = Table::new(rows, widths)
         .header(header)
         .block(left_block)
-        .row_highlight_style(Style::default().bg(Color::Rgb(32, 39, 48)))
+        .row_highlight_style(Style::default().bg(p.highlight))
         .highlight_symbol("  ")
         .highlight_spacing(HighlightSpacing::Always);
     f.render_stateful_widget(table, columns[0], &mut ui.sessions_page_table);
@@ -460,7 +460,7 @@ pub(super) fn render_sessions_page(
             .total_usage
             .as_ref()
             .filter(|u| u.total_tokens > 0)
-            .map(|usage| usage_line_lang(usage, lang))
+            .map(|usage| usage_line_lang(&ui.display_format, usage, lang))
             .unwrap_or_else(|| "tok in/out/rsn/ttl: -".to_string());
         let last_tok_per_second = format_tok_per_second(row.last_output_tokens_per_second);
         let avg_tok_per_second = format_tok_per_second(row.avg_output_tokens_per_second);
@@ -622,6 +622,22 @@ pub(super) fn render_settings_page(
             Style::default().fg(p.muted),
         ),
     ]));
+    lines.push(Line::from(vec![
+        Span::styled(
+            i18n::text(ui.language, msg::THEME_LABEL),
+            Style::default().fg(p.muted),
+        ),
+        Span::styled(ui.theme.as_str(), Style::default().fg(p.text)),
+        Span::styled(
+            match (ui.language, ui.runtime_connection.is_remote_observer()) {
+                (Language::Zh, false) => "  （T 切换主题并保存到 config.toml）",
+                (Language::En, false) => "  (T cycles the theme and saves it to config.toml)",
+                (Language::Zh, true) => "  （T 仅切换当前 TUI 会话）",
+                (Language::En, true) => "  (T changes this TUI session only)",
+            },
+            Style::default().fg(p.muted),
+        ),
+    ]));
 
     if ui.allows_local_codex_switch() {
         lines.push(Line::from(""));
@@ -726,8 +742,12 @@ pub(super) fn render_settings_page(
             crate::config::config_file_path().display()
         )));
         lines.push(Line::from(format!("  home: {}", home.display())));
+        let request_log_sampling = ui
+            .request_log_sample_rate
+            .map(|rate| format!(" (sampled: {:.0}% of successes)", rate * 100.0))
+            .unwrap_or_default();
         lines.push(Line::from(format!(
-            "  request log: {}",
+            "  request log: {}{request_log_sampling}",
             crate::logging::request_log_path().display()
         )));
         lines.push(Line::from(format!(
@@ -7,21 +7,27 @@ use ratatui::widgets::{
 };
 use unicode_width::UnicodeWidthStr;
 
+use crate::dashboard_core::{
+    LatencyHistogram, OperatorProviderCapacity, OperatorRequestSummary, WindowStats,
+};
 use crate::quota_analytics::{
     PoolQuotaAnalytics, QuotaAnalyticsSupport, QuotaFreshnessStatus, QuotaPaceStatus,
     QuotaRateStatus, QuotaReconciliationStatus,
 };
 use crate::quota_pool::{IdentityConfidence, QuotaQuantity, QuotaUnit, QuotaWindowKind};
-use crate::state::{ProviderBalanceSnapshot, UsageBucket, UsageDayDimensionRow, UsageDayView};
+use crate::state::{
+    ProviderBalanceSnapshot, RequestQuotaUsage, UsageBucket, UsageDayDimensionRow, UsageDayView,
+};
 use crate::tui::Language;
 use crate::tui::ProviderOption;
 use crate::tui::model::{
     Palette, Snapshot, duration_short, format_age, now_ms, provider_usage_rate_summary_lang,
     provider_usage_report_is_current, provider_usage_source_label_lang,
-    provider_usage_window_brief_lang, provider_usage_window_summary_lang, shorten, tokens_short,
+    provider_usage_window_brief_lang, provider_usage_window_summary_lang, shorten,
 };
 use crate::tui::state::UiState;
-use crate::tui::types::StatsFocus;
+use crate::tui::types::{StatsFocus, StatsLatencyWindow};
+use crate::usage_format::DisplayFormat;
 
 #[derive(Default)]
 struct SelectedQuotaUsage<'a> {
@@ -35,7 +41,7 @@ pub(super) fn render_stats_page(
     p: Palette,
     ui: &mut UiState,
     snapshot: &Snapshot,
-    _providers: &[ProviderOption],
+    providers: &[ProviderOption],
     area: Rect,
 ) {
     let compact = area.width < 100 || area.height < 19;
@@ -57,7 +63,7 @@ pub(super) fn render_stats_page(
         7
     };
     let quota_row_height = desired_quota_row_height.min(area.height);
-    let compact_local_usage_height = area.height.saturating_sub(quota_row_height).min(6);
+    let compact_local_usage_height = area.height.saturating_sub(quota_row_height).min(7);
     let rows = Layout::default()
         .direction(Direction::Vertical)
         .constraints(if compact {
@@ -69,19 +75,19 @@ pub(super) fn render_stats_page(
         } else {
             vec![
                 Constraint::Length(quota_row_height),
-                Constraint::Length(6),
-                Constraint::Length(5),
+                Constraint::Length(7),
+                Constraint::Length(if area.height >= 30 { 5 } else { 4 }),
                 Constraint::Min(0),
             ]
         })
         .split(area);
 
     render_quota_kpi_row(f, p, ui, snapshot, &selected_usage, rows[0], compact);
-    render_local_usage_row(f, p, ui.language, &snapshot.usage_day, rows[1]);
+    render_local_usage_row(f, p, ui.language, &ui.display_format, snapshot, rows[1]);
     if compact {
         render_dimension_area(f, p, ui, snapshot, rows[2], true);
     } else {
-        render_activity_row(f, p, ui.language, &snapshot.usage_day, rows[2]);
+        render_activity_row(f, p, ui.language, &snapshot.usage_day, providers, rows[2]);
         render_dimension_area(f, p, ui, snapshot, rows[3], false);
     }
 }
@@ -310,14 +316,14 @@ fn render_quota_kpi_row(
             panel_kv_line(
                 p,
                 "local cost",
-                &summary.cost.display_total(),
+                &summary.cost.display_total(&ui.display_format),
                 p.text,
                 cols[3],
             ),
             panel_kv_line(
                 p,
                 "local tokens",
-                &tokens_short(summary.usage.total_tokens),
+                &ui.display_format.tokens_short(summary.usage.total_tokens),
                 p.accent,
                 cols[3],
             ),
@@ -331,12 +337,25 @@ fn render_activity_row(
     p: Palette,
     lang: Language,
     usage: &UsageDayView,
+    providers: &[ProviderOption],
     area: Rect,
 ) {
+    let concurrency = concurrency_limit_rows(providers);
     let cols = Layout::default()
         .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(58), Constraint::Percentage(42)])
+        .constraints(if concurrency.is_empty() {
+            vec![Constraint::Percentage(58), Constraint::Percentage(42)]
+        } else {
+            vec![
+                Constraint::Percentage(42),
+                Constraint::Percentage(32),
+                Constraint::Percentage(26),
+            ]
+        })
         .split(area);
+    if !concurrency.is_empty() {
+        render_concurrency_limits(f, p, lang, &concurrency, cols[2]);
+    }
 
     let data = usage
         .hourly
@@ -418,13 +437,92 @@ fn render_activity_row(
     );
 }
 
+/// Providers (or endpoints, when the provider has no shared pool) with a concurrency limit,
+/// those held below their configured maximum by `lb.adaptive_concurrency` first.
+fn concurrency_limit_rows(
+    providers: &[ProviderOption],
+) -> Vec<(String, &OperatorProviderCapacity)> {
+    let mut rows = Vec::new();
+    for provider in providers {
+        if provider.capacity.limit.is_some() {
+            rows.push((provider.name.clone(), &provider.capacity));
+            continue;
+        }
+        for endpoint in &provider.endpoints {
+            if endpoint.capacity.limit.is_some() {
+                rows.push((
+                    format!("{}/{}", provider.name, endpoint.name),
+                    &endpoint.capacity,
+                ));
+            }
+        }
+    }
+    rows.sort_by_key(|(_, capacity)| !concurrency_is_throttled(capacity));
+    rows
+}
+
+fn concurrency_is_throttled(capacity: &OperatorProviderCapacity) -> bool {
+    matches!(
+        (capacity.limit, capacity.effective_max_concurrent_requests),
+        (Some(limit), Some(max)) if limit < max
+    )
+}
+
+fn render_concurrency_limits(
+    f: &mut Frame<'_>,
+    p: Palette,
+    lang: Language,
+    rows: &[(String, &OperatorProviderCapacity)],
+    area: Rect,
+) {
+    let lines = rows
+        .iter()
+        .map(|(name, capacity)| {
+            let limit = capacity.limit.unwrap_or_default();
+            let active = capacity
+                .active
+                .map_or_else(|| "-".to_string(), |active| active.to_string());
+            let mut spans = vec![
+                Span::styled(shorten(name, 14), Style::default().fg(p.text)),
+                Span::raw(" "),
+                Span::styled(
+                    format!("{active}/{limit}"),
+                    Style::default().fg(if capacity.saturated { p.warn } else { p.text }),
+                ),
+            ];
+            if concurrency_is_throttled(capacity)
+                && let Some(max) = capacity.effective_max_concurrent_requests
+            {
+                spans.push(Span::raw(" "));
+                spans.push(Span::styled(
+                    format!("max {max}"),
+                    Style::default().fg(p.warn),
+                ));
+            }
+            Line::from(spans)
+        })
+        .collect::<Vec<_>>();
+    render_info_block(
+        f,
+        p,
+        match lang {
+            Language::Zh => "并发上限",
+            Language::En => "Concurrency",
+        },
+        lines,
+        area,
+    );
+}
+
 fn render_local_usage_row(
     f: &mut Frame<'_>,
     p: Palette,
     lang: Language,
-    usage: &UsageDayView,
+    format: &DisplayFormat,
+    snapshot: &Snapshot,
     area: Rect,
 ) {
+    let usage = &snapshot.usage_day;
     let cols = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([Constraint::Percentage(58), Constraint::Percentage(42)])
@@ -435,6 +533,76 @@ fn render_local_usage_row(
         .requests_total
         .saturating_sub(summary.requests_error);
 
+    let mut usage_lines = vec![
+        Line::from(vec![
+            muted(p, "tokens "),
+            Span::styled(
+                format.tokens_short(metrics.total_tokens),
+                Style::default().fg(p.accent),
+            ),
+            Span::raw("  "),
+            muted(p, "cost "),
+            Span::styled(
+                summary.cost.display_total(format),
+                Style::default().fg(p.text),
+            ),
+        ]),
+        Line::from(vec![
+            muted(p, "in "),
+            Span::styled(
+                format.tokens_short(metrics.input_tokens),
+                Style::default().fg(p.text),
+            ),
+            Span::raw("  "),
+            muted(p, "out "),
+            Span::styled(
+                format.tokens_short(metrics.output_tokens),
+                Style::default().fg(p.text),
+            ),
+            Span::raw("  "),
+            muted(p, "reasoning "),
+            Span::styled(
+                format.tokens_short(metrics.reasoning_output_tokens_total()),
+                Style::default().fg(p.text),
+            ),
+        ]),
+        Line::from(vec![
+            muted(p, "cache read "),
+            Span::styled(
+                format.tokens_short(metrics.cache_read_tokens_total()),
+                Style::default().fg(p.text),
+            ),
+            Span::raw("  "),
+            muted(p, "cache write "),
+            Span::styled(
+                format.tokens_short(metrics.cache_creation_tokens_total()),
+                Style::default().fg(p.text),
+            ),
+        ]),
+        Line::from(vec![
+            muted(p, "requests "),
+            Span::styled(
+                format.count(summary.requests_total),
+                Style::default().fg(p.text),
+            ),
+            Span::raw("  "),
+            muted(p, "ok "),
+            Span::styled(ok.to_string(), Style::default().fg(p.good)),
+            Span::raw("  "),
+            muted(p, "err "),
+            Span::styled(
+                summary.requests_error.to_string(),
+                Style::default().fg(if summary.requests_error > 0 {
+                    p.warn
+                } else {
+                    p.muted
+                }),
+            ),
+        ]),
+    ];
+    if let Some(line) = request_quota_line(p, format, &snapshot.request_quotas) {
+        usage_lines.push(line);
+    }
     render_info_block(
         f,
         p,
@@ -442,70 +610,7 @@ fn render_local_usage_row(
             Language::Zh => "本机用量",
             Language::En => "Local Usage",
         },
-        vec![
-            Line::from(vec![
-                muted(p, "tokens "),
-                Span::styled(
-                    tokens_short(metrics.total_tokens),
-                    Style::default().fg(p.accent),
-                ),
-                Span::raw("  "),
-                muted(p, "cost "),
-                Span::styled(summary.cost.display_total(), Style::default().fg(p.text)),
-            ]),
-            Line::from(vec![
-                muted(p, "in "),
-                Span::styled(
-                    tokens_short(metrics.input_tokens),
-                    Style::default().fg(p.text),
-                ),
-                Span::raw("  "),
-                muted(p, "out "),
-                Span::styled(
-                    tokens_short(metrics.output_tokens),
-                    Style::default().fg(p.text),
-                ),
-                Span::raw("  "),
-                muted(p, "reasoning "),
-                Span::styled(
-                    tokens_short(metrics.reasoning_output_tokens_total()),
-                    Style::default().fg(p.text),
-                ),
-            ]),
-            Line::from(vec![
-                muted(p, "cache read "),
-                Span::styled(
-                    tokens_short(metrics.cache_read_tokens_total()),
-                    Style::default().fg(p.text),
-                ),
-                Span::raw("  "),
-                muted(p, "cache write "),
-                Span::styled(
-                    tokens_short(metrics.cache_creation_tokens_total()),
-                    Style::default().fg(p.text),
-                ),
-            ]),
-            Line::from(vec![
-                muted(p, "requests "),
-                Span::styled(
-                    summary.requests_total.to_string(),
-                    Style::default().fg(p.text),
-                ),
-                Span::raw("  "),
-                muted(p, "ok "),
-                Span::styled(ok.to_string(), Style::default().fg(p.good)),
-                Span::raw("  "),
-                muted(p, "err "),
-                Span::styled(
-                    summary.requests_error.to_string(),
-                    Style::default().fg(if summary.requests_error > 0 {
-                        p.warn
-                    } else {
-                        p.muted
-                    }),
-                ),
-            ]),
-        ],
+        usage_lines,
         cols[0],
     );
 
@@ -565,6 +670,18 @@ fn render_local_usage_row(
                 ),
             ]),
             kv_line(p, "reason", &shorten(&reasons, 32), p.muted),
+            Line::from(vec![
+                muted(p, "fallback 5m "),
+                Span::styled(
+                    fallback_share_text(&snapshot.stats_5m),
+                    Style::default().fg(fallback_share_color(p, &snapshot.stats_5m)),
+                ),
+                muted(p, "  1h "),
+                Span::styled(
+                    fallback_share_text(&snapshot.stats_1h),
+                    Style::default().fg(fallback_share_color(p, &snapshot.stats_1h)),
+                ),
+            ]),
             Line::from(vec![
                 muted(p, "coverage "),
                 Span::styled(
@@ -588,6 +705,87 @@ fn render_local_usage_row(
     );
 }
 
+/// Shows the fullest `quota` budget: the busiest provider, or its busiest tenant with per-tenant
+/// quotas.
+fn request_quota_line(
+    p: Palette,
+    format: &DisplayFormat,
+    quotas: &[RequestQuotaUsage],
+) -> Option<Line<'static>> {
+    fn fill(used: u64, limit: Option<u64>) -> f64 {
+        limit.map_or(0.0, |limit| used as f64 / limit.max(1) as f64)
+    }
+    let fullness = |quota: &RequestQuotaUsage| {
+        fill(quota.requests, quota.max_requests)
+            .max(fill(quota.output_tokens, quota.max_output_tokens))
+    };
+    let quota = quotas
+        .iter()
+        .max_by(|left, right| fullness(left).total_cmp(&fullness(right)))?;
+    let color = match fullness(quota) {
+        full if full >= 1.0 => p.bad,
+        full if full >= 0.8 => p.warn,
+        _ => p.good,
+    };
+    let window = match quota.window_secs {
+        secs if secs % 3_600 == 0 => format!("{}h", secs / 3_600),
+        secs if secs % 60 == 0 => format!("{}m", secs / 60),
+        secs => format!("{secs}s"),
+    };
+    let mut spans = vec![muted(p, &format!("quota {window} "))];
+    if let Some(max_requests) = quota.max_requests {
+        spans.push(muted(p, "req "));
+        spans.push(Span::styled(
+            format!("{}/{}", quota.requests, max_requests),
+            Style::default().fg(color),
+        ));
+        spans.push(Span::raw("  "));
+    }
+    if let Some(max_output_tokens) = quota.max_output_tokens {
+        spans.push(muted(p, "out "));
+        spans.push(Span::styled(
+            format!(
+                "{}/{}",
+                format.tokens_short(i64::try_from(quota.output_tokens).unwrap_or(i64::MAX)),
+                format.tokens_short(i64::try_from(max_output_tokens).unwrap_or(i64::MAX))
+            ),
+            Style::default().fg(color),
+        ));
+        spans.push(Span::raw("  "));
+    }
+    if !quota.provider_id.is_empty() {
+        spans.push(muted(p, "provider "));
+        spans.push(Span::styled(
+            shorten(&quota.provider_id, 16),
+            Style::default().fg(p.text),
+        ));
+        spans.push(Span::raw("  "));
+    }
+    if let Some(tenant) = quota.tenant.as_deref() {
+        spans.push(muted(p, "tenant "));
+        spans.push(Span::styled(
+            shorten(tenant, 16),
+            Style::default().fg(p.text),
+        ));
+    }
+    Some(Line::from(spans))
+}
+
+fn fallback_share_text(stats: &WindowStats) -> String {
+    stats
+        .fallback_share
+        .map(|share| format!("{:.0}%", share * 100.0))
+        .unwrap_or_else(|| "-".to_string())
+}
+
+fn fallback_share_color(p: Palette, stats: &WindowStats) -> Color {
+    if stats.fallback_2xx > 0 {
+        p.warn
+    } else {
+        p.muted
+    }
+}
+
 fn render_dimension_area(
     f: &mut Frame<'_>,
     p: Palette,
@@ -605,7 +803,7 @@ fn render_dimension_area(
         .constraints([Constraint::Percentage(66), Constraint::Percentage(34)])
         .split(area);
     render_focus_table(f, p, ui, snapshot, cols[0]);
-    render_side_lists(f, p, ui.language, &snapshot.usage_day, cols[1]);
+    render_side_lists(f, p, ui, snapshot, cols[1]);
 }
 
 fn render_focus_table(
@@ -649,7 +847,7 @@ fn render_focus_table(
 
     let table_rows = rows
         .iter()
-        .map(|row| dimension_table_row(p, row))
+        .map(|row| dimension_table_row(p, &ui.display_format, row))
         .collect::<Vec<_>>();
     let table = Table::new(
         table_rows,
@@ -671,7 +869,7 @@ fn render_focus_table(
         Cell::from(muted(p, "avg")),
     ]))
     .block(panel_block(p, title))
-    .row_highlight_style(Style::default().bg(Color::Rgb(32, 39, 48)))
+    .row_highlight_style(Style::default().bg(p.highlight))
     .highlight_symbol("  ")
     .highlight_spacing(HighlightSpacing::Always);
     f.render_stateful_widget(table, area, table_state);
@@ -762,7 +960,7 @@ fn render_pool_table(
         Cell::from(muted(p, "scope / confidence")),
     ]))
     .block(panel_block(p, title))
-    .row_highlight_style(Style::default().bg(Color::Rgb(32, 39, 48)))
+    .row_highlight_style(Style::default().bg(p.highlight))
     .highlight_symbol("  ")
     .highlight_spacing(HighlightSpacing::Always);
     f.render_stateful_widget(table, area, &mut ui.stats_pools_table);
@@ -905,7 +1103,7 @@ fn render_project_table(
         Cell::from(muted(p, "requests")),
     ]))
     .block(panel_block(p, title))
-    .row_highlight_style(Style::default().bg(Color::Rgb(32, 39, 48)))
+    .row_highlight_style(Style::default().bg(p.highlight))
     .highlight_symbol("  ")
     .highlight_spacing(HighlightSpacing::Always);
     f.render_stateful_widget(table, areas[0], &mut ui.stats_projects_table);
@@ -927,13 +1125,16 @@ fn render_project_table(
     }
 }
 
-fn render_side_lists(
-    f: &mut Frame<'_>,
-    p: Palette,
-    lang: Language,
-    usage: &UsageDayView,
-    area: Rect,
-) {
+fn render_side_lists(f: &mut Frame<'_>, p: Palette, ui: &UiState, snapshot: &Snapshot, area: Rect) {
+    let lang = ui.language;
+    let usage = &snapshot.usage_day;
+    let sections = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(9), Constraint::Min(0)])
+        .split(area);
+    let histogram = latency_histogram(&snapshot.recent, now_ms(), ui.stats_latency_window);
+    render_latency_histogram(f, p, lang, ui.stats_latency_window, &histogram, sections[0]);
+
     let rows = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -941,10 +1142,11 @@ fn render_side_lists(
             Constraint::Percentage(33),
             Constraint::Percentage(33),
         ])
-        .split(area);
+        .split(sections[1]);
     render_compact_rows(
         f,
         p,
+        &ui.display_format,
         match lang {
             Language::Zh => "模型",
             Language::En => "Models",
@@ -955,6 +1157,7 @@ fn render_side_lists(
     render_compact_rows(
         f,
         p,
+        &ui.display_format,
         match lang {
             Language::Zh => "会话",
             Language::En => "Sessions",
@@ -965,6 +1168,7 @@ fn render_side_lists(
     render_compact_rows(
         f,
         p,
+        &ui.display_format,
         match lang {
             Language::Zh => "项目",
             Language::En => "Projects",
@@ -974,9 +1178,77 @@ fn render_side_lists(
     );
 }
 
+/// Durations of successful requests that ended inside `window`, bucketed.
+fn latency_histogram(
+    recent: &[OperatorRequestSummary],
+    now_ms: u64,
+    window: StatsLatencyWindow,
+) -> LatencyHistogram {
+    let cutoff = window
+        .window_ms()
+        .map_or(0, |window_ms| now_ms.saturating_sub(window_ms));
+    LatencyHistogram::from_durations(
+        recent
+            .iter()
+            .filter(|request| request.ended_at_ms >= cutoff)
+            .filter(|request| (200..300).contains(&request.status_code))
+            .map(|request| request.duration_ms),
+    )
+}
+
+fn render_latency_histogram(
+    f: &mut Frame<'_>,
+    p: Palette,
+    lang: Language,
+    window: StatsLatencyWindow,
+    histogram: &LatencyHistogram,
+    area: Rect,
+) {
+    let title = match lang {
+        Language::Zh => format!("延迟分布 {} n={}", window.label(lang), histogram.total),
+        Language::En => format!("Latency {} n={}", window.label(lang), histogram.total),
+    };
+    let lines = if histogram.total == 0 {
+        vec![Line::from(muted(
+            p,
+            match lang {
+                Language::Zh => "窗口内没有成功请求",
+                Language::En => "no successful requests in window",
+            },
+        ))]
+    } else {
+        // label(6) + count(6) + pct(5) + separators; the bar takes the rest.
+        let bar_width = usize::from(area.width.saturating_sub(2)).saturating_sub(20);
+        let max = histogram.counts.iter().copied().max().unwrap_or(0).max(1);
+        histogram
+            .counts
+            .iter()
+            .enumerate()
+            .map(|(idx, count)| {
+                let filled = (count * bar_width).div_ceil(max);
+                let share = histogram.share(idx).unwrap_or(0.0);
+                Line::from(vec![
+                    muted(p, &format!("{:>6} ", LatencyHistogram::bucket_label(idx))),
+                    Span::styled(
+                        format!("{:<bar_width$}", "█".repeat(filled)),
+                        Style::default().fg(if idx >= 5 { p.warn } else { p.accent }),
+                    ),
+                    Span::styled(format!(" {count:>5}"), Style::default().fg(p.text)),
+                    muted(p, &format!(" {:>3.0}%", share * 100.0)),
+                ])
+            })
+            .collect()
+    };
+    f.render_widget(
+        Paragraph::new(Text::from(lines)).block(panel_block(p, title)),
+        area,
+    );
+}
+
 fn render_compact_rows(
     f: &mut Frame<'_>,
     p: Palette,
+    format: &DisplayFormat,
     title: &'static str,
     rows: &[UsageDayDimensionRow],
     area: Rect,
@@ -990,9 +1262,9 @@ fn render_compact_rows(
                 Line::from(vec![
                     Span::styled(shorten(&row.name, 28), Style::default().fg(p.text)),
                     Span::raw("  "),
-                    muted(p, &tokens_short(row.bucket.usage.total_tokens)),
+                    muted(p, &format.tokens_short(row.bucket.usage.total_tokens)),
                     Span::raw("  "),
-                    muted(p, &format!("n={}", row.bucket.requests_total)),
+                    muted(p, &format!("n={}", format.count(row.bucket.requests_total))),
                 ])
             })
             .collect::<Vec<_>>()
@@ -1005,22 +1277,26 @@ fn render_compact_rows(
     );
 }
 
-fn dimension_table_row(p: Palette, row: &UsageDayDimensionRow) -> Row<'static> {
+fn dimension_table_row(
+    p: Palette,
+    format: &DisplayFormat,
+    row: &UsageDayDimensionRow,
+) -> Row<'static> {
     Row::new(vec![
         Cell::from(Span::styled(
             shorten(&row.name, 48),
             Style::default().fg(p.text),
         )),
         Cell::from(Span::styled(
-            row.bucket.requests_total.to_string(),
+            format.count(row.bucket.requests_total),
             Style::default().fg(p.text),
         )),
         Cell::from(Span::styled(
-            tokens_short(row.bucket.usage.total_tokens),
+            format.tokens_short(row.bucket.usage.total_tokens),
             Style::default().fg(p.accent),
         )),
         Cell::from(Span::styled(
-            row.bucket.cost.display_total(),
+            row.bucket.cost.display_total(format),
             Style::default().fg(p.text),
         )),
         Cell::from(Span::styled(
@@ -1110,11 +1386,17 @@ fn quota_summary_lines(
                     },
                     p.warn,
                 ),
-                kv_line(p, "local cost", &usage.summary.cost.display_total(), p.text),
+                kv_line(
+                    p,
+                    "local cost",
+                    &usage.summary.cost.display_total(&ui.display_format),
+                    p.text,
+                ),
                 kv_line(
                     p,
                     "local tokens",
-                    &tokens_short(usage.summary.usage.total_tokens),
+                    &ui.display_format
+                        .tokens_short(usage.summary.usage.total_tokens),
                     p.accent,
                 ),
             ],
@@ -1153,7 +1435,12 @@ fn quota_summary_lines(
                         p.muted
                     },
                 ),
-                kv_line(p, "local cost", &usage.summary.cost.display_total(), p.text),
+                kv_line(
+                    p,
+                    "local cost",
+                    &usage.summary.cost.display_total(&ui.display_format),
+                    p.text,
+                ),
             ],
         );
     };
@@ -1762,6 +2049,48 @@ mod tests {
     use crate::tui::model::Snapshot;
     use crate::tui::state::UiState;
 
+    fn limited_provider(name: &str, limit: u32, max: u32) -> ProviderOption {
+        ProviderOption {
+            name: name.to_string(),
+            alias: None,
+            configured_enabled: true,
+            effective_enabled: true,
+            routable_endpoints: 1,
+            credential_readiness: None,
+            endpoints: Vec::new(),
+            capacity: OperatorProviderCapacity {
+                configured_max_concurrent_requests: Some(max),
+                effective_max_concurrent_requests: Some(max),
+                active: Some(1),
+                limit: Some(limit),
+                ..OperatorProviderCapacity::default()
+            },
+            schedule: None,
+        }
+    }
+
+    #[test]
+    fn concurrency_limit_rows_list_adaptively_lowered_limits_first() {
+        let mut unlimited = limited_provider("open", 1, 1);
+        unlimited.capacity = OperatorProviderCapacity::default();
+        let providers = vec![
+            limited_provider("steady", 8, 8),
+            unlimited,
+            limited_provider("pressured", 2, 8),
+        ];
+
+        let rows = concurrency_limit_rows(&providers);
+
+        assert_eq!(
+            rows.iter()
+                .map(|(name, _)| name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["pressured", "steady"]
+        );
+        assert!(concurrency_is_throttled(rows[0].1));
+        assert!(!concurrency_is_throttled(rows[1].1));
+    }
+
     fn bucket(requests: u64, tokens: i64) -> UsageBucket {
         let mut bucket = UsageBucket {
             requests_total: requests,
@@ -1934,6 +2263,8 @@ mod tests {
             },
             stats_5m: crate::dashboard_core::WindowStats::default(),
             stats_1h: crate::dashboard_core::WindowStats::default(),
+            request_quotas: Vec::new(),
+            recent_capacity: 0,
             service_status: None,
             refreshed_at: Instant::now(),
         }
@@ -2282,6 +2613,60 @@ mod tests {
         }
     }
 
+    #[test]
+    fn stats_render_shows_current_fallback_share() {
+        let mut snapshot = sample_snapshot();
+        snapshot.stats_5m.ok_2xx = 8;
+        snapshot.stats_5m.fallback_2xx = 2;
+        snapshot.stats_5m.fallback_share = Some(0.25);
+        let mut ui = UiState {
+            page: crate::tui::types::Page::Stats,
+            stats_focus: StatsFocus::Pools,
+            ..UiState::default()
+        };
+
+        let text = render_text(120, 32, &mut ui, &snapshot);
+
+        assert!(text.contains("fallback 5m 25%  1h -"), "{text}");
+    }
+
+    #[test]
+    fn stats_render_shows_fullest_request_quota() {
+        let mut snapshot = sample_snapshot();
+        snapshot.request_quotas = vec![
+            RequestQuotaUsage {
+                provider_id: "relay".to_string(),
+                tenant: Some("alice".to_string()),
+                window_secs: 3_600,
+                requests: 12,
+                max_requests: Some(500),
+                output_tokens: 95_000,
+                max_output_tokens: Some(100_000),
+            },
+            RequestQuotaUsage {
+                provider_id: "relay".to_string(),
+                tenant: Some("bob".to_string()),
+                window_secs: 3_600,
+                requests: 3,
+                max_requests: Some(500),
+                output_tokens: 1_000,
+                max_output_tokens: Some(100_000),
+            },
+        ];
+        let mut ui = UiState {
+            page: crate::tui::types::Page::Stats,
+            stats_focus: StatsFocus::Pools,
+            ..UiState::default()
+        };
+
+        let text = render_text(120, 32, &mut ui, &snapshot);
+
+        assert!(
+            text.contains("quota 1h req 12/500  out 95.0k/100.0k  provider relay  tenant alice"),
+            "{text}"
+        );
+    }
+
     #[test]
     fn stats_render_keeps_narrow_layout_bounded() {
         let snapshot = sample_snapshot();
@@ -2579,4 +2964,103 @@ mod tests {
         assert!(text.contains("no reset"), "{text}");
         assert!(!text.contains("midnight"), "{text}");
     }
+
+    fn finished_request(
+        status_code: u16,
+        duration_ms: u64,
+        ended_at_ms: u64,
+    ) -> OperatorRequestSummary {
+        OperatorRequestSummary {
+            id: ended_at_ms,
+            trace_key: None,
+            session_key: None,
+            body_hash: None,
+            upstream_request_id: None,
+            model: None,
+            reasoning_effort: None,
+            service_tier: None,
+            provider_id: None,
+            endpoint_id: None,
+            provider_endpoint_key: None,
+            route_path: Vec::new(),
+            upstream_origin: None,
+            usage: None,
+            cache_accounting_convention: Default::default(),
+            cost: crate::pricing::CostBreakdown::default(),
+            retry: None,
+            provider_signal_codes: Vec::new(),
+            policy_action_codes: Vec::new(),
+            observability: crate::dashboard_core::OperatorRequestObservability {
+                duration_ms: Some(duration_ms),
+                ttfb_ms: None,
+                generation_ms: None,
+                output_tokens_per_second: None,
+                attempt_count: 1,
+                route_attempt_count: 0,
+                retried: false,
+                cross_provider_failover: false,
+                same_provider_retry: false,
+                fast_mode: false,
+                streaming: false,
+            },
+            service: "codex".to_string(),
+            method: "POST".to_string(),
+            path: "/v1/responses".to_string(),
+            status_code,
+            duration_ms,
+            ttfb_ms: None,
+            streaming: false,
+            ended_at_ms,
+        }
+    }
+
+    #[test]
+    fn latency_histogram_counts_successes_inside_the_selected_window() {
+        let now = 10 * 60 * 60_000;
+        let recent = vec![
+            finished_request(200, 150, now - 60_000),
+            finished_request(200, 1_500, now - 2 * 60_000),
+            finished_request(502, 150, now - 60_000),
+            finished_request(200, 12_000, now - 30 * 60_000),
+            finished_request(200, 700, now - 3 * 60 * 60_000),
+        ];
+
+        let five = latency_histogram(&recent, now, StatsLatencyWindow::FiveMinutes);
+        assert_eq!(five.total, 2);
+        assert_eq!(five.counts, [1, 0, 0, 1, 0, 0, 0]);
+
+        let hour = latency_histogram(&recent, now, StatsLatencyWindow::OneHour);
+        assert_eq!(hour.total, 3);
+        assert_eq!(hour.counts[6], 1);
+
+        let loaded = latency_histogram(&recent, now, StatsLatencyWindow::Loaded);
+        assert_eq!(loaded.total, 4);
+    }
+
+    #[test]
+    fn stats_render_shows_latency_histogram_with_counts_and_shares() {
+        let mut snapshot = sample_snapshot();
+        let now = now_ms();
+        snapshot.recent = vec![
+            finished_request(200, 150, now),
+            finished_request(200, 180, now),
+            finished_request(200, 3_000, now),
+            finished_request(200, 11_000, now),
+        ];
+        let mut ui = UiState {
+            page: crate::tui::types::Page::Stats,
+            ..UiState::default()
+        };
+
+        let text = render_text(140, 40, &mut ui, &snapshot);
+
+        assert!(text.contains("Latency 5m n=4"), "{text}");
+        assert!(text.contains("<200ms"), "{text}");
+        assert!(text.contains("10s+"), "{text}");
+        assert!(text.contains("50%"), "{text}");
+
+        ui.stats_latency_window = StatsLatencyWindow::Loaded;
+        let text = render_text(140, 40, &mut ui, &snapshot);
+        assert!(text.contains("Latency loaded n=4"), "{text}");
+    }
 }
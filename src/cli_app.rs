@@ -4,6 +4,7 @@ use crate::cli_types::{
 };
 use crate::codex_integration;
 use crate::commands;
+use crate::commands::cli_lang::pick;
 #[cfg(test)]
 use crate::config::save_helper_config;
 use crate::config::{
@@ -30,6 +31,7 @@ use crate::service_manager;
 use crate::service_receipt::{
     ServicePlatformBackend, ServiceReceipt, ServiceReceiptError, read_service_receipt,
 };
+use crate::state::ProxyState;
 use crate::tui;
 use anyhow::Context;
 use codex_helper_core::relay_target::{
@@ -42,12 +44,16 @@ use codex_helper_core::codex_switch::{
     self, CodexSwitchIntent, CodexSwitchPhase, ValidatedCodexBaseUrl,
 };
 use codex_helper_core::credentials::{CredentialAggregateReadiness, CredentialSourceCapabilities};
-use codex_helper_core::local_log_store::{LogRetention, RotatingLogWriter, repair_log};
+use codex_helper_core::local_log_store::{
+    LogRetention, RUNTIME_LOG_FILE_NAME, RotatingLogWriter, repair_log,
+};
 use owo_colors::OwoColorize;
 use std::io::ErrorKind;
 use std::net::{IpAddr, SocketAddr};
 use std::path::{Path, PathBuf};
 use std::process::Command as ProcessCommand;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Duration;
 use tracing_appender::non_blocking::WorkerGuard;
 use tracing_subscriber::EnvFilter;
@@ -60,6 +66,20 @@ struct ServeRuntimeOptions {
     desktop_managed: bool,
     service_managed: bool,
     auto_manage_codex_switch: bool,
+    idle_timeout: Option<Duration>,
+    warmup: bool,
+    restore_policy: ForegroundRestorePolicy,
+}
+
+/// What a foreground `ch` does on exit with the client switch it applied at startup.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum ForegroundRestorePolicy {
+    #[default]
+    Restore,
+    /// Ask on the terminal first when Codex `config.toml` was edited after switch-on.
+    ConfirmIfEdited,
+    /// Leave the switch applied; `switch off` restores it later.
+    Keep,
 }
 
 impl ServeRuntimeOptions {
@@ -88,6 +108,12 @@ impl ServeRuntimeOptions {
         self.resident || self.supervisor_managed || self.desktop_managed || self.service_managed
     }
 
+    /// Resident runtimes reload `config.toml` on SIGHUP. A foreground proxy treats it as a
+    /// terminal hangup and shuts down, restoring the client switch it applied.
+    fn reloads_config_on_sighup(self) -> bool {
+        self.is_resident()
+    }
+
     fn should_auto_manage_codex_switch(self, service_name: &str) -> bool {
         self.auto_manage_codex_switch
             && service_name == "codex"
@@ -141,6 +167,12 @@ pub async fn run_ch_cli() -> CliResult<()> {
 
 async fn run_codex_cli(entrypoint: CliEntrypoint) -> CliResult<()> {
     let cli = Cli::parse();
+    if let Some(profile) = cli.helper_profile.as_deref() {
+        commands::config::activate_helper_profile(profile)?;
+    }
+    if let Some(lang) = cli.lang {
+        commands::cli_lang::set_cli_language_override(lang);
+    }
     if let Some(Command::Service { cmd }) = cli.command.as_ref() {
         service_manager::configure_service_command_environment(cmd)?;
     }
@@ -156,6 +188,17 @@ async fn run_codex_cli(entrypoint: CliEntrypoint) -> CliResult<()> {
         supervisor_managed: false,
         desktop_managed: false,
         service_managed: false,
+        idle_timeout: None,
+        warmup: false,
+        no_auto_restore: false,
+        confirm_restore: false,
+        env_file: None,
+        unix_socket: None,
+        print_config: false,
+        json: false,
+        detach: false,
+        stop: false,
+        status: false,
     }) {
         Command::Default { codex, claude } => {
             handle_default_cmd(codex, claude).await?;
@@ -222,8 +265,14 @@ async fn run_codex_cli(entrypoint: CliEntrypoint) -> CliResult<()> {
                     )
                     .await?;
                 }
-                SwitchCommand::Off { codex, claude } => do_switch_off(codex, claude)?,
-                SwitchCommand::Status { codex, claude } => do_switch_status(codex, claude)?,
+                SwitchCommand::Off { codex, claude } => {
+                    do_switch_off(codex, claude, commands::cli_lang::cli_language().await)?
+                }
+                SwitchCommand::Status {
+                    codex,
+                    claude,
+                    json,
+                } => do_switch_status(codex, claude, json).await?,
             }
             return Ok(());
         }
@@ -255,7 +304,18 @@ async fn run_codex_cli(entrypoint: CliEntrypoint) -> CliResult<()> {
             commands::doctor::handle_doctor_cmd(json).await?;
             return Ok(());
         }
-        Command::Status { json } => {
+        Command::Status { json, oneline } => {
+            if oneline {
+                let (codex, claude) = tokio::join!(
+                    probe_status_oneline_service("codex"),
+                    probe_status_oneline_service("claude"),
+                );
+                println!(
+                    "{}",
+                    commands::doctor::status_oneline(&[codex, claude], crate::logging::now_ms())
+                );
+                return Ok(());
+            }
             let (codex, claude) = tokio::join!(
                 read_local_operator_model("codex", default_proxy_port_for_service("codex")),
                 read_local_operator_model("claude", default_proxy_port_for_service("claude")),
@@ -272,6 +332,29 @@ async fn run_codex_cli(entrypoint: CliEntrypoint) -> CliResult<()> {
         } => {
             let service_name = resolve_cli_service_name(codex, claude).await?;
             let port = port.unwrap_or_else(|| default_proxy_port_for_service(service_name));
+            if let UsageCommand::Replay { request_id, json } = cmd {
+                commands::usage::handle_usage_replay(request_id, json, port).await?;
+                return Ok(());
+            }
+            if let UsageCommand::Check { fix } = cmd {
+                commands::usage_check::handle_usage_check(fix)?;
+                return Ok(());
+            }
+            if let UsageCommand::Gc {
+                older_than,
+                keep,
+                compact,
+                yes,
+            } = cmd
+            {
+                commands::usage_gc::handle_usage_gc(commands::usage_gc::UsageGcOptions {
+                    older_than: older_than.map(Into::into),
+                    keep,
+                    compact,
+                    apply: yes,
+                })?;
+                return Ok(());
+            }
             let client = local_control_plane_client(port)?;
             let model = if source == UsageSource::Store || matches!(cmd, UsageCommand::Quota { .. })
             {
@@ -293,6 +376,7 @@ async fn run_codex_cli(entrypoint: CliEntrypoint) -> CliResult<()> {
                     no_toast,
                     toast,
                 } => notify::handle_codex_notify(notification_json, no_toast, toast).await?,
+                NotifyCommand::Test => notify::handle_notify_test().await?,
                 NotifyCommand::FlushCodex => notify::handle_codex_flush().await?,
             }
             return Ok(());
@@ -307,6 +391,17 @@ async fn run_codex_cli(entrypoint: CliEntrypoint) -> CliResult<()> {
             supervisor_managed,
             desktop_managed,
             service_managed,
+            idle_timeout,
+            warmup,
+            no_auto_restore,
+            confirm_restore,
+            env_file,
+            unix_socket,
+            print_config,
+            json,
+            detach,
+            stop,
+            status,
         } => {
             if [supervisor_managed, desktop_managed, service_managed]
                 .into_iter()
@@ -319,12 +414,46 @@ async fn run_codex_cli(entrypoint: CliEntrypoint) -> CliResult<()> {
                         .to_string(),
                 ));
             }
+            if unix_socket.is_some() && !cfg!(unix) {
+                return Err(CliError::Usage(
+                    "--unix-socket is only supported on Unix platforms".to_string(),
+                ));
+            }
+            if stop || status {
+                let service_name = resolve_cli_service_name(codex, claude).await?;
+                let port = port.unwrap_or_else(|| default_proxy_port_for_service(service_name));
+                return if stop {
+                    commands::serve_detach::handle_serve_stop(service_name, port).await
+                } else {
+                    commands::serve_detach::handle_serve_status(service_name, port).await
+                };
+            }
+            load_serve_env_file(env_file.as_deref())?;
             let service_name = resolve_cli_service_name(codex, claude).await?;
+            if print_config {
+                return commands::serve_preflight::handle_serve_print_config(service_name, json)
+                    .await;
+            }
             let port = port.unwrap_or_else(|| default_proxy_port_for_service(service_name));
-            run_server(
+            if detach {
+                return commands::serve_detach::handle_serve_detach(
+                    commands::serve_detach::DetachedServeOptions {
+                        service_name,
+                        host,
+                        port,
+                        idle_timeout: idle_timeout.map(|timeout| timeout.to_string()),
+                        warmup,
+                        env_file,
+                        unix_socket,
+                    },
+                )
+                .await;
+            }
+            run_server_with_unix_socket(
                 service_name,
                 host,
                 port,
+                unix_socket,
                 ServeRuntimeOptions {
                     enable_tui: !no_tui,
                     resident,
@@ -332,6 +461,17 @@ async fn run_codex_cli(entrypoint: CliEntrypoint) -> CliResult<()> {
                     desktop_managed,
                     service_managed,
                     auto_manage_codex_switch: entrypoint.auto_manages_codex_client(),
+                    idle_timeout: idle_timeout
+                        .map(Into::into)
+                        .filter(|timeout: &Duration| !timeout.is_zero()),
+                    warmup,
+                    restore_policy: if no_auto_restore {
+                        ForegroundRestorePolicy::Keep
+                    } else if confirm_restore {
+                        ForegroundRestorePolicy::ConfirmIfEdited
+                    } else {
+                        ForegroundRestorePolicy::Restore
+                    },
                 },
             )
             .await
@@ -342,6 +482,20 @@ async fn run_codex_cli(entrypoint: CliEntrypoint) -> CliResult<()> {
     Ok(())
 }
 
+fn load_serve_env_file(explicit: Option<&Path>) -> CliResult<()> {
+    let outcome = crate::env_file::load_env_file(explicit)
+        .map_err(|e| CliError::Configuration(format!("{e:#}")))?;
+    if let Some(outcome) = outcome {
+        tracing::info!(
+            "loaded env file {} ({} set, {} already in process env)",
+            outcome.path.display(),
+            outcome.applied,
+            outcome.kept_from_process
+        );
+    }
+    Ok(())
+}
+
 fn init_tracing(cli: &Cli) -> Option<WorkerGuard> {
     // Default to info logs unless the user sets RUST_LOG.
     let env_filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
@@ -355,9 +509,11 @@ fn init_tracing(cli: &Cli) -> Option<WorkerGuard> {
             supervisor_managed,
             desktop_managed,
             service_managed,
+            print_config,
             ..
         }) => {
-            !*resident
+            !*print_config
+                && !*resident
                 && !*supervisor_managed
                 && !*desktop_managed
                 && !*service_managed
@@ -384,16 +540,14 @@ fn init_tracing(cli: &Cli) -> Option<WorkerGuard> {
         })
     );
 
-    if interactive_tui || service_entrypoint {
+    let detached_serve = matches!(&cli.command, Some(Command::Serve { .. }))
+        && std::env::var_os(commands::serve_detach::DETACHED_SERVE_ENV).is_some();
+
+    if interactive_tui || service_entrypoint || detached_serve {
         let log_dir = crate::config::proxy_home_dir().join("logs");
         let _ = std::fs::create_dir_all(&log_dir);
 
-        let runtime_log_retention = LogRetention::from_env(
-            "CODEX_HELPER_RUNTIME_LOG_MAX_BYTES",
-            "CODEX_HELPER_RUNTIME_LOG_MAX_FILES",
-            DEFAULT_RUNTIME_LOG_MAX_BYTES,
-            DEFAULT_RUNTIME_LOG_MAX_FILES,
-        );
+        let runtime_log_retention = LogRetention::runtime_log_from_env();
         let runtime_log_path = log_dir.join(RUNTIME_LOG_FILE_NAME);
         repair_log(&runtime_log_path, runtime_log_retention);
 
@@ -414,10 +568,6 @@ fn init_tracing(cli: &Cli) -> Option<WorkerGuard> {
     }
 }
 
-const RUNTIME_LOG_FILE_NAME: &str = "runtime.log";
-const DEFAULT_RUNTIME_LOG_MAX_BYTES: u64 = 20 * 1024 * 1024;
-const DEFAULT_RUNTIME_LOG_MAX_FILES: usize = 10;
-
 async fn handle_daemon_cmd(cmd: DaemonCommand, auto_manage_codex_switch: bool) -> CliResult<()> {
     match cmd {
         DaemonCommand::Status {
@@ -488,7 +638,7 @@ async fn resolve_cli_service_name(codex: bool, claude: bool) -> CliResult<&'stat
     }
 }
 
-fn default_proxy_port_for_service(service_name: &str) -> u16 {
+pub(crate) fn default_proxy_port_for_service(service_name: &str) -> u16 {
     if service_name == "claude" { 3210 } else { 3211 }
 }
 
@@ -536,7 +686,10 @@ async fn handle_relay_cmd(cmd: RelayCommand, auto_manage_codex_switch: bool) ->
         }
         RelayCommand::List => list_relay_targets().await,
         RelayCommand::Status { target, json } => relay_status(target, json).await,
-        RelayCommand::Off => handle_relay_off(auto_manage_codex_switch),
+        RelayCommand::Off => handle_relay_off(
+            auto_manage_codex_switch,
+            commands::cli_lang::cli_language().await,
+        ),
         RelayCommand::Use {
             target,
             no_tui,
@@ -549,14 +702,14 @@ async fn handle_relay_cmd(cmd: RelayCommand, auto_manage_codex_switch: bool) ->
     }
 }
 
-fn handle_relay_off(auto_manage_codex_switch: bool) -> CliResult<()> {
+fn handle_relay_off(auto_manage_codex_switch: bool, language: tui::Language) -> CliResult<()> {
     if !auto_manage_codex_switch {
         return Err(CliError::Other(
             "`codex-helper relay off` does not modify Codex client configuration; run `codex-helper switch off` explicitly"
                 .to_string(),
         ));
     }
-    do_switch_off(false, false)
+    do_switch_off(false, false, language)
 }
 
 fn relay_service_from_flags(codex: bool, claude: bool) -> CliResult<ServiceKind> {
@@ -716,7 +869,7 @@ async fn relay_status(target: Option<String>, json: bool) -> CliResult<()> {
 
     println!("{}", "codex-helper relay status".bold());
     println!("targets: {}", relay_target_names(&cfg).join(", "));
-    print_codex_switch_status()?;
+    print_codex_switch_status(commands::cli_lang::cli_language().await)?;
     Ok(())
 }
 
@@ -984,7 +1137,7 @@ mod relay_use_mode_tests {
             })
         ));
 
-        let error = handle_relay_off(false)
+        let error = handle_relay_off(false, crate::tui::Language::En)
             .expect_err("ordinary codex-helper relay off must preserve Codex client files");
         assert!(error.to_string().contains("codex-helper switch off"));
     }
@@ -1054,7 +1207,7 @@ fn relay_proxy_port(target: &ResolvedRelayTarget) -> Option<u16> {
         .and_then(|url| url.port_or_known_default())
 }
 
-fn daemon_admin_base_url_for_proxy_port(port: u16) -> String {
+pub(crate) fn daemon_admin_base_url_for_proxy_port(port: u16) -> String {
     format!("http://{}", admin_loopback_addr_for_proxy_port(port))
 }
 
@@ -1206,11 +1359,42 @@ fn local_control_plane_client(port: u16) -> CliResult<ControlPlaneClient> {
     )
 }
 
-async fn read_local_operator_model(service_name: &str, port: u16) -> CliResult<OperatorReadModel> {
+pub(crate) async fn read_local_operator_model(
+    service_name: &str,
+    port: u16,
+) -> CliResult<OperatorReadModel> {
     let client = local_control_plane_client(port)?;
     Ok(client.refresh_operator_read_model(service_name, None).await)
 }
 
+/// Prompt integrations call `status --oneline` on every render, so a slow or hung admin port
+/// reads as "off" instead of blocking the shell.
+const STATUS_ONELINE_PROBE_TIMEOUT: Duration = Duration::from_millis(300);
+
+async fn probe_status_oneline_service(
+    service_name: &'static str,
+) -> commands::doctor::OnelineServiceStatus {
+    let port = default_proxy_port_for_service(service_name);
+    let model = tokio::time::timeout(
+        STATUS_ONELINE_PROBE_TIMEOUT,
+        read_local_operator_model(service_name, port),
+    )
+    .await
+    .ok()
+    .and_then(Result::ok)
+    .unwrap_or_else(|| OperatorReadModel::disconnected(service_name));
+    let started_at_ms = operator_read_model_is_reachable(&model)
+        .then(|| read_owner_marker_best_effort(service_name, port))
+        .flatten()
+        .map(|marker| marker.started_at_ms);
+    commands::doctor::OnelineServiceStatus {
+        service_name,
+        port,
+        model,
+        started_at_ms,
+    }
+}
+
 fn operator_read_model_is_reachable(model: &OperatorReadModel) -> bool {
     model.status != OperatorReadStatus::Disconnected
 }
@@ -1221,7 +1405,7 @@ fn daemon_status_is_running(status: OperatorReadStatus) -> bool {
     daemon_status_is_reachable(status)
 }
 
-fn daemon_status_is_reachable(status: OperatorReadStatus) -> bool {
+pub(crate) fn daemon_status_is_reachable(status: OperatorReadStatus) -> bool {
     status != OperatorReadStatus::Disconnected
 }
 
@@ -1293,7 +1477,11 @@ fn daemon_status_json_payload(
     serde_json::Value::Object(payload)
 }
 
-async fn print_daemon_status(service_name: &'static str, port: u16, json: bool) -> CliResult<()> {
+pub(crate) async fn print_daemon_status(
+    service_name: &'static str,
+    port: u16,
+    json: bool,
+) -> CliResult<()> {
     let owner_marker = read_owner_marker_best_effort(service_name, port);
     let model = read_local_operator_model(service_name, port).await?;
 
@@ -1420,7 +1608,7 @@ fn validate_daemon_stop_owner(
     }
 }
 
-async fn stop_daemon(service_name: &'static str, port: u16) -> CliResult<()> {
+pub(crate) async fn stop_daemon(service_name: &'static str, port: u16) -> CliResult<()> {
     let owner = read_owner_marker_best_effort(service_name, port);
     validate_daemon_stop_owner(service_name, port, owner.as_ref())?;
     let endpoint = ControlPlaneEndpoint::new(
@@ -1506,6 +1694,7 @@ mod daemon_status_contract_tests {
                 quota_analytics: Default::default(),
                 stats_5m: Default::default(),
                 stats_1h: Default::default(),
+                request_quotas: Vec::new(),
                 pricing_catalog: Default::default(),
                 service_status: None,
                 provider_balances: Vec::new(),
@@ -1909,7 +2098,7 @@ async fn supervise_daemon(
     };
     let (shutdown_tx, mut shutdown_rx) = tokio::sync::watch::channel(false);
     let shutdown_task = tokio::spawn(async move {
-        wait_for_shutdown_signal().await;
+        wait_for_shutdown_signal(false).await;
         let _ = shutdown_tx.send(true);
     });
     let mut restart_count = 0u32;
@@ -1999,6 +2188,7 @@ async fn supervise_daemon(
                         switch_guard = outcome.restore_lease.map(|restore_lease| {
                             ForegroundCodexSwitchGuard {
                                 restore_lease: Some(restore_lease),
+                                confirm_if_edited: false,
                             }
                         });
                     }
@@ -2503,6 +2693,7 @@ where
 
 struct ForegroundCodexSwitchGuard {
     restore_lease: Option<codex_switch::CodexSwitchRestoreLease>,
+    confirm_if_edited: bool,
 }
 
 impl ForegroundCodexSwitchGuard {
@@ -2510,6 +2701,15 @@ impl ForegroundCodexSwitchGuard {
         let Some(restore_lease) = self.restore_lease.take() else {
             return Ok(());
         };
+        if self.confirm_if_edited
+            && codex_switch::config_changed_since_switch_on(&restore_lease)?
+            && !confirm_restore_over_edits()
+        {
+            tracing::info!(
+                "ch left the Codex client switch applied at the operator's request; run `ch switch off` to restore it later"
+            );
+            return Ok(());
+        }
         log_foreground_codex_restore(codex_switch::restore_if_owned_with_retry(&restore_lease))
     }
 }
@@ -2529,6 +2729,23 @@ impl Drop for ForegroundCodexSwitchGuard {
     }
 }
 
+/// Asks on the terminal whether to restore over hand edits. Without a terminal on both stdin and
+/// stderr there is nobody to ask, so the restore proceeds as it would without `--confirm-restore`.
+fn confirm_restore_over_edits() -> bool {
+    if !atty::is(atty::Stream::Stdin) || !atty::is(atty::Stream::Stderr) {
+        return true;
+    }
+    eprint!(
+        "Codex config.toml was edited after ch switched it on. Restore the pre-switch client config now? [y/N] "
+    );
+    let _ = std::io::Write::flush(&mut std::io::stderr());
+    let mut answer = String::new();
+    if std::io::stdin().read_line(&mut answer).is_err() {
+        return true;
+    }
+    matches!(answer.trim().to_ascii_lowercase().as_str(), "y" | "yes")
+}
+
 fn log_foreground_codex_restore(
     result: Result<Option<codex_switch::CodexSwitchOutcome>, codex_switch::CodexSwitchError>,
 ) -> Result<(), codex_switch::CodexSwitchError> {
@@ -2589,11 +2806,18 @@ fn prepare_ch_codex_switch(
     if options.is_resident() {
         tracing::info!("ch resident mode leaves the Codex client switch applied");
         Ok(None)
+    } else if options.restore_policy == ForegroundRestorePolicy::Keep {
+        tracing::info!(
+            "ch --no-auto-restore leaves the Codex client switch applied on exit; run `ch switch off` to restore it"
+        );
+        Ok(None)
     } else {
         Ok(outcome
             .restore_lease
             .map(|restore_lease| ForegroundCodexSwitchGuard {
                 restore_lease: Some(restore_lease),
+                confirm_if_edited: options.restore_policy
+                    == ForegroundRestorePolicy::ConfirmIfEdited,
             }))
     }
 }
@@ -2656,12 +2880,21 @@ fn prepare_ch_claude_switch(
     let restore_lease = codex_integration::acquire_ephemeral_local_claude(port)
         .context("automatically switch Claude to the ready local ch proxy")?;
     tracing::info!(port, "ch switched Claude to the ready local proxy");
+    if options.restore_policy == ForegroundRestorePolicy::Keep {
+        tracing::info!(
+            "ch --no-auto-restore leaves Claude settings pointed at the local proxy on exit; run `ch switch off --claude` to restore them"
+        );
+        return Ok(None);
+    }
     Ok(Some(ForegroundClaudeSwitchGuard {
         restore_lease: Some(restore_lease),
     }))
 }
 
 async fn resolve_serve_tui_language(loaded: &LoadedConfig) -> tui::Language {
+    if let Some(language) = commands::cli_lang::cli_language_override() {
+        return language;
+    }
     if let Ok(language) = std::env::var("CODEX_HELPER_TUI_LANG") {
         return tui::resolve_language_preference(Some(&language));
     }
@@ -2776,12 +3009,27 @@ async fn run_server(
     host: IpAddr,
     port: u16,
     options: ServeRuntimeOptions,
+) -> anyhow::Result<()> {
+    run_server_with_unix_socket(service_name, host, port, None, options).await
+}
+
+/// Runs the foreground or resident proxy; `unix_socket` adds a second proxy listener next to
+/// the TCP one, which stays bound because clients and the admin API only speak TCP.
+async fn run_server_with_unix_socket(
+    service_name: &'static str,
+    host: IpAddr,
+    port: u16,
+    unix_socket: Option<PathBuf>,
+    options: ServeRuntimeOptions,
 ) -> anyhow::Result<()> {
     let interactive = !options.is_resident()
         && options.enable_tui
         && atty::is(atty::Stream::Stdin)
         && atty::is(atty::Stream::Stdout);
-    if try_attach_ch_to_running_service(service_name, host, port, options, interactive).await? {
+    // Attaching would silently drop the requested socket, so only a fresh runtime may bind it.
+    if unix_socket.is_none()
+        && try_attach_ch_to_running_service(service_name, host, port, options, interactive).await?
+    {
         return Ok(());
     }
     if options.should_auto_manage_codex_switch(service_name) {
@@ -2799,6 +3047,7 @@ async fn run_server(
         service_name,
         host,
         port,
+        unix_socket,
         options.service_managed,
         options.local_runtime_shutdown_policy(),
         loaded,
@@ -2833,14 +3082,28 @@ async fn run_server(
         admin_addr,
         service_name
     );
+    if let Some(unix_socket) = runtime.unix_socket.as_deref() {
+        tracing::info!(
+            "codex-helper proxy also listening on unix:{} (service: {})",
+            unix_socket.display(),
+            service_name
+        );
+    }
 
     {
         let shutdown_tx = shutdown_tx.clone();
+        let hangup_shuts_down = !options.reloads_config_on_sighup();
         tokio::spawn(async move {
-            wait_for_shutdown_signal().await;
+            wait_for_shutdown_signal(hangup_shuts_down).await;
             let _ = shutdown_tx.send(true);
         });
     }
+    if options.reloads_config_on_sighup() {
+        tokio::spawn(codex_helper_core::proxy::reload_runtime_config_on_sighup(
+            proxy.clone(),
+            shutdown_rx.clone(),
+        ));
+    }
     if options.supervisor_managed {
         let shutdown_tx = shutdown_tx.clone();
         tokio::spawn(async move {
@@ -2909,6 +3172,15 @@ async fn run_server(
     if options.supervisor_managed {
         publish_supervisor_child_ready(service_name, port, admin_addr.port())?;
     }
+    if options.warmup {
+        let proxy = proxy.clone();
+        tokio::spawn(async move {
+            proxy.warmup_upstreams().await;
+        });
+    }
+    let idle_shutdown = options
+        .idle_timeout
+        .map(|timeout| spawn_idle_timeout_watcher(state.clone(), timeout, shutdown_tx.clone()));
 
     let result = if interactive {
         let startup_readiness =
@@ -2956,6 +3228,13 @@ async fn run_server(
         await_server_shutdown(&mut running_runtime).await
     };
 
+    if let Some(idle_shutdown) = idle_shutdown.as_ref()
+        && idle_shutdown.load(Ordering::SeqCst)
+        && let Some(timeout) = options.idle_timeout
+    {
+        eprintln!("{}", idle_timeout_exit_message(timeout));
+    }
+
     let restore_result = match codex_switch_guard.as_mut() {
         Some(guard) => guard
             .finalize()
@@ -3009,6 +3288,7 @@ async fn build_local_proxy_runtime(
     service_name: &'static str,
     host: IpAddr,
     port: u16,
+    unix_socket: Option<PathBuf>,
     service_managed: bool,
     local_runtime_shutdown_policy: codex_helper_core::local_operator::LocalRuntimeShutdownPolicy,
     loaded: LoadedConfig,
@@ -3052,13 +3332,70 @@ async fn build_local_proxy_runtime(
             .with_admin_addr(admin_addr)
             .with_credential_sources(CredentialSourceCapabilities::platform_native())
             .with_service_runtime_identity(service_runtime_identity)
-            .with_local_runtime_shutdown_policy(local_runtime_shutdown_policy),
+            .with_local_runtime_shutdown_policy(local_runtime_shutdown_policy)
+            .with_unix_socket(unix_socket),
         loaded,
     )
     .await
     .map_err(|error| local_runtime_startup_error(error, service_name))
 }
 
+fn idle_timeout_exit_message(timeout: Duration) -> String {
+    format!(
+        "codex-helper: no requests processed for {}; shutting down (--idle-timeout)",
+        humantime::format_duration(timeout)
+    )
+}
+
+fn idle_timeout_elapsed(
+    now_ms: u64,
+    last_activity_ms: u64,
+    has_active_requests: bool,
+    timeout: Duration,
+) -> bool {
+    !has_active_requests
+        && u128::from(now_ms.saturating_sub(last_activity_ms)) >= timeout.as_millis()
+}
+
+/// Triggers a normal shutdown once the proxy has been idle for `timeout`; the returned flag
+/// records whether the shutdown was caused by the idle timeout.
+fn spawn_idle_timeout_watcher(
+    state: Arc<ProxyState>,
+    timeout: Duration,
+    shutdown_tx: tokio::sync::watch::Sender<bool>,
+) -> Arc<AtomicBool> {
+    let fired = Arc::new(AtomicBool::new(false));
+    let fired_by_watcher = fired.clone();
+    let poll_interval = (timeout / 4).clamp(Duration::from_millis(100), Duration::from_secs(5));
+    let mut shutdown_rx = shutdown_tx.subscribe();
+    tokio::spawn(async move {
+        loop {
+            tokio::select! {
+                _ = tokio::time::sleep(poll_interval) => {}
+                _ = shutdown_rx.changed() => return,
+            }
+            if *shutdown_rx.borrow() {
+                return;
+            }
+            if idle_timeout_elapsed(
+                crate::logging::now_ms(),
+                state.last_request_activity_ms(),
+                state.has_active_requests().await,
+                timeout,
+            ) {
+                tracing::info!(
+                    "no requests processed for {}; stopping proxy (--idle-timeout)",
+                    humantime::format_duration(timeout)
+                );
+                fired_by_watcher.store(true, Ordering::SeqCst);
+                let _ = shutdown_tx.send(true);
+                return;
+            }
+        }
+    });
+    fired
+}
+
 async fn await_server_shutdown(runtime: &mut RunningProxyRuntime) -> anyhow::Result<()> {
     runtime.wait().await
 }
@@ -3652,7 +3989,7 @@ async fn do_switch_on(
             ),
         }
         .map_err(|error| CliError::CodexConfig(error.to_string()))?;
-        print_claude_switch_status()?;
+        print_claude_switch_status(commands::cli_lang::cli_language().await)?;
         return Ok(());
     }
 
@@ -3711,36 +4048,58 @@ fn print_codex_client_patch(client_patch: &CodexClientPatchConfig) {
     );
 }
 
-fn do_switch_off(codex: bool, claude: bool) -> CliResult<()> {
+fn do_switch_off(codex: bool, claude: bool, language: tui::Language) -> CliResult<()> {
     if resolve_switch_client(codex, claude)? == SwitchClient::Claude {
         codex_integration::claude_switch_off()
             .map_err(|error| CliError::CodexConfig(error.to_string()))?;
-        print_claude_switch_status()?;
+        print_claude_switch_status(language)?;
         return Ok(());
     }
     let outcome = codex_switch::apply(CodexSwitchIntent::Off)
         .map_err(|error| CliError::CodexConfig(error.to_string()))?;
     println!(
-        "Codex switch: {} ({})",
+        "{} {} ({})",
+        pick(language, "Codex 切换：", "Codex switch:"),
         outcome.change.as_str(),
         outcome.status.phase.as_str()
     );
     Ok(())
 }
 
-fn do_switch_status(codex: bool, claude: bool) -> CliResult<()> {
+async fn do_switch_status(codex: bool, claude: bool, json: bool) -> CliResult<()> {
     let both_unspecified = !codex && !claude;
     let show_codex = codex || both_unspecified;
     let show_claude = claude || both_unspecified;
 
+    if json {
+        let mut entries = Vec::new();
+        if show_codex {
+            let status = codex_switch::inspect()
+                .map_err(|error| CliError::CodexConfig(error.to_string()))?;
+            entries.push(codex_switch_status_json_payload(&status));
+        }
+        if show_claude {
+            let status = codex_integration::claude_switch_status()
+                .map_err(|error| CliError::CodexConfig(error.to_string()))?;
+            entries.push(claude_switch_status_json_payload(&status));
+        }
+        let payload = switch_status_json_payload(entries);
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&payload).unwrap_or_else(|_| "{}".to_string())
+        );
+        return Ok(());
+    }
+
+    let language = commands::cli_lang::cli_language().await;
     if show_codex {
-        print_codex_switch_status()?;
+        print_codex_switch_status(language)?;
         if show_claude {
             println!();
         }
     }
     if show_claude {
-        print_claude_switch_status()?;
+        print_claude_switch_status(language)?;
     }
     Ok(())
 }
@@ -3781,49 +4140,158 @@ async fn handle_default_cmd(codex: bool, claude: bool) -> CliResult<()> {
     Ok(())
 }
 
-fn print_codex_switch_status() -> CliResult<()> {
+/// One selected service prints its status object; several print an array in Codex, Claude order.
+fn switch_status_json_payload(mut entries: Vec<serde_json::Value>) -> serde_json::Value {
+    if entries.len() == 1 {
+        entries.pop().unwrap_or(serde_json::Value::Null)
+    } else {
+        serde_json::Value::Array(entries)
+    }
+}
+
+fn codex_switch_status_json_payload(status: &codex_switch::CodexSwitchStatus) -> serde_json::Value {
+    serde_json::json!({
+        "service": "codex",
+        "config_path": status.config_path,
+        "enabled": status.enabled,
+        "base_url": status.base_url,
+        "model_provider": status.model_provider,
+        // Codex restores from the helper-owned switch state rather than a settings backup.
+        "has_backup": status.managed,
+        "phase": status.phase.as_str(),
+        "recovery_reason": status.recovery_reason,
+    })
+}
+
+fn claude_switch_status_json_payload(
+    status: &codex_integration::ClaudeSwitchStatus,
+) -> serde_json::Value {
+    serde_json::json!({
+        "service": "claude",
+        "config_path": status.settings_path,
+        "enabled": status.enabled,
+        "base_url": status.base_url,
+        "model_provider": serde_json::Value::Null,
+        "has_backup": status.has_backup,
+        "recovery_reason": status.recovery_reason,
+    })
+}
+
+fn print_codex_switch_status(language: tui::Language) -> CliResult<()> {
     let status =
         codex_switch::inspect().map_err(|error| CliError::CodexConfig(error.to_string()))?;
-    println!("{}", "Codex switch status".bold());
-    println!("  phase:   {}", status.phase.as_str());
-    println!("  enabled: {}", status.enabled);
-    println!("  managed: {}", status.managed);
-    println!("  config:  {:?}", status.config_path);
-    println!("  state:   {:?}", status.state_path);
+    println!(
+        "{}",
+        pick(language, "Codex 切换状态", "Codex switch status").bold()
+    );
+    println!(
+        "  {} {}",
+        pick(language, "阶段：", "phase:  "),
+        status.phase.as_str()
+    );
+    println!(
+        "  {} {}",
+        pick(language, "已启用：", "enabled:"),
+        yes_no(language, status.enabled)
+    );
+    println!(
+        "  {} {}",
+        pick(language, "由 helper 管理：", "managed:"),
+        yes_no(language, status.managed)
+    );
+    println!(
+        "  {} {:?}",
+        pick(language, "配置文件：", "config: "),
+        status.config_path
+    );
+    println!(
+        "  {} {:?}",
+        pick(language, "状态文件：", "state:  "),
+        status.state_path
+    );
     println!(
         "  base_url: {}",
-        status.base_url.as_deref().unwrap_or("<unset>")
+        status
+            .base_url
+            .as_deref()
+            .unwrap_or(pick(language, "<未设置>", "<unset>"))
     );
     if let Some(client_patch) = status.client_patch.as_ref() {
         print_codex_client_patch(client_patch);
     }
     if let Some(reason) = status.recovery_reason.as_deref() {
-        println!("  recovery: {}", reason.yellow());
+        println!(
+            "  {} {}",
+            pick(language, "恢复：", "recovery:"),
+            reason.yellow()
+        );
     }
     Ok(())
 }
 
-fn print_claude_switch_status() -> CliResult<()> {
+fn print_claude_switch_status(language: tui::Language) -> CliResult<()> {
     let status = codex_integration::claude_switch_status()
         .map_err(|error| CliError::CodexConfig(error.to_string()))?;
-    println!("{}", "Claude switch status (experimental)".bold());
-    println!("  enabled: {}", status.enabled);
-    println!("  managed: {}", status.has_backup);
-    println!("  settings: {:?}", status.settings_path);
+    println!(
+        "{}",
+        pick(
+            language,
+            "Claude 切换状态（实验性）",
+            "Claude switch status (experimental)"
+        )
+        .bold()
+    );
+    println!(
+        "  {} {}",
+        pick(language, "已启用：", "enabled:"),
+        yes_no(language, status.enabled)
+    );
+    println!(
+        "  {} {}",
+        pick(language, "由 helper 管理：", "managed:"),
+        yes_no(language, status.has_backup)
+    );
+    println!(
+        "  {} {:?}",
+        pick(language, "设置文件：", "settings:"),
+        status.settings_path
+    );
     println!(
         "  base_url: {}",
-        status.base_url.as_deref().unwrap_or("<unset>")
+        status
+            .base_url
+            .as_deref()
+            .unwrap_or(pick(language, "<未设置>", "<unset>"))
     );
     if let Some(reason) = status.recovery_reason.as_deref() {
-        println!("  recovery: {}", reason.yellow());
+        println!(
+            "  {} {}",
+            pick(language, "恢复：", "recovery:"),
+            reason.yellow()
+        );
     }
     Ok(())
 }
 
-async fn wait_for_shutdown_signal() {
+fn yes_no(language: tui::Language, value: bool) -> &'static str {
+    match (language, value) {
+        (tui::Language::Zh, true) => "是",
+        (tui::Language::Zh, false) => "否",
+        (tui::Language::En, true) => "true",
+        (tui::Language::En, false) => "false",
+    }
+}
+
+/// Waits for SIGINT or SIGTERM, and for SIGHUP too when `hangup` is set.
+async fn wait_for_shutdown_signal(hangup: bool) {
     #[cfg(unix)]
     {
         use tokio::signal::unix::{SignalKind, signal};
+        let mut sighup = if hangup {
+            signal(SignalKind::hangup()).ok()
+        } else {
+            None
+        };
         match (
             signal(SignalKind::interrupt()),
             signal(SignalKind::terminate()),
@@ -3832,6 +4300,14 @@ async fn wait_for_shutdown_signal() {
                 tokio::select! {
                     _ = sigint.recv() => {},
                     _ = sigterm.recv() => {},
+                    _ = async {
+                        match sighup.as_mut() {
+                            Some(sighup) => {
+                                sighup.recv().await;
+                            }
+                            None => std::future::pending::<()>().await,
+                        }
+                    } => {},
                 }
             }
             _ => {
@@ -3842,6 +4318,7 @@ async fn wait_for_shutdown_signal() {
     }
     #[cfg(not(unix))]
     {
+        let _ = hangup;
         let _ = tokio::signal::ctrl_c().await;
     }
 }
@@ -3850,15 +4327,18 @@ async fn wait_for_shutdown_signal() {
 mod switch_client_patch_tests {
     use super::{
         CodexSwitchClientPatchSelection, CodexSwitchCredentialReadiness, SwitchClient,
-        classify_switch_credential_readiness, configured_codex_relay_target_for_switch,
+        classify_switch_credential_readiness, claude_switch_status_json_payload,
+        codex_switch_status_json_payload, configured_codex_relay_target_for_switch,
         configured_relay_readiness_probe_error, ensure_switch_credential_readiness,
         legacy_client_facade, preflight_configured_relay_switch_target,
         relay_overview_json_payload, relay_target_has_authenticated_admin, relay_target_list_line,
         resolve_codex_switch_client_patch, resolve_relay_codex_client_patch, resolve_switch_client,
+        switch_status_json_payload,
     };
     use axum::Router;
     use axum::http::{HeaderMap, StatusCode};
     use axum::routing::get;
+    use codex_helper_core::codex_integration::ClaudeSwitchStatus;
     use codex_helper_core::codex_switch::{
         CodexSwitchPhase, CodexSwitchStatus, ValidatedCodexBaseUrl,
     };
@@ -3991,6 +4471,52 @@ mod switch_client_patch_tests {
         assert_eq!(payload["codex"]["client_patch"]["preset"], "default");
     }
 
+    #[test]
+    fn switch_status_json_uses_a_shared_shape_for_both_clients() {
+        let codex = codex_switch_status_json_payload(&CodexSwitchStatus {
+            phase: CodexSwitchPhase::Applied,
+            enabled: true,
+            model_provider: Some("codex_proxy".to_string()),
+            managed: true,
+            base_url: Some("http://127.0.0.1:3211".to_string()),
+            client_patch: None,
+            recovery_reason: None,
+            config_path: "/tmp/codex/config.toml".into(),
+            state_path: "/tmp/helper/state/codex-switch.json".into(),
+        });
+        let claude = claude_switch_status_json_payload(&ClaudeSwitchStatus {
+            enabled: false,
+            base_url: None,
+            has_backup: false,
+            settings_path: "/tmp/claude/settings.json".into(),
+            recovery_reason: None,
+        });
+
+        for payload in [&codex, &claude] {
+            for key in [
+                "service",
+                "config_path",
+                "enabled",
+                "base_url",
+                "model_provider",
+                "has_backup",
+            ] {
+                assert!(payload.get(key).is_some(), "missing {key} in {payload}");
+            }
+        }
+        assert_eq!(codex["service"], "codex");
+        assert_eq!(codex["model_provider"], "codex_proxy");
+        assert_eq!(codex["has_backup"], true);
+        assert_eq!(claude["service"], "claude");
+        assert_eq!(claude["config_path"], "/tmp/claude/settings.json");
+        assert!(claude["model_provider"].is_null());
+
+        assert_eq!(switch_status_json_payload(vec![codex.clone()]), codex);
+        let both = switch_status_json_payload(vec![codex, claude]);
+        assert_eq!(both[0]["service"], "codex");
+        assert_eq!(both[1]["service"], "claude");
+    }
+
     #[test]
     fn explicit_preset_resets_dependent_defaults_but_preserves_other_config_fields() {
         let resolved = resolve_codex_switch_client_patch(
@@ -4720,6 +5246,7 @@ mod serve_startup_tests {
             "codex",
             addr.ip(),
             addr.port(),
+            None,
             true,
             codex_helper_core::local_operator::LocalRuntimeShutdownPolicy::SystemService,
             empty_loaded_test_config(),
@@ -5124,6 +5651,40 @@ mod serve_startup_tests {
         let _ = std::fs::remove_dir_all(root);
     }
 
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn foreground_serve_shuts_down_on_sighup_and_resident_serve_reloads() {
+        assert!(!ServeRuntimeOptions::default().reloads_config_on_sighup());
+        for resident in [
+            ServeRuntimeOptions {
+                resident: true,
+                ..ServeRuntimeOptions::default()
+            },
+            ServeRuntimeOptions {
+                service_managed: true,
+                ..ServeRuntimeOptions::default()
+            },
+        ] {
+            assert!(resident.reloads_config_on_sighup());
+        }
+
+        // Keep a SIGHUP listener registered for the whole test so the signal never falls
+        // back to the default disposition and kills the test process.
+        let _guard = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::hangup())
+            .expect("register SIGHUP");
+        let waiter = tokio::spawn(wait_for_shutdown_signal(true));
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let status = ProcessCommand::new("kill")
+            .args(["-HUP", &std::process::id().to_string()])
+            .status()
+            .expect("send SIGHUP");
+        assert!(status.success());
+        tokio::time::timeout(Duration::from_secs(5), waiter)
+            .await
+            .expect("SIGHUP ends a foreground serve")
+            .expect("shutdown waiter");
+    }
+
     #[test]
     fn client_auto_management_is_owned_only_by_the_ch_entrypoint() {
         assert!(!CliEntrypoint::CodexHelper.auto_manages_codex_client());
@@ -5166,6 +5727,38 @@ mod serve_startup_tests {
         assert!(!resident.should_auto_manage_claude_switch("claude"));
     }
 
+    #[test]
+    fn idle_timeout_waits_for_quiet_period_without_active_requests() {
+        let timeout = Duration::from_secs(60);
+        assert!(!idle_timeout_elapsed(100_000, 50_000, false, timeout));
+        assert!(idle_timeout_elapsed(110_000, 50_000, false, timeout));
+        assert!(!idle_timeout_elapsed(500_000, 50_000, true, timeout));
+        assert!(!idle_timeout_elapsed(10_000, 50_000, false, timeout));
+        assert_eq!(
+            idle_timeout_exit_message(Duration::from_secs(90)),
+            "codex-helper: no requests processed for 1m 30s; shutting down (--idle-timeout)"
+        );
+    }
+
+    #[tokio::test]
+    async fn idle_timeout_watcher_triggers_shutdown_when_idle() {
+        let state = ProxyState::new_with_runtime_store(Arc::new(
+            codex_helper_core::runtime_store::RuntimeStore::open_in_memory()
+                .expect("open in-memory runtime store"),
+        ))
+        .expect("build proxy state");
+        let (shutdown_tx, mut shutdown_rx) = tokio::sync::watch::channel(false);
+        let fired =
+            spawn_idle_timeout_watcher(state, Duration::from_millis(200), shutdown_tx.clone());
+
+        tokio::time::timeout(Duration::from_secs(5), shutdown_rx.changed())
+            .await
+            .expect("idle watcher should request shutdown")
+            .expect("shutdown channel open");
+        assert!(*shutdown_rx.borrow());
+        assert!(fired.load(Ordering::SeqCst));
+    }
+
     #[test]
     fn ch_waits_only_for_platform_states_that_may_own_a_starting_endpoint() {
         for state in [
@@ -5472,7 +6065,7 @@ env_key = "EXTERNAL_API_KEY"
         assert!(status.enabled);
         assert_eq!(status.base_url.as_deref(), Some("http://127.0.0.1:3210"));
 
-        do_switch_off(false, true).expect("explicitly switch Claude off");
+        do_switch_off(false, true, crate::tui::Language::En).expect("explicitly switch Claude off");
         assert_eq!(
             std::fs::read_to_string(&settings_path).expect("read restored settings"),
             original
@@ -5511,6 +6104,7 @@ env_key = "EXTERNAL_API_KEY"
                 "claude",
                 IpAddr::V4(std::net::Ipv4Addr::LOCALHOST),
                 port,
+                None,
                 false,
                 codex_helper_core::local_operator::LocalRuntimeShutdownPolicy::ForegroundProcess,
                 loaded_claude_runtime_test_config(),
@@ -5644,6 +6238,7 @@ env_key = "EXTERNAL_API_KEY"
             "claude",
             addr.ip(),
             addr.port(),
+            None,
             false,
             codex_helper_core::local_operator::LocalRuntimeShutdownPolicy::ForegroundProcess,
             loaded_claude_runtime_test_config(),
@@ -5924,6 +6519,7 @@ env_key = "EXTERNAL_API_KEY"
             "codex",
             proxy_addr.ip(),
             proxy_addr.port(),
+            None,
             false,
             codex_helper_core::local_operator::LocalRuntimeShutdownPolicy::ForegroundProcess,
             empty_loaded_test_config(),
@@ -5970,6 +6566,7 @@ env_key = "EXTERNAL_API_KEY"
                 "codex",
                 proxy_addr.ip(),
                 proxy_addr.port(),
+                None,
                 false,
                 codex_helper_core::local_operator::LocalRuntimeShutdownPolicy::ForegroundProcess,
                 loaded_runtime_test_config(),
@@ -5997,6 +6594,7 @@ env_key = "EXTERNAL_API_KEY"
                 "codex",
                 IpAddr::V4(std::net::Ipv4Addr::LOCALHOST),
                 proxy_port,
+                None,
                 false,
                 codex_helper_core::local_operator::LocalRuntimeShutdownPolicy::ForegroundProcess,
                 loaded_runtime_test_config(),
@@ -7,10 +7,28 @@ use std::str::FromStr;
 #[command(name = "codex-helper", version)]
 #[command(about = "Helper proxy for Codex CLI", long_about = None)]
 pub(crate) struct Cli {
+    /// Use an isolated helper home at `<helper home>/profiles/<NAME>` for config, logs and state
+    #[arg(
+        long = "profile",
+        id = "helper_profile",
+        global = true,
+        value_name = "NAME"
+    )]
+    pub(crate) helper_profile: Option<String>,
+    /// Language for human-readable output; overrides CODEX_HELPER_LANG and ui.language (JSON stays English)
+    #[arg(long, global = true, value_enum, value_name = "LANG")]
+    pub(crate) lang: Option<CliLangArg>,
     #[command(subcommand)]
     pub(crate) command: Option<Command>,
 }
 
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CliLangArg {
+    Zh,
+    En,
+    Auto,
+}
+
 pub type CliResult<T> = Result<T, CliError>;
 
 #[derive(Debug)]
@@ -78,6 +96,51 @@ pub(crate) enum Command {
         /// Mark a resident proxy as system-service-owned; internal use by `service`
         #[arg(long, hide = true)]
         service_managed: bool,
+        /// Stop the proxy after no requests have been processed for this long (e.g. 30m); 0 = never
+        #[arg(long)]
+        idle_timeout: Option<humantime::Duration>,
+        /// Prime upstream connections in the background after startup (best-effort)
+        #[arg(long)]
+        warmup: bool,
+        /// With `ch`, leave the client switch applied when this foreground proxy exits instead of restoring it
+        #[arg(long, conflicts_with_all = ["resident", "confirm_restore"])]
+        no_auto_restore: bool,
+        /// With `ch`, ask before restoring on exit if Codex config.toml was edited after switch-on (interactive terminals only)
+        #[arg(long, conflicts_with = "resident")]
+        confirm_restore: bool,
+        /// Source this .env file before resolving provider auth env vars (defaults to ~/.codex-helper/.env when present; process env always wins)
+        #[arg(long, value_name = "PATH")]
+        env_file: Option<std::path::PathBuf>,
+        /// Also serve the proxy on this Unix domain socket (Unix only; the admin API and client switch stay on TCP loopback)
+        #[arg(long, value_name = "PATH")]
+        unix_socket: Option<std::path::PathBuf>,
+        /// Print the effective config (secrets redacted), env-var status and startup warnings, then exit without binding
+        #[arg(long)]
+        print_config: bool,
+        /// With --print-config, output JSON instead of TOML and text
+        #[arg(long, requires = "print_config")]
+        json: bool,
+        /// Start a resident proxy in the background, record its PID under ~/.codex-helper/run and return
+        #[arg(
+            long,
+            conflicts_with_all = [
+                "print_config",
+                "stop",
+                "status",
+                "supervisor_managed",
+                "desktop_managed",
+                "service_managed",
+                "no_auto_restore",
+                "confirm_restore",
+            ]
+        )]
+        detach: bool,
+        /// Gracefully stop the proxy started with --detach
+        #[arg(long, conflicts_with_all = ["print_config", "status"])]
+        stop: bool,
+        /// Show whether the proxy started with --detach is still running
+        #[arg(long, conflicts_with = "print_config")]
+        status: bool,
     },
     /// Inspect or control a resident codex-helper proxy
     Daemon {
@@ -143,15 +206,18 @@ pub(crate) enum Command {
     },
     /// Run environment diagnostics for Codex CLI and codex-helper
     Doctor {
-        /// Output diagnostics as JSON (machine-readable), without ANSI colors
+        /// Output check records (id, title, severity, detail, remediation) as JSON, without ANSI colors
         #[arg(long)]
         json: bool,
     },
     /// Show a brief status summary of codex-helper and upstream routing
     Status {
         /// Output status as JSON (machine-readable), without ANSI colors
-        #[arg(long)]
+        #[arg(long, conflicts_with = "oneline")]
         json: bool,
+        /// Print one uncolored line for shell prompts, e.g. `codex:on@3211 active=relay err=1% up=2h`
+        #[arg(long)]
+        oneline: bool,
     },
     /// Inspect the runtime's read-only operator usage projection
     Usage {
@@ -487,6 +553,8 @@ pub(crate) enum NotifyCommand {
         #[arg(long)]
         toast: bool,
     },
+    /// Show a sample system notification to check that the OS displays codex-helper toasts
+    Test,
     /// Internal: flush pending merged events and emit notifications (spawned in background).
     #[command(hide = true)]
     FlushCodex,
@@ -558,6 +626,9 @@ pub(crate) enum SwitchCommand {
         /// Show Claude switch status
         #[arg(long)]
         claude: bool,
+        /// Output status as JSON (an array when both clients are shown), without ANSI colors
+        #[arg(long)]
+        json: bool,
     },
 }
 
@@ -655,6 +726,14 @@ impl From<CodexRelayEvidenceKindArg> for codex_helper_core::proxy::CodexRelayEvi
     }
 }
 
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+#[value(rename_all = "kebab-case")]
+pub enum EnvTemplateShell {
+    Bash,
+    Fish,
+    Powershell,
+}
+
 #[derive(Subcommand, Debug)]
 pub enum ConfigCommand {
     /// Initialize a commented routing-first TOML config template
@@ -681,6 +760,172 @@ pub enum ConfigCommand {
         #[arg(long, requires = "write")]
         yes: bool,
     },
+    /// Check that every environment variable referenced by provider auth is set (values are never printed)
+    #[command(name = "validate-env")]
+    ValidateEnv {
+        /// Only check Codex providers (both services are checked when neither flag is set)
+        #[arg(long)]
+        codex: bool,
+        /// Only check Claude providers
+        #[arg(long)]
+        claude: bool,
+    },
+    /// Print a shell script skeleton that exports every environment variable referenced by provider auth (placeholders only)
+    #[command(name = "env-template")]
+    EnvTemplate {
+        /// Syntax of the generated script
+        #[arg(long, value_enum, default_value_t = EnvTemplateShell::Bash)]
+        shell: EnvTemplateShell,
+        /// Only include Codex providers (both services are included when neither flag is set)
+        #[arg(long)]
+        codex: bool,
+        /// Only include Claude providers
+        #[arg(long)]
+        claude: bool,
+    },
+    /// Move plaintext auth_token/api_key values out of config.toml into `*_env` references
+    #[command(name = "migrate-secrets")]
+    MigrateSecrets {
+        /// Preview which credentials would move and to which variables (the default).
+        #[arg(long, conflicts_with = "write")]
+        dry_run: bool,
+        /// Back up config.toml, replace inline credentials with `*_env`, and write an env template.
+        #[arg(long, conflicts_with = "dry_run", requires = "yes")]
+        write: bool,
+        /// Confirm rewriting config.toml.
+        #[arg(long, requires = "write")]
+        yes: bool,
+    },
+    /// Report advisory config problems: endpoints sharing a base_url, unusable enabled_schedule entries,
+    /// and, with routing.validate_models_online, models the cached upstream /v1/models lists do not offer
+    Lint {
+        /// Only lint Codex (both services are linted when neither flag is set)
+        #[arg(long)]
+        codex: bool,
+        /// Only lint Claude
+        #[arg(long)]
+        claude: bool,
+    },
+    /// List providers with routing level, active target and auth source (secrets are never printed)
+    List {
+        /// Only list the Codex provider catalog (text output lists both services when no service flag is set)
+        #[arg(long)]
+        codex: bool,
+        /// Only list the Claude provider catalog
+        #[arg(long)]
+        claude: bool,
+        /// List both services; with --json, emits an object keyed by service
+        #[arg(long, conflicts_with_all = ["codex", "claude"])]
+        both: bool,
+        /// Output JSON instead of text
+        #[arg(long)]
+        json: bool,
+    },
+    /// Show one provider's resolved settings: auth source, routing level, overrides (secrets are never printed)
+    Show {
+        /// Provider name or alias
+        name: String,
+        /// Target Codex provider catalog (default if neither flag is set)
+        #[arg(long)]
+        codex: bool,
+        /// Target Claude provider catalog
+        #[arg(long)]
+        claude: bool,
+        /// Output JSON instead of text
+        #[arg(long)]
+        json: bool,
+    },
+    /// Replay recent requests.jsonl entries through the current routing offline and report where they would land
+    SimulateRouting {
+        /// Only replay requests logged within this duration
+        #[arg(long, default_value = "24h")]
+        since: humantime::Duration,
+        /// Target Codex routing (default if neither flag is set)
+        #[arg(long)]
+        codex: bool,
+        /// Target Claude routing
+        #[arg(long)]
+        claude: bool,
+        /// Output JSON instead of text
+        #[arg(long)]
+        json: bool,
+    },
+    /// Print the provider catalog and entry routing as equivalent CLI commands (secrets are never printed)
+    Export {
+        /// Emit `provider add` / `provider set-auth` / `routing set` invocations
+        #[arg(long, required = true)]
+        as_commands: bool,
+        /// Only export Codex (both services are exported when neither flag is set)
+        #[arg(long)]
+        codex: bool,
+        /// Only export Claude
+        #[arg(long)]
+        claude: bool,
+    },
+    /// Dry-run the routing decision against the running proxy's live state without sending a request
+    #[command(name = "explain-route")]
+    ExplainRoute {
+        /// Target Codex routing (default if neither flag is set)
+        #[arg(long)]
+        codex: bool,
+        /// Target Claude routing
+        #[arg(long)]
+        claude: bool,
+        /// Requested model
+        #[arg(long)]
+        model: Option<String>,
+        /// Requested reasoning effort
+        #[arg(long)]
+        effort: Option<String>,
+        /// Requested service tier
+        #[arg(long = "service-tier")]
+        service_tier: Option<String>,
+        /// Session id, so existing session affinity is honored
+        #[arg(long)]
+        session: Option<String>,
+        /// Proxy port (defaults to the service's default port)
+        #[arg(long)]
+        port: Option<u16>,
+        /// Output JSON instead of text
+        #[arg(long)]
+        json: bool,
+    },
+    /// Show what the service is actively pinned to, or recent active changes with --history
+    Active {
+        /// Only show Codex (text output shows both services when no service flag is set)
+        #[arg(long)]
+        codex: bool,
+        /// Only show Claude
+        #[arg(long)]
+        claude: bool,
+        /// Show both services; with --json, emits an object keyed by service
+        #[arg(long, conflicts_with_all = ["codex", "claude"])]
+        both: bool,
+        /// Print recent pin / new-session preference changes instead of the current state
+        #[arg(long)]
+        history: bool,
+        /// Maximum history entries to print
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+        /// Output JSON instead of text
+        #[arg(long)]
+        json: bool,
+    },
+    /// Inspect `--profile` homes nested under the helper home
+    Profile {
+        #[command(subcommand)]
+        cmd: ConfigProfileCommand,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ConfigProfileCommand {
+    /// List the profiles that exist under the helper home
+    List {
+        /// Output JSON instead of text
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -925,7 +1170,7 @@ pub enum ProviderCommand {
         ArgGroup::new("provider_auth_source")
             .required(true)
             .multiple(false)
-            .args(["native", "secret_file", "environment"])
+            .args(["native", "secret_file", "environment", "inline"])
     ))]
     SetAuth {
         name: String,
@@ -941,6 +1186,12 @@ pub enum ProviderCommand {
         /// Bind to an environment variable name
         #[arg(long, value_name = "ENV")]
         environment: Option<String>,
+        /// Store the secret inline in config.toml, read from a masked prompt (or --stdin)
+        #[arg(long)]
+        inline: bool,
+        /// Read the inline secret from standard input instead of a masked TTY prompt
+        #[arg(long, conflicts_with_all = ["native", "secret_file", "environment"])]
+        stdin: bool,
         /// Target Codex provider catalog (default if neither flag is set)
         #[arg(long)]
         codex: bool,
@@ -1018,6 +1269,7 @@ pub enum RoutingPolicy {
     ManualSticky,
     OrderedFailover,
     RoundRobin,
+    LeastRecentErrors,
     TagPreferred,
 }
 
@@ -1027,6 +1279,7 @@ impl From<RoutingPolicy> for codex_helper_core::config::RouteStrategy {
             RoutingPolicy::ManualSticky => Self::ManualSticky,
             RoutingPolicy::OrderedFailover => Self::OrderedFailover,
             RoutingPolicy::RoundRobin => Self::RoundRobin,
+            RoutingPolicy::LeastRecentErrors => Self::LeastRecentErrors,
             RoutingPolicy::TagPreferred => Self::TagPreferred,
         }
     }
@@ -1068,12 +1321,18 @@ pub enum SessionCommand {
         /// Maximum number of sessions to show
         #[arg(long, default_value_t = 20)]
         limit: usize,
+        /// Skip this many sessions first (pages follow file mtime, newest first)
+        #[arg(long, default_value_t = 0)]
+        offset: usize,
         /// Optional directory to search sessions for; defaults to current dir
         #[arg(long)]
         path: Option<String>,
         /// Truncate the first prompt to N characters (default: do not truncate)
         #[arg(long)]
         truncate: Option<usize>,
+        /// Output the page as JSON
+        #[arg(long)]
+        json: bool,
     },
     /// Print recent Codex sessions as `project_root session_id` (one per line)
     Recent {
@@ -1086,13 +1345,13 @@ pub enum SessionCommand {
         /// Print the raw session cwd instead of inferring a git project root
         #[arg(long)]
         raw_cwd: bool,
-        /// Output format: text | tsv | json
+        /// Output format: text | tsv | json | ndjson
         #[arg(long, value_enum, default_value_t = RecentFormat::Text)]
         format: RecentFormat,
-        /// Open each session in a new terminal window/tab (best-effort; Windows-first)
+        /// Open each session in a new terminal window/tab (best-effort)
         #[arg(long)]
         open: bool,
-        /// Terminal backend used by --open (Windows: wt recommended; cross-platform: wezterm)
+        /// Terminal backend used by --open (default: wt on Windows, iTerm or Terminal on macOS, the first of wezterm/gnome-terminal/konsole found on Linux)
         #[arg(long, value_enum)]
         terminal: Option<RecentTerminal>,
         /// Shell executable for --open (Windows examples: `pwsh` or full path to pwsh.exe)
@@ -1127,9 +1386,15 @@ pub enum SessionCommand {
         /// Output format: text | markdown | json
         #[arg(long, default_value = "text")]
         format: String,
-        /// Include timestamps when available (text format only)
+        /// Include timestamps when available (text and markdown formats)
         #[arg(long)]
         timestamps: bool,
+        /// Only print messages whose text matches this regular expression
+        #[arg(long, value_name = "PATTERN")]
+        grep: Option<String>,
+        /// With --grep, also print N messages before and after each match
+        #[arg(long, value_name = "N", default_value_t = 0, requires = "grep")]
+        context: usize,
         /// Optional directory hint to resolve the session id; defaults to current dir
         #[arg(long)]
         path: Option<String>,
@@ -1142,8 +1407,14 @@ pub enum SessionCommand {
         #[arg(long, default_value_t = 20)]
         limit: usize,
         /// Optional directory to search sessions for; defaults to current dir
-        #[arg(long)]
+        #[arg(long, conflicts_with = "all")]
         path: Option<String>,
+        /// Search every project's sessions instead of only the current directory's
+        #[arg(long)]
+        all: bool,
+        /// With --all, only scan sessions updated within this duration (based on session file mtime)
+        #[arg(long, default_value = "30d", requires = "all")]
+        since: humantime::Duration,
         /// Truncate the first prompt to N characters (default: do not truncate)
         #[arg(long)]
         truncate: Option<usize>,
@@ -1151,19 +1422,53 @@ pub enum SessionCommand {
     /// Export a Codex session to a file
     Export {
         /// Session id to export
-        id: String,
-        /// Output format: markdown or json
+        #[arg(required_unless_present = "all_in_project")]
+        id: Option<String>,
+        /// Output format: markdown, json, html, or context (condensed plain text to paste as the
+        /// first prompt of a new session)
         #[arg(long, default_value = "markdown")]
         format: String,
-        /// Optional output path; defaults to stdout
+        /// With `--format context`, the character budget; the oldest messages are dropped first
+        #[arg(long, value_name = "N", default_value_t = 12_000)]
+        max_chars: usize,
+        /// With `--format context`, keep tool calls (their outputs are always left out)
         #[arg(long)]
+        include_tools: bool,
+        /// Optional output path; defaults to stdout
+        #[arg(long, conflicts_with = "all_in_project")]
         output: Option<String>,
+        /// Export every session of this project root, one file per session
+        #[arg(
+            long = "all-in-project",
+            value_name = "ROOT",
+            conflicts_with = "id",
+            requires = "output_dir"
+        )]
+        all_in_project: Option<String>,
+        /// Directory for `--all-in-project` files, named `<date>_<session id>.<ext>`
+        #[arg(long = "output-dir", requires = "all_in_project")]
+        output_dir: Option<String>,
     },
     /// Show the last Codex session for the current project
     Last {
         /// Optional directory to search sessions for; defaults to current dir
         #[arg(long)]
         path: Option<String>,
+        /// Print the session as one JSON object, with token totals when the runtime store has them
+        #[arg(long)]
+        json: bool,
+    },
+    /// Follow the current project's newest session and notify on errors, finished turns, or stalls
+    Watch {
+        /// Optional directory to search sessions for; defaults to current dir
+        #[arg(long)]
+        path: Option<String>,
+        /// How often to check the session file for growth
+        #[arg(long, default_value = "2s")]
+        interval: humantime::Duration,
+        /// Notify once a turn has been quiet this long without Codex recording `task_complete`
+        #[arg(long = "idle-after", default_value = "60s")]
+        idle_after: humantime::Duration,
     },
 }
 
@@ -1173,6 +1478,7 @@ pub enum RecentFormat {
     Text,
     Tsv,
     Json,
+    Ndjson,
 }
 
 #[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
@@ -1182,6 +1488,14 @@ pub enum RecentTerminal {
     Wt,
     /// WezTerm (`wezterm`)
     Wezterm,
+    /// macOS Terminal.app (via `osascript`)
+    Terminal,
+    /// iTerm2 on macOS (via `osascript`)
+    Iterm,
+    /// GNOME Terminal (`gnome-terminal`)
+    GnomeTerminal,
+    /// KDE Konsole (`konsole`)
+    Konsole,
 }
 
 #[derive(Subcommand, Debug)]
@@ -1200,6 +1514,9 @@ pub enum UsageCommand {
         /// Maximum number of recent entries to print
         #[arg(long, default_value_t = 20)]
         limit: usize,
+        /// Only print requests whose provider id contains this text
+        #[arg(long, alias = "config")]
+        provider: Option<String>,
         /// Print raw JSON lines instead of human-friendly format
         #[arg(long)]
         raw: bool,
@@ -1209,9 +1526,15 @@ pub enum UsageCommand {
         /// Maximum number of summary rows to show (sorted by total_tokens desc)
         #[arg(long, default_value_t = 20)]
         limit: usize,
-        /// Group summary rows by provider endpoint, provider, model, or session
+        /// Group summary rows by provider endpoint, provider, model, session, or tenant
         #[arg(long, value_enum, default_value_t = UsageSummaryBy::Provider)]
         by: UsageSummaryBy,
+        /// Output the summary as JSON with its generation time and covered window
+        #[arg(long)]
+        json: bool,
+        /// Write the JSON summary to this file atomically instead of stdout
+        #[arg(long, value_name = "PATH", requires = "json")]
+        output: Option<std::path::PathBuf>,
     },
     /// Find matching requests in the selected usage authority
     Find {
@@ -1270,6 +1593,41 @@ pub enum UsageCommand {
         #[arg(long)]
         json: bool,
     },
+    /// Resend one logged request through the local proxy using its captured debug body
+    Replay {
+        /// Request id from requests.jsonl (see `usage tail` / `usage find`)
+        request_id: u64,
+        /// Print the original and replayed outcomes as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Count unparseable requests.jsonl lines, such as a final line cut short by a crash
+    Check {
+        /// Rewrite the file without the unparseable lines
+        #[arg(long)]
+        fix: bool,
+    },
+    /// Prune old requests.jsonl entries; a dry run unless --yes is given
+    #[command(group(
+        clap::ArgGroup::new("gc_bound")
+            .required(true)
+            .multiple(true)
+            .args(["older_than", "keep"])
+    ))]
+    Gc {
+        /// Drop entries older than this, e.g. 30d or 12h
+        #[arg(long, value_name = "DURATION")]
+        older_than: Option<humantime::Duration>,
+        /// Keep at most this many of the newest entries
+        #[arg(long, value_name = "N")]
+        keep: Option<usize>,
+        /// Roll dropped entries into daily aggregates in requests_daily.jsonl
+        #[arg(long)]
+        compact: bool,
+        /// Rewrite the file; without it only the reclaimable size is reported
+        #[arg(long)]
+        yes: bool,
+    },
 }
 
 #[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
@@ -1280,6 +1638,8 @@ pub enum UsageSummaryBy {
     Provider,
     Model,
     Session,
+    /// Group by the `x-codex-helper-tenant` request header (`<untagged>` when absent)
+    Tenant,
 }
 
 #[derive(ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq)]
@@ -1456,6 +1816,48 @@ mod tests {
         }
     }
 
+    #[test]
+    fn profile_flag_is_global_and_distinct_from_retry_profile() {
+        let cli = Cli::try_parse_from([
+            "codex-helper",
+            "config",
+            "set-retry-profile",
+            "balanced",
+            "--profile",
+            "work",
+        ])
+        .expect("parse profile after subcommand");
+        assert_eq!(cli.helper_profile.as_deref(), Some("work"));
+        assert!(matches!(
+            cli.command,
+            Some(Command::Config {
+                cmd: ConfigCommand::SetRetryProfile {
+                    profile: RetryProfile::Balanced
+                }
+            })
+        ));
+
+        let cli = Cli::try_parse_from([
+            "codex-helper",
+            "--profile",
+            "work",
+            "config",
+            "profile",
+            "list",
+            "--json",
+        ])
+        .expect("parse profile list");
+        assert_eq!(cli.helper_profile.as_deref(), Some("work"));
+        assert!(matches!(
+            cli.command,
+            Some(Command::Config {
+                cmd: ConfigCommand::Profile {
+                    cmd: ConfigProfileCommand::List { json: true }
+                }
+            })
+        ));
+    }
+
     #[test]
     fn config_migrate_requires_explicit_write_confirmation() {
         assert!(Cli::try_parse_from(["codex-helper", "config", "migrate"]).is_ok());
@@ -1474,6 +1876,43 @@ mod tests {
         );
     }
 
+    #[test]
+    fn config_env_template_defaults_to_bash() {
+        let cli = Cli::try_parse_from(["codex-helper", "config", "env-template"])
+            .expect("parse config env-template");
+        assert!(matches!(
+            cli.command,
+            Some(Command::Config {
+                cmd: ConfigCommand::EnvTemplate {
+                    shell: EnvTemplateShell::Bash,
+                    codex: false,
+                    claude: false,
+                }
+            })
+        ));
+        let cli = Cli::try_parse_from([
+            "codex-helper",
+            "config",
+            "env-template",
+            "--shell",
+            "powershell",
+        ])
+        .expect("parse config env-template --shell");
+        assert!(matches!(
+            cli.command,
+            Some(Command::Config {
+                cmd: ConfigCommand::EnvTemplate {
+                    shell: EnvTemplateShell::Powershell,
+                    ..
+                }
+            })
+        ));
+        assert!(
+            Cli::try_parse_from(["codex-helper", "config", "env-template", "--shell", "zsh"])
+                .is_err()
+        );
+    }
+
     #[test]
     fn switch_on_accepts_explicit_base_url() {
         let cli = Cli::try_parse_from([
@@ -1889,6 +2328,84 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn usage_summary_output_requires_json() {
+        let summary = Cli::try_parse_from([
+            "codex-helper",
+            "usage",
+            "summary",
+            "--json",
+            "--output",
+            "/tmp/usage.json",
+        ])
+        .expect("parse usage summary json output");
+        let Some(Command::Usage {
+            cmd: UsageCommand::Summary { json, output, .. },
+            ..
+        }) = summary.command
+        else {
+            panic!("expected usage summary command");
+        };
+        assert!(json);
+        assert_eq!(
+            output.as_deref(),
+            Some(std::path::Path::new("/tmp/usage.json"))
+        );
+
+        let error = Cli::try_parse_from([
+            "codex-helper",
+            "usage",
+            "summary",
+            "--output",
+            "/tmp/usage.json",
+        ])
+        .expect_err("--output without --json should be rejected");
+        assert_eq!(
+            error.kind(),
+            clap::error::ErrorKind::MissingRequiredArgument
+        );
+    }
+
+    #[test]
+    fn usage_gc_requires_a_bound_and_defaults_to_dry_run() {
+        let gc = Cli::try_parse_from([
+            "codex-helper",
+            "usage",
+            "gc",
+            "--older-than",
+            "30d",
+            "--compact",
+        ])
+        .expect("parse usage gc");
+        let Some(Command::Usage {
+            cmd:
+                UsageCommand::Gc {
+                    older_than,
+                    keep,
+                    compact,
+                    yes,
+                },
+            ..
+        }) = gc.command
+        else {
+            panic!("expected usage gc command");
+        };
+        assert_eq!(
+            older_than.map(std::time::Duration::from),
+            Some(std::time::Duration::from_secs(30 * 24 * 60 * 60))
+        );
+        assert_eq!(keep, None);
+        assert!(compact);
+        assert!(!yes);
+
+        let error = Cli::try_parse_from(["codex-helper", "usage", "gc", "--yes"])
+            .expect_err("gc without --older-than or --keep should be rejected");
+        assert_eq!(
+            error.kind(),
+            clap::error::ErrorKind::MissingRequiredArgument
+        );
+    }
+
     #[test]
     fn codex_relay_cli_live_smoke_requires_acknowledgement_argument() {
         let error = Cli::try_parse_from([
@@ -2090,6 +2607,124 @@ mod tests {
         assert!(desktop_managed);
     }
 
+    #[test]
+    fn serve_cli_parses_idle_timeout() {
+        let cli = Cli::try_parse_from(["codex-helper", "serve", "--idle-timeout", "15m"])
+            .expect("parse serve idle timeout");
+
+        let Some(Command::Serve { idle_timeout, .. }) = cli.command else {
+            panic!("expected serve command");
+        };
+        assert_eq!(
+            idle_timeout.map(Into::into),
+            Some(std::time::Duration::from_secs(15 * 60))
+        );
+    }
+
+    #[test]
+    fn serve_cli_warmup_is_opt_in() {
+        let Some(Command::Serve { warmup, .. }) = Cli::try_parse_from(["codex-helper", "serve"])
+            .expect("parse serve")
+            .command
+        else {
+            panic!("expected serve command");
+        };
+        assert!(!warmup);
+
+        let Some(Command::Serve { warmup, .. }) =
+            Cli::try_parse_from(["codex-helper", "serve", "--warmup"])
+                .expect("parse serve warmup")
+                .command
+        else {
+            panic!("expected serve command");
+        };
+        assert!(warmup);
+    }
+
+    #[test]
+    fn serve_cli_parses_unix_socket_path() {
+        let Some(Command::Serve { unix_socket, .. }) =
+            Cli::try_parse_from(["codex-helper", "serve", "--unix-socket", "/tmp/ch.sock"])
+                .expect("parse serve unix socket")
+                .command
+        else {
+            panic!("expected serve command");
+        };
+        assert_eq!(
+            unix_socket.as_deref(),
+            Some(std::path::Path::new("/tmp/ch.sock"))
+        );
+    }
+
+    #[test]
+    fn serve_cli_restore_flags_are_exclusive() {
+        let Some(Command::Serve {
+            no_auto_restore,
+            confirm_restore,
+            ..
+        }) = Cli::try_parse_from(["ch", "serve", "--no-auto-restore"])
+            .expect("parse serve no-auto-restore")
+            .command
+        else {
+            panic!("expected serve command");
+        };
+        assert!(no_auto_restore);
+        assert!(!confirm_restore);
+
+        assert!(
+            Cli::try_parse_from(["ch", "serve", "--no-auto-restore", "--confirm-restore"]).is_err()
+        );
+        assert!(Cli::try_parse_from(["ch", "serve", "--resident", "--no-auto-restore"]).is_err());
+    }
+
+    #[test]
+    fn serve_cli_parses_print_config_and_requires_it_for_json() {
+        let Some(Command::Serve {
+            print_config, json, ..
+        }) = Cli::try_parse_from(["codex-helper", "serve", "--print-config", "--json"])
+            .expect("parse serve print-config")
+            .command
+        else {
+            panic!("expected serve command");
+        };
+        assert!(print_config);
+        assert!(json);
+
+        assert!(Cli::try_parse_from(["codex-helper", "serve", "--json"]).is_err());
+    }
+
+    #[test]
+    fn serve_cli_detach_controls_are_exclusive() {
+        let Some(Command::Serve { detach, stop, .. }) =
+            Cli::try_parse_from(["codex-helper", "serve", "--claude", "--detach"])
+                .expect("parse serve detach")
+                .command
+        else {
+            panic!("expected serve command");
+        };
+        assert!(detach);
+        assert!(!stop);
+
+        assert!(Cli::try_parse_from(["codex-helper", "serve", "--detach", "--stop"]).is_err());
+        assert!(Cli::try_parse_from(["codex-helper", "serve", "--stop", "--status"]).is_err());
+        assert!(
+            Cli::try_parse_from(["codex-helper", "serve", "--detach", "--print-config"]).is_err()
+        );
+    }
+
+    #[test]
+    fn notify_test_cli_parses() {
+        let cli =
+            Cli::try_parse_from(["codex-helper", "notify", "test"]).expect("parse notify test");
+
+        assert!(matches!(
+            cli.command,
+            Some(Command::Notify {
+                cmd: NotifyCommand::Test
+            })
+        ));
+    }
+
     #[test]
     fn serve_cli_parses_hidden_service_managed_flag() {
         let cli = Cli::try_parse_from(["codex-helper", "serve", "--codex", "--service-managed"])
@@ -2757,6 +3392,16 @@ mod tests {
                 "--environment",
                 "RELAY_API_KEY",
             ],
+            vec![
+                "codex-helper",
+                "provider",
+                "set-auth",
+                "relay",
+                "--kind",
+                "bearer",
+                "--inline",
+                "--stdin",
+            ],
         ] {
             Cli::try_parse_from(args).expect("parse provider reference source");
         }
@@ -2821,6 +3466,17 @@ mod tests {
                 "--native",
                 "relay.primary",
             ],
+            vec![
+                "codex-helper",
+                "provider",
+                "set-auth",
+                "relay",
+                "--kind",
+                "bearer",
+                "--environment",
+                "RELAY_TOKEN",
+                "--stdin",
+            ],
         ] {
             assert!(Cli::try_parse_from(args).is_err());
         }
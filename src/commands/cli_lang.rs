@@ -0,0 +1,92 @@
+//! Language for human CLI output, shared with the TUI's `ui.language` setting.
+//!
+//! Precedence: the global `--lang` flag, then `CODEX_HELPER_LANG`, then `ui.language`, then the
+//! system locale. `--json` output never goes through this; it stays English.
+
+use std::sync::OnceLock;
+
+use crate::cli_types::CliLangArg;
+use crate::config::storage::load_config;
+use crate::doctor::DoctorLang;
+use crate::tui::{Language, detect_system_language, resolve_language_preference};
+
+static CLI_LANGUAGE_OVERRIDE: OnceLock<Language> = OnceLock::new();
+
+pub(crate) fn set_cli_language_override(lang: CliLangArg) {
+    let language = match lang {
+        CliLangArg::Zh => Language::Zh,
+        CliLangArg::En => Language::En,
+        CliLangArg::Auto => detect_system_language(),
+    };
+    let _ = CLI_LANGUAGE_OVERRIDE.set(language);
+}
+
+/// The `--lang` override when one was given on the command line.
+pub(crate) fn cli_language_override() -> Option<Language> {
+    CLI_LANGUAGE_OVERRIDE.get().copied()
+}
+
+pub(crate) async fn cli_language() -> Language {
+    let configured = match cli_language_override() {
+        Some(_) => None,
+        None => load_config()
+            .await
+            .ok()
+            .and_then(|config| config.ui.language),
+    };
+    resolve_cli_language(
+        cli_language_override(),
+        std::env::var("CODEX_HELPER_LANG").ok().as_deref(),
+        configured.as_deref(),
+    )
+}
+
+fn resolve_cli_language(
+    flag: Option<Language>,
+    env: Option<&str>,
+    configured: Option<&str>,
+) -> Language {
+    if let Some(language) = flag {
+        return language;
+    }
+    match env.map(str::trim).filter(|value| !value.is_empty()) {
+        Some(value) => resolve_language_preference(Some(value)),
+        None => resolve_language_preference(configured),
+    }
+}
+
+pub(crate) fn doctor_lang(language: Language) -> DoctorLang {
+    match language {
+        Language::Zh => DoctorLang::Zh,
+        Language::En => DoctorLang::En,
+    }
+}
+
+pub(crate) fn pick(language: Language, zh: &'static str, en: &'static str) -> &'static str {
+    match language {
+        Language::Zh => zh,
+        Language::En => en,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flag_beats_env_and_env_beats_config() {
+        assert_eq!(
+            resolve_cli_language(Some(Language::En), Some("zh"), Some("zh")),
+            Language::En
+        );
+        assert_eq!(
+            resolve_cli_language(None, Some("zh-CN"), Some("en")),
+            Language::Zh
+        );
+        assert_eq!(
+            resolve_cli_language(None, Some(" "), Some("en")),
+            Language::En
+        );
+        assert_eq!(resolve_cli_language(None, None, Some("zh")), Language::Zh);
+    }
+}
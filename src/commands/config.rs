@@ -1,8 +1,24 @@
+use super::config_doc::{ordered_provider_names, routing_exhausted_label, routing_policy_label};
+use super::config_env_template::handle_config_env_template;
+use super::config_secrets::handle_config_migrate_secrets;
+use super::config_show::{handle_config_list, handle_config_show};
+use super::config_simulate::handle_config_simulate_routing;
+use super::route_view::{ExplainRouteArgs, handle_explain_route};
+use super::routing::handle_config_active;
+use super::term_style::{Tone, configured_theme, paint};
+use crate::cli_types::ConfigProfileCommand;
 use crate::config::{
-    RetryConfig, RetryProfileName,
+    CredentialRef, HELPER_PROFILES_DIR, HelperConfig, ProviderConfig, RetryConfig,
+    RetryProfileName, RouteExhaustedAction, RouteStrategy, ServiceKind, ServiceRouteConfig,
+    UpstreamAuth, helper_profile_home, list_helper_profiles, proxy_home_dir,
     storage::{init_config_toml_with_outcome, load_config, mutate_helper_config},
 };
+use crate::config_lint::service_lint_warnings;
+use crate::doctor::env_var_references;
+use crate::upstream_models::{load_upstream_model_cache, model_validation_warnings};
 use crate::{CliError, CliResult, ConfigCommand, RetryProfile};
+use std::path::PathBuf;
+use std::sync::OnceLock;
 
 pub async fn handle_config_cmd(cmd: ConfigCommand) -> CliResult<()> {
     match cmd {
@@ -71,7 +87,663 @@ pub async fn handle_config_cmd(cmd: ConfigCommand) -> CliResult<()> {
                 .map_err(|e| CliError::Configuration(e.to_string()))?;
             print!("{report}");
         }
+        ConfigCommand::ValidateEnv { codex, claude } => {
+            // Check what `serve` would see, including the helper home's .env file.
+            crate::env_file::load_env_file(None)
+                .map_err(|e| CliError::Configuration(format!("{e:#}")))?;
+            let config = load_config()
+                .await
+                .map_err(|e| CliError::Configuration(e.to_string()))?;
+            let services = match (codex, claude) {
+                (true, false) => vec![ServiceKind::Codex],
+                (false, true) => vec![ServiceKind::Claude],
+                _ => vec![ServiceKind::Codex, ServiceKind::Claude],
+            };
+            let references = env_var_references(&config, &services);
+            if references.is_empty() {
+                println!("No provider references an environment variable.");
+                return Ok(());
+            }
+            let theme = configured_theme().await;
+            for reference in &references {
+                let state = if reference.set {
+                    paint(theme, Tone::Good, "set")
+                } else if reference.active {
+                    paint(theme, Tone::Bad, "missing")
+                } else {
+                    paint(theme, Tone::Warn, "missing")
+                };
+                let disabled = if reference.active { "" } else { " (disabled)" };
+                println!(
+                    "{state} {}/{} {}={}{disabled}",
+                    reference.service, reference.provider_id, reference.field, reference.variable
+                );
+            }
+            let missing = references
+                .iter()
+                .filter(|reference| reference.active && !reference.set)
+                .map(|reference| reference.variable.as_str())
+                .collect::<Vec<_>>();
+            if !missing.is_empty() {
+                return Err(CliError::Configuration(format!(
+                    "enabled providers reference unset environment variables: {}",
+                    missing.join(", ")
+                )));
+            }
+        }
+        ConfigCommand::EnvTemplate {
+            shell,
+            codex,
+            claude,
+        } => {
+            handle_config_env_template(shell, codex, claude).await?;
+        }
+        ConfigCommand::MigrateSecrets {
+            dry_run: _,
+            write,
+            yes: _,
+        } => {
+            // Same --write/--yes pairing as `config migrate`; the default only previews.
+            handle_config_migrate_secrets(write).await?;
+        }
+        ConfigCommand::Lint { codex, claude } => {
+            let config = load_config()
+                .await
+                .map_err(|e| CliError::Configuration(e.to_string()))?;
+            let services = match (codex, claude) {
+                (true, false) => vec![ServiceKind::Codex],
+                (false, true) => vec![ServiceKind::Claude],
+                _ => vec![ServiceKind::Codex, ServiceKind::Claude],
+            };
+            let theme = configured_theme().await;
+            let mut found = 0;
+            for service in services {
+                let (label, view) = match service {
+                    ServiceKind::Codex => ("codex", &config.codex),
+                    ServiceKind::Claude => ("claude", &config.claude),
+                };
+                let mut warnings = service_lint_warnings(view);
+                if view
+                    .routing
+                    .as_ref()
+                    .is_some_and(|routing| routing.validate_models_online)
+                {
+                    warnings.extend(model_validation_warnings(
+                        view,
+                        &load_upstream_model_cache(label).providers,
+                    ));
+                }
+                for warning in warnings {
+                    found += 1;
+                    println!(
+                        "{} [{label}] {warning}",
+                        paint(theme, Tone::Warn, "warning")
+                    );
+                }
+            }
+            if found == 0 {
+                println!("No problems found.");
+            }
+        }
+        ConfigCommand::List {
+            codex,
+            claude,
+            both,
+            json,
+        } => {
+            handle_config_list(codex, claude, both, json).await?;
+        }
+        ConfigCommand::Show {
+            name,
+            codex,
+            claude,
+            json,
+        } => {
+            handle_config_show(name, codex, claude, json).await?;
+        }
+        ConfigCommand::SimulateRouting {
+            since,
+            codex,
+            claude,
+            json,
+        } => {
+            handle_config_simulate_routing(since.into(), codex, claude, json).await?;
+        }
+        ConfigCommand::Export {
+            as_commands: _,
+            codex,
+            claude,
+        } => {
+            let config = load_config()
+                .await
+                .map_err(|e| CliError::Configuration(e.to_string()))?;
+            let services = match (codex, claude) {
+                (true, false) => vec![ServiceKind::Codex],
+                (false, true) => vec![ServiceKind::Claude],
+                _ => vec![ServiceKind::Codex, ServiceKind::Claude],
+            };
+            for line in export_config_as_commands(&config, &services) {
+                println!("{}", line.render());
+            }
+        }
+        ConfigCommand::ExplainRoute {
+            codex,
+            claude,
+            model,
+            effort,
+            service_tier,
+            session,
+            port,
+            json,
+        } => {
+            handle_explain_route(ExplainRouteArgs {
+                codex,
+                claude,
+                model,
+                effort,
+                service_tier,
+                session,
+                port,
+                json,
+            })
+            .await?;
+        }
+        ConfigCommand::Active {
+            codex,
+            claude,
+            both,
+            history,
+            limit,
+            json,
+        } => {
+            handle_config_active(codex, claude, both, history, limit, json).await?;
+        }
+        ConfigCommand::Profile {
+            cmd: ConfigProfileCommand::List { json },
+        } => {
+            handle_config_profile_list(json)?;
+        }
     }
 
     Ok(())
 }
+
+/// Base helper home and profile name recorded by [`activate_helper_profile`], before the profile
+/// home replaces `CODEX_HELPER_HOME` for the rest of the process.
+static ACTIVE_HELPER_PROFILE: OnceLock<(PathBuf, String)> = OnceLock::new();
+
+/// Point `CODEX_HELPER_HOME` at `<helper home>/profiles/<name>` so config, logs and state
+/// resolve inside the profile. Child processes (daemons, services) inherit the same home.
+pub(crate) fn activate_helper_profile(name: &str) -> CliResult<()> {
+    let base = proxy_home_dir();
+    let home =
+        helper_profile_home(&base, name).map_err(|e| CliError::Configuration(e.to_string()))?;
+    std::fs::create_dir_all(&home).map_err(|e| {
+        CliError::Configuration(format!("create profile home {}: {e}", home.display()))
+    })?;
+    unsafe {
+        std::env::set_var("CODEX_HELPER_HOME", &home);
+    }
+    let _ = ACTIVE_HELPER_PROFILE.set((base, name.trim().to_string()));
+    Ok(())
+}
+
+fn handle_config_profile_list(json: bool) -> CliResult<()> {
+    let (base, active) = match ACTIVE_HELPER_PROFILE.get() {
+        Some((base, name)) => (base.clone(), Some(name.as_str())),
+        None => (proxy_home_dir(), None),
+    };
+    let profiles =
+        list_helper_profiles(&base).map_err(|e| CliError::Configuration(e.to_string()))?;
+
+    if json {
+        let value = serde_json::json!({
+            "home": base,
+            "active": active,
+            "profiles": profiles,
+        });
+        let text = serde_json::to_string_pretty(&value)
+            .map_err(|e| CliError::Other(format!("serialize profiles: {e}")))?;
+        println!("{text}");
+        return Ok(());
+    }
+
+    if profiles.is_empty() {
+        println!(
+            "No profiles under {}; create one with `--profile <name>`.",
+            base.join(HELPER_PROFILES_DIR).display()
+        );
+        return Ok(());
+    }
+    for name in profiles {
+        let marker = if active == Some(name.as_str()) {
+            "*"
+        } else {
+            " "
+        };
+        println!("{marker} {name}");
+    }
+    Ok(())
+}
+
+/// One line of `config export --as-commands` output.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ExportLine {
+    /// A `codex-helper` invocation; the first element is the binary name.
+    Command(Vec<String>),
+    /// Something the CLI cannot express, printed as a shell comment.
+    Note(String),
+}
+
+impl ExportLine {
+    fn render(&self) -> String {
+        match self {
+            ExportLine::Command(args) => args
+                .iter()
+                .map(|arg| shell_quote(arg))
+                .collect::<Vec<_>>()
+                .join(" "),
+            ExportLine::Note(note) => format!("# {note}"),
+        }
+    }
+}
+
+fn shell_quote(value: &str) -> String {
+    let safe = !value.is_empty()
+        && value.chars().all(|ch| {
+            ch.is_ascii_alphanumeric()
+                || matches!(ch, '-' | '_' | '.' | '/' | ':' | '=' | '@' | ',' | '+')
+        });
+    if safe {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', r"'\''"))
+    }
+}
+
+/// Rebuild the provider catalog and entry routing of each service as CLI invocations.
+///
+/// Auth is exported only as environment-variable names and credential references; inline
+/// secrets are replaced by a note asking the operator to rebind them. The script starts with
+/// `config init`, which refuses to touch an existing config.toml.
+fn export_config_as_commands(config: &HelperConfig, services: &[ServiceKind]) -> Vec<ExportLine> {
+    let mut lines = vec![ExportLine::Command(command(["config", "init"]))];
+    for (kind, service, view) in [
+        (ServiceKind::Codex, "codex", &config.codex),
+        (ServiceKind::Claude, "claude", &config.claude),
+    ] {
+        if !services.contains(&kind) || view.providers.is_empty() {
+            continue;
+        }
+        let service_flag = format!("--{service}");
+        let mut exported = Vec::new();
+        for name in ordered_provider_names(view) {
+            let Some(provider) = view.providers.get(name.as_str()) else {
+                continue;
+            };
+            if export_provider(&mut lines, service, &service_flag, &name, provider) {
+                exported.push(name);
+            }
+        }
+        export_entry_routing(&mut lines, service, &service_flag, view, &exported);
+    }
+    lines
+}
+
+fn export_provider(
+    lines: &mut Vec<ExportLine>,
+    service: &str,
+    service_flag: &str,
+    name: &str,
+    provider: &ProviderConfig,
+) -> bool {
+    let Some(base_url) = provider
+        .base_url
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+    else {
+        lines.push(ExportLine::Note(format!(
+            "{service} provider '{name}' has no inline base_url; add it by editing config.toml"
+        )));
+        return false;
+    };
+
+    let auth = provider.effective_auth();
+    let mut add = command(["provider", "add", name, "--base-url", base_url]);
+    if let Some(variable) = auth.auth_token_env.as_deref() {
+        add.extend(["--auth-token-env".to_string(), variable.to_string()]);
+    }
+    if let Some(variable) = auth.api_key_env.as_deref() {
+        add.extend(["--api-key-env".to_string(), variable.to_string()]);
+    }
+    if auth.allow_anonymous == Some(true) {
+        add.push("--allow-anonymous".to_string());
+    }
+    if let Some(alias) = provider.alias.as_deref() {
+        add.extend(["--alias".to_string(), alias.to_string()]);
+    }
+    for (key, value) in &provider.tags {
+        add.extend(["--tag".to_string(), format!("{key}={value}")]);
+    }
+    for (model, supported) in &provider.supported_models {
+        if *supported {
+            add.extend(["--supported-model".to_string(), model.clone()]);
+        }
+    }
+    for (from, to) in &provider.model_mapping {
+        add.extend(["--model-map".to_string(), format!("{from}={to}")]);
+    }
+    if !provider.enabled {
+        add.push("--disabled".to_string());
+    }
+    add.push(service_flag.to_string());
+    lines.push(ExportLine::Command(add));
+
+    export_credential_refs(lines, service_flag, name, &auth);
+    for (field, kind) in [
+        (auth.auth_token.is_some(), "bearer"),
+        (auth.api_key.is_some(), "api-key"),
+    ] {
+        if field {
+            lines.push(ExportLine::Note(format!(
+                "{service} provider '{name}' stores an inline {kind} secret; bind it with `provider set-auth {name} --kind {kind} --environment <ENV>`"
+            )));
+        }
+    }
+    if provider
+        .supported_models
+        .values()
+        .any(|supported| !supported)
+    {
+        lines.push(ExportLine::Note(format!(
+            "{service} provider '{name}' marks some models unsupported; edit supported_models in config.toml"
+        )));
+    }
+    let unexported = [
+        (!provider.endpoints.is_empty(), "endpoints"),
+        (provider.continuity_domain.is_some(), "continuity_domain"),
+        (!provider.enabled_schedule.is_empty(), "enabled_schedule"),
+        (provider.limits != Default::default(), "limits"),
+        (
+            !provider.strip_request_headers.is_empty(),
+            "strip_request_headers",
+        ),
+        (provider.healthcheck.is_some(), "healthcheck"),
+        (provider.address_family.is_some(), "address_family"),
+        (provider.buffer_responses.is_some(), "buffer_responses"),
+    ]
+    .into_iter()
+    .filter_map(|(present, field)| present.then_some(field))
+    .collect::<Vec<_>>();
+    if !unexported.is_empty() {
+        lines.push(ExportLine::Note(format!(
+            "{service} provider '{name}' also sets {}; copy them from config.toml",
+            unexported.join(", ")
+        )));
+    }
+    true
+}
+
+fn export_credential_refs(
+    lines: &mut Vec<ExportLine>,
+    service_flag: &str,
+    name: &str,
+    auth: &UpstreamAuth,
+) {
+    for (kind, reference) in [
+        ("bearer", auth.auth_token_ref.as_ref()),
+        ("api-key", auth.api_key_ref.as_ref()),
+    ] {
+        let Some(reference) = reference else {
+            continue;
+        };
+        let (flag, value) = match reference {
+            CredentialRef::Native { name } => ("--native", name.as_str()),
+            CredentialRef::SecretFile { path } => ("--secret-file", path.as_str()),
+        };
+        lines.push(ExportLine::Command(command([
+            "provider",
+            "set-auth",
+            name,
+            "--kind",
+            kind,
+            flag,
+            value,
+            service_flag,
+        ])));
+    }
+}
+
+fn export_entry_routing(
+    lines: &mut Vec<ExportLine>,
+    service: &str,
+    service_flag: &str,
+    view: &ServiceRouteConfig,
+    exported: &[String],
+) {
+    let routing = crate::config::effective_routing(view);
+    let Some(entry) = routing.entry_node() else {
+        return;
+    };
+    let expressible = routing.routes.len() == 1
+        && !matches!(entry.strategy, RouteStrategy::Conditional)
+        && entry.prefer_tags.len() <= 1
+        && !entry.children.is_empty()
+        && entry.children.iter().all(|child| exported.contains(child));
+    if !expressible {
+        lines.push(ExportLine::Note(format!(
+            "{service} routing uses nested or conditional routes; copy [{service}.routing] from config.toml"
+        )));
+        return;
+    }
+
+    let mut set = command([
+        "routing",
+        "set",
+        "--policy",
+        routing_policy_label(entry.strategy),
+        "--order",
+    ]);
+    set.push(entry.children.join(","));
+    if let Some(target) = entry.target.as_deref() {
+        set.extend(["--target".to_string(), target.to_string()]);
+    }
+    for (key, value) in entry.prefer_tags.iter().flatten() {
+        set.extend(["--prefer-tag".to_string(), format!("{key}={value}")]);
+    }
+    if !matches!(entry.on_exhausted, RouteExhaustedAction::Continue) {
+        set.extend([
+            "--on-exhausted".to_string(),
+            routing_exhausted_label(entry.on_exhausted).to_string(),
+        ]);
+    }
+    set.push(service_flag.to_string());
+    lines.push(ExportLine::Command(set));
+}
+
+fn command<const N: usize>(args: [&str; N]) -> Vec<String> {
+    std::iter::once("codex-helper")
+        .chain(args)
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli_types::{Cli, Command};
+    use crate::commands::test_support::{ScopedEnv, TempTestDir, env_lock};
+    use crate::config::{RouteGraphConfig, RouteNodeConfig};
+    use clap::Parser;
+    use std::collections::BTreeMap;
+
+    fn export_fixture() -> HelperConfig {
+        let mut config = HelperConfig::default();
+        config.codex.providers.insert(
+            "primary".to_string(),
+            ProviderConfig {
+                alias: Some("Primary relay".to_string()),
+                base_url: Some("https://primary.example/v1".to_string()),
+                inline_auth: UpstreamAuth {
+                    auth_token_env: Some("PRIMARY_TOKEN".to_string()),
+                    ..UpstreamAuth::default()
+                },
+                tags: BTreeMap::from([("tier".to_string(), "paid".to_string())]),
+                supported_models: BTreeMap::from([("gpt-5".to_string(), true)]),
+                model_mapping: BTreeMap::from([("gpt-5".to_string(), "gpt-5-relay".to_string())]),
+                ..ProviderConfig::default()
+            },
+        );
+        config.codex.providers.insert(
+            "backup".to_string(),
+            ProviderConfig {
+                enabled: false,
+                base_url: Some("https://backup.example/v1".to_string()),
+                auth: UpstreamAuth {
+                    api_key_ref: Some(CredentialRef::Native {
+                        name: "backup.key".to_string(),
+                    }),
+                    ..UpstreamAuth::default()
+                },
+                ..ProviderConfig::default()
+            },
+        );
+        config.codex.providers.insert(
+            "legacy".to_string(),
+            ProviderConfig {
+                base_url: Some("https://legacy.example/v1".to_string()),
+                inline_auth: UpstreamAuth {
+                    auth_token: Some("sk-inline-secret-must-not-leak".into()),
+                    ..UpstreamAuth::default()
+                },
+                ..ProviderConfig::default()
+            },
+        );
+        config.codex.routing = Some(RouteGraphConfig {
+            routes: BTreeMap::from([(
+                "main".to_string(),
+                RouteNodeConfig {
+                    strategy: RouteStrategy::ManualSticky,
+                    children: vec![
+                        "legacy".to_string(),
+                        "primary".to_string(),
+                        "backup".to_string(),
+                    ],
+                    target: Some("legacy".to_string()),
+                    ..RouteNodeConfig::default()
+                },
+            )]),
+            ..RouteGraphConfig::default()
+        });
+        config.claude.providers.insert(
+            "anthropic".to_string(),
+            ProviderConfig {
+                base_url: Some("https://api.anthropic.com".to_string()),
+                inline_auth: UpstreamAuth {
+                    api_key_env: Some("ANTHROPIC_API_KEY".to_string()),
+                    ..UpstreamAuth::default()
+                },
+                tags: BTreeMap::from([("region".to_string(), "us".to_string())]),
+                ..ProviderConfig::default()
+            },
+        );
+        config
+    }
+
+    fn entry_summary(view: &ServiceRouteConfig) -> String {
+        let routing = crate::config::effective_routing(view);
+        let entry = routing.entry_node().expect("entry route");
+        format!(
+            "{:?} {:?} {:?} {:?} {:?}",
+            entry.strategy, entry.target, entry.children, entry.prefer_tags, entry.on_exhausted
+        )
+    }
+
+    #[test]
+    fn shell_quote_leaves_plain_words_and_quotes_the_rest() {
+        assert_eq!(
+            shell_quote("https://relay.example/v1"),
+            "https://relay.example/v1"
+        );
+        assert_eq!(shell_quote("Primary relay"), "'Primary relay'");
+        assert_eq!(shell_quote("it's"), r"'it'\''s'");
+        assert_eq!(shell_quote(""), "''");
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn exported_commands_rebuild_an_equivalent_config_on_a_clean_install() {
+        let _env_lock = env_lock().await;
+        let helper_home = TempTestDir::new("codex-helper-cli-test-config-export");
+        let mut scoped_env = ScopedEnv::default();
+        unsafe {
+            scoped_env.set_path("CODEX_HELPER_HOME", helper_home.path());
+        }
+
+        let source = export_fixture();
+        let lines = export_config_as_commands(&source, &[ServiceKind::Codex, ServiceKind::Claude]);
+        let rendered = lines.iter().map(ExportLine::render).collect::<Vec<_>>();
+        assert!(
+            rendered
+                .iter()
+                .all(|line| !line.contains("sk-inline-secret-must-not-leak"))
+        );
+        assert!(rendered.iter().any(|line| {
+            line.starts_with("# codex provider 'legacy' stores an inline bearer secret")
+        }));
+        assert!(
+            rendered.contains(
+                &"codex-helper provider set-auth backup --kind api-key --native backup.key --codex"
+                    .to_string()
+            )
+        );
+
+        for line in &lines {
+            let ExportLine::Command(args) = line else {
+                continue;
+            };
+            let cli = Cli::try_parse_from(args).expect("exported command parses");
+            match cli.command {
+                Some(Command::Provider { cmd }) => {
+                    crate::commands::provider::handle_provider_cmd(cmd).await
+                }
+                Some(Command::Routing { cmd }) => {
+                    crate::commands::routing::handle_routing_cmd(cmd).await
+                }
+                Some(Command::Config { cmd }) => handle_config_cmd(cmd).await,
+                other => panic!("unexpected exported command: {other:?}"),
+            }
+            .unwrap_or_else(|error| panic!("exported command {args:?} failed: {error}"));
+        }
+
+        let rebuilt = load_config().await.expect("load rebuilt config");
+        for (expected, actual) in [
+            (&source.codex, &rebuilt.codex),
+            (&source.claude, &rebuilt.claude),
+        ] {
+            assert_eq!(
+                expected.providers.keys().collect::<Vec<_>>(),
+                actual.providers.keys().collect::<Vec<_>>()
+            );
+            for (name, want) in &expected.providers {
+                let got = &actual.providers[name];
+                let (want_auth, got_auth) = (want.effective_auth(), got.effective_auth());
+                assert_eq!(want.alias, got.alias, "{name} alias");
+                assert_eq!(want.enabled, got.enabled, "{name} enabled");
+                assert_eq!(want.base_url, got.base_url, "{name} base_url");
+                assert_eq!(want.tags, got.tags, "{name} tags");
+                assert_eq!(want.supported_models, got.supported_models, "{name} models");
+                assert_eq!(
+                    want.model_mapping, got.model_mapping,
+                    "{name} model_mapping"
+                );
+                assert_eq!(want_auth.auth_token_env, got_auth.auth_token_env, "{name}");
+                assert_eq!(want_auth.api_key_env, got_auth.api_key_env, "{name}");
+                assert_eq!(want_auth.auth_token_ref, got_auth.auth_token_ref, "{name}");
+                assert_eq!(want_auth.api_key_ref, got_auth.api_key_ref, "{name}");
+                assert!(got_auth.auth_token.is_none() && got_auth.api_key.is_none());
+            }
+            assert_eq!(entry_summary(expected), entry_summary(actual));
+        }
+    }
+}
@@ -43,17 +43,66 @@ pub(super) async fn load_helper_config(
     command_group: &str,
 ) -> anyhow::Result<(HelperConfig, &'static str, &'static str)> {
     let service = resolve_service(codex, claude).await?;
-    let cfg = load_config_document().await.map_err(|error| {
+    let cfg = load_command_config(command_group).await?;
+    Ok((cfg, service, service_label(service)))
+}
+
+/// Like [`load_helper_config`] for read-only commands, which may report on both services.
+/// Returns `(service, label)` pairs in the order sections should be printed.
+pub(super) async fn load_helper_config_for_read(
+    codex: bool,
+    claude: bool,
+    both: bool,
+    json: bool,
+    command_group: &str,
+) -> anyhow::Result<(HelperConfig, Vec<(&'static str, &'static str)>)> {
+    let services = if reports_both_services(codex, claude, both, json) {
+        vec!["codex", "claude"]
+    } else {
+        vec![resolve_service(codex, claude).await?]
+    };
+    let cfg = load_command_config(command_group).await?;
+    Ok((
+        cfg,
+        services
+            .into_iter()
+            .map(|service| (service, service_label(service)))
+            .collect(),
+    ))
+}
+
+/// Pretty JSON for a read-only command: the bare payload for a single service, or an object
+/// keyed by service when several were requested.
+pub(super) fn per_service_json<T: serde::Serialize>(
+    mut payloads: Vec<(&'static str, T)>,
+) -> serde_json::Result<String> {
+    if payloads.len() == 1 {
+        let (_, payload) = payloads.remove(0);
+        return serde_json::to_string_pretty(&payload);
+    }
+    serde_json::to_string_pretty(&payloads.into_iter().collect::<BTreeMap<_, _>>())
+}
+
+/// `--both`, or text output without a service flag. JSON without a flag keeps the single
+/// default service so existing scripts see the same payload shape.
+fn reports_both_services(codex: bool, claude: bool, both: bool, json: bool) -> bool {
+    both || (!codex && !claude && !json)
+}
+
+async fn load_command_config(command_group: &str) -> anyhow::Result<HelperConfig> {
+    load_config_document().await.map_err(|error| {
         anyhow::anyhow!(
             "{command_group} commands require the canonical version = {CURRENT_CONFIG_VERSION} route graph config: {error}"
         )
-    })?;
-    let label = if service == "claude" {
+    })
+}
+
+fn service_label(service: &str) -> &'static str {
+    if service == "claude" {
         "Claude"
     } else {
         "Codex"
-    };
-    Ok((cfg, service, label))
+    }
 }
 
 pub(super) fn select_service_route_config_mut<'a>(
@@ -137,6 +186,7 @@ pub(super) fn routing_policy_label(policy: RouteStrategy) -> &'static str {
         RouteStrategy::ManualSticky => "manual-sticky",
         RouteStrategy::OrderedFailover => "ordered-failover",
         RouteStrategy::RoundRobin => "round-robin",
+        RouteStrategy::LeastRecentErrors => "least-recent-errors",
         RouteStrategy::TagPreferred => "tag-preferred",
         RouteStrategy::Conditional => "conditional",
     }
@@ -265,6 +315,15 @@ pub(super) fn print_provider_list(label: &str, view: &ServiceRouteConfig) {
 mod tests {
     use super::*;
 
+    #[test]
+    fn read_only_commands_cover_both_services_unless_narrowed() {
+        assert!(reports_both_services(false, false, false, false));
+        assert!(reports_both_services(false, false, true, true));
+        assert!(!reports_both_services(true, false, false, false));
+        assert!(!reports_both_services(false, true, false, false));
+        assert!(!reports_both_services(false, false, false, true));
+    }
+
     #[test]
     fn parse_cli_string_map_rejects_invalid_entries() {
         let map = parse_cli_string_map(
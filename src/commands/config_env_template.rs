@@ -0,0 +1,128 @@
+use std::collections::BTreeMap;
+
+use crate::cli_types::EnvTemplateShell;
+use crate::config::{ServiceKind, storage::load_config};
+use crate::doctor::{EnvVarReference, env_var_references};
+use crate::{CliError, CliResult};
+
+pub(super) async fn handle_config_env_template(
+    shell: EnvTemplateShell,
+    codex: bool,
+    claude: bool,
+) -> CliResult<()> {
+    let config = load_config()
+        .await
+        .map_err(|e| CliError::Configuration(e.to_string()))?;
+    let services = match (codex, claude) {
+        (true, false) => vec![ServiceKind::Codex],
+        (false, true) => vec![ServiceKind::Claude],
+        _ => vec![ServiceKind::Codex, ServiceKind::Claude],
+    };
+    let references = env_var_references(&config, &services);
+    if references.is_empty() {
+        println!("# No provider references an environment variable.");
+        return Ok(());
+    }
+    print!("{}", render_env_template(&references, shell));
+    Ok(())
+}
+
+/// Renders one placeholder assignment per variable, preceded by comments naming every provider
+/// field that reads it. Values are always empty: the config only stores variable names.
+pub(super) fn render_env_template(
+    references: &[EnvVarReference],
+    shell: EnvTemplateShell,
+) -> String {
+    let mut users: BTreeMap<&str, Vec<&EnvVarReference>> = BTreeMap::new();
+    for reference in references {
+        users
+            .entry(reference.variable.as_str())
+            .or_default()
+            .push(reference);
+    }
+
+    let mut out = String::from(
+        "# codex-helper provider credentials. Fill in each value, then load this file\n\
+         # before `codex-helper serve`; check the result with `codex-helper config validate-env`.\n",
+    );
+    for (variable, users) in users {
+        out.push('\n');
+        for reference in users {
+            let disabled = if reference.active { "" } else { " (disabled)" };
+            out.push_str(&format!(
+                "# {}/{} {}{disabled}\n",
+                reference.service, reference.provider_id, reference.field
+            ));
+        }
+        if !is_portable_variable_name(variable) {
+            out.push_str(&format!(
+                "# skipped {variable:?}: not a valid shell variable name\n"
+            ));
+            continue;
+        }
+        let line = match shell {
+            EnvTemplateShell::Bash => format!("export {variable}=\"\"\n"),
+            EnvTemplateShell::Fish => format!("set -gx {variable} \"\"\n"),
+            EnvTemplateShell::Powershell => format!("$env:{variable} = \"\"\n"),
+        };
+        out.push_str(&line);
+    }
+    out
+}
+
+fn is_portable_variable_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reference(provider_id: &str, field: &'static str, variable: &str) -> EnvVarReference {
+        EnvVarReference {
+            service: "codex",
+            provider_id: provider_id.to_string(),
+            field,
+            variable: variable.to_string(),
+            active: true,
+            set: true,
+        }
+    }
+
+    #[test]
+    fn env_template_groups_shared_variables_and_never_emits_values() {
+        let mut disabled = reference("backup", "api_key_env", "CODEX_OPENROUTER_KEY");
+        disabled.active = false;
+        let references = vec![
+            reference("openrouter", "auth_token_env", "CODEX_OPENROUTER_KEY"),
+            disabled,
+            reference("relay", "auth_token_env", "RELAY_TOKEN"),
+        ];
+
+        let bash = render_env_template(&references, EnvTemplateShell::Bash);
+        assert!(bash.contains(
+            "# codex/openrouter auth_token_env\n# codex/backup api_key_env (disabled)\nexport CODEX_OPENROUTER_KEY=\"\"\n"
+        ));
+        assert_eq!(bash.matches("export CODEX_OPENROUTER_KEY").count(), 1);
+        assert!(bash.contains("export RELAY_TOKEN=\"\"\n"));
+
+        let fish = render_env_template(&references, EnvTemplateShell::Fish);
+        assert!(fish.contains("set -gx RELAY_TOKEN \"\"\n"));
+        let powershell = render_env_template(&references, EnvTemplateShell::Powershell);
+        assert!(powershell.contains("$env:RELAY_TOKEN = \"\"\n"));
+    }
+
+    #[test]
+    fn env_template_comments_out_names_a_shell_cannot_assign() {
+        let references = vec![reference("odd", "auth_token_env", "BAD-NAME")];
+
+        let bash = render_env_template(&references, EnvTemplateShell::Bash);
+
+        assert!(bash.contains("# skipped \"BAD-NAME\": not a valid shell variable name\n"));
+        assert!(!bash.contains("export BAD-NAME"));
+    }
+}
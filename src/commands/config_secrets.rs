@@ -0,0 +1,200 @@
+use crate::cli_types::EnvTemplateShell;
+use crate::config::{
+    HelperConfig, InlineCredentialValue, ServiceKind, UpstreamAuth, proxy_home_dir,
+    storage::{config_file_path, load_config, mutate_helper_config},
+};
+use crate::doctor::{InlineSecret, env_var_references, inline_secrets};
+use crate::{CliError, CliResult};
+
+use super::config_env_template::render_env_template;
+
+const SECRETS_BACKUP_FILE_NAME: &str = "config.toml.secrets.bak";
+const SECRETS_TEMPLATE_FILE_NAME: &str = "secrets.env.template";
+
+/// `config migrate-secrets`: preview by default; `--write` backs up config.toml, replaces every
+/// inline credential with a `*_env` reference and writes a value-less env template.
+pub(super) async fn handle_config_migrate_secrets(write: bool) -> CliResult<()> {
+    let config = load_config()
+        .await
+        .map_err(|e| CliError::Configuration(e.to_string()))?;
+    let planned = inline_secrets(&config);
+    if planned.is_empty() {
+        println!("No plaintext credentials in config.toml; nothing to migrate.");
+        return Ok(());
+    }
+
+    let config_path = config_file_path();
+    if !write {
+        println!(
+            "Would move {} plaintext credential(s) out of {}:",
+            planned.len(),
+            config_path.display()
+        );
+        print_moves(&planned);
+        println!(
+            "Re-run with --write --yes to apply; config.toml is backed up to {SECRETS_BACKUP_FILE_NAME} first."
+        );
+        return Ok(());
+    }
+
+    let backup_path = config_path.with_file_name(SECRETS_BACKUP_FILE_NAME);
+    tokio::fs::copy(&config_path, &backup_path)
+        .await
+        .map_err(|e| {
+            CliError::Configuration(format!(
+                "back up {} to {}: {e}",
+                config_path.display(),
+                backup_path.display()
+            ))
+        })?;
+    let (_, moved) = mutate_helper_config(|config| Ok(move_inline_secrets(config)))
+        .await
+        .map_err(|e| CliError::Configuration(e.to_string()))?;
+    println!(
+        "Backed up config.toml to {} (it still holds the values).",
+        backup_path.display()
+    );
+    println!("Moved {} credential(s):", moved.len());
+    print_moves(&moved);
+
+    let migrated = load_config()
+        .await
+        .map_err(|e| CliError::Configuration(e.to_string()))?;
+    let references = env_var_references(&migrated, &[ServiceKind::Codex, ServiceKind::Claude])
+        .into_iter()
+        .filter(|reference| {
+            moved
+                .iter()
+                .any(|secret| secret.suggested_env == reference.variable)
+        })
+        .collect::<Vec<_>>();
+    let template_path = proxy_home_dir().join(SECRETS_TEMPLATE_FILE_NAME);
+    std::fs::write(
+        &template_path,
+        render_env_template(&references, EnvTemplateShell::Bash),
+    )
+    .map_err(|e| CliError::Configuration(format!("write {}: {e}", template_path.display())))?;
+    println!(
+        "Wrote {} without values. Fill them in from the backup, save the file as {} (or export the variables), then run `codex-helper config validate-env`.",
+        template_path.display(),
+        crate::env_file::default_env_file_path().display()
+    );
+    println!(
+        "To undo, copy {} back over {}.",
+        backup_path.display(),
+        config_path.display()
+    );
+    Ok(())
+}
+
+fn print_moves(secrets: &[InlineSecret]) {
+    for secret in secrets {
+        println!(
+            "  {}/{} {} -> {}_env = \"{}\"",
+            secret.service, secret.provider_id, secret.field, secret.field, secret.suggested_env
+        );
+    }
+}
+
+/// Clears each inline `auth_token` / `api_key` and points the matching `*_env` at the suggested
+/// variable, in the auth section that held the value (the provider-level keys win when both did).
+fn move_inline_secrets(config: &mut HelperConfig) -> Vec<InlineSecret> {
+    let secrets = inline_secrets(config);
+    for secret in &secrets {
+        let view = if secret.service == "claude" {
+            &mut config.claude
+        } else {
+            &mut config.codex
+        };
+        let Some(provider) = view.providers.get_mut(&secret.provider_id) else {
+            continue;
+        };
+        let (inline_value, _) = credential_slots(&mut provider.inline_auth, secret.field);
+        let inline_holds_value = inline_value.take().is_some();
+        let (nested_value, nested_env) = credential_slots(&mut provider.auth, secret.field);
+        *nested_value = None;
+        if inline_holds_value {
+            *credential_slots(&mut provider.inline_auth, secret.field).1 =
+                Some(secret.suggested_env.clone());
+        } else {
+            *nested_env = Some(secret.suggested_env.clone());
+        }
+    }
+    secrets
+}
+
+fn credential_slots<'a>(
+    auth: &'a mut UpstreamAuth,
+    field: &str,
+) -> (
+    &'a mut Option<InlineCredentialValue>,
+    &'a mut Option<String>,
+) {
+    if field == "auth_token" {
+        (&mut auth.auth_token, &mut auth.auth_token_env)
+    } else {
+        (&mut auth.api_key, &mut auth.api_key_env)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn move_inline_secrets_replaces_values_with_env_references() {
+        let mut config: HelperConfig = toml::from_str(
+            r#"
+version = 6
+
+[codex.providers.relay]
+base_url = "https://relay.example/v1"
+auth_token = "sk-inline"
+
+[codex.providers.keyed]
+base_url = "https://keyed.example/v1"
+api_key_env = "KEYED_API_KEY"
+
+[codex.providers.keyed.auth]
+api_key = "sk-nested"
+
+[claude.providers.env-only]
+base_url = "https://claude.example"
+auth_token_env = "CLAUDE_TOKEN"
+"#,
+        )
+        .expect("config");
+
+        let moved = move_inline_secrets(&mut config);
+
+        assert_eq!(
+            moved
+                .iter()
+                .map(|secret| (secret.provider_id.as_str(), secret.suggested_env.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("keyed", "KEYED_API_KEY"),
+                ("relay", "CODEX_RELAY_AUTH_TOKEN")
+            ]
+        );
+        assert!(inline_secrets(&config).is_empty());
+        let relay = config.codex.providers["relay"].effective_auth();
+        assert_eq!(
+            relay.auth_token_env.as_deref(),
+            Some("CODEX_RELAY_AUTH_TOKEN")
+        );
+        let keyed = &config.codex.providers["keyed"];
+        assert_eq!(
+            keyed.effective_auth().api_key_env.as_deref(),
+            Some("KEYED_API_KEY")
+        );
+        assert!(keyed.auth.api_key.is_none());
+        assert_eq!(
+            config.claude.providers["env-only"]
+                .effective_auth()
+                .auth_token_env
+                .as_deref(),
+            Some("CLAUDE_TOKEN")
+        );
+    }
+}
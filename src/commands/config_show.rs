@@ -0,0 +1,651 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use super::config_doc::{
+    load_helper_config, load_helper_config_for_read, per_service_json, routing_policy_label,
+    select_service_route_config,
+};
+use super::doctor::compact_age;
+use crate::config::{
+    CURRENT_CONFIG_VERSION, CredentialRef, HelperConfig, ProviderConcurrencyLimits,
+    ProviderTlsConfig, RetryProfileName, ServiceRouteConfig, default_upstream_user_agent,
+};
+use crate::upstream_models::{UpstreamModelList, load_upstream_model_cache};
+use crate::{CliError, CliResult};
+
+#[derive(Debug, Serialize)]
+struct ConfigShowView {
+    schema_version: u32,
+    service: String,
+    name: String,
+    alias: Option<String>,
+    enabled: bool,
+    routing: RoutingPlacement,
+    endpoints: Vec<EndpointView>,
+    auth: Vec<AuthSourceView>,
+    allow_anonymous: bool,
+    user_agent: UserAgentView,
+    retry: RetryView,
+    #[serde(skip_serializing_if = "limits_are_unset")]
+    limits: ProviderConcurrencyLimits,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    enabled_schedule: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    schedule_timezone: Option<String>,
+    #[serde(skip_serializing_if = "ProviderTlsConfig::is_default")]
+    tls: ProviderTlsConfig,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    continuity_domain: Option<String>,
+    supported_models: Vec<String>,
+    model_mapping: BTreeMap<String, String>,
+    /// The provider's last `/v1/models` list cached by a proxy with
+    /// `routing.validate_models_online`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    upstream_models: Option<UpstreamModelList>,
+}
+
+fn limits_are_unset(limits: &ProviderConcurrencyLimits) -> bool {
+    limits == &ProviderConcurrencyLimits::default()
+}
+
+#[derive(Debug, Serialize)]
+struct RoutingPlacement {
+    entry_strategy: &'static str,
+    /// 1-based position in the resolved route order; `None` when routing never reaches it.
+    level: Option<usize>,
+    manual_target: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct EndpointView {
+    name: String,
+    base_url: String,
+    enabled: bool,
+    priority: u32,
+}
+
+/// The credential the proxy would load for one auth kind, in runtime precedence order:
+/// credential reference, then inline secret, then environment variable.
+#[derive(Debug, Serialize)]
+struct AuthSourceView {
+    kind: &'static str,
+    source: &'static str,
+    /// Native credential name, secret file path or variable name; inline secrets stay redacted.
+    reference: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    env_set: Option<bool>,
+    /// A configured variable that is ignored because a higher-precedence source wins.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    shadowed_env: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct UserAgentView {
+    value: String,
+    source: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+struct RetryView {
+    profile: Option<RetryProfileName>,
+    upstream_max_attempts: u32,
+    provider_max_attempts: u32,
+}
+
+pub(super) async fn handle_config_show(
+    name: String,
+    codex: bool,
+    claude: bool,
+    json: bool,
+) -> CliResult<()> {
+    let (cfg, service, label) = load_helper_config(codex, claude, "config")
+        .await
+        .map_err(|e| CliError::Configuration(e.to_string()))?;
+    let mut view = build_config_show(&cfg, service, name.as_str(), |variable| {
+        std::env::var_os(variable).is_some_and(|value| !value.is_empty())
+    })
+    .ok_or_else(|| {
+        CliError::Configuration(format!(
+            "{label} provider '{name}' not found (matched by name or alias)"
+        ))
+    })?;
+    view.upstream_models = load_upstream_model_cache(label)
+        .providers
+        .remove(&view.name);
+
+    if json {
+        let text = serde_json::to_string_pretty(&view)
+            .map_err(|e| CliError::Configuration(e.to_string()))?;
+        println!("{text}");
+    } else {
+        print_config_show(label, &view);
+    }
+    Ok(())
+}
+
+/// One row of `config list`: the routing and auth summary of `config show` for every provider.
+#[derive(Debug, Serialize)]
+struct ConfigListEntry {
+    name: String,
+    alias: Option<String>,
+    /// 1-based position in the resolved route order; `None` when routing never reaches it.
+    level: Option<usize>,
+    enabled: bool,
+    /// The provider is the pinned target of a manual-sticky route.
+    active: bool,
+    upstream_count: usize,
+    /// Credential kinds and where they come from, e.g. `bearer=env:OPENAI_KEY`; never a secret.
+    auth_source: String,
+}
+
+pub(super) async fn handle_config_list(
+    codex: bool,
+    claude: bool,
+    both: bool,
+    json: bool,
+) -> CliResult<()> {
+    let (cfg, services) = load_helper_config_for_read(codex, claude, both, json, "config")
+        .await
+        .map_err(|e| CliError::Configuration(e.to_string()))?;
+
+    if json {
+        let payloads = services
+            .iter()
+            .map(|(service, _)| (*service, build_config_list(&cfg, service)))
+            .collect();
+        let text =
+            per_service_json(payloads).map_err(|e| CliError::Configuration(e.to_string()))?;
+        println!("{text}");
+        return Ok(());
+    }
+    for (index, (service, label)) in services.iter().enumerate() {
+        if index > 0 {
+            println!();
+        }
+        print_config_list(label, &build_config_list(&cfg, service));
+    }
+    Ok(())
+}
+
+fn print_config_list(label: &str, entries: &[ConfigListEntry]) {
+    if entries.is_empty() {
+        println!("No {label} providers configured.");
+        return;
+    }
+    println!("{label} providers:");
+    for entry in entries {
+        let mut line = format!(
+            "  {} {:<3} {}",
+            if entry.active { "*" } else { " " },
+            entry
+                .level
+                .map(|level| level.to_string())
+                .unwrap_or_else(|| "-".to_string()),
+            entry.name
+        );
+        if let Some(alias) = entry.alias.as_deref() {
+            line.push_str(&format!(" ({alias})"));
+        }
+        line.push_str(&format!(
+            "  upstreams={} auth={}",
+            entry.upstream_count, entry.auth_source
+        ));
+        if !entry.enabled {
+            line.push_str("  [disabled]");
+        }
+        println!("{line}");
+    }
+}
+
+fn build_config_list(cfg: &HelperConfig, service: &str) -> Vec<ConfigListEntry> {
+    let (view, _) = select_service_route_config(cfg, service);
+    let mut entries = view
+        .providers
+        .keys()
+        .filter_map(|name| build_config_show(cfg, service, name, |_| false))
+        .map(|show| ConfigListEntry {
+            auth_source: auth_source_summary(&show.auth, show.allow_anonymous),
+            name: show.name,
+            alias: show.alias,
+            level: show.routing.level,
+            enabled: show.enabled,
+            active: show.routing.manual_target,
+            upstream_count: show.endpoints.len(),
+        })
+        .collect::<Vec<_>>();
+    // Providers routing never reaches sort after every ranked one.
+    entries.sort_by(|a, b| {
+        (a.level.is_none(), a.level, &a.name).cmp(&(b.level.is_none(), b.level, &b.name))
+    });
+    entries
+}
+
+fn auth_source_summary(auth: &[AuthSourceView], allow_anonymous: bool) -> String {
+    if auth.is_empty() {
+        return if allow_anonymous { "anonymous" } else { "none" }.to_string();
+    }
+    auth.iter()
+        .map(|source| match source.source {
+            "inline" => format!("{}=inline", source.kind),
+            kind => format!("{}={kind}:{}", source.kind, source.reference),
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn find_provider_name<'a>(view: &'a ServiceRouteConfig, name: &str) -> Option<&'a str> {
+    if let Some((key, _)) = view.providers.get_key_value(name) {
+        return Some(key.as_str());
+    }
+    view.providers
+        .iter()
+        .find(|(_, provider)| provider.alias.as_deref() == Some(name))
+        .map(|(key, _)| key.as_str())
+}
+
+fn build_config_show(
+    cfg: &HelperConfig,
+    service: &str,
+    name: &str,
+    is_set: impl Fn(&str) -> bool,
+) -> Option<ConfigShowView> {
+    let (view, _) = select_service_route_config(cfg, service);
+    let name = find_provider_name(view, name)?;
+    let provider = view.providers.get(name)?;
+    let auth = provider.effective_auth();
+
+    let routing = crate::config::effective_routing(view);
+    let entry = routing.entry_node();
+    let level = crate::config::resolved_provider_order("config-show", view)
+        .ok()
+        .and_then(|order| order.iter().position(|candidate| candidate == name))
+        .map(|index| index + 1);
+    let manual_target = entry.is_some_and(|node| {
+        matches!(node.strategy, crate::config::RouteStrategy::ManualSticky)
+            && node.target.as_deref() == Some(name)
+    });
+
+    let mut endpoints = Vec::new();
+    if let Some(base_url) = provider
+        .base_url
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+    {
+        endpoints.push(EndpointView {
+            name: "default".to_string(),
+            base_url: base_url.to_string(),
+            enabled: provider.enabled,
+            priority: 0,
+        });
+    }
+    endpoints.extend(
+        provider
+            .endpoints
+            .iter()
+            .map(|(endpoint_name, endpoint)| EndpointView {
+                name: endpoint_name.clone(),
+                base_url: endpoint.base_url.clone(),
+                enabled: endpoint.enabled,
+                priority: endpoint.priority,
+            }),
+    );
+
+    let auth_sources = [
+        auth_source_view(
+            "bearer",
+            auth.auth_token_ref.as_ref(),
+            auth.auth_token.is_some(),
+            auth.auth_token_env.as_deref(),
+            &is_set,
+        ),
+        auth_source_view(
+            "api_key",
+            auth.api_key_ref.as_ref(),
+            auth.api_key.is_some(),
+            auth.api_key_env.as_deref(),
+            &is_set,
+        ),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    let user_agent = if let Some(value) = provider.user_agent.as_deref() {
+        UserAgentView {
+            value: value.to_string(),
+            source: "provider",
+        }
+    } else if let Some(value) = cfg.http.user_agent.as_deref() {
+        UserAgentView {
+            value: value.to_string(),
+            source: "http.user_agent",
+        }
+    } else {
+        // Nothing configured: the client's own header is forwarded, with this as the fallback.
+        UserAgentView {
+            value: default_upstream_user_agent(),
+            source: "client",
+        }
+    };
+    let resolved_retry = cfg.retry.resolve();
+
+    Some(ConfigShowView {
+        schema_version: CURRENT_CONFIG_VERSION,
+        service: service.to_string(),
+        name: name.to_string(),
+        alias: provider.alias.clone(),
+        enabled: provider.enabled,
+        routing: RoutingPlacement {
+            entry_strategy: entry
+                .map(|node| routing_policy_label(node.strategy))
+                .unwrap_or("-"),
+            level,
+            manual_target,
+        },
+        endpoints,
+        auth: auth_sources,
+        allow_anonymous: auth.allow_anonymous == Some(true),
+        user_agent,
+        retry: RetryView {
+            profile: cfg.retry.profile,
+            upstream_max_attempts: resolved_retry.upstream.max_attempts,
+            provider_max_attempts: resolved_retry.route.max_attempts,
+        },
+        limits: provider.limits.clone(),
+        enabled_schedule: provider.enabled_schedule.clone(),
+        schedule_timezone: provider.schedule_timezone.clone(),
+        tls: provider.tls.clone(),
+        continuity_domain: provider.continuity_domain.clone(),
+        supported_models: provider
+            .supported_models
+            .iter()
+            .filter(|(_, supported)| **supported)
+            .map(|(model, _)| model.clone())
+            .collect(),
+        model_mapping: provider.model_mapping.clone(),
+        upstream_models: None,
+    })
+}
+
+fn auth_source_view(
+    kind: &'static str,
+    reference: Option<&CredentialRef>,
+    has_inline: bool,
+    env: Option<&str>,
+    is_set: &impl Fn(&str) -> bool,
+) -> Option<AuthSourceView> {
+    let env = env.map(str::trim).filter(|name| !name.is_empty());
+    let (source, reference) = match (reference, has_inline, env) {
+        (Some(CredentialRef::Native { name }), _, _) => ("native", name.clone()),
+        (Some(CredentialRef::SecretFile { path }), _, _) => ("secret_file", path.clone()),
+        (None, true, _) => ("inline", "<redacted>".to_string()),
+        (None, false, Some(env)) => {
+            return Some(AuthSourceView {
+                kind,
+                source: "env",
+                reference: env.to_string(),
+                env_set: Some(is_set(env)),
+                shadowed_env: None,
+            });
+        }
+        (None, false, None) => return None,
+    };
+    Some(AuthSourceView {
+        kind,
+        source,
+        reference,
+        env_set: None,
+        shadowed_env: env.map(ToOwned::to_owned),
+    })
+}
+
+fn print_config_show(label: &str, view: &ConfigShowView) {
+    println!("Service: {label}");
+    println!("Provider: {}", view.name);
+    if let Some(alias) = view.alias.as_deref() {
+        println!("Alias: {alias}");
+    }
+    println!("Enabled: {}", view.enabled);
+    println!(
+        "Routing: strategy={} level={}{}",
+        view.routing.entry_strategy,
+        view.routing
+            .level
+            .map(|level| level.to_string())
+            .unwrap_or_else(|| "-".to_string()),
+        if view.routing.manual_target {
+            " (manual target)"
+        } else {
+            ""
+        }
+    );
+    println!("Endpoints:");
+    if view.endpoints.is_empty() {
+        println!("  <none>");
+    }
+    for endpoint in &view.endpoints {
+        println!(
+            "  [{}] {} enabled={} priority={}",
+            endpoint.name, endpoint.base_url, endpoint.enabled, endpoint.priority
+        );
+    }
+    println!("Auth:");
+    if view.auth.is_empty() {
+        let note = if view.allow_anonymous {
+            "anonymous (explicitly allowed)"
+        } else {
+            "<none>"
+        };
+        println!("  {note}");
+    }
+    for auth in &view.auth {
+        let mut line = format!("  {}: {} {}", auth.kind, auth.source, auth.reference);
+        if let Some(set) = auth.env_set {
+            line.push_str(if set { " (set)" } else { " (not set)" });
+        }
+        if let Some(shadowed) = auth.shadowed_env.as_deref() {
+            line.push_str(&format!("; {shadowed} is configured but not used"));
+        }
+        println!("{line}");
+    }
+    if view.user_agent.source == "client" {
+        println!(
+            "User-Agent: forwarded from the client ({} when it sends none)",
+            view.user_agent.value
+        );
+    } else {
+        println!(
+            "User-Agent: {} (from {})",
+            view.user_agent.value, view.user_agent.source
+        );
+    }
+    println!(
+        "Retry: profile={} upstream_max_attempts={} provider_max_attempts={}",
+        view.retry
+            .profile
+            .and_then(|profile| serde_json::to_value(profile).ok())
+            .and_then(|value| value.as_str().map(ToOwned::to_owned))
+            .unwrap_or_else(|| "custom".to_string()),
+        view.retry.upstream_max_attempts,
+        view.retry.provider_max_attempts
+    );
+    if let Some(max) = view.limits.max_concurrent_requests {
+        println!("Max concurrent requests: {max}");
+    }
+    if let Some(group) = view.limits.limit_group.as_deref() {
+        println!("Limit group: {group}");
+    }
+    if !view.enabled_schedule.is_empty() {
+        println!(
+            "Schedule: {} ({})",
+            view.enabled_schedule.join(", "),
+            view.schedule_timezone.as_deref().unwrap_or("local time")
+        );
+    }
+    if !view.tls.is_default() {
+        let paths = [
+            ("client_cert", view.tls.client_cert_path.as_ref()),
+            ("client_key", view.tls.client_key_path.as_ref()),
+            ("ca_bundle", view.tls.ca_bundle_path.as_ref()),
+        ];
+        let tls = paths
+            .iter()
+            .filter_map(|(field, path)| path.map(|path| format!("{field}={}", path.display())))
+            .collect::<Vec<_>>();
+        println!("TLS: {}", tls.join(" "));
+    }
+    if let Some(domain) = view.continuity_domain.as_deref() {
+        println!("Continuity domain: {domain}");
+    }
+    if !view.supported_models.is_empty() {
+        println!("Supported models: {}", view.supported_models.join(","));
+    }
+    if !view.model_mapping.is_empty() {
+        let mapping = view
+            .model_mapping
+            .iter()
+            .map(|(from, to)| format!("{from}={to}"))
+            .collect::<Vec<_>>();
+        println!("Model mapping: {}", mapping.join(","));
+    }
+    if let Some(list) = view.upstream_models.as_ref() {
+        println!(
+            "Upstream models (fetched {} ago): {}",
+            compact_age(crate::logging::now_ms().saturating_sub(list.fetched_at_ms)),
+            list.models.iter().cloned().collect::<Vec<_>>().join(",")
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{ProviderConfig, UpstreamAuth};
+
+    fn fixture() -> HelperConfig {
+        let mut config = HelperConfig::default();
+        config.codex.providers.insert(
+            "primary".to_string(),
+            ProviderConfig {
+                alias: Some("main".to_string()),
+                base_url: Some("https://primary.example/v1".to_string()),
+                inline_auth: UpstreamAuth {
+                    auth_token_env: Some("PRIMARY_TOKEN".to_string()),
+                    api_key: Some("sk-inline-secret-must-not-leak".into()),
+                    api_key_env: Some("PRIMARY_KEY".to_string()),
+                    ..UpstreamAuth::default()
+                },
+                user_agent: Some("relay-client/1".to_string()),
+                ..ProviderConfig::default()
+            },
+        );
+        config.codex.providers.insert(
+            "backup".to_string(),
+            ProviderConfig {
+                enabled: false,
+                base_url: Some("https://backup.example/v1".to_string()),
+                auth: UpstreamAuth {
+                    auth_token_ref: Some(CredentialRef::Native {
+                        name: "backup.token".to_string(),
+                    }),
+                    ..UpstreamAuth::default()
+                },
+                ..ProviderConfig::default()
+            },
+        );
+        config
+    }
+
+    #[test]
+    fn resolves_auth_sources_by_runtime_precedence_without_secrets() {
+        let config = fixture();
+        let view = build_config_show(&config, "codex", "main", |variable| {
+            variable == "PRIMARY_TOKEN"
+        })
+        .expect("alias resolves to provider");
+
+        assert_eq!(view.name, "primary");
+        assert_eq!(view.user_agent.source, "provider");
+        assert_eq!(view.auth.len(), 2);
+        assert_eq!(view.auth[0].kind, "bearer");
+        assert_eq!(view.auth[0].source, "env");
+        assert_eq!(view.auth[0].reference, "PRIMARY_TOKEN");
+        assert_eq!(view.auth[0].env_set, Some(true));
+        assert_eq!(view.auth[1].source, "inline");
+        assert_eq!(view.auth[1].shadowed_env.as_deref(), Some("PRIMARY_KEY"));
+
+        let json = serde_json::to_string(&view).expect("serialize");
+        assert!(!json.contains("sk-inline-secret-must-not-leak"), "{json}");
+    }
+
+    #[test]
+    fn lists_providers_by_level_then_name_without_secrets() {
+        let mut config = fixture();
+        config.codex.providers.insert(
+            "anon".to_string(),
+            ProviderConfig {
+                base_url: Some("https://anon.example/v1".to_string()),
+                inline_auth: UpstreamAuth {
+                    allow_anonymous: Some(true),
+                    ..UpstreamAuth::default()
+                },
+                ..ProviderConfig::default()
+            },
+        );
+
+        config.codex.routing = Some(crate::config::RouteGraphConfig::manual_sticky(
+            "primary".to_string(),
+            vec!["primary".to_string(), "backup".to_string()],
+        ));
+
+        let entries = build_config_list(&config, "codex");
+        let names = entries
+            .iter()
+            .map(|entry| entry.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["primary", "anon", "backup"]);
+        assert_eq!(entries[0].level, Some(1));
+        assert_eq!(
+            entries[1].level, None,
+            "unrouted providers sort last, by name"
+        );
+        assert_eq!(
+            entries[2].level, None,
+            "disabled providers are never routed"
+        );
+        assert!(entries.iter().all(|entry| entry.upstream_count == 1));
+
+        let primary = &entries[0];
+        assert!(primary.active);
+        assert_eq!(primary.alias.as_deref(), Some("main"));
+        assert_eq!(
+            primary.auth_source,
+            "bearer=env:PRIMARY_TOKEN,api_key=inline"
+        );
+        assert_eq!(entries[1].auth_source, "anonymous");
+        assert!(!entries[2].active);
+        assert!(!entries[2].enabled);
+        assert_eq!(entries[2].auth_source, "bearer=native:backup.token");
+
+        let json = serde_json::to_string(&entries).expect("serialize");
+        assert!(!json.contains("sk-inline-secret-must-not-leak"), "{json}");
+        assert!(build_config_list(&config, "claude").is_empty());
+    }
+
+    #[test]
+    fn reports_routing_level_reference_auth_and_client_user_agent() {
+        let config = fixture();
+        let view = build_config_show(&config, "codex", "backup", |_| false).expect("provider");
+
+        assert!(!view.enabled);
+        assert!(!view.routing.manual_target);
+        assert_eq!(view.auth[0].source, "native");
+        assert_eq!(view.auth[0].reference, "backup.token");
+        assert_eq!(view.auth[0].env_set, None);
+        assert_eq!(view.user_agent.source, "client");
+        assert!(build_config_show(&config, "codex", "missing", |_| false).is_none());
+        assert!(build_config_show(&config, "claude", "primary", |_| false).is_none());
+    }
+}
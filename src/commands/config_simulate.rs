@@ -0,0 +1,463 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use std::time::Duration;
+
+use codex_helper_core::runtime_identity::ProviderEndpointKey;
+use serde::Serialize;
+
+use super::config_doc::{load_helper_config, select_service_route_config};
+use crate::config::{HelperConfig, ResolvedRetryConfig};
+use crate::proxy::response_penalty_cooldown_secs;
+use crate::routing_ir::{
+    RoutePlanAttemptState, RoutePlanExecutor, RoutePlanRuntimeState, RoutePlanTemplate,
+    RouteRequestContext, compile_route_plan_template_with_request,
+};
+use crate::usage::request_log::{RequestLogRecord, read_request_log_with_rotated};
+use crate::{CliError, CliResult};
+
+/// An upstream that failed this request in the log, with the cooldown the current retry
+/// settings would give it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct HistoricalFailure {
+    provider_endpoint: String,
+    cooldown_secs: u64,
+}
+
+fn provider_endpoint(
+    request: &RequestLogRecord,
+    provider_id: Option<&str>,
+    endpoint_id: Option<&str>,
+) -> Option<String> {
+    let provider_id = provider_id?;
+    Some(
+        ProviderEndpointKey::new(
+            request.service.as_str(),
+            provider_id,
+            endpoint_id.unwrap_or("default"),
+        )
+        .stable_key(),
+    )
+}
+
+fn served_by(request: &RequestLogRecord) -> Option<String> {
+    if !(200..400).contains(&request.status_code) {
+        return None;
+    }
+    provider_endpoint(
+        request,
+        request.provider_id.as_deref(),
+        request.endpoint_id.as_deref(),
+    )
+}
+
+/// Upstream attempts that failed with a status or transport error, i.e. the ones that
+/// would have put the upstream into cooldown and triggered a failover.
+fn failures(request: &RequestLogRecord, retry: &ResolvedRetryConfig) -> Vec<HistoricalFailure> {
+    let mut failures: Vec<HistoricalFailure> = Vec::new();
+    for attempt in request
+        .retry
+        .iter()
+        .flat_map(|retry| retry.route_attempts.iter())
+        .filter(|attempt| !attempt.skipped)
+        .filter(|attempt| {
+            matches!(
+                attempt.decision.as_str(),
+                "failed_status" | "failed_transport"
+            )
+        })
+    {
+        let Some(provider_endpoint) = provider_endpoint(
+            request,
+            attempt.provider_id.as_deref(),
+            attempt.endpoint_id.as_deref(),
+        ) else {
+            continue;
+        };
+        if failures
+            .iter()
+            .any(|failure| failure.provider_endpoint == provider_endpoint)
+        {
+            continue;
+        }
+        failures.push(HistoricalFailure {
+            provider_endpoint,
+            cooldown_secs: response_penalty_cooldown_secs(
+                retry.cloudflare_challenge_cooldown_secs,
+                retry.cloudflare_timeout_cooldown_secs,
+                retry.transport_cooldown_secs,
+                attempt.error_class.as_deref(),
+                attempt.retry_after_ms.map(|ms| ms.div_ceil(1000)),
+            ),
+        });
+    }
+    if request.status_code >= 500
+        && let Some(provider_endpoint) = provider_endpoint(
+            request,
+            request.provider_id.as_deref(),
+            request.endpoint_id.as_deref(),
+        )
+        && !failures
+            .iter()
+            .any(|failure| failure.provider_endpoint == provider_endpoint)
+    {
+        failures.push(HistoricalFailure {
+            provider_endpoint,
+            cooldown_secs: retry.transport_cooldown_secs,
+        });
+    }
+    failures
+}
+
+fn route_request(request: &RequestLogRecord) -> RouteRequestContext {
+    RouteRequestContext {
+        model: request.model.clone(),
+        service_tier: request.service_tier.requested.clone(),
+        reasoning_effort: request.reasoning_effort.clone(),
+        path: request.path.clone(),
+        method: request.method.clone(),
+        headers: BTreeMap::new(),
+    }
+}
+
+/// Counts are estimated requests: a line kept by `logging.sample_rate` counts as
+/// `1 / log_sample_rate` requests.
+#[derive(Debug, Default, Serialize, PartialEq)]
+struct SimulatedUpstream {
+    provider_endpoint: String,
+    /// Requests this upstream answered in the log.
+    historical: f64,
+    /// Requests the current config would land on this upstream.
+    simulated: f64,
+    /// Simulated attempts that failed here and moved on to the next candidate.
+    failed_over: f64,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct RoutingSimulationReport {
+    service: String,
+    since_secs: u64,
+    /// Logged lines replayed; the other counts are weighted by their sample rate.
+    logged_requests: u64,
+    requests: f64,
+    /// Requests that needed at least one failover under the current config.
+    failed_over_requests: f64,
+    /// Requests whose every allowed attempt hit an upstream that failed in the log.
+    exhausted_requests: f64,
+    /// Requests with no eligible candidate at all (unsupported model, schedule, cooldown).
+    unroutable_requests: f64,
+    upstreams: Vec<SimulatedUpstream>,
+}
+
+pub(super) async fn handle_config_simulate_routing(
+    since: Duration,
+    codex: bool,
+    claude: bool,
+    json: bool,
+) -> CliResult<()> {
+    let (cfg, service, label) = load_helper_config(codex, claude, "config")
+        .await
+        .map_err(|e| CliError::Configuration(e.to_string()))?;
+    let request_log = crate::logging::request_log_path();
+    let now_ms = crate::logging::now_ms();
+    let cutoff_ms = now_ms.saturating_sub(since.as_millis() as u64);
+    let requests = read_logged_requests(&request_log, service, cutoff_ms)?;
+    let mut report = simulate_routing(&cfg, service, &requests)
+        .map_err(|e| CliError::Configuration(e.to_string()))?;
+    report.since_secs = since.as_secs();
+
+    if json {
+        let text =
+            serde_json::to_string_pretty(&report).map_err(|e| CliError::Other(e.to_string()))?;
+        println!("{text}");
+    } else {
+        print_routing_simulation(label, &request_log, &report);
+    }
+    Ok(())
+}
+
+fn read_logged_requests(
+    request_log: &Path,
+    service: &str,
+    cutoff_ms: u64,
+) -> CliResult<Vec<RequestLogRecord>> {
+    let mut requests = read_request_log_with_rotated(request_log)
+        .map_err(|error| {
+            CliError::Other(format!("failed to read {}: {error}", request_log.display()))
+        })?
+        .into_iter()
+        .filter(|request| request.service == service && request.timestamp_ms >= cutoff_ms)
+        .collect::<Vec<_>>();
+    requests.sort_by_key(|request| request.timestamp_ms);
+    Ok(requests)
+}
+
+/// Replays logged requests through the current route plan without contacting any upstream.
+///
+/// An upstream that failed a request in the log is assumed to fail it again and is put into
+/// cooldown with the current retry settings; every other selected upstream is assumed to succeed.
+fn simulate_routing(
+    cfg: &HelperConfig,
+    service: &str,
+    requests: &[RequestLogRecord],
+) -> anyhow::Result<RoutingSimulationReport> {
+    let (view, _) = select_service_route_config(cfg, service);
+    let retry = cfg.retry.resolve();
+    let max_attempts = retry.route.max_attempts.max(1);
+    let mut templates: HashMap<String, RoutePlanTemplate> = HashMap::new();
+    let mut cooldown_until_ms: BTreeMap<ProviderEndpointKey, u64> = BTreeMap::new();
+    let mut upstreams: BTreeMap<String, SimulatedUpstream> = BTreeMap::new();
+    let mut report = RoutingSimulationReport {
+        service: service.to_string(),
+        ..RoutingSimulationReport::default()
+    };
+
+    for request in requests {
+        let weight = request.sample_weight();
+        report.logged_requests += 1;
+        report.requests += weight;
+        if let Some(served_by) = served_by(request) {
+            upstreams.entry(served_by).or_default().historical += weight;
+        }
+
+        let route_request = route_request(request);
+        let template_key = format!("{route_request:?}");
+        if !templates.contains_key(&template_key) {
+            let template = compile_route_plan_template_with_request(service, view, &route_request)?;
+            templates.insert(template_key.clone(), template);
+        }
+        let template = &templates[&template_key];
+
+        let mut runtime = RoutePlanRuntimeState::default();
+        runtime.apply_provider_schedules(template, request.timestamp_ms);
+        for (key, until_ms) in &cooldown_until_ms {
+            if *until_ms > request.timestamp_ms {
+                let mut state = runtime.provider_endpoint(key);
+                state.cooldown_active = true;
+                state.cooldown_remaining_secs =
+                    Some((until_ms - request.timestamp_ms).div_ceil(1000));
+                runtime.set_provider_endpoint(key.clone(), state);
+            }
+        }
+
+        let failures = failures(request, &retry);
+        let executor = RoutePlanExecutor::new(template);
+        let mut attempt_state = RoutePlanAttemptState::default();
+        let mut failed_over = false;
+        let mut landed = false;
+        let mut selected_any = false;
+        for _ in 0..max_attempts {
+            let selection = executor.select_supported_candidate_with_runtime_state(
+                &mut attempt_state,
+                &runtime,
+                route_request.model.as_deref(),
+            );
+            let Some(selected) = selection.selected else {
+                break;
+            };
+            selected_any = true;
+            let stable_key = selected.provider_endpoint.stable_key();
+            let Some(failure) = failures
+                .iter()
+                .find(|failure| failure.provider_endpoint == stable_key)
+            else {
+                upstreams.entry(stable_key).or_default().simulated += weight;
+                landed = true;
+                break;
+            };
+            upstreams.entry(stable_key).or_default().failed_over += weight;
+            failed_over = true;
+            let until_ms = request
+                .timestamp_ms
+                .saturating_add(failure.cooldown_secs.saturating_mul(1000));
+            let entry = cooldown_until_ms
+                .entry(selected.provider_endpoint.clone())
+                .or_default();
+            *entry = (*entry).max(until_ms);
+            attempt_state.avoid_selected(&selected);
+        }
+
+        if failed_over {
+            report.failed_over_requests += weight;
+        }
+        if !selected_any {
+            report.unroutable_requests += weight;
+        } else if !landed {
+            report.exhausted_requests += weight;
+        }
+    }
+
+    report.upstreams = upstreams
+        .into_iter()
+        .map(|(provider_endpoint, upstream)| SimulatedUpstream {
+            provider_endpoint,
+            ..upstream
+        })
+        .collect();
+    Ok(report)
+}
+
+fn print_routing_simulation(label: &str, request_log: &Path, report: &RoutingSimulationReport) {
+    println!("Service: {label}");
+    println!("Source: {}", request_log.display());
+    println!(
+        "Window: last {}",
+        humantime::format_duration(Duration::from_secs(report.since_secs))
+    );
+    if report.logged_requests == 0 {
+        println!("No {} requests in this window.", report.service);
+        return;
+    }
+    if report.requests > report.logged_requests as f64 {
+        println!(
+            "Requests: {:.0} (estimated from {} sampled log lines)",
+            report.requests, report.logged_requests
+        );
+    } else {
+        println!("Requests: {:.0}", report.requests);
+    }
+    println!("Failed over: {:.0}", report.failed_over_requests);
+    println!("Exhausted: {:.0}", report.exhausted_requests);
+    println!("Unroutable: {:.0}", report.unroutable_requests);
+    println!("Upstreams (historical -> simulated, failed over):");
+    for upstream in &report.upstreams {
+        println!(
+            "  {} {:.0} -> {:.0} ({:.0})",
+            upstream.provider_endpoint,
+            upstream.historical,
+            upstream.simulated,
+            upstream.failed_over
+        );
+    }
+    println!(
+        "Simulated offline: upstreams that failed in the log are assumed to fail again; all others succeed."
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ProviderConfig;
+    use crate::logging::{RetryInfo, RouteAttemptLog};
+
+    fn provider(base_url: &str) -> ProviderConfig {
+        ProviderConfig {
+            base_url: Some(base_url.to_string()),
+            ..ProviderConfig::default()
+        }
+    }
+
+    fn config() -> HelperConfig {
+        let mut config = HelperConfig::default();
+        config.codex.providers.insert(
+            "primary".to_string(),
+            provider("https://primary.example/v1"),
+        );
+        config
+            .codex
+            .providers
+            .insert("backup".to_string(), provider("https://backup.example/v1"));
+        config.codex.routing = Some(crate::config::RouteGraphConfig::ordered_failover(vec![
+            "primary".to_string(),
+            "backup".to_string(),
+        ]));
+        config
+    }
+
+    fn attempt(provider_id: &str, decision: &str) -> RouteAttemptLog {
+        let mut attempt = RouteAttemptLog::default();
+        attempt.provider_id = Some(provider_id.to_string());
+        attempt.endpoint_id = Some("default".to_string());
+        attempt.decision = decision.to_string();
+        attempt
+    }
+
+    fn logged(timestamp_ms: u64, failed_primary: bool) -> RequestLogRecord {
+        let served_by = if failed_primary { "backup" } else { "primary" };
+        let mut route_attempts = Vec::new();
+        if failed_primary {
+            route_attempts.push(attempt("primary", "failed_transport"));
+        }
+        route_attempts.push(attempt(served_by, "completed"));
+        RequestLogRecord {
+            timestamp_ms,
+            service: "codex".to_string(),
+            method: Some("POST".to_string()),
+            path: Some("/v1/responses".to_string()),
+            status_code: 200,
+            model: Some("gpt-5".to_string()),
+            provider_id: Some(served_by.to_string()),
+            endpoint_id: Some("default".to_string()),
+            retry: Some(RetryInfo {
+                attempts: route_attempts.len() as u32,
+                route_attempts,
+            }),
+            ..RequestLogRecord::default()
+        }
+    }
+
+    fn upstream<'a>(report: &'a RoutingSimulationReport, key: &str) -> &'a SimulatedUpstream {
+        report
+            .upstreams
+            .iter()
+            .find(|upstream| upstream.provider_endpoint == key)
+            .expect("upstream in report")
+    }
+
+    #[test]
+    fn failed_upstream_fails_over_and_stays_in_cooldown() {
+        let config = config();
+        let transport_cooldown_ms = config.retry.resolve().transport_cooldown_secs * 1000;
+        let requests = vec![
+            logged(1_000, true),
+            // Still inside the primary's cooldown: goes straight to the backup.
+            logged(2_000, false),
+            // Cooldown over: back on the primary.
+            logged(1_000 + transport_cooldown_ms + 1, false),
+        ];
+
+        let report = simulate_routing(&config, "codex", &requests).expect("simulate");
+
+        assert_eq!(report.requests, 3.0);
+        assert_eq!(report.failed_over_requests, 1.0);
+        assert_eq!(report.exhausted_requests, 0.0);
+        assert_eq!(report.unroutable_requests, 0.0);
+        let primary = upstream(&report, "codex/primary/default");
+        assert_eq!(
+            (primary.historical, primary.simulated, primary.failed_over),
+            (2.0, 1.0, 1.0)
+        );
+        let backup = upstream(&report, "codex/backup/default");
+        assert_eq!((backup.historical, backup.simulated), (1.0, 2.0));
+    }
+
+    #[test]
+    fn reordered_levels_move_traffic_without_upstream_calls() {
+        let mut config = config();
+        config.codex.routing = Some(crate::config::RouteGraphConfig::ordered_failover(vec![
+            "backup".to_string(),
+            "primary".to_string(),
+        ]));
+        let requests = vec![logged(1_000, false), logged(2_000, false)];
+
+        let report = simulate_routing(&config, "codex", &requests).expect("simulate");
+
+        assert_eq!(upstream(&report, "codex/primary/default").simulated, 0.0);
+        assert_eq!(upstream(&report, "codex/backup/default").simulated, 2.0);
+        assert_eq!(report.failed_over_requests, 0.0);
+    }
+
+    #[test]
+    fn sampled_lines_count_for_the_requests_they_stand_for() {
+        let config = config();
+        let mut sampled = logged(1_000, false);
+        sampled.log_sample_rate = Some(0.1);
+        let requests = vec![sampled, logged(2_000, false)];
+
+        let report = simulate_routing(&config, "codex", &requests).expect("simulate");
+
+        assert_eq!(report.logged_requests, 2);
+        assert_eq!(report.requests, 11.0);
+        let primary = upstream(&report, "codex/primary/default");
+        assert_eq!((primary.historical, primary.simulated), (11.0, 11.0));
+    }
+}
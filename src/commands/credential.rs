@@ -224,11 +224,46 @@ fn read_secret_input(
     stdin: bool,
     input: &dyn CredentialInput,
 ) -> CliResult<SecretValue> {
+    let (bytes, from_stdin) = read_secret_bytes(name, stdin, input)?;
+    validate_secret_bytes(bytes, from_stdin)
+}
+
+/// Reads a provider secret that will be stored inline in `config.toml`, using the
+/// same masked-prompt / `--stdin` rules and validation as native credentials.
+pub(super) fn read_inline_secret(
+    label: &str,
+    stdin: bool,
+) -> CliResult<crate::config::InlineCredentialValue> {
+    read_inline_secret_from(label, stdin, &ProcessCredentialInput)
+}
+
+fn read_inline_secret_from(
+    label: &str,
+    stdin: bool,
+    input: &dyn CredentialInput,
+) -> CliResult<crate::config::InlineCredentialValue> {
+    let (bytes, from_stdin) = read_secret_bytes(&label, stdin, input)?;
+    let mut bytes = Zeroizing::new(bytes);
+    if from_stdin {
+        strip_terminal_line_ending(&mut bytes);
+    }
+    validate_secret_bytes(bytes.to_vec(), false)?;
+    let value = String::from_utf8(std::mem::take(&mut *bytes))
+        .map_err(|_| CliError::Other("invalid credential value: not valid UTF-8".to_string()))?;
+    Ok(value.into())
+}
+
+/// Returns the raw secret bytes and whether they came from stdin.
+fn read_secret_bytes(
+    name: &dyn std::fmt::Display,
+    stdin: bool,
+    input: &dyn CredentialInput,
+) -> CliResult<(Vec<u8>, bool)> {
     if stdin {
         let bytes = input
             .read_stdin(NATIVE_CREDENTIAL_MAX_BYTES)
             .map_err(|error| CliError::Other(format!("read credential from stdin: {error}")))?;
-        return validate_secret_bytes(bytes, true);
+        return Ok((bytes, true));
     }
     if !input.is_interactive() {
         return Err(CliError::Other(
@@ -253,19 +288,20 @@ fn read_secret_input(
             "credential confirmation does not match".to_string(),
         ));
     }
-    validate_secret_bytes(std::mem::take(&mut *bytes), false)
+    Ok((std::mem::take(&mut *bytes), false))
 }
 
-fn validate_secret_bytes(
-    mut bytes: Vec<u8>,
-    strip_terminal_line_ending: bool,
-) -> CliResult<SecretValue> {
-    if strip_terminal_line_ending {
-        if bytes.ends_with(b"\r\n") {
-            bytes.truncate(bytes.len() - 2);
-        } else if bytes.ends_with(b"\n") {
-            bytes.truncate(bytes.len() - 1);
-        }
+fn strip_terminal_line_ending(bytes: &mut Vec<u8>) {
+    if bytes.ends_with(b"\r\n") {
+        bytes.truncate(bytes.len() - 2);
+    } else if bytes.ends_with(b"\n") {
+        bytes.truncate(bytes.len() - 1);
+    }
+}
+
+fn validate_secret_bytes(mut bytes: Vec<u8>, from_stdin: bool) -> CliResult<SecretValue> {
+    if from_stdin {
+        strip_terminal_line_ending(&mut bytes);
     }
     let value = SecretValue::new(bytes)
         .map_err(|error| CliError::Other(format!("invalid credential value: {error}")))?;
@@ -853,6 +889,28 @@ mod tests {
         assert!(validate_secret_bytes(vec![b'x'; NATIVE_CREDENTIAL_MAX_BYTES], true).is_ok());
     }
 
+    #[test]
+    fn inline_secret_uses_the_same_input_rules_as_native_credentials() {
+        let piped = FakeInput {
+            interactive: false,
+            masked: Vec::new(),
+            masked_confirmation: None,
+            stdin: b"sk-rotated\r\n".to_vec(),
+            confirmed: false,
+            masked_calls: Cell::new(0),
+        };
+        let value = read_inline_secret_from("relay bearer", true, &piped).expect("stdin secret");
+        assert_eq!(value.as_str(), "sk-rotated");
+
+        assert!(read_inline_secret_from("relay bearer", false, &piped).is_err());
+
+        let multi_line = FakeInput {
+            stdin: b"two\nlines\n".to_vec(),
+            ..piped
+        };
+        assert!(read_inline_secret_from("relay bearer", true, &multi_line).is_err());
+    }
+
     #[test]
     fn environment_import_does_not_mutate_or_remove_the_source() {
         let source = String::from("imported-value\n");
@@ -1,12 +1,20 @@
+use super::cli_lang::{cli_language, doctor_lang, pick};
+use super::term_style::{Tone, configured_theme, paint};
 use crate::CliResult;
 use crate::config::load_config;
-use crate::dashboard_core::{OperatorReadModel, OperatorReadStatus};
+use crate::dashboard_core::{
+    OperatorProviderEndpointSummary, OperatorReadModel, OperatorReadStatus,
+};
 use crate::doctor::{
-    ConfigurationServiceStatusSnapshot, ConfigurationStatusSnapshot, DoctorLang, DoctorStatus,
+    ConfigurationServiceStatusSnapshot, ConfigurationStatusSnapshot, DoctorSeverity, DoctorStatus,
     configuration_status_snapshot, run_doctor,
 };
+use crate::state::RuntimeConfigState;
+use crate::tui::Language;
 use codex_helper_core::credentials::CredentialSourceCapabilities;
 use owo_colors::OwoColorize;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
 
 pub async fn handle_status_cmd(
     json: bool,
@@ -26,12 +34,13 @@ pub async fn handle_status_cmd(
         return Ok(());
     }
 
+    let language = cli_language().await;
     println!("{}", "codex-helper status".bold());
     println!("{}", "===================".bold());
 
-    print_configuration_status(&configuration);
-    print_operator_status("Codex", codex);
-    print_operator_status("Claude", claude);
+    print_configuration_status(language, &configuration);
+    print_operator_status(language, "Codex", codex);
+    print_operator_status(language, "Claude", claude);
 
     Ok(())
 }
@@ -41,31 +50,119 @@ fn status_payload(
     codex: &OperatorReadModel,
     claude: &OperatorReadModel,
 ) -> serde_json::Value {
-    serde_json::json!({
+    let mut payload = serde_json::json!({
         "api_version": 1,
         "source": "operator_read_model",
         "configuration": configuration,
         "codex": codex,
         "claude": claude,
+    });
+    let mut health = serde_json::Map::new();
+    for (service_name, model) in [("codex", codex), ("claude", claude)] {
+        if let Some(service_health) = runtime_health_by_priority(model) {
+            health.insert(
+                service_name.to_string(),
+                serde_json::to_value(service_health).unwrap_or_default(),
+            );
+        }
+    }
+    if !health.is_empty() {
+        payload["health"] = serde_json::Value::Object(health);
+    }
+    payload
+}
+
+/// Endpoint health of one priority level (lower value = preferred tier).
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+struct PriorityTierHealth {
+    priority: u32,
+    providers: usize,
+    healthy_providers: usize,
+    endpoints: usize,
+    healthy_endpoints: usize,
+    /// True when no endpoint in this tier can currently take traffic.
+    tier_degraded: bool,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+struct RuntimeHealthByPriority {
+    levels: Vec<PriorityTierHealth>,
+    any_tier_degraded: bool,
+}
+
+fn endpoint_is_healthy(endpoint: &OperatorProviderEndpointSummary) -> bool {
+    endpoint.effective_enabled
+        && endpoint.routable
+        && endpoint.runtime_state == RuntimeConfigState::Normal
+}
+
+/// Groups the running proxy's endpoint health by priority; `None` when no runtime data is
+/// available so callers can omit the block.
+fn runtime_health_by_priority(model: &OperatorReadModel) -> Option<RuntimeHealthByPriority> {
+    let data = model.data.as_ref()?;
+    #[derive(Default)]
+    struct Tier<'a> {
+        providers: BTreeSet<&'a str>,
+        healthy_providers: BTreeSet<&'a str>,
+        endpoints: usize,
+        healthy_endpoints: usize,
+    }
+    let mut tiers = BTreeMap::<u32, Tier<'_>>::new();
+    for provider in &data.summary.providers {
+        for endpoint in &provider.endpoints {
+            let tier = tiers.entry(endpoint.priority).or_default();
+            tier.providers.insert(provider.name.as_str());
+            tier.endpoints += 1;
+            if endpoint_is_healthy(endpoint) {
+                tier.healthy_providers.insert(provider.name.as_str());
+                tier.healthy_endpoints += 1;
+            }
+        }
+    }
+    let levels = tiers
+        .into_iter()
+        .map(|(priority, tier)| PriorityTierHealth {
+            priority,
+            providers: tier.providers.len(),
+            healthy_providers: tier.healthy_providers.len(),
+            endpoints: tier.endpoints,
+            healthy_endpoints: tier.healthy_endpoints,
+            tier_degraded: tier.healthy_endpoints == 0,
+        })
+        .collect::<Vec<_>>();
+    let any_tier_degraded = levels.iter().any(|level| level.tier_degraded);
+    Some(RuntimeHealthByPriority {
+        levels,
+        any_tier_degraded,
     })
 }
 
-fn print_configuration_status(configuration: &ConfigurationStatusSnapshot) {
-    println!("{}", "Canonical configuration:".bold());
-    println!("  version: {}", configuration.config_version);
-    print_configuration_service(&configuration.codex);
-    print_configuration_service(&configuration.claude);
+fn print_configuration_status(language: Language, configuration: &ConfigurationStatusSnapshot) {
+    println!(
+        "{}",
+        pick(language, "规范配置：", "Canonical configuration:").bold()
+    );
+    println!(
+        "  {} {}",
+        pick(language, "版本：", "version:"),
+        configuration.config_version
+    );
+    print_configuration_service(language, &configuration.codex);
+    print_configuration_service(language, &configuration.claude);
 }
 
-fn print_configuration_service(service: &ConfigurationServiceStatusSnapshot) {
+fn print_configuration_service(language: Language, service: &ConfigurationServiceStatusSnapshot) {
+    let none = pick(language, "<无>", "<none>");
     println!("  {}:", service.service_name);
     println!(
-        "    default profile: {}",
-        service.default_profile.as_deref().unwrap_or("<none>")
+        "    {} {}",
+        pick(language, "默认 profile：", "default profile:"),
+        service.default_profile.as_deref().unwrap_or(none)
     );
     if let Some(client_patch) = service.client_patch.as_ref() {
         println!(
-            "    client patch: preset={}, responses_websocket={}, compaction={}, translate_models={}, hosted_image_generation={}",
+            "    {} preset={}, responses_websocket={}, compaction={}, translate_models={}, hosted_image_generation={}",
+            pick(language, "客户端补丁：", "client patch:"),
             client_patch.preset,
             client_patch.responses_websocket,
             client_patch.compaction,
@@ -73,33 +170,26 @@ fn print_configuration_service(service: &ConfigurationServiceStatusSnapshot) {
             client_patch.hosted_image_generation,
         );
     }
+    let providers_label = pick(language, "providers：", "providers:");
     if service.providers.is_empty() {
-        println!("    providers: <none>");
+        println!("    {providers_label} {none}");
         return;
     }
-    println!("    providers:");
+    println!("    {providers_label}");
     for provider in &service.providers {
-        let state = if provider.enabled {
-            "enabled"
-        } else {
-            "disabled"
-        };
+        let state = enabled_label(language, provider.enabled);
         let route_state = if provider.in_route_graph {
-            "in-route"
+            pick(language, "在路由中", "in-route")
         } else {
-            "configured-only"
+            pick(language, "仅配置", "configured-only")
         };
         println!("      {} [{state}; {route_state}]", provider.provider_id);
         for endpoint in &provider.endpoints {
-            let endpoint_state = if endpoint.enabled {
-                "enabled"
-            } else {
-                "disabled"
-            };
+            let endpoint_state = enabled_label(language, endpoint.enabled);
             let route = endpoint
                 .route_order
-                .map(|order| format!("route-order={order}"))
-                .unwrap_or_else(|| "configured-only".to_string());
+                .map(|order| format!("{}={order}", pick(language, "路由顺序", "route-order")))
+                .unwrap_or_else(|| pick(language, "仅配置", "configured-only").to_string());
             println!(
                 "        {} [{endpoint_state}; {route}]",
                 endpoint.endpoint_id
@@ -108,55 +198,152 @@ fn print_configuration_service(service: &ConfigurationServiceStatusSnapshot) {
     }
 }
 
-fn print_operator_status(label: &str, model: &OperatorReadModel) {
+fn enabled_label(language: Language, enabled: bool) -> &'static str {
+    if enabled {
+        pick(language, "已启用", "enabled")
+    } else {
+        pick(language, "已禁用", "disabled")
+    }
+}
+
+fn print_operator_status(language: Language, label: &str, model: &OperatorReadModel) {
     let status = match model.status {
-        OperatorReadStatus::Ready => "ready".green().to_string(),
-        OperatorReadStatus::Stale => "stale".yellow().to_string(),
-        OperatorReadStatus::Disconnected => "disconnected".yellow().to_string(),
-        OperatorReadStatus::AuthRequired => "auth_required".yellow().to_string(),
+        OperatorReadStatus::Ready => pick(language, "就绪", "ready").green().to_string(),
+        OperatorReadStatus::Stale => pick(language, "过期", "stale").yellow().to_string(),
+        OperatorReadStatus::Disconnected => pick(language, "未连接", "disconnected")
+            .yellow()
+            .to_string(),
+        OperatorReadStatus::AuthRequired => pick(language, "需要认证", "auth_required")
+            .yellow()
+            .to_string(),
+    };
+    let heading = match language {
+        Language::Zh => format!("{label} 运行时："),
+        Language::En => format!("{label} runtime:"),
     };
-    println!("{} {status}", format!("{label} runtime:").bold());
+    println!("{} {status}", heading.bold());
 
     let Some(data) = model.data.as_ref() else {
         if let Some(issue) = model.issue {
-            println!("  issue: {issue:?}");
+            println!("  {} {issue:?}", pick(language, "问题：", "issue:"));
         }
         return;
     };
 
     println!("  captured_at_ms: {}", model.captured_at_ms);
     println!(
-        "  default profile: {}",
+        "  {} {}",
+        pick(language, "默认 profile：", "default profile:"),
         data.summary
             .runtime
             .default_profile
             .as_deref()
-            .unwrap_or("<none>")
-    );
-    println!(
-        "  active requests: {}, recent requests: {}, providers: {}",
-        data.summary.counts.active_requests,
-        data.summary.counts.recent_requests,
-        data.summary.counts.providers
+            .unwrap_or(pick(language, "<无>", "<none>"))
     );
+    let counts = &data.summary.counts;
+    match language {
+        Language::Zh => println!(
+            "  活跃请求：{}，最近请求：{}，providers：{}",
+            counts.active_requests, counts.recent_requests, counts.providers
+        ),
+        Language::En => println!(
+            "  active requests: {}, recent requests: {}, providers: {}",
+            counts.active_requests, counts.recent_requests, counts.providers
+        ),
+    }
     for provider in &data.summary.providers {
-        let state = if provider.effective_enabled {
-            "enabled"
-        } else {
-            "disabled"
-        };
+        let state = enabled_label(language, provider.effective_enabled);
         println!(
-            "    {} [{state}; routable endpoints: {}/{}]",
+            "    {} [{state}; {} {}/{}]",
             provider.name,
+            pick(language, "可路由端点：", "routable endpoints:"),
             provider.routable_endpoints,
             provider.endpoints.len()
         );
     }
 }
 
+/// One service as probed by `status --oneline`.
+pub struct OnelineServiceStatus {
+    pub service_name: &'static str,
+    pub port: u16,
+    pub model: OperatorReadModel,
+    pub started_at_ms: Option<u64>,
+}
+
+/// Renders the `status --oneline` prompt line.
+///
+/// Each running service becomes `<service>:on@<port> active=<provider> err=<pct>% up=<age>`;
+/// unknown values are `-`. Stopped services are left out, and `codex:off` is printed when
+/// nothing is running, so the line is never empty.
+pub fn status_oneline(services: &[OnelineServiceStatus], now_ms: u64) -> String {
+    let segments = services
+        .iter()
+        .filter(|service| service.model.status != OperatorReadStatus::Disconnected)
+        .map(|service| status_oneline_segment(service, now_ms))
+        .collect::<Vec<_>>();
+    if segments.is_empty() {
+        return "codex:off".to_string();
+    }
+    segments.join(" ")
+}
+
+fn status_oneline_segment(service: &OnelineServiceStatus, now_ms: u64) -> String {
+    let data = service.model.data.as_ref();
+    let active = data
+        .and_then(|data| {
+            data.routing
+                .as_ref()
+                .and_then(|routing| routing.entry_target.clone())
+                .or_else(|| {
+                    data.stats_5m
+                        .top_provider
+                        .as_ref()
+                        .map(|(name, _)| name.clone())
+                })
+        })
+        .unwrap_or_else(|| "-".to_string());
+    let err = data
+        .map(|data| {
+            let stats = &data.stats_5m;
+            let failed = stats.total.saturating_sub(stats.ok_2xx);
+            let pct = if stats.total == 0 {
+                0
+            } else {
+                (failed * 100).div_ceil(stats.total)
+            };
+            format!("{pct}%")
+        })
+        .unwrap_or_else(|| "-".to_string());
+    let up = service
+        .started_at_ms
+        .map(|started_at_ms| compact_age(now_ms.saturating_sub(started_at_ms)))
+        .unwrap_or_else(|| "-".to_string());
+    format!(
+        "{}:on@{} active={active} err={err} up={up}",
+        service.service_name, service.port
+    )
+}
+
+pub(super) fn compact_age(elapsed_ms: u64) -> String {
+    let secs = elapsed_ms / 1000;
+    match secs {
+        0..60 => format!("{secs}s"),
+        60..3_600 => format!("{}m", secs / 60),
+        3_600..86_400 => format!("{}h", secs / 3_600),
+        _ => format!("{}d", secs / 86_400),
+    }
+}
+
 pub async fn handle_doctor_cmd(json: bool) -> CliResult<()> {
+    // JSON stays English so scripts see the same text whatever the user's language.
+    let language = if json {
+        Language::En
+    } else {
+        cli_language().await
+    };
     let report = run_doctor(
-        DoctorLang::Zh,
+        doctor_lang(language),
         CredentialSourceCapabilities::platform_native(),
     )
     .await;
@@ -164,23 +351,37 @@ pub async fn handle_doctor_cmd(json: bool) -> CliResult<()> {
         let text = serde_json::to_string_pretty(&report)
             .map_err(|error| crate::CliError::Other(error.to_string()))?;
         println!("{text}");
-        return Ok(());
-    }
-
-    println!("{}", "codex-helper doctor".bold());
-    println!("{}", "===================".bold());
-    if let Some(configuration) = report.configuration.as_ref() {
-        print_configuration_status(configuration);
-    }
-    for check in report.checks {
-        match check.status {
-            DoctorStatus::Ok => println!("{}   {}", "[OK]".green(), check.message),
-            DoctorStatus::Info => println!("{} {}", "[INFO]".cyan(), check.message),
-            DoctorStatus::Warn => println!("{} {}", "[WARN]".yellow(), check.message),
-            DoctorStatus::Fail => println!("{} {}", "[FAIL]".red(), check.message),
+    } else {
+        let theme = configured_theme().await;
+        println!("{}", "codex-helper doctor".bold());
+        println!("{}", "===================".bold());
+        if let Some(configuration) = report.configuration.as_ref() {
+            print_configuration_status(language, configuration);
+        }
+        for check in &report.checks {
+            let label = match check.status {
+                DoctorStatus::Ok => format!("{}  ", paint(theme, Tone::Good, "[OK]")),
+                DoctorStatus::Info => paint(theme, Tone::Info, "[INFO]"),
+                DoctorStatus::Warn => paint(theme, Tone::Warn, "[WARN]"),
+                DoctorStatus::Fail => paint(theme, Tone::Bad, "[FAIL]"),
+            };
+            println!("{label} {}: {}", check.title.bold(), check.detail);
+            if let Some(remediation) = check.remediation.as_deref() {
+                println!("       -> {remediation}");
+            }
         }
     }
 
+    if report.worst_severity() == DoctorSeverity::Error {
+        let errors = report
+            .checks
+            .iter()
+            .filter(|check| check.severity == DoctorSeverity::Error)
+            .count();
+        return Err(crate::CliError::Other(format!(
+            "doctor found {errors} error-level check(s)"
+        )));
+    }
     Ok(())
 }
 
@@ -258,6 +459,7 @@ mod tests {
                 quota_analytics: Default::default(),
                 stats_5m: Default::default(),
                 stats_1h: Default::default(),
+                request_quotas: Vec::new(),
                 pricing_catalog: Default::default(),
                 service_status: None,
                 provider_balances: Vec::new(),
@@ -265,6 +467,41 @@ mod tests {
         )
     }
 
+    #[test]
+    fn status_oneline_prints_running_services_and_falls_back_to_off() {
+        let offline = [
+            OnelineServiceStatus {
+                service_name: "codex",
+                port: 3211,
+                model: OperatorReadModel::disconnected("codex"),
+                started_at_ms: None,
+            },
+            OnelineServiceStatus {
+                service_name: "claude",
+                port: 3210,
+                model: OperatorReadModel::disconnected("claude"),
+                started_at_ms: None,
+            },
+        ];
+        assert_eq!(status_oneline(&offline, 0), "codex:off");
+
+        let mut model = ready_operator_model("codex");
+        let data = model.data.as_mut().expect("ready data");
+        data.stats_5m.total = 200;
+        data.stats_5m.ok_2xx = 198;
+        data.stats_5m.top_provider = Some(("openrouter".to_string(), 150));
+        let running = [OnelineServiceStatus {
+            service_name: "codex",
+            port: 3211,
+            model,
+            started_at_ms: Some(1_000),
+        }];
+        assert_eq!(
+            status_oneline(&running, 1_000 + 2 * 3_600_000 + 5_000),
+            "codex:on@3211 active=openrouter err=1% up=2h"
+        );
+    }
+
     #[test]
     fn status_payload_keeps_configuration_when_both_runtimes_are_offline() {
         let payload = status_payload(
@@ -289,6 +526,7 @@ mod tests {
         );
         assert_eq!(payload["codex"]["status"], "disconnected");
         assert_eq!(payload["claude"]["status"], "disconnected");
+        assert!(payload.get("health").is_none());
     }
 
     #[test]
@@ -307,4 +545,84 @@ mod tests {
         assert_eq!(payload["claude"]["status"], "ready");
         assert_eq!(payload["codex"]["data"]["summary"]["service_name"], "codex");
     }
+
+    fn endpoint(
+        name: &str,
+        priority: u32,
+        state: RuntimeConfigState,
+    ) -> OperatorProviderEndpointSummary {
+        OperatorProviderEndpointSummary {
+            provider_name: String::new(),
+            name: name.to_string(),
+            provider_endpoint_key: format!("endpoint:{name}"),
+            origin: None,
+            priority,
+            configured_enabled: true,
+            effective_enabled: true,
+            routable: true,
+            credential_readiness: None,
+            credential_details: Vec::new(),
+            runtime_enabled_override: None,
+            runtime_state: state,
+            runtime_state_override: None,
+            capacity: Default::default(),
+            policy_actions: Vec::new(),
+            primary_sticky_remaining_secs: None,
+        }
+    }
+
+    #[test]
+    fn status_payload_groups_runtime_health_by_priority_tier() {
+        use crate::dashboard_core::OperatorProviderSummary;
+
+        let provider =
+            |name: &str, endpoints: Vec<OperatorProviderEndpointSummary>| OperatorProviderSummary {
+                name: name.to_string(),
+                alias: None,
+                configured_enabled: true,
+                effective_enabled: true,
+                routable_endpoints: endpoints.len(),
+                credential_readiness: None,
+                endpoints,
+                capacity: Default::default(),
+                schedule: None,
+            };
+        let mut codex = ready_operator_model("codex");
+        codex.data.as_mut().expect("ready data").summary.providers = vec![
+            provider(
+                "primary",
+                vec![
+                    endpoint("a", 0, RuntimeConfigState::BreakerOpen),
+                    endpoint("b", 0, RuntimeConfigState::Draining),
+                ],
+            ),
+            provider(
+                "backup",
+                vec![
+                    endpoint("c", 10, RuntimeConfigState::Normal),
+                    endpoint("d", 10, RuntimeConfigState::BreakerOpen),
+                ],
+            ),
+        ];
+
+        let payload = status_payload(
+            &configuration(),
+            &codex,
+            &OperatorReadModel::disconnected("claude"),
+        );
+
+        let levels = &payload["health"]["codex"]["levels"];
+        assert_eq!(levels[0]["priority"], 0);
+        assert_eq!(levels[0]["endpoints"], 2);
+        assert_eq!(levels[0]["healthy_endpoints"], 0);
+        assert_eq!(levels[0]["tier_degraded"], true);
+        assert_eq!(levels[1]["priority"], 10);
+        assert_eq!(levels[1]["providers"], 1);
+        assert_eq!(levels[1]["healthy_providers"], 1);
+        assert_eq!(levels[1]["healthy_endpoints"], 1);
+        assert_eq!(levels[1]["tier_degraded"], false);
+        assert_eq!(payload["health"]["codex"]["any_tier_degraded"], true);
+        assert!(payload["health"].get("claude").is_none());
+        assert_eq!(payload["codex"]["status"], "ready");
+    }
 }
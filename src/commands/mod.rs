@@ -1,4 +1,9 @@
+pub(crate) mod cli_lang;
 mod config_doc;
+mod config_env_template;
+mod config_secrets;
+mod config_show;
+mod config_simulate;
 
 pub mod codex;
 pub mod config;
@@ -8,8 +13,15 @@ pub mod pricing;
 pub mod provider;
 mod route_view;
 pub mod routing;
+pub mod serve_detach;
+pub mod serve_preflight;
 pub mod session;
+mod session_context;
+mod session_watch;
+mod term_style;
 pub mod usage;
+pub mod usage_check;
+pub mod usage_gc;
 
 #[cfg(test)]
 pub(crate) mod test_support {
@@ -247,10 +247,23 @@ pub async fn handle_provider_cmd(cmd: ProviderCommand) -> CliResult<()> {
             native,
             secret_file,
             environment,
+            inline,
+            stdin,
             codex,
             claude,
         } => {
-            let source = ProviderAuthSource::from_args(native, secret_file, environment)?;
+            let inline = if inline {
+                let label = format!("{name} {}", provider_auth_kind_label(kind));
+                Some(super::credential::read_inline_secret(&label, stdin)?)
+            } else {
+                None
+            };
+            let source = ProviderAuthSource::from_args(native, secret_file, environment, inline)?;
+            if matches!(source, ProviderAuthSource::Inline(_)) {
+                eprintln!(
+                    "warning: the secret is stored in plain text in config.toml; prefer --environment or --native so it stays off disk"
+                );
+            }
             let requested_service = requested_service(codex, claude)?;
             load_config()
                 .await
@@ -320,6 +333,7 @@ enum ProviderAuthSource {
     Native(String),
     SecretFile(String),
     Environment(String),
+    Inline(crate::config::InlineCredentialValue),
 }
 
 impl ProviderAuthSource {
@@ -327,14 +341,15 @@ impl ProviderAuthSource {
         native: Option<String>,
         secret_file: Option<std::path::PathBuf>,
         environment: Option<String>,
+        inline: Option<crate::config::InlineCredentialValue>,
     ) -> CliResult<Self> {
-        match (native, secret_file, environment) {
-            (Some(name), None, None) => {
+        match (native, secret_file, environment, inline) {
+            (Some(name), None, None, None) => {
                 let name = codex_helper_core::credentials::CredentialName::parse(name)
                     .map_err(|error| CliError::Configuration(error.to_string()))?;
                 Ok(Self::Native(name.to_string()))
             }
-            (None, Some(path), None) => {
+            (None, Some(path), None, None) => {
                 if !path.is_absolute() {
                     return Err(CliError::Configuration(
                         "secret-file credential path must be absolute".to_string(),
@@ -352,12 +367,13 @@ impl ProviderAuthSource {
                 }
                 Ok(Self::SecretFile(path))
             }
-            (None, None, Some(name)) => {
+            (None, None, Some(name), None) => {
                 validate_environment_reference(&name)?;
                 Ok(Self::Environment(name))
             }
+            (None, None, None, Some(value)) => Ok(Self::Inline(value)),
             _ => Err(CliError::Configuration(
-                "provider auth requires exactly one of --native, --secret-file, or --environment"
+                "provider auth requires exactly one of --native, --secret-file, --environment, or --inline"
                     .to_string(),
             )),
         }
@@ -368,6 +384,7 @@ impl ProviderAuthSource {
             Self::Native(name) => format!("native:{name}"),
             Self::SecretFile(path) => format!("secret_file:{path}"),
             Self::Environment(name) => format!("environment:{name}"),
+            Self::Inline(_) => "inline:<redacted>".to_string(),
         }
     }
 }
@@ -397,6 +414,9 @@ fn set_provider_auth(
         (ProviderAuthKind::Bearer, ProviderAuthSource::Environment(name)) => {
             provider.auth.auth_token_env = Some(name);
         }
+        (ProviderAuthKind::Bearer, ProviderAuthSource::Inline(value)) => {
+            provider.inline_auth.auth_token = Some(value);
+        }
         (ProviderAuthKind::ApiKey, ProviderAuthSource::Native(name)) => {
             provider.auth.api_key_ref = Some(CredentialRef::Native { name });
         }
@@ -406,6 +426,9 @@ fn set_provider_auth(
         (ProviderAuthKind::ApiKey, ProviderAuthSource::Environment(name)) => {
             provider.auth.api_key_env = Some(name);
         }
+        (ProviderAuthKind::ApiKey, ProviderAuthSource::Inline(value)) => {
+            provider.inline_auth.api_key = Some(value);
+        }
     }
 }
 
@@ -765,6 +788,14 @@ mod tests {
                         },
                     },
                 )]),
+                enabled_schedule: Vec::new(),
+                schedule_timezone: None,
+                tls: crate::config::ProviderTlsConfig::default(),
+                user_agent: None,
+                address_family: None,
+                buffer_responses: None,
+                strip_request_headers: Vec::new(),
+                healthcheck: None,
             },
         );
         config.codex.providers.insert(
@@ -878,7 +909,7 @@ mod tests {
     #[test]
     fn provider_auth_sources_validate_each_reference_kind() {
         assert!(matches!(
-            ProviderAuthSource::from_args(Some("relay.primary".to_string()), None, None)
+            ProviderAuthSource::from_args(Some("relay.primary".to_string()), None, None, None)
                 .expect("native source"),
             ProviderAuthSource::Native(_)
         ));
@@ -887,23 +918,62 @@ mod tests {
                 None,
                 Some(std::env::temp_dir().join("relay-secret")),
                 None,
+                None,
             )
             .expect("secret-file source"),
             ProviderAuthSource::SecretFile(_)
         ));
         assert!(matches!(
-            ProviderAuthSource::from_args(None, None, Some("RELAY_TOKEN".to_string()))
+            ProviderAuthSource::from_args(None, None, Some("RELAY_TOKEN".to_string()), None)
                 .expect("environment source"),
             ProviderAuthSource::Environment(_)
         ));
 
         assert!(
-            ProviderAuthSource::from_args(Some("Relay.Invalid".to_string()), None, None).is_err()
+            ProviderAuthSource::from_args(Some("Relay.Invalid".to_string()), None, None, None)
+                .is_err()
+        );
+        assert!(ProviderAuthSource::from_args(None, Some("relative".into()), None, None).is_err());
+        assert!(
+            ProviderAuthSource::from_args(None, None, Some("BAD\u{1b}ENV".to_string()), None)
+                .is_err()
         );
-        assert!(ProviderAuthSource::from_args(None, Some("relative".into()), None).is_err());
         assert!(
-            ProviderAuthSource::from_args(None, None, Some("BAD\u{1b}ENV".to_string())).is_err()
+            ProviderAuthSource::from_args(
+                None,
+                None,
+                Some("RELAY_TOKEN".to_string()),
+                Some("sk-inline".into()),
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn setting_inline_auth_replaces_other_sources_and_never_echoes_the_secret() {
+        let mut provider = ProviderConfig {
+            base_url: Some("https://relay.example/v1".to_string()),
+            auth: UpstreamAuth {
+                auth_token_env: Some("OLD_BEARER".to_string()),
+                api_key_env: Some("KEEP_API_KEY".to_string()),
+                ..UpstreamAuth::default()
+            },
+            ..ProviderConfig::default()
+        };
+        let source = ProviderAuthSource::from_args(None, None, None, Some("sk-rotated".into()))
+            .expect("inline source");
+        assert_eq!(source.summary(), "inline:<redacted>");
+
+        set_provider_auth(&mut provider, ProviderAuthKind::Bearer, source);
+
+        let effective = provider.effective_auth();
+        assert_eq!(
+            effective.auth_token.as_ref().map(|value| value.as_str()),
+            Some("sk-rotated")
         );
+        assert!(effective.auth_token_env.is_none());
+        assert!(effective.auth_token_ref.is_none());
+        assert_eq!(effective.api_key_env.as_deref(), Some("KEEP_API_KEY"));
     }
 
     #[test]
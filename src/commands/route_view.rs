@@ -3,6 +3,9 @@ use super::config_doc::{
     routing_exhausted_label, routing_policy_label, select_service_route_config,
 };
 use crate::config::{CURRENT_CONFIG_VERSION, ServiceRouteConfig};
+use crate::control_plane_client::{
+    ControlPlaneClient, ControlPlaneEndpoint, configured_local_admin_token_env,
+};
 use crate::routing_explain::{
     RoutingExplainCandidate, RoutingExplainResponse, RoutingExplainSkipReason,
     build_routing_explain_response_with_request, parse_routing_explain_headers,
@@ -272,13 +275,15 @@ fn build_config_only_explain(
         method: request.method.clone(),
         header_names: request.headers.keys().cloned().collect(),
     };
+    let mut runtime = RoutePlanRuntimeState::default();
+    runtime.apply_provider_schedules(&template, crate::logging::now_ms());
     let compatibility = build_routing_explain_response_with_request(
         service_name,
         None,
         request,
         None,
         &template,
-        &RoutePlanRuntimeState::default(),
+        &runtime,
     );
     let first_config_eligible_candidate = compatibility.selected_route.clone();
 
@@ -299,6 +304,7 @@ fn config_skip_reason_label(reason: &RoutingExplainSkipReason) -> &'static str {
     match reason {
         RoutingExplainSkipReason::UnsupportedModel { .. } => "unsupported_model",
         RoutingExplainSkipReason::RuntimeDisabled => "runtime_disabled",
+        RoutingExplainSkipReason::OutsideSchedule => "outside_schedule",
         RoutingExplainSkipReason::Draining => "draining",
         RoutingExplainSkipReason::Cooldown => "cooldown",
         RoutingExplainSkipReason::BreakerOpen { .. } => "breaker_open",
@@ -383,6 +389,184 @@ fn print_config_only_explain_text(explain: &ConfigOnlyRouteExplain) {
     }
 }
 
+#[derive(Debug, Serialize)]
+struct ExplainRouteOutput<'a> {
+    source: &'static str,
+    #[serde(flatten)]
+    explain: &'a RoutingExplainResponse,
+}
+
+fn explain_route_text_lines(explain: &RoutingExplainResponse, source: &str) -> Vec<String> {
+    let mut lines = vec![
+        format!("Source: {source}"),
+        format!("Service: {}", explain.service_name),
+    ];
+    let request = &explain.request_context;
+    let mut request_parts = Vec::new();
+    if let Some(model) = request.model.as_deref() {
+        request_parts.push(format!("model={model}"));
+    }
+    if let Some(effort) = request.reasoning_effort.as_deref() {
+        request_parts.push(format!("effort={effort}"));
+    }
+    if let Some(service_tier) = request.service_tier.as_deref() {
+        request_parts.push(format!("service_tier={service_tier}"));
+    }
+    if let Some(session) = explain.session_id.as_deref() {
+        request_parts.push(format!("session={session}"));
+    }
+    if !request_parts.is_empty() {
+        lines.push(format!("Request: {}", request_parts.join(" ")));
+    }
+    match &explain.selected_route {
+        Some(selected) => {
+            lines.push(format!(
+                "Selected: {} provider={} upstream={}",
+                selected.provider_endpoint_key, selected.provider_id, selected.upstream_base_url
+            ));
+            lines.push(format!(
+                "Reason: {}",
+                explain
+                    .selection_reason
+                    .map(|reason| reason.as_str())
+                    .unwrap_or("-")
+            ));
+        }
+        None => lines.push("Selected: <none> (no eligible candidate)".to_string()),
+    }
+
+    if explain.candidates.is_empty() {
+        lines.push("Candidates: <empty>".to_string());
+        return lines;
+    }
+    lines.push("Candidates:".to_string());
+    for (idx, candidate) in explain.candidates.iter().enumerate() {
+        let marker = if candidate.selected { "*" } else { " " };
+        let mut status = candidate.availability.summary();
+        if let Some(remaining) = candidate.availability.cooldown_remaining_secs {
+            status.push_str(&format!(" cooldown={remaining}s"));
+        }
+        if let Some(remaining) = candidate.availability.primary_sticky_remaining_secs {
+            status.push_str(&format!(" sticky-hold={remaining}s"));
+        }
+        let skips = if candidate.skip_reasons.is_empty() {
+            "-".to_string()
+        } else {
+            candidate
+                .skip_reasons
+                .iter()
+                .map(RoutingExplainSkipReason::code)
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        lines.push(format!(
+            "  {} {}. {} group={} upstream={} status={} skip={}",
+            marker,
+            idx + 1,
+            candidate.provider_endpoint_key,
+            candidate.preference_group,
+            candidate.upstream_base_url,
+            status,
+            skips
+        ));
+    }
+    lines
+}
+
+async fn fetch_runtime_explain(
+    admin_base_url: &str,
+    request: &RouteRequestContext,
+    session: Option<&str>,
+) -> anyhow::Result<RoutingExplainResponse> {
+    let endpoint = ControlPlaneEndpoint::new(
+        admin_base_url,
+        configured_local_admin_token_env().map(str::to_string),
+    )?;
+    ControlPlaneClient::new(endpoint)?
+        .routing_explain(request, session)
+        .await
+}
+
+pub(super) struct ExplainRouteArgs {
+    pub(super) codex: bool,
+    pub(super) claude: bool,
+    pub(super) model: Option<String>,
+    pub(super) effort: Option<String>,
+    pub(super) service_tier: Option<String>,
+    pub(super) session: Option<String>,
+    pub(super) port: Option<u16>,
+    pub(super) json: bool,
+}
+
+/// Asks the running proxy which route it would pick right now; falls back to a config-only
+/// preview (no health, cooldown, or session affinity) when the proxy cannot be reached.
+pub(super) async fn handle_explain_route(args: ExplainRouteArgs) -> CliResult<()> {
+    let service = resolve_service(args.codex, args.claude)
+        .await
+        .map_err(|e| CliError::Configuration(e.to_string()))?;
+    let request = build_route_request_context(
+        args.model,
+        args.service_tier,
+        args.effort,
+        None,
+        None,
+        Vec::new(),
+    )
+    .map_err(|e| CliError::Configuration(e.to_string()))?;
+    let session = clean_optional(args.session);
+    let port = args
+        .port
+        .unwrap_or_else(|| crate::cli_app::default_proxy_port_for_service(service));
+    let admin_base_url = crate::cli_app::daemon_admin_base_url_for_proxy_port(port);
+
+    let (explain, source) = match fetch_runtime_explain(
+        &admin_base_url,
+        &request,
+        session.as_deref(),
+    )
+    .await
+    {
+        Ok(explain) if explain.service_name == service => (explain, "runtime"),
+        Ok(explain) => {
+            return Err(CliError::Other(format!(
+                "proxy on port {port} serves '{}', not '{service}'",
+                explain.service_name
+            )));
+        }
+        Err(err) => {
+            eprintln!(
+                "Proxy admin API at {admin_base_url} is unavailable ({err}); showing a config-only preview without health, cooldown, or session affinity."
+            );
+            let document = load_config_document()
+                .await
+                .map_err(|e| CliError::Configuration(e.to_string()))?;
+            let (view, _) = select_service_route_config(&document, service);
+            let template = compile_route_plan_template_with_request(service, view, &request)
+                .map_err(|e| CliError::Configuration(e.to_string()))?;
+            let mut runtime = RoutePlanRuntimeState::default();
+            runtime.apply_provider_schedules(&template, crate::logging::now_ms());
+            let explain = build_routing_explain_response_with_request(
+                service, None, request, session, &template, &runtime,
+            );
+            (explain, "config_only")
+        }
+    };
+
+    if args.json {
+        let text = serde_json::to_string_pretty(&ExplainRouteOutput {
+            source,
+            explain: &explain,
+        })
+        .map_err(|e| CliError::Other(e.to_string()))?;
+        println!("{text}");
+    } else {
+        for line in explain_route_text_lines(&explain, source) {
+            println!("{line}");
+        }
+    }
+    Ok(())
+}
+
 pub async fn handle_route_view_cmd(cmd: RoutingCommand) -> CliResult<()> {
     match cmd {
         RoutingCommand::List { codex, claude } => {
@@ -550,4 +734,53 @@ mod tests {
             "  * 1. endpoint=codex/input/default group=0 provider=input path=[main > input]"
         ));
     }
+
+    #[test]
+    fn explain_route_text_reports_reason_and_candidate_eligibility() {
+        let view = ServiceRouteConfig {
+            providers: BTreeMap::from([
+                (
+                    "old".to_string(),
+                    provider("https://old.example/v1", &["gpt-4.1"]),
+                ),
+                (
+                    "new".to_string(),
+                    provider("https://new.example/v1", &["gpt-5"]),
+                ),
+            ]),
+            routing: Some(RouteGraphConfig::ordered_failover(vec![
+                "old".to_string(),
+                "new".to_string(),
+            ])),
+            ..ServiceRouteConfig::default()
+        };
+        let explain = build_config_only_explain(
+            "codex",
+            &view,
+            None,
+            RouteRequestContext {
+                model: Some("gpt-5".to_string()),
+                reasoning_effort: Some("high".to_string()),
+                ..RouteRequestContext::default()
+            },
+        )
+        .expect("config-only explain")
+        .compatibility;
+
+        let lines = explain_route_text_lines(&explain, "config_only");
+
+        assert_eq!(
+            lines,
+            vec![
+                "Source: config_only",
+                "Service: codex",
+                "Request: model=gpt-5 effort=high",
+                "Selected: codex/new/default provider=new upstream=https://new.example/v1",
+                "Reason: auto",
+                "Candidates:",
+                "    1. codex/old/default group=0 upstream=https://old.example/v1 status=unavailable(unsupported_model) skip=unsupported_model",
+                "  * 2. codex/new/default group=1 upstream=https://new.example/v1 status=available skip=-",
+            ]
+        );
+    }
 }
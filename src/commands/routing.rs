@@ -1,9 +1,13 @@
 use super::config_doc::{
-    ensure_routing, load_helper_config, ordered_provider_names, parse_cli_tags,
-    routing_exhausted_label, routing_policy_label, select_service_route_config,
-    select_service_route_config_mut,
+    ensure_routing, load_helper_config, load_helper_config_for_read, ordered_provider_names,
+    parse_cli_tags, per_service_json, routing_exhausted_label, routing_policy_label,
+    select_service_route_config, select_service_route_config_mut,
 };
 use super::route_view;
+use crate::active_history::{
+    ActiveChangeKind, ActiveChangeRecord, ActiveChangeSource, read_recent_active_changes,
+    record_active_change,
+};
 use crate::cli_types::{RoutingCommand, RoutingPolicy};
 use crate::config::{
     CURRENT_CONFIG_VERSION, PersistedRoutingProviderRef, PersistedRoutingSpec, RouteAffinityPolicy,
@@ -95,9 +99,7 @@ pub async fn handle_routing_cmd(cmd: RoutingCommand) -> CliResult<()> {
                 )
             };
 
-            let (_, service) = mutate_helper_config(move |config| {
-                let service = select_requested_service(config, requested_service);
-                let (view, _) = select_service_route_config_mut(config, service);
+            let service = mutate_service_routing(requested_service, move |view| {
                 let mut changed = false;
                 let current_routing = crate::config::effective_routing(view);
                 let current_entry = current_routing.entry_node();
@@ -179,10 +181,9 @@ pub async fn handle_routing_cmd(cmd: RoutingCommand) -> CliResult<()> {
                     next_prefer_tags,
                     next_on_exhausted,
                 );
-                Ok(service)
+                Ok(())
             })
-            .await
-            .map_err(|e| CliError::Configuration(e.to_string()))?;
+            .await?;
             let label = service_label(service);
             println!("{label} routing updated");
         }
@@ -196,9 +197,7 @@ pub async fn handle_routing_cmd(cmd: RoutingCommand) -> CliResult<()> {
                 .await
                 .map_err(|e| CliError::Configuration(e.to_string()))?;
             let target_label = target.clone();
-            let (_, service) = mutate_helper_config(move |config| {
-                let service = select_requested_service(config, requested_service);
-                let (view, _) = select_service_route_config_mut(config, service);
+            let service = mutate_service_routing(requested_service, move |view| {
                 ensure_routing_target_exists(view, target.as_str())?;
 
                 let order =
@@ -211,10 +210,9 @@ pub async fn handle_routing_cmd(cmd: RoutingCommand) -> CliResult<()> {
                     Vec::new(),
                     RouteExhaustedAction::Continue,
                 );
-                Ok(service)
+                Ok(())
             })
-            .await
-            .map_err(|e| CliError::Configuration(e.to_string()))?;
+            .await?;
             let label = service_label(service);
             println!("{label} routing pinned to target '{}'", target_label);
         }
@@ -227,9 +225,7 @@ pub async fn handle_routing_cmd(cmd: RoutingCommand) -> CliResult<()> {
             load_helper_config(codex, claude, "routing")
                 .await
                 .map_err(|e| CliError::Configuration(e.to_string()))?;
-            let (_, service) = mutate_helper_config(move |config| {
-                let service = select_requested_service(config, requested_service);
-                let (view, _) = select_service_route_config_mut(config, service);
+            let service = mutate_service_routing(requested_service, move |view| {
                 let order = normalize_complete_order(view, providers, None)?;
                 set_entry_routing(
                     view,
@@ -239,10 +235,9 @@ pub async fn handle_routing_cmd(cmd: RoutingCommand) -> CliResult<()> {
                     Vec::new(),
                     RouteExhaustedAction::Continue,
                 );
-                Ok(service)
+                Ok(())
             })
-            .await
-            .map_err(|e| CliError::Configuration(e.to_string()))?;
+            .await?;
             let label = service_label(service);
             println!("{label} routing order updated");
         }
@@ -259,9 +254,7 @@ pub async fn handle_routing_cmd(cmd: RoutingCommand) -> CliResult<()> {
                 .map_err(|e| CliError::Configuration(e.to_string()))?;
             let prefer_tag =
                 parse_cli_tags(&tags).map_err(|e| CliError::Configuration(e.to_string()))?;
-            let (_, service) = mutate_helper_config(move |config| {
-                let service = select_requested_service(config, requested_service);
-                let (view, _) = select_service_route_config_mut(config, service);
+            let service = mutate_service_routing(requested_service, move |view| {
                 let order = if order.is_empty() {
                     normalize_complete_order(view, Vec::new(), None)?
                 } else {
@@ -278,10 +271,9 @@ pub async fn handle_routing_cmd(cmd: RoutingCommand) -> CliResult<()> {
                     vec![prefer_tag],
                     next_on_exhausted,
                 );
-                Ok(service)
+                Ok(())
             })
-            .await
-            .map_err(|e| CliError::Configuration(e.to_string()))?;
+            .await?;
             let label = service_label(service);
             println!("{label} tag-preferred routing updated");
         }
@@ -290,9 +282,7 @@ pub async fn handle_routing_cmd(cmd: RoutingCommand) -> CliResult<()> {
             load_helper_config(codex, claude, "routing")
                 .await
                 .map_err(|e| CliError::Configuration(e.to_string()))?;
-            let (_, service) = mutate_helper_config(move |config| {
-                let service = select_requested_service(config, requested_service);
-                let (view, _) = select_service_route_config_mut(config, service);
+            let service = mutate_service_routing(requested_service, move |view| {
                 let current_routing = crate::config::effective_routing(view);
                 let Some(current_entry) = current_routing.entry_node() else {
                     anyhow::bail!("routing clear-target requires an existing source routing block");
@@ -300,10 +290,9 @@ pub async fn handle_routing_cmd(cmd: RoutingCommand) -> CliResult<()> {
                 let next_order =
                     normalize_complete_order(view, current_entry.children.clone(), None)?;
                 view.ensure_routing_mut().clear_entry_target(next_order);
-                Ok(service)
+                Ok(())
             })
-            .await
-            .map_err(|e| CliError::Configuration(e.to_string()))?;
+            .await?;
             let label = service_label(service);
             println!("{label} routing target cleared");
         }
@@ -312,6 +301,141 @@ pub async fn handle_routing_cmd(cmd: RoutingCommand) -> CliResult<()> {
     Ok(())
 }
 
+/// Applies `mutate` to the selected service's routing and records any change to the
+/// manual-sticky pin in the active-change history.
+async fn mutate_service_routing(
+    requested_service: Option<&'static str>,
+    mutate: impl FnOnce(&mut ServiceRouteConfig) -> anyhow::Result<()>,
+) -> CliResult<&'static str> {
+    let (_, (service, old_pin, new_pin)) = mutate_helper_config(move |config| {
+        let service = select_requested_service(config, requested_service);
+        let (view, _) = select_service_route_config_mut(config, service);
+        let old_pin = pinned_routing_target(view);
+        mutate(view)?;
+        Ok((service, old_pin, pinned_routing_target(view)))
+    })
+    .await
+    .map_err(|e| CliError::Configuration(e.to_string()))?;
+    let record = ActiveChangeRecord::new(
+        service,
+        ActiveChangeKind::Pin,
+        old_pin,
+        new_pin,
+        ActiveChangeSource::Cli,
+    );
+    if let Err(err) = record_active_change(&record) {
+        eprintln!("warning: failed to record active-change history: {err}");
+    }
+    Ok(service)
+}
+
+fn pinned_routing_target(view: &ServiceRouteConfig) -> Option<String> {
+    let routing = crate::config::effective_routing(view);
+    routing
+        .entry_node()
+        .filter(|node| matches!(node.strategy, RouteStrategy::ManualSticky))
+        .and_then(|node| node.target.clone())
+}
+
+#[derive(Debug, Serialize)]
+struct ActivePayload {
+    service: String,
+    pinned_target: Option<String>,
+    auto_active_by_health: bool,
+}
+
+fn active_payload(cfg: &crate::config::HelperConfig, service: &str) -> ActivePayload {
+    let (view, _) = select_service_route_config(cfg, service);
+    ActivePayload {
+        service: service.to_string(),
+        pinned_target: pinned_routing_target(view),
+        auto_active_by_health: crate::config::effective_routing(view).auto_active_by_health,
+    }
+}
+
+pub(super) async fn handle_config_active(
+    codex: bool,
+    claude: bool,
+    both: bool,
+    history: bool,
+    limit: usize,
+    json: bool,
+) -> CliResult<()> {
+    let (cfg, services) = load_helper_config_for_read(codex, claude, both, json, "config active")
+        .await
+        .map_err(|e| CliError::Configuration(e.to_string()))?;
+    let sectioned = services.len() > 1;
+
+    if json {
+        let text = if history {
+            per_service_json(
+                services
+                    .iter()
+                    .map(|(service, _)| {
+                        (*service, read_recent_active_changes(limit, Some(service)))
+                    })
+                    .collect(),
+            )
+        } else {
+            per_service_json(
+                services
+                    .iter()
+                    .map(|(service, _)| (*service, active_payload(&cfg, service)))
+                    .collect(),
+            )
+        }
+        .map_err(|e| CliError::Configuration(e.to_string()))?;
+        println!("{text}");
+        return Ok(());
+    }
+
+    for (index, (service, label)) in services.iter().enumerate() {
+        if !history {
+            let payload = active_payload(&cfg, service);
+            match payload.pinned_target {
+                Some(target) => println!("{label} active: pinned to '{target}'"),
+                None if payload.auto_active_by_health => println!(
+                    "{label} active: auto by health (highest-priority healthy target; `config explain-route` shows the live pick)"
+                ),
+                None => println!("{label} active: auto (routing policy order)"),
+            }
+            continue;
+        }
+        let records = read_recent_active_changes(limit, Some(service));
+        if sectioned && index > 0 {
+            println!();
+        }
+        if records.is_empty() {
+            println!("{label}: no active changes recorded");
+            continue;
+        }
+        if sectioned {
+            println!("{label}:");
+        }
+        for line in active_history_text_lines(&records) {
+            println!("{line}");
+        }
+    }
+    Ok(())
+}
+
+fn active_history_text_lines(records: &[ActiveChangeRecord]) -> Vec<String> {
+    records
+        .iter()
+        .map(|record| {
+            let ts = std::time::UNIX_EPOCH + std::time::Duration::from_millis(record.ts_ms);
+            format!(
+                "{}  {:<4} {:<22} {} -> {}",
+                humantime::format_rfc3339_seconds(ts),
+                record.source.as_str(),
+                record.kind.as_str(),
+                record.old.as_deref().unwrap_or("<auto>"),
+                record.new.as_deref().unwrap_or("<auto>"),
+            )
+        })
+        .collect()
+}
+
 fn requested_service(codex: bool, claude: bool) -> CliResult<Option<&'static str>> {
     match (codex, claude) {
         (true, true) => Err(CliError::Configuration(
@@ -364,6 +488,11 @@ fn persisted_routing_spec_from_view(view: &ServiceRouteConfig) -> PersistedRouti
         scheduling_preset: routing.scheduling_preset,
         fallback_ttl_ms: routing.fallback_ttl_ms,
         reprobe_preferred_after_ms: routing.reprobe_preferred_after_ms,
+        auto_active_by_health: routing.auto_active_by_health,
+        prefer_cheapest: routing.prefer_cheapest,
+        fallback_alert_threshold_pct: routing.fallback_alert_threshold_pct,
+        fallback_alert_window_secs: routing.fallback_alert_window_secs,
+        primary_sticky_ms: routing.primary_sticky_ms,
         routes: routing.routes.clone(),
         policy: entry_node
             .map(|node| node.strategy)
@@ -406,6 +535,12 @@ fn print_routing_text(label: &str, view: &ServiceRouteConfig) {
     );
     println!("Scheduling preset: {}", scheduling_preset.as_str());
     println!("Routing target: {}", target.as_deref().unwrap_or("<none>"));
+    if routing.auto_active_by_health {
+        println!("Auto-active by health: on");
+    }
+    if routing.prefer_cheapest {
+        println!("Prefer cheapest: on");
+    }
     let order = if order.is_empty() {
         "<provider key order>".to_string()
     } else {
@@ -702,6 +837,7 @@ fn set_entry_routing(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::commands::test_support::{ScopedEnv, TempTestDir, env_lock};
     use crate::config::{
         ProviderConfig, ProviderEndpointConfig, RouteGraphConfig, RouteNodeConfig,
     };
@@ -838,4 +974,73 @@ mod tests {
         )
         .expect("route target validates");
     }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn routing_pin_and_clear_record_active_history() {
+        let _env_lock = env_lock().await;
+        let helper_home = TempTestDir::new("codex-helper-cli-test-active-history");
+        let mut scoped_env = ScopedEnv::default();
+        unsafe {
+            scoped_env.set_path("CODEX_HELPER_HOME", helper_home.path());
+        }
+        mutate_helper_config(|config| {
+            for name in ["alpha", "beta"] {
+                config.codex.providers.insert(
+                    name.to_string(),
+                    ProviderConfig {
+                        base_url: Some(format!("https://{name}.example/v1")),
+                        ..ProviderConfig::default()
+                    },
+                );
+            }
+            Ok(())
+        })
+        .await
+        .expect("seed config");
+
+        for cmd in [
+            RoutingCommand::Pin {
+                target: "alpha".to_string(),
+                codex: true,
+                claude: false,
+            },
+            RoutingCommand::Pin {
+                target: "alpha".to_string(),
+                codex: true,
+                claude: false,
+            },
+            RoutingCommand::Pin {
+                target: "beta".to_string(),
+                codex: true,
+                claude: false,
+            },
+            RoutingCommand::ClearTarget {
+                codex: true,
+                claude: false,
+            },
+        ] {
+            handle_routing_cmd(cmd).await.expect("routing command");
+        }
+
+        let records = read_recent_active_changes(10, Some("codex"));
+        let transitions = records
+            .iter()
+            .map(|record| (record.old.as_deref(), record.new.as_deref()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            transitions,
+            vec![
+                (None, Some("alpha")),
+                (Some("alpha"), Some("beta")),
+                (Some("beta"), None),
+            ]
+        );
+        assert!(
+            records
+                .iter()
+                .all(|record| record.source == ActiveChangeSource::Cli
+                    && record.kind == ActiveChangeKind::Pin)
+        );
+        assert!(active_history_text_lines(&records)[2].ends_with("beta -> <auto>"));
+    }
 }
@@ -0,0 +1,359 @@
+//! `serve --detach` / `--stop` / `--status`: a resident proxy started in the background and
+//! tracked through a PID file next to the runtime owner markers.
+
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+use std::process::{Command as ProcessCommand, Stdio};
+use std::time::{Duration, Instant};
+
+use owo_colors::OwoColorize;
+
+use codex_helper_core::local_log_store::RUNTIME_LOG_FILE_NAME;
+
+use crate::cli_app::{
+    daemon_status_is_reachable, default_proxy_port_for_service, print_daemon_status,
+    read_local_operator_model, stop_daemon,
+};
+use crate::runtime_manager::{read_owner_marker_best_effort, runtime_run_dir};
+use crate::{CliError, CliResult};
+
+/// Set on the detached child so it writes tracing output to `logs/runtime.log` instead of the
+/// closed stderr.
+pub(crate) const DETACHED_SERVE_ENV: &str = "CODEX_HELPER_INTERNAL_DETACHED_SERVE";
+
+const DETACH_READY_TIMEOUT: Duration = Duration::from_secs(15);
+const DETACH_STOP_TIMEOUT: Duration = Duration::from_secs(10);
+const DETACH_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+pub(crate) struct DetachedServeOptions {
+    pub service_name: &'static str,
+    pub host: IpAddr,
+    pub port: u16,
+    pub idle_timeout: Option<String>,
+    pub warmup: bool,
+    pub env_file: Option<PathBuf>,
+    pub unix_socket: Option<PathBuf>,
+}
+
+pub(crate) async fn handle_serve_detach(options: DetachedServeOptions) -> CliResult<()> {
+    let service_name = options.service_name;
+    let port = options.port;
+    let pid_path = pid_file_path(service_name, port);
+    if let Some(pid) = read_pid_file(&pid_path) {
+        if detached_proxy_is_running(service_name, port, pid) {
+            return Err(CliError::Other(format!(
+                "a detached {service_name} proxy is already running on port {port} (PID {pid}); stop it with `{}`",
+                stop_command(service_name, port)
+            )));
+        }
+        remove_pid_file(&pid_path);
+    }
+
+    let exe = std::env::current_exe()
+        .map_err(|err| CliError::Other(format!("failed to locate current executable: {err}")))?;
+    let args = detached_serve_args(&options);
+    let mut command = ProcessCommand::new(&exe);
+    command
+        .args(&args)
+        .env(DETACHED_SERVE_ENV, "1")
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    #[cfg(unix)]
+    {
+        // A new session without a controlling terminal keeps terminal Ctrl-C and hangup away
+        // from the proxy once this parent exits and it is reparented.
+        use std::os::unix::process::CommandExt;
+        // SAFETY: `setsid` is async-signal-safe and touches no parent memory after the fork.
+        unsafe {
+            command.pre_exec(|| {
+                if libc::setsid() == -1 {
+                    return Err(std::io::Error::last_os_error());
+                }
+                Ok(())
+            });
+        }
+    }
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        use windows_sys::Win32::System::Threading::{CREATE_NEW_PROCESS_GROUP, DETACHED_PROCESS};
+        command.creation_flags(DETACHED_PROCESS | CREATE_NEW_PROCESS_GROUP);
+    }
+    let mut child = command.spawn().map_err(|err| {
+        CliError::Other(format!(
+            "failed to spawn detached proxy {:?} {:?}: {err}",
+            exe, args
+        ))
+    })?;
+    let pid = child.id();
+    write_pid_file(&pid_path, pid)?;
+
+    let log_path = crate::config::proxy_home_dir()
+        .join("logs")
+        .join(RUNTIME_LOG_FILE_NAME);
+    let deadline = Instant::now() + DETACH_READY_TIMEOUT;
+    loop {
+        if let Some(status) = child
+            .try_wait()
+            .map_err(|err| CliError::Other(format!("wait for detached proxy: {err}")))?
+        {
+            remove_pid_file(&pid_path);
+            return Err(CliError::Other(format!(
+                "detached {service_name} proxy exited during startup ({status}); see {}",
+                log_path.display()
+            )));
+        }
+        let owned_by_child = read_owner_marker_best_effort(service_name, port)
+            .is_some_and(|marker| marker.pid == pid);
+        if owned_by_child
+            && daemon_status_is_reachable(
+                read_local_operator_model(service_name, port).await?.status,
+            )
+        {
+            break;
+        }
+        if Instant::now() >= deadline {
+            return Err(CliError::Other(format!(
+                "detached {service_name} proxy (PID {pid}) did not become ready within {}s; see {} or stop it with `{}`",
+                DETACH_READY_TIMEOUT.as_secs(),
+                log_path.display(),
+                stop_command(service_name, port)
+            )));
+        }
+        tokio::time::sleep(DETACH_POLL_INTERVAL).await;
+    }
+
+    println!(
+        "{} {} proxy running in the background on http://{}:{} (PID {})",
+        "[OK]".green(),
+        service_name,
+        options.host,
+        port,
+        pid
+    );
+    println!("  logs: {}", log_path.display());
+    println!("  stop: {}", stop_command(service_name, port));
+    Ok(())
+}
+
+pub(crate) async fn handle_serve_stop(service_name: &'static str, port: u16) -> CliResult<()> {
+    let pid_path = pid_file_path(service_name, port);
+    let Some(pid) = read_pid_file(&pid_path) else {
+        return Err(CliError::Other(format!(
+            "no detached {service_name} proxy is recorded at {}; use `codex-helper daemon stop` for a resident proxy started another way",
+            pid_path.display()
+        )));
+    };
+    if !detached_proxy_is_running(service_name, port, pid) {
+        remove_pid_file(&pid_path);
+        println!(
+            "Detached {service_name} proxy (PID {pid}) is no longer running; removed stale {}",
+            pid_path.display()
+        );
+        return Ok(());
+    }
+
+    stop_daemon(service_name, port).await?;
+    let deadline = Instant::now() + DETACH_STOP_TIMEOUT;
+    while process_is_running(pid) {
+        if Instant::now() >= deadline {
+            return Err(CliError::Other(format!(
+                "detached {service_name} proxy (PID {pid}) is still running {}s after the shutdown request",
+                DETACH_STOP_TIMEOUT.as_secs()
+            )));
+        }
+        tokio::time::sleep(DETACH_POLL_INTERVAL).await;
+    }
+    remove_pid_file(&pid_path);
+    println!(
+        "{} detached {} proxy (PID {}) exited",
+        "[OK]".green(),
+        service_name,
+        pid
+    );
+    Ok(())
+}
+
+pub(crate) async fn handle_serve_status(service_name: &'static str, port: u16) -> CliResult<()> {
+    print_daemon_status(service_name, port, false).await?;
+    let pid_path = pid_file_path(service_name, port);
+    match read_pid_file(&pid_path) {
+        Some(pid) if detached_proxy_is_running(service_name, port, pid) => {
+            println!("  detached: PID {pid} ({})", pid_path.display());
+        }
+        Some(pid) => {
+            remove_pid_file(&pid_path);
+            println!(
+                "  detached: PID {pid} is gone; removed stale {}",
+                pid_path.display()
+            );
+        }
+        None => println!("  detached: <none>"),
+    }
+    Ok(())
+}
+
+fn stop_command(service_name: &str, port: u16) -> String {
+    if port == default_proxy_port_for_service(service_name) {
+        format!("codex-helper serve --{service_name} --stop")
+    } else {
+        format!("codex-helper serve --{service_name} --port {port} --stop")
+    }
+}
+
+fn pid_file_path(service_name: &str, port: u16) -> PathBuf {
+    runtime_run_dir().join(format!("{service_name}-{port}.pid"))
+}
+
+fn read_pid_file(path: &Path) -> Option<u32> {
+    std::fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+fn write_pid_file(path: &Path, pid: u32) -> CliResult<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|err| {
+            CliError::Other(format!("create runtime run dir {}: {err}", dir.display()))
+        })?;
+    }
+    std::fs::write(path, format!("{pid}\n"))
+        .map_err(|err| CliError::Other(format!("write {}: {err}", path.display())))
+}
+
+fn remove_pid_file(path: &Path) {
+    if let Err(err) = std::fs::remove_file(path)
+        && err.kind() != std::io::ErrorKind::NotFound
+    {
+        tracing::warn!("failed to remove PID file {}: {}", path.display(), err);
+    }
+}
+
+/// Arguments for the detached child: a plain resident `serve` without the terminal dashboard.
+fn detached_serve_args(options: &DetachedServeOptions) -> Vec<String> {
+    let mut args = vec![
+        "serve".to_string(),
+        format!("--{}", options.service_name),
+        "--host".to_string(),
+        options.host.to_string(),
+        "--port".to_string(),
+        options.port.to_string(),
+        "--resident".to_string(),
+        "--no-tui".to_string(),
+    ];
+    if let Some(idle_timeout) = options.idle_timeout.as_ref() {
+        args.extend(["--idle-timeout".to_string(), idle_timeout.clone()]);
+    }
+    if options.warmup {
+        args.push("--warmup".to_string());
+    }
+    if let Some(env_file) = options.env_file.as_ref() {
+        args.extend(["--env-file".to_string(), env_file.display().to_string()]);
+    }
+    if let Some(unix_socket) = options.unix_socket.as_ref() {
+        args.extend([
+            "--unix-socket".to_string(),
+            unix_socket.display().to_string(),
+        ]);
+    }
+    args
+}
+
+/// A live PID alone may belong to an unrelated process that reused it; the recorded proxy is
+/// running only while the runtime owner marker for this service and port names the same PID.
+fn detached_proxy_is_running(service_name: &str, port: u16, pid: u32) -> bool {
+    read_owner_marker_best_effort(service_name, port).is_some_and(|marker| marker.pid == pid)
+        && process_is_running(pid)
+}
+
+#[cfg(unix)]
+fn process_is_running(pid: u32) -> bool {
+    ProcessCommand::new("kill")
+        .args(["-0", &pid.to_string()])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+#[cfg(windows)]
+fn process_is_running(pid: u32) -> bool {
+    let filter = format!("PID eq {pid}");
+    ProcessCommand::new("tasklist")
+        .args(["/FI", filter.as_str(), "/FO", "CSV", "/NH"])
+        .output()
+        .is_ok_and(|output| String::from_utf8_lossy(&output.stdout).contains(&format!("\"{pid}\"")))
+}
+
+#[cfg(not(any(unix, windows)))]
+fn process_is_running(_pid: u32) -> bool {
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detached_serve_args_run_a_resident_proxy_without_the_dashboard() {
+        let args = detached_serve_args(&DetachedServeOptions {
+            service_name: "claude",
+            host: IpAddr::from([127, 0, 0, 1]),
+            port: 3210,
+            idle_timeout: Some("30m".to_string()),
+            warmup: true,
+            env_file: Some(PathBuf::from("/etc/codex-helper.env")),
+            unix_socket: None,
+        });
+
+        assert_eq!(
+            args,
+            [
+                "serve",
+                "--claude",
+                "--host",
+                "127.0.0.1",
+                "--port",
+                "3210",
+                "--resident",
+                "--no-tui",
+                "--idle-timeout",
+                "30m",
+                "--warmup",
+                "--env-file",
+                "/etc/codex-helper.env",
+            ]
+        );
+    }
+
+    #[test]
+    fn pid_files_round_trip_and_detect_a_stale_process() {
+        let dir = std::env::temp_dir().join(format!(
+            "codex-helper-serve-detach-{}",
+            uuid::Uuid::new_v4()
+        ));
+        let path = dir.join("codex-3211.pid");
+        assert_eq!(read_pid_file(&path), None);
+
+        write_pid_file(&path, std::process::id()).expect("write pid file");
+        assert_eq!(read_pid_file(&path), Some(std::process::id()));
+        assert!(process_is_running(std::process::id()));
+        assert!(
+            !detached_proxy_is_running("codex", 3211, std::process::id()),
+            "a live PID that no owner marker names is not the detached proxy"
+        );
+
+        let mut exited = ProcessCommand::new(std::env::current_exe().expect("test exe"))
+            .arg("--list")
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .expect("spawn short-lived process");
+        let exited_pid = exited.id();
+        exited.wait().expect("wait");
+        assert!(!process_is_running(exited_pid));
+
+        remove_pid_file(&path);
+        assert_eq!(read_pid_file(&path), None);
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,92 @@
+//! `serve --print-config`: load the config exactly as `serve` would, report it, and exit.
+
+use serde::Serialize;
+
+use super::term_style::{Tone, configured_theme, paint};
+use crate::config::{ServiceKind, load_config_with_source, redacted_helper_config};
+use crate::doctor::{EnvVarReference, env_var_references};
+use crate::model_routing::effort_override_warnings;
+use crate::runtime_host::validate_service_has_upstream;
+use crate::{CliError, CliResult};
+
+#[derive(Debug, Serialize)]
+struct ServePreflightView {
+    service: &'static str,
+    /// Effective configuration after migration and defaults, with secrets redacted.
+    config: toml::Value,
+    env: Vec<EnvVarReference>,
+    warnings: Vec<String>,
+    /// Why `serve` would refuse to start; `None` when it would start.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    startup_error: Option<String>,
+}
+
+pub async fn handle_serve_print_config(service_name: &'static str, json: bool) -> CliResult<()> {
+    let loaded = load_config_with_source()
+        .await
+        .map_err(|e| CliError::Configuration(e.to_string()))?;
+    let service = match service_name {
+        "claude" => ServiceKind::Claude,
+        _ => ServiceKind::Codex,
+    };
+    let view = ServePreflightView {
+        service: service_name,
+        config: redacted_helper_config(&loaded.source)
+            .map_err(|e| CliError::Configuration(format!("{e:#}")))?,
+        env: env_var_references(&loaded.source, &[service]),
+        warnings: effort_override_warnings(&loaded.source),
+        startup_error: validate_service_has_upstream(service_name, &loaded.source)
+            .err()
+            .map(|e| format!("{e:#}")),
+    };
+
+    if json {
+        let text = serde_json::to_string_pretty(&view)
+            .map_err(|e| CliError::Configuration(e.to_string()))?;
+        println!("{text}");
+    } else {
+        print_serve_preflight(&view).await?;
+    }
+    match view.startup_error {
+        Some(error) => Err(CliError::Configuration(error)),
+        None => Ok(()),
+    }
+}
+
+async fn print_serve_preflight(view: &ServePreflightView) -> CliResult<()> {
+    let body =
+        toml::to_string_pretty(&view.config).map_err(|e| CliError::Configuration(e.to_string()))?;
+    println!("# effective {} config (secrets redacted)", view.service);
+    println!("{body}");
+
+    let theme = configured_theme().await;
+    if view.env.is_empty() {
+        println!("env: no provider references an environment variable");
+    } else {
+        println!("env:");
+        for reference in &view.env {
+            let state = if reference.set {
+                paint(theme, Tone::Good, "set")
+            } else if reference.active {
+                paint(theme, Tone::Bad, "missing")
+            } else {
+                paint(theme, Tone::Warn, "missing")
+            };
+            let disabled = if reference.active { "" } else { " (disabled)" };
+            println!(
+                "  {state} {}/{} {}={}{disabled}",
+                reference.service, reference.provider_id, reference.field, reference.variable
+            );
+        }
+    }
+
+    if view.warnings.is_empty() {
+        println!("warnings: none");
+    } else {
+        println!("warnings:");
+        for warning in &view.warnings {
+            println!("  {} {warning}", paint(theme, Tone::Warn, "warning"));
+        }
+    }
+    Ok(())
+}
@@ -1,10 +1,21 @@
+use super::session_context::{ContextExportOptions, session_context_export};
+use crate::request_ledger::{
+    RequestLedger, RequestLogFilters, RequestUsageAggregate, RequestUsageSummaryGroup,
+};
+use crate::runtime_store::RuntimeStoreReader;
 use crate::sessions::{
-    SessionSummary, find_codex_session_file_by_id, find_codex_sessions_for_current_dir,
-    find_codex_sessions_for_dir, find_recent_codex_sessions, infer_project_root_from_cwd,
-    read_codex_session_meta, read_codex_session_transcript, search_codex_sessions_for_current_dir,
+    RecentSession, SessionSummary, SessionTranscriptMessage, estimate_transcript_tokens,
+    find_all_codex_sessions_for_dir, find_codex_session_file_by_id,
+    find_codex_sessions_for_current_dir, find_codex_sessions_for_dir,
+    find_codex_sessions_page_for_dir, find_recent_codex_sessions, infer_project_root_from_cwd,
+    read_codex_session_meta, read_codex_session_transcript, scan_recent_codex_sessions,
+    search_codex_sessions_all_projects, search_codex_sessions_for_current_dir,
     search_codex_sessions_for_dir,
 };
 use crate::{CliResult, RecentFormat, RecentTerminal, SessionCommand};
+use codex_helper_core::usage_format::tokens_short;
+use std::io::{self, Write};
+use std::ops::ControlFlow;
 
 fn basename_lower(path: &str) -> String {
     std::path::Path::new(path)
@@ -18,10 +29,69 @@ fn render_resume_cmd(template: &str, session_id: &str) -> String {
     template.replace("{id}", session_id)
 }
 
-fn print_recent_sessions(
-    format: RecentFormat,
-    rows: &[(String, String, Option<String>, u64)],
-) -> CliResult<()> {
+type RecentSessionRow = (String, String, Option<String>, u64);
+
+fn recent_session_row(session: RecentSession, raw_cwd: bool) -> RecentSessionRow {
+    let cwd = session.cwd.as_deref().unwrap_or("-");
+    let root = if raw_cwd {
+        cwd.to_string()
+    } else {
+        infer_project_root_from_cwd(cwd)
+    };
+    (root, session.id, session.cwd, session.mtime_ms)
+}
+
+/// One `session recent --format json` element; `ndjson` writes the same object per line.
+#[derive(serde::Serialize)]
+struct RecentSessionJson<'a> {
+    project_root: &'a str,
+    session_id: &'a str,
+    cwd: Option<&'a str>,
+    mtime_ms: u64,
+}
+
+impl<'a> From<&'a RecentSessionRow> for RecentSessionJson<'a> {
+    fn from((root, id, cwd, mtime_ms): &'a RecentSessionRow) -> Self {
+        Self {
+            project_root: root.as_str(),
+            session_id: id.as_str(),
+            cwd: cwd.as_deref(),
+            mtime_ms: *mtime_ms,
+        }
+    }
+}
+
+fn write_recent_session_ndjson(out: &mut impl Write, row: &RecentSessionRow) -> io::Result<()> {
+    serde_json::to_writer(&mut *out, &RecentSessionJson::from(row))?;
+    out.write_all(b"\n")?;
+    out.flush()
+}
+
+/// Streams `ndjson` rows while the session directories are scanned, so `head` on a huge
+/// history returns as soon as it has enough lines. A closed stdout ends the scan quietly.
+async fn stream_recent_sessions_ndjson(
+    since: std::time::Duration,
+    limit: usize,
+    raw_cwd: bool,
+) -> CliResult<Vec<RecentSessionRow>> {
+    let mut stdout = io::stdout();
+    let mut rows = Vec::new();
+    scan_recent_codex_sessions(since, limit, |session| {
+        let row = recent_session_row(session, raw_cwd);
+        match write_recent_session_ndjson(&mut stdout, &row) {
+            Ok(()) => {
+                rows.push(row);
+                Ok(ControlFlow::Continue(()))
+            }
+            Err(error) if error.kind() == io::ErrorKind::BrokenPipe => Ok(ControlFlow::Break(())),
+            Err(error) => Err(error.into()),
+        }
+    })
+    .await?;
+    Ok(rows)
+}
+
+fn print_recent_sessions(format: RecentFormat, rows: &[RecentSessionRow]) -> CliResult<()> {
     match format {
         RecentFormat::Text => {
             for (root, id, _cwd, _mtime_ms) in rows {
@@ -35,29 +105,129 @@ fn print_recent_sessions(
             }
         }
         RecentFormat::Json => {
-            #[derive(serde::Serialize)]
-            struct Row<'a> {
-                project_root: &'a str,
-                session_id: &'a str,
-                cwd: Option<&'a str>,
-                mtime_ms: u64,
-            }
-            let json_rows: Vec<Row<'_>> = rows
-                .iter()
-                .map(|(root, id, cwd, mtime_ms)| Row {
-                    project_root: root.as_str(),
-                    session_id: id.as_str(),
-                    cwd: cwd.as_deref(),
-                    mtime_ms: *mtime_ms,
-                })
-                .collect();
+            let json_rows: Vec<RecentSessionJson<'_>> =
+                rows.iter().map(RecentSessionJson::from).collect();
             let s = serde_json::to_string_pretty(&json_rows).unwrap_or_else(|_| "[]".to_string());
             println!("{s}");
         }
+        RecentFormat::Ndjson => {
+            let mut stdout = io::stdout();
+            for row in rows {
+                write_recent_session_ndjson(&mut stdout, row).map_err(anyhow::Error::from)?;
+            }
+        }
     }
     Ok(())
 }
 
+fn print_session_list_json(sessions: &[SessionSummary], offset: usize, truncate: Option<usize>) {
+    let rows = session_list_json_rows(sessions, offset, truncate);
+    let s = serde_json::to_string_pretty(&rows).unwrap_or_else(|_| "[]".to_string());
+    println!("{s}");
+}
+
+fn session_list_json_rows(
+    sessions: &[SessionSummary],
+    offset: usize,
+    truncate: Option<usize>,
+) -> Vec<serde_json::Value> {
+    sessions
+        .iter()
+        .enumerate()
+        .map(|(index, s)| {
+            serde_json::json!({
+                "position": offset + index,
+                "session_id": s.id,
+                "path": s.path,
+                "cwd": s.cwd,
+                "created_at": s.created_at,
+                "updated_at": s.updated_at,
+                "last_response_at": s.last_response_at,
+                "rounds": s.rounds,
+                "user_turns": s.user_turns,
+                "assistant_turns": s.assistant_turns,
+                "first_prompt": session_prompt_preview(s.first_user_message.as_deref(), truncate),
+            })
+        })
+        .collect()
+}
+
+fn session_last_json(
+    session: Option<&SessionSummary>,
+    usage: Option<&RequestUsageAggregate>,
+    estimated_tokens: Option<u64>,
+) -> serde_json::Value {
+    let Some(s) = session else {
+        return serde_json::Value::Null;
+    };
+    let mut value = serde_json::json!({
+        "session_id": s.id,
+        "path": s.path,
+        "cwd": s.cwd,
+        "root": s.cwd.as_deref().map(infer_project_root_from_cwd),
+        "created_at": s.created_at,
+        "updated_at": s.updated_at,
+        "last_response_at": s.last_response_at,
+        "rounds": s.rounds,
+        "user_turns": s.user_turns,
+        "assistant_turns": s.assistant_turns,
+        "first_prompt": session_prompt_preview(s.first_user_message.as_deref(), None),
+    });
+    if let Some(usage) = usage {
+        value["usage"] = serde_json::json!({
+            "requests": usage.requests,
+            "input_tokens": usage.input_tokens,
+            "output_tokens": usage.output_tokens,
+            "reasoning_tokens": usage.reasoning_tokens,
+            "cache_read_input_tokens": usage.cache_read_input_tokens,
+            "cache_creation_input_tokens": usage.cache_creation_input_tokens,
+            "total_tokens": usage.total_tokens,
+        });
+    } else if let Some(estimated_tokens) = estimated_tokens {
+        value["estimated_tokens"] = serde_json::json!(estimated_tokens);
+    }
+    value
+}
+
+/// Transcript-size token estimate for sessions the request log knows nothing about (for example
+/// sessions older than the runtime store). Best-effort: unreadable transcripts yield `None`.
+async fn session_estimated_tokens(session: &SessionSummary) -> Option<u64> {
+    let messages = read_codex_session_transcript(&session.path, None)
+        .await
+        .ok()?;
+    Some(estimate_transcript_tokens(&messages))
+}
+
+fn estimate_scope(tail: Option<usize>, shown: usize) -> String {
+    match tail {
+        Some(_) => format!("estimate, chars/4 over the last {shown} message(s)"),
+        None => "estimate, chars/4 over the whole transcript".to_string(),
+    }
+}
+
+/// Token totals of the committed Codex requests tagged with this session; `None` when the runtime
+/// store is unavailable or has no request for it.
+fn session_usage_totals(session_id: &str) -> Option<RequestUsageAggregate> {
+    let reader = RuntimeStoreReader::open_default().ok()?;
+    let summary = RequestLedger::new(&reader)
+        .summarize(
+            RequestUsageSummaryGroup::Session,
+            &RequestLogFilters {
+                service: Some("codex".to_string()),
+                session: Some(session_id.to_string()),
+                ..RequestLogFilters::default()
+            },
+            usize::MAX,
+        )
+        .ok()?;
+    summary
+        .rows
+        .into_iter()
+        .find(|row| row.group_value == session_id)
+        .map(|row| row.aggregate)
+        .filter(|aggregate| aggregate.requests > 0)
+}
+
 fn session_prompt_preview(first_user_message: Option<&str>, truncate: Option<usize>) -> String {
     let preview_raw = first_user_message.unwrap_or("").replace('\n', " ");
     if let Some(n) = truncate {
@@ -127,22 +297,15 @@ fn spawn_windows_terminal_wt(
     Ok(())
 }
 
-fn spawn_wezterm(
-    workdir: &str,
+/// `shell` plus the arguments that run `command` in it, for terminals that take an argv.
+fn shell_invocation(
+    program: &str,
     shell: &str,
     keep_open: bool,
     command: &str,
-    dry_run: bool,
-) -> CliResult<()> {
+) -> CliResult<Vec<String>> {
     let shell_base = basename_lower(shell);
-    let mut args: Vec<String> = vec![
-        "start".to_string(),
-        "--cwd".to_string(),
-        workdir.to_string(),
-        "--".to_string(),
-        shell.to_string(),
-    ];
-
+    let mut args = vec![shell.to_string()];
     if shell_base.contains("pwsh") || shell_base.contains("powershell") {
         if keep_open {
             args.push("-NoExit".to_string());
@@ -158,44 +321,391 @@ fn spawn_wezterm(
         }
     } else {
         return Err(crate::CliError::Other(format!(
-            "unsupported shell for wezterm: {} (supported: pwsh/powershell/sh/bash/zsh)",
-            shell
+            "unsupported shell for {program}: {shell} (supported: pwsh/powershell/sh/bash/zsh)"
         )));
     }
+    Ok(args)
+}
+
+/// Opens a new window of an argv-driven terminal: wezterm, gnome-terminal or konsole.
+fn spawn_argv_terminal(
+    term: RecentTerminal,
+    workdir: &str,
+    shell: &str,
+    keep_open: bool,
+    command: &str,
+    dry_run: bool,
+) -> CliResult<()> {
+    let (program, mut args) = match term {
+        RecentTerminal::Wezterm => (
+            "wezterm",
+            vec![
+                "start".to_string(),
+                "--cwd".to_string(),
+                workdir.to_string(),
+                "--".to_string(),
+            ],
+        ),
+        RecentTerminal::GnomeTerminal => (
+            "gnome-terminal",
+            vec![format!("--working-directory={workdir}"), "--".to_string()],
+        ),
+        RecentTerminal::Konsole => (
+            "konsole",
+            vec![
+                "--workdir".to_string(),
+                workdir.to_string(),
+                "-e".to_string(),
+            ],
+        ),
+        RecentTerminal::Wt | RecentTerminal::Terminal | RecentTerminal::Iterm => {
+            unreachable!("{term:?} is not an argv terminal")
+        }
+    };
+    args.extend(shell_invocation(program, shell, keep_open, command)?);
 
     if dry_run {
-        spawn_cmd_dry_run("wezterm", "wezterm", &args);
+        spawn_cmd_dry_run(program, program, &args);
         return Ok(());
     }
 
-    std::process::Command::new("wezterm")
+    std::process::Command::new(program)
         .args(&args)
         .spawn()
         .map_err(|e| {
             crate::CliError::Other(format!(
-                "failed to spawn wezterm; is WezTerm installed and `wezterm` in PATH? ({e})"
+                "failed to spawn {program}; is it installed and `{program}` in PATH? ({e})"
             ))
         })?;
     Ok(())
 }
 
+fn posix_single_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+fn applescript_string(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// AppleScript that opens a new Terminal.app or iTerm window in `workdir` and types `command`.
+///
+/// The window runs the user's login shell, so `--shell` does not apply here.
+fn macos_terminal_script(
+    term: RecentTerminal,
+    workdir: &str,
+    keep_open: bool,
+    command: &str,
+) -> String {
+    let mut line = format!("cd {} && {command}", posix_single_quote(workdir));
+    if !keep_open {
+        line.push_str("; exit");
+    }
+    let line = applescript_string(&line);
+    match term {
+        RecentTerminal::Iterm => format!(
+            "tell application \"iTerm\"\n  activate\n  set newWindow to (create window with default profile)\n  tell current session of newWindow to write text {line}\nend tell"
+        ),
+        _ => format!("tell application \"Terminal\"\n  activate\n  do script {line}\nend tell"),
+    }
+}
+
+fn spawn_macos_terminal(
+    term: RecentTerminal,
+    workdir: &str,
+    keep_open: bool,
+    command: &str,
+    dry_run: bool,
+) -> CliResult<()> {
+    let args = vec![
+        "-e".to_string(),
+        macos_terminal_script(term, workdir, keep_open, command),
+    ];
+    if dry_run {
+        spawn_cmd_dry_run("osascript", "osascript", &args);
+        return Ok(());
+    }
+
+    let status = std::process::Command::new("osascript")
+        .args(&args)
+        .status()
+        .map_err(|e| crate::CliError::Other(format!("failed to run osascript ({e})")))?;
+    if !status.success() {
+        return Err(crate::CliError::Other(format!(
+            "osascript could not open a {} window ({status}); allow codex-helper to control it under System Settings > Privacy & Security > Automation",
+            match term {
+                RecentTerminal::Iterm => "iTerm",
+                _ => "Terminal",
+            }
+        )));
+    }
+    Ok(())
+}
+
+fn program_on_path(program: &str) -> bool {
+    std::env::var_os("PATH")
+        .is_some_and(|path| std::env::split_paths(&path).any(|dir| dir.join(program).is_file()))
+}
+
+/// The `--open` terminal when `--terminal` is not given: `wt` on Windows, iTerm (when installed)
+/// or Terminal on macOS, and the first of wezterm/gnome-terminal/konsole on `PATH` elsewhere.
+fn default_recent_terminal() -> CliResult<RecentTerminal> {
+    if cfg!(windows) {
+        return Ok(RecentTerminal::Wt);
+    }
+    if cfg!(target_os = "macos") {
+        return Ok(
+            if std::path::Path::new("/Applications/iTerm.app").exists() {
+                RecentTerminal::Iterm
+            } else {
+                RecentTerminal::Terminal
+            },
+        );
+    }
+    [
+        (RecentTerminal::Wezterm, "wezterm"),
+        (RecentTerminal::GnomeTerminal, "gnome-terminal"),
+        (RecentTerminal::Konsole, "konsole"),
+    ]
+    .into_iter()
+    .find(|(_, program)| program_on_path(program))
+    .map(|(term, _)| term)
+    .ok_or_else(|| {
+        crate::CliError::Other(
+            "no supported terminal found in PATH (tried wezterm, gnome-terminal, konsole); install one or pass --terminal".to_string(),
+        )
+    })
+}
+
+fn session_export_extension(format: &str) -> &'static str {
+    match format.to_lowercase().as_str() {
+        "json" => "json",
+        "html" => "html",
+        "context" => "txt",
+        _ => "md",
+    }
+}
+
+async fn session_export_content(
+    sess: &SessionSummary,
+    format: &str,
+    context: ContextExportOptions,
+) -> CliResult<String> {
+    if format.eq_ignore_ascii_case("context") {
+        session_context_export(sess, context).await
+    } else {
+        Ok(render_session_export(sess, format))
+    }
+}
+
+fn render_session_export(sess: &SessionSummary, format: &str) -> String {
+    match format.to_lowercase().as_str() {
+        "json" => {
+            // Minimal JSON export: same fields as SessionSummary for now.
+            let json = serde_json::json!({
+                "id": sess.id,
+                "cwd": sess.cwd,
+                "created_at": sess.created_at,
+                "updated_at": sess.updated_at,
+                "last_response_at": sess.last_response_at,
+                "user_turns": sess.user_turns,
+                "assistant_turns": sess.assistant_turns,
+                "rounds": sess.rounds,
+                "first_user_message": sess.first_user_message,
+                "path": sess.path,
+            });
+            serde_json::to_string_pretty(&json).unwrap_or_else(|_| "{}".to_string())
+        }
+        "html" => {
+            let mut html = String::new();
+            html.push_str("<!DOCTYPE html>\n<html>\n<head><meta charset=\"utf-8\">");
+            html.push_str(&format!(
+                "<title>Codex session {}</title></head>\n<body>\n",
+                escape_html(&sess.id)
+            ));
+            html.push_str("<h1>Codex session</h1>\n<ul>\n");
+            let mut field = |label: &str, value: &str| {
+                html.push_str(&format!(
+                    "<li>{label}: <code>{}</code></li>\n",
+                    escape_html(value)
+                ));
+            };
+            field("id", &sess.id);
+            if let Some(updated) = sess.updated_at.as_deref() {
+                field("updated_at", updated);
+            }
+            if let Some(updated) = sess.last_response_at.as_deref() {
+                field("last_response_at", updated);
+            }
+            field("rounds", &sess.rounds.to_string());
+            if let Some(cwd) = sess.cwd.as_deref() {
+                field("cwd", cwd);
+            }
+            html.push_str("</ul>\n");
+            if let Some(msg) = sess.first_user_message.as_deref() {
+                html.push_str("<h2>First user message</h2>\n<pre>");
+                html.push_str(&escape_html(msg));
+                html.push_str("</pre>\n");
+            }
+            html.push_str("</body>\n</html>\n");
+            html
+        }
+        _ => {
+            // Default: markdown export with basic header and first prompt.
+            let mut md = String::new();
+            md.push_str("# Codex session\n\n");
+            md.push_str(&format!("- id: `{}`\n", sess.id));
+            if let Some(updated) = sess.updated_at.as_deref() {
+                md.push_str(&format!("- updated_at: `{}`\n", updated));
+            }
+            if let Some(updated) = sess.last_response_at.as_deref() {
+                md.push_str(&format!("- last_response_at: `{}`\n", updated));
+            }
+            md.push_str(&format!("- rounds: `{}`\n", sess.rounds));
+            if let Some(cwd) = sess.cwd.as_deref() {
+                md.push_str(&format!("- cwd: `{}`\n", cwd));
+            }
+            md.push('\n');
+            if let Some(msg) = sess.first_user_message.as_deref() {
+                md.push_str("## First user message\n\n");
+                md.push_str(msg);
+                md.push('\n');
+            }
+            md
+        }
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn write_session_export(out_path: &std::path::Path, content: &str) -> CliResult<()> {
+    if let Some(parent) = out_path.parent()
+        && !parent.as_os_str().is_empty()
+        && let Err(e) = std::fs::create_dir_all(parent)
+    {
+        return Err(crate::CliError::Other(format!(
+            "failed to create parent dir {:?}: {}",
+            parent, e
+        )));
+    }
+    std::fs::write(out_path, content).map_err(|e| {
+        crate::CliError::Other(format!("failed to write export file {:?}: {}", out_path, e))
+    })
+}
+
+/// `<date>_<session id>.<ext>`, dated by creation time (falling back to the last update).
+fn session_export_file_name(sess: &SessionSummary, format: &str) -> String {
+    let date = sess
+        .created_at
+        .as_deref()
+        .or(sess.updated_at.as_deref())
+        .and_then(|ts| ts.get(..10))
+        .unwrap_or("undated");
+    let id = sess
+        .id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+    format!("{date}_{id}.{}", session_export_extension(format))
+}
+
+async fn export_project_sessions(
+    root: &std::path::Path,
+    format: &str,
+    context: ContextExportOptions,
+    output_dir: &str,
+) -> CliResult<()> {
+    let sessions = find_all_codex_sessions_for_dir(root).await?;
+    if sessions.is_empty() {
+        println!("No Codex sessions found for project {:?}", root);
+        return Ok(());
+    }
+
+    let output_dir = std::path::PathBuf::from(output_dir);
+    std::fs::create_dir_all(&output_dir).map_err(|e| {
+        crate::CliError::Other(format!(
+            "failed to create output dir {:?}: {}",
+            output_dir, e
+        ))
+    })?;
+
+    let total = sessions.len();
+    let mut exported = 0usize;
+    for (index, sess) in sessions.iter().enumerate() {
+        let position = index + 1;
+        if let Err(e) = std::fs::File::open(&sess.path) {
+            eprintln!(
+                "[{position}/{total}] warning: skipping session {} ({:?} is unreadable: {e})",
+                sess.id, sess.path
+            );
+            continue;
+        }
+        let out_path = output_dir.join(session_export_file_name(sess, format));
+        let written = session_export_content(sess, format, context)
+            .await
+            .and_then(|content| write_session_export(&out_path, &content));
+        match written {
+            Ok(()) => {
+                exported += 1;
+                println!(
+                    "[{position}/{total}] exported {} -> {:?}",
+                    sess.id, out_path
+                );
+            }
+            Err(e) => eprintln!(
+                "[{position}/{total}] warning: skipping session {}: {e}",
+                sess.id
+            ),
+        }
+    }
+    println!(
+        "Exported {exported} of {total} sessions for {:?} to {:?}",
+        root, output_dir
+    );
+    Ok(())
+}
+
 pub async fn handle_session_cmd(cmd: SessionCommand) -> CliResult<()> {
     match cmd {
         SessionCommand::List {
             limit,
+            offset,
             path,
             truncate,
+            json,
         } => {
-            let sessions: Vec<SessionSummary> = if let Some(p) = path {
-                let root = std::path::PathBuf::from(p);
-                find_codex_sessions_for_dir(&root, limit).await?
-            } else {
-                find_codex_sessions_for_current_dir(limit).await?
+            let root = match path {
+                Some(p) => std::path::PathBuf::from(p),
+                None => std::env::current_dir().map_err(|e| {
+                    crate::CliError::Other(format!("failed to resolve current directory: {e}"))
+                })?,
             };
-            if sessions.is_empty() {
-                println!("No Codex sessions found under ~/.codex/sessions");
+            let sessions = find_codex_sessions_page_for_dir(&root, offset, limit).await?;
+            if json {
+                print_session_list_json(&sessions, offset, truncate);
+            } else if sessions.is_empty() {
+                if offset > 0 {
+                    println!("No more Codex sessions after offset {offset}");
+                } else {
+                    println!("No Codex sessions found under ~/.codex/sessions");
+                }
             } else {
-                println!("Recent Codex sessions (newest first):");
+                if offset > 0 {
+                    println!("Recent Codex sessions (newest first, from offset {offset}):");
+                } else {
+                    println!("Recent Codex sessions (newest first):");
+                }
                 for s in sessions {
                     let last_update = s.updated_at.as_deref().unwrap_or("-");
                     let last_response = s.last_response_at.as_deref().unwrap_or("-");
@@ -228,31 +738,43 @@ pub async fn handle_session_cmd(cmd: SessionCommand) -> CliResult<()> {
             delay_ms,
             dry_run,
         } => {
-            let sessions = find_recent_codex_sessions(since.into(), limit).await?;
-            let mut rows: Vec<(String, String, Option<String>, u64)> =
-                Vec::with_capacity(sessions.len());
-            for s in sessions {
-                let cwd_opt = s.cwd.clone();
-                let cwd = cwd_opt.as_deref().unwrap_or("-");
-                let root = if raw_cwd {
-                    cwd.to_string()
-                } else {
-                    infer_project_root_from_cwd(cwd)
-                };
-                rows.push((root, s.id, cwd_opt, s.mtime_ms));
-            }
-
-            print_recent_sessions(format, &rows)?;
+            let rows = if format == RecentFormat::Ndjson {
+                stream_recent_sessions_ndjson(since.into(), limit, raw_cwd).await?
+            } else {
+                let rows = find_recent_codex_sessions(since.into(), limit)
+                    .await?
+                    .into_iter()
+                    .map(|session| recent_session_row(session, raw_cwd))
+                    .collect::<Vec<_>>();
+                print_recent_sessions(format, &rows)?;
+                rows
+            };
 
             if !open {
                 return Ok(());
             }
 
-            let term = terminal.unwrap_or(if cfg!(windows) {
-                RecentTerminal::Wt
-            } else {
-                RecentTerminal::Wezterm
-            });
+            let term = match terminal {
+                Some(term) => term,
+                None => default_recent_terminal()?,
+            };
+            if matches!(term, RecentTerminal::Wt) && !cfg!(windows) {
+                return Err(crate::CliError::Other(
+                    "--terminal wt is only supported on Windows".to_string(),
+                ));
+            }
+            if matches!(term, RecentTerminal::Terminal | RecentTerminal::Iterm)
+                && !cfg!(target_os = "macos")
+            {
+                return Err(crate::CliError::Other(format!(
+                    "--terminal {} is only supported on macOS",
+                    if term == RecentTerminal::Iterm {
+                        "iterm"
+                    } else {
+                        "terminal"
+                    }
+                )));
+            }
             let shell = shell.unwrap_or(if cfg!(windows) {
                 "pwsh".to_string()
             } else {
@@ -273,17 +795,17 @@ pub async fn handle_session_cmd(cmd: SessionCommand) -> CliResult<()> {
                 let full_cmd = render_resume_cmd(&resume_cmd, &id);
                 match term {
                     RecentTerminal::Wt => {
-                        if !cfg!(windows) {
-                            return Err(crate::CliError::Other(
-                                "--terminal wt is only supported on Windows".to_string(),
-                            ));
-                        }
                         spawn_windows_terminal_wt(
                             wt_window, workdir, &shell, keep_open, &full_cmd, dry_run,
                         )?;
                     }
-                    RecentTerminal::Wezterm => {
-                        spawn_wezterm(workdir, &shell, keep_open, &full_cmd, dry_run)?;
+                    RecentTerminal::Terminal | RecentTerminal::Iterm => {
+                        spawn_macos_terminal(term, workdir, keep_open, &full_cmd, dry_run)?;
+                    }
+                    RecentTerminal::Wezterm
+                    | RecentTerminal::GnomeTerminal
+                    | RecentTerminal::Konsole => {
+                        spawn_argv_terminal(term, workdir, &shell, keep_open, &full_cmd, dry_run)?;
                     }
                 }
 
@@ -292,14 +814,26 @@ pub async fn handle_session_cmd(cmd: SessionCommand) -> CliResult<()> {
                 }
             }
         }
-        SessionCommand::Last { path } => {
+        SessionCommand::Last { path, json } => {
             let mut sessions = if let Some(p) = path {
                 let root = std::path::PathBuf::from(p);
                 find_codex_sessions_for_dir(&root, 1).await?
             } else {
                 find_codex_sessions_for_current_dir(1).await?
             };
-            if let Some(s) = sessions.pop() {
+            if json {
+                let session = sessions.pop();
+                let usage = session
+                    .as_ref()
+                    .and_then(|s| session_usage_totals(s.id.as_str()));
+                let estimated_tokens = match (session.as_ref(), usage.as_ref()) {
+                    (Some(s), None) => session_estimated_tokens(s).await,
+                    _ => None,
+                };
+                let value = session_last_json(session.as_ref(), usage.as_ref(), estimated_tokens);
+                let s = serde_json::to_string_pretty(&value).unwrap_or_else(|_| "null".to_string());
+                println!("{s}");
+            } else if let Some(s) = sessions.pop() {
                 println!("Last Codex session for current project:");
                 println!("  id: {}", s.id);
                 println!("  rounds: {}", s.rounds);
@@ -312,6 +846,19 @@ pub async fn handle_session_cmd(cmd: SessionCommand) -> CliResult<()> {
                     s.updated_at.as_deref().unwrap_or("-")
                 );
                 println!("  cwd: {}", s.cwd.as_deref().unwrap_or("-"));
+                if let Some(usage) = session_usage_totals(s.id.as_str()) {
+                    println!(
+                        "  tokens: {} ({} logged request(s))",
+                        tokens_short(usage.total_tokens),
+                        usage.requests
+                    );
+                } else if let Some(estimated) = session_estimated_tokens(&s).await {
+                    println!(
+                        "  tokens: ~{} ({})",
+                        tokens_short(estimated as i64),
+                        estimate_scope(None, 0)
+                    );
+                }
                 if let Some(msg) = s.first_user_message.as_deref() {
                     let msg_single = msg.replace('\n', " ");
                     println!("  first_prompt: {}", msg_single);
@@ -323,14 +870,32 @@ pub async fn handle_session_cmd(cmd: SessionCommand) -> CliResult<()> {
                 println!("No Codex sessions found under ~/.codex/sessions");
             }
         }
+        SessionCommand::Watch {
+            path,
+            interval,
+            idle_after,
+        } => {
+            super::session_watch::handle_session_watch(path, interval.into(), idle_after.into())
+                .await?;
+        }
         SessionCommand::Transcript {
             id,
             all,
             tail,
             format,
             timestamps,
+            grep,
+            context,
             path,
         } => {
+            let pattern = grep
+                .as_deref()
+                .map(|pattern| {
+                    regex::Regex::new(pattern).map_err(|e| {
+                        crate::CliError::Other(format!("invalid --grep pattern '{pattern}': {e}"))
+                    })
+                })
+                .transpose()?;
             let session_opt: Option<SessionSummary> = if let Some(p) = path.as_deref() {
                 let root = std::path::PathBuf::from(p);
                 let sessions = find_codex_sessions_for_dir(&root, usize::MAX).await?;
@@ -361,13 +926,32 @@ pub async fn handle_session_cmd(cmd: SessionCommand) -> CliResult<()> {
                 }
             }
             println!("  file: {:?}", session_path);
-            println!();
-
             let slice = if all { None } else { Some(tail) };
             let messages = read_codex_session_transcript(&session_path, slice).await?;
-
+            let estimated_tokens = estimate_transcript_tokens(&messages);
+            println!(
+                "  estimated_tokens: ~{} ({})",
+                tokens_short(estimated_tokens as i64),
+                estimate_scope(slice, messages.len())
+            );
+            println!();
+            let message_count = messages.len();
+            let groups = match pattern.as_ref() {
+                Some(pattern) => grep_transcript_groups(messages, pattern, context),
+                None => vec![messages],
+            };
             let fmt = format.to_lowercase();
+            // JSON output stays parseable when nothing matches: an empty array.
+            if let Some(pattern) = pattern.as_ref()
+                && groups.is_empty()
+                && fmt != "json"
+            {
+                println!("No messages match /{}/.", pattern.as_str());
+                return Ok(());
+            }
+
             if fmt == "json" {
+                let messages = groups.into_iter().flatten().collect::<Vec<_>>();
                 let json =
                     serde_json::to_string_pretty(&messages).unwrap_or_else(|_| "[]".to_string());
                 println!("{json}");
@@ -380,24 +964,76 @@ pub async fn handle_session_cmd(cmd: SessionCommand) -> CliResult<()> {
                 if let Some(cwd) = meta.as_ref().and_then(|m| m.cwd.as_deref()) {
                     println!("- cwd: `{}`", cwd);
                 }
+                println!(
+                    "- estimated tokens: ~{} ({})",
+                    tokens_short(estimated_tokens as i64),
+                    estimate_scope(slice, message_count)
+                );
                 println!();
-                for m in messages {
-                    println!("## {}", m.role);
-                    println!();
-                    println!("{}", m.text);
-                    println!();
+                for (idx, group) in groups.into_iter().enumerate() {
+                    if idx > 0 {
+                        println!("---");
+                        println!();
+                    }
+                    for m in group {
+                        match m.timestamp.as_deref().filter(|_| timestamps) {
+                            Some(ts) => println!("## {} ({})", m.role, ts),
+                            None => println!("## {}", m.role),
+                        }
+                        println!();
+                        println!("{}", m.text);
+                        println!();
+                    }
                 }
                 return Ok(());
             }
 
             // Default: text
-            for m in messages {
-                if timestamps && let Some(ts) = m.timestamp.as_deref() {
-                    println!("[{}] {}: {}", ts, m.role, m.text);
-                    continue;
+            for (idx, group) in groups.into_iter().enumerate() {
+                if idx > 0 {
+                    println!("--");
+                    println!();
+                }
+                for m in group {
+                    if timestamps && let Some(ts) = m.timestamp.as_deref() {
+                        println!("[{}] {}: {}", ts, m.role, m.text);
+                        continue;
+                    }
+                    println!("{}: {}", m.role, m.text);
+                    println!();
                 }
-                println!("{}: {}", m.role, m.text);
+            }
+        }
+        SessionCommand::Search {
+            query,
+            limit,
+            all: true,
+            since,
+            ..
+        } => {
+            println!(
+                "Sessions in all projects matching '{}' (updated within {}):",
+                query, since
+            );
+            let mut on_hit = |hit: &crate::sessions::SessionSearchHit| {
+                let project = hit
+                    .cwd
+                    .as_deref()
+                    .map(infer_project_root_from_cwd)
+                    .filter(|root| !root.is_empty())
+                    .unwrap_or_else(|| "-".to_string());
+                println!("- id: {}", hit.id);
+                println!("  project: {}", project);
+                println!("  match: {}", hit.snippet);
                 println!();
+            };
+            let hits = search_codex_sessions_all_projects(&query, since.into(), limit, &mut on_hit)
+                .await?;
+            if hits.is_empty() {
+                println!(
+                    "No Codex sessions under ~/.codex/sessions matched query: {}",
+                    query
+                );
             }
         }
         SessionCommand::Search {
@@ -405,6 +1041,7 @@ pub async fn handle_session_cmd(cmd: SessionCommand) -> CliResult<()> {
             limit,
             path,
             truncate,
+            ..
         } => {
             let sessions: Vec<SessionSummary> = if let Some(p) = path {
                 let root = std::path::PathBuf::from(p);
@@ -437,7 +1074,31 @@ pub async fn handle_session_cmd(cmd: SessionCommand) -> CliResult<()> {
                 }
             }
         }
-        SessionCommand::Export { id, format, output } => {
+        SessionCommand::Export {
+            id: None,
+            format,
+            max_chars,
+            include_tools,
+            all_in_project: Some(root),
+            output_dir: Some(output_dir),
+            ..
+        } => {
+            let context = ContextExportOptions {
+                max_chars,
+                include_tools,
+            };
+            export_project_sessions(std::path::Path::new(&root), &format, context, &output_dir)
+                .await?;
+        }
+        SessionCommand::Export {
+            id,
+            format,
+            max_chars,
+            include_tools,
+            output,
+            ..
+        } => {
+            let id = id.unwrap_or_default();
             // For now, only lookup by scanning all sessions under current dir.
             let cwd = std::env::current_dir().map_err(|e| {
                 crate::CliError::Other(format!("failed to resolve current directory: {e}"))
@@ -448,63 +1109,14 @@ pub async fn handle_session_cmd(cmd: SessionCommand) -> CliResult<()> {
                 return Ok(());
             };
 
-            let fmt = format.to_lowercase();
-            let content = if fmt == "json" {
-                // Minimal JSON export: same fields as SessionSummary for now.
-                let json = serde_json::json!({
-                    "id": sess.id,
-                    "cwd": sess.cwd,
-                    "created_at": sess.created_at,
-                    "updated_at": sess.updated_at,
-                    "last_response_at": sess.last_response_at,
-                    "user_turns": sess.user_turns,
-                    "assistant_turns": sess.assistant_turns,
-                    "rounds": sess.rounds,
-                    "first_user_message": sess.first_user_message,
-                    "path": sess.path,
-                });
-                serde_json::to_string_pretty(&json).unwrap_or_else(|_| "{}".to_string())
-            } else {
-                // Default: markdown export with basic header and first prompt.
-                let mut md = String::new();
-                md.push_str("# Codex session\n\n");
-                md.push_str(&format!("- id: `{}`\n", sess.id));
-                if let Some(updated) = sess.updated_at.as_deref() {
-                    md.push_str(&format!("- updated_at: `{}`\n", updated));
-                }
-                if let Some(updated) = sess.last_response_at.as_deref() {
-                    md.push_str(&format!("- last_response_at: `{}`\n", updated));
-                }
-                md.push_str(&format!("- rounds: `{}`\n", sess.rounds));
-                if let Some(cwd) = sess.cwd.as_deref() {
-                    md.push_str(&format!("- cwd: `{}`\n", cwd));
-                }
-                md.push('\n');
-                if let Some(msg) = sess.first_user_message.as_deref() {
-                    md.push_str("## First user message\n\n");
-                    md.push_str(msg);
-                    md.push('\n');
-                }
-                md
+            let context = ContextExportOptions {
+                max_chars,
+                include_tools,
             };
-
+            let content = session_export_content(&sess, &format, context).await?;
             if let Some(path) = output {
                 let out_path = std::path::PathBuf::from(path);
-                if let Some(parent) = out_path.parent()
-                    && !parent.as_os_str().is_empty()
-                    && let Err(e) = std::fs::create_dir_all(parent)
-                {
-                    return Err(crate::CliError::Other(format!(
-                        "failed to create parent dir {:?}: {}",
-                        parent, e
-                    )));
-                }
-                if let Err(e) = std::fs::write(&out_path, content) {
-                    return Err(crate::CliError::Other(format!(
-                        "failed to write export file {:?}: {}",
-                        out_path, e
-                    )));
-                }
+                write_session_export(&out_path, &content)?;
                 println!("Exported session {} to {:?}", id, out_path);
             } else {
                 println!("{content}");
@@ -515,10 +1127,125 @@ pub async fn handle_session_cmd(cmd: SessionCommand) -> CliResult<()> {
     Ok(())
 }
 
+/// Keep messages matching `pattern` plus `context` neighbours on each side, grouped into
+/// contiguous runs so callers can print a separator between them (like `grep -C`).
+fn grep_transcript_groups(
+    messages: Vec<SessionTranscriptMessage>,
+    pattern: &regex::Regex,
+    context: usize,
+) -> Vec<Vec<SessionTranscriptMessage>> {
+    let mut keep = vec![false; messages.len()];
+    for (idx, message) in messages.iter().enumerate() {
+        if pattern.is_match(&message.text) {
+            let end = (idx + context).min(messages.len() - 1);
+            keep[idx.saturating_sub(context)..=end].fill(true);
+        }
+    }
+
+    let mut groups: Vec<Vec<SessionTranscriptMessage>> = Vec::new();
+    let mut previous_kept = false;
+    for (message, kept) in messages.into_iter().zip(keep) {
+        if kept {
+            match groups.last_mut() {
+                Some(group) if previous_kept => group.push(message),
+                _ => groups.push(vec![message]),
+            }
+        }
+        previous_kept = kept;
+    }
+    groups
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn recent_ndjson_rows_match_the_json_format_fields() {
+        let rows = vec![
+            (
+                "/work/app".to_string(),
+                "sid-1".to_string(),
+                Some("/work/app/src".to_string()),
+                20,
+            ),
+            ("-".to_string(), "sid-2".to_string(), None, 10),
+        ];
+
+        let mut out = Vec::new();
+        for row in &rows {
+            write_recent_session_ndjson(&mut out, row).expect("write ndjson row");
+        }
+        let text = String::from_utf8(out).expect("utf-8 ndjson");
+        let lines = text
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).expect("json line"))
+            .collect::<Vec<_>>();
+
+        let json_rows: Vec<RecentSessionJson<'_>> =
+            rows.iter().map(RecentSessionJson::from).collect();
+        assert_eq!(
+            serde_json::Value::Array(lines),
+            serde_json::to_value(json_rows).expect("json rows")
+        );
+        assert!(text.ends_with('\n'));
+    }
+
+    fn transcript_message(role: &str, text: &str) -> SessionTranscriptMessage {
+        SessionTranscriptMessage {
+            timestamp: None,
+            role: role.to_string(),
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn grep_transcript_groups_keeps_matches_with_context_runs() {
+        let messages = [
+            "start",
+            "boom: ECONNRESET",
+            "retry",
+            "ok",
+            "quiet",
+            "boom again",
+            "end",
+        ]
+        .into_iter()
+        .map(|text| transcript_message("assistant", text))
+        .collect::<Vec<_>>();
+        let pattern = regex::Regex::new("^boom").expect("pattern");
+
+        let texts = |groups: Vec<Vec<SessionTranscriptMessage>>| {
+            groups
+                .into_iter()
+                .map(|group| group.into_iter().map(|m| m.text).collect::<Vec<_>>())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            texts(grep_transcript_groups(messages.clone(), &pattern, 0)),
+            vec![vec!["boom: ECONNRESET"], vec!["boom again"]]
+        );
+        assert_eq!(
+            texts(grep_transcript_groups(messages.clone(), &pattern, 1)),
+            vec![
+                vec!["start", "boom: ECONNRESET", "retry"],
+                vec!["quiet", "boom again", "end"]
+            ]
+        );
+        assert_eq!(
+            texts(grep_transcript_groups(messages, &pattern, 2)),
+            vec![vec![
+                "start",
+                "boom: ECONNRESET",
+                "retry",
+                "ok",
+                "quiet",
+                "boom again",
+                "end"
+            ]]
+        );
+    }
+
     #[test]
     fn session_prompt_preview_keeps_full_prompt_by_default() {
         let prompt = "first line\nsecond line with enough extra words to exceed the old search preview width";
@@ -531,10 +1258,140 @@ mod tests {
         );
     }
 
+    #[test]
+    fn session_list_json_rows_number_positions_from_the_offset() {
+        let summary = SessionSummary {
+            id: "sid-1".to_string(),
+            path: "/tmp/rollout.jsonl".into(),
+            cwd: Some("/work/project".to_string()),
+            created_at: None,
+            updated_at: Some("2026-05-18T00:00:00Z".to_string()),
+            last_response_at: None,
+            user_turns: 2,
+            assistant_turns: 1,
+            rounds: 1,
+            first_user_message: Some("abcdefghijklmnop".to_string()),
+            source: Default::default(),
+            sort_hint_ms: None,
+        };
+
+        let rows = session_list_json_rows(&[summary], 20, Some(4));
+
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0]["position"], 20);
+        assert_eq!(rows[0]["session_id"], "sid-1");
+        assert_eq!(rows[0]["first_prompt"], "abcd...");
+    }
+
+    #[test]
+    fn session_last_json_includes_usage_only_when_known() {
+        let summary = SessionSummary {
+            id: "sid-9".to_string(),
+            path: "/tmp/rollout-9.jsonl".into(),
+            cwd: Some("/work/project".to_string()),
+            created_at: Some("2026-05-18T00:00:00Z".to_string()),
+            updated_at: Some("2026-05-18T01:00:00Z".to_string()),
+            last_response_at: None,
+            user_turns: 3,
+            assistant_turns: 3,
+            rounds: 3,
+            first_user_message: Some("fix the\nbuild".to_string()),
+            source: Default::default(),
+            sort_hint_ms: None,
+        };
+
+        let without_usage = session_last_json(Some(&summary), None, None);
+        assert_eq!(without_usage["session_id"], "sid-9");
+        assert_eq!(without_usage["path"], "/tmp/rollout-9.jsonl");
+        assert_eq!(without_usage["cwd"], "/work/project");
+        assert_eq!(without_usage["rounds"], 3);
+        assert_eq!(without_usage["first_prompt"], "fix the build");
+        assert!(without_usage.get("usage").is_none());
+        assert!(without_usage.get("estimated_tokens").is_none());
+        let estimated = session_last_json(Some(&summary), None, Some(420));
+        assert_eq!(estimated["estimated_tokens"], 420);
+
+        let usage = RequestUsageAggregate {
+            requests: 2,
+            input_tokens: 1_200,
+            output_tokens: 300,
+            total_tokens: 1_500,
+            ..RequestUsageAggregate::default()
+        };
+        let with_usage = session_last_json(Some(&summary), Some(&usage), Some(420));
+        assert_eq!(with_usage["usage"]["requests"], 2);
+        assert!(
+            with_usage.get("estimated_tokens").is_none(),
+            "logged usage wins"
+        );
+        assert_eq!(with_usage["usage"]["total_tokens"], 1_500);
+
+        assert!(session_last_json(None, None, None).is_null());
+    }
+
     #[test]
     fn session_prompt_preview_truncates_when_requested() {
         let preview = session_prompt_preview(Some("abcdefghijklmnopqrstuvwxyz"), Some(10));
 
         assert_eq!(preview, "abcdefghij...");
     }
+
+    #[test]
+    fn session_export_file_name_uses_date_and_sanitized_id() {
+        let summary = SessionSummary {
+            id: "019a/abc".to_string(),
+            path: "/tmp/rollout.jsonl".into(),
+            cwd: None,
+            created_at: Some("2026-05-18T09:30:00Z".to_string()),
+            updated_at: None,
+            last_response_at: None,
+            user_turns: 1,
+            assistant_turns: 1,
+            rounds: 1,
+            first_user_message: Some("<b>hi</b>".to_string()),
+            source: Default::default(),
+            sort_hint_ms: None,
+        };
+
+        assert_eq!(
+            session_export_file_name(&summary, "HTML"),
+            "2026-05-18_019a_abc.html"
+        );
+        assert_eq!(
+            session_export_file_name(&summary, "markdown"),
+            "2026-05-18_019a_abc.md"
+        );
+        assert!(render_session_export(&summary, "html").contains("&lt;b&gt;hi&lt;/b&gt;"));
+    }
+
+    #[test]
+    fn macos_terminal_script_quotes_workdir_and_command() {
+        let script = macos_terminal_script(
+            RecentTerminal::Terminal,
+            "/tmp/it's here",
+            true,
+            r#"codex resume "abc""#,
+        );
+        assert_eq!(
+            script,
+            "tell application \"Terminal\"\n  activate\n  do script \"cd '/tmp/it'\\\\''s here' && codex resume \\\"abc\\\"\"\nend tell"
+        );
+
+        let iterm = macos_terminal_script(RecentTerminal::Iterm, "/work", false, "codex resume x");
+        assert!(iterm.starts_with("tell application \"iTerm\""));
+        assert!(iterm.contains("write text \"cd '/work' && codex resume x; exit\""));
+    }
+
+    #[test]
+    fn argv_terminals_share_shell_invocation() {
+        assert_eq!(
+            shell_invocation("konsole", "/bin/bash", true, "codex resume x").expect("bash"),
+            vec!["/bin/bash", "-lc", "codex resume x; exec bash"]
+        );
+        assert_eq!(
+            shell_invocation("gnome-terminal", "pwsh", false, "codex resume x").expect("pwsh"),
+            vec!["pwsh", "-Command", "codex resume x"]
+        );
+        assert!(shell_invocation("konsole", "fish", true, "codex resume x").is_err());
+    }
 }
@@ -0,0 +1,210 @@
+use crate::CliResult;
+use crate::sessions::{
+    SessionSummary, SessionTranscriptMessage, read_codex_session_transcript,
+    read_codex_session_transcript_with_tool_calls,
+};
+
+/// Tool calls are reminders of what was done, not content; long arguments are cut to this.
+const MAX_TOOL_CALL_CHARS: usize = 200;
+/// Room kept for the omission marker once the oldest messages no longer fit.
+const OMISSION_MARKER_RESERVE: usize = 48;
+
+/// Options for `session export --format context`.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct ContextExportOptions {
+    pub max_chars: usize,
+    pub include_tools: bool,
+}
+
+/// Reads the session's full transcript and condenses it for pasting into a new session.
+pub(super) async fn session_context_export(
+    sess: &SessionSummary,
+    options: ContextExportOptions,
+) -> CliResult<String> {
+    let messages = if options.include_tools {
+        read_codex_session_transcript_with_tool_calls(&sess.path).await?
+    } else {
+        read_codex_session_transcript(&sess.path, None).await?
+    };
+    Ok(render_session_context(sess, &messages, options.max_chars))
+}
+
+/// Plain text with every user message and only the last assistant message of each turn (its
+/// conclusion). When over `max_chars`, the oldest messages are dropped first.
+fn render_session_context(
+    sess: &SessionSummary,
+    messages: &[SessionTranscriptMessage],
+    max_chars: usize,
+) -> String {
+    let mut header = format!("Context from a previous Codex session ({}", sess.id);
+    if let Some(cwd) = sess.cwd.as_deref() {
+        header.push_str(&format!(", cwd {cwd}"));
+    }
+    header.push_str("):\n\n");
+
+    let entries = condensed_entries(messages);
+    let budget = max_chars.saturating_sub(header.chars().count());
+    let cost = |entry: &String| entry.chars().count() + 2;
+    let total = entries.iter().map(cost).sum::<usize>();
+    let mut kept: Vec<String> = Vec::new();
+    if total <= budget {
+        kept = entries.clone();
+    } else {
+        let budget = budget.saturating_sub(OMISSION_MARKER_RESERVE);
+        let mut used = 0usize;
+        for entry in entries.iter().rev() {
+            if used + cost(entry) > budget {
+                break;
+            }
+            used += cost(entry);
+            kept.push(entry.clone());
+        }
+        if kept.is_empty()
+            && let Some(newest) = entries.last()
+        {
+            // Even the newest message alone is too long: keep its beginning.
+            let keep = budget.saturating_sub(3);
+            kept.push(format!(
+                "{}…",
+                newest.chars().take(keep).collect::<String>()
+            ));
+        }
+        kept.reverse();
+    }
+
+    let mut out = header;
+    let omitted = entries.len() - kept.len();
+    if omitted > 0 {
+        out.push_str(&format!("[{omitted} earlier message(s) omitted]\n\n"));
+    }
+    for entry in kept {
+        out.push_str(&entry);
+        out.push_str("\n\n");
+    }
+    out.truncate(out.trim_end().len());
+    out.push('\n');
+    out
+}
+
+fn condensed_entries(messages: &[SessionTranscriptMessage]) -> Vec<String> {
+    let mut entries = Vec::new();
+    let mut conclusion: Option<&str> = None;
+    for message in messages {
+        match message.role.as_str() {
+            "Assistant" => {
+                conclusion = Some(message.text.trim());
+                continue;
+            }
+            "Tool" => {
+                let call = message
+                    .text
+                    .split_whitespace()
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                let call = if call.chars().count() > MAX_TOOL_CALL_CHARS {
+                    format!(
+                        "{}…",
+                        call.chars().take(MAX_TOOL_CALL_CHARS).collect::<String>()
+                    )
+                } else {
+                    call
+                };
+                entries.push(format!("Tool: {call}"));
+                continue;
+            }
+            _ => {}
+        }
+        if let Some(text) = conclusion.take() {
+            entries.push(format!("Assistant: {text}"));
+        }
+        entries.push(format!("{}: {}", message.role, message.text.trim()));
+    }
+    if let Some(text) = conclusion {
+        entries.push(format!("Assistant: {text}"));
+    }
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(role: &str, text: &str) -> SessionTranscriptMessage {
+        SessionTranscriptMessage {
+            timestamp: None,
+            role: role.to_string(),
+            text: text.to_string(),
+        }
+    }
+
+    fn summary() -> SessionSummary {
+        SessionSummary {
+            id: "sid-1".to_string(),
+            path: "session.jsonl".into(),
+            cwd: Some("/work/app".to_string()),
+            created_at: None,
+            updated_at: None,
+            last_response_at: None,
+            user_turns: 2,
+            assistant_turns: 3,
+            rounds: 2,
+            first_user_message: None,
+            source: Default::default(),
+            sort_hint_ms: None,
+        }
+    }
+
+    fn transcript() -> Vec<SessionTranscriptMessage> {
+        vec![
+            message("User", "fix the flaky test"),
+            message("Assistant", "Looking at the test first."),
+            message("Tool", "shell {\"command\":\n  [\"cargo\", \"test\"]}"),
+            message("Assistant", "The timeout was too short; raised it to 5s."),
+            message("User", "now update the changelog"),
+            message("Assistant", "Added an entry under Unreleased."),
+        ]
+    }
+
+    #[test]
+    fn context_keeps_user_messages_and_turn_conclusions() {
+        let text = render_session_context(&summary(), &transcript(), 10_000);
+
+        assert_eq!(
+            text,
+            "Context from a previous Codex session (sid-1, cwd /work/app):\n\n\
+             User: fix the flaky test\n\n\
+             Tool: shell {\"command\": [\"cargo\", \"test\"]}\n\n\
+             Assistant: The timeout was too short; raised it to 5s.\n\n\
+             User: now update the changelog\n\n\
+             Assistant: Added an entry under Unreleased.\n"
+        );
+    }
+
+    #[test]
+    fn context_drops_the_oldest_messages_to_fit_the_budget() {
+        let messages = transcript()
+            .into_iter()
+            .filter(|message| message.role != "Tool")
+            .collect::<Vec<_>>();
+
+        let text = render_session_context(&summary(), &messages, 200);
+
+        assert!(text.chars().count() <= 200, "{text}");
+        assert!(text.contains("[2 earlier message(s) omitted]"), "{text}");
+        assert!(!text.contains("flaky"), "{text}");
+        assert!(text.ends_with(
+            "User: now update the changelog\n\nAssistant: Added an entry under Unreleased.\n"
+        ));
+    }
+
+    #[test]
+    fn context_cuts_an_oversized_final_message() {
+        let messages = vec![message("User", &"x".repeat(1_000))];
+
+        let text = render_session_context(&summary(), &messages, 150);
+
+        assert!(text.chars().count() <= 150, "{text}");
+        assert!(text.contains("User: xxx"));
+        assert!(text.trim_end().ends_with('…'));
+    }
+}
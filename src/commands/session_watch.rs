@@ -0,0 +1,309 @@
+use std::collections::{HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+
+use crate::sessions::{
+    SessionAlertEvent, find_codex_sessions_for_dir, infer_project_root_from_cwd, session_line_alert,
+};
+use crate::{CliError, CliResult};
+
+const MAX_NOTIFICATION_MESSAGE_CHARS: usize = 200;
+/// Alerts remembered for de-duplication after a rewrite; older ones are forgotten first.
+const MAX_SEEN_ALERTS: usize = 256;
+
+/// Read position and alert state for the session file currently being followed.
+struct SessionTail {
+    session_id: String,
+    path: PathBuf,
+    offset: u64,
+    partial: Vec<u8>,
+    seen: HashSet<SessionAlertEvent>,
+    seen_order: VecDeque<SessionAlertEvent>,
+    last_growth: Option<Instant>,
+    /// Set once the quiet period needs no idle alert: it was reported, or the turn ended.
+    idle_notified: bool,
+}
+
+impl SessionTail {
+    /// Starts at `offset` so history written before the watch began is not replayed.
+    fn new(session_id: String, path: PathBuf, offset: u64) -> Self {
+        Self {
+            session_id,
+            path,
+            offset,
+            partial: Vec::new(),
+            seen: HashSet::new(),
+            seen_order: VecDeque::new(),
+            last_growth: None,
+            idle_notified: false,
+        }
+    }
+
+    /// Consumes newly appended bytes and returns alerts not reported before. A trailing line
+    /// without `\n` is kept until the writer finishes it. A completed or aborted turn is not
+    /// reported again as idle.
+    fn ingest(&mut self, chunk: &[u8], now: Instant) -> Vec<SessionAlertEvent> {
+        if chunk.is_empty() {
+            return Vec::new();
+        }
+        self.offset += chunk.len() as u64;
+        self.last_growth = Some(now);
+        self.idle_notified = false;
+        self.partial.extend_from_slice(chunk);
+
+        let Some(end) = self.partial.iter().rposition(|byte| *byte == b'\n') else {
+            return Vec::new();
+        };
+        let complete: Vec<u8> = self.partial.drain(..=end).collect();
+        let mut alerts = Vec::new();
+        for event in String::from_utf8_lossy(&complete)
+            .lines()
+            .filter_map(session_line_alert)
+        {
+            if matches!(
+                event,
+                SessionAlertEvent::TaskComplete { .. } | SessionAlertEvent::TurnAborted { .. }
+            ) {
+                self.idle_notified = true;
+            }
+            if self.remember(&event) {
+                alerts.push(event);
+            }
+        }
+        alerts
+    }
+
+    /// False when `event` was already reported.
+    fn remember(&mut self, event: &SessionAlertEvent) -> bool {
+        if !self.seen.insert(event.clone()) {
+            return false;
+        }
+        self.seen_order.push_back(event.clone());
+        if self.seen_order.len() > MAX_SEEN_ALERTS
+            && let Some(oldest) = self.seen_order.pop_front()
+        {
+            self.seen.remove(&oldest);
+        }
+        true
+    }
+
+    /// The file was rewritten or truncated; read it again from the start. Alerts already seen
+    /// stay suppressed.
+    fn rewind(&mut self) {
+        self.offset = 0;
+        self.partial.clear();
+    }
+
+    /// True once per quiet period: when the session grew and then stayed quiet for `idle_after`
+    /// without finishing the turn.
+    fn take_idle(&mut self, now: Instant, idle_after: Duration) -> bool {
+        let Some(last_growth) = self.last_growth else {
+            return false;
+        };
+        if self.idle_notified || now.duration_since(last_growth) < idle_after {
+            return false;
+        }
+        self.idle_notified = true;
+        true
+    }
+}
+
+pub(super) async fn handle_session_watch(
+    path: Option<String>,
+    interval: Duration,
+    idle_after: Duration,
+) -> CliResult<()> {
+    if interval.is_zero() {
+        return Err(CliError::Other(
+            "--interval must be greater than 0".to_string(),
+        ));
+    }
+    let root = match path {
+        Some(path) => PathBuf::from(path),
+        None => std::env::current_dir()
+            .map_err(|e| CliError::Other(format!("failed to read current dir: {e}")))?,
+    };
+    println!(
+        "Watching the newest Codex session for {}; press Ctrl+C to stop.",
+        infer_project_root_from_cwd(&root.to_string_lossy())
+    );
+
+    let mut tail: Option<SessionTail> = None;
+    let ctrl_c = tokio::signal::ctrl_c();
+    tokio::pin!(ctrl_c);
+    loop {
+        if let Err(error) = poll_latest_session(&root, &mut tail, idle_after).await {
+            eprintln!("session watch: {error}");
+        }
+        tokio::select! {
+            _ = &mut ctrl_c => break,
+            _ = tokio::time::sleep(interval) => {}
+        }
+    }
+    Ok(())
+}
+
+async fn poll_latest_session(
+    root: &Path,
+    tail: &mut Option<SessionTail>,
+    idle_after: Duration,
+) -> anyhow::Result<()> {
+    let Some(latest) = find_codex_sessions_for_dir(root, 1).await?.pop() else {
+        return Ok(());
+    };
+    if tail.as_ref().is_none_or(|tail| tail.path != latest.path) {
+        let len = tokio::fs::metadata(&latest.path).await?.len();
+        println!(
+            "Following session {} ({})",
+            latest.id,
+            latest.path.display()
+        );
+        *tail = Some(SessionTail::new(latest.id, latest.path, len));
+    }
+    let Some(tail) = tail.as_mut() else {
+        return Ok(());
+    };
+
+    let mut file = tokio::fs::File::open(&tail.path).await?;
+    let len = file.metadata().await?.len();
+    if len < tail.offset {
+        tail.rewind();
+    }
+    let mut chunk = Vec::new();
+    if len > tail.offset {
+        file.seek(std::io::SeekFrom::Start(tail.offset)).await?;
+        file.take(len - tail.offset).read_to_end(&mut chunk).await?;
+    }
+
+    let now = Instant::now();
+    for event in tail.ingest(&chunk, now) {
+        let (title, message) = match &event {
+            SessionAlertEvent::Error { message, .. } => ("Codex session error", message.as_str()),
+            SessionAlertEvent::TurnAborted { reason, .. } => {
+                ("Codex turn aborted", reason.as_str())
+            }
+            SessionAlertEvent::TaskComplete {
+                last_agent_message, ..
+            } => (
+                "Codex turn complete",
+                last_agent_message.as_deref().unwrap_or("finished"),
+            ),
+        };
+        notify(title, format!("{}: {}", tail.session_id, short(message)));
+    }
+    if tail.take_idle(now, idle_after) {
+        notify(
+            "Codex session idle",
+            format!(
+                "{} has been quiet for {} without finishing the turn",
+                tail.session_id,
+                humantime::format_duration(idle_after)
+            ),
+        );
+    }
+    Ok(())
+}
+
+fn short(message: &str) -> String {
+    let message = message.split_whitespace().collect::<Vec<_>>().join(" ");
+    if message.chars().count() <= MAX_NOTIFICATION_MESSAGE_CHARS {
+        return message;
+    }
+    let mut out = message
+        .chars()
+        .take(MAX_NOTIFICATION_MESSAGE_CHARS)
+        .collect::<String>();
+    out.push('…');
+    out
+}
+
+/// Best-effort toast; the event is always printed so the watch is useful without a backend.
+fn notify(title: &'static str, body: String) {
+    println!("{title}: {body}");
+    if crate::notify::system_notification_backend().is_none() {
+        return;
+    }
+    tokio::task::spawn_blocking(move || {
+        if let Err(error) = crate::notify::send_system_notification(title, &body) {
+            eprintln!("session watch: failed to show system notification: {error}");
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ERROR_LINE: &str = r#"{"timestamp":"2025-12-22T00:00:05.000Z","type":"event_msg","payload":{"type":"error","message":"stream disconnected"}}"#;
+
+    fn tail() -> SessionTail {
+        SessionTail::new("sid".to_string(), PathBuf::from("session.jsonl"), 0)
+    }
+
+    #[test]
+    fn ingest_waits_for_complete_lines_and_skips_duplicates() {
+        let mut tail = tail();
+        let now = Instant::now();
+        let (head, rest) = ERROR_LINE.split_at(40);
+
+        assert!(tail.ingest(head.as_bytes(), now).is_empty());
+        let alerts = tail.ingest(format!("{rest}\n").as_bytes(), now);
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(tail.offset, ERROR_LINE.len() as u64 + 1);
+
+        tail.rewind();
+        assert!(
+            tail.ingest(format!("{ERROR_LINE}\n").as_bytes(), now)
+                .is_empty(),
+            "a rewritten file must not repeat an alert"
+        );
+    }
+
+    #[test]
+    fn completed_turn_is_reported_once_and_never_as_idle() {
+        let mut tail = tail();
+        let start = Instant::now();
+        let idle_after = Duration::from_secs(60);
+        let line = r#"{"timestamp":"2025-12-22T00:00:09.000Z","type":"event_msg","payload":{"type":"task_complete","last_agent_message":"Done."}}"#;
+
+        let alerts = tail.ingest(format!("{line}\n").as_bytes(), start);
+        assert!(matches!(
+            alerts.as_slice(),
+            [SessionAlertEvent::TaskComplete { .. }]
+        ));
+        assert!(!tail.take_idle(start + idle_after, idle_after));
+    }
+
+    #[test]
+    fn seen_alerts_are_bounded() {
+        let mut tail = tail();
+        let now = Instant::now();
+        for index in 0..MAX_SEEN_ALERTS + 10 {
+            let line = format!(
+                r#"{{"timestamp":"{index}","type":"event_msg","payload":{{"type":"error","message":"boom"}}}}"#
+            );
+            assert_eq!(tail.ingest(format!("{line}\n").as_bytes(), now).len(), 1);
+        }
+        assert_eq!(tail.seen.len(), MAX_SEEN_ALERTS);
+        assert_eq!(tail.seen_order.len(), MAX_SEEN_ALERTS);
+    }
+
+    #[test]
+    fn idle_fires_once_per_quiet_period_after_activity() {
+        let mut tail = tail();
+        let start = Instant::now();
+        let idle_after = Duration::from_secs(60);
+        assert!(!tail.take_idle(start + idle_after * 2, idle_after));
+
+        tail.ingest(b"{}\n", start);
+        assert!(!tail.take_idle(start + Duration::from_secs(59), idle_after));
+        assert!(tail.take_idle(start + idle_after, idle_after));
+        assert!(!tail.take_idle(start + idle_after * 2, idle_after));
+
+        let later = start + idle_after * 3;
+        tail.ingest(b"{}\n", later);
+        assert!(tail.take_idle(later + idle_after, idle_after));
+    }
+}
@@ -0,0 +1,43 @@
+//! Status colors for human CLI output, following `ui.tui_theme` like the TUI palette.
+
+use crate::config::{TuiTheme, storage::load_config};
+use owo_colors::{AnsiColors, OwoColorize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Tone {
+    Good,
+    Info,
+    Warn,
+    Bad,
+}
+
+/// Configured theme with `auto` resolved; an unreadable config falls back to auto detection.
+pub(crate) async fn configured_theme() -> TuiTheme {
+    load_config()
+        .await
+        .ok()
+        .and_then(|config| config.ui.tui_theme)
+        .unwrap_or_default()
+        .resolve()
+}
+
+pub(crate) fn paint(theme: TuiTheme, tone: Tone, text: &str) -> String {
+    let color = match (theme, tone) {
+        (TuiTheme::HighContrast, Tone::Good) => AnsiColors::BrightGreen,
+        (TuiTheme::HighContrast, Tone::Info) => AnsiColors::BrightCyan,
+        (TuiTheme::HighContrast, Tone::Warn) => AnsiColors::BrightYellow,
+        (TuiTheme::HighContrast, Tone::Bad) => AnsiColors::BrightRed,
+        // Yellow and cyan wash out on light backgrounds.
+        (TuiTheme::Light, Tone::Info) => AnsiColors::Blue,
+        (TuiTheme::Light, Tone::Warn) => AnsiColors::Magenta,
+        (_, Tone::Good) => AnsiColors::Green,
+        (_, Tone::Info) => AnsiColors::Cyan,
+        (_, Tone::Warn) => AnsiColors::Yellow,
+        (_, Tone::Bad) => AnsiColors::Red,
+    };
+    if theme == TuiTheme::HighContrast {
+        text.color(color).bold().to_string()
+    } else {
+        text.color(color).to_string()
+    }
+}
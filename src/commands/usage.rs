@@ -1,4 +1,4 @@
-use crate::config::load_config;
+use crate::config::{HelperConfig, load_config};
 use crate::control_plane_client::{ControlPlaneClient, ControlPlaneEndpoint};
 use crate::dashboard_core::{
     OperatorReadData, OperatorReadModel, OperatorReadStatus, OperatorRequestSummary,
@@ -6,15 +6,17 @@ use crate::dashboard_core::{
 use crate::relay_target::resolve_relay_target;
 use crate::request_chain::{RequestChainExport, RequestChainSelector};
 use crate::request_ledger::{
-    RequestLedger, RequestLogFilters, RequestUsageSummary, RequestUsageSummaryGroup,
-    RequestUsageSummaryRow,
+    RequestLedger, RequestLogFilters, RequestUsageSummary, RequestUsageSummaryCoverage,
+    RequestUsageSummaryGroup, RequestUsageSummaryRow,
 };
 use crate::runtime_store::{RuntimeStoreError, RuntimeStoreReader};
 use crate::{CliError, CliResult, UsageCommand, UsageSource, UsageSummaryBy};
 use codex_helper_core::runtime_identity::ProviderEndpointKey;
+use codex_helper_core::usage_format::DisplayFormat;
 use codex_helper_core::{quota_analytics as analytics, quota_pool as pool};
 use owo_colors::OwoColorize;
 use std::io::Write;
+use std::path::{Path, PathBuf};
 
 pub async fn handle_usage_cmd(
     cmd: UsageCommand,
@@ -30,14 +32,38 @@ pub async fn handle_usage_cmd(
         }
         Err(cmd) => *cmd,
     };
+    let config = load_config().await.ok();
+    if matches!(cmd, UsageCommand::Summary { json: false, .. }) {
+        print_request_log_sampling_note(config.as_ref());
+    }
+    let display_format = config
+        .as_ref()
+        .map(|config| DisplayFormat::from_ui_config(&config.ui))
+        .unwrap_or_default();
 
     match select_usage_source(source, service_name, model)? {
-        SelectedUsageSource::Runtime(model) => handle_runtime_usage_cmd(cmd, client, *model).await,
-        SelectedUsageSource::Store(reader) => handle_store_usage_cmd(cmd, service_name, &reader),
+        SelectedUsageSource::Runtime(model) => {
+            handle_runtime_usage_cmd(cmd, client, *model, &display_format).await
+        }
+        SelectedUsageSource::Store(reader) => {
+            handle_store_usage_cmd(cmd, service_name, &reader, &display_format)
+        }
         SelectedUsageSource::Empty => Ok(()),
     }
 }
 
+/// With `logging.sample_rate` set, `requests.jsonl` under-counts successes while these totals,
+/// read from the runtime store, stay complete; say so before anyone compares the two.
+fn print_request_log_sampling_note(config: Option<&HelperConfig>) {
+    let Some(rate) = config.and_then(|config| config.logging.active_sample_rate()) else {
+        return;
+    };
+    eprintln!(
+        "Note: logging.sample_rate = {rate} keeps about {:.0}% of successful requests in requests.jsonl; the totals below come from the runtime store and are complete.",
+        rate * 100.0
+    );
+}
+
 enum SelectedUsageSource {
     Runtime(Box<OperatorReadModel>),
     Store(RuntimeStoreReader),
@@ -108,6 +134,7 @@ async fn handle_runtime_usage_cmd(
     cmd: UsageCommand,
     client: &ControlPlaneClient,
     model: OperatorReadModel,
+    display_format: &DisplayFormat,
 ) -> CliResult<()> {
     let data = model
         .data
@@ -121,8 +148,16 @@ async fn handle_runtime_usage_cmd(
     }
 
     match cmd {
-        UsageCommand::Tail { limit, raw } => {
-            let requests = recent_requests(data, limit);
+        UsageCommand::Tail {
+            limit,
+            provider,
+            raw,
+        } => {
+            let filters = OperatorRequestFilters {
+                provider,
+                ..OperatorRequestFilters::default()
+            };
+            let requests = recent_requests(data, &filters, limit);
             for request in requests {
                 if raw {
                     println!(
@@ -136,7 +171,12 @@ async fn handle_runtime_usage_cmd(
                 }
             }
         }
-        UsageCommand::Summary { limit, by } => {
+        UsageCommand::Summary {
+            limit,
+            by,
+            json,
+            output,
+        } => {
             let group = RequestUsageSummaryGroup::from(by);
             let (mut rows, source) = summary_rows(data, by);
             rows.sort_by(|left, right| {
@@ -147,6 +187,20 @@ async fn handle_runtime_usage_cmd(
                     .then_with(|| left.group_value.cmp(&right.group_value))
             });
             rows.truncate(limit);
+            if json {
+                let window = canonical_summary(&data.usage_summaries, by)
+                    .map(|summary| summary.coverage.clone());
+                let document = UsageSummaryDocument::new(
+                    &model.service_name,
+                    "runtime",
+                    group,
+                    limit,
+                    window,
+                    rows,
+                    display_format,
+                );
+                return write_usage_summary_json(&document, output.as_deref());
+            }
 
             println!(
                 "{}",
@@ -160,7 +214,7 @@ async fn handle_runtime_usage_cmd(
             println!(
                 "{}",
                 format!(
-                    "{} | requests | input | output | cache_read | cache_create | reasoning | total | avg_duration_ms",
+                    "{} | requests | input | output | cache_read | cache_create | reasoning | total | avg_duration_ms | avg_ttfb_ms",
                     group.column_name()
                 )
                 .bold()
@@ -251,6 +305,12 @@ async fn handle_runtime_usage_cmd(
         UsageCommand::Quota { target, .. } => {
             return Err(quota_error(&target, "internal_dispatch_error"));
         }
+        UsageCommand::Replay { request_id, .. } => {
+            return Err(replay_dispatch_error(request_id));
+        }
+        UsageCommand::Check { .. } | UsageCommand::Gc { .. } => {
+            return Err(request_log_dispatch_error());
+        }
     }
 
     Ok(())
@@ -260,14 +320,20 @@ fn handle_store_usage_cmd(
     cmd: UsageCommand,
     service_name: &str,
     reader: &RuntimeStoreReader,
+    display_format: &DisplayFormat,
 ) -> CliResult<()> {
     let ledger = RequestLedger::new(reader);
     match cmd {
-        UsageCommand::Tail { limit, raw } => {
+        UsageCommand::Tail {
+            limit,
+            provider,
+            raw,
+        } => {
             let requests = ledger
                 .find_finished_requests(
                     &RequestLogFilters {
                         service: Some(service_name.to_string()),
+                        provider,
                         ..RequestLogFilters::default()
                     },
                     limit,
@@ -275,9 +341,16 @@ fn handle_store_usage_cmd(
                 .map_err(|error| store_usage_error(reader, error))?;
             print_store_requests(requests, raw)?;
         }
-        UsageCommand::Summary { limit, by } => {
+        UsageCommand::Summary {
+            limit,
+            by,
+            json,
+            output,
+        } => {
             let group = RequestUsageSummaryGroup::from(by);
-            let mut rows = ledger
+            let RequestUsageSummary {
+                coverage, mut rows, ..
+            } = ledger
                 .summarize(
                     group,
                     &RequestLogFilters {
@@ -295,6 +368,18 @@ fn handle_store_usage_cmd(
                     }
                 }
             }
+            if json {
+                let document = UsageSummaryDocument::new(
+                    service_name,
+                    "store",
+                    group,
+                    limit,
+                    Some(coverage),
+                    rows,
+                    display_format,
+                );
+                return write_usage_summary_json(&document, output.as_deref());
+            }
 
             println!(
                 "{}",
@@ -308,7 +393,7 @@ fn handle_store_usage_cmd(
             println!(
                 "{}",
                 format!(
-                    "{} | requests | input | output | cache_read | cache_create | reasoning | total | avg_duration_ms",
+                    "{} | requests | input | output | cache_read | cache_create | reasoning | total | avg_duration_ms | avg_ttfb_ms",
                     group.column_name()
                 )
                 .bold()
@@ -386,6 +471,12 @@ fn handle_store_usage_cmd(
         UsageCommand::Quota { target, .. } => {
             return Err(quota_error(&target, "internal_dispatch_error"));
         }
+        UsageCommand::Replay { request_id, .. } => {
+            return Err(replay_dispatch_error(request_id));
+        }
+        UsageCommand::Check { .. } | UsageCommand::Gc { .. } => {
+            return Err(request_log_dispatch_error());
+        }
     }
     Ok(())
 }
@@ -471,8 +562,16 @@ fn print_request_chain_export(
     Ok(())
 }
 
-fn recent_requests(data: &OperatorReadData, limit: usize) -> Vec<&OperatorRequestSummary> {
-    let mut requests = data.recent_requests.iter().collect::<Vec<_>>();
+fn recent_requests<'a>(
+    data: &'a OperatorReadData,
+    filters: &OperatorRequestFilters,
+    limit: usize,
+) -> Vec<&'a OperatorRequestSummary> {
+    let mut requests = data
+        .recent_requests
+        .iter()
+        .filter(|request| filters.matches(request))
+        .collect::<Vec<_>>();
     requests.sort_by_key(|request| std::cmp::Reverse((request.ended_at_ms, request.id)));
     requests.truncate(limit);
     requests
@@ -488,13 +587,19 @@ fn format_operator_request(request: &OperatorRequestSummary) -> String {
         .as_ref()
         .map(|usage| usage.total_tokens)
         .unwrap_or(0);
+    let ttfb = request
+        .observability
+        .ttfb_ms
+        .map(|ttfb_ms| format!("{ttfb_ms}ms"))
+        .unwrap_or_else(|| "-".to_string());
     format!(
-        "[{}] {} {} -> {} ({}ms, provider={}/{}, model={}, tokens={}, session={})",
+        "[{}] {} {} -> {} ({}ms, ttfb={}, provider={}/{}, model={}, tokens={}, session={})",
         request.ended_at_ms,
         request.method,
         request.path,
         request.status_code,
         request.duration_ms,
+        ttfb,
         provider,
         endpoint,
         model,
@@ -510,12 +615,111 @@ fn summary_rows(
     canonical_summary_rows(&data.usage_summaries, by)
 }
 
+/// `usage summary --json` output; `window` is the terminal range the aggregation covered.
+#[derive(Debug, serde::Serialize)]
+struct UsageSummaryDocument {
+    generated_at_ms: u64,
+    generated_at: String,
+    service: String,
+    source: &'static str,
+    by: RequestUsageSummaryGroup,
+    limit: usize,
+    window: Option<RequestUsageSummaryCoverage>,
+    rows: Vec<UsageSummaryJsonRow>,
+}
+
+/// A summary row plus its cost rendered with `ui.currency` / `ui.number_locale`; the raw USD
+/// total stays in `aggregate.cost.total_cost_usd`.
+#[derive(Debug, serde::Serialize)]
+struct UsageSummaryJsonRow {
+    #[serde(flatten)]
+    row: RequestUsageSummaryRow,
+    cost_display: String,
+}
+
+impl UsageSummaryJsonRow {
+    fn new(row: RequestUsageSummaryRow, display_format: &DisplayFormat) -> Self {
+        let cost_display = row.aggregate.cost.display_total(display_format);
+        Self { row, cost_display }
+    }
+}
+
+impl UsageSummaryDocument {
+    fn new(
+        service: &str,
+        source: &'static str,
+        by: RequestUsageSummaryGroup,
+        limit: usize,
+        window: Option<RequestUsageSummaryCoverage>,
+        rows: Vec<RequestUsageSummaryRow>,
+        display_format: &DisplayFormat,
+    ) -> Self {
+        let generated_at_ms = codex_helper_core::logging::now_ms();
+        let generated_at = humantime::format_rfc3339_millis(
+            std::time::UNIX_EPOCH + std::time::Duration::from_millis(generated_at_ms),
+        )
+        .to_string();
+        Self {
+            generated_at_ms,
+            generated_at,
+            service: service.to_string(),
+            source,
+            by,
+            limit,
+            window,
+            rows: rows
+                .into_iter()
+                .map(|row| UsageSummaryJsonRow::new(row, display_format))
+                .collect(),
+        }
+    }
+}
+
+/// Prints the summary to stdout, or replaces `output` atomically so readers never see a partial file.
+fn write_usage_summary_json(
+    document: &UsageSummaryDocument,
+    output: Option<&Path>,
+) -> CliResult<()> {
+    let text = serde_json::to_string_pretty(document)
+        .map_err(|error| CliError::Usage(format!("无法序列化 usage summary: {error}")))?;
+    match output {
+        Some(path) => {
+            let path = absolute_output_path(path)?;
+            codex_helper_core::write_text_file(&path, &format!("{text}\n")).map_err(|error| {
+                CliError::Usage(format!(
+                    "failed to write usage summary to {}: {error:#}",
+                    path.display()
+                ))
+            })?;
+            eprintln!("Wrote usage summary to {}", path.display());
+        }
+        None => println!("{text}"),
+    }
+    Ok(())
+}
+
+fn absolute_output_path(path: &Path) -> CliResult<PathBuf> {
+    std::path::absolute(path).map_err(|error| {
+        CliError::Usage(format!(
+            "invalid usage summary output path {}: {error}",
+            path.display()
+        ))
+    })
+}
+
+fn canonical_summary(
+    summaries: &[RequestUsageSummary],
+    by: UsageSummaryBy,
+) -> Option<&RequestUsageSummary> {
+    let group = RequestUsageSummaryGroup::from(by);
+    summaries.iter().find(|summary| summary.group == group)
+}
+
 fn canonical_summary_rows(
     summaries: &[RequestUsageSummary],
     by: UsageSummaryBy,
 ) -> (Vec<RequestUsageSummaryRow>, String) {
-    let group = RequestUsageSummaryGroup::from(by);
-    let Some(summary) = summaries.iter().find(|summary| summary.group == group) else {
+    let Some(summary) = canonical_summary(summaries, by) else {
         return (
             Vec::new(),
             "committed operator ledger projection unavailable".to_string(),
@@ -583,6 +787,264 @@ fn contains_optional(value: Option<&str>, expected: Option<&str>) -> bool {
     })
 }
 
+const REPLAY_BODY_CAPTURE_HINT: &str = "Request bodies are only captured when the proxy runs with CODEX_HELPER_HTTP_DEBUG=1 and CODEX_HELPER_HTTP_LOG_REQUEST_BODY=1 (add CODEX_HELPER_HTTP_DEBUG_ALL=1 to also capture successful requests). Restart the proxy with those variables, reproduce the request, then replay its new request id.";
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+struct ReplayOriginal {
+    request_id: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    trace_id: Option<String>,
+    method: String,
+    path: String,
+    status_code: u64,
+    duration_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    provider_id: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+struct ReplayOutcome {
+    status_code: u16,
+    duration_ms: u64,
+    response_bytes: usize,
+}
+
+#[derive(Debug)]
+struct ReplayRequest {
+    original: ReplayOriginal,
+    client_uri: String,
+    content_type: Option<String>,
+    body: Vec<u8>,
+}
+
+pub async fn handle_usage_replay(request_id: u64, json: bool, port: u16) -> CliResult<()> {
+    let request = load_replay_request(&crate::logging::request_log_path(), request_id)?;
+    let url = format!("http://127.0.0.1:{port}{}", request.client_uri);
+    let method = reqwest::Method::from_bytes(request.original.method.as_bytes()).map_err(|_| {
+        CliError::Usage(format!(
+            "request {request_id} has an unsupported method {:?}",
+            request.original.method
+        ))
+    })?;
+
+    let client = reqwest::Client::new();
+    let mut builder = client.request(method, &url).body(request.body);
+    if let Some(content_type) = request.content_type.as_deref() {
+        builder = builder.header(reqwest::header::CONTENT_TYPE, content_type);
+    }
+    let started = std::time::Instant::now();
+    let response = builder.send().await.map_err(|error| {
+        CliError::Usage(format!(
+            "replay of request {request_id} could not reach the local proxy at {url}: {error}"
+        ))
+    })?;
+    let status_code = response.status().as_u16();
+    let response_body = response.bytes().await.map_err(|error| {
+        CliError::Usage(format!(
+            "replay of request {request_id} failed while reading the response: {error}"
+        ))
+    })?;
+    let replay = ReplayOutcome {
+        status_code,
+        duration_ms: started.elapsed().as_millis() as u64,
+        response_bytes: response_body.len(),
+    };
+
+    let mut stdout = std::io::stdout();
+    write_replay_comparison(&request.original, &replay, json, &mut stdout)
+}
+
+fn load_replay_request(request_log: &std::path::Path, request_id: u64) -> CliResult<ReplayRequest> {
+    let entry = find_newest_json_line(request_log, |entry| {
+        entry.get("request_id").and_then(serde_json::Value::as_u64) == Some(request_id)
+    })?
+    .ok_or_else(|| {
+        CliError::Usage(format!(
+            "request {request_id} was not found in {}",
+            request_log.display()
+        ))
+    })?;
+
+    let str_field = |name: &str| {
+        entry
+            .get(name)
+            .and_then(serde_json::Value::as_str)
+            .map(str::to_string)
+    };
+    let original = ReplayOriginal {
+        request_id,
+        trace_id: str_field("trace_id"),
+        method: str_field("method").unwrap_or_else(|| "POST".to_string()),
+        path: str_field("path").unwrap_or_else(|| "/".to_string()),
+        status_code: entry
+            .get("status_code")
+            .and_then(serde_json::Value::as_u64)
+            .unwrap_or_default(),
+        duration_ms: entry
+            .get("duration_ms")
+            .and_then(serde_json::Value::as_u64)
+            .unwrap_or_default(),
+        provider_id: str_field("provider_id"),
+    };
+
+    let http_debug = match entry.get("http_debug") {
+        Some(http_debug) => Some(http_debug.clone()),
+        None => match entry
+            .get("http_debug_ref")
+            .and_then(|reference| reference.get("id"))
+            .and_then(serde_json::Value::as_str)
+        {
+            Some(debug_id) => {
+                let debug_log = request_log.with_file_name("requests_debug.jsonl");
+                find_newest_json_line(&debug_log, |debug| {
+                    debug.get("id").and_then(serde_json::Value::as_str) == Some(debug_id)
+                })?
+                .and_then(|mut debug| debug.get_mut("http_debug").map(serde_json::Value::take))
+            }
+            None => None,
+        },
+    };
+    let Some(http_debug) = http_debug else {
+        return Err(CliError::Usage(format!(
+            "request {request_id} has no HTTP debug record, so its body cannot be replayed. {REPLAY_BODY_CAPTURE_HINT}"
+        )));
+    };
+    let Some(preview) = http_debug.get("client_body").filter(|body| !body.is_null()) else {
+        return Err(CliError::Usage(format!(
+            "request {request_id} was logged without its request body, so it cannot be replayed. {REPLAY_BODY_CAPTURE_HINT}"
+        )));
+    };
+
+    let original_len = preview
+        .get("original_len")
+        .and_then(serde_json::Value::as_u64)
+        .unwrap_or_default();
+    if preview
+        .get("truncated")
+        .and_then(serde_json::Value::as_bool)
+        .unwrap_or(false)
+    {
+        return Err(CliError::Usage(format!(
+            "the captured body of request {request_id} was truncated ({original_len} bytes originally); set CODEX_HELPER_HTTP_DEBUG_BODY_MAX above {original_len}, reproduce the request, then replay its new request id"
+        )));
+    }
+    let data = preview
+        .get("data")
+        .and_then(serde_json::Value::as_str)
+        .unwrap_or_default();
+    let body = match preview.get("encoding").and_then(serde_json::Value::as_str) {
+        Some("base64") => {
+            use base64::Engine as _;
+            base64::engine::general_purpose::STANDARD
+                .decode(data)
+                .map_err(|error| {
+                    CliError::Usage(format!(
+                        "the captured body of request {request_id} is not valid base64: {error}"
+                    ))
+                })?
+        }
+        _ => data.as_bytes().to_vec(),
+    };
+
+    let client_uri = http_debug
+        .get("client_uri")
+        .and_then(serde_json::Value::as_str)
+        .filter(|uri| uri.starts_with('/'))
+        .map(str::to_string)
+        .unwrap_or_else(|| original.path.clone());
+    let content_type = preview
+        .get("content_type")
+        .and_then(serde_json::Value::as_str)
+        .map(str::to_string);
+
+    Ok(ReplayRequest {
+        original,
+        client_uri,
+        content_type,
+        body,
+    })
+}
+
+fn find_newest_json_line(
+    path: &std::path::Path,
+    matches: impl Fn(&serde_json::Value) -> bool,
+) -> CliResult<Option<serde_json::Value>> {
+    let text = match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(error) => {
+            return Err(CliError::Usage(format!(
+                "无法读取 {}: {error}",
+                path.display()
+            )));
+        }
+    };
+    Ok(text
+        .lines()
+        .rev()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .find(|value| matches(value)))
+}
+
+fn write_replay_comparison(
+    original: &ReplayOriginal,
+    replay: &ReplayOutcome,
+    json: bool,
+    writer: &mut dyn Write,
+) -> CliResult<()> {
+    let write_error = |error: std::io::Error| CliError::Usage(format!("无法写入输出: {error}"));
+    if json {
+        let payload = serde_json::json!({
+            "original": original,
+            "replay": replay,
+        });
+        let text = serde_json::to_string_pretty(&payload)
+            .map_err(|error| CliError::Usage(format!("无法序列化 replay 结果: {error}")))?;
+        return writeln!(writer, "{text}").map_err(write_error);
+    }
+
+    writeln!(
+        writer,
+        "Replay of request {} ({} {}) trace={}",
+        original.request_id,
+        original.method,
+        original.path,
+        original.trace_id.as_deref().unwrap_or("-"),
+    )
+    .map_err(write_error)?;
+    writeln!(writer, "{:<12} {:>10} {:>10}", "", "original", "replay").map_err(write_error)?;
+    writeln!(
+        writer,
+        "{:<12} {:>10} {:>10}",
+        "status", original.status_code, replay.status_code
+    )
+    .map_err(write_error)?;
+    writeln!(
+        writer,
+        "{:<12} {:>10} {:>10}",
+        "duration_ms", original.duration_ms, replay.duration_ms
+    )
+    .map_err(write_error)?;
+    writeln!(
+        writer,
+        "original provider={}; replay response_bytes={}",
+        original.provider_id.as_deref().unwrap_or("-"),
+        replay.response_bytes
+    )
+    .map_err(write_error)?;
+    Ok(())
+}
+
+fn replay_dispatch_error(request_id: u64) -> CliError {
+    CliError::Usage(format!(
+        "usage replay {request_id} must be dispatched to the local proxy"
+    ))
+}
+
+fn request_log_dispatch_error() -> CliError {
+    CliError::Usage("usage gc/check must be dispatched to the local request log".to_string())
+}
+
 fn extract_quota_command(cmd: UsageCommand) -> Result<(String, bool), Box<UsageCommand>> {
     match cmd {
         UsageCommand::Quota { target, json } => Ok((target, json)),
@@ -881,6 +1343,7 @@ impl From<UsageSummaryBy> for RequestUsageSummaryGroup {
             UsageSummaryBy::Provider => Self::Provider,
             UsageSummaryBy::Model => Self::Model,
             UsageSummaryBy::Session => Self::Session,
+            UsageSummaryBy::Tenant => Self::Tenant,
         }
     }
 }
@@ -891,7 +1354,7 @@ mod tests {
     use crate::cli_types::{Cli, Command};
     use crate::commands::test_support::{ScopedEnv, TempTestDir, env_lock};
     use crate::dashboard_core::OperatorRequestObservability;
-    use crate::pricing::CostBreakdown;
+    use crate::pricing::{CostBreakdown, CostConfidence};
     use crate::request_ledger::{RequestUsageAggregate, RequestUsageSummaryCoverage};
     use crate::usage::UsageMetrics;
     use axum::http::{HeaderMap, Uri};
@@ -899,7 +1362,7 @@ mod tests {
     use axum::{Json, Router};
     use clap::Parser;
     use codex_helper_core::config::{
-        HelperConfig, RelayTargetConfig, ServiceKind, save_helper_config,
+        CurrencyDisplayConfig, RelayTargetConfig, ServiceKind, UiConfig, save_helper_config,
     };
     use codex_helper_core::dashboard_core::{ApiV1OperatorSummary, OperatorRevisionBundle};
     use codex_helper_core::proxy::ADMIN_TOKEN_HEADER;
@@ -919,6 +1382,8 @@ mod tests {
             id: 7,
             trace_key: None,
             session_key: Some("session:sha256:abc".to_string()),
+            body_hash: None,
+            upstream_request_id: None,
             model: Some("gpt-5.6".to_string()),
             reasoning_effort: Some("high".to_string()),
             service_tier: Some("priority".to_string()),
@@ -1022,6 +1487,84 @@ mod tests {
         }
     }
 
+    #[tokio::test(flavor = "current_thread")]
+    async fn store_summary_json_output_replaces_the_file_with_window_and_timestamp() {
+        let _env_lock = env_lock().await;
+        let helper_home = TempTestDir::new("codex-helper-cli-test-usage-summary-json");
+        let mut scoped_env = ScopedEnv::default();
+        unsafe {
+            scoped_env.set_path("CODEX_HELPER_HOME", helper_home.path());
+        }
+        drop(RuntimeStore::open_in_home(helper_home.path()).expect("create runtime store"));
+        let reader = RuntimeStoreReader::open_default().expect("open runtime store reader");
+        let output = helper_home.path().join("usage-summary.json");
+        std::fs::write(&output, "stale partial").expect("seed previous output");
+
+        handle_store_usage_cmd(
+            UsageCommand::Summary {
+                limit: 5,
+                by: UsageSummaryBy::Model,
+                json: true,
+                output: Some(output.clone()),
+            },
+            "codex",
+            &reader,
+            &DisplayFormat::default(),
+        )
+        .expect("write usage summary json");
+
+        let value: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&output).expect("read output"))
+                .expect("output is complete JSON");
+        assert!(value["generated_at_ms"].as_u64().is_some_and(|ms| ms > 0));
+        assert!(
+            value["generated_at"]
+                .as_str()
+                .is_some_and(|ts| ts.ends_with('Z'))
+        );
+        assert_eq!(value["service"], "codex");
+        assert_eq!(value["source"], "store");
+        assert_eq!(value["by"], "model");
+        assert_eq!(value["limit"], 5);
+        assert_eq!(value["window"]["all_history"], true);
+        assert_eq!(value["window"]["requests"], 0);
+        assert!(value["window"]["first_terminal_at_ms"].is_null());
+        assert_eq!(value["rows"], serde_json::json!([]));
+    }
+
+    #[test]
+    fn summary_json_rows_carry_the_raw_cost_and_a_display_string() {
+        let mut aggregate = RequestUsageAggregate {
+            requests: 1,
+            ..RequestUsageAggregate::default()
+        };
+        let mut cost = CostBreakdown::default();
+        cost.total_cost_usd = Some("1234.5".to_string());
+        cost.confidence = CostConfidence::Estimated;
+        aggregate.cost.record_usage_cost(&cost);
+        let ui = UiConfig {
+            currency: Some(CurrencyDisplayConfig {
+                symbol: "€".to_string(),
+                code: None,
+            }),
+            number_locale: Some("de".to_string()),
+            ..Default::default()
+        };
+        let row = UsageSummaryJsonRow::new(
+            RequestUsageSummaryRow {
+                group_value: "gpt-5".to_string(),
+                aggregate,
+            },
+            &DisplayFormat::from_ui_config(&ui),
+        );
+
+        let value = serde_json::to_value(&row).expect("serialize summary row");
+        assert_eq!(value["group_value"], "gpt-5");
+        assert_eq!(value["aggregate"]["cost"]["total_cost_usd"], "1234.5");
+        assert_eq!(value["aggregate"]["cost"]["confidence"], "estimated");
+        assert_eq!(value["cost_display"], "€1.234,5");
+    }
+
     #[tokio::test(flavor = "current_thread")]
     async fn auto_usage_source_treats_a_missing_store_as_empty_when_runtime_is_unavailable() {
         let _env_lock = env_lock().await;
@@ -1046,6 +1589,7 @@ mod tests {
         handle_usage_cmd(
             UsageCommand::Tail {
                 limit: 20,
+                provider: None,
                 raw: true,
             },
             UsageSource::Auto,
@@ -1139,6 +1683,7 @@ mod tests {
         handle_usage_cmd(
             UsageCommand::Tail {
                 limit: 20,
+                provider: None,
                 raw: true,
             },
             UsageSource::Store,
@@ -1172,6 +1717,7 @@ mod tests {
                     cache_read_input_tokens: 100,
                     cache_creation_input_tokens: 200,
                     total_tokens: 1_020,
+                    ..RequestUsageAggregate::default()
                 },
             }],
         }];
@@ -1278,6 +1824,7 @@ mod tests {
                 usage_rollup: Default::default(),
                 stats_5m: Default::default(),
                 stats_1h: Default::default(),
+                request_quotas: Vec::new(),
                 pricing_catalog: Default::default(),
                 service_status: None,
                 provider_balances: Vec::new(),
@@ -1335,6 +1882,7 @@ mod tests {
         assert!(
             extract_quota_command(UsageCommand::Tail {
                 limit: 20,
+                provider: None,
                 raw: false,
             })
             .is_err()
@@ -1501,4 +2049,102 @@ mod tests {
         );
         assert!(empty.contains("No quota pools reported"));
     }
+
+    #[test]
+    fn usage_replay_requires_an_explicit_request_id() {
+        assert!(Cli::try_parse_from(["codex-helper", "usage", "replay"]).is_err());
+
+        let cli = Cli::try_parse_from(["codex-helper", "usage", "replay", "42", "--json"])
+            .expect("parse usage replay command");
+        let Some(Command::Usage { cmd, .. }) = cli.command else {
+            panic!("expected usage command");
+        };
+        assert!(matches!(
+            cmd,
+            UsageCommand::Replay {
+                request_id: 42,
+                json: true
+            }
+        ));
+    }
+
+    #[test]
+    fn replay_request_resolves_split_debug_body_for_the_newest_matching_entry() {
+        let dir = TempTestDir::new("codex-helper-cli-test-usage-replay");
+        let request_log = dir.path().join("requests.jsonl");
+        std::fs::write(
+            &request_log,
+            [
+                r#"{"request_id":7,"method":"POST","path":"/v1/responses","status_code":200,"duration_ms":5}"#,
+                r#"{"request_id":7,"trace_id":"t-7","method":"POST","path":"/v1/responses","status_code":502,"duration_ms":1200,"provider_id":"alpha","http_debug_ref":{"id":"d-1","file":"requests_debug.jsonl"}}"#,
+                r#"{"request_id":8,"method":"POST","path":"/v1/responses","status_code":200,"duration_ms":9}"#,
+                // A final line cut short by a crash is skipped, not an error.
+                r#"{"request_id":7,"method":"POST","pa"#,
+            ]
+            .join("\n"),
+        )
+        .expect("write request log");
+        std::fs::write(
+            dir.path().join("requests_debug.jsonl"),
+            r#"{"id":"d-1","request_id":7,"http_debug":{"client_uri":"/v1/responses?stream=true","client_headers":[],"upstream_request_headers":[],"client_body":{"content_type":"application/json","encoding":"utf8","data":"{\"model\":\"gpt\"}","truncated":false,"original_len":15}}}"#,
+        )
+        .expect("write debug log");
+
+        let request = load_replay_request(&request_log, 7).expect("load replay request");
+
+        assert_eq!(request.original.status_code, 502);
+        assert_eq!(request.original.trace_id.as_deref(), Some("t-7"));
+        assert_eq!(request.client_uri, "/v1/responses?stream=true");
+        assert_eq!(request.content_type.as_deref(), Some("application/json"));
+        assert_eq!(request.body, br#"{"model":"gpt"}"#);
+
+        let mut out = Vec::new();
+        write_replay_comparison(
+            &request.original,
+            &ReplayOutcome {
+                status_code: 200,
+                duration_ms: 800,
+                response_bytes: 64,
+            },
+            false,
+            &mut out,
+        )
+        .expect("write replay comparison");
+        let text = String::from_utf8(out).expect("UTF-8 output");
+        assert!(text.contains("Replay of request 7 (POST /v1/responses) trace=t-7"));
+        assert!(text.contains("status              502        200"));
+        assert!(text.contains("duration_ms        1200        800"));
+    }
+
+    #[test]
+    fn replay_request_explains_how_to_capture_missing_or_truncated_bodies() {
+        let dir = TempTestDir::new("codex-helper-cli-test-usage-replay");
+        let request_log = dir.path().join("requests.jsonl");
+        std::fs::write(
+            &request_log,
+            [
+                r#"{"request_id":1,"method":"POST","path":"/v1/responses","status_code":502,"duration_ms":5}"#,
+                r#"{"request_id":2,"method":"POST","path":"/v1/responses","status_code":502,"duration_ms":5,"http_debug":{"client_uri":"/v1/responses","client_headers":[],"upstream_request_headers":[],"client_body":{"encoding":"utf8","data":"{","truncated":true,"original_len":90000}}}"#,
+            ]
+            .join("\n"),
+        )
+        .expect("write request log");
+
+        let missing = load_replay_request(&request_log, 1).expect_err("body was not captured");
+        assert!(
+            missing
+                .to_string()
+                .contains("CODEX_HELPER_HTTP_LOG_REQUEST_BODY=1")
+        );
+
+        let truncated = load_replay_request(&request_log, 2).expect_err("body was truncated");
+        assert!(
+            truncated
+                .to_string()
+                .contains("CODEX_HELPER_HTTP_DEBUG_BODY_MAX above 90000")
+        );
+
+        let unknown = load_replay_request(&request_log, 3).expect_err("request is unknown");
+        assert!(unknown.to_string().contains("request 3 was not found"));
+    }
 }
@@ -0,0 +1,197 @@
+use std::path::Path;
+
+use serde_json::Value;
+
+use super::usage_gc::{LogRewrite, gc_error};
+use crate::CliResult;
+
+#[derive(Debug, Default, PartialEq, Eq)]
+struct UsageCheckReport {
+    lines: usize,
+    parsed: usize,
+    unparseable: usize,
+    /// 1-based line number of the first line that is not valid JSON.
+    first_bad_line: Option<usize>,
+    /// The file ends without a newline, typically a write cut short by a crash.
+    partial_last_line: bool,
+    fixed: bool,
+}
+
+pub fn handle_usage_check(fix: bool) -> CliResult<()> {
+    let request_log = crate::logging::request_log_path();
+    let report = check_request_log(&request_log, fix)?;
+    let name = request_log.display();
+    if report.lines == 0 {
+        println!("{name}: no entries");
+        return Ok(());
+    }
+    println!(
+        "{name}: {} lines, {} parseable, {} unparseable",
+        report.lines, report.parsed, report.unparseable
+    );
+    let Some(first_bad_line) = report.first_bad_line else {
+        return Ok(());
+    };
+    println!(
+        "First unparseable line: {first_bad_line}{}",
+        if report.partial_last_line {
+            " (the last line is incomplete)"
+        } else {
+            ""
+        }
+    );
+    if report.fixed {
+        println!(
+            "Dropped {} unparseable line(s) and rewrote the file.",
+            report.unparseable
+        );
+    } else {
+        println!("Nothing was changed; re-run with --fix to drop the unparseable lines.");
+    }
+    Ok(())
+}
+
+fn check_request_log(path: &Path, fix: bool) -> CliResult<UsageCheckReport> {
+    let text = match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+            return Ok(UsageCheckReport::default());
+        }
+        Err(error) => return Err(gc_error(path, "read", error)),
+    };
+    let mut report = UsageCheckReport::default();
+    let mut kept = Vec::new();
+    for (index, segment) in text.split_inclusive('\n').enumerate() {
+        let line = segment.strip_suffix('\n');
+        let terminated = line.is_some();
+        let line = line.unwrap_or(segment);
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        if line.trim().is_empty() {
+            continue;
+        }
+        report.lines += 1;
+        let parsed = serde_json::from_str::<Value>(line).is_ok();
+        if parsed {
+            report.parsed += 1;
+        } else {
+            report.unparseable += 1;
+            report.first_bad_line.get_or_insert(index + 1);
+        }
+        // A complete final line that only lacks its newline is not worth a rewrite on its own.
+        report.partial_last_line = !terminated && !parsed;
+        // The proxy may still be appending an unterminated final line; the commit decides
+        // whether it is carried over.
+        if parsed && terminated {
+            kept.push(line);
+        }
+    }
+    if !fix || report.unparseable == 0 {
+        return Ok(report);
+    }
+
+    let mut rewrite = LogRewrite::create(path)?;
+    for line in kept {
+        rewrite.push_line(line)?;
+    }
+    let complete_len = text.rfind('\n').map_or(0, |newline| newline + 1) as u64;
+    if report.partial_last_line {
+        rewrite.commit_dropping_stale_tail(complete_len, text.len() as u64)?;
+    } else {
+        rewrite.commit(complete_len)?;
+    }
+    report.fixed = true;
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::TempTestDir;
+
+    fn entry(request_id: u64) -> String {
+        serde_json::json!({
+            "request_id": request_id,
+            "timestamp_ms": request_id * 1_000,
+            "service": "codex",
+            "status_code": 200,
+        })
+        .to_string()
+    }
+
+    fn write_log(dir: &TempTestDir, text: &str) -> std::path::PathBuf {
+        let path = dir.path().join("requests.jsonl");
+        std::fs::write(&path, text).expect("write request log");
+        path
+    }
+
+    #[test]
+    fn check_reports_a_truncated_final_line_without_touching_the_file() {
+        let dir = TempTestDir::new("codex-helper-cli-test-usage-check");
+        let truncated = &entry(3)[..20];
+        let text = format!("{}\n{}\n{truncated}", entry(1), entry(2));
+        let path = write_log(&dir, &text);
+
+        let report = check_request_log(&path, false).expect("check");
+
+        assert_eq!(
+            report,
+            UsageCheckReport {
+                lines: 3,
+                parsed: 2,
+                unparseable: 1,
+                first_bad_line: Some(3),
+                partial_last_line: true,
+                fixed: false,
+            }
+        );
+        assert_eq!(std::fs::read_to_string(&path).expect("read log"), text);
+    }
+
+    #[test]
+    fn fix_drops_garbage_and_the_truncated_final_line() {
+        let dir = TempTestDir::new("codex-helper-cli-test-usage-check");
+        let truncated = &entry(4)[..20];
+        let path = write_log(
+            &dir,
+            &format!(
+                "{}\nnot json\n\n{}\n{}\n{truncated}",
+                entry(1),
+                entry(2),
+                entry(3)
+            ),
+        );
+
+        let report = check_request_log(&path, true).expect("fix");
+
+        assert_eq!(report.lines, 5);
+        assert_eq!(report.unparseable, 2);
+        assert_eq!(report.first_bad_line, Some(2));
+        assert!(report.fixed);
+        assert_eq!(
+            std::fs::read_to_string(&path).expect("read log"),
+            format!("{}\n{}\n{}\n", entry(1), entry(2), entry(3))
+        );
+        assert_eq!(
+            check_request_log(&path, false).expect("recheck"),
+            UsageCheckReport {
+                lines: 3,
+                parsed: 3,
+                ..UsageCheckReport::default()
+            }
+        );
+    }
+
+    #[test]
+    fn fix_leaves_a_clean_log_without_a_final_newline_alone() {
+        let dir = TempTestDir::new("codex-helper-cli-test-usage-check");
+        let text = format!("{}\n{}", entry(1), entry(2));
+        let path = write_log(&dir, &text);
+
+        let report = check_request_log(&path, true).expect("fix");
+
+        assert_eq!(report.unparseable, 0);
+        assert!(!report.partial_last_line);
+        assert!(!report.fixed);
+        assert_eq!(std::fs::read_to_string(&path).expect("read log"), text);
+    }
+}
@@ -0,0 +1,665 @@
+use std::collections::BTreeMap;
+use std::io::{BufRead, BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{CliError, CliResult};
+
+const DAILY_AGGREGATES_FILE: &str = "requests_daily.jsonl";
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UsageGcOptions {
+    pub older_than: Option<Duration>,
+    pub keep: Option<usize>,
+    pub compact: bool,
+    pub apply: bool,
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+struct UsageGcReport {
+    entries: usize,
+    dropped: usize,
+    bytes_before: u64,
+    bytes_after: u64,
+    compacted_days: usize,
+}
+
+/// One `requests_daily.jsonl` line: every pruned request of a day, service, provider and model.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct DailyUsageAggregate {
+    date: String,
+    service: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    provider_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    model: Option<String>,
+    requests: u64,
+    errors: u64,
+    input_tokens: i64,
+    output_tokens: i64,
+    total_tokens: i64,
+}
+
+type AggregateKey = (String, String, Option<String>, Option<String>);
+
+pub fn handle_usage_gc(options: UsageGcOptions) -> CliResult<()> {
+    let request_log = crate::logging::request_log_path();
+    let report = gc_request_log(&request_log, options, crate::logging::now_ms())?;
+    let name = request_log.display();
+    if report.entries == 0 {
+        println!("{name}: nothing to prune");
+        return Ok(());
+    }
+    println!(
+        "{name}: {} entries, {} {} dropped, {} kept",
+        report.entries,
+        report.dropped,
+        if options.apply { "were" } else { "would be" },
+        report.entries - report.dropped
+    );
+    println!(
+        "{} {} ({} -> {})",
+        if options.apply {
+            "Reclaimed"
+        } else {
+            "Would reclaim"
+        },
+        format_bytes(report.bytes_before.saturating_sub(report.bytes_after)),
+        format_bytes(report.bytes_before),
+        format_bytes(report.bytes_after)
+    );
+    if options.compact && report.dropped > 0 {
+        println!(
+            "{} dropped entries into {} daily aggregate(s) in {}",
+            if options.apply {
+                "Rolled"
+            } else {
+                "Would roll"
+            },
+            report.compacted_days,
+            aggregates_path(&request_log).display()
+        );
+    }
+    if !options.apply && report.dropped > 0 {
+        println!("Dry run: nothing was changed; re-run with --yes to rewrite the file.");
+    }
+    Ok(())
+}
+
+fn gc_request_log(path: &Path, options: UsageGcOptions, now_ms: u64) -> CliResult<UsageGcReport> {
+    let len = match std::fs::metadata(path) {
+        Ok(metadata) => metadata.len(),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+            return Ok(UsageGcReport::default());
+        }
+        Err(error) => return Err(gc_error(path, "stat", error)),
+    };
+    let read_len = complete_lines_len(path, len)?;
+    let mut parsed = 0;
+    if options.keep.is_some() {
+        // `keep` counts from the end, so the entries are counted in a first pass.
+        for_each_log_line(path, read_len, |line| {
+            parsed += usize::from(serde_json::from_str::<Value>(line).is_ok());
+            Ok(())
+        })?;
+    }
+    let cutoff_ms = options
+        .older_than
+        .map(|age| now_ms.saturating_sub(age.as_millis() as u64));
+    let first_kept_by_count = options.keep.map_or(0, |keep| parsed.saturating_sub(keep));
+
+    let mut rewrite = if options.apply {
+        Some(LogRewrite::create(path)?)
+    } else {
+        None
+    };
+    let mut aggregates = BTreeMap::<AggregateKey, DailyUsageAggregate>::new();
+    let mut report = UsageGcReport {
+        bytes_before: len,
+        // The unterminated tail is carried over untouched.
+        bytes_after: len - read_len,
+        ..UsageGcReport::default()
+    };
+    let mut keep_line = |line: &str, report: &mut UsageGcReport| -> CliResult<()> {
+        report.bytes_after += line.len() as u64 + 1;
+        match rewrite.as_mut() {
+            Some(rewrite) => rewrite.push_line(line),
+            None => Ok(()),
+        }
+    };
+    for_each_log_line(path, read_len, |line| {
+        // Lines that do not parse are kept untouched; gc only drops what it can date.
+        let Ok(entry) = serde_json::from_str::<Value>(line) else {
+            return keep_line(line, &mut report);
+        };
+        let timestamp_ms = entry.get("timestamp_ms").and_then(Value::as_u64);
+        let expired = cutoff_ms
+            .zip(timestamp_ms)
+            .is_some_and(|(cutoff, ts)| ts < cutoff);
+        let over_count = report.entries < first_kept_by_count;
+        report.entries += 1;
+        if !expired && !over_count {
+            return keep_line(line, &mut report);
+        }
+        report.dropped += 1;
+        if options.compact {
+            add_to_daily_aggregate(&mut aggregates, &entry, timestamp_ms.unwrap_or_default());
+        }
+        Ok(())
+    })?;
+    report.compacted_days = aggregates.len();
+    let Some(rewrite) = rewrite.filter(|_| report.dropped > 0) else {
+        return Ok(report);
+    };
+
+    report.bytes_after = rewrite.commit(read_len)?;
+    // Only once the pruned log is in place, so a failed rewrite never double-counts a day.
+    if options.compact {
+        merge_daily_aggregates(&aggregates_path(path), aggregates)?;
+    }
+    Ok(report)
+}
+
+/// `len` cut back to just past the last newline in the first `len` bytes of `path`. The proxy
+/// may still be appending the line after it; scans stop there and the commit carries it over.
+fn complete_lines_len(path: &Path, len: u64) -> CliResult<u64> {
+    let mut file = std::fs::File::open(path).map_err(|error| gc_error(path, "read", error))?;
+    let mut buf = [0; 8 * 1024];
+    let mut end = len;
+    while end > 0 {
+        let start = end.saturating_sub(buf.len() as u64);
+        let chunk = &mut buf[..(end - start) as usize];
+        file.seek(SeekFrom::Start(start))
+            .and_then(|_| file.read_exact(chunk))
+            .map_err(|error| gc_error(path, "read", error))?;
+        if let Some(newline) = chunk.iter().rposition(|byte| *byte == b'\n') {
+            return Ok(start + newline as u64 + 1);
+        }
+        end = start;
+    }
+    Ok(0)
+}
+
+/// Calls `visit` with each non-blank line among the first `read_len` bytes, without the newline.
+pub(super) fn for_each_log_line(
+    path: &Path,
+    read_len: u64,
+    mut visit: impl FnMut(&str) -> CliResult<()>,
+) -> CliResult<()> {
+    let file = std::fs::File::open(path).map_err(|error| gc_error(path, "read", error))?;
+    let mut reader = BufReader::new(file.take(read_len));
+    let mut buf = String::new();
+    loop {
+        buf.clear();
+        let read = reader
+            .read_line(&mut buf)
+            .map_err(|error| gc_error(path, "read", error))?;
+        if read == 0 {
+            return Ok(());
+        }
+        let line = buf.strip_suffix('\n').unwrap_or(&buf);
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        if !line.trim().is_empty() {
+            visit(line)?;
+        }
+    }
+}
+
+/// A replacement for a log the proxy may still be appending to. Kept lines stream into a temp
+/// file beside the log; `commit` swaps it in. The temp file is removed if it never is.
+pub(super) struct LogRewrite {
+    path: PathBuf,
+    temp_path: PathBuf,
+    writer: BufWriter<std::fs::File>,
+    bytes: u64,
+    committed: bool,
+}
+
+impl LogRewrite {
+    pub(super) fn create(path: &Path) -> CliResult<Self> {
+        let mut temp_name = path.as_os_str().to_owned();
+        temp_name.push(".rewrite");
+        let temp_path = PathBuf::from(temp_name);
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let file = options
+            .open(&temp_path)
+            .map_err(|error| gc_error(&temp_path, "create", error))?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            temp_path,
+            writer: BufWriter::new(file),
+            bytes: 0,
+            committed: false,
+        })
+    }
+
+    pub(super) fn push_line(&mut self, line: &str) -> CliResult<()> {
+        self.writer
+            .write_all(line.as_bytes())
+            .and_then(|()| self.writer.write_all(b"\n"))
+            .map_err(|error| gc_error(&self.temp_path, "write", error))?;
+        self.bytes += line.len() as u64 + 1;
+        Ok(())
+    }
+
+    /// Holds the log's write lock while it copies everything appended after the first
+    /// `read_len` bytes and renames the temp file over the log. Returns the new size.
+    pub(super) fn commit(self, read_len: u64) -> CliResult<u64> {
+        self.commit_after(read_len, read_len)
+    }
+
+    /// Like [`Self::commit`] for a read of `read_len` bytes that ended in an unterminated line
+    /// starting at `complete_len`. Under the lock no append is half-written, so if the log has
+    /// not grown the fragment is a leftover that is dropped; otherwise the proxy was still
+    /// writing it and it is copied over whole.
+    pub(super) fn commit_dropping_stale_tail(
+        self,
+        complete_len: u64,
+        read_len: u64,
+    ) -> CliResult<u64> {
+        self.commit_after(complete_len, read_len)
+    }
+
+    fn commit_after(mut self, complete_len: u64, read_len: u64) -> CliResult<u64> {
+        let path = self.path.clone();
+        let _lock = codex_helper_core::local_log_store::lock_log_file(&path)
+            .map_err(|error| gc_error(&path, "lock", error))?;
+        let mut file =
+            std::fs::File::open(&path).map_err(|error| gc_error(&path, "reopen", error))?;
+        let current_len = file
+            .metadata()
+            .map_err(|error| gc_error(&path, "stat", error))?
+            .len();
+        if current_len < read_len {
+            return Err(CliError::Usage(format!(
+                "{} was rotated while it was being rewritten; nothing was changed, re-run the command",
+                path.display()
+            )));
+        }
+        let copy_from = if current_len == read_len {
+            read_len
+        } else {
+            complete_len
+        };
+        let copied = file
+            .seek(SeekFrom::Start(copy_from))
+            .and_then(|_| std::io::copy(&mut file, &mut self.writer))
+            .map_err(|error| gc_error(&path, "copy new entries of", error))?;
+        self.bytes += copied;
+        self.writer
+            .flush()
+            .and_then(|()| self.writer.get_ref().sync_all())
+            .map_err(|error| gc_error(&self.temp_path, "write", error))?;
+        std::fs::rename(&self.temp_path, &path)
+            .map_err(|error| gc_error(&path, "replace", error))?;
+        self.committed = true;
+        Ok(self.bytes)
+    }
+}
+
+impl Drop for LogRewrite {
+    fn drop(&mut self) {
+        if !self.committed {
+            let _ = std::fs::remove_file(&self.temp_path);
+        }
+    }
+}
+
+fn add_to_daily_aggregate(
+    aggregates: &mut BTreeMap<AggregateKey, DailyUsageAggregate>,
+    entry: &Value,
+    timestamp_ms: u64,
+) {
+    let str_field = |name: &str| entry.get(name).and_then(Value::as_str).map(str::to_string);
+    let date = utc_date(timestamp_ms);
+    let service = str_field("service").unwrap_or_default();
+    let provider_id = str_field("provider_id");
+    let model = str_field("model");
+    let aggregate = aggregates
+        .entry((
+            date.clone(),
+            service.clone(),
+            provider_id.clone(),
+            model.clone(),
+        ))
+        .or_insert_with(|| DailyUsageAggregate {
+            date,
+            service,
+            provider_id,
+            model,
+            ..DailyUsageAggregate::default()
+        });
+    aggregate.requests += 1;
+    if entry
+        .get("status_code")
+        .and_then(Value::as_u64)
+        .is_some_and(|status| status >= 400)
+    {
+        aggregate.errors += 1;
+    }
+    let usage = entry.get("usage");
+    let tokens = |name: &str| {
+        usage
+            .and_then(|usage| usage.get(name))
+            .and_then(Value::as_i64)
+            .unwrap_or_default()
+    };
+    aggregate.input_tokens += tokens("input_tokens");
+    aggregate.output_tokens += tokens("output_tokens");
+    aggregate.total_tokens += tokens("total_tokens");
+}
+
+fn merge_daily_aggregates(
+    path: &Path,
+    aggregates: BTreeMap<AggregateKey, DailyUsageAggregate>,
+) -> CliResult<()> {
+    let mut merged = BTreeMap::<AggregateKey, DailyUsageAggregate>::new();
+    match std::fs::read_to_string(path) {
+        Ok(text) => {
+            for line in text.lines().filter(|line| !line.trim().is_empty()) {
+                let aggregate =
+                    serde_json::from_str::<DailyUsageAggregate>(line).map_err(|error| {
+                        CliError::Usage(format!(
+                            "refusing to rewrite {}: unreadable aggregate line: {error}",
+                            path.display()
+                        ))
+                    })?;
+                merged.insert(aggregate_key(&aggregate), aggregate);
+            }
+        }
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => {}
+        Err(error) => return Err(gc_error(path, "read", error)),
+    }
+    for (key, aggregate) in aggregates {
+        match merged.get_mut(&key) {
+            Some(existing) => {
+                existing.requests += aggregate.requests;
+                existing.errors += aggregate.errors;
+                existing.input_tokens += aggregate.input_tokens;
+                existing.output_tokens += aggregate.output_tokens;
+                existing.total_tokens += aggregate.total_tokens;
+            }
+            None => {
+                merged.insert(key, aggregate);
+            }
+        }
+    }
+    let mut text = String::new();
+    for aggregate in merged.values() {
+        let line = serde_json::to_string(aggregate).map_err(|error| {
+            CliError::Usage(format!("failed to serialize daily aggregate: {error}"))
+        })?;
+        text.push_str(&line);
+        text.push('\n');
+    }
+    codex_helper_core::write_text_private_file(path, &text)
+        .map_err(|error| gc_error(path, "write", error))
+}
+
+fn aggregate_key(aggregate: &DailyUsageAggregate) -> AggregateKey {
+    (
+        aggregate.date.clone(),
+        aggregate.service.clone(),
+        aggregate.provider_id.clone(),
+        aggregate.model.clone(),
+    )
+}
+
+fn aggregates_path(request_log: &Path) -> PathBuf {
+    request_log.with_file_name(DAILY_AGGREGATES_FILE)
+}
+
+fn utc_date(timestamp_ms: u64) -> String {
+    let time = UNIX_EPOCH + Duration::from_millis(timestamp_ms);
+    humantime::format_rfc3339_seconds(time).to_string()[..10].to_string()
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+pub(super) fn gc_error(path: &Path, action: &str, error: impl std::fmt::Display) -> CliError {
+    CliError::Usage(format!("failed to {action} {}: {error:#}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::TempTestDir;
+
+    const DAY_MS: u64 = 24 * 60 * 60 * 1000;
+    const NOW_MS: u64 = 20 * DAY_MS;
+
+    fn entry(request_id: u64, timestamp_ms: u64, total_tokens: i64) -> String {
+        serde_json::json!({
+            "request_id": request_id,
+            "timestamp_ms": timestamp_ms,
+            "service": "codex",
+            "provider_id": "relay",
+            "model": "gpt-5",
+            "status_code": 200,
+            "usage": {"input_tokens": total_tokens - 1, "output_tokens": 1, "total_tokens": total_tokens},
+        })
+        .to_string()
+    }
+
+    fn write_log(dir: &TempTestDir, lines: &[String]) -> PathBuf {
+        let path = dir.path().join("requests.jsonl");
+        std::fs::write(&path, format!("{}\n", lines.join("\n"))).expect("write request log");
+        path
+    }
+
+    #[test]
+    fn dry_run_reports_reclaimable_bytes_without_touching_the_file() {
+        let dir = TempTestDir::new("codex-helper-cli-test-usage-gc");
+        let path = write_log(
+            &dir,
+            &[
+                entry(1, DAY_MS, 10),
+                entry(2, 15 * DAY_MS, 10),
+                entry(3, 19 * DAY_MS, 10),
+            ],
+        );
+        let before = std::fs::read_to_string(&path).expect("read log");
+
+        let report = gc_request_log(
+            &path,
+            UsageGcOptions {
+                older_than: Some(Duration::from_millis(7 * DAY_MS)),
+                ..UsageGcOptions::default()
+            },
+            NOW_MS,
+        )
+        .expect("dry run");
+
+        assert_eq!(report.entries, 3);
+        assert_eq!(report.dropped, 1);
+        assert!(report.bytes_after < report.bytes_before);
+        assert_eq!(std::fs::read_to_string(&path).expect("read log"), before);
+    }
+
+    #[test]
+    fn apply_keeps_the_newest_entries_and_compacts_the_rest_by_day() {
+        let dir = TempTestDir::new("codex-helper-cli-test-usage-gc");
+        let path = write_log(
+            &dir,
+            &[
+                entry(1, DAY_MS, 10),
+                entry(2, DAY_MS + 1, 20),
+                "not json".to_string(),
+                entry(3, 2 * DAY_MS, 5),
+                entry(4, 19 * DAY_MS, 7),
+            ],
+        );
+
+        let options = UsageGcOptions {
+            keep: Some(1),
+            compact: true,
+            apply: true,
+            ..UsageGcOptions::default()
+        };
+        let report = gc_request_log(&path, options, NOW_MS).expect("apply gc");
+
+        assert_eq!(report.dropped, 3);
+        assert_eq!(report.compacted_days, 2);
+        let remaining = std::fs::read_to_string(&path).expect("read log");
+        assert_eq!(
+            remaining,
+            format!("not json\n{}\n", entry(4, 19 * DAY_MS, 7))
+        );
+
+        let aggregates = std::fs::read_to_string(aggregates_path(&path)).expect("read aggregates");
+        let days = aggregates
+            .lines()
+            .map(|line| serde_json::from_str::<DailyUsageAggregate>(line).expect("aggregate"))
+            .collect::<Vec<_>>();
+        assert_eq!(days.len(), 2);
+        assert_eq!(days[0].date, "1970-01-02");
+        assert_eq!(days[0].requests, 2);
+        assert_eq!(days[0].total_tokens, 30);
+        assert_eq!(days[1].date, "1970-01-03");
+
+        // A second pass adds to the stored aggregates instead of replacing them.
+        write_log(&dir, &[entry(5, DAY_MS + 2, 1), entry(6, 19 * DAY_MS, 1)]);
+        gc_request_log(&path, options, NOW_MS).expect("second gc");
+        let aggregates = std::fs::read_to_string(aggregates_path(&path)).expect("read aggregates");
+        let first_day: DailyUsageAggregate =
+            serde_json::from_str(aggregates.lines().next().expect("first day")).expect("aggregate");
+        assert_eq!(first_day.requests, 3);
+        assert_eq!(first_day.total_tokens, 31);
+    }
+
+    #[test]
+    fn rewrite_carries_over_lines_appended_during_the_scan() {
+        let dir = TempTestDir::new("codex-helper-cli-test-usage-gc");
+        let path = write_log(&dir, &[entry(1, DAY_MS, 10), entry(2, 19 * DAY_MS, 10)]);
+        let read_len = std::fs::metadata(&path).expect("stat log").len();
+
+        let mut rewrite = LogRewrite::create(&path).expect("create rewrite");
+        rewrite
+            .push_line(&entry(2, 19 * DAY_MS, 10))
+            .expect("push line");
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .expect("open log");
+        writeln!(file, "{}", entry(3, 19 * DAY_MS, 10)).expect("append");
+        drop(file);
+        rewrite.commit(read_len).expect("commit");
+
+        assert_eq!(
+            std::fs::read_to_string(&path).expect("read log"),
+            format!(
+                "{}\n{}\n",
+                entry(2, 19 * DAY_MS, 10),
+                entry(3, 19 * DAY_MS, 10)
+            )
+        );
+        let leftovers = std::fs::read_dir(dir.path())
+            .expect("read dir")
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_name().to_string_lossy().ends_with(".rewrite"))
+            .count();
+        assert_eq!(leftovers, 0);
+    }
+
+    #[test]
+    fn apply_leaves_a_line_still_being_appended_whole() {
+        let dir = TempTestDir::new("codex-helper-cli-test-usage-gc");
+        let path = dir.path().join("requests.jsonl");
+        let appending = entry(3, 19 * DAY_MS, 10);
+        let (head, rest) = appending.split_at(20);
+        std::fs::write(
+            &path,
+            format!(
+                "{}\n{}\n{head}",
+                entry(1, DAY_MS, 10),
+                entry(2, 19 * DAY_MS, 10)
+            ),
+        )
+        .expect("write request log");
+
+        let report = gc_request_log(
+            &path,
+            UsageGcOptions {
+                older_than: Some(Duration::from_millis(7 * DAY_MS)),
+                apply: true,
+                ..UsageGcOptions::default()
+            },
+            NOW_MS,
+        )
+        .expect("apply gc");
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .expect("open log");
+        writeln!(file, "{rest}").expect("finish the line");
+        drop(file);
+
+        assert_eq!(report.entries, 2);
+        assert_eq!(report.dropped, 1);
+        assert_eq!(
+            std::fs::read_to_string(&path).expect("read log"),
+            format!("{}\n{appending}\n", entry(2, 19 * DAY_MS, 10))
+        );
+    }
+
+    #[test]
+    fn stale_tail_is_dropped_only_when_nothing_was_appended_after_it() {
+        let dir = TempTestDir::new("codex-helper-cli-test-usage-gc");
+        let path = dir.path().join("requests.jsonl");
+        let kept = entry(1, DAY_MS, 10);
+        let appending = entry(2, DAY_MS, 10);
+        let (head, rest) = appending.split_at(20);
+        let text = format!("{kept}\n{head}");
+        let complete_len = kept.len() as u64 + 1;
+
+        std::fs::write(&path, &text).expect("write request log");
+        let mut rewrite = LogRewrite::create(&path).expect("create rewrite");
+        rewrite.push_line(&kept).expect("push line");
+        rewrite
+            .commit_dropping_stale_tail(complete_len, text.len() as u64)
+            .expect("commit");
+        assert_eq!(
+            std::fs::read_to_string(&path).expect("read log"),
+            format!("{kept}\n")
+        );
+
+        std::fs::write(&path, &text).expect("write request log");
+        let mut rewrite = LogRewrite::create(&path).expect("create rewrite");
+        rewrite.push_line(&kept).expect("push line");
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .expect("open log");
+        writeln!(file, "{rest}").expect("finish the line");
+        drop(file);
+        rewrite
+            .commit_dropping_stale_tail(complete_len, text.len() as u64)
+            .expect("commit");
+        assert_eq!(
+            std::fs::read_to_string(&path).expect("read log"),
+            format!("{kept}\n{appending}\n")
+        );
+    }
+}
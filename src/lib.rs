@@ -12,9 +12,10 @@ pub use cli_types::{
     UsageSummaryBy,
 };
 pub use codex_helper_core::{
-    codex_integration, codex_onboarding, codex_switch, config, control_plane_client,
-    dashboard_core, doctor, endpoint_health, filter, logging, model_routing, notify, pricing,
-    proxy, relay_target, request_chain, request_ledger, routing_explain, routing_ir, runtime_host,
-    runtime_manager, runtime_store, sessions, state, usage, usage_providers,
+    active_history, codex_integration, codex_onboarding, codex_switch, config, config_lint,
+    control_plane_client, dashboard_core, doctor, endpoint_health, env_file, filter, logging,
+    model_routing, notify, pricing, proxy, relay_target, request_chain, request_ledger,
+    routing_explain, routing_ir, runtime_host, runtime_manager, runtime_store, sessions, state,
+    upstream_models, usage, usage_providers,
 };
 pub use codex_helper_tui::tui;